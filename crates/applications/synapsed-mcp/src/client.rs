@@ -116,6 +116,7 @@ impl McpClient {
             id: Some(serde_json::Value::Number(id.into())),
             method: method.to_string(),
             params: Some(params),
+            caller: None,
         };
         
         debug!("Sending request {}: {}", id, method);
@@ -300,6 +301,21 @@ impl McpClient {
             .to_string())
     }
     
+    /// Call a tool, fetching and reassembling every chunk of a large result
+    pub async fn call_tool(&self, name: &str, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let first = self.call_method("tools/call", serde_json::json!({
+            "name": name,
+            "arguments": arguments,
+        })).await?;
+        
+        crate::paging::collect_chunks(first, |cursor| {
+            self.call_method("tools/call", serde_json::json!({
+                "name": name,
+                "cursor": cursor,
+            }))
+        }).await
+    }
+    
     /// Disconnect from server
     pub async fn disconnect(&self) -> Result<()> {
         info!("Disconnecting from MCP server");
@@ -0,0 +1,116 @@
+//! Request interceptors for the MCP server
+//!
+//! Interceptors run in order before a request reaches its handler. Each one can
+//! inspect or rewrite the request, or short-circuit it with a response of its
+//! own. This is where cross-cutting policy such as authentication, rate limiting
+//! or request validation lives, so individual tools don't need to enforce it.
+
+use crate::protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+/// JSON-RPC error code used when an interceptor rejects a request
+pub const REQUEST_REJECTED: i32 = -32001;
+
+/// Outcome of running an interceptor
+#[derive(Debug, Clone)]
+pub enum Flow {
+    /// Pass the (possibly modified) request to the next interceptor
+    Continue(JsonRpcRequest),
+    /// Stop processing and reply with this response
+    Respond(JsonRpcResponse),
+}
+
+impl Flow {
+    /// Short-circuit a request with a JSON-RPC error
+    pub fn reject(id: Option<Value>, code: i32, message: impl Into<String>) -> Self {
+        Flow::Respond(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(JsonRpcError {
+                code,
+                message: message.into(),
+                data: None,
+            }),
+            id,
+        })
+    }
+}
+
+/// Cross-cutting request processing run before a request is handled
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    /// Name used in logs
+    fn name(&self) -> &str;
+
+    /// Inspect a request before it is handled
+    async fn before(&self, request: JsonRpcRequest) -> Flow;
+}
+
+/// Ordered list of interceptors applied to every request
+#[derive(Clone, Default)]
+pub struct InterceptorChain {
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+impl InterceptorChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an interceptor to the chain
+    pub fn with(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.push(interceptor);
+        self
+    }
+
+    /// Append an interceptor to the chain
+    pub fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    /// Append every interceptor of another chain
+    pub fn extend(&mut self, other: &InterceptorChain) {
+        self.interceptors.extend(other.interceptors.iter().cloned());
+    }
+
+    /// Number of interceptors in the chain
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// Whether the chain has no interceptors
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// Run the request through every interceptor in order
+    ///
+    /// Stops at the first interceptor that responds.
+    pub async fn run(&self, mut request: JsonRpcRequest) -> Flow {
+        for interceptor in &self.interceptors {
+            match interceptor.before(request).await {
+                Flow::Continue(next) => request = next,
+                Flow::Respond(response) => {
+                    tracing::debug!(
+                        "Interceptor {} short-circuited request",
+                        interceptor.name()
+                    );
+                    return Flow::Respond(response);
+                }
+            }
+        }
+        Flow::Continue(request)
+    }
+}
+
+impl fmt::Debug for InterceptorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.interceptors.iter().map(|i| i.name()))
+            .finish()
+    }
+}
@@ -19,13 +19,21 @@ pub mod client_transport;
 pub mod anonymous_transport;
 pub mod distributed_state;
 pub mod observability;
+pub mod interceptor;
+pub mod rate_limit;
+pub mod paging;
+pub mod schema;
 mod intent_store;  // Internal module - not exported
 mod protocol;      // Internal module - protocol handler
 mod agent_spawner; // Internal module - agent spawning
 pub mod rmcp_adapter; // Adapter for rmcp server integration
 
 pub use server::{McpServer, ServerConfig};
-pub use tools::{IntentTools, VerificationTools};
+pub use interceptor::{Flow, Interceptor, InterceptorChain};
+pub use paging::{PagerLimits, ResultChunk, ResultPager};
+pub use rate_limit::{RateLimit, RateLimiter, RateLimits};
+pub use protocol::{Caller, JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+pub use tools::{IntentTools, ToolSchema, VerificationTools};
 pub use swarm_tools::SwarmTools;
 pub use resources::ContextResources;
 pub use error::{McpError, Result};
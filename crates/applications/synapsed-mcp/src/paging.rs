@@ -0,0 +1,337 @@
+//! Chunked delivery of large tool results
+//!
+//! Results whose serialized form exceeds the configured chunk size are held on
+//! the server and returned one chunk at a time. Each chunk carries a
+//! `next_cursor`; the client passes it back with `tools/call` to fetch the next
+//! chunk. Cursors encode the byte offset, so a client that loses a response can
+//! re-request the same chunk until the result expires.
+//!
+//! Each result belongs to the session, identified by its [`Caller`], whose
+//! request produced it, and its cursors only work for that session. How many
+//! results and bytes are held is capped per session and overall by
+//! [`PagerLimits`]; making room drops the oldest results.
+
+use crate::error::{McpError, Result};
+use crate::protocol::Caller;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Default maximum size of a single result chunk in bytes
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// How long a chunked result stays available for fetching
+pub const DEFAULT_RESULT_TTL: Duration = Duration::from_secs(300);
+
+/// Caps on the results held for chunked fetching
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PagerLimits {
+    /// Maximum number of results held for one session
+    pub max_results_per_session: usize,
+    /// Maximum bytes held for one session
+    pub max_bytes_per_session: usize,
+    /// Maximum number of results held across all sessions
+    pub max_results: usize,
+    /// Maximum bytes held across all sessions
+    pub max_bytes: usize,
+}
+
+impl Default for PagerLimits {
+    fn default() -> Self {
+        Self {
+            max_results_per_session: 16,
+            max_bytes_per_session: 16 * 1024 * 1024,
+            max_results: 1024,
+            max_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+/// Key wrapping a chunk in a tool result
+pub const RESULT_CHUNK_KEY: &str = "result_chunk";
+
+/// One chunk of a serialized tool result
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ResultChunk {
+    /// Slice of the JSON-serialized result
+    pub chunk: String,
+    /// Byte offset of this chunk in the serialized result
+    pub offset: usize,
+    /// Total size of the serialized result in bytes
+    pub total_size: usize,
+    /// Cursor for the next chunk, `None` on the last chunk
+    pub next_cursor: Option<String>,
+}
+
+impl ResultChunk {
+    /// Wrap the chunk as a tool result
+    pub fn into_result(self) -> serde_json::Value {
+        serde_json::json!({ RESULT_CHUNK_KEY: self })
+    }
+
+    /// Extract a chunk from a tool result, `None` for unchunked results
+    pub fn from_result(result: &serde_json::Value) -> Option<Self> {
+        result
+            .get(RESULT_CHUNK_KEY)
+            .and_then(|chunk| serde_json::from_value(chunk.clone()).ok())
+    }
+}
+
+/// A result held for chunked fetching
+#[derive(Debug)]
+struct PagedResult {
+    session: Caller,
+    data: String,
+    created: Instant,
+}
+
+/// Server-side store of results being delivered in chunks
+#[derive(Debug)]
+pub struct ResultPager {
+    chunk_size: usize,
+    ttl: Duration,
+    limits: PagerLimits,
+    results: RwLock<HashMap<Uuid, PagedResult>>,
+}
+
+impl ResultPager {
+    /// Create a pager splitting results into chunks of at most `chunk_size` bytes
+    pub fn new(chunk_size: usize, ttl: Duration) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            ttl,
+            limits: PagerLimits::default(),
+            results: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set the caps on the results held for chunked fetching
+    pub fn with_limits(mut self, limits: PagerLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Return `result` unchanged if it fits in one chunk, otherwise its first chunk
+    ///
+    /// Results too large to hold within the limits are refused.
+    pub async fn page(
+        &self,
+        session: &Caller,
+        result: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let data = serde_json::to_string(&result)
+            .map_err(|e| McpError::SerializationError(e.to_string()))?;
+        if data.len() <= self.chunk_size {
+            return Ok(result);
+        }
+        if data.len() > self.limits.max_bytes_per_session.min(self.limits.max_bytes) {
+            return Err(McpError::Internal(format!(
+                "Result of {} bytes is too large to deliver in chunks",
+                data.len()
+            )));
+        }
+
+        let id = Uuid::new_v4();
+        let chunk = self.chunk(id, &data, 0);
+
+        let mut results = self.results.write().await;
+        let ttl = self.ttl;
+        results.retain(|_, paged| paged.created.elapsed() < ttl);
+
+        // The session gives up its own results first, then the oldest overall go
+        let limits = self.limits;
+        make_room(
+            &mut results,
+            Some(session),
+            data.len(),
+            limits.max_results_per_session,
+            limits.max_bytes_per_session,
+        );
+        make_room(&mut results, None, data.len(), limits.max_results, limits.max_bytes);
+        results.insert(id, PagedResult {
+            session: session.clone(),
+            data,
+            created: Instant::now(),
+        });
+
+        Ok(chunk.into_result())
+    }
+
+    /// Fetch the chunk a cursor points at
+    ///
+    /// Cursors of results paged for another session are treated as unknown.
+    pub async fn next(&self, session: &Caller, cursor: &str) -> Result<serde_json::Value> {
+        let invalid = || McpError::InvalidParams(format!("Invalid cursor: {}", cursor));
+        let (id, offset) = cursor.split_once(':').ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+        let offset: usize = offset.parse().map_err(|_| invalid())?;
+
+        let results = self.results.read().await;
+        let paged = results
+            .get(&id)
+            .filter(|paged| paged.session == *session && paged.created.elapsed() < self.ttl)
+            .ok_or_else(|| McpError::NotFound(format!("Result for cursor {} expired", cursor)))?;
+        if offset >= paged.data.len() || !paged.data.is_char_boundary(offset) {
+            return Err(invalid());
+        }
+
+        Ok(self.chunk(id, &paged.data, offset).into_result())
+    }
+
+    fn chunk(&self, id: Uuid, data: &str, offset: usize) -> ResultChunk {
+        // Never split a UTF-8 character across chunks
+        let mut end = (offset + self.chunk_size).min(data.len());
+        while !data.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == offset {
+            end = offset + data[offset..].chars().next().map_or(0, char::len_utf8);
+        }
+
+        ResultChunk {
+            chunk: data[offset..end].to_string(),
+            offset,
+            total_size: data.len(),
+            next_cursor: (end < data.len()).then(|| format!("{}:{}", id, end)),
+        }
+    }
+}
+
+/// Drop the oldest results, only those of `session` if given, until one more
+/// result of `size` bytes fits within `max_results` and `max_bytes`
+fn make_room(
+    results: &mut HashMap<Uuid, PagedResult>,
+    session: Option<&Caller>,
+    size: usize,
+    max_results: usize,
+    max_bytes: usize,
+) {
+    let counted = |paged: &PagedResult| session.is_none_or(|session| paged.session == *session);
+    loop {
+        let (count, bytes) = results
+            .values()
+            .filter(|paged| counted(paged))
+            .fold((0, 0), |(count, bytes), paged| (count + 1, bytes + paged.data.len()));
+        if count < max_results && bytes + size <= max_bytes {
+            return;
+        }
+
+        let oldest = results
+            .iter()
+            .filter(|(_, paged)| counted(paged))
+            .min_by_key(|(_, paged)| paged.created)
+            .map(|(id, _)| *id);
+        match oldest {
+            Some(id) => results.remove(&id),
+            None => return,
+        };
+    }
+}
+
+impl Default for ResultPager {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHUNK_SIZE, DEFAULT_RESULT_TTL)
+    }
+}
+
+/// Reassemble a possibly chunked tool result
+///
+/// `fetch` is called with each `next_cursor` and must return the tool result
+/// for that cursor. Unchunked results are returned as they are.
+pub async fn collect_chunks<F, Fut>(first: serde_json::Value, mut fetch: F) -> Result<serde_json::Value>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<serde_json::Value>>,
+{
+    let Some(mut chunk) = ResultChunk::from_result(&first) else {
+        return Ok(first);
+    };
+
+    let mut data = String::with_capacity(chunk.total_size);
+    loop {
+        if chunk.offset != data.len() {
+            return Err(McpError::Protocol(format!(
+                "Expected chunk at offset {}, got {}",
+                data.len(),
+                chunk.offset
+            )));
+        }
+        data.push_str(&chunk.chunk);
+
+        let Some(cursor) = chunk.next_cursor else {
+            break;
+        };
+        let next = fetch(cursor).await?;
+        chunk = ResultChunk::from_result(&next)
+            .ok_or_else(|| McpError::Protocol("Expected a result chunk".to_string()))?;
+    }
+
+    serde_json::from_str(&data).map_err(|e| McpError::SerializationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn large_result(marker: usize) -> serde_json::Value {
+        serde_json::json!({ "marker": marker, "padding": "x".repeat(100) })
+    }
+
+    fn cursor(result: &serde_json::Value) -> String {
+        ResultChunk::from_result(result).unwrap().next_cursor.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cursor_only_works_for_its_session() {
+        let pager = ResultPager::new(32, DEFAULT_RESULT_TTL);
+        let owner = Caller::Authenticated("did:example:owner".to_string());
+        let other = Caller::Authenticated("did:example:other".to_string());
+
+        let first = pager.page(&owner, large_result(0)).await.unwrap();
+        let cursor = cursor(&first);
+
+        assert!(matches!(pager.next(&other, &cursor).await, Err(McpError::NotFound(_))));
+        assert!(pager.next(&owner, &cursor).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_results_held_are_capped_per_session_and_overall() {
+        let limits = PagerLimits {
+            max_results_per_session: 2,
+            max_results: 3,
+            ..PagerLimits::default()
+        };
+        let pager = ResultPager::new(32, DEFAULT_RESULT_TTL).with_limits(limits);
+        let a = Caller::Authenticated("did:example:a".to_string());
+        let b = Caller::Authenticated("did:example:b".to_string());
+
+        let mut cursors = Vec::new();
+        for marker in 0..3 {
+            cursors.push(cursor(&pager.page(&a, large_result(marker)).await.unwrap()));
+        }
+
+        // The session's oldest result made room for its third
+        assert!(pager.next(&a, &cursors[0]).await.is_err());
+        assert!(pager.next(&a, &cursors[1]).await.is_ok());
+        assert_eq!(pager.results.read().await.len(), 2);
+
+        // Two more from another session push the overall oldest out
+        cursors.push(cursor(&pager.page(&b, large_result(3)).await.unwrap()));
+        cursors.push(cursor(&pager.page(&b, large_result(4)).await.unwrap()));
+        assert_eq!(pager.results.read().await.len(), 3);
+        assert!(pager.next(&a, &cursors[1]).await.is_err());
+        assert!(pager.next(&a, &cursors[2]).await.is_ok());
+
+        // Results larger than a session may hold are refused outright
+        let pager = ResultPager::new(32, DEFAULT_RESULT_TTL).with_limits(PagerLimits {
+            max_bytes_per_session: 64,
+            ..PagerLimits::default()
+        });
+        assert!(pager.page(&a, large_result(5)).await.is_err());
+        assert!(pager.results.read().await.is_empty());
+    }
+}
@@ -8,6 +8,8 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::RwLock;
@@ -20,6 +22,30 @@ pub struct JsonRpcRequest {
     pub method: String,
     pub params: Option<Value>,
     pub id: Option<Value>,
+    /// Who sent the request, set by the transport and never read from the wire
+    #[serde(skip)]
+    pub caller: Option<Caller>,
+}
+
+/// Identity of the party that sent a request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Caller {
+    /// Identity established by authentication, such as a DID
+    Authenticated(String),
+    /// Network address of the peer
+    Peer(SocketAddr),
+    /// Local process, such as the client on the other end of stdio
+    Local,
+}
+
+impl fmt::Display for Caller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Caller::Authenticated(identity) => write!(f, "{}", identity),
+            Caller::Peer(addr) => write!(f, "{}", addr),
+            Caller::Local => write!(f, "local"),
+        }
+    }
 }
 
 /// JSON-RPC response
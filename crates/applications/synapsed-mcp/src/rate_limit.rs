@@ -0,0 +1,394 @@
+//! Per-client, per-tool rate limiting for the MCP server
+//!
+//! Each caller/tool pair gets its own token bucket. Callers are identified by
+//! the [`Caller`] the transport attached to the request, so a client cannot
+//! reset its limits by changing anything in the request itself; network peers
+//! are identified by IP address alone, so reconnecting from another port
+//! doesn't reset them either. A request
+//! consumes one token; when the bucket is empty the request is rejected with a
+//! [`RATE_LIMITED`] JSON-RPC error whose data carries the time until the next
+//! token is available. Only registered tools get buckets of their own; calls
+//! naming any other tool share one, so made-up names cannot flood the bucket
+//! table and evict the buckets of real tools. Once the table is full, new
+//! callers are refused until an existing bucket has refilled, rather than
+//! evicting a bucket that still limits someone.
+
+use crate::interceptor::{Flow, Interceptor};
+use crate::protocol::{Caller, JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// JSON-RPC error code returned when a rate limit is exceeded
+pub const RATE_LIMITED: i32 = -32002;
+
+/// Default maximum number of token buckets kept in memory
+pub const DEFAULT_MAX_BUCKETS: usize = 10_000;
+
+/// Token bucket parameters
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RateLimit {
+    /// Maximum number of requests that can be made in a burst
+    pub burst: u32,
+    /// Tokens added back per second
+    pub per_second: f64,
+}
+
+impl RateLimit {
+    /// Create a limit allowing `burst` requests, refilled at `per_second`
+    pub fn new(burst: u32, per_second: f64) -> Self {
+        Self { burst, per_second }
+    }
+}
+
+/// Rate limit configuration
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RateLimits {
+    /// Limit applied to tools without a specific limit, unlimited if `None`
+    pub default: Option<RateLimit>,
+    /// Limits for individual tools or methods
+    pub tools: HashMap<String, RateLimit>,
+    /// Maximum number of caller/tool buckets kept in memory
+    pub max_buckets: usize,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            default: None,
+            tools: HashMap::new(),
+            max_buckets: DEFAULT_MAX_BUCKETS,
+        }
+    }
+}
+
+impl RateLimits {
+    /// Set the limit for a tool or method
+    pub fn with_tool(mut self, tool: impl Into<String>, limit: RateLimit) -> Self {
+        self.tools.insert(tool.into(), limit);
+        self
+    }
+
+    /// Set the limit for tools without a specific limit
+    pub fn with_default(mut self, limit: RateLimit) -> Self {
+        self.default = Some(limit);
+        self
+    }
+
+    /// Set the maximum number of caller/tool buckets kept in memory
+    pub fn with_max_buckets(mut self, max_buckets: usize) -> Self {
+        self.max_buckets = max_buckets;
+        self
+    }
+
+    /// Get the limit applying to a tool
+    pub fn limit_for(&self, tool: &str) -> Option<RateLimit> {
+        self.tools.get(tool).copied().or(self.default)
+    }
+
+    /// Whether no limits are configured
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.tools.is_empty()
+    }
+}
+
+/// Token bucket for a single client/tool pair
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long until one is available
+    fn try_take(&mut self) -> std::result::Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst as f64);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        if self.limit.per_second <= 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / self.limit.per_second))
+    }
+
+    /// Whether the bucket has refilled completely, making it equivalent to a new one
+    fn is_full(&self, now: Instant) -> bool {
+        self.time_until_full(now).is_zero()
+    }
+
+    /// Time until the bucket has refilled completely
+    fn time_until_full(&self, now: Instant) -> Duration {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        let missing = self.limit.burst as f64 - self.tokens - elapsed * self.limit.per_second;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else if self.limit.per_second <= 0.0 {
+            Duration::MAX
+        } else {
+            Duration::from_secs_f64(missing / self.limit.per_second)
+        }
+    }
+}
+
+/// Identity a caller's buckets are kept under
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ClientKey {
+    /// Network peers, by IP address only
+    Address(IpAddr),
+    /// Every other caller
+    Caller(Caller),
+}
+
+impl From<&Caller> for ClientKey {
+    fn from(caller: &Caller) -> Self {
+        match caller {
+            Caller::Peer(addr) => ClientKey::Address(addr.ip()),
+            caller => ClientKey::Caller(caller.clone()),
+        }
+    }
+}
+
+/// Interceptor enforcing [`RateLimits`]
+pub struct RateLimiter {
+    limits: RateLimits,
+    tools: HashSet<String>,
+    buckets: Mutex<HashMap<(ClientKey, String), TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter for the given limits
+    ///
+    /// No tools are registered, so every `tools/call` request shares the
+    /// bucket of the `tools/call` method until [`RateLimiter::with_tools`]
+    /// registers them.
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            tools: HashSet::new(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register the tools that get a bucket of their own
+    pub fn with_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tools.extend(tools.into_iter().map(Into::into));
+        self
+    }
+
+    /// Consume a token for a caller/tool pair
+    ///
+    /// Returns the time until the next token is available when the limit is
+    /// exceeded. A caller without a bucket is refused while the table is full,
+    /// with the time until an existing bucket has refilled and can be dropped.
+    pub async fn check(&self, caller: &Caller, tool: &str) -> std::result::Result<(), Duration> {
+        let Some(limit) = self.limits.limit_for(tool) else {
+            return Ok(());
+        };
+
+        let mut buckets = self.buckets.lock().await;
+        let key = (ClientKey::from(caller), tool.to_string());
+        if !buckets.contains_key(&key) && buckets.len() >= self.limits.max_buckets {
+            make_room(&mut buckets, self.limits.max_buckets)?;
+        }
+        buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(limit))
+            .try_take()
+    }
+
+    /// Number of caller/tool buckets currently held
+    pub async fn bucket_count(&self) -> usize {
+        self.buckets.lock().await.len()
+    }
+}
+
+/// Make room for a new bucket
+///
+/// Drops every bucket that has refilled completely, since a fresh bucket
+/// behaves the same. Buckets that still limit a caller are never dropped; if
+/// none has refilled, returns the time until the first one will have.
+fn make_room(
+    buckets: &mut HashMap<(ClientKey, String), TokenBucket>,
+    max_buckets: usize,
+) -> std::result::Result<(), Duration> {
+    let now = Instant::now();
+    buckets.retain(|_, bucket| !bucket.is_full(now));
+    if buckets.len() < max_buckets {
+        return Ok(());
+    }
+
+    Err(buckets
+        .values()
+        .map(|bucket| bucket.time_until_full(now))
+        .min()
+        .unwrap_or(Duration::ZERO))
+}
+
+/// Name of the tool a request targets, or its method for non-tool requests
+///
+/// Tools outside `tools` are counted against the `tools/call` method.
+fn tool_name<'a>(request: &'a JsonRpcRequest, tools: &HashSet<String>) -> &'a str {
+    if request.method == "tools/call" {
+        if let Some(name) = request
+            .params
+            .as_ref()
+            .and_then(|params| params.get("name"))
+            .and_then(|name| name.as_str())
+            .filter(|name| tools.contains(*name))
+        {
+            return name;
+        }
+    }
+    &request.method
+}
+
+#[async_trait]
+impl Interceptor for RateLimiter {
+    fn name(&self) -> &str {
+        "rate-limiter"
+    }
+
+    async fn before(&self, request: JsonRpcRequest) -> Flow {
+        let caller = request.caller.clone().unwrap_or(Caller::Local);
+        let tool = tool_name(&request, &self.tools).to_string();
+
+        match self.check(&caller, &tool).await {
+            Ok(()) => Flow::Continue(request),
+            Err(retry_after) => {
+                let retry_after_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+                tracing::warn!("Rate limit exceeded for {} on {}", caller, tool);
+                Flow::Respond(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: RATE_LIMITED,
+                        message: "Rate limit exceeded".to_string(),
+                        data: Some(serde_json::json!({
+                            "caller": caller.to_string(),
+                            "tool": tool,
+                            "retry_after_ms": retry_after_ms,
+                        })),
+                    }),
+                    id: request.id,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_new_callers_are_refused_at_capacity() {
+        let limits = RateLimits::default()
+            .with_default(RateLimit::new(1, 10.0))
+            .with_max_buckets(2);
+        let limiter = RateLimiter::new(limits);
+        let caller = |n: u8| Caller::Authenticated(format!("did:example:{}", n));
+
+        limiter.check(&caller(1), "tool").await.unwrap();
+        limiter.check(&caller(2), "tool").await.unwrap();
+
+        // Spraying new callers neither gets through nor resets existing limits
+        for n in 3..10 {
+            let retry_after = limiter.check(&caller(n), "tool").await.unwrap_err();
+            assert!(retry_after <= Duration::from_millis(100));
+        }
+        assert_eq!(limiter.bucket_count().await, 2);
+        assert!(limiter.check(&caller(1), "tool").await.is_err());
+        assert!(limiter.check(&caller(2), "tool").await.is_err());
+
+        // Once a bucket has refilled its slot goes to a new caller
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        limiter.check(&caller(3), "tool").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_peers_are_limited_by_ip_address() {
+        let limits = RateLimits::default().with_default(RateLimit::new(1, 0.0));
+        let limiter = RateLimiter::new(limits);
+        let peer = |port: u16| Caller::Peer(std::net::SocketAddr::from(([192, 0, 2, 1], port)));
+
+        limiter.check(&peer(40000), "tool").await.unwrap();
+
+        // Reconnecting from another source port does not reset the limit
+        assert!(limiter.check(&peer(40001), "tool").await.is_err());
+        assert_eq!(limiter.bucket_count().await, 1);
+
+        let other = Caller::Peer(std::net::SocketAddr::from(([192, 0, 2, 2], 40000)));
+        limiter.check(&other, "tool").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refilled_buckets_are_evicted_first() {
+        let limits = RateLimits::default()
+            .with_default(RateLimit::new(1, 1000.0))
+            .with_max_buckets(2);
+        let limiter = RateLimiter::new(limits);
+        let caller = |n: u8| Caller::Authenticated(format!("did:example:{}", n));
+
+        limiter.check(&caller(1), "tool").await.unwrap();
+        limiter.check(&caller(2), "tool").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        limiter.check(&caller(3), "tool").await.unwrap();
+        assert_eq!(limiter.bucket_count().await, 1);
+    }
+
+    fn tool_call(caller: &Caller, name: &str) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "tools/call".to_string(),
+            params: Some(serde_json::json!({ "name": name, "arguments": {} })),
+            id: Some(serde_json::json!(1)),
+            caller: Some(caller.clone()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tool_names_cannot_evict_buckets() {
+        let limits = RateLimits::default()
+            .with_default(RateLimit::new(1, 0.0))
+            .with_max_buckets(2);
+        let limiter = RateLimiter::new(limits).with_tools(["intent_list"]);
+        let caller = Caller::Authenticated("did:example:1".to_string());
+
+        assert!(matches!(limiter.before(tool_call(&caller, "intent_list")).await, Flow::Continue(_)));
+
+        // Made-up names all draw from one shared bucket
+        assert!(matches!(limiter.before(tool_call(&caller, "made_up_0")).await, Flow::Continue(_)));
+        for i in 1..10 {
+            let flow = limiter.before(tool_call(&caller, &format!("made_up_{}", i))).await;
+            assert!(matches!(flow, Flow::Respond(_)));
+        }
+        assert_eq!(limiter.bucket_count().await, 2);
+
+        // The exhausted bucket of the real tool was not evicted
+        assert!(matches!(limiter.before(tool_call(&caller, "intent_list")).await, Flow::Respond(_)));
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     intent_store::IntentStore,
     agent_spawner::AgentSpawner,
     observability::{McpEvent, EVENT_CIRCUIT},
+    tools::{self, ToolSchema},
 };
 use rmcp::{
     Handler, InitializeOptions, InitializedNotification, ListResourcesResponse,
@@ -16,6 +17,42 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, debug};
 
+/// Schemas of the tools served by the adapter
+///
+/// The intent tools share their declarations with [`crate::McpServer`], so
+/// both list and validate them the same way.
+fn adapter_tool_schemas() -> Vec<ToolSchema> {
+    let mut schemas = tools::intent_tool_schemas();
+    schemas.extend(
+        tools::verification_tool_schemas()
+            .into_iter()
+            .filter(|schema| schema.name == "intent_status"),
+    );
+    schemas.push(ToolSchema::new(
+        "context_inject",
+        "Inject context for sub-agents",
+        json!({
+            "type": "object",
+            "properties": {
+                "agent_id": {
+                    "type": "string",
+                    "description": "Target agent ID"
+                },
+                "context": {
+                    "type": "object",
+                    "description": "Context to inject"
+                },
+                "boundaries": {
+                    "type": "object",
+                    "description": "Context boundaries"
+                }
+            },
+            "required": ["agent_id", "context"]
+        }),
+    ));
+    schemas
+}
+
 /// Adapter that bridges rmcp's Handler trait with Synapsed's verification system
 pub struct SynapsedMcpAdapter {
     /// Intent store for managing intents
@@ -79,94 +116,14 @@ impl Handler for SynapsedMcpAdapter {
     async fn list_tools(&self) -> ListToolsResponse {
         debug!("Listing Synapsed MCP tools");
         
-        let tools = vec![
-            Tool {
-                name: "intent_declare".to_string(),
-                description: Some("Declare an intent before performing actions".to_string()),
-                input_schema: json!({
-                    "type": "object",
-                    "properties": {
-                        "goal": {
-                            "type": "string",
-                            "description": "The goal of the intent"
-                        },
-                        "description": {
-                            "type": "string",
-                            "description": "Optional description"
-                        },
-                        "steps": {
-                            "type": "array",
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "name": { "type": "string" },
-                                    "action": { "type": "string" }
-                                }
-                            }
-                        },
-                        "success_criteria": {
-                            "type": "array",
-                            "items": { "type": "string" }
-                        }
-                    },
-                    "required": ["goal", "steps", "success_criteria"]
-                }),
-            },
-            Tool {
-                name: "intent_verify".to_string(),
-                description: Some("Verify execution against declared intent".to_string()),
-                input_schema: json!({
-                    "type": "object",
-                    "properties": {
-                        "intent_id": {
-                            "type": "string",
-                            "description": "UUID of the intent to verify"
-                        },
-                        "evidence": {
-                            "type": "object",
-                            "description": "Evidence of completion"
-                        }
-                    },
-                    "required": ["intent_id", "evidence"]
-                }),
-            },
-            Tool {
-                name: "intent_status".to_string(),
-                description: Some("Get status of an intent".to_string()),
-                input_schema: json!({
-                    "type": "object",
-                    "properties": {
-                        "intent_id": {
-                            "type": "string",
-                            "description": "UUID of the intent"
-                        }
-                    },
-                    "required": ["intent_id"]
-                }),
-            },
-            Tool {
-                name: "context_inject".to_string(),
-                description: Some("Inject context for sub-agents".to_string()),
-                input_schema: json!({
-                    "type": "object",
-                    "properties": {
-                        "agent_id": {
-                            "type": "string",
-                            "description": "Target agent ID"
-                        },
-                        "context": {
-                            "type": "object",
-                            "description": "Context to inject"
-                        },
-                        "boundaries": {
-                            "type": "object",
-                            "description": "Context boundaries"
-                        }
-                    },
-                    "required": ["agent_id", "context"]
-                }),
-            },
-        ];
+        let tools = adapter_tool_schemas()
+            .into_iter()
+            .map(|schema| Tool {
+                name: schema.name,
+                description: Some(schema.description),
+                input_schema: schema.input_schema,
+            })
+            .collect();
         
         ListToolsResponse { tools }
     }
@@ -175,12 +132,19 @@ impl Handler for SynapsedMcpAdapter {
     async fn call_tool(&self, request: CallToolRequest) -> CallToolResponse {
         debug!("Calling tool: {}", request.name);
         
-        let result = match request.name.as_str() {
-            "intent_declare" => self.handle_intent_declare(request.arguments).await,
-            "intent_verify" => self.handle_intent_verify(request.arguments).await,
-            "intent_status" => self.handle_intent_status(request.arguments).await,
-            "context_inject" => self.handle_context_inject(request.arguments).await,
-            _ => Err(McpError::InvalidMethod(format!("Unknown tool: {}", request.name))),
+        let schema = adapter_tool_schemas()
+            .into_iter()
+            .find(|schema| schema.name == request.name);
+        let result = match schema.map(|schema| schema.validate(&request.arguments)) {
+            None => Err(McpError::InvalidMethod(format!("Unknown tool: {}", request.name))),
+            Some(Err(violation)) => Err(McpError::InvalidParams(violation.to_string())),
+            Some(Ok(())) => match request.name.as_str() {
+                "intent_declare" => self.handle_intent_declare(request.arguments).await,
+                "intent_verify" => self.handle_intent_verify(request.arguments).await,
+                "intent_status" => self.handle_intent_status(request.arguments).await,
+                "context_inject" => self.handle_context_inject(request.arguments).await,
+                _ => Err(McpError::InvalidMethod(format!("Unknown tool: {}", request.name))),
+            },
         };
         
         match result {
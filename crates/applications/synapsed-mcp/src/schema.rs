@@ -0,0 +1,184 @@
+//! Validation of tool arguments against their declared JSON Schema
+//!
+//! Supports the subset of JSON Schema used by the tool declarations: `type`,
+//! `enum`, `format: "uuid"`, `required`, `properties`,
+//! `additionalProperties: false`, `items`, `minItems`, `minLength`, `minimum`
+//! and `maximum`.
+
+use serde_json::Value;
+use std::fmt;
+
+/// A value that does not satisfy its schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Path to the offending value, e.g. `$.steps[0].name`
+    pub path: String,
+    /// What is wrong with the value
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for SchemaViolation {}
+
+/// Validate `instance` against `schema`, returning the first violation found
+pub fn validate(schema: &Value, instance: &Value) -> std::result::Result<(), SchemaViolation> {
+    validate_at(schema, instance, "$")
+}
+
+fn violation(path: &str, message: impl Into<String>) -> SchemaViolation {
+    SchemaViolation {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn validate_at(schema: &Value, instance: &Value, path: &str) -> std::result::Result<(), SchemaViolation> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, instance)) {
+            return Err(violation(
+                path,
+                format!("expected {}, found {}", allowed.join(" or "), type_name(instance)),
+            ));
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(instance) {
+            return Err(violation(path, format!("must be one of {}", Value::Array(options.clone()))));
+        }
+    }
+
+    match instance {
+        Value::String(s) => {
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if (s.chars().count() as u64) < min {
+                    return Err(violation(path, format!("must be at least {} characters", min)));
+                }
+            }
+            if schema.get("format").and_then(Value::as_str) == Some("uuid") && uuid::Uuid::parse_str(s).is_err() {
+                return Err(violation(path, "must be a UUID"));
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if n < min {
+                    return Err(violation(path, format!("must be at least {}", min)));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if n > max {
+                    return Err(violation(path, format!("must be at most {}", max)));
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    return Err(violation(path, format!("must have at least {} items", min)));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{}[{}]", path, i))?;
+                }
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for field in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(field) {
+                        return Err(violation(&format!("{}.{}", path, field), "missing required field"));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            for (name, value) in fields {
+                let field_path = format!("{}.{}", path, name);
+                match properties.and_then(|p| p.get(name)) {
+                    Some(field_schema) => validate_at(field_schema, value, &field_path)?,
+                    None if closed => return Err(violation(&field_path, "unknown field")),
+                    None => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nested_violation_paths() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "steps": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"]
+                    }
+                }
+            },
+            "required": ["steps"],
+            "additionalProperties": false
+        });
+
+        assert!(validate(&schema, &json!({ "steps": [{ "name": "a" }] })).is_ok());
+        assert_eq!(validate(&schema, &json!({})).unwrap_err().path, "$.steps");
+        assert_eq!(
+            validate(&schema, &json!({ "steps": [{ "name": "a" }, {}] })).unwrap_err().path,
+            "$.steps[1].name"
+        );
+        assert_eq!(
+            validate(&schema, &json!({ "steps": [{ "name": 1 }] })).unwrap_err().message,
+            "expected string, found integer"
+        );
+        assert_eq!(
+            validate(&schema, &json!({ "steps": [], "extra": true })).unwrap_err().path,
+            "$.extra"
+        );
+    }
+}
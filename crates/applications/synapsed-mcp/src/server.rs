@@ -2,10 +2,13 @@
 
 use crate::{
     error::{McpError, Result},
-    tools::{IntentTools, VerificationTools},
+    tools::{IntentDeclareParams, IntentTools, IntentVerifyParams, ToolSchema, VerificationTools},
     resources::ContextResources,
     intent_store::IntentStore,
-    protocol::{McpProtocolHandler, JsonRpcRequest, JsonRpcResponse},
+    interceptor::{Flow, InterceptorChain},
+    paging::{PagerLimits, ResultPager, DEFAULT_CHUNK_SIZE, DEFAULT_RESULT_TTL},
+    rate_limit::{RateLimiter, RateLimits},
+    protocol::{Caller, McpProtocolHandler, JsonRpcError, JsonRpcRequest, JsonRpcResponse},
     agent_spawner::AgentSpawner,
     observability::{McpEvent, EVENT_CIRCUIT},
 };
@@ -34,6 +37,18 @@ pub struct ServerConfig {
     pub max_concurrent_intents: usize,
     /// Trust threshold for verification
     pub trust_threshold: f64,
+    /// Maximum size in bytes of a tool result before it is returned in chunks
+    #[serde(default = "default_result_chunk_size")]
+    pub result_chunk_size: usize,
+    /// Caps on the tool results held for chunked fetching
+    #[serde(default)]
+    pub result_limits: PagerLimits,
+    /// Per-client, per-tool rate limits
+    #[serde(default)]
+    pub rate_limits: RateLimits,
+    /// Interceptors run in order before each request is handled
+    #[serde(skip)]
+    pub interceptors: InterceptorChain,
 }
 
 impl Default for ServerConfig {
@@ -47,10 +62,18 @@ impl Default for ServerConfig {
             enable_context_injection: true,
             max_concurrent_intents: 10,
             trust_threshold: 0.8,
+            result_chunk_size: DEFAULT_CHUNK_SIZE,
+            result_limits: PagerLimits::default(),
+            rate_limits: RateLimits::default(),
+            interceptors: InterceptorChain::new(),
         }
     }
 }
 
+fn default_result_chunk_size() -> usize {
+    DEFAULT_CHUNK_SIZE
+}
+
 impl ServerConfig {
     /// Load configuration from environment variables
     pub fn from_env() -> Result<Self> {
@@ -95,6 +118,8 @@ pub struct McpServer {
     context_resources: Arc<ContextResources>,
     protocol_handler: Arc<McpProtocolHandler>,
     agent_spawner: Arc<AgentSpawner>,
+    interceptors: InterceptorChain,
+    result_pager: ResultPager,
 }
 
 impl McpServer {
@@ -166,6 +191,19 @@ impl McpServer {
             }
         });
         
+        // Rate limits run after the configured interceptors so they see any
+        // caller identity an authentication interceptor establishes
+        let mut interceptors = config.interceptors.clone();
+        if !config.rate_limits.is_empty() {
+            let tools = intent_tools.schemas()
+                .chain(verification_tools.schemas())
+                .map(|schema| schema.name.clone());
+            interceptors.push(Arc::new(RateLimiter::new(config.rate_limits.clone()).with_tools(tools)));
+        }
+        
+        let result_pager = ResultPager::new(config.result_chunk_size, DEFAULT_RESULT_TTL)
+            .with_limits(config.result_limits);
+        
         Self {
             config,
             state,
@@ -174,12 +212,112 @@ impl McpServer {
             context_resources,
             protocol_handler,
             agent_spawner,
+            interceptors,
+            result_pager,
         }
     }
     
+    /// Handle a JSON-RPC request received from a caller
+    pub async fn handle_request_from(&self, caller: Caller, mut request: JsonRpcRequest) -> JsonRpcResponse {
+        request.caller = Some(caller);
+        self.handle_request(request).await
+    }
+    
     /// Handle a JSON-RPC request
     pub async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        self.protocol_handler.handle_request(request).await
+        match self.interceptors.run(request).await {
+            Flow::Continue(request) if request.method == "tools/call" => self.call_tool(request).await,
+            Flow::Continue(request) => self.protocol_handler.handle_request(request).await,
+            Flow::Respond(response) => response,
+        }
+    }
+    
+    /// Get the declared schema of a tool
+    pub fn tool_schema(&self, name: &str) -> Option<&ToolSchema> {
+        self.intent_tools.schema(name)
+            .or_else(|| self.verification_tools.schema(name))
+    }
+    
+    /// Handle a `tools/call` request
+    ///
+    /// Arguments are validated against the tool's declared schema before the
+    /// tool is invoked, so tools only ever see well-formed input. Results
+    /// larger than the configured chunk size are returned in chunks; passing
+    /// a chunk's `next_cursor` fetches the next one.
+    async fn call_tool(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        let caller = request.caller.unwrap_or(Caller::Local);
+        let params = request.params.unwrap_or_default();
+        if let Some(cursor) = params.get("cursor").and_then(|cursor| cursor.as_str()) {
+            let result = self.result_pager.next(&caller, cursor).await;
+            return tool_response(request.id, result);
+        }
+        
+        let Some(name) = params.get("name").and_then(|name| name.as_str()) else {
+            return error_response(request.id, -32602, "Invalid params: missing tool name".to_string(), None);
+        };
+        let arguments = params.get("arguments").cloned()
+            .unwrap_or_else(|| serde_json::json!({}));
+        
+        let Some(schema) = self.tool_schema(name) else {
+            return error_response(request.id, -32602, format!("Unknown tool: {}", name), None);
+        };
+        if let Err(violation) = schema.validate(&arguments) {
+            return error_response(
+                request.id,
+                -32602,
+                format!("Invalid params: {}", violation),
+                Some(serde_json::json!({
+                    "tool": name,
+                    "path": violation.path,
+                    "reason": violation.message,
+                })),
+            );
+        }
+        
+        let result = match self.dispatch_tool(name, arguments).await {
+            Ok(result) => self.result_pager.page(&caller, result).await,
+            Err(e) => Err(e),
+        };
+        tool_response(request.id, result)
+    }
+    
+    /// Invoke a tool with validated arguments
+    async fn dispatch_tool(&self, name: &str, arguments: serde_json::Value) -> Result<serde_json::Value> {
+        let arg = |field: &str| -> Result<String> {
+            arguments.get(field)
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| McpError::InvalidParams(format!("missing {}", field)))
+        };
+        let optional = |field: &str| arguments.get(field).and_then(|value| value.as_str()).map(str::to_string);
+        
+        match name {
+            "intent_declare" => {
+                let params: IntentDeclareParams = serde_json::from_value(arguments.clone())
+                    .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+                self.intent_tools.intent_declare(params).await
+            }
+            "intent_verify" => {
+                let params: IntentVerifyParams = serde_json::from_value(arguments.clone())
+                    .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+                self.intent_tools.intent_verify(params).await
+            }
+            "trust_check" => {
+                let agent_id = uuid::Uuid::parse_str(&arg("agent_id")?)
+                    .map_err(|e| McpError::InvalidParams(e.to_string()))?;
+                self.verification_tools.trust_check(agent_id).await
+            }
+            "intent_status" => self.verification_tools.intent_status(arg("intent_id")?).await,
+            "intent_complete" => self.verification_tools.intent_complete(arg("intent_id")?).await,
+            "intent_list" => self.verification_tools.intent_list(optional("status")).await,
+            "intent_children" => self.verification_tools.intent_children(arg("parent_id")?).await,
+            "intent_step_status" => {
+                self.verification_tools
+                    .intent_step_status(arg("intent_id")?, arg("step_name")?, arg("status")?, optional("error"))
+                    .await
+            }
+            _ => Err(McpError::ToolNotFound(name.to_string())),
+        }
     }
     
     /// Serve over stdio transport
@@ -224,7 +362,7 @@ impl McpServer {
             
             // Parse as JSON-RPC request
             if let Ok(request) = serde_json::from_str::<JsonRpcRequest>(&line) {
-                let response = self.handle_request(request).await;
+                let response = self.handle_request_from(Caller::Local, request).await;
                 
                 // Write response
                 use tokio::io::AsyncWriteExt;
@@ -242,4 +380,217 @@ impl McpServer {
         info!("MCP server shutting down");
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Build a JSON-RPC error response
+fn error_response(
+    id: Option<serde_json::Value>,
+    code: i32,
+    message: String,
+    data: Option<serde_json::Value>,
+) -> JsonRpcResponse {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: None,
+        error: Some(JsonRpcError { code, message, data }),
+        id,
+    }
+}
+
+/// Build the response to a tool call
+fn tool_response(id: Option<serde_json::Value>, result: Result<serde_json::Value>) -> JsonRpcResponse {
+    match result {
+        Ok(result) => JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(result),
+            error: None,
+            id,
+        },
+        Err(McpError::InvalidParams(message)) => {
+            error_response(id, -32602, format!("Invalid params: {}", message), None)
+        }
+        Err(e) => error_response(id, -32603, e.to_string(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interceptor::{Interceptor, REQUEST_REJECTED};
+    use crate::paging::{collect_chunks, ResultChunk};
+    use crate::rate_limit::{RateLimit, RATE_LIMITED};
+    use async_trait::async_trait;
+    
+    /// Rejects tool calls that don't carry an auth token
+    struct RequireAuth;
+    
+    #[async_trait]
+    impl Interceptor for RequireAuth {
+        fn name(&self) -> &str {
+            "require-auth"
+        }
+        
+        async fn before(&self, request: JsonRpcRequest) -> Flow {
+            let authenticated = request
+                .params
+                .as_ref()
+                .and_then(|params| params.get("auth_token"))
+                .is_some();
+            if request.method == "tools/call" && !authenticated {
+                return Flow::reject(request.id, REQUEST_REJECTED, "Unauthenticated");
+            }
+            Flow::Continue(request)
+        }
+    }
+    
+    fn request(method: &str, params: Option<serde_json::Value>) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+            id: Some(serde_json::json!(1)),
+            caller: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_interceptor_rejects_unauthenticated_tool_calls() {
+        let config = ServerConfig {
+            interceptors: InterceptorChain::new().with(Arc::new(RequireAuth)),
+            ..ServerConfig::default()
+        };
+        let server = McpServer::new(config);
+        
+        let response = server.handle_request(request("tools/call", None)).await;
+        let error = response.error.expect("unauthenticated call should be rejected");
+        assert_eq!(error.code, REQUEST_REJECTED);
+        assert_eq!(response.id, Some(serde_json::json!(1)));
+        
+        // Other methods pass through to the protocol handler
+        let response = server.handle_request(request("initialize", None)).await;
+        assert_ne!(response.error.map(|e| e.code), Some(REQUEST_REJECTED));
+        
+        let params = serde_json::json!({ "auth_token": "secret" });
+        let response = server.handle_request(request("tools/call", Some(params))).await;
+        assert_ne!(response.error.map(|e| e.code), Some(REQUEST_REJECTED));
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limit_per_client_and_tool() {
+        let config = ServerConfig {
+            rate_limits: RateLimits::default().with_tool("intent/list", RateLimit::new(2, 20.0)),
+            ..ServerConfig::default()
+        };
+        let server = McpServer::new(config);
+        let peer_a = Caller::Peer("10.0.0.1:4000".parse().unwrap());
+        let peer_b = Caller::Peer("10.0.0.2:4000".parse().unwrap());
+        
+        for _ in 0..2 {
+            let response = server.handle_request_from(peer_a.clone(), request("intent/list", None)).await;
+            assert!(response.error.is_none());
+        }
+        
+        let response = server.handle_request_from(peer_a.clone(), request("intent/list", None)).await;
+        let error = response.error.expect("third call should be rate limited");
+        assert_eq!(error.code, RATE_LIMITED);
+        let data = error.data.unwrap();
+        assert_eq!(data["caller"], "10.0.0.1:4000");
+        assert_eq!(data["tool"], "intent/list");
+        assert!(data["retry_after_ms"].as_u64().unwrap() <= 50);
+        
+        // Claiming another client ID in the request doesn't reset the limit
+        let params = serde_json::json!({ "client_id": "agent-b" });
+        let response = server.handle_request_from(peer_a.clone(), request("intent/list", Some(params))).await;
+        assert_eq!(response.error.map(|e| e.code), Some(RATE_LIMITED));
+        
+        // Other callers have their own bucket
+        let response = server.handle_request_from(peer_b, request("intent/list", None)).await;
+        assert!(response.error.is_none());
+        
+        // The bucket refills over time
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+        let response = server.handle_request_from(peer_a, request("intent/list", None)).await;
+        assert!(response.error.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_tool_arguments_validated_against_schema() {
+        let server = McpServer::new(ServerConfig::default());
+        
+        let params = serde_json::json!({
+            "name": "intent_declare",
+            "arguments": {
+                "steps": [],
+                "success_criteria": ["done"]
+            }
+        });
+        let response = server.handle_request(request("tools/call", Some(params))).await;
+        
+        let error = response.error.expect("missing goal should fail validation");
+        assert_eq!(error.code, -32602);
+        assert!(error.message.contains("goal"));
+        let data = error.data.unwrap();
+        assert_eq!(data["path"], "$.goal");
+        assert_eq!(data["tool"], "intent_declare");
+        
+        // Nested fields are reported with their full path
+        let params = serde_json::json!({
+            "name": "intent_declare",
+            "arguments": {
+                "goal": "build",
+                "steps": [{ "name": "compile" }],
+                "success_criteria": []
+            }
+        });
+        let response = server.handle_request(request("tools/call", Some(params))).await;
+        assert_eq!(response.error.unwrap().data.unwrap()["path"], "$.steps[0].action");
+    }
+    
+    #[tokio::test]
+    async fn test_large_tool_result_returned_in_chunks() {
+        let config = ServerConfig {
+            result_chunk_size: 128,
+            ..ServerConfig::default()
+        };
+        let server = McpServer::new(config);
+        
+        for i in 0..10 {
+            let params = serde_json::json!({
+                "name": "intent_declare",
+                "arguments": {
+                    "goal": format!("intent number {}", i),
+                    "steps": [{ "name": "run", "action": "echo ü" }],
+                    "success_criteria": []
+                }
+            });
+            let response = server.handle_request(request("tools/call", Some(params))).await;
+            assert!(response.error.is_none());
+        }
+        
+        let params = serde_json::json!({ "name": "intent_list", "arguments": {} });
+        let first = server.handle_request(request("tools/call", Some(params))).await.result.unwrap();
+        let chunk = ResultChunk::from_result(&first).expect("result should be chunked");
+        assert!(chunk.chunk.len() <= 128);
+        assert!(chunk.next_cursor.is_some());
+        
+        let mut fetched = 0;
+        let result = collect_chunks(first, |cursor| {
+            fetched += 1;
+            let params = serde_json::json!({ "name": "intent_list", "cursor": cursor });
+            let server = &server;
+            async move {
+                let response = server.handle_request(request("tools/call", Some(params))).await;
+                response.result.ok_or_else(|| McpError::Protocol("missing chunk".to_string()))
+            }
+        }).await.unwrap();
+        
+        assert!(fetched >= 2);
+        assert_eq!(result["count"], 10);
+        assert_eq!(result["intents"].as_array().unwrap().len(), 10);
+        
+        // Small results are returned unchunked
+        let params = serde_json::json!({ "name": "intent_children", "arguments": { "parent_id": "none" } });
+        let result = server.handle_request(request("tools/call", Some(params))).await.result.unwrap();
+        assert!(ResultChunk::from_result(&result).is_none());
+    }
+}
@@ -3,9 +3,12 @@
 use crate::{
     error::{McpError, Result},
     observability::{McpEvent, EVENT_CIRCUIT},
+    schema::{self, SchemaViolation},
 };
 use rmcp::tool;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -67,15 +70,178 @@ pub struct IntentVerifyParams {
     pub evidence: serde_json::Value,
 }
 
+/// Declared name, description and input schema of a tool
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ToolSchema {
+    /// Tool name used in `tools/call`
+    pub name: String,
+    /// Tool description
+    pub description: String,
+    /// JSON Schema of the tool arguments
+    pub input_schema: serde_json::Value,
+}
+
+impl ToolSchema {
+    /// Create a tool schema
+    pub fn new(name: &str, description: &str, input_schema: serde_json::Value) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema,
+        }
+    }
+    
+    /// Validate tool arguments against the input schema
+    pub fn validate(&self, arguments: &serde_json::Value) -> std::result::Result<(), SchemaViolation> {
+        schema::validate(&self.input_schema, arguments)
+    }
+}
+
+/// Declared schemas of the intent tools
+///
+/// The single definition used both to validate `tools/call` arguments and
+/// to list the tools, here and in the rmcp adapter.
+pub fn intent_tool_schemas() -> Vec<ToolSchema> {
+    let step = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "action": { "type": "string" },
+            "verification": {
+                "type": ["object", "null"],
+                "properties": {
+                    "verification_type": { "type": "string" },
+                    "expected": {}
+                },
+                "required": ["verification_type", "expected"]
+            }
+        },
+        "required": ["name", "action"]
+    });
+    
+    vec![
+        ToolSchema::new(
+            "intent_declare",
+            "Declare an intent before performing actions",
+            json!({
+                "type": "object",
+                "properties": {
+                    "goal": { "type": "string", "minLength": 1, "description": "The goal of the intent" },
+                    "description": { "type": ["string", "null"], "description": "Optional description" },
+                    "steps": { "type": "array", "items": step },
+                    "success_criteria": { "type": "array", "items": { "type": "string" } },
+                    "context_bounds": {
+                        "type": ["object", "null"],
+                        "properties": {
+                            "allowed_operations": { "type": "array", "items": { "type": "string" } },
+                            "restricted_paths": { "type": "array", "items": { "type": "string" } },
+                            "max_execution_time": { "type": ["integer", "null"], "minimum": 0 }
+                        },
+                        "required": ["allowed_operations", "restricted_paths"]
+                    }
+                },
+                "required": ["goal", "steps", "success_criteria"]
+            }),
+        ),
+        ToolSchema::new(
+            "intent_verify",
+            "Verify that an intent was completed successfully",
+            json!({
+                "type": "object",
+                "properties": {
+                    "intent_id": { "type": "string", "format": "uuid", "description": "UUID of the intent to verify" },
+                    "evidence": { "description": "Evidence of completion" }
+                },
+                "required": ["intent_id", "evidence"]
+            }),
+        ),
+    ]
+}
+
+/// Declared schemas of the verification tools
+///
+/// See [`intent_tool_schemas`].
+pub fn verification_tool_schemas() -> Vec<ToolSchema> {
+    let intent_id = json!({
+        "type": "object",
+        "properties": { "intent_id": { "type": "string", "description": "ID of the intent" } },
+        "required": ["intent_id"]
+    });
+    
+    vec![
+        ToolSchema::new(
+            "trust_check",
+            "Check the trust level of an agent based on promise fulfillment",
+            json!({
+                "type": "object",
+                "properties": { "agent_id": { "type": "string", "format": "uuid" } },
+                "required": ["agent_id"]
+            }),
+        ),
+        ToolSchema::new("intent_status", "Get the current status of a declared intent", intent_id.clone()),
+        ToolSchema::new("intent_complete", "Mark an intent as completed", intent_id),
+        ToolSchema::new(
+            "intent_list",
+            "List all intents with optional status filter",
+            json!({
+                "type": "object",
+                "properties": { "status": { "type": ["string", "null"] } }
+            }),
+        ),
+        ToolSchema::new(
+            "intent_children",
+            "Get all child intents of a parent intent",
+            json!({
+                "type": "object",
+                "properties": { "parent_id": { "type": "string" } },
+                "required": ["parent_id"]
+            }),
+        ),
+        ToolSchema::new(
+            "intent_step_status",
+            "Update the status of a specific step in an intent",
+            json!({
+                "type": "object",
+                "properties": {
+                    "intent_id": { "type": "string" },
+                    "step_name": { "type": "string" },
+                    "status": { "type": "string" },
+                    "error": { "type": ["string", "null"] }
+                },
+                "required": ["intent_id", "step_name", "status"]
+            }),
+        ),
+    ]
+}
+
+/// Index tool schemas by name
+fn register_schemas(schemas: Vec<ToolSchema>) -> HashMap<String, ToolSchema> {
+    schemas.into_iter().map(|schema| (schema.name.clone(), schema)).collect()
+}
+
 /// Intent tools for MCP
 pub struct IntentTools {
     state: Arc<RwLock<crate::server::ServerState>>,
+    schemas: HashMap<String, ToolSchema>,
 }
 
 impl IntentTools {
     /// Create new intent tools
     pub fn new(state: Arc<RwLock<crate::server::ServerState>>) -> Self {
-        Self { state }
+        Self {
+            state,
+            schemas: register_schemas(intent_tool_schemas()),
+        }
+    }
+    
+    /// Get the declared schema of a tool
+    pub fn schema(&self, name: &str) -> Option<&ToolSchema> {
+        self.schemas.get(name)
+    }
+    
+    /// Get the declared schemas of all tools
+    pub fn schemas(&self) -> impl Iterator<Item = &ToolSchema> {
+        self.schemas.values()
     }
     
     /// Declare an intent before execution
@@ -190,12 +356,26 @@ impl IntentTools {
 /// Verification tools for MCP
 pub struct VerificationTools {
     state: Arc<RwLock<crate::server::ServerState>>,
+    schemas: HashMap<String, ToolSchema>,
 }
 
 impl VerificationTools {
     /// Create new verification tools
     pub fn new(state: Arc<RwLock<crate::server::ServerState>>) -> Self {
-        Self { state }
+        Self {
+            state,
+            schemas: register_schemas(verification_tool_schemas()),
+        }
+    }
+    
+    /// Get the declared schema of a tool
+    pub fn schema(&self, name: &str) -> Option<&ToolSchema> {
+        self.schemas.get(name)
+    }
+    
+    /// Get the declared schemas of all tools
+    pub fn schemas(&self) -> impl Iterator<Item = &ToolSchema> {
+        self.schemas.values()
     }
     
     /// Check trust level of an agent
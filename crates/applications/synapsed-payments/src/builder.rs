@@ -6,6 +6,8 @@ use crate::gateway::{GatewayConfig, GatewayFactory};
 use crate::processor::{BasicRiskEngine, PaymentProcessor, PaymentStorage, ProcessorConfig, RetryConfig, RiskEngine};
 use crate::storage::MemoryPaymentStorage;
 use crate::types::{Currency, PaymentConfig};
+use crate::vault::{PaymentSource, PaymentToken, PaymentVault, SensitivePaymentMethod};
+use synapsed_storage::{Storage, StorageError};
 
 /// Builder for creating a complete PaymentManager instance
 pub struct PaymentManagerBuilder {
@@ -15,11 +17,14 @@ pub struct PaymentManagerBuilder {
     retry_config: Option<RetryConfig>,
     storage: Option<Arc<dyn PaymentStorage + Send + Sync>>,
     risk_engine: Option<Arc<dyn RiskEngine + Send + Sync>>,
+    vault_key: Option<[u8; 32]>,
+    vault_storage: Option<Arc<dyn Storage<Error = StorageError>>>,
 }
 
 /// Complete payment management system
 pub struct PaymentManager {
     processor: PaymentProcessor,
+    vault: PaymentVault,
 }
 
 impl PaymentManagerBuilder {
@@ -32,6 +37,8 @@ impl PaymentManagerBuilder {
             retry_config: None,
             storage: None,
             risk_engine: None,
+            vault_key: None,
+            vault_storage: None,
         }
     }
 
@@ -71,6 +78,24 @@ impl PaymentManagerBuilder {
         self
     }
 
+    /// Set the key used to encrypt tokenized payment methods
+    ///
+    /// A random key is generated if none is set, in which case tokens only
+    /// stay valid for the lifetime of the manager.
+    pub fn with_vault_key(mut self, key: [u8; 32]) -> Self {
+        self.vault_key = Some(key);
+        self
+    }
+
+    /// Keep tokenized payment methods in a storage backend
+    ///
+    /// Tokens are held in memory if none is set. Persistent storage needs a
+    /// vault key as well, so the records stay readable after a restart.
+    pub fn with_vault_storage(mut self, storage: Arc<dyn Storage<Error = StorageError>>) -> Self {
+        self.vault_storage = Some(storage);
+        self
+    }
+
     /// Add a quick Stripe gateway configuration
     pub fn with_stripe_gateway(
         mut self,
@@ -285,7 +310,18 @@ impl PaymentManagerBuilder {
             }
         }
 
-        Ok(PaymentManager { processor })
+        let vault = match (self.vault_key, self.vault_storage) {
+            (Some(key), Some(storage)) => PaymentVault::with_storage(key, storage),
+            (Some(key), None) => PaymentVault::with_key(key),
+            (None, None) => PaymentVault::new(),
+            (None, Some(_)) => {
+                return Err(PaymentError::configuration_error(
+                    "A vault key is required when the vault uses its own storage",
+                ));
+            }
+        };
+
+        Ok(PaymentManager { processor, vault })
     }
 }
 
@@ -310,12 +346,44 @@ impl PaymentManager {
         self.processor.create_payment_intent(amount, description, customer_id).await
     }
 
+    /// Get a reference to the payment method vault
+    pub fn vault(&self) -> &PaymentVault {
+        &self.vault
+    }
+
+    /// Store a customer's payment method in the vault and get a reusable token for it
+    pub async fn tokenize(
+        &self,
+        customer_id: &str,
+        payment_method: SensitivePaymentMethod,
+    ) -> PaymentResult<PaymentToken> {
+        self.vault.tokenize(customer_id, payment_method).await
+    }
+
+    /// Revoke a customer's payment token
+    pub async fn revoke_token(&self, customer_id: &str, token: &str) -> PaymentResult<()> {
+        self.vault.revoke(token, customer_id).await
+    }
+
     /// Convenience method to process a payment
+    ///
+    /// Accepts either a payment method or a vault token. A token must belong to
+    /// the payment's customer.
     pub async fn process_payment(
         &self,
         payment_id: uuid::Uuid,
-        payment_method: crate::types::PaymentMethod,
+        source: impl Into<PaymentSource>,
     ) -> PaymentResult<crate::types::Transaction> {
+        let payment_method = match source.into() {
+            PaymentSource::Method(method) => method,
+            PaymentSource::Token(token) => {
+                let payment = self.processor.get_payment(payment_id).await?;
+                let customer_id = payment.customer_id.ok_or_else(|| PaymentError::AccessDenied {
+                    resource: format!("token {} for payment without customer", token),
+                })?;
+                self.vault.resolve(&token, &customer_id).await?
+            }
+        };
         self.processor.process_payment(payment_id, payment_method).await
     }
 
@@ -329,6 +397,14 @@ impl PaymentManager {
         self.processor.refund_payment(payment_id, amount, reason).await
     }
 
+    /// Net settlement figures for a period, for reconciling processor payouts
+    pub async fn settlement_report(
+        &self,
+        period: std::ops::Range<chrono::DateTime<chrono::Utc>>,
+    ) -> PaymentResult<crate::settlement::SettlementReport> {
+        self.processor.settlement_report(period).await
+    }
+
     /// Get payment status
     pub async fn get_payment_status(
         &self,
@@ -479,4 +555,187 @@ mod tests {
             panic!("Expected configuration error");
         }
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_tokenized_payment_never_stores_pan() {
+        use crate::processor::PaymentStorage;
+        use crate::vault::{CardDetails, SensitivePaymentMethod};
+
+        const PAN: &str = "4111111111111111";
+
+        let storage = Arc::new(MemoryPaymentStorage::new());
+        let manager = PaymentManagerBuilder::development()
+            .with_storage(storage.clone())
+            .build()
+            .expect("Failed to build manager");
+
+        let card = SensitivePaymentMethod::CreditCard(CardDetails {
+            number: PAN.to_string(),
+            brand: "Visa".to_string(),
+            exp_month: 12,
+            exp_year: 2030,
+            holder_name: "Test User".to_string(),
+        });
+        let token = manager.tokenize("cust_1", card).await.unwrap();
+
+        let amount = Amount::new(Decimal::new(2500, 2), Currency::Fiat(FiatCurrency::USD)).unwrap();
+        let payment = manager
+            .create_payment(amount.clone(), "Tokenized".to_string(), Some("cust_1".to_string()))
+            .await
+            .unwrap();
+        let transaction = manager.process_payment(payment.id, &token).await.unwrap();
+        assert_eq!(transaction.payment_method, token.payment_method);
+
+        // The token can be reused, but only by its customer
+        let other = manager
+            .create_payment(amount.clone(), "Other customer".to_string(), Some("cust_2".to_string()))
+            .await
+            .unwrap();
+        assert!(manager.process_payment(other.id, &token).await.is_err());
+
+        let stored = storage.get_payment(payment.id).await.unwrap();
+        let processed_by = transaction.gateway_response.as_ref().map(|r| r.gateway_id.as_str());
+        assert!(processed_by.is_some());
+        assert_eq!(stored.gateway_id.as_deref(), processed_by);
+        let transactions = storage.get_payment_transactions(payment.id).await.unwrap();
+        let serialized = format!(
+            "{}{}",
+            serde_json::to_string(&stored).unwrap(),
+            serde_json::to_string(&transactions).unwrap()
+        );
+        assert!(!serialized.contains(PAN));
+        assert!(serialized.contains("1111"));
+
+        manager.revoke_token("cust_1", &token.token).await.unwrap();
+        let again = manager
+            .create_payment(amount, "After revoke".to_string(), Some("cust_1".to_string()))
+            .await
+            .unwrap();
+        assert!(manager.process_payment(again.id, &token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_settlement_report_nets_per_currency() {
+        use crate::processor::PaymentStorage;
+        use crate::types::{PaymentMethod, Transaction, TransactionType};
+
+        let storage = Arc::new(MemoryPaymentStorage::new());
+        let manager = PaymentManagerBuilder::development()
+            .with_storage(storage.clone())
+            .build()
+            .expect("Failed to build manager");
+        let usd = Currency::Fiat(FiatCurrency::USD);
+        let eur = Currency::Fiat(FiatCurrency::EUR);
+        let card = PaymentMethod::CreditCard {
+            last_four: "4242".to_string(),
+            brand: "Visa".to_string(),
+            exp_month: 12,
+            exp_year: 2030,
+            holder_name: "Test User".to_string(),
+        };
+        let start = chrono::Utc::now() - chrono::Duration::minutes(1);
+
+        let mut payments = Vec::new();
+        for (value, currency) in [(10000, &usd), (5000, &usd), (8000, &eur)] {
+            let amount = Amount::new(Decimal::new(value, 2), currency.clone()).unwrap();
+            let payment = manager
+                .create_payment(amount, "Settlement".to_string(), None)
+                .await
+                .unwrap();
+            manager.process_payment(payment.id, card.clone()).await.unwrap();
+            payments.push(payment.id);
+        }
+        let usd_refund = Amount::new(Decimal::new(3000, 2), usd.clone()).unwrap();
+        manager.refund_payment(payments[0], Some(usd_refund), None).await.unwrap();
+        manager.refund_payment(payments[2], None, None).await.unwrap();
+
+        // A captured payment with a fee but no gateway reference
+        let mut unmatched = Transaction::new_with_payment_id(
+            uuid::Uuid::new_v4(),
+            TransactionType::Payment,
+            Amount::new(Decimal::new(2000, 2), usd.clone()).unwrap(),
+        );
+        unmatched.gateway = Some("mock_primary".to_string());
+        unmatched.fees = Some(Amount::new(Decimal::new(100, 2), usd.clone()).unwrap());
+        unmatched.mark_completed();
+        storage.store_transaction(&unmatched).await.unwrap();
+
+        let end = chrono::Utc::now() + chrono::Duration::minutes(1);
+        let report = manager.settlement_report(start..end).await.unwrap();
+
+        let usd_totals = report.totals(&usd).unwrap();
+        assert_eq!(usd_totals.gross, Decimal::new(17000, 2));
+        assert_eq!(usd_totals.refunds, Decimal::new(3000, 2));
+        assert_eq!(usd_totals.fees, Decimal::new(100, 2));
+        assert_eq!(usd_totals.payment_count, 3);
+        assert_eq!(report.net(&usd), Decimal::new(13900, 2));
+
+        let eur_totals = report.totals(&eur).unwrap();
+        assert_eq!(eur_totals.gross, Decimal::new(8000, 2));
+        assert_eq!(eur_totals.refunds, Decimal::new(8000, 2));
+        assert_eq!(report.net(&eur), Decimal::ZERO);
+
+        assert_eq!(
+            report.gateway_totals("mock_primary", &usd).map(|t| t.net()),
+            Some(Decimal::new(13900, 2))
+        );
+        assert_eq!(report.line_items.len(), 6);
+
+        let unreconciled: Vec<_> = report.unreconciled().collect();
+        assert_eq!(unreconciled.len(), 1);
+        assert_eq!(unreconciled[0].id, unmatched.id);
+
+        let later = end..end + chrono::Duration::hours(1);
+        let empty = manager.settlement_report(later).await.unwrap();
+        assert!(empty.line_items.is_empty());
+        assert_eq!(empty.net(&usd), Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_settlement_report_uses_capture_and_refund_times() {
+        use crate::processor::PaymentStorage;
+        use crate::types::{Refund, Transaction, TransactionType};
+
+        let storage = Arc::new(MemoryPaymentStorage::new());
+        let manager = PaymentManagerBuilder::development()
+            .with_storage(storage.clone())
+            .build()
+            .expect("Failed to build manager");
+        let usd = Currency::Fiat(FiatCurrency::USD);
+        let amount = Amount::new(Decimal::new(5000, 2), usd.clone()).unwrap();
+        let start = chrono::Utc::now() - chrono::Duration::minutes(1);
+
+        // Authorized before the period, captured during it
+        let mut payment = Transaction::new_with_payment_id(
+            uuid::Uuid::new_v4(),
+            TransactionType::Payment,
+            amount.clone(),
+        );
+        payment.gateway = Some("mock_primary".to_string());
+        payment.gateway_transaction_id = Some("txn_1".to_string());
+        payment.created_at = start - chrono::Duration::days(2);
+        payment.mark_completed();
+        storage.store_transaction(&payment).await.unwrap();
+
+        // Requested before the period, processed during it
+        let mut refund = Refund::new(payment.payment_id, payment.id, amount.clone(), None);
+        refund.status = crate::types::PaymentStatus::Completed;
+        refund.gateway_refund_id = Some("re_1".to_string());
+        refund.created_at = start - chrono::Duration::days(1);
+        refund.processed_at = Some(chrono::Utc::now());
+        storage.store_refund(&refund).await.unwrap();
+
+        let end = chrono::Utc::now() + chrono::Duration::minutes(1);
+        let report = manager.settlement_report(start..end).await.unwrap();
+        let totals = report.totals(&usd).unwrap();
+        assert_eq!(totals.gross, Decimal::new(5000, 2));
+        assert_eq!(totals.refunds, Decimal::new(5000, 2));
+        assert_eq!(report.line_items.len(), 2);
+        assert!(report.line_items.iter().all(|item| start <= item.occurred_at));
+
+        // Neither falls in the period when they were only created
+        let earlier = payment.created_at..start;
+        let empty = manager.settlement_report(earlier).await.unwrap();
+        assert!(empty.line_items.is_empty());
+    }
+}
@@ -11,6 +11,10 @@ pub enum PaymentError {
     #[error("Gateway error: {gateway} - {message}")]
     GatewayError { gateway: String, message: String },
 
+    /// Gateway could not be reached, so the request was never accepted
+    #[error("Gateway unavailable: {gateway} - {message}")]
+    GatewayUnavailable { gateway: String, message: String },
+
     /// Validation errors
     #[error("Validation failed: {field} - {message}")]
     ValidationError { field: String, message: String },
@@ -221,6 +225,14 @@ impl PaymentError {
         }
     }
 
+    /// Create a gateway unavailable error
+    pub fn gateway_unavailable(gateway: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::GatewayUnavailable {
+            gateway: gateway.into(),
+            message: message.into(),
+        }
+    }
+
     /// Create a validation error
     pub fn validation_error(field: impl Into<String>, message: impl Into<String>) -> Self {
         Self::ValidationError {
@@ -278,10 +290,22 @@ impl PaymentError {
             PaymentError::NetworkError { .. } |
             PaymentError::Timeout { .. } |
             PaymentError::GatewayError { .. } |
+            PaymentError::GatewayUnavailable { .. } |
             PaymentError::InternalError { .. }
         )
     }
 
+    /// Check if the gateway definitely did not act on the request
+    ///
+    /// Only these errors are safe to retry on a different gateway. A timeout
+    /// or gateway error may come after the payment was charged.
+    pub fn is_unprocessed(&self) -> bool {
+        matches!(self,
+            PaymentError::GatewayUnavailable { .. } |
+            PaymentError::RateLimitExceeded { .. }
+        )
+    }
+
     /// Check if error is permanent
     pub fn is_permanent(&self) -> bool {
         matches!(self,
@@ -300,6 +324,7 @@ impl PaymentError {
         match self {
             PaymentError::ProcessingFailed { .. } => "PROCESSING_FAILED",
             PaymentError::GatewayError { .. } => "GATEWAY_ERROR",
+            PaymentError::GatewayUnavailable { .. } => "GATEWAY_UNAVAILABLE",
             PaymentError::ValidationError { .. } => "VALIDATION_ERROR",
             PaymentError::ConfigurationError { .. } => "CONFIGURATION_ERROR",
             PaymentError::AuthenticationError { .. } => "AUTHENTICATION_ERROR",
@@ -365,6 +390,14 @@ impl From<sqlx::Error> for PaymentError {
     }
 }
 
+impl From<synapsed_storage::StorageError> for PaymentError {
+    fn from(err: synapsed_storage::StorageError) -> Self {
+        Self::DatabaseError {
+            message: err.to_string(),
+        }
+    }
+}
+
 #[cfg(feature = "http-gateway")]
 impl From<reqwest::Error> for PaymentError {
     fn from(err: reqwest::Error) -> Self {
@@ -446,6 +479,21 @@ mod tests {
         assert!(validation_error.is_permanent());
     }
 
+    #[test]
+    fn test_error_unprocessed() {
+        let unavailable = PaymentError::gateway_unavailable("primary", "Connection refused");
+        assert!(unavailable.is_retryable());
+        assert!(unavailable.is_unprocessed());
+
+        // Retryable on the same gateway, but the charge may have gone through
+        let timeout = PaymentError::Timeout {
+            operation: "payment".to_string(),
+        };
+        assert!(timeout.is_retryable());
+        assert!(!timeout.is_unprocessed());
+        assert!(!PaymentError::gateway_error("primary", "HTTP 502").is_unprocessed());
+    }
+
     #[test]
     fn test_gateway_error_code() {
         let code = GatewayErrorCode::CardDeclined;
@@ -2,7 +2,8 @@ use async_trait::async_trait;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use crate::error::{PaymentError, PaymentResult};
 use crate::types::{
@@ -30,6 +31,17 @@ pub trait PaymentGateway {
     /// Health check for the gateway
     async fn health_check(&self) -> PaymentResult<()>;
 
+    /// Look up a payment submitted under an idempotency key (optional)
+    ///
+    /// Returns `Ok(None)` if the gateway has no record of the payment, which
+    /// means it was never charged. Gateways that cannot look payments up
+    /// return an error, so the outcome stays unknown.
+    async fn find_payment(&self, _idempotency_key: &str) -> PaymentResult<Option<GatewayResponse>> {
+        Err(PaymentError::ConfigurationError {
+            message: "Gateway does not support payment lookup".to_string(),
+        })
+    }
+
     /// Get gateway capabilities (optional)
     async fn get_capabilities(&self) -> PaymentResult<crate::types::GatewayCapabilities> {
         // Default implementation with basic capabilities
@@ -94,6 +106,15 @@ pub struct HttpPaymentGateway {
     client: reqwest::Client,
 }
 
+/// Payment as returned by an HTTP gateway
+#[cfg(feature = "http-gateway")]
+#[derive(Deserialize)]
+struct HttpPaymentResponse {
+    id: String,
+    status: String,
+    message: Option<String>,
+}
+
 #[cfg(feature = "http-gateway")]
 impl HttpPaymentGateway {
     /// Create a new HTTP gateway
@@ -115,6 +136,17 @@ impl HttpPaymentGateway {
         endpoint: &str,
         body: Option<serde_json::Value>,
     ) -> PaymentResult<T> {
+        let response = self.send_request(method, endpoint, body).await?;
+        self.parse_response(response).await
+    }
+
+    /// Send an authenticated request without checking the response status
+    async fn send_request(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        body: Option<serde_json::Value>,
+    ) -> PaymentResult<reqwest::Response> {
         let url = format!("{}/{}", self.config.api_url.trim_end_matches('/'), endpoint);
         
         let mut request = self.client.request(method, &url);
@@ -127,13 +159,30 @@ impl HttpPaymentGateway {
             request = request.json(&body);
         }
 
-        let response = request.send().await.map_err(|e| PaymentError::NetworkError {
-            message: format!("Request failed: {}", e),
-        })?;
+        request.send().await.map_err(|e| {
+            if e.is_connect() {
+                PaymentError::gateway_unavailable(&self.config.gateway_id, format!("Connection failed: {}", e))
+            } else {
+                PaymentError::NetworkError {
+                    message: format!("Request failed: {}", e),
+                }
+            }
+        })
+    }
 
+    /// Check the response status and parse the body
+    async fn parse_response<T: serde::de::DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> PaymentResult<T> {
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(PaymentError::RateLimitExceeded {
+                    message: format!("{}: {}", self.config.gateway_id, text),
+                });
+            }
             return Err(PaymentError::GatewayError {
                 gateway: self.config.gateway_id.clone(),
                 message: format!("HTTP {}: {}", status, text),
@@ -145,6 +194,22 @@ impl HttpPaymentGateway {
         })
     }
 
+    /// Convert a payment response into a gateway response
+    fn payment_response(&self, response: HttpPaymentResponse) -> GatewayResponse {
+        GatewayResponse {
+            gateway_id: self.config.gateway_id.clone(),
+            transaction_id: response.id.clone(),
+            status_code: response.status.clone(),
+            message: response.message.clone().unwrap_or_default(),
+            raw_response: serde_json::json!({
+                "id": response.id,
+                "status": response.status,
+                "message": response.message
+            }),
+            timestamp: Utc::now(),
+        }
+    }
+
     /// Add authentication headers (to be customized per gateway)
     fn add_auth_headers(&self, request: reqwest::RequestBuilder) -> PaymentResult<reqwest::RequestBuilder> {
         // This is a placeholder - each gateway will have different auth methods
@@ -225,33 +290,15 @@ impl PaymentGateway for HttpPaymentGateway {
             "currency": payment.amount.currency,
             "description": payment.description,
             "payment_method": payment_method_data,
-            "idempotency_key": payment.id,
+            "idempotency_key": payment.idempotency_key(),
             "metadata": payment.metadata
         });
 
-        #[derive(Deserialize)]
-        struct PaymentResponse {
-            id: String,
-            status: String,
-            message: Option<String>,
-        }
-
-        let response: PaymentResponse = self
+        let response: HttpPaymentResponse = self
             .make_request(reqwest::Method::POST, "payments", Some(request_body))
             .await?;
 
-        Ok(GatewayResponse {
-            gateway_id: self.config.gateway_id.clone(),
-            transaction_id: response.id,
-            status_code: response.status,
-            message: response.message.unwrap_or_default(),
-            raw_response: serde_json::json!({
-                "id": response.id,
-                "status": response.status,
-                "message": response.message
-            }),
-            timestamp: Utc::now(),
-        })
+        Ok(self.payment_response(response))
     }
 
     async fn process_refund(
@@ -292,6 +339,18 @@ impl PaymentGateway for HttpPaymentGateway {
         })
     }
 
+    async fn find_payment(&self, idempotency_key: &str) -> PaymentResult<Option<GatewayResponse>> {
+        let endpoint = format!("payments?idempotency_key={}", idempotency_key);
+        let response = self.send_request(reqwest::Method::GET, &endpoint, None).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response: HttpPaymentResponse = self.parse_response(response).await?;
+        Ok(Some(self.payment_response(response)))
+    }
+
     async fn health_check(&self) -> PaymentResult<()> {
         #[derive(Deserialize)]
         struct HealthResponse {
@@ -367,7 +426,12 @@ impl PaymentGateway for HttpPaymentGateway {
 pub struct MockPaymentGateway {
     pub gateway_id: String,
     pub should_fail: bool,
+    pub should_decline: bool,
+    pub should_be_unavailable: bool,
+    pub should_fail_after_charge: bool,
     pub delay_ms: Option<u64>,
+    /// Charged payments by idempotency key
+    charged: Mutex<HashMap<String, GatewayResponse>>,
 }
 
 impl MockPaymentGateway {
@@ -375,7 +439,11 @@ impl MockPaymentGateway {
         Self {
             gateway_id,
             should_fail: false,
+            should_decline: false,
+            should_be_unavailable: false,
+            should_fail_after_charge: false,
             delay_ms: None,
+            charged: Mutex::new(HashMap::new()),
         }
     }
 
@@ -384,6 +452,22 @@ impl MockPaymentGateway {
         self
     }
 
+    pub fn with_decline(mut self, should_decline: bool) -> Self {
+        self.should_decline = should_decline;
+        self
+    }
+
+    pub fn with_unavailable(mut self, should_be_unavailable: bool) -> Self {
+        self.should_be_unavailable = should_be_unavailable;
+        self
+    }
+
+    /// Charge payments but fail as if the response was lost
+    pub fn with_failure_after_charge(mut self, should_fail_after_charge: bool) -> Self {
+        self.should_fail_after_charge = should_fail_after_charge;
+        self
+    }
+
     pub fn with_delay(mut self, delay_ms: u64) -> Self {
         self.delay_ms = Some(delay_ms);
         self
@@ -401,6 +485,13 @@ impl PaymentGateway for MockPaymentGateway {
             tokio::time::sleep(Duration::from_millis(delay)).await;
         }
 
+        if self.should_be_unavailable {
+            return Err(PaymentError::gateway_unavailable(
+                self.gateway_id.clone(),
+                "Mock gateway unreachable",
+            ));
+        }
+
         if self.should_fail {
             return Err(PaymentError::GatewayError {
                 gateway: self.gateway_id.clone(),
@@ -408,17 +499,48 @@ impl PaymentGateway for MockPaymentGateway {
             });
         }
 
-        Ok(GatewayResponse {
-            gateway_id: self.gateway_id.clone(),
-            transaction_id: format!("mock_tx_{}", payment.id),
-            status_code: "success".to_string(),
-            message: "Mock payment processed".to_string(),
-            raw_response: serde_json::json!({
-                "mock": true,
-                "payment_id": payment.id
-            }),
-            timestamp: Utc::now(),
-        })
+        if self.should_decline {
+            return Err(PaymentError::processing_failed_with_code(
+                "Mock card declined",
+                "card_declined",
+            ));
+        }
+
+        let response = self
+            .charged
+            .lock()
+            .expect("mock charges lock poisoned")
+            .entry(payment.idempotency_key())
+            .or_insert_with(|| GatewayResponse {
+                gateway_id: self.gateway_id.clone(),
+                transaction_id: format!("mock_tx_{}", payment.id),
+                status_code: "success".to_string(),
+                message: "Mock payment processed".to_string(),
+                raw_response: serde_json::json!({
+                    "mock": true,
+                    "payment_id": payment.id
+                }),
+                timestamp: Utc::now(),
+            })
+            .clone();
+
+        if self.should_fail_after_charge {
+            return Err(PaymentError::GatewayError {
+                gateway: self.gateway_id.clone(),
+                message: "Mock gateway failure after charge".to_string(),
+            });
+        }
+
+        Ok(response)
+    }
+
+    async fn find_payment(&self, idempotency_key: &str) -> PaymentResult<Option<GatewayResponse>> {
+        Ok(self
+            .charged
+            .lock()
+            .expect("mock charges lock poisoned")
+            .get(idempotency_key)
+            .cloned())
     }
 
     async fn process_refund(
@@ -461,6 +583,254 @@ impl PaymentGateway for MockPaymentGateway {
     }
 }
 
+/// Health tracking settings for [`FailoverGateway`]
+#[derive(Debug, Clone)]
+pub struct FailoverConfig {
+    /// Consecutive gateway-level failures before a gateway is skipped
+    pub failure_threshold: u32,
+    /// How long a failing gateway is skipped before being tried again
+    pub cooldown: Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Health of a gateway behind a [`FailoverGateway`]
+#[derive(Debug, Clone, Default)]
+struct GatewayHealth {
+    consecutive_failures: u32,
+    skipped_until: Option<Instant>,
+}
+
+/// Result of routing a payment through a [`FailoverGateway`]
+#[derive(Debug, Clone)]
+pub struct FailoverOutcome {
+    /// Response from the gateway that processed the payment
+    pub response: GatewayResponse,
+    /// ID of the gateway that processed the payment
+    pub processed_by: String,
+    /// Gateways that failed before it, with their error codes
+    pub failed_over: Vec<(String, &'static str)>,
+}
+
+/// Gateway that fails over across an ordered list of gateways
+///
+/// Gateway-level errors (network failures, timeouts, gateway outages) move the
+/// payment on to the next gateway. Every gateway sees the payment under the
+/// same idempotency key, and after a failure that may have come after the
+/// charge the failed gateway is asked whether it charged under that key; the
+/// payment only moves on if it did not, so it is never charged twice. If the
+/// gateway cannot say, the error is returned for the caller to reconcile.
+/// Any other error, such as a decline, is returned immediately without trying
+/// another gateway. A gateway that fails `failure_threshold` times in a row is
+/// skipped for `cooldown`, unless every gateway is being skipped.
+///
+/// [`PaymentGateway::process_payment`] reports the processing gateway's ID in
+/// the response's `gateway_id`; store it on the payment's
+/// [`PaymentIntent::gateway_id`] so refunds reach the same gateway.
+pub struct FailoverGateway {
+    gateways: Vec<(String, Box<dyn PaymentGateway + Send + Sync>)>,
+    config: FailoverConfig,
+    health: Mutex<Vec<GatewayHealth>>,
+}
+
+impl FailoverGateway {
+    /// Create an empty failover gateway
+    pub fn new(config: FailoverConfig) -> Self {
+        Self {
+            gateways: Vec::new(),
+            config,
+            health: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Append a gateway, tried after all gateways added before it
+    pub fn with_gateway(
+        mut self,
+        gateway_id: impl Into<String>,
+        gateway: Box<dyn PaymentGateway + Send + Sync>,
+    ) -> Self {
+        self.gateways.push((gateway_id.into(), gateway));
+        self.health
+            .get_mut()
+            .expect("gateway health lock poisoned")
+            .push(GatewayHealth::default());
+        self
+    }
+
+    /// Whether a gateway is currently being skipped
+    pub fn is_skipped(&self, gateway_id: &str) -> bool {
+        let Some(index) = self.gateways.iter().position(|(id, _)| id == gateway_id) else {
+            return false;
+        };
+        let health = self.health.lock().expect("gateway health lock poisoned");
+        health[index]
+            .skipped_until
+            .is_some_and(|until| until > Instant::now())
+    }
+
+    /// Indices of the gateways to try, in order
+    fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().expect("gateway health lock poisoned");
+        let available: Vec<usize> = (0..self.gateways.len())
+            .filter(|&i| !health[i].skipped_until.is_some_and(|until| until > now))
+            .collect();
+
+        if available.is_empty() {
+            (0..self.gateways.len()).collect()
+        } else {
+            available
+        }
+    }
+
+    fn record_success(&self, index: usize) {
+        let mut health = self.health.lock().expect("gateway health lock poisoned");
+        health[index] = GatewayHealth::default();
+    }
+
+    fn record_failure(&self, index: usize) {
+        let mut health = self.health.lock().expect("gateway health lock poisoned");
+        let entry = &mut health[index];
+        entry.consecutive_failures += 1;
+        if entry.consecutive_failures >= self.config.failure_threshold {
+            entry.skipped_until = Some(Instant::now() + self.config.cooldown);
+        }
+    }
+
+    /// Process a payment, reporting which gateway processed it
+    pub async fn process_payment_with_failover(
+        &self,
+        payment: &PaymentIntent,
+        method: &PaymentMethod,
+    ) -> PaymentResult<FailoverOutcome> {
+        let mut failed_over = Vec::new();
+        let mut last_error = None;
+
+        for index in self.candidates() {
+            let (gateway_id, gateway) = &self.gateways[index];
+            match gateway.process_payment(payment, method).await {
+                Ok(response) => {
+                    self.record_success(index);
+                    return Ok(FailoverOutcome {
+                        response,
+                        processed_by: gateway_id.clone(),
+                        failed_over,
+                    });
+                }
+                Err(e) if e.is_unprocessed() => {
+                    tracing::warn!("Gateway {} failed, failing over: {}", gateway_id, e);
+                    self.record_failure(index);
+                    failed_over.push((gateway_id.clone(), e.code()));
+                    last_error = Some(e);
+                }
+                // The payment may have been charged before the gateway failed
+                Err(e) if e.is_retryable() => {
+                    self.record_failure(index);
+                    match gateway.find_payment(&payment.idempotency_key()).await {
+                        Ok(Some(response)) => {
+                            tracing::warn!("Gateway {} failed after charging payment {}: {}", gateway_id, payment.id, e);
+                            return Ok(FailoverOutcome {
+                                response,
+                                processed_by: gateway_id.clone(),
+                                failed_over,
+                            });
+                        }
+                        Ok(None) => {
+                            tracing::warn!("Gateway {} failed, failing over: {}", gateway_id, e);
+                            failed_over.push((gateway_id.clone(), e.code()));
+                            last_error = Some(e);
+                        }
+                        Err(lookup_error) => {
+                            tracing::warn!(
+                                "Gateway {} failed and cannot confirm whether payment {} was charged: {}",
+                                gateway_id, payment.id, lookup_error
+                            );
+                            return Err(e);
+                        }
+                    }
+                }
+                // Declines and validation errors would fail on any gateway
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| PaymentError::ConfigurationError {
+            message: "No payment gateways configured for failover".to_string(),
+        }))
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for FailoverGateway {
+    async fn process_payment(
+        &self,
+        payment: &PaymentIntent,
+        method: &PaymentMethod,
+    ) -> PaymentResult<GatewayResponse> {
+        // Report the gateway under the ID refunds are routed by
+        self.process_payment_with_failover(payment, method)
+            .await
+            .map(|outcome| GatewayResponse {
+                gateway_id: outcome.processed_by,
+                ..outcome.response
+            })
+    }
+
+    /// Refunds go to the gateway recorded on the payment
+    ///
+    /// With a single gateway there is nothing to choose. Otherwise the payment
+    /// must record the gateway that processed it.
+    async fn process_refund(
+        &self,
+        payment: &PaymentIntent,
+        refund: &Refund,
+    ) -> PaymentResult<GatewayResponse> {
+        let gateway = match (payment.gateway_id.as_deref(), self.gateways.as_slice()) {
+            (_, []) => {
+                return Err(PaymentError::ConfigurationError {
+                    message: "No payment gateways configured for failover".to_string(),
+                })
+            }
+            (Some(id), gateways) => gateways
+                .iter()
+                .find(|(gid, _)| *gid == id)
+                .map(|(_, gateway)| gateway)
+                .ok_or_else(|| PaymentError::ConfigurationError {
+                    message: format!("Gateway {} is no longer configured", id),
+                })?,
+            (None, [(_, only)]) => only,
+            (None, _) => {
+                return Err(PaymentError::RefundError {
+                    message: format!("No processing gateway recorded for payment {}", payment.id),
+                })
+            }
+        };
+
+        gateway.process_refund(payment, refund).await
+    }
+
+    async fn health_check(&self) -> PaymentResult<()> {
+        let mut last_error = None;
+        for (_, gateway) in &self.gateways {
+            match gateway.health_check().await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| PaymentError::ConfigurationError {
+            message: "No payment gateways configured for failover".to_string(),
+        }))
+    }
+}
+
 /// Gateway factory for creating gateway instances
 pub struct GatewayFactory;
 
@@ -543,6 +913,97 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_failover_gateway() {
+        let gateway = FailoverGateway::new(FailoverConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(60),
+        })
+        .with_gateway("primary", Box::new(MockPaymentGateway::new("primary".to_string()).with_failure(true)))
+        .with_gateway("secondary", Box::new(MockPaymentGateway::new("secondary".to_string())));
+
+        let amount = Amount::new(Decimal::new(10000, 2), Currency::Fiat(FiatCurrency::USD)).expect("Failed to create amount");
+        let payment = PaymentIntent::new(amount, "Test payment".to_string());
+
+        let method = PaymentMethod::CreditCard {
+            last_four: "4242".to_string(),
+            brand: "Visa".to_string(),
+            exp_month: 12,
+            exp_year: 2025,
+            holder_name: "Test User".to_string(),
+        };
+
+        // Primary outage fails over to the secondary
+        let outcome = gateway.process_payment_with_failover(&payment, &method).await.unwrap();
+        assert_eq!(outcome.processed_by, "secondary");
+        assert_eq!(outcome.response.gateway_id, "secondary");
+        assert_eq!(outcome.failed_over, vec![("primary".to_string(), "GATEWAY_ERROR")]);
+
+        // A persistently failing primary is skipped
+        assert!(!gateway.is_skipped("primary"));
+        let outcome = gateway.process_payment_with_failover(&payment, &method).await.unwrap();
+        assert_eq!(outcome.failed_over.len(), 1);
+        assert!(gateway.is_skipped("primary"));
+        let outcome = gateway.process_payment_with_failover(&payment, &method).await.unwrap();
+        assert!(outcome.failed_over.is_empty());
+
+        // Declines are not retried against the next gateway
+        let gateway = FailoverGateway::new(FailoverConfig::default())
+            .with_gateway("primary", Box::new(MockPaymentGateway::new("primary".to_string()).with_decline(true)))
+            .with_gateway("secondary", Box::new(MockPaymentGateway::new("secondary".to_string())));
+
+        let result = gateway.process_payment(&payment, &method).await;
+        assert!(matches!(result, Err(PaymentError::ProcessingFailed { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_failover_gateway_does_not_charge_twice() {
+        // The primary charges the payment but the response is lost
+        let gateway = FailoverGateway::new(FailoverConfig::default())
+            .with_gateway("primary", Box::new(MockPaymentGateway::new("primary".to_string()).with_failure_after_charge(true)))
+            .with_gateway("secondary", Box::new(MockPaymentGateway::new("secondary".to_string())));
+
+        let amount = Amount::new(Decimal::new(10000, 2), Currency::Fiat(FiatCurrency::USD)).expect("Failed to create amount");
+        let payment = PaymentIntent::new(amount, "Test payment".to_string());
+
+        let method = PaymentMethod::CreditCard {
+            last_four: "4242".to_string(),
+            brand: "Visa".to_string(),
+            exp_month: 12,
+            exp_year: 2025,
+            holder_name: "Test User".to_string(),
+        };
+
+        let outcome = gateway.process_payment_with_failover(&payment, &method).await.unwrap();
+        assert_eq!(outcome.processed_by, "primary");
+        assert_eq!(outcome.response.gateway_id, "primary");
+        assert!(outcome.failed_over.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failover_gateway_refunds_recorded_gateway() {
+        let gateway = FailoverGateway::new(FailoverConfig::default())
+            .with_gateway("primary", Box::new(MockPaymentGateway::new("primary".to_string())))
+            .with_gateway("secondary", Box::new(MockPaymentGateway::new("secondary".to_string())));
+
+        let amount = Amount::new(Decimal::new(10000, 2), Currency::Fiat(FiatCurrency::USD)).expect("Failed to create amount");
+        let mut payment = PaymentIntent::new(amount, "Test payment".to_string());
+        let refund_amount = Amount::new(Decimal::new(1000, 2), Currency::Fiat(FiatCurrency::USD)).expect("Failed to create amount");
+        let refund = Refund::new(payment.id, Uuid::new_v4(), refund_amount, Some("Test refund".to_string()));
+
+        // Without a recorded gateway a refund cannot pick between gateways
+        let result = gateway.process_refund(&payment, &refund).await;
+        assert!(matches!(result, Err(PaymentError::RefundError { .. })));
+
+        payment.gateway_id = Some("secondary".to_string());
+        let response = gateway.process_refund(&payment, &refund).await.unwrap();
+        assert_eq!(response.gateway_id, "secondary");
+
+        payment.gateway_id = Some("retired".to_string());
+        let result = gateway.process_refund(&payment, &refund).await;
+        assert!(matches!(result, Err(PaymentError::ConfigurationError { .. })));
+    }
+
     #[test]
     fn test_gateway_factory() {
         let config = GatewayConfig {
@@ -48,7 +48,9 @@
 //! - [`processor`]: Main payment processing engine
 //! - [`gateway`]: Payment gateway abstractions and implementations
 //! - [`builder`]: Builder pattern for easy configuration
+//! - [`settlement`]: Settlement reporting and reconciliation
 //! - [`storage`]: Data persistence layer
+//! - [`vault`]: Tokenization of card and account details
 //! - [`substrate_integration`]: Blockchain payment processing
 //!
 //! ## Features
@@ -80,9 +82,11 @@ pub mod builder;
 pub mod error;
 pub mod gateway;
 pub mod processor;
+pub mod settlement;
 pub mod storage;
 pub mod substrate_integration;
 pub mod types;
+pub mod vault;
 
 // Zero-knowledge proof and privacy modules
 // Simplified ZK proof implementation for TDD
@@ -103,18 +107,25 @@ pub mod wasm_pwa;
 // Re-export commonly used types for convenience
 pub use builder::{PaymentManager, PaymentManagerBuilder};
 pub use error::{PaymentError, PaymentResult};
-pub use gateway::{GatewayConfig, PaymentGateway};
+pub use gateway::{FailoverConfig, FailoverGateway, GatewayConfig, PaymentGateway};
 pub use processor::{PaymentProcessor, ProcessorConfig, RetryConfig, RiskEngine};
+pub use settlement::{
+    LineItemKind, ReconciliationIssue, SettlementLineItem, SettlementReport, SettlementTotals,
+};
 pub use storage::MemoryPaymentStorage;
 pub use types::{
     Amount, Currency, Customer, FiatCurrency, PaymentIntent, PaymentMethod, PaymentStatus,
     Transaction, TransactionType,
 };
+pub use vault::{
+    BankAccountDetails, CardDetails, PaymentSource, PaymentToken, PaymentVault,
+    SensitivePaymentMethod,
+};
 
 // Re-export privacy and ZKP components
 pub use zkp::{
-    AnonymousSubscription, SubscriptionProof, SubscriptionTier, VerificationRequest,
-    VerificationResult, ZKProofEngine,
+    AnonymousSubscription, NonRevocationWitness, RevocationAccumulator, RevokedLeaf,
+    SubscriptionProof, SubscriptionTier, VerificationRequest, VerificationResult, ZKProofEngine,
 };
 
 pub use did_integration::{
@@ -203,6 +214,7 @@ impl From<PaymentError> for SynapsedError {
             PaymentError::InvalidAmount { message } => SynapsedError::InvalidInput(message),
             PaymentError::ProcessingFailed { message, .. } => SynapsedError::Payment(message),
             PaymentError::GatewayError { message, .. } => SynapsedError::Payment(message),
+            PaymentError::GatewayUnavailable { message, .. } => SynapsedError::Network(message),
             PaymentError::InsufficientFunds { requested, available } => {
                 SynapsedError::Payment(format!("Insufficient funds: requested {}, available {}", requested, available))
             },
@@ -1,14 +1,17 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{info, error};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::error::{PaymentError, PaymentResult};
 use crate::gateway::{PaymentGateway, GatewayConfig};
+use crate::settlement::{self, SettlementReport};
 use crate::types::{
     Amount, Currency, Customer, PaymentConfig, PaymentIntent, PaymentMethod, 
     PaymentStatus, Refund, RiskAssessment, RiskLevel, Transaction, TransactionType,
@@ -21,6 +24,8 @@ pub struct PaymentProcessor {
     risk_engine: Arc<dyn RiskEngine + Send + Sync>,
     storage: Arc<dyn PaymentStorage + Send + Sync>,
     active_payments: Arc<RwLock<HashMap<Uuid, PaymentSession>>>,
+    /// Refund amounts reserved by in-flight refunds, keyed by payment
+    pending_refunds: Arc<Mutex<HashMap<Uuid, Decimal>>>,
 }
 
 /// Payment session tracking
@@ -93,6 +98,20 @@ pub trait PaymentStorage {
 
     /// Get customer
     async fn get_customer(&self, customer_id: &str) -> PaymentResult<Option<Customer>>;
+
+    /// List transactions captured within a period, or created if not captured
+    async fn list_transactions(&self, _period: &Range<DateTime<Utc>>) -> PaymentResult<Vec<Transaction>> {
+        Err(PaymentError::configuration_error(
+            "Storage backend does not support listing transactions",
+        ))
+    }
+
+    /// List refunds processed within a period, or created if not processed
+    async fn list_refunds(&self, _period: &Range<DateTime<Utc>>) -> PaymentResult<Vec<Refund>> {
+        Err(PaymentError::configuration_error(
+            "Storage backend does not support listing refunds",
+        ))
+    }
 }
 
 impl PaymentProcessor {
@@ -108,6 +127,7 @@ impl PaymentProcessor {
             risk_engine,
             storage,
             active_payments: Arc::new(RwLock::new(HashMap::new())),
+            pending_refunds: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -195,9 +215,9 @@ impl PaymentProcessor {
         // Update payment with method and set processing
         payment.payment_method = Some(payment_method.clone());
         payment.status = PaymentStatus::Processing;
-        self.storage
-            .update_payment_status(payment_id, PaymentStatus::Processing)
-            .await?;
+        payment.updated_at = Utc::now();
+        // Persist the method too, refunds are routed by it
+        self.storage.store_payment(&payment).await?;
 
         // Select appropriate gateway
         let gateway_id = self.select_gateway(&payment_method, &payment.amount.currency)?;
@@ -231,6 +251,7 @@ impl PaymentProcessor {
             payment.amount.clone(),
         );
         transaction.payment_method = payment_method.clone();
+        transaction.gateway = Some(gateway_id.clone());
         transaction.user_id = payment.customer_id.clone().unwrap_or_default();
         transaction.description = payment.description.clone();
 
@@ -238,13 +259,14 @@ impl PaymentProcessor {
         match gateway.process_payment(&payment, &payment_method).await {
             Ok(gateway_response) => {
                 transaction.gateway_transaction_id = Some(gateway_response.transaction_id.clone());
-                transaction.gateway_response = Some(gateway_response);
                 transaction.mark_completed();
 
-                // Update payment status
-                self.storage
-                    .update_payment_status(payment_id, PaymentStatus::Completed)
-                    .await?;
+                // Record which gateway took the payment, refunds go back to it
+                payment.gateway_id = Some(gateway_response.gateway_id.clone());
+                payment.status = PaymentStatus::Completed;
+                payment.updated_at = Utc::now();
+                self.storage.store_payment(&payment).await?;
+                transaction.gateway_response = Some(gateway_response);
 
                 info!(
                     payment_id = %payment_id,
@@ -291,12 +313,52 @@ impl PaymentProcessor {
     }
 
     /// Refund a payment
+    ///
+    /// Partial refunds are allowed until their cumulative total reaches the
+    /// captured amount. The refund amount is reserved before the gateway is
+    /// called, so concurrent refunds of the same payment cannot over-refund.
     pub async fn refund_payment(
         &self,
         payment_id: Uuid,
         amount: Option<Amount>,
         reason: Option<String>,
     ) -> PaymentResult<Refund> {
+        let (payment, refund_amount) = self.reserve_refund(payment_id, amount).await?;
+
+        // Create refund record
+        let mut refund = Refund::new(
+            payment_id, 
+            Uuid::new_v4(), // Generate a new transaction ID for the refund
+            refund_amount, 
+            reason
+        );
+        refund.status = PaymentStatus::Processing;
+
+        let result = self.execute_refund(&payment, &mut refund).await;
+        self.settle_refund(payment_id, refund.amount.value, result.is_ok())
+            .await?;
+
+        if let Err(e) = result {
+            if refund.status == PaymentStatus::Failed {
+                self.storage.store_refund(&refund).await?;
+            }
+            return Err(e);
+        }
+
+        // Store refund
+        self.storage.store_refund(&refund).await?;
+
+        Ok(refund)
+    }
+
+    /// Validate a refund request and reserve its amount against the payment
+    async fn reserve_refund(
+        &self,
+        payment_id: Uuid,
+        amount: Option<Amount>,
+    ) -> PaymentResult<(PaymentIntent, Amount)> {
+        let mut pending = self.pending_refunds.lock().await;
+
         // Get original payment
         let payment = self.storage.get_payment(payment_id).await?;
 
@@ -307,8 +369,14 @@ impl PaymentProcessor {
             });
         }
 
+        let reserved = pending.get(&payment_id).copied().unwrap_or(Decimal::ZERO);
+        let available = (payment.refundable_amount() - reserved).max(Decimal::ZERO);
+
         // Determine refund amount
-        let refund_amount = amount.unwrap_or(payment.amount.clone());
+        let refund_amount = match amount {
+            Some(amount) => amount,
+            None => Amount::new(available, payment.amount.currency.clone())?,
+        };
 
         // Validate refund amount
         if refund_amount.currency != payment.amount.currency {
@@ -317,70 +385,137 @@ impl PaymentProcessor {
             });
         }
 
-        if refund_amount.value > payment.amount.value {
+        if !refund_amount.is_positive() {
             return Err(PaymentError::RefundError {
-                message: "Refund amount cannot exceed payment amount".to_string(),
+                message: "Payment has no refundable amount remaining".to_string(),
             });
         }
 
-        // Create refund record
-        let mut refund = Refund::new(
-            payment_id, 
-            Uuid::new_v4(), // Generate a new transaction ID for the refund
-            refund_amount, 
-            reason
-        );
-        refund.status = PaymentStatus::Processing;
+        if refund_amount.value > available {
+            return Err(PaymentError::RefundError {
+                message: format!(
+                    "Refund amount {} exceeds refundable amount {} (captured {}, refunded {}, pending {})",
+                    refund_amount.value,
+                    available,
+                    payment.amount.value,
+                    payment.refunded_amount,
+                    reserved
+                ),
+            });
+        }
 
-        // Get gateway from payment method
-        if let Some(payment_method) = &payment.payment_method {
-            let gateway_id = self.select_gateway(payment_method, &payment.amount.currency)?;
-            let gateway = self
-                .gateways
-                .get(&gateway_id)
-                .ok_or_else(|| PaymentError::ConfigurationError {
-                    message: format!("Gateway not found: {}", gateway_id),
-                })?;
-
-            // Process refund through gateway
-            match gateway.process_refund(&payment, &refund).await {
-                Ok(gateway_response) => {
-                    refund.gateway_refund_id = Some(gateway_response.transaction_id);
-                    refund.status = PaymentStatus::Completed;
-                    refund.processed_at = Some(Utc::now());
-
-                    info!(
-                        payment_id = %payment_id,
-                        refund_id = %refund.id,
-                        amount = %refund.amount,
-                        "Refund processed successfully"
-                    );
-                }
-                Err(e) => {
-                    refund.status = PaymentStatus::Failed;
-                    refund.processed_at = Some(Utc::now());
-
-                    error!(
-                        payment_id = %payment_id,
-                        refund_id = %refund.id,
-                        error = %e,
-                        "Refund processing failed"
-                    );
-
-                    self.storage.store_refund(&refund).await?;
-                    return Err(e);
-                }
+        *pending.entry(payment_id).or_insert(Decimal::ZERO) += refund_amount.value;
+
+        Ok((payment, refund_amount))
+    }
+
+    /// Release a refund reservation, recording it on the payment if it succeeded
+    async fn settle_refund(
+        &self,
+        payment_id: Uuid,
+        amount: Decimal,
+        succeeded: bool,
+    ) -> PaymentResult<()> {
+        let mut pending = self.pending_refunds.lock().await;
+
+        if let Some(reserved) = pending.get_mut(&payment_id) {
+            *reserved -= amount;
+            if *reserved <= Decimal::ZERO {
+                pending.remove(&payment_id);
             }
-        } else {
-            return Err(PaymentError::RefundError {
+        }
+
+        if succeeded {
+            let mut payment = self.storage.get_payment(payment_id).await?;
+            payment.refunded_amount += amount;
+            payment.updated_at = Utc::now();
+            self.storage.store_payment(&payment).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Send a refund to the payment's gateway and update the refund record
+    async fn execute_refund(
+        &self,
+        payment: &PaymentIntent,
+        refund: &mut Refund,
+    ) -> PaymentResult<()> {
+        let payment_method = payment.payment_method.as_ref().ok_or_else(|| {
+            PaymentError::RefundError {
                 message: "Payment method not found".to_string(),
-            });
+            }
+        })?;
+
+        // Get gateway from payment method
+        let gateway_id = self.select_gateway(payment_method, &payment.amount.currency)?;
+        let gateway = self
+            .gateways
+            .get(&gateway_id)
+            .ok_or_else(|| PaymentError::ConfigurationError {
+                message: format!("Gateway not found: {}", gateway_id),
+            })?;
+
+        // Process refund through gateway
+        match gateway.process_refund(payment, refund).await {
+            Ok(gateway_response) => {
+                refund.gateway_refund_id = Some(gateway_response.transaction_id);
+                refund.status = PaymentStatus::Completed;
+                refund.processed_at = Some(Utc::now());
+
+                info!(
+                    payment_id = %payment.id,
+                    refund_id = %refund.id,
+                    amount = %refund.amount,
+                    "Refund processed successfully"
+                );
+
+                Ok(())
+            }
+            Err(e) => {
+                refund.status = PaymentStatus::Failed;
+                refund.processed_at = Some(Utc::now());
+
+                error!(
+                    payment_id = %payment.id,
+                    refund_id = %refund.id,
+                    error = %e,
+                    "Refund processing failed"
+                );
+
+                Err(e)
+            }
         }
+    }
 
-        // Store refund
-        self.storage.store_refund(&refund).await?;
+    /// Aggregate the payments and refunds of a period into a settlement report
+    ///
+    /// Refunds processed in the period are attributed to the gateway of the
+    /// payment they refund, even if that payment was captured earlier.
+    pub async fn settlement_report(
+        &self,
+        period: Range<DateTime<Utc>>,
+    ) -> PaymentResult<SettlementReport> {
+        let transactions = self.storage.list_transactions(&period).await?;
+        let refunds = self.storage.list_refunds(&period).await?;
+
+        let mut originals = HashMap::new();
+        for refund in &refunds {
+            if originals.contains_key(&refund.payment_id) {
+                continue;
+            }
+            let captured = self
+                .storage
+                .get_payment_transactions(refund.payment_id)
+                .await?
+                .into_iter()
+                .find(settlement::is_captured);
+            if let Some(captured) = captured {
+                originals.insert(refund.payment_id, captured);
+            }
+        }
 
-        Ok(refund)
+        Ok(SettlementReport::compile(period, &transactions, &refunds, &originals))
     }
 
     /// Get payment status
@@ -669,6 +804,57 @@ mod tests {
         }
     }
 
+    fn refund_test_processor() -> (PaymentProcessor, Arc<crate::storage::MemoryPaymentStorage>) {
+        let config = ProcessorConfig {
+            payment_config: PaymentConfig {
+                merchant_id: "test_merchant".to_string(),
+                supported_currencies: vec![Currency::Fiat(FiatCurrency::USD)],
+                supported_payment_methods: vec!["card".to_string()],
+                webhook_url: None,
+                return_url: None,
+                cancel_url: None,
+                auto_capture: true,
+                capture_delay_hours: None,
+                max_retry_attempts: 3,
+            },
+            gateway_configs: HashMap::new(),
+            risk_threshold: 70,
+            retry_config: RetryConfig::default(),
+        };
+
+        let storage = Arc::new(crate::storage::MemoryPaymentStorage::new());
+        let mut processor = PaymentProcessor::new(
+            config,
+            Arc::new(BasicRiskEngine::new(70)),
+            storage.clone(),
+        );
+        processor.register_gateway("mock".to_string(), Arc::new(MockGateway));
+
+        (processor, storage)
+    }
+
+    async fn store_captured_payment(
+        storage: &crate::storage::MemoryPaymentStorage,
+        value: Decimal,
+    ) -> Uuid {
+        let amount = Amount::new(value, Currency::Fiat(FiatCurrency::USD)).unwrap();
+        let mut payment = PaymentIntent::new(amount, "Captured payment".to_string());
+        payment.status = PaymentStatus::Completed;
+        payment.payment_method = Some(PaymentMethod::CreditCard {
+            last_four: "4242".to_string(),
+            brand: "Visa".to_string(),
+            exp_month: 12,
+            exp_year: 2030,
+            holder_name: "Test User".to_string(),
+        });
+        storage.store_payment(&payment).await.unwrap();
+        payment.id
+    }
+
+    fn usd(value: Decimal) -> Option<Amount> {
+        Some(Amount::new(value, Currency::Fiat(FiatCurrency::USD)).unwrap())
+    }
+
     #[tokio::test]
     async fn test_payment_processor_creation() {
         let config = ProcessorConfig {
@@ -728,4 +914,64 @@ mod tests {
         assert_eq!(intent.description, "Test payment");
         assert_eq!(intent.status, PaymentStatus::Pending);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_partial_refunds_cannot_exceed_captured_amount() {
+        let (processor, storage) = refund_test_processor();
+        let payment_id = store_captured_payment(&storage, Decimal::new(10000, 2)).await;
+
+        processor
+            .refund_payment(payment_id, usd(Decimal::new(6000, 2)), None)
+            .await
+            .expect("first partial refund should succeed");
+        processor
+            .refund_payment(payment_id, usd(Decimal::new(4000, 2)), None)
+            .await
+            .expect("second partial refund should succeed");
+
+        let payment = processor.get_payment(payment_id).await.unwrap();
+        assert_eq!(payment.refunded_amount, Decimal::new(10000, 2));
+        assert_eq!(payment.refundable_amount(), Decimal::ZERO);
+
+        let result = processor
+            .refund_payment(payment_id, usd(Decimal::new(1, 2)), None)
+            .await;
+        assert!(matches!(result, Err(PaymentError::RefundError { .. })));
+
+        let result = processor.refund_payment(payment_id, None, None).await;
+        assert!(matches!(result, Err(PaymentError::RefundError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_double_refund_only_one_succeeds() {
+        let (processor, storage) = refund_test_processor();
+        let processor = Arc::new(processor);
+        let payment_id = store_captured_payment(&storage, Decimal::new(5000, 2)).await;
+
+        let first = {
+            let processor = processor.clone();
+            tokio::spawn(async move {
+                processor
+                    .refund_payment(payment_id, usd(Decimal::new(5000, 2)), None)
+                    .await
+            })
+        };
+        let second = {
+            let processor = processor.clone();
+            tokio::spawn(async move {
+                processor
+                    .refund_payment(payment_id, usd(Decimal::new(5000, 2)), None)
+                    .await
+            })
+        };
+
+        let results = [first.await.unwrap(), second.await.unwrap()];
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, Err(PaymentError::RefundError { .. }))));
+
+        let payment = processor.get_payment(payment_id).await.unwrap();
+        assert_eq!(payment.refunded_amount, Decimal::new(5000, 2));
+    }
+}
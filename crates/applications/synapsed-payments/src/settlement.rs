@@ -0,0 +1,320 @@
+//! Settlement reporting for reconciliation against processor payouts
+//!
+//! A [`SettlementReport`] covers a period and aggregates captured payments,
+//! refunds and fees into net settlement figures, per currency and per gateway.
+//! Amounts are only ever summed within their own currency. Every payment and
+//! refund in the period is also listed as a line item, together with any
+//! reason it does not reconcile.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use uuid::Uuid;
+
+use crate::types::{Currency, PaymentStatus, Refund, Transaction, TransactionStatus};
+
+/// Gateway name used when a line item's gateway is not known
+pub const UNKNOWN_GATEWAY: &str = "unknown";
+
+/// Kind of a settlement line item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LineItemKind {
+    /// Captured payment
+    Payment,
+    /// Completed refund
+    Refund,
+}
+
+/// Reason a line item does not reconcile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReconciliationIssue {
+    /// No gateway is recorded for the payment
+    MissingGateway,
+    /// The gateway's reference for the transaction or refund is missing
+    MissingGatewayReference,
+    /// The fee is charged in a different currency than the transaction
+    FeeCurrencyMismatch {
+        /// Currency of the transaction
+        expected: Currency,
+        /// Currency of the fee
+        found: Currency,
+    },
+    /// The payment a refund belongs to has no captured transaction
+    OriginalPaymentNotFound,
+    /// A refund is in a different currency than the payment it refunds
+    RefundCurrencyMismatch {
+        /// Currency of the original payment
+        expected: Currency,
+        /// Currency of the refund
+        found: Currency,
+    },
+}
+
+impl fmt::Display for ReconciliationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReconciliationIssue::MissingGateway => write!(f, "no gateway recorded"),
+            ReconciliationIssue::MissingGatewayReference => write!(f, "no gateway reference"),
+            ReconciliationIssue::FeeCurrencyMismatch { expected, found } => {
+                write!(f, "fee in {} for a {} transaction", found, expected)
+            }
+            ReconciliationIssue::OriginalPaymentNotFound => write!(f, "original payment not found"),
+            ReconciliationIssue::RefundCurrencyMismatch { expected, found } => {
+                write!(f, "refund in {} for a {} payment", found, expected)
+            }
+        }
+    }
+}
+
+/// A single payment or refund in a settlement report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementLineItem {
+    /// Whether this is a payment or a refund
+    pub kind: LineItemKind,
+    /// Transaction or refund ID
+    pub id: Uuid,
+    /// Payment the item belongs to
+    pub payment_id: Uuid,
+    /// Gateway that settled the item
+    pub gateway: String,
+    /// Gateway's reference for the item
+    pub gateway_reference: Option<String>,
+    /// Currency of the item
+    pub currency: Currency,
+    /// Gross amount
+    pub amount: Decimal,
+    /// Fee charged in the item's currency
+    pub fee: Decimal,
+    /// Effect on the settlement: positive for payments, negative for refunds
+    pub net: Decimal,
+    /// When the payment was captured or the refund processed
+    pub occurred_at: DateTime<Utc>,
+    /// Reasons the item does not reconcile
+    pub issues: Vec<ReconciliationIssue>,
+}
+
+impl SettlementLineItem {
+    /// Whether the item reconciles
+    pub fn is_reconciled(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Aggregated figures for one currency
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementTotals {
+    /// Sum of captured payments
+    pub gross: Decimal,
+    /// Sum of refunds
+    pub refunds: Decimal,
+    /// Sum of fees
+    pub fees: Decimal,
+    /// Number of payments
+    pub payment_count: usize,
+    /// Number of refunds
+    pub refund_count: usize,
+}
+
+impl SettlementTotals {
+    /// Amount expected to be paid out: gross less refunds and fees
+    pub fn net(&self) -> Decimal {
+        self.gross - self.refunds - self.fees
+    }
+
+    fn add(&mut self, item: &SettlementLineItem) {
+        match item.kind {
+            LineItemKind::Payment => {
+                self.gross += item.amount;
+                self.payment_count += 1;
+            }
+            LineItemKind::Refund => {
+                self.refunds += item.amount;
+                self.refund_count += 1;
+            }
+        }
+        self.fees += item.fee;
+    }
+}
+
+/// Net settlement figures for a period
+#[derive(Debug, Clone)]
+pub struct SettlementReport {
+    /// Start of the period, inclusive
+    pub period_start: DateTime<Utc>,
+    /// End of the period, exclusive
+    pub period_end: DateTime<Utc>,
+    /// When the report was generated
+    pub generated_at: DateTime<Utc>,
+    /// Totals per currency
+    pub currencies: HashMap<Currency, SettlementTotals>,
+    /// Totals per gateway, then per currency
+    pub gateways: HashMap<String, HashMap<Currency, SettlementTotals>>,
+    /// Every payment and refund in the period, oldest first
+    pub line_items: Vec<SettlementLineItem>,
+}
+
+impl SettlementReport {
+    /// Build a report from the transactions and refunds of a period
+    ///
+    /// `originals` maps payment IDs to their captured transaction and is used
+    /// to attribute refunds to a gateway. Transactions and refunds outside the
+    /// period, or not yet settled, are ignored.
+    pub fn compile(
+        period: Range<DateTime<Utc>>,
+        transactions: &[Transaction],
+        refunds: &[Refund],
+        originals: &HashMap<Uuid, Transaction>,
+    ) -> Self {
+        let mut line_items: Vec<SettlementLineItem> = transactions
+            .iter()
+            .filter(|tx| is_captured(tx) && period.contains(&capture_time(tx)))
+            .map(payment_line_item)
+            .collect();
+
+        line_items.extend(
+            refunds
+                .iter()
+                .filter(|refund| refund.status == PaymentStatus::Completed)
+                .filter(|refund| period.contains(&refund_time(refund)))
+                .map(|refund| refund_line_item(refund, originals.get(&refund.payment_id))),
+        );
+        line_items.sort_by_key(|item| item.occurred_at);
+
+        let mut currencies: HashMap<Currency, SettlementTotals> = HashMap::new();
+        let mut gateways: HashMap<String, HashMap<Currency, SettlementTotals>> = HashMap::new();
+        for item in &line_items {
+            currencies.entry(item.currency.clone()).or_default().add(item);
+            gateways
+                .entry(item.gateway.clone())
+                .or_default()
+                .entry(item.currency.clone())
+                .or_default()
+                .add(item);
+        }
+
+        Self {
+            period_start: period.start,
+            period_end: period.end,
+            generated_at: Utc::now(),
+            currencies,
+            gateways,
+            line_items,
+        }
+    }
+
+    /// Totals for a currency
+    pub fn totals(&self, currency: &Currency) -> Option<&SettlementTotals> {
+        self.currencies.get(currency)
+    }
+
+    /// Net settlement for a currency, zero if it had no activity
+    pub fn net(&self, currency: &Currency) -> Decimal {
+        self.totals(currency).map(SettlementTotals::net).unwrap_or_default()
+    }
+
+    /// Totals for a gateway in a currency
+    pub fn gateway_totals(&self, gateway: &str, currency: &Currency) -> Option<&SettlementTotals> {
+        self.gateways.get(gateway).and_then(|totals| totals.get(currency))
+    }
+
+    /// Line items that do not reconcile
+    pub fn unreconciled(&self) -> impl Iterator<Item = &SettlementLineItem> {
+        self.line_items.iter().filter(|item| !item.is_reconciled())
+    }
+}
+
+/// Whether a transaction captured funds
+pub(crate) fn is_captured(transaction: &Transaction) -> bool {
+    matches!(
+        transaction.status,
+        TransactionStatus::Completed | TransactionStatus::Refunding | TransactionStatus::Refunded
+    )
+}
+
+/// When a transaction captured funds, or was created if that wasn't recorded
+pub(crate) fn capture_time(transaction: &Transaction) -> DateTime<Utc> {
+    transaction.captured_at.unwrap_or(transaction.created_at)
+}
+
+/// When a refund was processed, or created if it hasn't been
+pub(crate) fn refund_time(refund: &Refund) -> DateTime<Utc> {
+    refund.processed_at.unwrap_or(refund.created_at)
+}
+
+fn payment_line_item(transaction: &Transaction) -> SettlementLineItem {
+    let mut issues = Vec::new();
+    let gateway = transaction.gateway.clone().unwrap_or_else(|| {
+        issues.push(ReconciliationIssue::MissingGateway);
+        UNKNOWN_GATEWAY.to_string()
+    });
+    if transaction.gateway_transaction_id.is_none() {
+        issues.push(ReconciliationIssue::MissingGatewayReference);
+    }
+
+    // A fee in another currency can't be netted against this transaction
+    let currency = transaction.amount.currency.clone();
+    let fee = match &transaction.fees {
+        Some(fee) if fee.currency == currency => fee.value,
+        Some(fee) => {
+            issues.push(ReconciliationIssue::FeeCurrencyMismatch {
+                expected: currency.clone(),
+                found: fee.currency.clone(),
+            });
+            Decimal::ZERO
+        }
+        None => Decimal::ZERO,
+    };
+
+    SettlementLineItem {
+        kind: LineItemKind::Payment,
+        id: transaction.id,
+        payment_id: transaction.payment_id,
+        gateway,
+        gateway_reference: transaction.gateway_transaction_id.clone(),
+        currency,
+        amount: transaction.amount.value,
+        fee,
+        net: transaction.amount.value - fee,
+        occurred_at: capture_time(transaction),
+        issues,
+    }
+}
+
+fn refund_line_item(refund: &Refund, original: Option<&Transaction>) -> SettlementLineItem {
+    let mut issues = Vec::new();
+    let gateway = match original {
+        Some(original) => {
+            if original.amount.currency != refund.amount.currency {
+                issues.push(ReconciliationIssue::RefundCurrencyMismatch {
+                    expected: original.amount.currency.clone(),
+                    found: refund.amount.currency.clone(),
+                });
+            }
+            original.gateway.clone().unwrap_or_else(|| UNKNOWN_GATEWAY.to_string())
+        }
+        None => {
+            issues.push(ReconciliationIssue::OriginalPaymentNotFound);
+            UNKNOWN_GATEWAY.to_string()
+        }
+    };
+    if refund.gateway_refund_id.is_none() {
+        issues.push(ReconciliationIssue::MissingGatewayReference);
+    }
+
+    SettlementLineItem {
+        kind: LineItemKind::Refund,
+        id: refund.id,
+        payment_id: refund.payment_id,
+        gateway,
+        gateway_reference: refund.gateway_refund_id.clone(),
+        currency: refund.amount.currency.clone(),
+        amount: refund.amount.value,
+        fee: Decimal::ZERO,
+        net: -refund.amount.value,
+        occurred_at: refund_time(refund),
+        issues,
+    }
+}
@@ -1,11 +1,14 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::error::{PaymentError, PaymentResult};
 use crate::processor::PaymentStorage;
+use crate::settlement;
 use crate::types::{Customer, PaymentIntent, PaymentMethod, PaymentStatus, Refund, Transaction};
 
 /// In-memory payment storage implementation for development/testing
@@ -121,6 +124,25 @@ impl PaymentStorage for MemoryPaymentStorage {
         let customers = self.customers.read().await;
         Ok(customers.get(customer_id).cloned())
     }
+
+    async fn list_transactions(&self, period: &Range<DateTime<Utc>>) -> PaymentResult<Vec<Transaction>> {
+        let transactions = self.transactions.read().await;
+        Ok(transactions
+            .values()
+            .flatten()
+            .filter(|transaction| period.contains(&settlement::capture_time(transaction)))
+            .cloned()
+            .collect())
+    }
+
+    async fn list_refunds(&self, period: &Range<DateTime<Utc>>) -> PaymentResult<Vec<Refund>> {
+        let refunds = self.refunds.read().await;
+        Ok(refunds
+            .values()
+            .filter(|refund| period.contains(&settlement::refund_time(refund)))
+            .cloned()
+            .collect())
+    }
 }
 
 impl Default for MemoryPaymentStorage {
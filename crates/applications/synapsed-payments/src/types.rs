@@ -400,6 +400,9 @@ pub struct Transaction {
     pub created_at: DateTime<Utc>,
     /// When transaction was last updated
     pub updated_at: DateTime<Utc>,
+    /// When transaction captured funds
+    #[serde(default)]
+    pub captured_at: Option<DateTime<Utc>>,
     /// When transaction expires (for pending transactions)
     pub expires_at: Option<DateTime<Utc>>,
     /// Gateway transaction ID
@@ -436,6 +439,7 @@ impl Transaction {
             metadata: HashMap::new(),
             created_at: now,
             updated_at: now,
+            captured_at: None,
             expires_at: None,
             gateway_transaction_id: None,
             gateway: None,
@@ -465,6 +469,7 @@ impl Transaction {
             metadata: HashMap::new(),
             created_at: now,
             updated_at: now,
+            captured_at: None,
             expires_at: None,
             gateway_transaction_id: None,
             gateway: None,
@@ -482,6 +487,7 @@ impl Transaction {
     pub fn mark_completed(&mut self) {
         self.status = TransactionStatus::Completed;
         self.updated_at = Utc::now();
+        self.captured_at.get_or_insert(self.updated_at);
     }
     
     /// Mark transaction as failed
@@ -498,8 +504,12 @@ impl Transaction {
             });
         }
         
+        let captured = status == TransactionStatus::Completed;
         self.status = status;
         self.updated_at = Utc::now();
+        if captured {
+            self.captured_at.get_or_insert(self.updated_at);
+        }
         Ok(())
     }
 
@@ -789,6 +799,12 @@ pub struct PaymentIntent {
     pub expires_at: Option<DateTime<Utc>>,
     /// Metadata
     pub metadata: HashMap<String, String>,
+    /// Cumulative amount refunded so far (same currency as `amount`)
+    #[serde(default)]
+    pub refunded_amount: Decimal,
+    /// Gateway that processed the payment, refunds must go back to it
+    #[serde(default)]
+    pub gateway_id: Option<String>,
 }
 
 impl PaymentIntent {
@@ -806,9 +822,24 @@ impl PaymentIntent {
             updated_at: now,
             expires_at: None,
             metadata: HashMap::new(),
+            refunded_amount: Decimal::ZERO,
+            gateway_id: None,
         }
     }
 
+    /// Key that identifies this payment to every gateway it is submitted to
+    ///
+    /// Resubmitting under the same key never charges twice, and lets a
+    /// gateway report whether it charged a payment whose response was lost.
+    pub fn idempotency_key(&self) -> String {
+        self.id.to_string()
+    }
+
+    /// Amount still available for refunds
+    pub fn refundable_amount(&self) -> Decimal {
+        (self.amount.value - self.refunded_amount).max(Decimal::ZERO)
+    }
+
     /// Check if payment intent is expired
     pub fn is_expired(&self) -> bool {
         if let Some(expires_at) = self.expires_at {
@@ -0,0 +1,492 @@
+//! Tokenization of sensitive payment method details
+//!
+//! Full card and account numbers are handed to the vault once, encrypted with a
+//! vault key and replaced by an opaque token. Payments are then made with the
+//! token; only the masked [`PaymentMethod`] (last four digits, brand, expiry)
+//! ever leaves the vault, so payment intents and transactions never hold the
+//! raw numbers.
+//!
+//! Tokens are scoped to the customer that created them and can be revoked.
+
+use std::fmt;
+use std::sync::Arc;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use synapsed_storage::backends::MemoryStorage;
+use synapsed_storage::config::MemoryConfig;
+use synapsed_storage::{Storage, StorageError};
+use uuid::Uuid;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::error::{PaymentError, PaymentResult};
+use crate::types::PaymentMethod;
+
+/// Prefix of every token issued by the vault
+pub const TOKEN_PREFIX: &str = "tok_";
+
+/// Storage key prefix of vault records
+const RECORD_PREFIX: &str = "payments/vault/";
+
+/// Length of the ChaCha20-Poly1305 nonce
+const NONCE_LEN: usize = 12;
+
+/// Full card details, supplied only when tokenizing
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct CardDetails {
+    /// Primary account number
+    pub number: String,
+    /// Card brand (Visa, MasterCard, etc.)
+    pub brand: String,
+    /// Expiration month (1-12)
+    pub exp_month: u8,
+    /// Expiration year
+    pub exp_year: u16,
+    /// Cardholder name
+    pub holder_name: String,
+}
+
+impl fmt::Debug for CardDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CardDetails")
+            .field("number", &mask(&self.number))
+            .field("brand", &self.brand)
+            .field("exp_month", &self.exp_month)
+            .field("exp_year", &self.exp_year)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Full bank account details, supplied only when tokenizing
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct BankAccountDetails {
+    /// Bank name
+    pub bank_name: String,
+    /// Account type (checking, savings)
+    pub account_type: String,
+    /// Full account number
+    pub account_number: String,
+    /// Bank routing number
+    pub routing_number: String,
+}
+
+impl fmt::Debug for BankAccountDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BankAccountDetails")
+            .field("bank_name", &self.bank_name)
+            .field("account_type", &self.account_type)
+            .field("account_number", &mask(&self.account_number))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Payment method details that must not be stored in the clear
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SensitivePaymentMethod {
+    /// Credit card
+    CreditCard(CardDetails),
+    /// Debit card
+    DebitCard(CardDetails),
+    /// Bank account for transfers
+    BankTransfer(BankAccountDetails),
+}
+
+impl SensitivePaymentMethod {
+    /// The masked payment method safe to store and pass to gateways
+    pub fn masked(&self) -> PaymentMethod {
+        match self {
+            SensitivePaymentMethod::CreditCard(card) => PaymentMethod::CreditCard {
+                last_four: last_four(&card.number),
+                brand: card.brand.clone(),
+                exp_month: card.exp_month,
+                exp_year: card.exp_year,
+                holder_name: card.holder_name.clone(),
+            },
+            SensitivePaymentMethod::DebitCard(card) => PaymentMethod::DebitCard {
+                last_four: last_four(&card.number),
+                brand: card.brand.clone(),
+                exp_month: card.exp_month,
+                exp_year: card.exp_year,
+                holder_name: card.holder_name.clone(),
+            },
+            SensitivePaymentMethod::BankTransfer(account) => PaymentMethod::BankTransfer {
+                bank_name: account.bank_name.clone(),
+                account_type: account.account_type.clone(),
+                last_four: last_four(&account.account_number),
+            },
+        }
+    }
+
+    fn validate(&self) -> PaymentResult<()> {
+        match self {
+            SensitivePaymentMethod::CreditCard(card) | SensitivePaymentMethod::DebitCard(card) => {
+                if !luhn_valid(&card.number) {
+                    return Err(PaymentError::validation_error("number", "Invalid card number"));
+                }
+                if !(1..=12).contains(&card.exp_month) {
+                    return Err(PaymentError::validation_error("exp_month", "Must be between 1 and 12"));
+                }
+            }
+            SensitivePaymentMethod::BankTransfer(account) => {
+                if account.account_number.len() < 4
+                    || !account.account_number.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return Err(PaymentError::validation_error(
+                        "account_number",
+                        "Must be at least 4 digits",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Opaque, reusable reference to a vaulted payment method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentToken {
+    /// Token passed in place of the payment method details
+    pub token: String,
+    /// Customer the token belongs to
+    pub customer_id: String,
+    /// Masked payment method for display
+    pub payment_method: PaymentMethod,
+    /// When the token was issued
+    pub created_at: DateTime<Utc>,
+}
+
+/// How a payment is funded: raw (already masked) details or a vault token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentSource {
+    /// Payment method details supplied directly
+    Method(PaymentMethod),
+    /// Token issued by the vault
+    Token(String),
+}
+
+impl From<PaymentMethod> for PaymentSource {
+    fn from(method: PaymentMethod) -> Self {
+        PaymentSource::Method(method)
+    }
+}
+
+impl From<&PaymentToken> for PaymentSource {
+    fn from(token: &PaymentToken) -> Self {
+        PaymentSource::Token(token.token.clone())
+    }
+}
+
+/// Encrypted record persisted for a token
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultRecord {
+    customer_id: String,
+    payment_method: PaymentMethod,
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Vec<u8>,
+    created_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl VaultRecord {
+    fn token(&self, token: &str) -> PaymentToken {
+        PaymentToken {
+            token: token.to_string(),
+            customer_id: self.customer_id.clone(),
+            payment_method: self.payment_method.clone(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Encrypted store of tokenized payment methods
+///
+/// Records are kept in a [`Storage`] backend, so a vault opened on persistent
+/// storage with the same key resolves tokens issued before a restart.
+pub struct PaymentVault {
+    cipher: ChaCha20Poly1305,
+    storage: Arc<dyn Storage<Error = StorageError>>,
+}
+
+impl PaymentVault {
+    /// Create an in-memory vault with a random key
+    pub fn new() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        let vault = Self::with_key(key);
+        key.zeroize();
+        vault
+    }
+
+    /// Create an in-memory vault with an existing key
+    pub fn with_key(key: [u8; 32]) -> Self {
+        Self::with_storage(key, Arc::new(MemoryStorage::new(MemoryConfig::default())))
+    }
+
+    /// Create a vault that keeps its records in `storage`
+    pub fn with_storage(mut key: [u8; 32], storage: Arc<dyn Storage<Error = StorageError>>) -> Self {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        key.zeroize();
+        Self { cipher, storage }
+    }
+
+    /// Encrypt a payment method and issue a token for it
+    pub async fn tokenize(
+        &self,
+        customer_id: &str,
+        payment_method: SensitivePaymentMethod,
+    ) -> PaymentResult<PaymentToken> {
+        if customer_id.is_empty() {
+            return Err(PaymentError::validation_error("customer_id", "Customer ID is required"));
+        }
+        payment_method.validate()?;
+
+        let token = format!("{}{}", TOKEN_PREFIX, Uuid::new_v4().simple());
+        let plaintext = Zeroizing::new(serde_json::to_vec(&payment_method)?);
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let aad = associated_data(&token, customer_id);
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: &plaintext, aad: &aad })
+            .map_err(|_| PaymentError::CryptographyError {
+                message: "Failed to encrypt payment method".to_string(),
+            })?;
+
+        let record = VaultRecord {
+            customer_id: customer_id.to_string(),
+            payment_method: payment_method.masked(),
+            nonce,
+            ciphertext,
+            created_at: Utc::now(),
+            revoked_at: None,
+        };
+        self.store(&token, &record).await?;
+
+        tracing::info!("Tokenized payment method for customer {}", customer_id);
+        Ok(record.token(&token))
+    }
+
+    /// Resolve a token to its masked payment method
+    ///
+    /// Fails if the token is unknown, revoked, belongs to another customer or
+    /// its record has been tampered with.
+    pub async fn resolve(&self, token: &str, customer_id: &str) -> PaymentResult<PaymentMethod> {
+        let method = self.reveal(token, customer_id).await?;
+        Ok(method.masked())
+    }
+
+    /// Decrypt the full payment method details behind a token
+    ///
+    /// Intended for gateway integrations that must forward the full number to
+    /// the card network. The result should be dropped as soon as it is sent.
+    pub async fn reveal(&self, token: &str, customer_id: &str) -> PaymentResult<SensitivePaymentMethod> {
+        let record = self.owned_record(token, customer_id).await?;
+        if record.revoked_at.is_some() {
+            return Err(PaymentError::InvalidPaymentMethod {
+                method: format!("revoked token {}", token),
+            });
+        }
+
+        let plaintext = self.decrypt(token, &record)?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    /// Revoke a token so it can no longer be used
+    ///
+    /// The encrypted details are erased; the token stays known so later uses
+    /// are reported as revoked rather than unknown.
+    pub async fn revoke(&self, token: &str, customer_id: &str) -> PaymentResult<()> {
+        let mut record = self.owned_record(token, customer_id).await?;
+        record.ciphertext.zeroize();
+        record.ciphertext.clear();
+        record.revoked_at.get_or_insert_with(Utc::now);
+        self.store(token, &record).await?;
+
+        tracing::info!("Revoked payment token {} for customer {}", token, customer_id);
+        Ok(())
+    }
+
+    /// Active tokens belonging to a customer
+    pub async fn tokens_for(&self, customer_id: &str) -> PaymentResult<Vec<PaymentToken>> {
+        let mut tokens = Vec::new();
+        for key in self.storage.list(RECORD_PREFIX.as_bytes()).await? {
+            let Some(token) = std::str::from_utf8(&key)
+                .ok()
+                .and_then(|key| key.strip_prefix(RECORD_PREFIX))
+            else {
+                continue;
+            };
+            if let Some(record) = self.load(token).await? {
+                if record.customer_id == customer_id && record.revoked_at.is_none() {
+                    tokens.push(record.token(token));
+                }
+            }
+        }
+        Ok(tokens)
+    }
+
+    async fn load(&self, token: &str) -> PaymentResult<Option<VaultRecord>> {
+        match self.storage.get(record_key(token).as_bytes()).await? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn store(&self, token: &str, record: &VaultRecord) -> PaymentResult<()> {
+        let bytes = serde_json::to_vec(record)?;
+        self.storage.put(record_key(token).as_bytes(), &bytes).await?;
+        Ok(())
+    }
+
+    async fn owned_record(&self, token: &str, customer_id: &str) -> PaymentResult<VaultRecord> {
+        // Report tokens of other customers as unknown so tokens can't be probed
+        self.load(token)
+            .await?
+            .filter(|record| record.customer_id == customer_id)
+            .ok_or_else(|| PaymentError::InvalidPaymentMethod {
+                method: format!("unknown token {}", token),
+            })
+    }
+
+    fn decrypt(&self, token: &str, record: &VaultRecord) -> PaymentResult<Zeroizing<Vec<u8>>> {
+        let aad = associated_data(token, &record.customer_id);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(&record.nonce),
+                Payload { msg: &record.ciphertext, aad: &aad },
+            )
+            .map(Zeroizing::new)
+            .map_err(|_| PaymentError::internal_error("Vault record failed integrity check"))
+    }
+}
+
+impl Default for PaymentVault {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PaymentVault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaymentVault").finish_non_exhaustive()
+    }
+}
+
+fn record_key(token: &str) -> String {
+    format!("{}{}", RECORD_PREFIX, token)
+}
+
+/// Binds a ciphertext to its token and customer
+fn associated_data(token: &str, customer_id: &str) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(16 + token.len() + customer_id.len());
+    aad.extend_from_slice(&(token.len() as u64).to_be_bytes());
+    aad.extend_from_slice(token.as_bytes());
+    aad.extend_from_slice(&(customer_id.len() as u64).to_be_bytes());
+    aad.extend_from_slice(customer_id.as_bytes());
+    aad
+}
+
+fn last_four(number: &str) -> String {
+    let digits: Vec<char> = number.chars().filter(char::is_ascii_digit).collect();
+    digits[digits.len().saturating_sub(4)..].iter().collect()
+}
+
+fn mask(number: &str) -> String {
+    format!("•••• {}", last_four(number))
+}
+
+fn luhn_valid(number: &str) -> bool {
+    let digits: Vec<u32> = number
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_digit(10))
+        .collect::<Option<_>>()
+        .unwrap_or_default();
+    if !(12..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2, d * 2) {
+            (1, doubled) if doubled > 9 => doubled - 9,
+            (1, doubled) => doubled,
+            _ => d,
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(number: &str) -> SensitivePaymentMethod {
+        SensitivePaymentMethod::CreditCard(CardDetails {
+            number: number.to_string(),
+            brand: "Visa".to_string(),
+            exp_month: 12,
+            exp_year: 2030,
+            holder_name: "Test User".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_vault_tokens_are_scoped_and_revocable() {
+        let storage = Arc::new(MemoryStorage::new(MemoryConfig::default()));
+        let vault = PaymentVault::with_storage([7u8; 32], storage.clone());
+        let token = vault.tokenize("cust_1", card("4242424242424242")).await.unwrap();
+
+        assert!(token.token.starts_with(TOKEN_PREFIX));
+        assert!(!token.token.contains("4242424242424242"));
+        let stored = storage.get(record_key(&token.token).as_bytes()).await.unwrap().unwrap();
+        assert!(!stored.windows(16).any(|w| w == b"4242424242424242"));
+
+        let method = vault.resolve(&token.token, "cust_1").await.unwrap();
+        assert_eq!(method, token.payment_method);
+        assert!(matches!(method, PaymentMethod::CreditCard { ref last_four, .. } if last_four == "4242"));
+        assert_eq!(vault.tokens_for("cust_1").await.unwrap(), vec![token.clone()]);
+
+        assert!(vault.resolve(&token.token, "cust_2").await.is_err());
+        assert!(vault.revoke(&token.token, "cust_2").await.is_err());
+
+        vault.revoke(&token.token, "cust_1").await.unwrap();
+        assert!(vault.resolve(&token.token, "cust_1").await.is_err());
+        assert!(vault.tokens_for("cust_1").await.unwrap().is_empty());
+
+        assert!(vault.tokenize("cust_1", card("4242424242424241")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_vault_tokens_survive_reopening_storage() {
+        use synapsed_storage::backends::FileStorage;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.json");
+        let open = |path: std::path::PathBuf| async move {
+            let storage = tokio::task::spawn_blocking(move || FileStorage::new(path))
+                .await
+                .unwrap()
+                .unwrap();
+            Arc::new(storage) as Arc<dyn Storage<Error = StorageError>>
+        };
+
+        let vault = PaymentVault::with_storage([7u8; 32], open(path.clone()).await);
+        let token = vault.tokenize("cust_1", card("4242424242424242")).await.unwrap();
+        drop(vault);
+
+        let reopened = PaymentVault::with_storage([7u8; 32], open(path.clone()).await);
+        let method = reopened.resolve(&token.token, "cust_1").await.unwrap();
+        assert_eq!(method, token.payment_method);
+
+        // Records can't be read, or silently misread, with another key
+        let wrong_key = PaymentVault::with_storage([8u8; 32], open(path).await);
+        assert!(wrong_key.resolve(&token.token, "cust_1").await.is_err());
+    }
+}
@@ -369,6 +369,7 @@ impl WasmZKEngine {
             timestamp: now,
             expires_at: expires_at.min(now + chrono::Duration::hours(1)),
             commitments,
+            non_revocation: None,
         })
     }
 
@@ -542,6 +543,7 @@ mod tests {
                 did_commitment: b"mock_did_commit".to_vec(),
                 nullifier: b"mock_nullifier".to_vec(),
             },
+            non_revocation: None,
         };
         
         let result = engine.verify_wasm_proof(&proof, 1).await;
@@ -589,6 +591,7 @@ mod tests {
                     did_commitment: b"test".to_vec(),
                     nullifier: b"test".to_vec(),
                 },
+                non_revocation: None,
             },
             generated_at: Utc::now(),
             last_verified: None,
@@ -7,7 +7,8 @@
 
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use synapsed_crypto::hash::h;
 use uuid::Uuid;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -20,6 +21,10 @@ pub struct ZKProofEngine {
     anonymous_subscriptions: HashMap<String, AnonymousSubscription>,
     /// Used nullifiers to prevent double-spending
     used_nullifiers: HashMap<Vec<u8>, DateTime<Utc>>,
+    /// Accumulator over revoked credentials
+    revocation: RevocationAccumulator,
+    /// Issuer key for binding revocation handles to credentials
+    issuer_key: Vec<u8>,
 }
 
 /// Anonymous subscription proof  
@@ -35,6 +40,9 @@ pub struct SubscriptionProof {
     pub expires_at: DateTime<Utc>,
     /// Public commitments
     pub commitments: ProofCommitments,
+    /// Witness that the credential is not revoked
+    #[serde(default)]
+    pub non_revocation: Option<NonRevocationWitness>,
 }
 
 /// Public commitments in the proof
@@ -48,6 +56,181 @@ pub struct ProofCommitments {
     pub nullifier: Vec<u8>,
 }
 
+/// Proof that a credential is not in the revocation accumulator
+///
+/// Revoked handles are kept sorted in a Merkle tree. The witness shows the
+/// two revoked handles adjacent to the credential's own handle, which proves
+/// the handle itself isn't in the tree. The handle is bound to the presented
+/// credential by an issuer tag, so a witness can't be lifted from another
+/// credential. The witness is tied to an accumulator state and has to be
+/// regenerated after every revocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonRevocationWitness {
+    /// Accumulator epoch the witness was computed against
+    pub epoch: u64,
+    /// Accumulator root the witness was computed against
+    pub accumulator: Vec<u8>,
+    /// Revocation handle of the presented credential
+    pub handle: Vec<u8>,
+    /// Issuer tag binding the handle to the credential
+    pub credential_tag: Vec<u8>,
+    /// Closest revoked handle sorting below the credential's handle
+    pub lower: Option<RevokedLeaf>,
+    /// Closest revoked handle sorting above the credential's handle
+    pub upper: Option<RevokedLeaf>,
+}
+
+/// Merkle inclusion proof for a revoked handle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokedLeaf {
+    /// Revoked handle
+    pub handle: Vec<u8>,
+    /// Position of the handle in sorted order
+    pub index: usize,
+    /// Sibling hashes from the leaf up to the root
+    pub path: Vec<Vec<u8>>,
+}
+
+/// Revocation list for anonymous credentials
+///
+/// Only opaque revocation handles derived from credential secrets are
+/// accumulated, never subscription IDs or DIDs. The accumulator value is the
+/// root of a Merkle tree over the sorted handles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationAccumulator {
+    /// Current accumulator root, changes with every revocation
+    pub value: Vec<u8>,
+    /// Number of revocations applied
+    pub epoch: u64,
+    /// Accumulated revocation handles, sorted
+    revoked: BTreeSet<Vec<u8>>,
+}
+
+impl RevocationAccumulator {
+    fn new() -> Self {
+        Self {
+            value: h(b"revocation-accumulator").to_vec(),
+            epoch: 0,
+            revoked: BTreeSet::new(),
+        }
+    }
+
+    /// Add a handle, returning false if it was already revoked
+    fn add(&mut self, handle: Vec<u8>) -> bool {
+        if !self.revoked.insert(handle) {
+            return false;
+        }
+        self.epoch += 1;
+        self.value = self.levels().last().map(|root| root[0].clone()).unwrap_or_default();
+        true
+    }
+
+    /// Number of revoked credentials
+    pub fn len(&self) -> usize {
+        self.revoked.len()
+    }
+
+    /// Whether no credential has been revoked
+    pub fn is_empty(&self) -> bool {
+        self.revoked.is_empty()
+    }
+
+    /// Tree levels from the leaves up to the root
+    ///
+    /// An unpaired node at the end of a level is carried up unchanged.
+    fn levels(&self) -> Vec<Vec<Vec<u8>>> {
+        let mut levels = vec![self.revoked.iter().map(|handle| leaf_hash(handle)).collect::<Vec<_>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    /// Revoked neighbours of a handle, with their inclusion proofs
+    fn neighbours(&self, handle: &[u8]) -> (Option<RevokedLeaf>, Option<RevokedLeaf>) {
+        let levels = self.levels();
+        let handles: Vec<&Vec<u8>> = self.revoked.iter().collect();
+        let leaf = |index: usize| {
+            let mut path = Vec::new();
+            let mut position = index;
+            for level in &levels[..levels.len() - 1] {
+                if let Some(sibling) = level.get(position ^ 1) {
+                    path.push(sibling.clone());
+                }
+                position /= 2;
+            }
+            RevokedLeaf {
+                handle: handles[index].clone(),
+                index,
+                path,
+            }
+        };
+        let position = handles.partition_point(|revoked| revoked.as_slice() < handle);
+        let lower = (position > 0).then(|| leaf(position - 1));
+        let upper = (position < handles.len()).then(|| leaf(position));
+        (lower, upper)
+    }
+
+    /// Verify an inclusion proof against the current root
+    fn verify_leaf(&self, leaf: &RevokedLeaf) -> bool {
+        if leaf.index >= self.len() {
+            return false;
+        }
+        let mut path = leaf.path.iter();
+        let mut hash = leaf_hash(&leaf.handle);
+        let mut position = leaf.index;
+        let mut width = self.len();
+        while width > 1 {
+            if position ^ 1 < width {
+                let Some(sibling) = path.next() else {
+                    return false;
+                };
+                hash = if position % 2 == 0 {
+                    node_hash(&hash, sibling)
+                } else {
+                    node_hash(sibling, &hash)
+                };
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        path.next().is_none() && hash == self.value
+    }
+
+    /// Check a witness against the current state
+    ///
+    /// Returns the reason the witness is rejected, if any.
+    fn check(&self, witness: &NonRevocationWitness) -> Option<&'static str> {
+        if witness.epoch != self.epoch || witness.accumulator != self.value {
+            return Some("stale_revocation_witness");
+        }
+        let proofs_valid = [&witness.lower, &witness.upper]
+            .into_iter()
+            .flatten()
+            .all(|leaf| self.verify_leaf(leaf));
+        let adjacent = match (&witness.lower, &witness.upper) {
+            (Some(lower), Some(upper)) => upper.index == lower.index + 1,
+            (Some(lower), None) => lower.index + 1 == self.len(),
+            (None, Some(upper)) => upper.index == 0,
+            (None, None) => self.is_empty(),
+        };
+        if !proofs_valid || !adjacent {
+            return Some("invalid_revocation_witness");
+        }
+        let below = witness.lower.as_ref().is_none_or(|leaf| leaf.handle < witness.handle);
+        let above = witness.upper.as_ref().is_none_or(|leaf| leaf.handle > witness.handle);
+        (!below || !above).then_some("credential_revoked")
+    }
+}
+
 /// Anonymous subscription state
 #[derive(Debug, Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct AnonymousSubscription {
@@ -90,6 +273,9 @@ pub struct ProofSecrets {
     pub witness: Vec<u8>,
     /// DID signing key (for proof authorization)
     pub did_key: Vec<u8>,
+    /// Issuer tag binding the revocation handle to this credential
+    #[serde(default)]
+    pub revocation_tag: Vec<u8>,
 }
 
 /// Subscription tiers for anonymous verification
@@ -138,6 +324,8 @@ impl ZKProofEngine {
         Ok(Self {
             anonymous_subscriptions: HashMap::new(),
             used_nullifiers: HashMap::new(),
+            revocation: RevocationAccumulator::new(),
+            issuer_key: (0..32).map(|_| rand::random::<u8>()).collect(),
         })
     }
 
@@ -158,6 +346,7 @@ impl ZKProofEngine {
             blinding_factor: (0..32).map(|_| rand::random::<u8>()).collect(),
             witness: (0..64).map(|_| rand::random::<u8>()).collect(),
             did_key: (0..32).map(|_| rand::random::<u8>()).collect(),
+            revocation_tag: Vec::new(),
         };
         
         let private_data = SubscriptionPrivateData {
@@ -166,7 +355,7 @@ impl ZKProofEngine {
             proof_secrets,
         };
         
-        let subscription = AnonymousSubscription {
+        let mut subscription = AnonymousSubscription {
             id: anonymous_id.clone(),
             did: did.clone(),
             tier,
@@ -176,6 +365,10 @@ impl ZKProofEngine {
             expires_at,
             private_data,
         };
+        subscription.private_data.proof_secrets.revocation_tag = self.credential_tag(
+            &self.generate_validity_proof(&subscription)?,
+            &revocation_handle(&subscription),
+        );
         
         // Store the anonymous subscription
         self.anonymous_subscriptions.insert(anonymous_id.clone(), subscription.clone());
@@ -208,6 +401,8 @@ impl ZKProofEngine {
         let tier_proof = self.generate_tier_proof(subscription.tier, min_tier)?;
         let commitments = self.generate_commitments(subscription, context)?;
 
+        let non_revocation = self.generate_non_revocation_witness(subscription);
+
         let proof_expiry = subscription.expires_at.min(Utc::now() + Duration::hours(1));
 
         Ok(SubscriptionProof {
@@ -216,6 +411,7 @@ impl ZKProofEngine {
             timestamp: Utc::now(),
             expires_at: proof_expiry,
             commitments,
+            non_revocation: Some(non_revocation),
         })
     }
 
@@ -251,6 +447,28 @@ impl ZKProofEngine {
             });
         }
 
+        // Non-revocation check against the current accumulator
+        let revocation_error = match &request.proof.non_revocation {
+            Some(witness)
+                if witness.credential_tag
+                    != self.credential_tag(&request.proof.validity_proof, &witness.handle) =>
+            {
+                Some("revocation_witness_mismatch")
+            }
+            Some(witness) => self.revocation.check(witness),
+            None => Some("missing_revocation_witness"),
+        };
+        if let Some(error) = revocation_error {
+            return Ok(VerificationResult {
+                is_valid: false,
+                tier_sufficient: false,
+                verified_at: Utc::now(),
+                expires_at: request.proof.expires_at,
+                allowed_features: vec![],
+                metadata: [(String::from("error"), String::from(error))].into(),
+            });
+        }
+
         // Simplified proof verification
         let is_valid = self.verify_validity_proof(&request.proof.validity_proof)?;
         let tier_sufficient = self.verify_tier_proof(&request.proof.tier_proof, request.min_tier)?;
@@ -304,6 +522,60 @@ impl ZKProofEngine {
         }
     }
 
+    /// Revoke an anonymous credential
+    ///
+    /// Adds the credential's revocation handle to the accumulator, so its
+    /// proofs fail verification from now on. Nothing identifying the
+    /// subscriber is published. Outstanding proofs of other credentials must be
+    /// regenerated against the new accumulator state.
+    pub async fn revoke(&mut self, credential_id: &str) -> PaymentResult<()> {
+        let subscription = self
+            .anonymous_subscriptions
+            .get_mut(credential_id)
+            .ok_or_else(|| PaymentError::SubscriptionNotFound {
+                subscription_id: credential_id.to_string(),
+            })?;
+
+        let handle = revocation_handle(subscription);
+        subscription.status = PaymentStatus::Cancelled;
+        self.revocation.add(handle);
+        Ok(())
+    }
+
+    /// Replace a compromised credential with a fresh one
+    ///
+    /// The old credential is revoked and a new one with new secrets is issued
+    /// for the same subscription.
+    pub async fn reissue(&mut self, credential_id: &str) -> PaymentResult<AnonymousSubscription> {
+        let old = self
+            .anonymous_subscriptions
+            .get(credential_id)
+            .ok_or_else(|| PaymentError::SubscriptionNotFound {
+                subscription_id: credential_id.to_string(),
+            })?
+            .clone();
+        self.revoke(credential_id).await?;
+
+        let mut subscription = self
+            .create_anonymous_subscription(
+                old.did.clone(),
+                old.private_data.stripe_subscription_id.clone().unwrap_or_default(),
+                old.tier,
+                old.amount.clone(),
+                old.expires_at,
+            )
+            .await?;
+        subscription.private_data.payment_fingerprint = old.private_data.payment_fingerprint.clone();
+        self.anonymous_subscriptions
+            .insert(subscription.id.clone(), subscription.clone());
+        Ok(subscription)
+    }
+
+    /// Current revocation accumulator
+    pub fn revocation_accumulator(&self) -> &RevocationAccumulator {
+        &self.revocation
+    }
+
     /// Clean up expired subscriptions
     pub async fn cleanup_expired_subscriptions(&mut self) -> PaymentResult<usize> {
         let now = Utc::now();
@@ -363,6 +635,25 @@ impl ZKProofEngine {
         })
     }
 
+    /// Build a witness that the credential is not revoked
+    fn generate_non_revocation_witness(&self, subscription: &AnonymousSubscription) -> NonRevocationWitness {
+        let handle = revocation_handle(subscription);
+        let (lower, upper) = self.revocation.neighbours(&handle);
+        NonRevocationWitness {
+            epoch: self.revocation.epoch,
+            accumulator: self.revocation.value.clone(),
+            credential_tag: subscription.private_data.proof_secrets.revocation_tag.clone(),
+            handle,
+            lower,
+            upper,
+        }
+    }
+
+    /// Issuer tag over a credential's validity proof and revocation handle
+    fn credential_tag(&self, validity_proof: &[u8], handle: &[u8]) -> Vec<u8> {
+        h(&[b"revocation-tag:".as_slice(), &self.issuer_key, handle, validity_proof].concat()).to_vec()
+    }
+
     /// Verify validity proof (simplified)
     fn verify_validity_proof(&self, proof: &[u8]) -> PaymentResult<bool> {
         // Simplified: check if proof is well-formed
@@ -418,6 +709,22 @@ impl ZKProofEngine {
     }
 }
 
+/// Opaque revocation handle derived from a credential's secret witness
+fn revocation_handle(subscription: &AnonymousSubscription) -> Vec<u8> {
+    let witness = &subscription.private_data.proof_secrets.witness;
+    h(&[b"revocation:".as_slice(), witness].concat()).to_vec()
+}
+
+/// Merkle leaf hash of a revoked handle
+fn leaf_hash(handle: &[u8]) -> Vec<u8> {
+    h(&[[0u8].as_slice(), handle].concat()).to_vec()
+}
+
+/// Merkle interior node hash
+fn node_hash(left: &[u8], right: &[u8]) -> Vec<u8> {
+    h(&[[1u8].as_slice(), left, right].concat()).to_vec()
+}
+
 impl Default for SubscriptionTier {
     fn default() -> Self {
         SubscriptionTier::Free
@@ -554,4 +861,108 @@ mod tests {
         let updated_sub = engine.anonymous_subscriptions.get(&subscription.id).unwrap();
         assert_eq!(updated_sub.did, "did:key:new456");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_revoked_credential_fails_verification() {
+        let mut engine = ZKProofEngine::new().unwrap();
+        let amount = Amount::new(Decimal::new(2999, 2), Currency::Fiat(FiatCurrency::USD)).unwrap();
+
+        let leaked = engine.create_anonymous_subscription(
+            "did:key:leaked".to_string(),
+            "sub_leaked".to_string(),
+            SubscriptionTier::Premium,
+            amount.clone(),
+            Utc::now() + Duration::days(30),
+        ).await.unwrap();
+        let other = engine.create_anonymous_subscription(
+            "did:key:other".to_string(),
+            "sub_other".to_string(),
+            SubscriptionTier::Premium,
+            amount,
+            Utc::now() + Duration::days(30),
+        ).await.unwrap();
+
+        let request = |proof: SubscriptionProof| VerificationRequest {
+            proof,
+            min_tier: SubscriptionTier::Basic,
+            features: vec![],
+            context: "test_api".to_string(),
+        };
+
+        let before = engine.generate_subscription_proof(&leaked.id, SubscriptionTier::Basic, "api").await.unwrap();
+        engine.revoke(&leaked.id).await.unwrap();
+        assert_eq!(engine.revocation_accumulator().len(), 1);
+
+        // Proofs made before the revocation carry a stale witness
+        let result = engine.verify_subscription_proof(&request(before)).await.unwrap();
+        assert!(!result.is_valid);
+
+        let revoked = engine.generate_subscription_proof(&leaked.id, SubscriptionTier::Basic, "api").await.unwrap();
+        let result = engine.verify_subscription_proof(&request(revoked)).await.unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.metadata.get("error").map(String::as_str), Some("credential_revoked"));
+
+        let valid = engine.generate_subscription_proof(&other.id, SubscriptionTier::Basic, "api").await.unwrap();
+        let result = engine.verify_subscription_proof(&request(valid)).await.unwrap();
+        assert!(result.is_valid);
+
+        // The accumulator never holds identifying data
+        let accumulator = serde_json::to_string(engine.revocation_accumulator()).unwrap();
+        assert!(!accumulator.contains(&leaked.id));
+        assert!(!accumulator.contains("did:key:leaked"));
+
+        let reissued = engine.reissue(&other.id).await.unwrap();
+        assert_ne!(reissued.id, other.id);
+        let stale = engine.generate_subscription_proof(&other.id, SubscriptionTier::Basic, "api").await.unwrap();
+        assert!(!engine.verify_subscription_proof(&request(stale)).await.unwrap().is_valid);
+        let fresh = engine.generate_subscription_proof(&reissued.id, SubscriptionTier::Basic, "api").await.unwrap();
+        assert!(engine.verify_subscription_proof(&request(fresh)).await.unwrap().is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_witness_swapped_between_credentials_is_rejected() {
+        let mut engine = ZKProofEngine::new().unwrap();
+        let amount = Amount::new(Decimal::new(2999, 2), Currency::Fiat(FiatCurrency::USD)).unwrap();
+
+        let mut credentials = Vec::new();
+        for i in 0..5 {
+            credentials.push(engine.create_anonymous_subscription(
+                format!("did:key:{}", i),
+                format!("sub_{}", i),
+                SubscriptionTier::Premium,
+                amount.clone(),
+                Utc::now() + Duration::days(30),
+            ).await.unwrap());
+        }
+        for revoked in &credentials[..3] {
+            engine.revoke(&revoked.id).await.unwrap();
+        }
+
+        let request = |proof: SubscriptionProof| VerificationRequest {
+            proof,
+            min_tier: SubscriptionTier::Basic,
+            features: vec![],
+            context: "test_api".to_string(),
+        };
+
+        // Non-membership holds for credentials that weren't revoked
+        let valid = engine.generate_subscription_proof(&credentials[3].id, SubscriptionTier::Basic, "api").await.unwrap();
+        assert!(engine.verify_subscription_proof(&request(valid.clone())).await.unwrap().is_valid);
+
+        // A revoked credential presenting another credential's witness
+        let mut swapped = engine.generate_subscription_proof(&credentials[0].id, SubscriptionTier::Basic, "api").await.unwrap();
+        swapped.non_revocation = valid.non_revocation.clone();
+        let result = engine.verify_subscription_proof(&request(swapped)).await.unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.metadata.get("error").map(String::as_str), Some("revocation_witness_mismatch"));
+
+        // Dropping a neighbour leaves a gap that could hide a revoked handle
+        let mut gapped = valid;
+        let witness = gapped.non_revocation.as_mut().unwrap();
+        if witness.upper.take().is_none() {
+            witness.lower = None;
+        }
+        let result = engine.verify_subscription_proof(&request(gapped)).await.unwrap();
+        assert_eq!(result.metadata.get("error").map(String::as_str), Some("invalid_revocation_witness"));
+    }
+}
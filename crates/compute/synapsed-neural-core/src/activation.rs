@@ -0,0 +1,62 @@
+//! Activation functions.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::error::NeuralError;
+use crate::traits::ActivationFunction;
+
+/// Built-in activation functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Activation {
+    /// Identity
+    Linear,
+    /// Rectified linear unit
+    Relu,
+    /// Logistic sigmoid
+    Sigmoid,
+    /// Hyperbolic tangent
+    Tanh,
+}
+
+impl ActivationFunction for Activation {
+    fn activate(&self, x: f32) -> f32 {
+        match self {
+            Activation::Linear => x,
+            Activation::Relu => x.max(0.0),
+            Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Activation::Tanh => x.tanh(),
+        }
+    }
+
+    fn derivative(&self, output: f32) -> f32 {
+        match self {
+            Activation::Linear => 1.0,
+            Activation::Relu => {
+                if output > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Activation::Sigmoid => output * (1.0 - output),
+            Activation::Tanh => 1.0 - output * output,
+        }
+    }
+}
+
+impl FromStr for Activation {
+    type Err = NeuralError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "linear" | "identity" => Ok(Activation::Linear),
+            "relu" => Ok(Activation::Relu),
+            "sigmoid" => Ok(Activation::Sigmoid),
+            "tanh" => Ok(Activation::Tanh),
+            _ => Err(NeuralError::UnknownActivation {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
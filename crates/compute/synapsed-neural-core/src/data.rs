@@ -0,0 +1,268 @@
+//! Mini-batch loading of training data
+//!
+//! A [`DataLoader`] wraps a [`Dataset`] and yields its samples in mini-batches,
+//! one [`Epoch`] at a time. Each epoch visits every sample exactly once, in a
+//! freshly shuffled order when shuffling is enabled. Seeding the loader makes
+//! the sequence of epochs reproducible.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+/// Indexed collection of training samples
+pub trait Dataset: Send + Sync {
+    /// A single sample, typically an input/target pair
+    type Sample: Send;
+
+    /// Number of samples
+    fn len(&self) -> usize;
+
+    /// Whether the dataset has no samples
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The sample at `index`, which is less than [`len`](Self::len)
+    fn get(&self, index: usize) -> Self::Sample;
+}
+
+impl<S: Clone + Send + Sync> Dataset for Vec<S> {
+    type Sample = S;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get(&self, index: usize) -> S {
+        self[index].clone()
+    }
+}
+
+/// Data loader configuration
+#[derive(Debug, Clone)]
+pub struct DataLoaderConfig {
+    /// Samples per batch
+    pub batch_size: usize,
+    /// Visit samples in a new random order every epoch
+    pub shuffle: bool,
+    /// Seed for shuffling, random when unset
+    pub seed: Option<u64>,
+    /// Skip the final batch of an epoch if it is smaller than `batch_size`
+    pub drop_last: bool,
+    /// Assemble the next batch on a background thread while the current one is used
+    pub prefetch: bool,
+}
+
+impl Default for DataLoaderConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            shuffle: true,
+            seed: None,
+            drop_last: false,
+            prefetch: false,
+        }
+    }
+}
+
+/// Yields shuffled mini-batches from a dataset
+pub struct DataLoader<D: Dataset> {
+    dataset: Arc<D>,
+    config: DataLoaderConfig,
+    rng: StdRng,
+    epochs: usize,
+}
+
+impl<D: Dataset + 'static> DataLoader<D> {
+    /// Create a loader over `dataset`
+    ///
+    /// A batch size of zero is treated as one.
+    pub fn new(dataset: D, mut config: DataLoaderConfig) -> Self {
+        config.batch_size = config.batch_size.max(1);
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            dataset: Arc::new(dataset),
+            config,
+            rng,
+            epochs: 0,
+        }
+    }
+
+    /// The loader configuration
+    pub fn config(&self) -> &DataLoaderConfig {
+        &self.config
+    }
+
+    /// The wrapped dataset
+    pub fn dataset(&self) -> &D {
+        &self.dataset
+    }
+
+    /// Number of epochs started so far
+    pub fn epochs(&self) -> usize {
+        self.epochs
+    }
+
+    /// Number of batches each epoch yields
+    pub fn batches_per_epoch(&self) -> usize {
+        let samples = self.dataset.len();
+        if self.config.drop_last {
+            samples / self.config.batch_size
+        } else {
+            samples.div_ceil(self.config.batch_size)
+        }
+    }
+
+    /// Start a new epoch over the dataset
+    pub fn epoch(&mut self) -> Epoch<D> {
+        let mut order: Vec<usize> = (0..self.dataset.len()).collect();
+        if self.config.shuffle {
+            order.shuffle(&mut self.rng);
+        }
+        if self.config.drop_last {
+            order.truncate(self.batches_per_epoch() * self.config.batch_size);
+        }
+        self.epochs += 1;
+
+        let batches = BatchIndices {
+            order,
+            batch_size: self.config.batch_size,
+            position: 0,
+        };
+        let source = if self.config.prefetch {
+            EpochSource::Prefetched(prefetch(self.dataset.clone(), batches))
+        } else {
+            EpochSource::Direct {
+                dataset: self.dataset.clone(),
+                batches,
+            }
+        };
+        Epoch { source }
+    }
+}
+
+/// Sample indices of an epoch, split into batches
+struct BatchIndices {
+    order: Vec<usize>,
+    batch_size: usize,
+    position: usize,
+}
+
+impl BatchIndices {
+    fn next_batch<D: Dataset>(&mut self, dataset: &D) -> Option<Vec<D::Sample>> {
+        if self.position >= self.order.len() {
+            return None;
+        }
+        let end = (self.position + self.batch_size).min(self.order.len());
+        let batch = self.order[self.position..end]
+            .iter()
+            .map(|&index| dataset.get(index))
+            .collect();
+        self.position = end;
+        Some(batch)
+    }
+}
+
+/// Assemble batches on a background thread, keeping one ready ahead of the consumer
+fn prefetch<D: Dataset + 'static>(dataset: Arc<D>, mut batches: BatchIndices) -> Receiver<Vec<D::Sample>> {
+    let (sender, receiver) = mpsc::sync_channel(1);
+    thread::spawn(move || {
+        while let Some(batch) = batches.next_batch(dataset.as_ref()) {
+            // The epoch was dropped before it was consumed
+            if sender.send(batch).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+enum EpochSource<D: Dataset> {
+    Direct { dataset: Arc<D>, batches: BatchIndices },
+    Prefetched(Receiver<Vec<D::Sample>>),
+}
+
+/// One pass over a dataset, yielding mini-batches
+pub struct Epoch<D: Dataset> {
+    source: EpochSource<D>,
+}
+
+impl<D: Dataset> Iterator for Epoch<D> {
+    type Item = Vec<D::Sample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.source {
+            EpochSource::Direct { dataset, batches } => batches.next_batch(dataset.as_ref()),
+            EpochSource::Prefetched(receiver) => receiver.recv().ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(prefetch: bool, seed: u64) -> DataLoader<Vec<usize>> {
+        let config = DataLoaderConfig {
+            batch_size: 4,
+            seed: Some(seed),
+            prefetch,
+            ..Default::default()
+        };
+        DataLoader::new((0..10).collect(), config)
+    }
+
+    #[test]
+    fn test_epoch_covers_every_sample_once() {
+        for prefetch in [false, true] {
+            let mut loader = loader(prefetch, 7);
+            assert_eq!(loader.batches_per_epoch(), 3);
+
+            let batches: Vec<Vec<usize>> = loader.epoch().collect();
+            assert_eq!(batches.len(), 3);
+            assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 4, 2]);
+
+            let mut samples: Vec<usize> = batches.into_iter().flatten().collect();
+            samples.sort_unstable();
+            assert_eq!(samples, (0..10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_seeded_shuffle_is_reproducible() {
+        let mut first = loader(false, 42);
+        let mut second = loader(true, 42);
+        for _ in 0..3 {
+            let a: Vec<Vec<usize>> = first.epoch().collect();
+            let b: Vec<Vec<usize>> = second.epoch().collect();
+            assert_eq!(a, b);
+        }
+
+        // Epochs are reshuffled, so a different seed gives a different order
+        let mut first = loader(false, 42);
+        let mut other = loader(false, 43);
+        let a: Vec<Vec<Vec<usize>>> = (0..3).map(|_| first.epoch().collect()).collect();
+        let b: Vec<Vec<Vec<usize>>> = (0..3).map(|_| other.epoch().collect()).collect();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_drop_last_skips_partial_batch() {
+        let config = DataLoaderConfig {
+            batch_size: 4,
+            shuffle: false,
+            drop_last: true,
+            ..Default::default()
+        };
+        let mut loader = DataLoader::new((0..10).collect::<Vec<usize>>(), config);
+        assert_eq!(loader.batches_per_epoch(), 2);
+        let batches: Vec<Vec<usize>> = loader.epoch().collect();
+        assert_eq!(batches, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+    }
+}
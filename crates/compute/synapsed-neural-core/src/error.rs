@@ -0,0 +1,29 @@
+//! Error types for neural network operations.
+
+use thiserror::Error;
+
+/// Result type alias for neural network operations.
+pub type Result<T> = std::result::Result<T, NeuralError>;
+
+/// Errors raised while building, running or training a network.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum NeuralError {
+    /// A tensor did not have the shape an operation expected.
+    #[error("Shape mismatch: expected {expected:?}, got {actual:?}")]
+    ShapeMismatch {
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+
+    /// The requested architecture cannot be built.
+    #[error("Invalid architecture: {message}")]
+    InvalidArchitecture { message: String },
+
+    /// The named activation function is not supported.
+    #[error("Unknown activation function: {name}")]
+    UnknownActivation { name: String },
+
+    /// Training could not proceed.
+    #[error("Training error: {message}")]
+    TrainingError { message: String },
+}
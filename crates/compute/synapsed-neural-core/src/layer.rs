@@ -0,0 +1,123 @@
+//! Network layers.
+
+use ndarray::{Array1, Array2, Axis};
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+
+use crate::activation::Activation;
+use crate::error::{NeuralError, Result};
+use crate::traits::{ActivationFunction, Layer};
+use crate::types::{Bias, Weight};
+
+/// Fully connected layer followed by an activation.
+#[derive(Debug, Clone)]
+pub struct DenseLayer {
+    weights: Weight,
+    bias: Bias,
+    activation: Activation,
+    weight_grad: Weight,
+    bias_grad: Bias,
+    input: Option<Array2<f32>>,
+    output: Option<Array2<f32>>,
+}
+
+impl DenseLayer {
+    /// Create a layer with He-initialised weights and zero bias.
+    pub fn new(inputs: usize, outputs: usize, activation: Activation, rng: &mut impl Rng) -> Self {
+        let normal = Normal::new(0.0, (2.0 / inputs.max(1) as f32).sqrt())
+            .expect("standard deviation is finite and positive");
+        let weights = Array2::from_shape_simple_fn((inputs, outputs), || normal.sample(rng));
+        Self::from_parameters(weights, Array1::zeros(outputs), activation)
+    }
+
+    /// Create a layer from existing parameters.
+    pub fn from_parameters(weights: Weight, bias: Bias, activation: Activation) -> Self {
+        Self {
+            weight_grad: Array2::zeros(weights.raw_dim()),
+            bias_grad: Array1::zeros(bias.raw_dim()),
+            weights,
+            bias,
+            activation,
+            input: None,
+            output: None,
+        }
+    }
+
+    /// Weight matrix, shaped `[inputs, outputs]`.
+    pub fn weights(&self) -> &Weight {
+        &self.weights
+    }
+
+    /// Bias vector.
+    pub fn bias(&self) -> &Bias {
+        &self.bias
+    }
+
+    /// Activation applied to the layer output.
+    pub fn activation(&self) -> Activation {
+        self.activation
+    }
+}
+
+impl Layer for DenseLayer {
+    fn input_size(&self) -> usize {
+        self.weights.nrows()
+    }
+
+    fn output_size(&self) -> usize {
+        self.weights.ncols()
+    }
+
+    fn forward(&mut self, input: &Array2<f32>) -> Result<Array2<f32>> {
+        if input.ncols() != self.input_size() {
+            return Err(NeuralError::ShapeMismatch {
+                expected: vec![input.nrows(), self.input_size()],
+                actual: input.shape().to_vec(),
+            });
+        }
+        let activation = self.activation;
+        let output = (input.dot(&self.weights) + &self.bias).mapv(|x| activation.activate(x));
+        self.input = Some(input.clone());
+        self.output = Some(output.clone());
+        Ok(output)
+    }
+
+    fn backward(&mut self, grad_output: &Array2<f32>) -> Result<Array2<f32>> {
+        let (Some(input), Some(output)) = (&self.input, &self.output) else {
+            return Err(NeuralError::TrainingError {
+                message: "backward called before forward".to_string(),
+            });
+        };
+        if grad_output.raw_dim() != output.raw_dim() {
+            return Err(NeuralError::ShapeMismatch {
+                expected: output.shape().to_vec(),
+                actual: grad_output.shape().to_vec(),
+            });
+        }
+        let activation = self.activation;
+        let delta = grad_output * &output.mapv(|y| activation.derivative(y));
+        self.weight_grad = input.t().dot(&delta);
+        self.bias_grad = delta.sum_axis(Axis(0));
+        Ok(delta.dot(&self.weights.t()))
+    }
+
+    fn parameters_mut(&mut self) -> Vec<(&mut [f32], &[f32])> {
+        vec![
+            (
+                self.weights.as_slice_mut().expect("weights are contiguous"),
+                self.weight_grad.as_slice().expect("gradients are contiguous"),
+            ),
+            (
+                self.bias.as_slice_mut().expect("bias is contiguous"),
+                self.bias_grad.as_slice().expect("gradients are contiguous"),
+            ),
+        ]
+    }
+
+    fn parameters(&self) -> Vec<Vec<f32>> {
+        vec![
+            self.weights.iter().copied().collect(),
+            self.bias.to_vec(),
+        ]
+    }
+}
@@ -0,0 +1,199 @@
+//! Ephemeral feedforward networks.
+
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use uuid::Uuid;
+
+use crate::activation::Activation;
+use crate::error::{NeuralError, Result};
+use crate::layer::DenseLayer;
+use crate::optimizer::Sgd;
+use crate::traits::{Layer, NeuralNetwork, Optimizer};
+use crate::types::{Architecture, Tensor};
+
+/// Feedforward network built on demand from an [`Architecture`]
+///
+/// Trained with mean squared error loss.
+pub struct EphemeralNetwork {
+    id: Uuid,
+    architecture: Architecture,
+    layers: Vec<DenseLayer>,
+    optimizer: Box<dyn Optimizer>,
+}
+
+impl EphemeralNetwork {
+    /// Build a network with randomly initialised weights
+    pub fn new(architecture: Architecture) -> Result<Self> {
+        Self::build(architecture, StdRng::from_entropy())
+    }
+
+    /// Build a network whose initial weights are determined by `seed`
+    pub fn with_seed(architecture: Architecture, seed: u64) -> Result<Self> {
+        Self::build(architecture, StdRng::seed_from_u64(seed))
+    }
+
+    fn build(architecture: Architecture, mut rng: StdRng) -> Result<Self> {
+        let sizes = architecture.layer_sizes();
+        if sizes.contains(&0) {
+            return Err(NeuralError::InvalidArchitecture {
+                message: format!("layer sizes must be non-zero, got {:?}", sizes),
+            });
+        }
+        let hidden: Activation = architecture.activation.parse()?;
+        let output: Activation = architecture.output_activation.parse()?;
+
+        let layers = sizes
+            .windows(2)
+            .enumerate()
+            .map(|(index, pair)| {
+                let activation = if index + 2 == sizes.len() { output } else { hidden };
+                DenseLayer::new(pair[0], pair[1], activation, &mut rng)
+            })
+            .collect();
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            architecture,
+            layers,
+            optimizer: Box::new(Sgd::new(0.01)),
+        })
+    }
+
+    /// Replace the optimizer
+    pub fn with_optimizer(mut self, optimizer: impl Optimizer + 'static) -> Self {
+        self.optimizer = Box::new(optimizer);
+        self
+    }
+
+    /// Unique network identifier
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Architecture the network was built from
+    pub fn architecture(&self) -> &Architecture {
+        &self.architecture
+    }
+
+    /// Layers from input to output
+    pub fn layers(&self) -> &[DenseLayer] {
+        &self.layers
+    }
+
+    /// Forward pass returning the output error and mean squared error against `target`
+    fn forward_with_loss(&mut self, input: &Tensor, target: &Tensor) -> Result<(Array2<f32>, f32)> {
+        let mut activations = input.to_matrix()?;
+        for layer in &mut self.layers {
+            activations = layer.forward(&activations)?;
+        }
+        let target = target.to_matrix()?;
+        if target.raw_dim() != activations.raw_dim() {
+            return Err(NeuralError::ShapeMismatch {
+                expected: activations.shape().to_vec(),
+                actual: target.shape().to_vec(),
+            });
+        }
+        let error = &activations - &target;
+        let loss = error.mapv(|e| e * e).mean().unwrap_or(0.0);
+        Ok((error, loss))
+    }
+}
+
+impl NeuralNetwork for EphemeralNetwork {
+    fn forward(&mut self, input: &Tensor) -> Result<Tensor> {
+        let mut activations = input.to_matrix()?;
+        for layer in &mut self.layers {
+            activations = layer.forward(&activations)?;
+        }
+        Ok(Tensor::from_matrix(activations))
+    }
+
+    fn train_step(&mut self, input: &Tensor, target: &Tensor) -> Result<f32> {
+        let (error, loss) = self.forward_with_loss(input, target)?;
+
+        let mut grad = error * (2.0 / target.len() as f32);
+        for layer in self.layers.iter_mut().rev() {
+            grad = layer.backward(&grad)?;
+        }
+        for (index, layer) in self.layers.iter_mut().enumerate() {
+            for (group, (params, grads)) in layer.parameters_mut().into_iter().enumerate() {
+                self.optimizer.step(index * 2 + group, params, grads);
+            }
+        }
+        Ok(loss)
+    }
+
+    fn evaluate(&mut self, input: &Tensor, target: &Tensor) -> Result<f32> {
+        self.forward_with_loss(input, target).map(|(_, loss)| loss)
+    }
+
+    fn snapshot(&self) -> Vec<Vec<f32>> {
+        self.layers.iter().flat_map(|layer| layer.parameters()).collect()
+    }
+
+    fn restore(&mut self, snapshot: &[Vec<f32>]) -> Result<()> {
+        let expected: Vec<usize> = self.snapshot().iter().map(Vec::len).collect();
+        let actual: Vec<usize> = snapshot.iter().map(Vec::len).collect();
+        if expected != actual {
+            return Err(NeuralError::ShapeMismatch { expected, actual });
+        }
+        let mut groups = snapshot.iter();
+        for layer in &mut self.layers {
+            for (params, _) in layer.parameters_mut() {
+                params.copy_from_slice(groups.next().expect("group count checked above"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimizer::Adam;
+
+    #[test]
+    fn test_learns_xor() {
+        let architecture = Architecture::feedforward()
+            .input_size(2)
+            .hidden_layers(vec![8])
+            .output_size(1)
+            .activation("tanh")
+            .output_activation("sigmoid");
+        let mut network = EphemeralNetwork::with_seed(architecture, 1)
+            .unwrap()
+            .with_optimizer(Adam::new(0.05));
+
+        let input = Tensor::from_vec(vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0], vec![4, 2]);
+        let target = Tensor::from_vec(vec![0.0, 1.0, 1.0, 0.0], vec![4, 1]);
+
+        let initial = network.evaluate(&input, &target).unwrap();
+        for _ in 0..500 {
+            network.train_step(&input, &target).unwrap();
+        }
+        let trained = network.evaluate(&input, &target).unwrap();
+        assert!(trained < initial / 10.0, "loss went from {} to {}", initial, trained);
+
+        let output = network.forward(&input).unwrap();
+        let predicted: Vec<f32> = output.data().iter().map(|y| y.round()).collect();
+        assert_eq!(predicted, target.data());
+    }
+
+    #[test]
+    fn test_restore_round_trips_snapshot() {
+        let architecture = Architecture::feedforward().input_size(3).hidden_layers(vec![4]).output_size(2);
+        let mut network = EphemeralNetwork::with_seed(architecture, 7).unwrap();
+        let input = Tensor::from_vec(vec![0.5, -0.5, 1.0], vec![3]);
+        let target = Tensor::from_vec(vec![1.0, 0.0], vec![2]);
+
+        let snapshot = network.snapshot();
+        let before = network.forward(&input).unwrap();
+        network.train_step(&input, &target).unwrap();
+        assert_ne!(network.forward(&input).unwrap(), before);
+
+        network.restore(&snapshot).unwrap();
+        assert_eq!(network.forward(&input).unwrap(), before);
+        assert!(network.restore(&snapshot[1..]).is_err());
+    }
+}
@@ -0,0 +1,727 @@
+//! Import of ONNX models for inference
+//!
+//! Supports the subset of ONNX used by feedforward and convolutional networks:
+//! `Gemm`, `MatMul`, `Conv`, `Add`, `Relu`, `Sigmoid` and `Tanh` over float
+//! tensors. Models using any other operator are rejected when loaded, with an
+//! error naming the operator.
+
+use ndarray::{ArrayD, Ix2, IxDyn};
+use prost::Message;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// ONNX `TensorProto.DataType.FLOAT`
+const ONNX_FLOAT: i32 = 1;
+
+/// Errors from importing or running an ONNX model
+#[derive(Error, Debug)]
+pub enum OnnxError {
+    #[error("Failed to decode ONNX model: {0}")]
+    Decode(#[from] prost::DecodeError),
+
+    #[error("Unsupported ONNX operator: {0}")]
+    UnsupportedOp(String),
+
+    #[error("Invalid ONNX model: {0}")]
+    InvalidModel(String),
+
+    #[error("Shape mismatch in {op}: {message}")]
+    Shape { op: String, message: String },
+}
+
+pub type OnnxResult<T> = std::result::Result<T, OnnxError>;
+
+/// The subset of the ONNX protobuf schema needed for import
+pub(crate) mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ModelProto {
+        #[prost(int64, tag = "1")]
+        pub ir_version: i64,
+        #[prost(message, optional, tag = "7")]
+        pub graph: Option<GraphProto>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GraphProto {
+        #[prost(message, repeated, tag = "1")]
+        pub node: Vec<NodeProto>,
+        #[prost(string, tag = "2")]
+        pub name: String,
+        #[prost(message, repeated, tag = "5")]
+        pub initializer: Vec<TensorProto>,
+        #[prost(message, repeated, tag = "11")]
+        pub input: Vec<ValueInfoProto>,
+        #[prost(message, repeated, tag = "12")]
+        pub output: Vec<ValueInfoProto>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NodeProto {
+        #[prost(string, repeated, tag = "1")]
+        pub input: Vec<String>,
+        #[prost(string, repeated, tag = "2")]
+        pub output: Vec<String>,
+        #[prost(string, tag = "3")]
+        pub name: String,
+        #[prost(string, tag = "4")]
+        pub op_type: String,
+        #[prost(message, repeated, tag = "5")]
+        pub attribute: Vec<AttributeProto>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AttributeProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(float, tag = "2")]
+        pub f: f32,
+        #[prost(int64, tag = "3")]
+        pub i: i64,
+        #[prost(bytes = "vec", tag = "4")]
+        pub s: Vec<u8>,
+        #[prost(int64, repeated, packed = "false", tag = "8")]
+        pub ints: Vec<i64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TensorProto {
+        #[prost(int64, repeated, packed = "false", tag = "1")]
+        pub dims: Vec<i64>,
+        #[prost(int32, tag = "2")]
+        pub data_type: i32,
+        #[prost(float, repeated, tag = "4")]
+        pub float_data: Vec<f32>,
+        #[prost(string, tag = "8")]
+        pub name: String,
+        #[prost(bytes = "vec", tag = "9")]
+        pub raw_data: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ValueInfoProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+    }
+}
+
+/// Supported operators with their attributes
+#[derive(Debug, Clone)]
+enum Op {
+    Gemm {
+        alpha: f32,
+        beta: f32,
+        trans_a: bool,
+        trans_b: bool,
+    },
+    MatMul,
+    Conv {
+        strides: [usize; 2],
+        /// Top, left, bottom, right
+        pads: [usize; 4],
+        dilations: [usize; 2],
+    },
+    Add,
+    Relu,
+    Sigmoid,
+    Tanh,
+}
+
+impl Op {
+    fn name(&self) -> &'static str {
+        match self {
+            Op::Gemm { .. } => "Gemm",
+            Op::MatMul => "MatMul",
+            Op::Conv { .. } => "Conv",
+            Op::Add => "Add",
+            Op::Relu => "Relu",
+            Op::Sigmoid => "Sigmoid",
+            Op::Tanh => "Tanh",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    op: Op,
+    inputs: Vec<String>,
+    output: String,
+}
+
+/// An ONNX graph loaded for inference
+#[derive(Debug, Clone)]
+pub struct OnnxModel {
+    input: String,
+    output: String,
+    initializers: HashMap<String, ArrayD<f32>>,
+    nodes: Vec<Node>,
+}
+
+impl OnnxModel {
+    /// Parse a serialized ONNX `ModelProto`
+    ///
+    /// The graph must have a single input (besides its initializers) and a
+    /// single output, and its nodes must be topologically sorted as the ONNX
+    /// specification requires.
+    pub fn from_bytes(bytes: &[u8]) -> OnnxResult<Self> {
+        let model = proto::ModelProto::decode(bytes)?;
+        let graph = model
+            .graph
+            .ok_or_else(|| OnnxError::InvalidModel("model has no graph".to_string()))?;
+
+        let initializers = graph
+            .initializer
+            .iter()
+            .map(|tensor| Ok((tensor.name.clone(), tensor_to_array(tensor)?)))
+            .collect::<OnnxResult<HashMap<_, _>>>()?;
+
+        // Older exporters list initializers among the graph inputs
+        let inputs: Vec<&str> = graph
+            .input
+            .iter()
+            .map(|input| input.name.as_str())
+            .filter(|name| !initializers.contains_key(*name))
+            .collect();
+        let [input] = inputs.as_slice() else {
+            return Err(OnnxError::InvalidModel(format!(
+                "expected one graph input, found {}",
+                inputs.len()
+            )));
+        };
+        let [output] = graph.output.as_slice() else {
+            return Err(OnnxError::InvalidModel(format!(
+                "expected one graph output, found {}",
+                graph.output.len()
+            )));
+        };
+
+        let nodes = graph.node.iter().map(parse_node).collect::<OnnxResult<Vec<_>>>()?;
+
+        // Every node input must be produced before it is used
+        let mut available: HashSet<&str> = initializers.keys().map(String::as_str).collect();
+        available.insert(input);
+        for node in &nodes {
+            if let Some(missing) = node
+                .inputs
+                .iter()
+                .find(|name| !name.is_empty() && !available.contains(name.as_str()))
+            {
+                return Err(OnnxError::InvalidModel(format!(
+                    "{} node uses undefined value '{}'",
+                    node.op.name(),
+                    missing
+                )));
+            }
+            available.insert(&node.output);
+        }
+        if !available.contains(output.name.as_str()) {
+            return Err(OnnxError::InvalidModel(format!(
+                "graph output '{}' is never produced",
+                output.name
+            )));
+        }
+
+        Ok(Self {
+            input: input.to_string(),
+            output: output.name.clone(),
+            initializers,
+            nodes,
+        })
+    }
+
+    /// Name of the graph input
+    pub fn input_name(&self) -> &str {
+        &self.input
+    }
+
+    /// Name of the graph output
+    pub fn output_name(&self) -> &str {
+        &self.output
+    }
+
+    /// Run inference on a single input tensor
+    pub fn run(&self, input: ArrayD<f32>) -> OnnxResult<ArrayD<f32>> {
+        let mut values: HashMap<&str, ArrayD<f32>> = HashMap::new();
+        values.insert(&self.input, input);
+
+        for node in &self.nodes {
+            let args = node
+                .inputs
+                .iter()
+                .map(|name| {
+                    if name.is_empty() {
+                        None
+                    } else {
+                        values.get(name.as_str()).or_else(|| self.initializers.get(name))
+                    }
+                })
+                .collect::<Vec<_>>();
+            let result = evaluate(&node.op, &args)?;
+            values.insert(&node.output, result);
+        }
+
+        values
+            .remove(self.output.as_str())
+            .ok_or_else(|| OnnxError::InvalidModel(format!("output '{}' was not computed", self.output)))
+    }
+}
+
+fn tensor_to_array(tensor: &proto::TensorProto) -> OnnxResult<ArrayD<f32>> {
+    if tensor.data_type != ONNX_FLOAT {
+        return Err(OnnxError::InvalidModel(format!(
+            "tensor '{}' has data type {}, only float tensors are supported",
+            tensor.name, tensor.data_type
+        )));
+    }
+    let shape = tensor
+        .dims
+        .iter()
+        .map(|&dim| usize::try_from(dim))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| OnnxError::InvalidModel(format!("tensor '{}' has a negative dimension", tensor.name)))?;
+
+    let data = if tensor.raw_data.is_empty() {
+        tensor.float_data.clone()
+    } else {
+        tensor
+            .raw_data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect()
+    };
+    ArrayD::from_shape_vec(IxDyn(&shape), data).map_err(|e| {
+        OnnxError::InvalidModel(format!("tensor '{}' data does not match its shape: {}", tensor.name, e))
+    })
+}
+
+fn parse_node(node: &proto::NodeProto) -> OnnxResult<Node> {
+    let attribute = |name: &str| node.attribute.iter().find(|a| a.name == name);
+    let ints = |name: &str, default: [usize; 2]| -> OnnxResult<[usize; 2]> {
+        match attribute(name) {
+            Some(a) => match a.ints.as_slice() {
+                [x, y] if *x >= 0 && *y >= 0 => Ok([*x as usize, *y as usize]),
+                _ => Err(OnnxError::InvalidModel(format!("Conv attribute {} must have two values", name))),
+            },
+            None => Ok(default),
+        }
+    };
+
+    let op = match node.op_type.as_str() {
+        "Gemm" => Op::Gemm {
+            alpha: attribute("alpha").map_or(1.0, |a| a.f),
+            beta: attribute("beta").map_or(1.0, |a| a.f),
+            trans_a: attribute("transA").is_some_and(|a| a.i != 0),
+            trans_b: attribute("transB").is_some_and(|a| a.i != 0),
+        },
+        "MatMul" => Op::MatMul,
+        "Conv" => {
+            if let Some(group) = attribute("group").filter(|a| a.i != 1) {
+                return Err(OnnxError::UnsupportedOp(format!("Conv with group={}", group.i)));
+            }
+            if let Some(auto_pad) = attribute("auto_pad").filter(|a| a.s != b"NOTSET") {
+                return Err(OnnxError::UnsupportedOp(format!(
+                    "Conv with auto_pad={}",
+                    String::from_utf8_lossy(&auto_pad.s)
+                )));
+            }
+            let pads = match attribute("pads") {
+                Some(a) => match a.ints.as_slice() {
+                    [t, l, b, r] if [t, l, b, r].iter().all(|&&p| p >= 0) => {
+                        [*t as usize, *l as usize, *b as usize, *r as usize]
+                    }
+                    _ => return Err(OnnxError::InvalidModel("Conv attribute pads must have four values".to_string())),
+                },
+                None => [0; 4],
+            };
+            Op::Conv {
+                strides: ints("strides", [1, 1])?,
+                pads,
+                dilations: ints("dilations", [1, 1])?,
+            }
+        }
+        "Add" => Op::Add,
+        "Relu" => Op::Relu,
+        "Sigmoid" => Op::Sigmoid,
+        "Tanh" => Op::Tanh,
+        other => return Err(OnnxError::UnsupportedOp(other.to_string())),
+    };
+
+    let [output] = node.output.as_slice() else {
+        return Err(OnnxError::InvalidModel(format!(
+            "{} node must have exactly one output",
+            node.op_type
+        )));
+    };
+    Ok(Node {
+        op,
+        inputs: node.input.clone(),
+        output: output.clone(),
+    })
+}
+
+fn shape_error(op: &Op, message: impl Into<String>) -> OnnxError {
+    OnnxError::Shape {
+        op: op.name().to_string(),
+        message: message.into(),
+    }
+}
+
+fn evaluate(op: &Op, args: &[Option<&ArrayD<f32>>]) -> OnnxResult<ArrayD<f32>> {
+    let arg = |index: usize| {
+        args.get(index)
+            .copied()
+            .flatten()
+            .ok_or_else(|| shape_error(op, format!("missing input {}", index)))
+    };
+    let matrix = |array: &ArrayD<f32>| {
+        array
+            .clone()
+            .into_dimensionality::<Ix2>()
+            .map_err(|_| shape_error(op, format!("expected a matrix, got shape {:?}", array.shape())))
+    };
+
+    match op {
+        Op::Gemm {
+            alpha,
+            beta,
+            trans_a,
+            trans_b,
+        } => {
+            let mut a = matrix(arg(0)?)?;
+            let mut b = matrix(arg(1)?)?;
+            if *trans_a {
+                a = a.reversed_axes();
+            }
+            if *trans_b {
+                b = b.reversed_axes();
+            }
+            if a.ncols() != b.nrows() {
+                return Err(shape_error(op, format!("cannot multiply {:?} by {:?}", a.shape(), b.shape())));
+            }
+            let product = (a.dot(&b) * *alpha).into_dyn();
+            match args.get(2).copied().flatten() {
+                Some(c) => add(op, &product, &(c * *beta)),
+                None => Ok(product),
+            }
+        }
+        Op::MatMul => {
+            let a = matrix(arg(0)?)?;
+            let b = matrix(arg(1)?)?;
+            if a.ncols() != b.nrows() {
+                return Err(shape_error(op, format!("cannot multiply {:?} by {:?}", a.shape(), b.shape())));
+            }
+            Ok(a.dot(&b).into_dyn())
+        }
+        Op::Conv {
+            strides,
+            pads,
+            dilations,
+        } => conv2d(op, arg(0)?, arg(1)?, args.get(2).copied().flatten(), *strides, *pads, *dilations),
+        Op::Add => add(op, arg(0)?, arg(1)?),
+        Op::Relu => Ok(arg(0)?.mapv(|x| x.max(0.0))),
+        Op::Sigmoid => Ok(arg(0)?.mapv(|x| 1.0 / (1.0 + (-x).exp()))),
+        Op::Tanh => Ok(arg(0)?.mapv(f32::tanh)),
+    }
+}
+
+/// Elementwise addition where one operand broadcasts to the other's shape
+fn add(op: &Op, a: &ArrayD<f32>, b: &ArrayD<f32>) -> OnnxResult<ArrayD<f32>> {
+    if let Some(b) = b.broadcast(a.raw_dim()) {
+        Ok(a + &b)
+    } else if let Some(a) = a.broadcast(b.raw_dim()) {
+        Ok(&a + b)
+    } else {
+        Err(shape_error(op, format!("cannot broadcast {:?} with {:?}", a.shape(), b.shape())))
+    }
+}
+
+/// 2D convolution over NCHW input with MCkHkW weights
+fn conv2d(
+    op: &Op,
+    input: &ArrayD<f32>,
+    weights: &ArrayD<f32>,
+    bias: Option<&ArrayD<f32>>,
+    strides: [usize; 2],
+    pads: [usize; 4],
+    dilations: [usize; 2],
+) -> OnnxResult<ArrayD<f32>> {
+    let &[batch, channels, height, width] = input.shape() else {
+        return Err(shape_error(op, format!("expected NCHW input, got shape {:?}", input.shape())));
+    };
+    let &[filters, filter_channels, kernel_h, kernel_w] = weights.shape() else {
+        return Err(shape_error(op, format!("expected 4D weights, got shape {:?}", weights.shape())));
+    };
+    if filter_channels != channels {
+        return Err(shape_error(
+            op,
+            format!("weights expect {} channels, input has {}", filter_channels, channels),
+        ));
+    }
+    if let Some(bias) = bias {
+        if bias.shape() != [filters] {
+            return Err(shape_error(op, format!("bias shape {:?} does not match {} filters", bias.shape(), filters)));
+        }
+    }
+    if strides.contains(&0) || dilations.contains(&0) {
+        return Err(shape_error(op, "strides and dilations must be positive"));
+    }
+    if kernel_h == 0 || kernel_w == 0 {
+        return Err(shape_error(op, format!("kernel must be non-empty, got {}x{}", kernel_h, kernel_w)));
+    }
+
+    // Dilated kernel size; overflow means it can't fit any input
+    let extent = |dilation: usize, kernel: usize| dilation.checked_mul(kernel - 1).and_then(|e| e.checked_add(1));
+    let padded_h = height.checked_add(pads[0]).and_then(|h| h.checked_add(pads[2]));
+    let padded_w = width.checked_add(pads[1]).and_then(|w| w.checked_add(pads[3]));
+    let (Some(extent_h), Some(extent_w), Some(padded_h), Some(padded_w)) = (
+        extent(dilations[0], kernel_h),
+        extent(dilations[1], kernel_w),
+        padded_h,
+        padded_w,
+    ) else {
+        return Err(shape_error(op, "kernel, dilation or padding is too large"));
+    };
+    if padded_h < extent_h || padded_w < extent_w {
+        return Err(shape_error(op, "kernel is larger than the padded input"));
+    }
+    let out_h = (padded_h - extent_h) / strides[0] + 1;
+    let out_w = (padded_w - extent_w) / strides[1] + 1;
+
+    let mut output = ArrayD::<f32>::zeros(IxDyn(&[batch, filters, out_h, out_w]));
+    for n in 0..batch {
+        for m in 0..filters {
+            let initial = bias.map_or(0.0, |bias| bias[[m]]);
+            for oy in 0..out_h {
+                for ox in 0..out_w {
+                    let mut sum = initial;
+                    for c in 0..channels {
+                        for ky in 0..kernel_h {
+                            // Position in the padded input, skipping padding
+                            let y = oy * strides[0] + ky * dilations[0];
+                            if y < pads[0] || y - pads[0] >= height {
+                                continue;
+                            }
+                            for kx in 0..kernel_w {
+                                let x = ox * strides[1] + kx * dilations[1];
+                                if x < pads[1] || x - pads[1] >= width {
+                                    continue;
+                                }
+                                sum += input[[n, c, y - pads[0], x - pads[1]]] * weights[[m, c, ky, kx]];
+                            }
+                        }
+                    }
+                    output[[n, m, oy, ox]] = sum;
+                }
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::proto::*;
+    use super::*;
+
+    fn tensor(name: &str, dims: &[i64], data: &[f32]) -> TensorProto {
+        TensorProto {
+            dims: dims.to_vec(),
+            data_type: ONNX_FLOAT,
+            float_data: data.to_vec(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn raw_tensor(name: &str, dims: &[i64], data: &[f32]) -> TensorProto {
+        TensorProto {
+            dims: dims.to_vec(),
+            data_type: ONNX_FLOAT,
+            raw_data: data.iter().flat_map(|x| x.to_le_bytes()).collect(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn node(op_type: &str, inputs: &[&str], output: &str, attribute: Vec<AttributeProto>) -> NodeProto {
+        NodeProto {
+            input: inputs.iter().map(|s| s.to_string()).collect(),
+            output: vec![output.to_string()],
+            op_type: op_type.to_string(),
+            attribute,
+            ..Default::default()
+        }
+    }
+
+    fn int_attr(name: &str, i: i64) -> AttributeProto {
+        AttributeProto {
+            name: name.to_string(),
+            i,
+            ..Default::default()
+        }
+    }
+
+    fn ints_attr(name: &str, ints: &[i64]) -> AttributeProto {
+        AttributeProto {
+            name: name.to_string(),
+            ints: ints.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn model(nodes: Vec<NodeProto>, initializer: Vec<TensorProto>, output: &str) -> Vec<u8> {
+        let value = |name: &str| ValueInfoProto { name: name.to_string() };
+        ModelProto {
+            ir_version: 8,
+            graph: Some(GraphProto {
+                node: nodes,
+                name: "test".to_string(),
+                initializer,
+                input: vec![value("x")],
+                output: vec![value(output)],
+            }),
+        }
+        .encode_to_vec()
+    }
+
+    const FC1_WEIGHT: [f32; 12] = [0.2, -0.1, 0.4, -0.3, 0.8, 0.1, 0.5, 0.5, -0.5, 0.0, -0.2, 0.3];
+    const FC1_BIAS: [f32; 4] = [0.1, -0.2, 0.0, 0.05];
+    const FC2_WEIGHT: [f32; 8] = [0.7, -0.4, 0.2, 0.9, -0.6, 0.3, 0.8, -0.1];
+    const FC2_BIAS: [f32; 2] = [0.0, 0.1];
+
+    /// 3-4-2 MLP in the node layout `torch.onnx.export` uses for
+    /// `Sequential(Linear(3, 4), ReLU(), Linear(4, 2), Sigmoid())`
+    fn mlp() -> Vec<u8> {
+        model(
+            vec![
+                node("Gemm", &["x", "fc1.weight", "fc1.bias"], "h", vec![int_attr("transB", 1)]),
+                node("Relu", &["h"], "a", vec![]),
+                node("Gemm", &["a", "fc2.weight", "fc2.bias"], "z", vec![int_attr("transB", 1)]),
+                node("Sigmoid", &["z"], "y", vec![]),
+            ],
+            vec![
+                tensor("fc1.weight", &[4, 3], &FC1_WEIGHT),
+                tensor("fc1.bias", &[4], &FC1_BIAS),
+                raw_tensor("fc2.weight", &[2, 4], &FC2_WEIGHT),
+                raw_tensor("fc2.bias", &[2], &FC2_BIAS),
+            ],
+            "y",
+        )
+    }
+
+    /// Dense layer `W x + b` in f64, with `W` stored row-major as `[outputs, inputs]`
+    fn reference_dense(weights: &[f32], bias: &[f32], input: &[f64]) -> Vec<f64> {
+        weights
+            .chunks(input.len())
+            .zip(bias)
+            .map(|(row, b)| row.iter().zip(input).map(|(w, x)| *w as f64 * x).sum::<f64>() + *b as f64)
+            .collect()
+    }
+
+    #[test]
+    fn test_mlp_matches_reference_output() {
+        let model = OnnxModel::from_bytes(&mlp()).unwrap();
+        assert_eq!(model.input_name(), "x");
+        assert_eq!(model.output_name(), "y");
+
+        let input = ArrayD::from_shape_vec(IxDyn(&[1, 3]), vec![0.5, -1.0, 2.0]).unwrap();
+        let output = model.run(input).unwrap();
+
+        // Independent f64 evaluation of the same network
+        let hidden: Vec<f64> = reference_dense(&FC1_WEIGHT, &FC1_BIAS, &[0.5, -1.0, 2.0])
+            .into_iter()
+            .map(|h| h.max(0.0))
+            .collect();
+        let expected: Vec<f64> = reference_dense(&FC2_WEIGHT, &FC2_BIAS, &hidden)
+            .into_iter()
+            .map(|z| 1.0 / (1.0 + (-z).exp()))
+            .collect();
+        assert_eq!(output.shape(), &[1, 2]);
+        for (actual, expected) in output.iter().zip(expected) {
+            assert!((*actual as f64 - expected).abs() < 1e-5, "{} != {}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_conv_with_padding_and_stride() {
+        let bytes = model(
+            vec![
+                node(
+                    "Conv",
+                    &["x", "w", "b"],
+                    "c",
+                    vec![
+                        ints_attr("kernel_shape", &[2, 2]),
+                        ints_attr("pads", &[1, 1, 1, 1]),
+                        ints_attr("strides", &[2, 2]),
+                    ],
+                ),
+                node("Relu", &["c"], "y", vec![]),
+            ],
+            vec![tensor("w", &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0]), tensor("b", &[1], &[0.5])],
+            "y",
+        );
+        let model = OnnxModel::from_bytes(&bytes).unwrap();
+
+        let input = ArrayD::from_shape_vec(IxDyn(&[1, 1, 3, 3]), (1..=9).map(|x| x as f32).collect()).unwrap();
+        let output = model.run(input).unwrap();
+        assert_eq!(output.shape(), &[1, 1, 2, 2]);
+
+        // Each output is the kernel over the zero-padded 4x4 window at (2oy, 2ox)
+        let padded = |y: usize, x: usize| match (y.checked_sub(1), x.checked_sub(1)) {
+            (Some(y), Some(x)) if y < 3 && x < 3 => (y * 3 + x + 1) as f32,
+            _ => 0.0,
+        };
+        let kernel = [[1.0, 2.0], [3.0, 4.0]];
+        let mut expected = Vec::new();
+        for oy in 0..2 {
+            for ox in 0..2 {
+                let mut sum = 0.5;
+                for (ky, row) in kernel.iter().enumerate() {
+                    for (kx, w) in row.iter().enumerate() {
+                        sum += w * padded(2 * oy + ky, 2 * ox + kx);
+                    }
+                }
+                expected.push(f32::max(sum, 0.0));
+            }
+        }
+        assert_eq!(output.iter().copied().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_conv_rejects_empty_and_oversized_kernels() {
+        let conv = |weight_dims: &[i64], dilations: &[i64]| {
+            let weights = vec![1.0; weight_dims.iter().product::<i64>() as usize];
+            let bytes = model(
+                vec![node("Conv", &["x", "w"], "y", vec![ints_attr("dilations", dilations)])],
+                vec![tensor("w", weight_dims, &weights)],
+                "y",
+            );
+            let input = ArrayD::from_shape_vec(IxDyn(&[1, 1, 3, 3]), vec![1.0; 9]).unwrap();
+            OnnxModel::from_bytes(&bytes).and_then(|model| model.run(input))
+        };
+
+        assert!(conv(&[1, 1, 2, 2], &[1, 1]).is_ok());
+        for (dims, dilations) in [
+            (&[1, 1, 0, 2][..], &[1, 1][..]),
+            (&[1, 1, 2, 0], &[1, 1]),
+            (&[1, 1, 4, 4], &[1, 1]),
+            (&[1, 1, 2, 2], &[3, 3]),
+            (&[1, 1, 2, 2], &[i64::MAX, 1]),
+        ] {
+            assert!(
+                matches!(conv(dims, dilations), Err(OnnxError::Shape { .. })),
+                "{:?} with dilations {:?} was accepted",
+                dims,
+                dilations
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsupported_op_is_named() {
+        let bytes = model(vec![node("Softmax", &["x"], "y", vec![])], vec![], "y");
+        let err = OnnxModel::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(&err, OnnxError::UnsupportedOp(op) if op == "Softmax"));
+        assert!(err.to_string().contains("Softmax"));
+    }
+}
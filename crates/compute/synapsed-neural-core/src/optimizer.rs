@@ -0,0 +1,109 @@
+//! Parameter update rules.
+
+use std::collections::HashMap;
+
+use crate::traits::Optimizer;
+
+/// Stochastic gradient descent with optional momentum.
+#[derive(Debug, Clone)]
+pub struct Sgd {
+    /// Step size
+    pub learning_rate: f32,
+    /// Fraction of the previous update carried into the next
+    pub momentum: f32,
+    velocity: HashMap<usize, Vec<f32>>,
+}
+
+impl Sgd {
+    /// Plain gradient descent with the given learning rate.
+    pub fn new(learning_rate: f32) -> Self {
+        Self {
+            learning_rate,
+            momentum: 0.0,
+            velocity: HashMap::new(),
+        }
+    }
+
+    /// Set the momentum factor.
+    pub fn momentum(mut self, momentum: f32) -> Self {
+        self.momentum = momentum;
+        self
+    }
+}
+
+impl Optimizer for Sgd {
+    fn step(&mut self, key: usize, params: &mut [f32], grads: &[f32]) {
+        let velocity = self.velocity.entry(key).or_insert_with(|| vec![0.0; params.len()]);
+        for ((param, grad), v) in params.iter_mut().zip(grads).zip(velocity.iter_mut()) {
+            *v = self.momentum * *v - self.learning_rate * grad;
+            *param += *v;
+        }
+    }
+}
+
+/// Adam optimizer.
+#[derive(Debug, Clone)]
+pub struct Adam {
+    /// Step size
+    pub learning_rate: f32,
+    /// Decay rate of the first moment estimate
+    pub beta1: f32,
+    /// Decay rate of the second moment estimate
+    pub beta2: f32,
+    /// Term added to the denominator for numerical stability
+    pub epsilon: f32,
+    moments: HashMap<usize, AdamMoments>,
+}
+
+#[derive(Debug, Clone)]
+struct AdamMoments {
+    first: Vec<f32>,
+    second: Vec<f32>,
+    steps: i32,
+}
+
+impl Adam {
+    /// Adam with the usual defaults and the given learning rate.
+    pub fn new(learning_rate: f32) -> Self {
+        Self {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            moments: HashMap::new(),
+        }
+    }
+
+    /// Set the first moment decay rate.
+    pub fn beta1(mut self, beta1: f32) -> Self {
+        self.beta1 = beta1;
+        self
+    }
+
+    /// Set the second moment decay rate.
+    pub fn beta2(mut self, beta2: f32) -> Self {
+        self.beta2 = beta2;
+        self
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, key: usize, params: &mut [f32], grads: &[f32]) {
+        let moments = self.moments.entry(key).or_insert_with(|| AdamMoments {
+            first: vec![0.0; params.len()],
+            second: vec![0.0; params.len()],
+            steps: 0,
+        });
+        moments.steps += 1;
+        let first_correction = 1.0 - self.beta1.powi(moments.steps);
+        let second_correction = 1.0 - self.beta2.powi(moments.steps);
+
+        for (i, (param, grad)) in params.iter_mut().zip(grads).enumerate() {
+            moments.first[i] = self.beta1 * moments.first[i] + (1.0 - self.beta1) * grad;
+            moments.second[i] = self.beta2 * moments.second[i] + (1.0 - self.beta2) * grad * grad;
+            let first = moments.first[i] / first_correction;
+            let second = moments.second[i] / second_correction;
+            *param -= self.learning_rate * first / (second.sqrt() + self.epsilon);
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Validation metrics and early stopping
+//!
+//! An [`EarlyStoppingMonitor`] is fed the metrics of each training epoch along
+//! with a snapshot of the weights that produced them. It keeps the snapshot
+//! with the lowest validation loss and reports when validation loss has not
+//! improved for [`EarlyStopping::patience`] epochs, so the caller can stop and
+//! restore the best weights. [`fit`] runs that loop for a network and a
+//! [`DataLoader`].
+
+use crate::data::{DataLoader, Dataset};
+use crate::error::{NeuralError, Result};
+use crate::traits::NeuralNetwork;
+use crate::types::Tensor;
+
+/// Early stopping criterion
+#[derive(Debug, Clone)]
+pub struct EarlyStopping {
+    /// Epochs without improvement before training stops
+    pub patience: usize,
+    /// Minimum decrease in validation loss that counts as an improvement
+    pub min_delta: f32,
+    /// Upper bound on the number of epochs
+    pub max_epochs: usize,
+}
+
+impl Default for EarlyStopping {
+    fn default() -> Self {
+        Self {
+            patience: 5,
+            min_delta: 0.0,
+            max_epochs: 100,
+        }
+    }
+}
+
+/// Metrics for one training epoch
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochMetrics {
+    /// Zero-based epoch number
+    pub epoch: usize,
+    /// Mean loss over the training set
+    pub train_loss: f32,
+    /// Mean loss over the validation set
+    pub val_loss: f32,
+    /// Fraction of validation samples classified correctly, if applicable
+    pub val_accuracy: Option<f32>,
+}
+
+/// Tracks validation loss across epochs and keeps the best weights
+#[derive(Debug)]
+pub struct EarlyStoppingMonitor<W> {
+    config: EarlyStopping,
+    history: Vec<EpochMetrics>,
+    best: Option<(usize, W)>,
+    epochs_without_improvement: usize,
+}
+
+impl<W> EarlyStoppingMonitor<W> {
+    /// Create a monitor for the given criterion
+    pub fn new(config: EarlyStopping) -> Self {
+        Self {
+            config,
+            history: Vec::new(),
+            best: None,
+            epochs_without_improvement: 0,
+        }
+    }
+
+    /// Record an epoch, returning whether training should continue
+    ///
+    /// `snapshot` is only called when the epoch improves on the best
+    /// validation loss so far.
+    pub fn record(&mut self, metrics: EpochMetrics, snapshot: impl FnOnce() -> W) -> bool {
+        let improved = match self.best_metrics() {
+            Some(best) => metrics.val_loss < best.val_loss - self.config.min_delta,
+            None => true,
+        };
+        if improved {
+            self.best = Some((self.history.len(), snapshot()));
+            self.epochs_without_improvement = 0;
+        } else {
+            self.epochs_without_improvement += 1;
+        }
+        self.history.push(metrics);
+        !self.should_stop()
+    }
+
+    /// Whether the patience or epoch limit has been reached
+    pub fn should_stop(&self) -> bool {
+        self.epochs_without_improvement >= self.config.patience
+            || self.history.len() >= self.config.max_epochs
+    }
+
+    /// Metrics of every recorded epoch, in order
+    pub fn history(&self) -> &[EpochMetrics] {
+        &self.history
+    }
+
+    /// Metrics of the epoch with the lowest validation loss
+    pub fn best_metrics(&self) -> Option<&EpochMetrics> {
+        self.best.as_ref().map(|(index, _)| &self.history[*index])
+    }
+
+    /// Consume the monitor, returning the epoch history and the best weights
+    pub fn finish(self) -> (Vec<EpochMetrics>, Option<W>) {
+        (self.history, self.best.map(|(_, weights)| weights))
+    }
+}
+
+/// Fraction of outputs whose highest value is at the same position as the target's
+pub fn classification_accuracy(outputs: &[Vec<f32>], targets: &[Vec<f32>]) -> f32 {
+    fn argmax(values: &[f32]) -> Option<usize> {
+        values
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(index, _)| index)
+    }
+
+    if outputs.is_empty() {
+        return 0.0;
+    }
+    let correct = outputs
+        .iter()
+        .zip(targets)
+        .filter(|(output, target)| argmax(output).is_some() && argmax(output) == argmax(target))
+        .count();
+    correct as f32 / outputs.len() as f32
+}
+
+/// Train `network` until `config` says to stop, then restore the best weights
+///
+/// Each epoch trains on every batch from `train` and then evaluates the
+/// network on `validation`. Validation accuracy is reported when targets have
+/// more than one column. Returns the metrics of every epoch.
+pub fn fit<N, D>(
+    network: &mut N,
+    train: &mut DataLoader<D>,
+    validation: &[(Vec<f32>, Vec<f32>)],
+    config: EarlyStopping,
+) -> Result<Vec<EpochMetrics>>
+where
+    N: NeuralNetwork,
+    D: Dataset<Sample = (Vec<f32>, Vec<f32>)> + 'static,
+{
+    let (val_input, val_target) = stack(validation)?;
+    let mut monitor = EarlyStoppingMonitor::new(config);
+
+    loop {
+        let mut loss_sum = 0.0;
+        let mut samples = 0;
+        for batch in train.epoch() {
+            let (input, target) = stack(&batch)?;
+            loss_sum += network.train_step(&input, &target)? * batch.len() as f32;
+            samples += batch.len();
+        }
+        if samples == 0 {
+            return Err(NeuralError::TrainingError {
+                message: "training set yields no batches".to_string(),
+            });
+        }
+
+        let val_loss = network.evaluate(&val_input, &val_target)?;
+        let val_accuracy = match val_target.shape() {
+            [_, columns] if *columns > 1 => {
+                let outputs = rows(&network.forward(&val_input)?);
+                Some(classification_accuracy(&outputs, &rows(&val_target)))
+            }
+            _ => None,
+        };
+        let metrics = EpochMetrics {
+            epoch: monitor.history().len(),
+            train_loss: loss_sum / samples as f32,
+            val_loss,
+            val_accuracy,
+        };
+        tracing::debug!(?metrics, "epoch finished");
+        if !monitor.record(metrics, || network.snapshot()) {
+            break;
+        }
+    }
+
+    let (history, best) = monitor.finish();
+    if let Some(best) = best {
+        network.restore(&best)?;
+    }
+    Ok(history)
+}
+
+/// Stack input/target pairs into `[samples, features]` tensors
+fn stack(samples: &[(Vec<f32>, Vec<f32>)]) -> Result<(Tensor, Tensor)> {
+    let Some((first_input, first_target)) = samples.first() else {
+        return Err(NeuralError::TrainingError {
+            message: "no samples to stack".to_string(),
+        });
+    };
+    let (input_width, target_width) = (first_input.len(), first_target.len());
+    let mut inputs = Vec::with_capacity(samples.len() * input_width);
+    let mut targets = Vec::with_capacity(samples.len() * target_width);
+    for (input, target) in samples {
+        if input.len() != input_width || target.len() != target_width {
+            return Err(NeuralError::ShapeMismatch {
+                expected: vec![input_width, target_width],
+                actual: vec![input.len(), target.len()],
+            });
+        }
+        inputs.extend_from_slice(input);
+        targets.extend_from_slice(target);
+    }
+    Ok((
+        Tensor::from_vec(inputs, vec![samples.len(), input_width]),
+        Tensor::from_vec(targets, vec![samples.len(), target_width]),
+    ))
+}
+
+/// Split a `[samples, features]` tensor into rows
+fn rows(tensor: &Tensor) -> Vec<Vec<f32>> {
+    let width = tensor.shape().last().copied().unwrap_or(1).max(1);
+    tensor.data().chunks(width).map(<[f32]>::to_vec).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataLoaderConfig;
+    use crate::network::EphemeralNetwork;
+    use crate::optimizer::Adam;
+    use crate::types::Architecture;
+
+    fn metrics(epoch: usize, val_loss: f32) -> EpochMetrics {
+        EpochMetrics {
+            epoch,
+            train_loss: 1.0 / (epoch + 1) as f32,
+            val_loss,
+            val_accuracy: None,
+        }
+    }
+
+    #[test]
+    fn test_stops_after_plateau_and_keeps_best_weights() {
+        let mut monitor = EarlyStoppingMonitor::new(EarlyStopping {
+            patience: 3,
+            min_delta: 0.005,
+            max_epochs: 20,
+        });
+
+        // Validation loss bottoms out at epoch 2; epoch 5 is within min_delta
+        let val_losses = [1.0, 0.8, 0.6, 0.61, 0.62, 0.598, 0.63, 0.5];
+        let mut stopped_at = None;
+        for (epoch, val_loss) in val_losses.into_iter().enumerate() {
+            // Stand-in for the network's weights after this epoch
+            let weights = vec![epoch as f32; 3];
+            if !monitor.record(metrics(epoch, val_loss), || weights) {
+                stopped_at = Some(epoch);
+                break;
+            }
+        }
+
+        assert_eq!(stopped_at, Some(5));
+        assert_eq!(monitor.best_metrics().unwrap().epoch, 2);
+        let (history, best) = monitor.finish();
+        assert_eq!(history.len(), 6);
+        assert_eq!(best, Some(vec![2.0; 3]));
+    }
+
+    #[test]
+    fn test_max_epochs_bounds_training() {
+        let mut monitor = EarlyStoppingMonitor::new(EarlyStopping {
+            patience: 10,
+            min_delta: 0.0,
+            max_epochs: 3,
+        });
+        assert!(monitor.record(metrics(0, 0.9), || 0));
+        assert!(monitor.record(metrics(1, 0.8), || 1));
+        assert!(!monitor.record(metrics(2, 0.7), || 2));
+        assert_eq!(monitor.finish().1, Some(2));
+    }
+
+    #[test]
+    fn test_fit_reports_validation_metrics_and_restores_best_weights() {
+        // Two separable clusters, one-hot targets
+        let sample = |i: usize| {
+            let class = i % 2;
+            let offset = (i / 2) as f32 * 0.01;
+            let x = if class == 0 { -1.0 + offset } else { 1.0 - offset };
+            let mut target = vec![0.0; 2];
+            target[class] = 1.0;
+            (vec![x, -x], target)
+        };
+        let train: Vec<_> = (0..40).map(sample).collect();
+        let validation: Vec<_> = (40..50).map(sample).collect();
+
+        let architecture = Architecture::feedforward()
+            .input_size(2)
+            .hidden_layers(vec![4])
+            .output_size(2)
+            .output_activation("sigmoid");
+        let mut network = EphemeralNetwork::with_seed(architecture, 5)
+            .unwrap()
+            .with_optimizer(Adam::new(0.05));
+        let mut loader = DataLoader::new(train, DataLoaderConfig {
+            batch_size: 8,
+            seed: Some(5),
+            ..Default::default()
+        });
+
+        let history = fit(&mut network, &mut loader, &validation, EarlyStopping {
+            patience: 3,
+            min_delta: 0.0,
+            max_epochs: 50,
+        })
+        .unwrap();
+
+        assert!(!history.is_empty() && history.len() <= 50);
+        assert!(history.iter().enumerate().all(|(i, m)| m.epoch == i && m.val_accuracy.is_some()));
+        let best = history.iter().min_by(|a, b| a.val_loss.total_cmp(&b.val_loss)).unwrap();
+        assert_eq!(best.val_accuracy, Some(1.0));
+
+        // The network is left with the weights of the best epoch
+        let (val_input, val_target) = stack(&validation).unwrap();
+        let restored = network.evaluate(&val_input, &val_target).unwrap();
+        assert!((restored - best.val_loss).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_classification_accuracy() {
+        let outputs = vec![vec![0.1, 0.9], vec![0.8, 0.2], vec![0.3, 0.7]];
+        let targets = vec![vec![0.0, 1.0], vec![0.0, 1.0], vec![0.0, 1.0]];
+        assert!((classification_accuracy(&outputs, &targets) - 2.0 / 3.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,62 @@
+//! Core traits implemented by networks, layers, optimizers and activations.
+
+use ndarray::Array2;
+
+use crate::error::Result;
+use crate::types::Tensor;
+
+/// Element-wise activation function.
+pub trait ActivationFunction {
+    /// Apply the function to a pre-activation value.
+    fn activate(&self, x: f32) -> f32;
+
+    /// Derivative expressed in terms of the activated output.
+    fn derivative(&self, output: f32) -> f32;
+}
+
+/// A differentiable layer operating on `[batch, features]` matrices.
+pub trait Layer: Send {
+    /// Number of input features.
+    fn input_size(&self) -> usize;
+
+    /// Number of output features.
+    fn output_size(&self) -> usize;
+
+    /// Compute the layer output, caching what `backward` needs.
+    fn forward(&mut self, input: &Array2<f32>) -> Result<Array2<f32>>;
+
+    /// Store parameter gradients and return the gradient for the layer input.
+    fn backward(&mut self, grad_output: &Array2<f32>) -> Result<Array2<f32>>;
+
+    /// Parameters paired with their latest gradients.
+    fn parameters_mut(&mut self) -> Vec<(&mut [f32], &[f32])>;
+
+    /// Copy of the parameters.
+    fn parameters(&self) -> Vec<Vec<f32>>;
+}
+
+/// Gradient-based parameter update rule.
+pub trait Optimizer: Send {
+    /// Update one parameter group in place.
+    ///
+    /// `key` identifies the group across steps so per-parameter state can be kept.
+    fn step(&mut self, key: usize, params: &mut [f32], grads: &[f32]);
+}
+
+/// A trainable network.
+pub trait NeuralNetwork {
+    /// Run a forward pass.
+    fn forward(&mut self, input: &Tensor) -> Result<Tensor>;
+
+    /// Train on one batch, returning the loss before the update.
+    fn train_step(&mut self, input: &Tensor, target: &Tensor) -> Result<f32>;
+
+    /// Loss on a batch without updating the network.
+    fn evaluate(&mut self, input: &Tensor, target: &Tensor) -> Result<f32>;
+
+    /// Copy of every parameter group, for checkpointing.
+    fn snapshot(&self) -> Vec<Vec<f32>>;
+
+    /// Restore parameters taken with [`snapshot`](Self::snapshot).
+    fn restore(&mut self, snapshot: &[Vec<f32>]) -> Result<()>;
+}
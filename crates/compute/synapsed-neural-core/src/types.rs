@@ -0,0 +1,165 @@
+//! Core data types: tensors, parameters and architecture descriptions.
+
+use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NeuralError, Result};
+
+/// Weight matrix of a dense layer, shaped `[inputs, outputs]`.
+pub type Weight = Array2<f32>;
+
+/// Bias vector of a dense layer, one entry per output.
+pub type Bias = Array1<f32>;
+
+/// Dense row-major tensor of `f32` values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tensor {
+    data: Vec<f32>,
+    shape: Vec<usize>,
+}
+
+impl Tensor {
+    /// Create a tensor from values and a shape.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shape does not describe exactly `data.len()` values.
+    pub fn from_vec(data: Vec<f32>, shape: Vec<usize>) -> Self {
+        assert_eq!(
+            shape.iter().product::<usize>(),
+            data.len(),
+            "tensor shape {:?} does not match {} values",
+            shape,
+            data.len()
+        );
+        Self { data, shape }
+    }
+
+    /// Create a tensor filled with zeros.
+    pub fn zeros(shape: Vec<usize>) -> Self {
+        Self {
+            data: vec![0.0; shape.iter().product()],
+            shape,
+        }
+    }
+
+    /// Tensor dimensions.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Values in row-major order.
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the tensor holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Index of the largest value.
+    pub fn argmax(&self) -> Option<usize> {
+        self.data
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// View as a `[rows, features]` matrix, treating a vector as a single row.
+    pub(crate) fn to_matrix(&self) -> Result<Array2<f32>> {
+        let (rows, cols) = match self.shape.as_slice() {
+            [features] => (1, *features),
+            [rows, features] => (*rows, *features),
+            _ => {
+                return Err(NeuralError::ShapeMismatch {
+                    expected: vec![0, 0],
+                    actual: self.shape.clone(),
+                })
+            }
+        };
+        Ok(Array2::from_shape_vec((rows, cols), self.data.clone())
+            .expect("tensor shape checked on construction"))
+    }
+
+    /// Build a tensor from a `[rows, features]` matrix.
+    pub(crate) fn from_matrix(matrix: Array2<f32>) -> Self {
+        let shape = matrix.shape().to_vec();
+        Self {
+            data: matrix.into_iter().collect(),
+            shape,
+        }
+    }
+}
+
+/// Network architecture description.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Architecture {
+    /// Number of input features
+    pub input_size: usize,
+    /// Sizes of the hidden layers, in order
+    pub hidden_layers: Vec<usize>,
+    /// Number of outputs
+    pub output_size: usize,
+    /// Activation of the hidden layers
+    pub activation: String,
+    /// Activation of the output layer
+    pub output_activation: String,
+}
+
+impl Architecture {
+    /// Start describing a feedforward network.
+    pub fn feedforward() -> Self {
+        Self {
+            input_size: 1,
+            hidden_layers: Vec::new(),
+            output_size: 1,
+            activation: "relu".to_string(),
+            output_activation: "linear".to_string(),
+        }
+    }
+
+    /// Set the number of input features.
+    pub fn input_size(mut self, size: usize) -> Self {
+        self.input_size = size;
+        self
+    }
+
+    /// Set the hidden layer sizes.
+    pub fn hidden_layers(mut self, sizes: Vec<usize>) -> Self {
+        self.hidden_layers = sizes;
+        self
+    }
+
+    /// Set the number of outputs.
+    pub fn output_size(mut self, size: usize) -> Self {
+        self.output_size = size;
+        self
+    }
+
+    /// Set the hidden layer activation by name.
+    pub fn activation(mut self, name: &str) -> Self {
+        self.activation = name.to_string();
+        self
+    }
+
+    /// Set the output layer activation by name.
+    pub fn output_activation(mut self, name: &str) -> Self {
+        self.output_activation = name.to_string();
+        self
+    }
+
+    /// Layer widths from the input to the output.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        std::iter::once(self.input_size)
+            .chain(self.hidden_layers.iter().copied())
+            .chain(std::iter::once(self.output_size))
+            .collect()
+    }
+}
@@ -1,16 +1,50 @@
+use synapsed_neural_core::optimizer::Adam;
 use synapsed_neural_core::*;
 
 #[tokio::test]
 async fn test_neural_core_basic() {
-    // Basic integration test for neural core
-    assert!(true);
+    // Fit y = 2x - 1 from shuffled mini-batches
+    let samples: Vec<(f32, f32)> = (0..32).map(|i| {
+        let x = i as f32 / 32.0;
+        (x, 2.0 * x - 1.0)
+    }).collect();
+    let mut loader = DataLoader::new(samples, DataLoaderConfig {
+        batch_size: 8,
+        seed: Some(3),
+        ..Default::default()
+    });
+
+    let architecture = Architecture::feedforward().input_size(1).output_size(1);
+    let mut network = EphemeralNetwork::with_seed(architecture, 3)
+        .unwrap()
+        .with_optimizer(Adam::new(0.05));
+
+    let mut last_loss = f32::MAX;
+    for _ in 0..200 {
+        for batch in loader.epoch() {
+            let (inputs, targets): (Vec<f32>, Vec<f32>) = batch.into_iter().unzip();
+            let rows = inputs.len();
+            let input = Tensor::from_vec(inputs, vec![rows, 1]);
+            let target = Tensor::from_vec(targets, vec![rows, 1]);
+            last_loss = network.train_step(&input, &target).unwrap();
+        }
+    }
+    assert!(last_loss < 1e-3, "final loss {}", last_loss);
 }
 
 #[tokio::test]
 async fn test_neural_types() {
     // Test that core types are accessible
-    let result = std::panic::catch_unwind(|| {
-        format!("Neural core module loaded");
-    });
-    assert!(result.is_ok());
-}
\ No newline at end of file
+    let tensor = Tensor::from_vec(vec![0.1, 0.7, 0.2], vec![1, 3]);
+    assert_eq!(tensor.shape(), &[1, 3]);
+    assert_eq!(tensor.argmax(), Some(1));
+
+    let architecture = Architecture::feedforward().input_size(3).hidden_layers(vec![4]).output_size(2);
+    assert_eq!(architecture.layer_sizes(), vec![3, 4, 2]);
+
+    let invalid = Architecture::feedforward().activation("softsign");
+    assert!(matches!(
+        EphemeralNetwork::new(invalid),
+        Err(NeuralError::UnknownActivation { .. })
+    ));
+}
@@ -0,0 +1,24 @@
+#!/bin/bash
+# Build the scalar and SIMD browser packages of synapsed-wasm
+#
+# Engines without WebAssembly SIMD reject a module containing any SIMD
+# instruction, so both variants are shipped and js/loader.js picks one.
+
+set -e
+
+FEATURES="${FEATURES:-crypto-modules}"
+
+echo "Building scalar package..."
+wasm-pack build --target web --out-dir pkg/scalar -- --features "$FEATURES"
+
+echo "Building SIMD package..."
+RUSTFLAGS="-C target-feature=+simd128" \
+    wasm-pack build --target web --out-dir pkg/simd -- --features "$FEATURES,simd"
+
+cp js/loader.js pkg/
+
+echo "Build complete!"
+echo "Packages available in:"
+echo "  - pkg/scalar/ (any WebAssembly engine)"
+echo "  - pkg/simd/ (engines with 128-bit SIMD)"
+echo "  - pkg/loader.js (picks one at run time)"
@@ -0,0 +1,25 @@
+// Load the SIMD build of synapsed-wasm where the engine supports it, and the
+// scalar build otherwise. Expects the layout produced by build.sh.
+
+// Smallest module using a 128-bit SIMD instruction (`i8x16.popcnt`)
+const SIMD_PROBE = new Uint8Array([
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic, version
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7b, // type: () -> v128
+    0x03, 0x02, 0x01, 0x00, // function
+    0x0a, 0x0a, 0x01, 0x08, 0x00, 0x41, 0x00, 0xfd, 0x0f, 0xfd, 0x62, 0x0b, // code
+]);
+
+export function simdSupported() {
+    try {
+        return WebAssembly.validate(SIMD_PROBE);
+    } catch {
+        return false;
+    }
+}
+
+export default async function load() {
+    const variant = simdSupported() ? 'simd' : 'scalar';
+    const module = await import(`./${variant}/synapsed_wasm.js`);
+    await module.default();
+    return module;
+}
@@ -18,6 +18,22 @@ use synapsed_crypto::*;
 use crate::error::{WasmError, WasmResult};
 use crate::types::{HostFunction, WasmValue};
 
+pub mod kyber768;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "simd"))]
+mod simd;
+
+/// Check whether this build uses WebAssembly 128-bit SIMD
+///
+/// A module containing any SIMD instruction fails validation on engines
+/// without SIMD, so support cannot be detected from inside the module. The
+/// crate is built twice instead (see `build.sh`) and `js/loader.js` picks the
+/// artifact the engine accepts.
+#[wasm_bindgen]
+pub fn simd_enabled() -> bool {
+    cfg!(all(target_arch = "wasm32", target_feature = "simd128", feature = "simd"))
+}
+
 /// Browser crypto manager for P2P secure operations
 pub struct BrowserCryptoManager {
     /// Web Crypto API interface
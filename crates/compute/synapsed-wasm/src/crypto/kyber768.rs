@@ -0,0 +1,339 @@
+//! Kyber768 (ML-KEM-768) key generation for the browser
+//!
+//! Keys are derived deterministically from a 64-byte seed `d || z`, as in
+//! `ML-KEM.KeyGen_internal` from FIPS 203. The polynomial arithmetic runs on
+//! WASM SIMD in builds with the `simd` feature and the `simd128` target
+//! feature (see [`simd_enabled`](super::simd_enabled)), and on the scalar path
+//! otherwise. Both paths produce identical keys.
+
+use sha3::digest::{Digest, ExtendableOutput, Update, XofReader};
+use sha3::{Sha3_256, Sha3_512, Shake128, Shake256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Module dimension
+pub(crate) const K: usize = 3;
+/// Polynomial degree
+pub(crate) const N: usize = 256;
+/// Modulus
+pub(crate) const Q: i16 = 3329;
+/// Noise parameter for key generation
+const ETA1: usize = 2;
+/// Size of an encoded polynomial
+const POLY_BYTES: usize = 384;
+
+/// Seed size in bytes (`d || z`)
+pub const SEED_SIZE: usize = 64;
+/// Public key size in bytes
+pub const PUBLIC_KEY_SIZE: usize = K * POLY_BYTES + 32;
+/// Secret key size in bytes
+pub const SECRET_KEY_SIZE: usize = K * POLY_BYTES + PUBLIC_KEY_SIZE + 64;
+
+/// q^-1 mod 2^16
+pub(crate) const QINV: i16 = -3327;
+/// 2^32 mod q, for conversion to Montgomery form
+pub(crate) const MONT_SQUARED: i16 = 1353;
+/// round(2^26 / q)
+pub(crate) const BARRETT_V: i16 = 20159;
+
+/// Powers of the 256th root of unity in Montgomery form, bit-reversed order
+pub(crate) const ZETAS: [i16; 128] = [
+    -1044, -758, -359, -1517, 1493, 1422, 287, 202,
+    -171, 622, 1577, 182, 962, -1202, -1474, 1468,
+    573, -1325, 264, 383, -829, 1458, -1602, -130,
+    -681, 1017, 732, 608, -1542, 411, -205, -1571,
+    1223, 652, -552, 1015, -1293, 1491, -282, -1544,
+    516, -8, -320, -666, -1618, -1162, 126, 1469,
+    -853, -90, -271, 830, 107, -1421, -247, -951,
+    -398, 961, -1508, -725, 448, -1065, 677, -1275,
+    -1103, 430, 555, 843, -1251, 871, 1550, 105,
+    422, 587, 177, -235, -291, -460, 1574, 1653,
+    -246, 778, 1159, -147, -777, 1483, -602, 1119,
+    -1590, 644, -872, 349, 418, 329, -156, -75,
+    817, 1097, 603, 610, 1322, -1285, -1465, 384,
+    -1215, -136, 1218, -1335, -874, 220, -1187, -1659,
+    -1185, -1530, -1278, 794, -1510, -854, -870, 478,
+    -108, -308, 996, 991, 958, -1460, 1522, 1628,
+];
+
+/// Polynomial with coefficients in the NTT or normal domain
+pub(crate) type Poly = [i16; N];
+
+/// Kyber768 key pair
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct KeyPair {
+    /// Encoded public key
+    pub public_key: Vec<u8>,
+    /// Encoded secret key
+    pub secret_key: Vec<u8>,
+}
+
+/// Polynomial arithmetic used by key generation
+///
+/// Implementations must agree bit-for-bit; they differ only in speed.
+pub(crate) trait PolyArith {
+    /// Forward NTT followed by Barrett reduction
+    fn ntt(p: &mut Poly);
+    /// `r = sum(a[i] * b[i])` in the NTT domain, reduced
+    fn basemul_acc(r: &mut Poly, a: &[Poly; K], b: &[Poly; K]);
+    /// Convert to Montgomery form
+    fn tomont(p: &mut Poly);
+    /// `r += a`
+    fn add(r: &mut Poly, a: &Poly);
+    /// Barrett-reduce every coefficient
+    fn reduce(p: &mut Poly);
+}
+
+/// Portable scalar arithmetic
+pub(crate) struct Scalar;
+
+/// Montgomery reduction: a * 2^-16 mod q
+#[inline]
+pub(crate) fn montgomery_reduce(a: i32) -> i16 {
+    let t = (a as i16).wrapping_mul(QINV);
+    ((a - t as i32 * Q as i32) >> 16) as i16
+}
+
+/// Barrett reduction to a representative in about [-q/2, q/2]
+#[inline]
+pub(crate) fn barrett_reduce(a: i16) -> i16 {
+    let t = ((BARRETT_V as i32 * a as i32 + (1 << 25)) >> 26) as i16;
+    a.wrapping_sub(t.wrapping_mul(Q))
+}
+
+/// Montgomery multiplication
+#[inline]
+pub(crate) fn fqmul(a: i16, b: i16) -> i16 {
+    montgomery_reduce(a as i32 * b as i32)
+}
+
+/// One NTT layer, butterflies of width `len`
+pub(crate) fn ntt_layer(p: &mut Poly, len: usize, k: &mut usize) {
+    for start in (0..N).step_by(2 * len) {
+        let zeta = ZETAS[*k];
+        *k += 1;
+        for j in start..start + len {
+            let t = fqmul(zeta, p[j + len]);
+            p[j + len] = p[j].wrapping_sub(t);
+            p[j] = p[j].wrapping_add(t);
+        }
+    }
+}
+
+/// Product of two degree-one polynomials modulo `X^2 - zeta`
+#[inline]
+pub(crate) fn basemul(r: &mut [i16], a: &[i16], b: &[i16], zeta: i16) {
+    r[0] = fqmul(fqmul(a[1], b[1]), zeta).wrapping_add(fqmul(a[0], b[0]));
+    r[1] = fqmul(a[0], b[1]).wrapping_add(fqmul(a[1], b[0]));
+}
+
+impl Scalar {
+    fn basemul_montgomery(r: &mut Poly, a: &Poly, b: &Poly) {
+        for i in 0..N / 4 {
+            let zeta = ZETAS[64 + i];
+            basemul(&mut r[4 * i..], &a[4 * i..], &b[4 * i..], zeta);
+            basemul(&mut r[4 * i + 2..], &a[4 * i + 2..], &b[4 * i + 2..], -zeta);
+        }
+    }
+}
+
+impl PolyArith for Scalar {
+    fn ntt(p: &mut Poly) {
+        let mut k = 1;
+        let mut len = 128;
+        while len >= 2 {
+            ntt_layer(p, len, &mut k);
+            len >>= 1;
+        }
+        Self::reduce(p);
+    }
+
+    fn basemul_acc(r: &mut Poly, a: &[Poly; K], b: &[Poly; K]) {
+        Self::basemul_montgomery(r, &a[0], &b[0]);
+        let mut t = [0i16; N];
+        for i in 1..K {
+            Self::basemul_montgomery(&mut t, &a[i], &b[i]);
+            Self::add(r, &t);
+        }
+        Self::reduce(r);
+    }
+
+    fn tomont(p: &mut Poly) {
+        for c in p.iter_mut() {
+            *c = fqmul(*c, MONT_SQUARED);
+        }
+    }
+
+    fn add(r: &mut Poly, a: &Poly) {
+        for (x, y) in r.iter_mut().zip(a.iter()) {
+            *x = x.wrapping_add(*y);
+        }
+    }
+
+    fn reduce(p: &mut Poly) {
+        for c in p.iter_mut() {
+            *c = barrett_reduce(*c);
+        }
+    }
+}
+
+/// Generate a key pair from `seed`, on SIMD in SIMD builds
+pub fn keypair_from_seed(seed: &[u8; SEED_SIZE]) -> KeyPair {
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "simd"))]
+    {
+        keypair_from_seed_simd(seed)
+    }
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128", feature = "simd")))]
+    {
+        keypair_from_seed_scalar(seed)
+    }
+}
+
+/// Generate a key pair from `seed` using scalar arithmetic only
+pub fn keypair_from_seed_scalar(seed: &[u8; SEED_SIZE]) -> KeyPair {
+    keypair_with::<Scalar>(seed)
+}
+
+/// Generate a key pair from `seed` using WASM SIMD arithmetic
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "simd"))]
+pub fn keypair_from_seed_simd(seed: &[u8; SEED_SIZE]) -> KeyPair {
+    keypair_with::<super::simd::Simd128>(seed)
+}
+
+fn keypair_with<A: PolyArith>(seed: &[u8; SEED_SIZE]) -> KeyPair {
+    let (d, z) = seed.split_at(32);
+
+    // (rho, sigma) = G(d || k)
+    let mut g = Sha3_512::new();
+    Digest::update(&mut g, d);
+    Digest::update(&mut g, [K as u8]);
+    let mut rho_sigma = g.finalize();
+    let (rho, sigma) = rho_sigma.split_at(32);
+
+    let a = expand_matrix(rho);
+
+    let mut s = [[0i16; N]; K];
+    let mut e = [[0i16; N]; K];
+    for (i, p) in s.iter_mut().enumerate() {
+        sample_noise(p, sigma, i as u8);
+    }
+    for (i, p) in e.iter_mut().enumerate() {
+        sample_noise(p, sigma, (K + i) as u8);
+    }
+    for p in s.iter_mut().chain(e.iter_mut()) {
+        A::ntt(p);
+    }
+
+    let mut t = [[0i16; N]; K];
+    for i in 0..K {
+        A::basemul_acc(&mut t[i], &a[i], &s);
+        A::tomont(&mut t[i]);
+        A::add(&mut t[i], &e[i]);
+        A::reduce(&mut t[i]);
+    }
+
+    let mut public_key = Vec::with_capacity(PUBLIC_KEY_SIZE);
+    for p in &t {
+        encode_poly(p, &mut public_key);
+    }
+    public_key.extend_from_slice(rho);
+
+    let mut secret_key = Vec::with_capacity(SECRET_KEY_SIZE);
+    for p in &s {
+        encode_poly(p, &mut secret_key);
+    }
+    secret_key.extend_from_slice(&public_key);
+    secret_key.extend_from_slice(&Sha3_256::digest(&public_key));
+    secret_key.extend_from_slice(z);
+
+    rho_sigma.zeroize();
+    s.zeroize();
+    e.zeroize();
+
+    KeyPair { public_key, secret_key }
+}
+
+/// Sample matrix A in the NTT domain from `rho`
+fn expand_matrix(rho: &[u8]) -> [[Poly; K]; K] {
+    let mut a = [[[0i16; N]; K]; K];
+    for (i, row) in a.iter_mut().enumerate() {
+        for (j, p) in row.iter_mut().enumerate() {
+            let mut xof = Shake128::default();
+            xof.update(rho);
+            xof.update(&[j as u8, i as u8]);
+            let mut reader = xof.finalize_xof();
+
+            let mut block = [0u8; 168];
+            let mut filled = 0;
+            while filled < N {
+                reader.read(&mut block);
+                for chunk in block.chunks_exact(3) {
+                    let d1 = (chunk[0] as u16 | (chunk[1] as u16) << 8) & 0xfff;
+                    let d2 = (chunk[1] as u16 >> 4 | (chunk[2] as u16) << 4) & 0xfff;
+                    for d in [d1, d2] {
+                        if filled < N && d < Q as u16 {
+                            p[filled] = d as i16;
+                            filled += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    a
+}
+
+/// Sample a noise polynomial from the centered binomial distribution
+fn sample_noise(p: &mut Poly, sigma: &[u8], nonce: u8) {
+    let mut prf = Shake256::default();
+    prf.update(sigma);
+    prf.update(&[nonce]);
+    let mut buf = [0u8; ETA1 * N / 4];
+    prf.finalize_xof().read(&mut buf);
+
+    for (i, chunk) in buf.chunks_exact(4).enumerate() {
+        let t = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let d = (t & 0x5555_5555) + ((t >> 1) & 0x5555_5555);
+        for j in 0..8 {
+            let x = ((d >> (4 * j)) & 3) as i16;
+            let y = ((d >> (4 * j + 2)) & 3) as i16;
+            p[8 * i + j] = x - y;
+        }
+    }
+    buf.zeroize();
+}
+
+/// Append the 12-bit encoding of `p`, after mapping coefficients to [0, q)
+fn encode_poly(p: &Poly, out: &mut Vec<u8>) {
+    for pair in p.chunks_exact(2) {
+        let t0 = (pair[0] + ((pair[0] >> 15) & Q)) as u16;
+        let t1 = (pair[1] + ((pair[1] >> 15) & Q)) as u16;
+        out.push(t0 as u8);
+        out.push((t0 >> 8 | t1 << 4) as u8);
+        out.push((t1 >> 4) as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keypair_from_seed_is_deterministic() {
+        let seed = [7u8; SEED_SIZE];
+        let first = keypair_from_seed_scalar(&seed);
+        let second = keypair_from_seed_scalar(&seed);
+
+        assert_eq!(first.public_key.len(), PUBLIC_KEY_SIZE);
+        assert_eq!(first.secret_key.len(), SECRET_KEY_SIZE);
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.secret_key, second.secret_key);
+
+        // The secret key embeds the public key, its hash and z
+        let pk_start = K * POLY_BYTES;
+        assert_eq!(&first.secret_key[pk_start..pk_start + PUBLIC_KEY_SIZE], &first.public_key[..]);
+        assert_eq!(&first.secret_key[SECRET_KEY_SIZE - 32..], &seed[32..]);
+
+        let other = keypair_from_seed_scalar(&[8u8; SEED_SIZE]);
+        assert_ne!(first.public_key, other.public_key);
+    }
+}
@@ -0,0 +1,151 @@
+//! WASM SIMD (128-bit) polynomial arithmetic for Kyber
+//!
+//! Each operation mirrors the scalar one in [`kyber768`](super::kyber768) lane
+//! by lane, eight coefficients at a time, so results are identical. The two
+//! innermost NTT layers work within a single vector and use the scalar
+//! butterflies.
+
+use core::arch::wasm32::*;
+
+use super::kyber768::{
+    ntt_layer, PolyArith, Poly, BARRETT_V, K, MONT_SQUARED, N, Q, QINV, ZETAS,
+};
+
+/// Arithmetic on 128-bit WASM SIMD vectors
+pub(crate) struct Simd128;
+
+/// Basemul zetas, one per coefficient pair: `zeta, -zeta` for each entry of
+/// the last NTT layer
+const BASEMUL_ZETAS: [i16; N / 2] = {
+    let mut zetas = [0i16; N / 2];
+    let mut i = 0;
+    while i < N / 4 {
+        zetas[2 * i] = ZETAS[64 + i];
+        zetas[2 * i + 1] = -ZETAS[64 + i];
+        i += 1;
+    }
+    zetas
+};
+
+#[inline]
+#[allow(unsafe_code)]
+fn load(p: &[i16]) -> v128 {
+    assert!(p.len() >= 8);
+    // SAFETY: `p` holds at least eight i16s, and v128 loads are unaligned
+    unsafe { v128_load(p.as_ptr() as *const v128) }
+}
+
+#[inline]
+#[allow(unsafe_code)]
+fn store(p: &mut [i16], v: v128) {
+    assert!(p.len() >= 8);
+    // SAFETY: `p` holds at least eight i16s, and v128 stores are unaligned
+    unsafe { v128_store(p.as_mut_ptr() as *mut v128, v) }
+}
+
+/// High 16 bits of each 32-bit lane product
+#[inline]
+fn mulhi(a: v128, b: v128) -> v128 {
+    let lo = i32x4_shr(i32x4_extmul_low_i16x8(a, b), 16);
+    let hi = i32x4_shr(i32x4_extmul_high_i16x8(a, b), 16);
+    i16x8_narrow_i32x4(lo, hi)
+}
+
+/// Lane-wise Montgomery multiplication
+///
+/// The low halves of `a * b` and `t * q` are equal by construction, so
+/// subtracting the high halves gives the same result as the scalar reduction.
+#[inline]
+fn fqmul(a: v128, b: v128) -> v128 {
+    let t = i16x8_mul(i16x8_mul(a, b), i16x8_splat(QINV));
+    i16x8_sub(mulhi(a, b), mulhi(t, i16x8_splat(Q)))
+}
+
+/// Lane-wise Barrett reduction
+#[inline]
+fn barrett_reduce(a: v128) -> v128 {
+    let v = i16x8_splat(BARRETT_V);
+    let round = i32x4_splat(1 << 25);
+    let lo = i32x4_shr(i32x4_add(i32x4_extmul_low_i16x8(a, v), round), 26);
+    let hi = i32x4_shr(i32x4_add(i32x4_extmul_high_i16x8(a, v), round), 26);
+    let t = i16x8_narrow_i32x4(lo, hi);
+    i16x8_sub(a, i16x8_mul(t, i16x8_splat(Q)))
+}
+
+impl Simd128 {
+    fn basemul_montgomery(r: &mut Poly, a: &Poly, b: &Poly) {
+        for c in (0..N).step_by(16) {
+            let (a_lo, a_hi) = (load(&a[c..]), load(&a[c + 8..]));
+            let (b_lo, b_hi) = (load(&b[c..]), load(&b[c + 8..]));
+            // Split eight coefficient pairs into their constant and linear terms
+            let a0 = i16x8_shuffle::<0, 2, 4, 6, 8, 10, 12, 14>(a_lo, a_hi);
+            let a1 = i16x8_shuffle::<1, 3, 5, 7, 9, 11, 13, 15>(a_lo, a_hi);
+            let b0 = i16x8_shuffle::<0, 2, 4, 6, 8, 10, 12, 14>(b_lo, b_hi);
+            let b1 = i16x8_shuffle::<1, 3, 5, 7, 9, 11, 13, 15>(b_lo, b_hi);
+            let zeta = load(&BASEMUL_ZETAS[c / 2..]);
+
+            let r0 = i16x8_add(fqmul(fqmul(a1, b1), zeta), fqmul(a0, b0));
+            let r1 = i16x8_add(fqmul(a0, b1), fqmul(a1, b0));
+
+            store(&mut r[c..], i16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(r0, r1));
+            store(&mut r[c + 8..], i16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(r0, r1));
+        }
+    }
+}
+
+impl PolyArith for Simd128 {
+    fn ntt(p: &mut Poly) {
+        let mut k = 1;
+        let mut len = 128;
+        while len >= 8 {
+            for start in (0..N).step_by(2 * len) {
+                let zeta = i16x8_splat(ZETAS[k]);
+                k += 1;
+                for j in (start..start + len).step_by(8) {
+                    let t = fqmul(zeta, load(&p[j + len..]));
+                    let x = load(&p[j..]);
+                    store(&mut p[j + len..], i16x8_sub(x, t));
+                    store(&mut p[j..], i16x8_add(x, t));
+                }
+            }
+            len >>= 1;
+        }
+        while len >= 2 {
+            ntt_layer(p, len, &mut k);
+            len >>= 1;
+        }
+        Self::reduce(p);
+    }
+
+    fn basemul_acc(r: &mut Poly, a: &[Poly; K], b: &[Poly; K]) {
+        Self::basemul_montgomery(r, &a[0], &b[0]);
+        let mut t = [0i16; N];
+        for i in 1..K {
+            Self::basemul_montgomery(&mut t, &a[i], &b[i]);
+            Self::add(r, &t);
+        }
+        Self::reduce(r);
+    }
+
+    fn tomont(p: &mut Poly) {
+        let f = i16x8_splat(MONT_SQUARED);
+        for c in (0..N).step_by(8) {
+            let x = fqmul(load(&p[c..]), f);
+            store(&mut p[c..], x);
+        }
+    }
+
+    fn add(r: &mut Poly, a: &Poly) {
+        for c in (0..N).step_by(8) {
+            let x = i16x8_add(load(&r[c..]), load(&a[c..]));
+            store(&mut r[c..], x);
+        }
+    }
+
+    fn reduce(p: &mut Poly) {
+        for c in (0..N).step_by(8) {
+            let x = barrett_reduce(load(&p[c..]));
+            store(&mut p[c..], x);
+        }
+    }
+}
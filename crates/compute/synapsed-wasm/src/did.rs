@@ -8,7 +8,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use async_trait::async_trait;
 use wasm_bindgen::prelude::*;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
+use hmac::{Hmac, Mac};
+use zeroize::Zeroizing;
 use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
 use rand::rngs::OsRng;
 
@@ -44,6 +46,41 @@ impl DidManager {
         Ok(manager)
     }
 
+    /// Restore a DID manager from a recovery phrase
+    ///
+    /// The manager holds the did:key derived from `phrase` along
+    /// `derivation_path`, so the same phrase and path restore the same DID on
+    /// any device.
+    pub fn from_mnemonic(phrase: &str, derivation_path: &str) -> WasmResult<Self> {
+        let keypair = KeyDerivation::derive_keypair_from_mnemonic(phrase, derivation_path)?;
+        let mut manager = Self::new()?;
+        manager.import_keypair(keypair);
+        Ok(manager)
+    }
+
+    /// Generate a new 24-word BIP39 recovery phrase
+    pub fn generate_mnemonic() -> WasmResult<String> {
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        getrandom::getrandom(entropy.as_mut())
+            .map_err(|_| WasmError::Cryptographic("Failed to generate entropy".to_string()))?;
+
+        let mnemonic = bip39::Mnemonic::from_entropy(entropy.as_ref())
+            .map_err(|e| WasmError::Cryptographic(format!("Failed to create mnemonic: {}", e)))?;
+        Ok(mnemonic.to_string())
+    }
+
+    /// Register the did:key of an existing keypair
+    pub fn import_keypair(&mut self, keypair: KeyPair) -> String {
+        let (did_id, document) = DidMethodKey::document_for_keypair(&keypair);
+
+        self.documents.insert(did_id.clone(), document);
+        self.keypairs.insert(did_id.clone(), keypair);
+        self.stats.dids_created += 1;
+
+        tracing::info!(did_id = %did_id, "DID imported");
+        did_id
+    }
+
     /// Register a DID method
     pub fn register_method(&mut self, method_name: &str, method: Box<dyn DidMethod>) -> WasmResult<()> {
         self.methods.insert(method_name.to_string(), method);
@@ -243,12 +280,9 @@ impl DidMethodKey {
     pub fn new() -> Self {
         Self
     }
-}
 
-#[async_trait]
-impl DidMethod for DidMethodKey {
-    async fn create_did(&self, _options: DidCreationOptions) -> WasmResult<(String, DidDocument, KeyPair)> {
-        let keypair = KeyPair::generate()?;
+    /// Build the did:key identifier and document for a keypair
+    fn document_for_keypair(keypair: &KeyPair) -> (String, DidDocument) {
         let public_key_bytes = keypair.public_key_bytes();
         
         // Create DID identifier from public key
@@ -276,6 +310,16 @@ impl DidMethod for DidMethodKey {
             updated: None,
         };
         
+        (did_id, document)
+    }
+}
+
+#[async_trait]
+impl DidMethod for DidMethodKey {
+    async fn create_did(&self, _options: DidCreationOptions) -> WasmResult<(String, DidDocument, KeyPair)> {
+        let keypair = KeyPair::generate()?;
+        let (did_id, document) = Self::document_for_keypair(&keypair);
+        
         Ok((did_id, document, keypair))
     }
     
@@ -535,6 +579,58 @@ impl KeyDerivation {
         KeyPair::from_secret_bytes(&derived_seed[..32])
     }
 
+    /// Derive keypair from a BIP39 mnemonic phrase
+    ///
+    /// The phrase is stretched into a seed with an empty passphrase, and the
+    /// key is derived along `derivation_path` with SLIP-0010.
+    pub fn derive_keypair_from_mnemonic(phrase: &str, derivation_path: &str) -> WasmResult<KeyPair> {
+        let mnemonic = bip39::Mnemonic::parse(phrase)
+            .map_err(|e| WasmError::Configuration(format!("Invalid mnemonic: {}", e)))?;
+        let seed = Zeroizing::new(mnemonic.to_seed(""));
+
+        let secret = Self::slip10_derive(seed.as_ref(), derivation_path)?;
+        KeyPair::from_secret_bytes(secret.as_ref())
+    }
+
+    /// SLIP-0010 Ed25519 derivation
+    ///
+    /// Ed25519 only defines hardened children, so every path segment must be
+    /// hardened (`44'` or `44h`).
+    fn slip10_derive(seed: &[u8], derivation_path: &str) -> WasmResult<Zeroizing<[u8; 32]>> {
+        const HARDENED: u32 = 1 << 31;
+
+        let mut segments = derivation_path.split('/');
+        if segments.next() != Some("m") {
+            return Err(WasmError::Configuration(format!("Derivation path '{}' must start with 'm'", derivation_path)));
+        }
+
+        let mut node = Zeroizing::new(Self::hmac_sha512(b"ed25519 seed", &[seed]));
+        for segment in segments {
+            let index = segment.strip_suffix('\'')
+                .or_else(|| segment.strip_suffix('h'))
+                .and_then(|index| index.parse::<u32>().ok())
+                .filter(|index| *index < HARDENED)
+                .ok_or_else(|| WasmError::Configuration(format!("Invalid hardened path segment '{}'", segment)))?;
+
+            let (key, chain_code) = node.split_at(32);
+            let child = Self::hmac_sha512(chain_code, &[&[0], key, &(index | HARDENED).to_be_bytes()]);
+            *node = child;
+        }
+
+        let mut secret = Zeroizing::new([0u8; 32]);
+        secret.copy_from_slice(&node[..32]);
+        Ok(secret)
+    }
+
+    fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+        let mut mac = Hmac::<Sha512>::new_from_slice(key)
+            .expect("HMAC accepts keys of any length");
+        for part in data {
+            mac.update(part);
+        }
+        mac.finalize().into_bytes().into()
+    }
+
     /// Derive multiple keys from master seed
     pub fn derive_multiple_keys(seed: &[u8], paths: &[&str]) -> WasmResult<Vec<KeyPair>> {
         let mut keypairs = Vec::new();
@@ -804,4 +900,41 @@ mod tests {
         let key1_again = KeyDerivation::derive_keypair_from_seed(seed, path1).unwrap();
         assert_eq!(key1.public_key_bytes(), key1_again.public_key_bytes());
     }
+
+    #[test]
+    fn test_did_from_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let path = "m/44'/0'/0'";
+
+        let did = DidManager::from_mnemonic(phrase, path).unwrap().list_dids();
+        let did_again = DidManager::from_mnemonic(phrase, path).unwrap().list_dids();
+        assert_eq!(did.len(), 1);
+        assert_eq!(did, did_again);
+
+        let other = DidManager::from_mnemonic(phrase, "m/44'/0'/1'").unwrap().list_dids();
+        assert_ne!(did, other);
+
+        // Ed25519 has no non-hardened derivation
+        assert!(DidManager::from_mnemonic(phrase, "m/44'/0'/0").is_err());
+        assert!(DidManager::from_mnemonic("abandon abandon", path).is_err());
+    }
+
+    #[test]
+    fn test_slip10_ed25519_vector() {
+        // SLIP-0010 test vector 1 for ed25519, chain m/0'
+        let seed: Vec<u8> = (0u8..16).collect();
+        let secret = KeyDerivation::slip10_derive(&seed, "m/0'").unwrap();
+        let secret_hex: String = secret.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(secret_hex, "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        let phrase = DidManager::generate_mnemonic().unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+        assert_ne!(phrase, DidManager::generate_mnemonic().unwrap());
+
+        let manager = DidManager::from_mnemonic(&phrase, "m/44'/0'/0'").unwrap();
+        assert_eq!(manager.list_dids().len(), 1);
+    }
 }
\ No newline at end of file
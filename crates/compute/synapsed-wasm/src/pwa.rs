@@ -5,6 +5,7 @@
 //! background synchronization optimized for P2P communication platforms.
 
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::Arc;
 use async_trait::async_trait;
 use wasm_bindgen::prelude::*;
@@ -20,6 +21,15 @@ use crate::error::{WasmError, WasmResult};
 use crate::types::{HostFunction, WasmValue};
 use crate::{DEFAULT_INDEXEDDB_QUOTA};
 
+pub mod offline_queue;
+
+pub use offline_queue::{
+    DrainOutcome, OfflineQueue, QueuedOperation, QueuedOperationKind, SyncOperationHandler,
+};
+
+/// Sync tag that drains the offline queue
+pub const OFFLINE_QUEUE_SYNC_TAG: &str = "offline-queue";
+
 /// PWA runtime for service worker and IndexedDB integration
 pub struct ServiceWorkerRuntime {
     /// Service worker registration
@@ -28,6 +38,10 @@ pub struct ServiceWorkerRuntime {
     message_channels: HashMap<String, MessageChannel>,
     /// Background sync registrations
     sync_registrations: HashMap<String, BackgroundSyncRegistration>,
+    /// Operations deferred while offline
+    offline_queue: OfflineQueue,
+    /// Re-runs queued operations during background sync
+    operation_handler: Option<Rc<dyn SyncOperationHandler>>,
     /// Runtime statistics
     stats: PwaStats,
 }
@@ -39,10 +53,29 @@ impl ServiceWorkerRuntime {
             registration: None,
             message_channels: HashMap::new(),
             sync_registrations: HashMap::new(),
+            offline_queue: OfflineQueue::default(),
+            operation_handler: None,
             stats: PwaStats::default(),
         })
     }
 
+    /// Use a different offline queue
+    pub fn with_offline_queue(mut self, queue: OfflineQueue) -> Self {
+        self.offline_queue = queue;
+        self
+    }
+
+    /// Set the handler that re-runs queued operations during background sync
+    pub fn with_operation_handler(mut self, handler: Rc<dyn SyncOperationHandler>) -> Self {
+        self.operation_handler = Some(handler);
+        self
+    }
+
+    /// Offline queue drained by background sync
+    pub fn offline_queue(&self) -> &OfflineQueue {
+        &self.offline_queue
+    }
+
     /// Register service worker
     pub async fn register_service_worker(&mut self, script_url: &str) -> WasmResult<()> {
         let window = web_sys::window()
@@ -94,7 +127,29 @@ impl ServiceWorkerRuntime {
     }
 
     /// Register background sync
+    ///
+    /// With a registered service worker, the tag is also registered with the
+    /// browser's `SyncManager`, which fires a `sync` event in the service worker
+    /// once connectivity returns, even if the page has been closed. Browsers
+    /// without Background Sync only get the local registration.
     pub async fn register_background_sync(&mut self, tag: String, options: BackgroundSyncOptions) -> WasmResult<()> {
+        if let Some(sw_registration) = self.registration.as_ref() {
+            let sync_manager = js_sys::Reflect::get(sw_registration, &"sync".into())
+                .unwrap_or(JsValue::UNDEFINED);
+            if sync_manager.is_undefined() {
+                tracing::warn!(tag = %tag, "Background Sync not supported by this browser");
+            } else {
+                let register: js_sys::Function = js_sys::Reflect::get(&sync_manager, &"register".into())
+                    .and_then(|register| register.dyn_into())
+                    .map_err(|_| WasmError::Configuration("SyncManager.register not available".to_string()))?;
+                let promise: Promise = register.call1(&sync_manager, &JsValue::from_str(&tag))
+                    .and_then(|promise| promise.dyn_into())
+                    .map_err(|_| WasmError::Configuration("Background sync registration failed".to_string()))?;
+                JsFuture::from(promise).await
+                    .map_err(|_| WasmError::Configuration("Background sync registration failed".to_string()))?;
+            }
+        }
+
         let registration = BackgroundSyncRegistration {
             tag: tag.clone(),
             options,
@@ -109,24 +164,62 @@ impl ServiceWorkerRuntime {
     }
 
     /// Handle background sync event
+    ///
+    /// Tags registered from a page are not known to the service worker's own
+    /// runtime, so unregistered tags run with default options. An error means
+    /// work is left over, and the browser should retry the sync later.
     pub async fn handle_sync_event(&self, tag: &str) -> WasmResult<()> {
-        if let Some(registration) = self.sync_registrations.get(tag) {
-            tracing::info!(tag = %tag, "Handling background sync event");
-            
-            // Execute sync operation based on tag
-            match tag {
-                "p2p-sync" => self.handle_p2p_sync(&registration.options).await?,
-                "crdt-sync" => self.handle_crdt_sync(&registration.options).await?,
-                "offline-queue" => self.handle_offline_queue(&registration.options).await?,
-                _ => {
-                    tracing::warn!(tag = %tag, "Unknown sync tag");
-                }
+        let options = self.sync_registrations.get(tag)
+            .map(|registration| registration.options.clone())
+            .unwrap_or_default();
+        tracing::info!(tag = %tag, "Handling background sync event");
+        
+        // Execute sync operation based on tag
+        match tag {
+            "p2p-sync" => self.handle_p2p_sync(&options).await?,
+            "crdt-sync" => self.handle_crdt_sync(&options).await?,
+            OFFLINE_QUEUE_SYNC_TAG => self.handle_offline_queue(&options).await?,
+            _ => {
+                tracing::warn!(tag = %tag, "Unknown sync tag");
             }
         }
         
         Ok(())
     }
 
+    /// Handle `sync` events dispatched to `target`
+    ///
+    /// In a service worker, `target` is the global scope. The event's
+    /// `waitUntil` keeps the worker alive until the sync completes, and a
+    /// failed sync rejects it so the browser retries.
+    pub fn install_sync_handler(self: Rc<Self>, target: &web_sys::EventTarget) -> WasmResult<()> {
+        let handler = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            let tag = js_sys::Reflect::get(&event, &"tag".into())
+                .ok()
+                .and_then(|tag| tag.as_string())
+                .unwrap_or_default();
+            let runtime = Rc::clone(&self);
+            let sync = wasm_bindgen_futures::future_to_promise(async move {
+                runtime.handle_sync_event(&tag).await
+                    .map(|()| JsValue::UNDEFINED)
+                    .map_err(|e| JsValue::from_str(&e.to_string()))
+            });
+
+            let wait_until = js_sys::Reflect::get(&event, &"waitUntil".into())
+                .ok()
+                .and_then(|wait_until| wait_until.dyn_into::<js_sys::Function>().ok());
+            if let Some(wait_until) = wait_until {
+                let _ = wait_until.call1(&event, &sync);
+            }
+        });
+
+        target.add_event_listener_with_callback("sync", handler.as_ref().unchecked_ref())
+            .map_err(|_| WasmError::Configuration("Failed to install sync handler".to_string()))?;
+        // The listener lives as long as the worker
+        handler.forget();
+        Ok(())
+    }
+
     /// Get runtime statistics
     pub fn get_stats(&self) -> &PwaStats {
         &self.stats
@@ -151,9 +244,22 @@ impl ServiceWorkerRuntime {
         Ok(())
     }
 
-    async fn handle_offline_queue(&self, _options: &BackgroundSyncOptions) -> WasmResult<()> {
+    async fn handle_offline_queue(&self, options: &BackgroundSyncOptions) -> WasmResult<()> {
         // Process offline queue when back online
-        tracing::debug!("Processing offline queue");
+        let handler = self.operation_handler.as_ref()
+            .ok_or_else(|| WasmError::Configuration("No sync operation handler set".to_string()))?;
+
+        let outcome = self.offline_queue.drain(handler.as_ref(), options.max_retries).await?;
+        tracing::debug!(
+            processed = outcome.processed,
+            retained = outcome.retained,
+            dropped = outcome.dropped,
+            "Processed offline queue"
+        );
+
+        if outcome.retained > 0 {
+            return Err(WasmError::Network(format!("{} queued operations still pending", outcome.retained)));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,226 @@
+//! IndexedDB-backed queue of operations deferred while offline
+//!
+//! Pages enqueue P2P sync and intent operations that could not be sent; the
+//! service worker drains the queue from a Background Sync event once
+//! connectivity returns, even if no page is open. The queue reaches IndexedDB
+//! through the global scope, so it works from both windows and workers.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use js_sys::{Array, Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbFactory, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+use crate::error::{WasmError, WasmResult};
+
+/// Default IndexedDB database holding the offline queue
+pub const DEFAULT_QUEUE_DB: &str = "synapsed-offline-queue";
+
+/// Object store holding queued operations
+const QUEUE_STORE: &str = "operations";
+
+/// Kind of queued operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuedOperationKind {
+    /// P2P data synchronization
+    P2pSync,
+    /// Intent execution
+    Intent,
+}
+
+/// Operation waiting for connectivity
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QueuedOperation {
+    /// Operation ID
+    pub id: String,
+    /// Operation kind
+    pub kind: QueuedOperationKind,
+    /// Operation data, interpreted by the handler
+    pub payload: serde_json::Value,
+    /// When the operation was queued
+    pub queued_at: DateTime<Utc>,
+    /// Failed processing attempts so far
+    pub attempts: u32,
+}
+
+impl QueuedOperation {
+    /// Create an operation with a fresh ID
+    pub fn new(kind: QueuedOperationKind, payload: serde_json::Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            payload,
+            queued_at: Utc::now(),
+            attempts: 0,
+        }
+    }
+}
+
+/// Re-runs queued operations when connectivity returns
+#[async_trait(?Send)]
+pub trait SyncOperationHandler {
+    /// Process one operation; an error leaves it queued for a later sync
+    async fn process(&self, operation: &QueuedOperation) -> WasmResult<()>;
+}
+
+/// Result of draining the queue
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DrainOutcome {
+    /// Operations processed and removed
+    pub processed: usize,
+    /// Operations that failed and stay queued
+    pub retained: usize,
+    /// Operations that failed too often and were removed
+    pub dropped: usize,
+}
+
+/// Offline operation queue stored in IndexedDB
+#[derive(Debug, Clone)]
+pub struct OfflineQueue {
+    /// Database name
+    db_name: String,
+}
+
+impl Default for OfflineQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUEUE_DB)
+    }
+}
+
+impl OfflineQueue {
+    /// Create a queue stored in the database `db_name`
+    pub fn new(db_name: &str) -> Self {
+        Self { db_name: db_name.to_string() }
+    }
+
+    /// Add an operation to the queue
+    pub async fn enqueue(&self, operation: &QueuedOperation) -> WasmResult<()> {
+        let store = self.store(IdbTransactionMode::Readwrite).await?;
+        self.put(&store, operation).await?;
+
+        tracing::debug!(id = %operation.id, kind = ?operation.kind, "Operation queued for background sync");
+        Ok(())
+    }
+
+    /// Queued operations, oldest first
+    pub async fn pending(&self) -> WasmResult<Vec<QueuedOperation>> {
+        let store = self.store(IdbTransactionMode::Readonly).await?;
+        let request = store.get_all()
+            .map_err(|_| WasmError::Storage("Failed to read offline queue".to_string()))?;
+        let values = Array::from(&await_request(&request).await?);
+
+        let mut operations = values.iter()
+            .filter_map(|value| value.as_string())
+            .map(|json| serde_json::from_str(&json))
+            .collect::<Result<Vec<QueuedOperation>, _>>()?;
+        operations.sort_by_key(|operation| operation.queued_at);
+        Ok(operations)
+    }
+
+    /// Remove an operation from the queue
+    pub async fn remove(&self, id: &str) -> WasmResult<()> {
+        let store = self.store(IdbTransactionMode::Readwrite).await?;
+        let request = store.delete(&JsValue::from_str(id))
+            .map_err(|_| WasmError::Storage("Failed to remove queued operation".to_string()))?;
+        await_request(&request).await?;
+        Ok(())
+    }
+
+    /// Process every queued operation with `handler`
+    ///
+    /// Processed operations are removed. A failed operation stays queued until
+    /// it has failed `max_retries` times, then it is dropped.
+    pub async fn drain(&self, handler: &dyn SyncOperationHandler, max_retries: u32) -> WasmResult<DrainOutcome> {
+        let mut outcome = DrainOutcome::default();
+
+        for mut operation in self.pending().await? {
+            match handler.process(&operation).await {
+                Ok(()) => {
+                    self.remove(&operation.id).await?;
+                    outcome.processed += 1;
+                }
+                Err(e) => {
+                    operation.attempts += 1;
+                    if operation.attempts >= max_retries {
+                        tracing::warn!(id = %operation.id, error = %e, "Dropping queued operation after repeated failures");
+                        self.remove(&operation.id).await?;
+                        outcome.dropped += 1;
+                    } else {
+                        tracing::debug!(id = %operation.id, error = %e, "Queued operation failed, will retry");
+                        let store = self.store(IdbTransactionMode::Readwrite).await?;
+                        self.put(&store, &operation).await?;
+                        outcome.retained += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    async fn put(&self, store: &IdbObjectStore, operation: &QueuedOperation) -> WasmResult<()> {
+        let json = serde_json::to_string(operation)?;
+        let request = store.put_with_key(&JsValue::from_str(&json), &JsValue::from_str(&operation.id))
+            .map_err(|_| WasmError::Storage("Failed to queue operation".to_string()))?;
+        await_request(&request).await?;
+        Ok(())
+    }
+
+    /// Open the queue's object store in a new transaction
+    async fn store(&self, mode: IdbTransactionMode) -> WasmResult<IdbObjectStore> {
+        let database = self.open().await?;
+        let transaction = database.transaction_with_str_and_mode(QUEUE_STORE, mode)
+            .map_err(|_| WasmError::Storage("Failed to create transaction".to_string()))?;
+        transaction.object_store(QUEUE_STORE)
+            .map_err(|_| WasmError::Storage(format!("Object store '{}' not found", QUEUE_STORE)))
+    }
+
+    async fn open(&self) -> WasmResult<IdbDatabase> {
+        let factory: IdbFactory = Reflect::get(&js_sys::global(), &"indexedDB".into())
+            .ok()
+            .and_then(|factory| factory.dyn_into().ok())
+            .ok_or_else(|| WasmError::Storage("IndexedDB not available".to_string()))?;
+
+        let request = factory.open_with_u32(&self.db_name, 1)
+            .map_err(|_| WasmError::Storage("Failed to open IndexedDB".to_string()))?;
+
+        let on_upgrade = Closure::once_into_js({
+            let request = request.clone();
+            move || {
+                if let Ok(database) = request.result().and_then(|db| db.dyn_into::<IdbDatabase>()) {
+                    if !database.object_store_names().contains(QUEUE_STORE) {
+                        let _ = database.create_object_store(QUEUE_STORE);
+                    }
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+        let database = await_request(&request).await?;
+        database.dyn_into()
+            .map_err(|_| WasmError::Storage("Database open failed".to_string()))
+    }
+}
+
+/// Wait for an IndexedDB request to complete and return its result
+async fn await_request(request: &IdbRequest) -> WasmResult<JsValue> {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let on_success = Closure::once_into_js({
+            let request = request.clone();
+            move || {
+                let result = request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+            }
+        });
+        let on_error = Closure::once_into_js(move || {
+            let _ = reject.call0(&JsValue::UNDEFINED);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+
+    JsFuture::from(promise).await
+        .map_err(|e| WasmError::Storage(format!("IndexedDB request failed: {:?}", e)))
+}
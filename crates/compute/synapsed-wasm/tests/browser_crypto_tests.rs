@@ -0,0 +1,37 @@
+//! Browser tests for the WASM SIMD crypto path
+//!
+//! Run in a browser with SIMD support, e.g.
+//! `RUSTFLAGS="-C target-feature=+simd128" wasm-pack test --headless --chrome -- --features crypto-modules,simd`
+
+#![cfg(all(
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    feature = "crypto-modules",
+    feature = "simd"
+))]
+
+use synapsed_wasm::crypto::{kyber768, simd_enabled};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn test_simd_enabled() {
+    assert!(simd_enabled());
+}
+
+#[wasm_bindgen_test]
+fn test_simd_and_scalar_kyber768_keygen_match() {
+    for i in 0..32u8 {
+        let mut seed = [0u8; kyber768::SEED_SIZE];
+        for (j, byte) in seed.iter_mut().enumerate() {
+            *byte = i.wrapping_mul(31).wrapping_add(j as u8);
+        }
+
+        let scalar = kyber768::keypair_from_seed_scalar(&seed);
+        let simd = kyber768::keypair_from_seed_simd(&seed);
+
+        assert_eq!(scalar.public_key, simd.public_key, "public keys differ for seed {i}");
+        assert_eq!(scalar.secret_key, simd.secret_key, "secret keys differ for seed {i}");
+    }
+}
@@ -0,0 +1,74 @@
+//! Browser tests for PWA background sync
+//!
+//! Run in a browser, e.g.
+//! `wasm-pack test --headless --chrome -- --features service-worker`
+
+#![cfg(all(target_arch = "wasm32", feature = "service-worker"))]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use async_trait::async_trait;
+use js_sys::{Promise, Reflect};
+use synapsed_wasm::pwa::{
+    OfflineQueue, QueuedOperation, QueuedOperationKind, ServiceWorkerRuntime, SyncOperationHandler,
+    OFFLINE_QUEUE_SYNC_TAG,
+};
+use synapsed_wasm::WasmResult;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Records every operation it is asked to process
+#[derive(Default)]
+struct RecordingHandler {
+    processed: RefCell<Vec<QueuedOperation>>,
+}
+
+#[async_trait(?Send)]
+impl SyncOperationHandler for RecordingHandler {
+    async fn process(&self, operation: &QueuedOperation) -> WasmResult<()> {
+        self.processed.borrow_mut().push(operation.clone());
+        Ok(())
+    }
+}
+
+#[wasm_bindgen_test]
+async fn test_sync_event_processes_queued_operation() {
+    let queue = OfflineQueue::new(&format!("synapsed-test-queue-{}", uuid::Uuid::new_v4()));
+    let handler = Rc::new(RecordingHandler::default());
+    let runtime = ServiceWorkerRuntime::new()
+        .unwrap()
+        .with_offline_queue(queue.clone())
+        .with_operation_handler(handler.clone());
+
+    // Queued while offline
+    let operation = QueuedOperation::new(
+        QueuedOperationKind::Intent,
+        serde_json::json!({"intent": "send_message", "peer": "peer-1"}),
+    );
+    queue.enqueue(&operation).await.unwrap();
+    assert!(handler.processed.borrow().is_empty());
+
+    let target = web_sys::EventTarget::new().unwrap();
+    Rc::new(runtime).install_sync_handler(&target).unwrap();
+
+    // Fire a sync event, keeping the promise passed to waitUntil as a service worker would
+    let waited: Rc<RefCell<Option<JsValue>>> = Rc::default();
+    let wait_until = Closure::<dyn FnMut(JsValue)>::new({
+        let waited = waited.clone();
+        move |promise| *waited.borrow_mut() = Some(promise)
+    });
+    let event = web_sys::Event::new("sync").unwrap();
+    Reflect::set(&event, &"tag".into(), &OFFLINE_QUEUE_SYNC_TAG.into()).unwrap();
+    Reflect::set(&event, &"waitUntil".into(), wait_until.as_ref()).unwrap();
+    target.dispatch_event(&event).unwrap();
+
+    let sync: Promise = waited.borrow_mut().take().expect("waitUntil not called").into();
+    JsFuture::from(sync).await.unwrap();
+
+    assert_eq!(handler.processed.borrow().as_slice(), &[operation]);
+    assert!(queue.pending().await.unwrap().is_empty());
+}
@@ -0,0 +1,63 @@
+//! NIST ML-KEM-768 known-answer tests for browser key generation
+//!
+//! The vectors are the first ML-KEM-768 cases of the ACVP keyGen internal
+//! projection. Natively this checks the scalar path; under
+//! `wasm-pack test` it checks whichever path the build dispatches to, so run
+//! it once more with `RUSTFLAGS="-C target-feature=+simd128"` and
+//! `--features crypto-modules,simd` to cover SIMD.
+
+#![cfg(feature = "crypto-modules")]
+
+use serde::Deserialize;
+use synapsed_wasm::crypto::kyber768::{self, KeyPair};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[derive(Deserialize)]
+struct KeyGenVectors {
+    tests: Vec<KeyGenCase>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyGenCase {
+    tc_id: u32,
+    d: String,
+    z: String,
+    ek: String,
+    dk: String,
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn check_vectors(keygen: fn(&[u8; kyber768::SEED_SIZE]) -> KeyPair) {
+    let vectors: KeyGenVectors =
+        serde_json::from_str(include_str!("data/ml_kem_768_keygen.json")).unwrap();
+    assert!(!vectors.tests.is_empty());
+
+    for case in &vectors.tests {
+        let mut seed = [0u8; kyber768::SEED_SIZE];
+        seed[..32].copy_from_slice(&unhex(&case.d));
+        seed[32..].copy_from_slice(&unhex(&case.z));
+
+        let keys = keygen(&seed);
+        assert_eq!(keys.public_key, unhex(&case.ek), "ek mismatch for tcId {}", case.tc_id);
+        assert_eq!(keys.secret_key, unhex(&case.dk), "dk mismatch for tcId {}", case.tc_id);
+    }
+}
+
+#[test]
+fn test_keypair_from_seed_matches_nist_vectors() {
+    check_vectors(kyber768::keypair_from_seed);
+}
+
+#[test]
+fn test_scalar_keypair_matches_nist_vectors() {
+    check_vectors(kyber768::keypair_from_seed_scalar);
+}
@@ -0,0 +1,259 @@
+//! Runtime capability registry for the Synapsed ecosystem.
+//!
+//! Cargo features decide what is compiled in, but a composed application
+//! also needs to know at runtime which components are present and at which
+//! version. Components register their capabilities in a
+//! [`CapabilityRegistry`], and builders and runtimes query it to check
+//! compatibility and gate optional behavior.
+
+use crate::{SynapsedError, SynapsedResult};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// Semantic version of a capability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CapabilityVersion {
+    /// Incompatible changes
+    pub major: u64,
+    /// Backwards-compatible additions
+    pub minor: u64,
+    /// Backwards-compatible fixes
+    pub patch: u64,
+}
+
+impl CapabilityVersion {
+    /// Create a new version
+    #[must_use] pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Check if this version satisfies a minimum required version
+    ///
+    /// Follows Cargo's caret rules: the version must be at least `required`
+    /// without crossing an incompatible boundary, which is the major version,
+    /// or the minor version while the major version is 0.
+    #[must_use] pub fn satisfies(&self, required: &Self) -> bool {
+        let compatible = if required.major == 0 {
+            self.major == 0 && self.minor == required.minor
+        } else {
+            self.major == required.major
+        };
+        compatible && self >= required
+    }
+}
+
+impl fmt::Display for CapabilityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for CapabilityVersion {
+    type Err = SynapsedError;
+
+    /// Parse `major[.minor[.patch]]`, with missing parts defaulting to 0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SynapsedError::invalid_input(format!("Invalid capability version: {s}"));
+        let mut parts = s.trim().split('.');
+        let mut next = |required: bool| match parts.next() {
+            Some(part) => part.parse::<u64>().map_err(|_| invalid()),
+            None if required => Err(invalid()),
+            None => Ok(0),
+        };
+
+        let version = Self::new(next(true)?, next(false)?, next(false)?);
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(version)
+    }
+}
+
+/// A capability provided by a component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capability {
+    /// Capability name, e.g. `storage.sql`
+    pub name: String,
+    /// Provided version
+    pub version: CapabilityVersion,
+    /// Component providing the capability
+    pub component: String,
+}
+
+/// Registry of the capabilities available in a running system
+#[derive(Debug, Default)]
+pub struct CapabilityRegistry {
+    /// Providers by capability name
+    capabilities: RwLock<HashMap<String, Vec<Capability>>>,
+}
+
+static GLOBAL_REGISTRY: LazyLock<CapabilityRegistry> = LazyLock::new(CapabilityRegistry::new);
+
+impl CapabilityRegistry {
+    /// Create an empty registry
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide registry shared by all components
+    #[must_use] pub fn global() -> &'static Self {
+        &GLOBAL_REGISTRY
+    }
+
+    /// Register a capability provided by a component
+    ///
+    /// Registering the same capability again from the same component
+    /// replaces its version.
+    pub fn register<C, N>(&self, component: C, name: N, version: CapabilityVersion)
+    where
+        C: Into<String>,
+        N: Into<String>,
+    {
+        let capability = Capability {
+            name: name.into(),
+            version,
+            component: component.into(),
+        };
+        let mut capabilities = self.capabilities.write();
+        let providers = capabilities.entry(capability.name.clone()).or_default();
+        match providers.iter_mut().find(|p| p.component == capability.component) {
+            Some(existing) => existing.version = capability.version,
+            None => providers.push(capability),
+        }
+    }
+
+    /// Remove every capability registered by a component
+    ///
+    /// Returns the number of capabilities removed.
+    pub fn unregister_component(&self, component: &str) -> usize {
+        let mut capabilities = self.capabilities.write();
+        let mut removed = 0;
+        capabilities.retain(|_, providers| {
+            let before = providers.len();
+            providers.retain(|p| p.component != component);
+            removed += before - providers.len();
+            !providers.is_empty()
+        });
+        removed
+    }
+
+    /// Check if any component provides a capability compatible with `min_version`
+    #[must_use] pub fn supports(&self, name: &str, min_version: CapabilityVersion) -> bool {
+        self.capabilities
+            .read()
+            .get(name)
+            .is_some_and(|providers| providers.iter().any(|p| p.version.satisfies(&min_version)))
+    }
+
+    /// Require a capability, failing with a descriptive error if it is missing
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SynapsedError::NotFound`] if no registered provider is
+    /// compatible with `min_version`.
+    pub fn require(&self, name: &str, min_version: CapabilityVersion) -> SynapsedResult<()> {
+        if self.supports(name, min_version) {
+            return Ok(());
+        }
+        let available: Vec<String> = self
+            .providers(name)
+            .iter()
+            .map(|p| format!("{} from {}", p.version, p.component))
+            .collect();
+        Err(SynapsedError::not_found(if available.is_empty() {
+            format!("Capability {name} >= {min_version} is not registered")
+        } else {
+            format!("Capability {name} >= {min_version} is not available (found {})", available.join(", "))
+        }))
+    }
+
+    /// Highest registered version of a capability
+    #[must_use] pub fn version(&self, name: &str) -> Option<CapabilityVersion> {
+        self.capabilities
+            .read()
+            .get(name)
+            .and_then(|providers| providers.iter().map(|p| p.version).max())
+    }
+
+    /// Components providing a capability
+    #[must_use] pub fn providers(&self, name: &str) -> Vec<Capability> {
+        self.capabilities.read().get(name).cloned().unwrap_or_default()
+    }
+
+    /// All registered capabilities, sorted by name and component
+    #[must_use] pub fn capabilities(&self) -> Vec<Capability> {
+        let mut all: Vec<Capability> = self.capabilities.read().values().flatten().cloned().collect();
+        all.sort_by(|a, b| (&a.name, &a.component).cmp(&(&b.name, &b.component)));
+        all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parsing() {
+        assert_eq!("1.2.3".parse::<CapabilityVersion>().unwrap(), CapabilityVersion::new(1, 2, 3));
+        assert_eq!("2".parse::<CapabilityVersion>().unwrap(), CapabilityVersion::new(2, 0, 0));
+        assert_eq!("0.4".parse::<CapabilityVersion>().unwrap(), CapabilityVersion::new(0, 4, 0));
+        assert!("".parse::<CapabilityVersion>().is_err());
+        assert!("1.x".parse::<CapabilityVersion>().is_err());
+        assert!("1.2.3.4".parse::<CapabilityVersion>().is_err());
+        assert_eq!(CapabilityVersion::new(1, 2, 3).to_string(), "1.2.3");
+    }
+
+    #[test]
+    fn test_version_compatibility() {
+        let v = CapabilityVersion::new;
+        assert!(v(1, 4, 2).satisfies(&v(1, 2, 0)));
+        assert!(v(1, 2, 0).satisfies(&v(1, 2, 0)));
+        assert!(!v(1, 1, 9).satisfies(&v(1, 2, 0)));
+        assert!(!v(2, 0, 0).satisfies(&v(1, 2, 0)));
+        assert!(v(0, 3, 5).satisfies(&v(0, 3, 1)));
+        assert!(!v(0, 4, 0).satisfies(&v(0, 3, 1)));
+    }
+
+    #[test]
+    fn test_registry_supports() {
+        let registry = CapabilityRegistry::new();
+        registry.register("synapsed-storage", "storage.sql", CapabilityVersion::new(1, 4, 0));
+        registry.register("synapsed-storage", "storage.cache", CapabilityVersion::new(0, 2, 1));
+        registry.register("synapsed-crypto", "crypto.kyber", CapabilityVersion::new(2, 0, 0));
+        registry.register("synapsed-crypto", "crypto.dilithium", CapabilityVersion::new(1, 0, 3));
+        registry.register("synapsed-net", "crypto.kyber", CapabilityVersion::new(1, 7, 0));
+
+        assert!(registry.supports("storage.sql", CapabilityVersion::new(1, 0, 0)));
+        assert!(registry.supports("storage.sql", CapabilityVersion::new(1, 4, 0)));
+        assert!(!registry.supports("storage.sql", CapabilityVersion::new(1, 5, 0)));
+        assert!(!registry.supports("storage.sql", CapabilityVersion::new(2, 0, 0)));
+        assert!(registry.supports("storage.cache", CapabilityVersion::new(0, 2, 0)));
+        assert!(!registry.supports("storage.cache", CapabilityVersion::new(0, 1, 0)));
+        assert!(!registry.supports("network.quic", CapabilityVersion::new(0, 0, 1)));
+
+        // Either provider can satisfy a requirement
+        assert!(registry.supports("crypto.kyber", CapabilityVersion::new(1, 5, 0)));
+        assert!(registry.supports("crypto.kyber", CapabilityVersion::new(2, 0, 0)));
+        assert_eq!(registry.version("crypto.kyber"), Some(CapabilityVersion::new(2, 0, 0)));
+        assert_eq!(registry.providers("crypto.kyber").len(), 2);
+
+        assert!(registry.require("crypto.dilithium", CapabilityVersion::new(1, 0, 0)).is_ok());
+        let err = registry.require("crypto.dilithium", CapabilityVersion::new(1, 1, 0)).unwrap_err();
+        assert!(err.to_string().contains("1.0.3 from synapsed-crypto"));
+
+        // Re-registering updates the version; unregistering removes the component
+        registry.register("synapsed-storage", "storage.sql", CapabilityVersion::new(1, 6, 0));
+        assert!(registry.supports("storage.sql", CapabilityVersion::new(1, 5, 0)));
+        assert_eq!(registry.providers("storage.sql").len(), 1);
+
+        assert_eq!(registry.unregister_component("synapsed-crypto"), 2);
+        assert!(!registry.supports("crypto.dilithium", CapabilityVersion::new(1, 0, 0)));
+        assert!(!registry.supports("crypto.kyber", CapabilityVersion::new(2, 0, 0)));
+        assert!(registry.supports("crypto.kyber", CapabilityVersion::new(1, 0, 0)));
+        assert_eq!(registry.capabilities().len(), 3);
+    }
+}
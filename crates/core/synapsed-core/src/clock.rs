@@ -0,0 +1,103 @@
+//! Pluggable clocks for testable time-dependent logic.
+//!
+//! Components that check expiry, TTLs or deadlines take a [`Clock`] instead
+//! of calling `chrono::Utc::now()` directly. Production code uses
+//! [`SystemClock`]; tests use a [`MockClock`] and advance it explicitly, so
+//! timeouts can be exercised without real sleeps.
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Source of the current time
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Shared handle to a clock
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Clock reading the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Shared handle to the system clock
+    #[must_use] pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Manually controlled clock for tests
+///
+/// Time stands still until the clock is advanced or set. Clones share the
+/// same time, so a test can keep one clone and inject another.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<RwLock<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `start`
+    #[must_use] pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(RwLock::new(start)),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, duration: Duration) {
+        *self.now.write() += duration;
+    }
+
+    /// Set the clock to a specific time
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.write() = now;
+    }
+}
+
+impl Default for MockClock {
+    /// A clock stopped at the current system time
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock() {
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+        let shared: SharedClock = Arc::new(clock.clone());
+        assert_eq!(shared.now(), start);
+
+        clock.advance(Duration::minutes(5));
+        assert_eq!(shared.now(), start + Duration::minutes(5));
+
+        clock.set(start);
+        assert_eq!(shared.now(), start);
+    }
+
+    #[test]
+    fn test_system_clock() {
+        let before = Utc::now();
+        let now = SystemClock::shared().now();
+        assert!(now >= before);
+    }
+}
@@ -28,6 +28,8 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod capability;
+pub mod clock;
 pub mod config;
 pub mod error;
 // TODO: Fix thread safety issues in memory module
@@ -43,6 +45,8 @@ pub mod utils;
 // Re-export commonly used items
 pub use error::{SynapsedError, SynapsedResult};
 pub use traits::{Observable, Configurable, Identifiable, Validatable};
+pub use capability::{CapabilityRegistry, CapabilityVersion};
+pub use clock::{Clock, MockClock, SharedClock, SystemClock};
 
 /// Version information for the Synapsed Core library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
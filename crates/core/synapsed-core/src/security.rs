@@ -3,6 +3,7 @@
 //! This module provides common security traits and utilities that can be
 //! used across all security-related Synapsed components.
 
+use crate::clock::{Clock, SystemClock};
 use crate::SynapsedResult;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -89,8 +90,13 @@ impl SecurityContext {
 
     /// Check if the context has expired
     #[must_use] pub fn is_expired(&self) -> bool {
+        self.is_expired_with(&SystemClock)
+    }
+
+    /// Check if the context has expired according to `clock`
+    #[must_use] pub fn is_expired_with(&self, clock: &dyn Clock) -> bool {
         match self.expires_at {
-            Some(expiry) => chrono::Utc::now() > expiry,
+            Some(expiry) => clock.now() > expiry,
             None => false,
         }
     }
@@ -282,6 +288,30 @@ pub struct SessionInfo {
     pub attributes: HashMap<String, String>,
 }
 
+impl SessionInfo {
+    /// Start a session at the clock's current time
+    pub fn new<S: Into<String>>(session_id: S, client_info: ClientInfo, clock: &dyn Clock) -> Self {
+        let now = clock.now();
+        Self {
+            session_id: session_id.into(),
+            started_at: now,
+            last_activity: now,
+            client_info,
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Record activity on the session
+    pub fn touch(&mut self, clock: &dyn Clock) {
+        self.last_activity = clock.now();
+    }
+
+    /// Check if the session has been idle for longer than `ttl`
+    #[must_use] pub fn is_expired(&self, ttl: chrono::Duration, clock: &dyn Clock) -> bool {
+        clock.now() - self.last_activity > ttl
+    }
+}
+
 /// Client information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientInfo {
@@ -566,6 +596,84 @@ pub enum SecurityEffect {
     Deny,
 }
 
+/// Compare two byte strings in constant time
+///
+/// The running time depends only on the lengths of the inputs, never on
+/// where they differ, so comparing a secret (session token, MAC, OTP) against
+/// attacker-supplied input doesn't leak how much of it was guessed right.
+/// Inputs of different lengths compare unequal immediately; lengths are not
+/// treated as secret.
+#[must_use]
+#[inline(never)]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    // Accumulate differences without branching; black_box keeps the compiler
+    // from turning this back into an early-exit comparison
+    let diff = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |acc, (x, y)| acc | std::hint::black_box(x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
+/// A secret string such as a token or password
+///
+/// Equality uses [`ct_eq`] and `Debug` output is redacted, so secrets don't
+/// leak through timing or logs.
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap a secret value
+    pub fn new<S: Into<String>>(secret: S) -> Self {
+        Self(secret.into())
+    }
+
+    /// Access the secret value
+    #[must_use] pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Length of the secret in bytes
+    #[must_use] pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if the secret is empty
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl PartialEq for SecretString {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(self.0.as_bytes(), other.0.as_bytes())
+    }
+}
+
+impl Eq for SecretString {}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
 /// Utility functions for common security operations
 pub mod utils {
     use super::{Uuid, SecurityContext, Principal, SecurityLevel};
@@ -672,4 +780,72 @@ mod tests {
         let timestamp = utils::current_timestamp();
         assert!(timestamp > 0);
     }
+
+    #[test]
+    fn test_session_expiry_with_mock_clock() {
+        use crate::clock::MockClock;
+
+        let clock = MockClock::default();
+        let client = ClientInfo {
+            ip_address: None,
+            user_agent: None,
+            client_type: "cli".to_string(),
+            client_version: None,
+        };
+        let ttl = chrono::Duration::minutes(30);
+        let mut session = SessionInfo::new(utils::generate_session_id(), client, &clock);
+        assert!(!session.is_expired(ttl, &clock));
+
+        clock.advance(chrono::Duration::minutes(20));
+        session.touch(&clock);
+        clock.advance(chrono::Duration::minutes(29));
+        assert!(!session.is_expired(ttl, &clock), "Activity extends the session");
+
+        clock.advance(chrono::Duration::minutes(2));
+        assert!(session.is_expired(ttl, &clock));
+
+        let context = utils::create_test_context()
+            .with_session(session)
+            .with_expiration(clock.now() + chrono::Duration::hours(1));
+        assert!(!context.is_expired_with(&clock));
+        clock.advance(chrono::Duration::hours(2));
+        assert!(context.is_expired_with(&clock));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"session-token", b"session-token"));
+        assert!(!ct_eq(b"session-token", b"session-tokem"));
+        assert!(!ct_eq(b"session-token", b"Session-token"));
+        assert!(!ct_eq(b"session-token", b"session-toke"));
+        assert!(!ct_eq(b"", b"a"));
+
+        // Every single-bit difference at every position is detected
+        let mac = [0x5au8; 32];
+        for i in 0..mac.len() {
+            for bit in 0..8 {
+                let mut other = mac;
+                other[i] ^= 1 << bit;
+                assert!(!ct_eq(&mac, &other));
+            }
+        }
+        assert!(ct_eq(&mac, &mac.clone()));
+    }
+
+    #[test]
+    fn test_secret_string() {
+        let secret = SecretString::new("hunter2-otp-123456");
+        let debug = format!("{secret:?}");
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains("123456"));
+        assert_eq!(debug, "SecretString([REDACTED])");
+        assert_eq!(format!("{:#?}", Some(&secret)).matches("REDACTED").count(), 1);
+
+        assert_eq!(secret, SecretString::from("hunter2-otp-123456"));
+        assert_ne!(secret, SecretString::from("hunter2-otp-123457"));
+        assert_ne!(secret, SecretString::default());
+        assert_eq!(secret.expose_secret(), "hunter2-otp-123456");
+        assert_eq!(secret.len(), 18);
+    }
 }
\ No newline at end of file
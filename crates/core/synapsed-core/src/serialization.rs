@@ -290,6 +290,125 @@ pub trait VersionedSerializer<T>: Send + Sync {
     async fn migrate_version(&self, data: &[u8], from_version: u32, to_version: u32) -> SynapsedResult<Vec<u8>>;
 }
 
+/// A payload tagged with the schema version it was written with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionedEnvelope<T> {
+    /// Schema version of the payload
+    pub version: u32,
+    /// The payload
+    pub payload: T,
+}
+
+impl<T> VersionedEnvelope<T> {
+    /// Wrap a payload written with schema `version`
+    pub fn new(version: u32, payload: T) -> Self {
+        Self { version, payload }
+    }
+}
+
+/// A migration from one schema version to the next
+type Migration = Box<dyn Fn(serde_json::Value) -> SynapsedResult<serde_json::Value> + Send + Sync>;
+
+/// Upgrades versioned JSON payloads to the current schema on load
+///
+/// Payloads are written in a [`VersionedEnvelope`] at the current version.
+/// On load, a payload written at an older version is passed through each
+/// registered `vN -> vN+1` migration in turn before being deserialized.
+pub struct Migrator<T> {
+    /// Schema version written by [`Migrator::serialize`]
+    current_version: u32,
+    /// Migrations by the version they upgrade from
+    migrations: HashMap<u32, Migration>,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Migrator<T>
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    /// Create a migrator for schema `current_version`
+    #[must_use] pub fn new(current_version: u32) -> Self {
+        Self {
+            current_version,
+            migrations: HashMap::new(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Register the migration from `from_version` to `from_version + 1`
+    #[must_use]
+    pub fn with_migration<F>(mut self, from_version: u32, migration: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> SynapsedResult<serde_json::Value> + Send + Sync + 'static,
+    {
+        self.migrations.insert(from_version, Box::new(migration));
+        self
+    }
+
+    /// Get current schema version
+    #[must_use] pub fn current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    /// Serialize a payload at the current schema version
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload can't be serialized.
+    pub fn serialize(&self, payload: &T) -> SynapsedResult<Vec<u8>> {
+        Ok(serde_json::to_vec(&VersionedEnvelope::new(self.current_version, payload))?)
+    }
+
+    /// Deserialize a payload, migrating it from an older schema version if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data isn't a versioned envelope, was written
+    /// by a newer schema version, has no migration path to the current
+    /// version, or doesn't match the current schema after migration.
+    pub fn deserialize(&self, data: &[u8]) -> SynapsedResult<T> {
+        let envelope: VersionedEnvelope<serde_json::Value> = serde_json::from_slice(data)?;
+        let upgraded = self.upgrade(envelope)?;
+        Ok(serde_json::from_value(upgraded.payload)?)
+    }
+
+    /// Upgrade an envelope to the current schema version
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the envelope was written by a newer schema
+    /// version, a migration is missing, or a migration fails.
+    pub fn upgrade(
+        &self,
+        mut envelope: VersionedEnvelope<serde_json::Value>,
+    ) -> SynapsedResult<VersionedEnvelope<serde_json::Value>> {
+        if envelope.version > self.current_version {
+            return Err(SynapsedError::serialization(format!(
+                "Unsupported schema version {}: newer than current version {}",
+                envelope.version, self.current_version
+            )));
+        }
+
+        while envelope.version < self.current_version {
+            let migration = self.migrations.get(&envelope.version).ok_or_else(|| {
+                SynapsedError::serialization(format!(
+                    "No migration registered from schema version {} to {}",
+                    envelope.version,
+                    envelope.version + 1
+                ))
+            })?;
+            envelope.payload = migration(envelope.payload).map_err(|e| {
+                SynapsedError::serialization(format!(
+                    "Migration from schema version {} failed: {e}",
+                    envelope.version
+                ))
+            })?;
+            envelope.version += 1;
+        }
+        Ok(envelope)
+    }
+}
+
 /// Batch serialization for multiple items
 pub struct BatchSerializer<T> {
     #[allow(dead_code)]
@@ -586,6 +705,64 @@ mod tests {
         assert_eq!(items, deserialized);
     }
 
+    #[test]
+    fn test_versioned_migration() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct TrustRecordV1 {
+            agent: String,
+            score: f64,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct TrustRecordV2 {
+            agent: String,
+            score: f64,
+            interactions: u64,
+        }
+
+        let v1_bytes = Migrator::<TrustRecordV1>::new(1)
+            .serialize(&TrustRecordV1 { agent: "alice".to_string(), score: 0.7 })
+            .unwrap();
+
+        let migrator = Migrator::<TrustRecordV2>::new(2).with_migration(1, |mut payload| {
+            payload["interactions"] = serde_json::json!(0);
+            Ok(payload)
+        });
+        let record = migrator.deserialize(&v1_bytes).unwrap();
+        assert_eq!(record, TrustRecordV2 { agent: "alice".to_string(), score: 0.7, interactions: 0 });
+
+        // Current-version payloads load as-is
+        let v2 = TrustRecordV2 { agent: "bob".to_string(), score: 0.4, interactions: 3 };
+        assert_eq!(migrator.deserialize(&migrator.serialize(&v2).unwrap()).unwrap(), v2);
+
+        // Payloads from the future are rejected
+        let v3_bytes = serde_json::to_vec(&VersionedEnvelope::new(3, &v2)).unwrap();
+        let err = migrator.deserialize(&v3_bytes).unwrap_err();
+        assert!(err.to_string().contains("Unsupported schema version 3"));
+
+        // Missing migrations are reported
+        let err = Migrator::<TrustRecordV2>::new(2).deserialize(&v1_bytes).unwrap_err();
+        assert!(err.to_string().contains("No migration registered from schema version 1 to 2"));
+    }
+
+    #[test]
+    fn test_chained_migrations() {
+        let migrator = Migrator::<Vec<u32>>::new(3)
+            .with_migration(1, |payload| Ok(serde_json::json!([payload])))
+            .with_migration(2, |mut payload| {
+                payload.as_array_mut()
+                    .ok_or_else(|| SynapsedError::serialization("Expected an array"))?
+                    .push(serde_json::json!(2));
+                Ok(payload)
+            });
+
+        let v1 = serde_json::to_vec(&VersionedEnvelope::new(1, 1)).unwrap();
+        assert_eq!(migrator.deserialize(&v1).unwrap(), vec![1, 2]);
+
+        let bad = serde_json::to_vec(&VersionedEnvelope::new(2, "not a list")).unwrap();
+        assert!(migrator.deserialize(&bad).unwrap_err().to_string().contains("Migration from schema version 2 failed"));
+    }
+
     #[test]
     fn test_utils() {
         let data = b"hello world";
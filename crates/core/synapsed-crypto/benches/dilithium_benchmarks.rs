@@ -3,6 +3,7 @@
 //! This benchmark suite measures the performance of:
 //! - Key generation
 //! - Signing
+//! - Signing with a reused signing context
 //! - Verification
 //! - Serialization/deserialization
 //!
@@ -10,7 +11,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use synapsed_crypto::prelude::*;
-use synapsed_crypto::dilithium::{DilithiumPublicKey, DilithiumSecretKey, DilithiumSignature};
+use synapsed_crypto::dilithium::{dilithium2, dilithium3, dilithium5, DilithiumPublicKey, DilithiumSecretKey, DilithiumSignature};
 use synapsed_crypto::params::dilithium::{dilithium3::K as DILITHIUM3_K};
 use synapsed_crypto::random::DefaultRng;
 use synapsed_crypto::traits::Serializable;
@@ -100,6 +101,51 @@ fn bench_dilithium_sign(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_dilithium_signing_context(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dilithium_signing_context");
+    
+    let mut rng = DefaultRng::default();
+    let (_, sk2) = Dilithium2::generate_keypair(&mut rng).unwrap();
+    let (_, sk3) = Dilithium3::generate_keypair(&mut rng).unwrap();
+    let (_, sk5) = Dilithium5::generate_keypair(&mut rng).unwrap();
+    let message = b"Benchmark message for repeated signing";
+    
+    group.bench_function("dilithium2/stateless", |b| {
+        let mut rng = DefaultRng::default();
+        b.iter(|| black_box(Dilithium2::sign(&sk2, message, &mut rng).unwrap()));
+    });
+    
+    group.bench_function("dilithium2/context", |b| {
+        let mut rng = DefaultRng::default();
+        let ctx = dilithium2::SigningContext::new(&sk2).unwrap();
+        b.iter(|| black_box(ctx.sign(message, &mut rng).unwrap()));
+    });
+    
+    group.bench_function("dilithium3/stateless", |b| {
+        let mut rng = DefaultRng::default();
+        b.iter(|| black_box(Dilithium3::sign(&sk3, message, &mut rng).unwrap()));
+    });
+    
+    group.bench_function("dilithium3/context", |b| {
+        let mut rng = DefaultRng::default();
+        let ctx = dilithium3::SigningContext::new(&sk3).unwrap();
+        b.iter(|| black_box(ctx.sign(message, &mut rng).unwrap()));
+    });
+    
+    group.bench_function("dilithium5/stateless", |b| {
+        let mut rng = DefaultRng::default();
+        b.iter(|| black_box(Dilithium5::sign(&sk5, message, &mut rng).unwrap()));
+    });
+    
+    group.bench_function("dilithium5/context", |b| {
+        let mut rng = DefaultRng::default();
+        let ctx = dilithium5::SigningContext::new(&sk5).unwrap();
+        b.iter(|| black_box(ctx.sign(message, &mut rng).unwrap()));
+    });
+    
+    group.finish();
+}
+
 fn bench_dilithium_verify(c: &mut Criterion) {
     let mut group = c.benchmark_group("dilithium_verify");
     
@@ -317,6 +363,7 @@ criterion_group!(
     benches,
     bench_dilithium_keygen,
     bench_dilithium_sign,
+    bench_dilithium_signing_context,
     bench_dilithium_verify,
     bench_dilithium_serialization,
     bench_dilithium_full_cycle,
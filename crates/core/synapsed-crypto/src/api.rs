@@ -118,6 +118,11 @@ impl KemAlgorithm {
     pub fn shared_secret_size(&self) -> usize {
         32
     }
+    
+    /// Whether [`decapsulate_batch`] supports this algorithm
+    pub fn supports_batch_decapsulation(&self) -> bool {
+        matches!(self, Self::Kyber512)
+    }
 }
 
 impl fmt::Display for KemAlgorithm {
@@ -197,15 +202,15 @@ pub fn generate_keypair<R: SecureRandom>(
     match algorithm {
         KemAlgorithm::Kyber512 => {
             let (pk, sk) = Kyber512::generate_keypair(rng)?;
-            Ok((pk.as_ref().to_vec(), sk.as_ref().to_vec()))
+            Ok((pk.as_ref().to_vec(), sk.as_slice().to_vec()))
         }
         KemAlgorithm::Kyber768 => {
             let (pk, sk) = Kyber768::generate_keypair(rng)?;
-            Ok((pk.as_ref().to_vec(), sk.as_ref().to_vec()))
+            Ok((pk.as_ref().to_vec(), sk.as_slice().to_vec()))
         }
         KemAlgorithm::Kyber1024 => {
             let (pk, sk) = Kyber1024::generate_keypair(rng)?;
-            Ok((pk.as_ref().to_vec(), sk.as_ref().to_vec()))
+            Ok((pk.as_ref().to_vec(), sk.as_slice().to_vec()))
         }
     }
 }
@@ -263,6 +268,41 @@ pub fn decapsulate(
     }
 }
 
+/// Decapsulate a batch of ciphertexts using one secret key
+///
+/// Returns one shared secret per ciphertext, in order, each identical to what
+/// [`decapsulate`] returns for that ciphertext. Work that depends only on the
+/// secret key is done once for the batch, while every ciphertext still gets
+/// constant-time implicit rejection: an invalid ciphertext yields a
+/// pseudorandom secret after the same computation as a valid one. Fails
+/// without decapsulating anything if the key or any ciphertext has the wrong
+/// length.
+///
+/// Only Kyber512 is supported, see
+/// [`KemAlgorithm::supports_batch_decapsulation`]; other algorithms fail with
+/// [`Error::InvalidParameter`] and should call [`decapsulate`] per ciphertext.
+pub fn decapsulate_batch<C: AsRef<[u8]>>(
+    algorithm: KemAlgorithm,
+    secret_key: &[u8],
+    ciphertexts: &[C],
+) -> Result<Vec<Vec<u8>>> {
+    if !algorithm.supports_batch_decapsulation() {
+        return Err(Error::InvalidParameter);
+    }
+    if ciphertexts.iter().any(|ct| ct.as_ref().len() != algorithm.ciphertext_size()) {
+        return Err(Error::InvalidCiphertext);
+    }
+    
+    match algorithm {
+        KemAlgorithm::Kyber512 => {
+            let sk = <<Kyber512 as Kem>::SecretKey as Serializable>::from_bytes(secret_key)?;
+            let secrets = Kyber512::decapsulate_batch(&sk, ciphertexts)?;
+            Ok(secrets.iter().map(|ss| ss.as_ref().to_vec()).collect())
+        }
+        KemAlgorithm::Kyber768 | KemAlgorithm::Kyber1024 => Err(Error::InvalidParameter),
+    }
+}
+
 /// Generate a signing keypair for the specified signature algorithm
 pub fn generate_signing_keypair<R: SecureRandom>(
     algorithm: SignatureAlgorithm,
@@ -271,15 +311,15 @@ pub fn generate_signing_keypair<R: SecureRandom>(
     match algorithm {
         SignatureAlgorithm::Dilithium2 => {
             let (pk, sk) = Dilithium2::generate_keypair(rng)?;
-            Ok((pk.as_ref().to_vec(), sk.as_ref().to_vec()))
+            Ok((pk.as_ref().to_vec(), sk.as_slice().to_vec()))
         }
         SignatureAlgorithm::Dilithium3 => {
             let (pk, sk) = Dilithium3::generate_keypair(rng)?;
-            Ok((pk.as_ref().to_vec(), sk.as_ref().to_vec()))
+            Ok((pk.as_ref().to_vec(), sk.as_slice().to_vec()))
         }
         SignatureAlgorithm::Dilithium5 => {
             let (pk, sk) = Dilithium5::generate_keypair(rng)?;
-            Ok((pk.as_ref().to_vec(), sk.as_ref().to_vec()))
+            Ok((pk.as_ref().to_vec(), sk.as_slice().to_vec()))
         }
     }
 }
@@ -335,6 +375,50 @@ pub fn sign_deterministic(
     }
 }
 
+/// Sign a message using randomness derived from an explicit seed
+///
+/// Unlike [`sign`], the signing randomness is expanded from `seed` with
+/// SHAKE256, so identical inputs always produce byte-identical signatures.
+/// This is intended for known-answer tests and protocols that require
+/// reproducible signatures; the seed must be kept as secret as the key.
+pub fn sign_with_seed(
+    algorithm: SignatureAlgorithm,
+    secret_key: &[u8],
+    message: &[u8],
+    seed: &[u8; 32],
+) -> Result<Vec<u8>> {
+    let mut rng = SeededRng::new(seed);
+    sign(algorithm, secret_key, message, &mut rng)
+}
+
+/// SHAKE256-based RNG used by [`sign_with_seed`]
+struct SeededRng {
+    reader: sha3::Shake256Reader,
+}
+
+impl SeededRng {
+    const DOMAIN: &'static [u8] = b"synapsed-crypto/sign-with-seed/v1";
+
+    fn new(seed: &[u8; 32]) -> Self {
+        use sha3::digest::{ExtendableOutput, Update};
+
+        let mut hasher = sha3::Shake256::default();
+        hasher.update(Self::DOMAIN);
+        hasher.update(seed);
+        Self {
+            reader: hasher.finalize_xof(),
+        }
+    }
+}
+
+impl SecureRandom for SeededRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        use sha3::digest::XofReader;
+
+        self.reader.read(dest);
+    }
+}
+
 /// Verify a signature with the public key
 pub fn verify(
     algorithm: SignatureAlgorithm,
@@ -3,6 +3,7 @@
 //! This module implements the Module-Lattice-Based Digital Signature Algorithm
 //! as specified in NIST FIPS 204.
 
+pub mod generic;
 pub mod dilithium2;
 pub mod dilithium3;
 pub mod dilithium5;
@@ -19,6 +20,7 @@ use crate::{
     error::{Error, Result},
     params::dilithium::*,
     traits::Serializable,
+    secure_memory::SecretBuffer,
 };
 use zeroize::Zeroize;
 
@@ -34,14 +36,19 @@ pub(crate) mod common {
         (a1, a0)
     }
     
-    /// Decompose for hint generation
+    /// Decompose a standard representative into high and low bits
     pub fn decompose(a: i32, gamma2: i32) -> (i32, i32) {
-        let a1 = (a + 127) >> 7;
-        let a1 = ((a1.wrapping_mul(1025).wrapping_add(1 << 21)) >> 22).wrapping_mul(3);
-        let a1 = a1 & !((((43i32.wrapping_sub(a1)) >> 31) & 1).wrapping_neg());
+        let mut a1 = (a + 127) >> 7;
+        if gamma2 == (Q - 1) / 32 {
+            a1 = (a1 * 1025 + (1 << 21)) >> 22;
+            a1 &= 15;
+        } else {
+            a1 = (a1 * 11275 + (1 << 23)) >> 24;
+            a1 ^= ((43 - a1) >> 31) & a1;
+        }
         
-        let a0 = a.wrapping_sub(a1.wrapping_mul(2).wrapping_mul(gamma2));
-        let a0 = a0.wrapping_sub(((Q / 2).wrapping_sub(a0).wrapping_sub(1).wrapping_neg() >> 31) & Q);
+        let mut a0 = a - a1 * 2 * gamma2;
+        a0 -= (((Q - 1) / 2 - a0) >> 31) & Q;
         
         (a1, a0)
     }
@@ -51,18 +58,59 @@ pub(crate) mod common {
         a0 > gamma2 || a0 < -gamma2 || (a0 == -gamma2 && a1 != 0)
     }
     
+    /// Bits per packed coefficient of a polynomial with coefficients in [-eta, eta]
+    pub fn eta_bits(eta: usize) -> usize {
+        if eta == 2 { 3 } else { 4 }
+    }
+    
+    /// Pack 256 values of `bits` bits each into a little-endian bit stream
+    pub fn pack_bits(values: &[u32; 256], bits: usize, out: &mut Vec<u8>) {
+        let mut acc = 0u64;
+        let mut filled = 0;
+        
+        for &value in values {
+            acc |= (value as u64) << filled;
+            filled += bits;
+            while filled >= 8 {
+                out.push(acc as u8);
+                acc >>= 8;
+                filled -= 8;
+            }
+        }
+    }
+    
+    /// Unpack 256 values of `bits` bits each from a little-endian bit stream
+    pub fn unpack_bits(bytes: &[u8], bits: usize) -> [u32; 256] {
+        let mask = (1u64 << bits) - 1;
+        let mut values = [0u32; 256];
+        let mut bytes = bytes.iter();
+        let mut acc = 0u64;
+        let mut filled = 0;
+        
+        for value in values.iter_mut() {
+            while filled < bits {
+                acc |= (*bytes.next().unwrap_or(&0) as u64) << filled;
+                filled += 8;
+            }
+            *value = (acc & mask) as u32;
+            acc >>= bits;
+            filled -= bits;
+        }
+        
+        values
+    }
+    
     /// Use hint to recover high bits
     pub fn use_hint(a: i32, hint: bool, gamma2: i32) -> i32 {
         let (a1, a0) = decompose(a, gamma2);
+        let m = (Q - 1) / (2 * gamma2);
         
-        if hint {
-            if a0 > 0 {
-                (a1 + 1) % 44
-            } else {
-                (a1 - 1 + 44) % 44
-            }
-        } else {
+        if !hint {
             a1
+        } else if a0 > 0 {
+            (a1 + 1).rem_euclid(m)
+        } else {
+            (a1 - 1).rem_euclid(m)
         }
     }
 }
@@ -106,15 +154,29 @@ impl<const K: usize> Serializable for DilithiumPublicKey<K> {
 }
 
 /// Dilithium secret key
-#[derive(Clone, Debug)]
+///
+/// The packed bytes live in a [`SecretBuffer`] that is wiped on drop. The
+/// key is deliberately not `Clone`; use [`duplicate`](Self::duplicate) when a
+/// second copy is really needed.
+#[derive(Debug)]
 pub struct DilithiumSecretKey<const K: usize> {
-    /// Packed secret key bytes
-    pub bytes: Vec<u8>,
+    bytes: SecretBuffer,
 }
 
-impl<const K: usize> AsRef<[u8]> for DilithiumSecretKey<K> {
-    fn as_ref(&self) -> &[u8] {
-        &self.bytes
+impl<const K: usize> DilithiumSecretKey<K> {
+    /// Wrap packed secret key bytes
+    pub(crate) fn new(bytes: SecretBuffer) -> Self {
+        Self { bytes }
+    }
+    
+    /// Get the packed secret key bytes
+    pub fn as_slice(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+    
+    /// Explicitly create an independent copy of the key
+    pub fn duplicate(&self) -> Self {
+        Self::new(self.bytes.duplicate())
     }
 }
 
@@ -132,7 +194,7 @@ impl<const K: usize> Drop for DilithiumSecretKey<K> {
 
 impl<const K: usize> Serializable for DilithiumSecretKey<K> {
     fn to_bytes(&self) -> Vec<u8> {
-        self.bytes.clone()
+        self.bytes.to_vec()
     }
     
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
@@ -150,7 +212,7 @@ impl<const K: usize> Serializable for DilithiumSecretKey<K> {
         }
         
         Ok(Self {
-            bytes: bytes.to_vec(),
+            bytes: SecretBuffer::from_slice(bytes),
         })
     }
 }
@@ -199,6 +261,15 @@ mod tests {
         assert!((-(1 << 12)..(1 << 12)).contains(&a0));
     }
     
+    #[test]
+    fn test_pack_bits_roundtrip() {
+        let values: [u32; 256] = core::array::from_fn(|i| (i as u32 * 37) & 0x1FFF);
+        let mut packed = Vec::new();
+        pack_bits(&values, 13, &mut packed);
+        assert_eq!(packed.len(), 416);
+        assert_eq!(unpack_bits(&packed, 13), values);
+    }
+    
     #[test]
     fn test_decompose() {
         let gamma2 = 95232;
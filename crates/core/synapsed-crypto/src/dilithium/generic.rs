@@ -0,0 +1,698 @@
+//! Generic Dilithium implementation using trait-based parameters
+//!
+//! Key generation, signing and verification are shared by all security
+//! levels; the level-specific modules only pick a parameter set.
+
+use crate::{
+    error::{Error, Result},
+    params::dilithium::{self as params, D, Q, SYMBYTES},
+    traits::SecureRandom,
+    dilithium::{DilithiumPublicKey, DilithiumSecretKey, DilithiumSignature, common::*},
+    dilithium::dilithium2::{DilithiumPoly, DilithiumPolyVec, DilithiumMatrix},
+    hash::{h, g, crh},
+    secure_memory::SecureArray,
+};
+use zeroize::Zeroize;
+
+use sha3::{Shake256, digest::{ExtendableOutput, Update, XofReader}};
+
+/// Trait defining Dilithium parameters for different security levels
+pub trait DilithiumParams: Send + Sync + 'static {
+    /// Number of rows of matrix A
+    const K: usize;
+    
+    /// Number of columns of matrix A
+    const L: usize;
+    
+    /// Bound on the secret key coefficients
+    const ETA: usize;
+    
+    /// Number of ±1's in the challenge polynomial
+    const TAU: usize;
+    
+    /// Maximum L∞ norm of c*s1 and c*s2
+    const BETA: usize;
+    
+    /// Range of the masking vector y
+    const GAMMA1: i32;
+    
+    /// Low-order rounding range
+    const GAMMA2: i32;
+    
+    /// Maximum number of 1's in the hint vector
+    const OMEGA: usize;
+    
+    /// Public key size in bytes
+    const PUBLIC_KEY_SIZE: usize;
+    
+    /// Secret key size in bytes
+    const SECRET_KEY_SIZE: usize;
+    
+    /// Signature size in bytes
+    const SIGNATURE_SIZE: usize;
+    
+    /// Get parameter name for debugging
+    const NAME: &'static str;
+}
+
+/// Dilithium2 parameters
+#[derive(Debug, Clone, Copy)]
+pub struct Dilithium2Params;
+
+impl DilithiumParams for Dilithium2Params {
+    const K: usize = params::dilithium2::K;
+    const L: usize = params::dilithium2::L;
+    const ETA: usize = params::dilithium2::ETA;
+    const TAU: usize = params::dilithium2::TAU;
+    const BETA: usize = params::dilithium2::BETA;
+    const GAMMA1: i32 = params::dilithium2::GAMMA1;
+    const GAMMA2: i32 = params::dilithium2::GAMMA2;
+    const OMEGA: usize = params::dilithium2::OMEGA;
+    const PUBLIC_KEY_SIZE: usize = params::dilithium2::PUBLIC_KEY_SIZE;
+    const SECRET_KEY_SIZE: usize = params::dilithium2::SECRET_KEY_SIZE;
+    const SIGNATURE_SIZE: usize = params::dilithium2::SIGNATURE_SIZE;
+    const NAME: &'static str = "Dilithium2";
+}
+
+/// Dilithium3 parameters
+#[derive(Debug, Clone, Copy)]
+pub struct Dilithium3Params;
+
+impl DilithiumParams for Dilithium3Params {
+    const K: usize = params::dilithium3::K;
+    const L: usize = params::dilithium3::L;
+    const ETA: usize = params::dilithium3::ETA;
+    const TAU: usize = params::dilithium3::TAU;
+    const BETA: usize = params::dilithium3::BETA;
+    const GAMMA1: i32 = params::dilithium3::GAMMA1;
+    const GAMMA2: i32 = params::dilithium3::GAMMA2;
+    const OMEGA: usize = params::dilithium3::OMEGA;
+    const PUBLIC_KEY_SIZE: usize = params::dilithium3::PUBLIC_KEY_SIZE;
+    const SECRET_KEY_SIZE: usize = params::dilithium3::SECRET_KEY_SIZE;
+    const SIGNATURE_SIZE: usize = params::dilithium3::SIGNATURE_SIZE;
+    const NAME: &'static str = "Dilithium3";
+}
+
+/// Dilithium5 parameters
+#[derive(Debug, Clone, Copy)]
+pub struct Dilithium5Params;
+
+impl DilithiumParams for Dilithium5Params {
+    const K: usize = params::dilithium5::K;
+    const L: usize = params::dilithium5::L;
+    const ETA: usize = params::dilithium5::ETA;
+    const TAU: usize = params::dilithium5::TAU;
+    const BETA: usize = params::dilithium5::BETA;
+    const GAMMA1: i32 = params::dilithium5::GAMMA1;
+    const GAMMA2: i32 = params::dilithium5::GAMMA2;
+    const OMEGA: usize = params::dilithium5::OMEGA;
+    const PUBLIC_KEY_SIZE: usize = params::dilithium5::PUBLIC_KEY_SIZE;
+    const SECRET_KEY_SIZE: usize = params::dilithium5::SECRET_KEY_SIZE;
+    const SIGNATURE_SIZE: usize = params::dilithium5::SIGNATURE_SIZE;
+    const NAME: &'static str = "Dilithium5";
+}
+
+/// Bits per packed coefficient of z
+fn z_bits<P: DilithiumParams>() -> usize {
+    if P::GAMMA1 == 1 << 17 { 18 } else { 20 }
+}
+
+/// Bits per packed coefficient of w1
+fn w1_bits<P: DilithiumParams>() -> usize {
+    if P::GAMMA2 == (Q - 1) / 88 { 6 } else { 4 }
+}
+
+/// Secret key components unpacked from their byte encoding
+struct SecretKeyParts<const K: usize, const L: usize> {
+    rho: [u8; 32],
+    key: SecureArray<32>,
+    tr: [u8; 32],
+    s1: DilithiumPolyVec<L>,
+    s2: DilithiumPolyVec<K>,
+    t0: DilithiumPolyVec<K>,
+}
+
+impl<const K: usize, const L: usize> Drop for SecretKeyParts<K, L> {
+    fn drop(&mut self) {
+        for poly in self.s1.polys.iter_mut() {
+            poly.coeffs.zeroize();
+        }
+        for poly in self.s2.polys.iter_mut().chain(self.t0.polys.iter_mut()) {
+            poly.coeffs.zeroize();
+        }
+    }
+}
+
+fn unpack_secret_key<P: DilithiumParams, const K: usize, const L: usize>(
+    secret_key: &DilithiumSecretKey<K>,
+) -> Result<SecretKeyParts<K, L>> {
+    let bytes = &secret_key.bytes;
+    if bytes.len() != P::SECRET_KEY_SIZE {
+        return Err(Error::InvalidKeySize);
+    }
+    
+    let eta_bytes = 32 * eta_bits(P::ETA);
+    let mut parts = SecretKeyParts {
+        rho: [0u8; 32],
+        key: SecureArray::<32>::zero(),
+        tr: [0u8; 32],
+        s1: DilithiumPolyVec::<L>::zero(),
+        s2: DilithiumPolyVec::<K>::zero(),
+        t0: DilithiumPolyVec::<K>::zero(),
+    };
+    parts.rho.copy_from_slice(&bytes[0..32]);
+    parts.key.as_mut().copy_from_slice(&bytes[32..64]);
+    parts.tr.copy_from_slice(&bytes[64..96]);
+    
+    let mut chunks = bytes[3 * SYMBYTES..].chunks(eta_bytes);
+    for poly in parts.s1.polys.iter_mut().chain(parts.s2.polys.iter_mut()) {
+        *poly = DilithiumPoly::unpack_eta(chunks.next().ok_or(Error::InvalidKeySize)?, P::ETA);
+    }
+    for (i, poly) in parts.t0.polys.iter_mut().enumerate() {
+        let offset = 3 * SYMBYTES + (K + L) * eta_bytes + i * 416;
+        *poly = DilithiumPoly::unpack_t0(&bytes[offset..offset + 416]);
+    }
+    
+    Ok(parts)
+}
+
+/// Map a coefficient to its representative in (-(q-1)/2, (q-1)/2]
+fn center(a: i32) -> i32 {
+    let a = a.rem_euclid(Q);
+    if a > (Q - 1) / 2 { a - Q } else { a }
+}
+
+fn center_vec<const K: usize>(v: &mut DilithiumPolyVec<K>) {
+    for poly in v.polys.iter_mut() {
+        for coeff in poly.coeffs.iter_mut() {
+            *coeff = center(*coeff);
+        }
+    }
+}
+
+/// Sample the masking polynomial with coefficients in (-gamma1, gamma1]
+fn sample_gamma1<P: DilithiumParams>(seed: &[u8], nonce: u16) -> DilithiumPoly {
+    let bits = z_bits::<P>();
+    let mut hasher = Shake256::default();
+    Update::update(&mut hasher, seed);
+    Update::update(&mut hasher, &nonce.to_le_bytes());
+    let mut reader = hasher.finalize_xof();
+    
+    let mut buf = vec![0u8; 32 * bits];
+    reader.read(&mut buf);
+    DilithiumPoly { coeffs: unpack_bits(&buf, bits).map(|v| P::GAMMA1 - v as i32) }
+}
+
+/// Sample the challenge polynomial with `tau` coefficients set to ±1
+fn sample_challenge(seed: &[u8], tau: usize) -> DilithiumPoly {
+    let mut poly = DilithiumPoly::zero();
+    
+    let mut hasher = Shake256::default();
+    Update::update(&mut hasher, seed);
+    let mut reader = hasher.finalize_xof();
+    
+    let mut buf = [0u8; 8];
+    reader.read(&mut buf);
+    let signs = u64::from_le_bytes(buf);
+    
+    let mut c_indices = Vec::with_capacity(tau);
+    while c_indices.len() < tau {
+        let mut idx_buf = [0u8; 1];
+        reader.read(&mut idx_buf);
+        let idx = idx_buf[0] as usize;
+        
+        if !c_indices.contains(&idx) {
+            c_indices.push(idx);
+        }
+    }
+    
+    for (i, &idx) in c_indices.iter().enumerate() {
+        poly.coeffs[idx] = if (signs >> i) & 1 == 1 { 1 } else { -1 };
+    }
+    
+    poly
+}
+
+/// Multiply a polynomial by the sparse challenge in Z_q[X]/(X^256 + 1)
+fn challenge_mul(c: &DilithiumPoly, a: &DilithiumPoly) -> DilithiumPoly {
+    let mut result = DilithiumPoly::zero();
+    
+    for (i, &ci) in c.coeffs.iter().enumerate() {
+        if ci == 0 {
+            continue;
+        }
+        for (j, &aj) in a.coeffs.iter().enumerate() {
+            let k = i + j;
+            // X^256 = -1
+            let term = if k < 256 { ci * aj } else { -ci * aj };
+            result.coeffs[k % 256] += term;
+        }
+    }
+    
+    result
+}
+
+fn challenge_mul_vec<const K: usize>(c: &DilithiumPoly, v: &DilithiumPolyVec<K>) -> DilithiumPolyVec<K> {
+    let mut result = DilithiumPolyVec::<K>::zero();
+    for i in 0..K {
+        result.polys[i] = challenge_mul(c, &v.polys[i]);
+    }
+    center_vec(&mut result);
+    result
+}
+
+/// Compute w = Ay in the coefficient domain
+fn commit<const K: usize, const L: usize>(
+    a: &DilithiumMatrix<K, L>,
+    y: &DilithiumPolyVec<L>,
+) -> DilithiumPolyVec<K> {
+    let mut y_hat = y.clone();
+    y_hat.ntt();
+    let mut w = a.mul_vec(&y_hat);
+    w.reduce();
+    w.inv_ntt();
+    w.freeze();
+    w
+}
+
+fn challenge_hash<P: DilithiumParams, const K: usize>(mu: &[u8], w1: &DilithiumPolyVec<K>) -> [u8; 32] {
+    let mut c_input = Vec::with_capacity(mu.len() + K * 32 * w1_bits::<P>());
+    c_input.extend_from_slice(mu);
+    for poly in &w1.polys {
+        pack_bits(&poly.coeffs.map(|c| c as u32), w1_bits::<P>(), &mut c_input);
+    }
+    h(&c_input)
+}
+
+fn message_digest(tr: &[u8], message: &[u8]) -> [u8; 48] {
+    let mut mu_input = Vec::with_capacity(tr.len() + message.len());
+    mu_input.extend_from_slice(tr);
+    mu_input.extend_from_slice(message);
+    crh(&mu_input)
+}
+
+/// Per-signature seed for the masking vector
+fn masking_seed<R: SecureRandom>(key: &[u8], mu: &[u8], rng: &mut R) -> SecureArray<32> {
+    let mut rand_bytes = SecureArray::<32>::zero();
+    rng.fill_bytes(rand_bytes.as_mut());
+    
+    let mut rhoprime_input = SecureArray::<112>::zero();
+    rhoprime_input.as_mut()[..32].copy_from_slice(key);
+    rhoprime_input.as_mut()[32..64].copy_from_slice(rand_bytes.as_ref());
+    rhoprime_input.as_mut()[64..112].copy_from_slice(mu);
+    
+    let mut rhoprime = SecureArray::<32>::zero();
+    rhoprime.as_mut().copy_from_slice(&g(rhoprime_input.as_ref())[..32]);
+    rhoprime
+}
+
+fn pack_signature<P: DilithiumParams, const K: usize, const L: usize>(
+    c_hash: &[u8; 32],
+    z: &DilithiumPolyVec<L>,
+    hints: &DilithiumPolyVec<K>,
+) -> DilithiumSignature {
+    let mut sig_bytes = Vec::with_capacity(P::SIGNATURE_SIZE);
+    sig_bytes.extend_from_slice(c_hash);
+    
+    for poly in &z.polys {
+        pack_bits(&poly.coeffs.map(|c| (P::GAMMA1 - c) as u32), z_bits::<P>(), &mut sig_bytes);
+    }
+    
+    // Hint positions followed by the running count after each polynomial
+    let mut positions = Vec::with_capacity(P::OMEGA);
+    let mut counts = Vec::with_capacity(K);
+    for poly in &hints.polys {
+        positions.extend(poly.coeffs.iter().enumerate().filter(|(_, &c)| c != 0).map(|(j, _)| j as u8));
+        counts.push(positions.len() as u8);
+    }
+    positions.resize(P::OMEGA, 0);
+    sig_bytes.extend_from_slice(&positions);
+    sig_bytes.extend_from_slice(&counts);
+    
+    DilithiumSignature { bytes: sig_bytes }
+}
+
+/// Unpack z and the hint vector, rejecting non-canonical hint encodings
+fn unpack_signature<P: DilithiumParams, const K: usize, const L: usize>(
+    signature: &DilithiumSignature,
+) -> Option<(DilithiumPolyVec<L>, DilithiumPolyVec<K>)> {
+    let bytes = &signature.bytes;
+    if bytes.len() != P::SIGNATURE_SIZE {
+        return None;
+    }
+    
+    let z_bytes = 32 * z_bits::<P>();
+    let mut z = DilithiumPolyVec::<L>::zero();
+    for (i, poly) in z.polys.iter_mut().enumerate() {
+        let offset = SYMBYTES + i * z_bytes;
+        let values = unpack_bits(&bytes[offset..offset + z_bytes], z_bits::<P>());
+        poly.coeffs = values.map(|v| P::GAMMA1 - v as i32);
+    }
+    
+    let hint_bytes = &bytes[SYMBYTES + L * z_bytes..];
+    let (positions, counts) = hint_bytes.split_at(P::OMEGA);
+    let mut hints = DilithiumPolyVec::<K>::zero();
+    let mut start = 0;
+    for (poly, &count) in hints.polys.iter_mut().zip(counts) {
+        let end = count as usize;
+        if end < start || end > P::OMEGA {
+            return None;
+        }
+        for j in start..end {
+            // Positions must be strictly increasing within a polynomial
+            if j > start && positions[j] <= positions[j - 1] {
+                return None;
+            }
+            poly.coeffs[positions[j] as usize] = 1;
+        }
+        start = end;
+    }
+    if positions[start..].iter().any(|&b| b != 0) {
+        return None;
+    }
+    
+    Some((z, hints))
+}
+
+/// Generate a keypair
+pub fn generate_keypair<P: DilithiumParams, R: SecureRandom, const K: usize, const L: usize>(
+    rng: &mut R
+) -> Result<(DilithiumPublicKey<K>, DilithiumSecretKey<K>)> {
+    // Generate random seed using secure memory
+    let mut seed = SecureArray::<32>::zero();
+    rng.fill_bytes(seed.as_mut());
+    
+    // Expand seed using secure memory
+    let mut expanded_seed = SecureArray::<96>::zero();
+    let hash_out = g(seed.as_ref());
+    expanded_seed.as_mut()[..32].copy_from_slice(seed.as_ref());
+    expanded_seed.as_mut()[32..96].copy_from_slice(&hash_out);
+    
+    let rho = &expanded_seed.as_ref()[0..32];
+    let rhoprime = &expanded_seed.as_ref()[32..64];
+    let key = &expanded_seed.as_ref()[64..96];
+    
+    // Expand matrix A
+    let a = DilithiumMatrix::<K, L>::expand_a(rho);
+    
+    // Sample secret vectors
+    let mut s1 = DilithiumPolyVec::<L>::zero();
+    let mut s2 = DilithiumPolyVec::<K>::zero();
+    
+    for i in 0..L {
+        s1.polys[i] = DilithiumPoly::sample_eta(rhoprime, i as u16, P::ETA);
+    }
+    
+    for i in 0..K {
+        s2.polys[i] = DilithiumPoly::sample_eta(rhoprime, (L + i) as u16, P::ETA);
+    }
+    
+    // Compute t = As1 + s2
+    let mut t = commit(&a, &s1);
+    t = t.add(&s2);
+    t.freeze();
+    
+    // Split t into t1 and t0
+    let (t1, t0) = t.power2round();
+    
+    // Pack public key: rho || t1
+    let mut pk_bytes = Vec::with_capacity(P::PUBLIC_KEY_SIZE);
+    pk_bytes.extend_from_slice(rho);
+    for poly in &t1.polys {
+        poly.pack_t1(&mut pk_bytes);
+    }
+    
+    // Pack secret key: rho || key || tr || s1 || s2 || t0
+    let mut sk_bytes = Vec::with_capacity(P::SECRET_KEY_SIZE);
+    sk_bytes.extend_from_slice(rho);
+    sk_bytes.extend_from_slice(key);
+    sk_bytes.extend_from_slice(&h(&pk_bytes));
+    
+    for poly in &s1.polys {
+        poly.pack_eta(P::ETA, &mut sk_bytes);
+    }
+    for poly in &s2.polys {
+        poly.pack_eta(P::ETA, &mut sk_bytes);
+    }
+    for poly in &t0.polys {
+        poly.pack_t0(&mut sk_bytes);
+    }
+    
+    for poly in s1.polys.iter_mut() {
+        poly.coeffs.zeroize();
+    }
+    for poly in s2.polys.iter_mut() {
+        poly.coeffs.zeroize();
+    }
+    
+    Ok((
+        DilithiumPublicKey { bytes: pk_bytes },
+        DilithiumSecretKey::new(sk_bytes.into())
+    ))
+}
+
+/// Sign a message
+///
+/// The products of the challenge with s1, s2 and t0 are computed directly
+/// in the coefficient domain, independently of the NTT-form state kept by
+/// [`SigningContext`].
+pub fn sign<P: DilithiumParams, R: SecureRandom, const K: usize, const L: usize>(
+    secret_key: &DilithiumSecretKey<K>,
+    message: &[u8],
+    rng: &mut R
+) -> Result<DilithiumSignature> {
+    let sk = unpack_secret_key::<P, K, L>(secret_key)?;
+    let a = DilithiumMatrix::<K, L>::expand_a(&sk.rho);
+    let mu = message_digest(&sk.tr, message);
+    let rhoprime = masking_seed(sk.key.as_ref(), &mu, rng);
+    
+    let mut nonce = 0u16;
+    loop {
+        let y = sample_y::<P, L>(rhoprime.as_ref(), &mut nonce);
+        let w = commit(&a, &y);
+        let (w1, w0) = w.decompose(P::GAMMA2);
+        let c_hash = challenge_hash::<P, K>(&mu, &w1);
+        let c = sample_challenge(&c_hash, P::TAU);
+        
+        let cs1 = challenge_mul_vec(&c, &sk.s1);
+        let cs2 = challenge_mul_vec(&c, &sk.s2);
+        let ct0 = challenge_mul_vec(&c, &sk.t0);
+        
+        if let Some(sig) = finish_signature::<P, K, L>(&c_hash, &y, &w1, &w0, &cs1, &cs2, &ct0) {
+            return Ok(sig);
+        }
+    }
+}
+
+/// Secret key state prepared once for signing many messages
+///
+/// Creating a context unpacks the secret key, expands matrix A and puts s1,
+/// s2 and t0 in NTT form; [`SigningContext::sign`] reuses all of it.
+/// Signatures are identical to [`sign`] given the same key, message and
+/// randomness.
+pub struct SigningContext<P: DilithiumParams, const K: usize, const L: usize> {
+    /// Matrix A expanded from rho
+    a: DilithiumMatrix<K, L>,
+    /// Secret vector s1, in NTT form
+    s1_hat: DilithiumPolyVec<L>,
+    /// Secret vector s2, in NTT form
+    s2_hat: DilithiumPolyVec<K>,
+    /// Low bits t0 of the public vector, in NTT form
+    t0_hat: DilithiumPolyVec<K>,
+    /// Signing key seed
+    key: SecureArray<32>,
+    /// Hash of the public key
+    tr: [u8; 32],
+    _params: core::marker::PhantomData<P>,
+}
+
+impl<P: DilithiumParams, const K: usize, const L: usize> SigningContext<P, K, L> {
+    /// Prepare a secret key for signing
+    pub fn new(secret_key: &DilithiumSecretKey<K>) -> Result<Self> {
+        let sk = unpack_secret_key::<P, K, L>(secret_key)?;
+        
+        let mut s1_hat = sk.s1.clone();
+        let mut s2_hat = sk.s2.clone();
+        let mut t0_hat = sk.t0.clone();
+        s1_hat.ntt();
+        s2_hat.ntt();
+        t0_hat.ntt();
+        
+        let mut key = SecureArray::<32>::zero();
+        key.as_mut().copy_from_slice(sk.key.as_ref());
+        
+        Ok(Self {
+            a: DilithiumMatrix::<K, L>::expand_a(&sk.rho),
+            s1_hat,
+            s2_hat,
+            t0_hat,
+            key,
+            tr: sk.tr,
+            _params: core::marker::PhantomData,
+        })
+    }
+    
+    /// Sign a message
+    pub fn sign<R: SecureRandom>(&self, message: &[u8], rng: &mut R) -> Result<DilithiumSignature> {
+        let mu = message_digest(&self.tr, message);
+        let rhoprime = masking_seed(self.key.as_ref(), &mu, rng);
+        
+        let mut nonce = 0u16;
+        loop {
+            let y = sample_y::<P, L>(rhoprime.as_ref(), &mut nonce);
+            let w = commit(&self.a, &y);
+            let (w1, w0) = w.decompose(P::GAMMA2);
+            let c_hash = challenge_hash::<P, K>(&mu, &w1);
+            let mut c_hat = sample_challenge(&c_hash, P::TAU);
+            c_hat.ntt();
+            
+            let cs1 = pointwise_challenge(&c_hat, &self.s1_hat);
+            let cs2 = pointwise_challenge(&c_hat, &self.s2_hat);
+            let ct0 = pointwise_challenge(&c_hat, &self.t0_hat);
+            
+            if let Some(sig) = finish_signature::<P, K, L>(&c_hash, &y, &w1, &w0, &cs1, &cs2, &ct0) {
+                return Ok(sig);
+            }
+        }
+    }
+}
+
+impl<P: DilithiumParams, const K: usize, const L: usize> core::fmt::Debug for SigningContext<P, K, L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SigningContext")
+            .field("params", &P::NAME)
+            .field("key", &"[REDACTED]")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: DilithiumParams, const K: usize, const L: usize> Drop for SigningContext<P, K, L> {
+    fn drop(&mut self) {
+        for poly in self.s1_hat.polys.iter_mut() {
+            poly.coeffs.zeroize();
+        }
+        for poly in self.s2_hat.polys.iter_mut().chain(self.t0_hat.polys.iter_mut()) {
+            poly.coeffs.zeroize();
+        }
+    }
+}
+
+/// Multiply an NTT-form vector by the NTT-form challenge
+fn pointwise_challenge<const K: usize>(c_hat: &DilithiumPoly, v_hat: &DilithiumPolyVec<K>) -> DilithiumPolyVec<K> {
+    let mut result = DilithiumPolyVec::<K>::zero();
+    for i in 0..K {
+        result.polys[i] = c_hat.pointwise_mul(&v_hat.polys[i]);
+    }
+    result.inv_ntt();
+    center_vec(&mut result);
+    result
+}
+
+fn sample_y<P: DilithiumParams, const L: usize>(rhoprime: &[u8], nonce: &mut u16) -> DilithiumPolyVec<L> {
+    let mut y = DilithiumPolyVec::<L>::zero();
+    for poly in y.polys.iter_mut() {
+        *poly = sample_gamma1::<P>(rhoprime, *nonce);
+        *nonce = nonce.wrapping_add(1);
+    }
+    y
+}
+
+/// Run the rejection checks and pack the signature, or `None` to retry
+fn finish_signature<P: DilithiumParams, const K: usize, const L: usize>(
+    c_hash: &[u8; 32],
+    y: &DilithiumPolyVec<L>,
+    w1: &DilithiumPolyVec<K>,
+    w0: &DilithiumPolyVec<K>,
+    cs1: &DilithiumPolyVec<L>,
+    cs2: &DilithiumPolyVec<K>,
+    ct0: &DilithiumPolyVec<K>,
+) -> Option<DilithiumSignature> {
+    // z = y + cs1
+    let mut z = y.add(cs1);
+    center_vec(&mut z);
+    if !z.check_norm(P::GAMMA1 - P::BETA as i32) {
+        return None;
+    }
+    
+    // r0 = LowBits(w - cs2)
+    let mut r0 = w0.sub(cs2);
+    center_vec(&mut r0);
+    if !r0.check_norm(P::GAMMA2 - P::BETA as i32) || !ct0.check_norm(P::GAMMA2) {
+        return None;
+    }
+    
+    // Hints recover HighBits(w - cs2) from w - cs2 + ct0
+    let mut hints = DilithiumPolyVec::<K>::zero();
+    let mut count = 0;
+    for i in 0..K {
+        for j in 0..256 {
+            let low = r0.polys[i].coeffs[j] + ct0.polys[i].coeffs[j];
+            if make_hint(low, w1.polys[i].coeffs[j], P::GAMMA2) {
+                hints.polys[i].coeffs[j] = 1;
+                count += 1;
+            }
+        }
+    }
+    if count > P::OMEGA {
+        return None;
+    }
+    
+    Some(pack_signature::<P, K, L>(c_hash, &z, &hints))
+}
+
+/// Verify a signature
+pub fn verify<P: DilithiumParams, const K: usize, const L: usize>(
+    public_key: &DilithiumPublicKey<K>,
+    message: &[u8],
+    signature: &DilithiumSignature
+) -> Result<bool> {
+    if public_key.bytes.len() != P::PUBLIC_KEY_SIZE {
+        return Err(Error::InvalidKeySize);
+    }
+    let Some((mut z, hints)) = unpack_signature::<P, K, L>(signature) else {
+        return Ok(false);
+    };
+    if !z.check_norm(P::GAMMA1 - P::BETA as i32) {
+        return Ok(false);
+    }
+    
+    let rho = &public_key.bytes[0..32];
+    let mut t1 = DilithiumPolyVec::<K>::zero();
+    for (i, poly) in t1.polys.iter_mut().enumerate() {
+        let offset = SYMBYTES + i * 320;
+        *poly = DilithiumPoly::unpack_t1(&public_key.bytes[offset..offset + 320]);
+    }
+    
+    let mu = message_digest(&h(&public_key.bytes), message);
+    let c_hash = &signature.bytes[0..32];
+    let mut c = sample_challenge(c_hash, P::TAU);
+    
+    // w' = Az - ct1 * 2^d
+    let a = DilithiumMatrix::<K, L>::expand_a(rho);
+    z.ntt();
+    let mut w = a.mul_vec(&z);
+    c.ntt();
+    for poly in t1.polys.iter_mut() {
+        for coeff in poly.coeffs.iter_mut() {
+            *coeff <<= D;
+        }
+        poly.ntt();
+    }
+    for i in 0..K {
+        w.polys[i] = w.polys[i].sub(&c.pointwise_mul(&t1.polys[i]));
+    }
+    w.reduce();
+    w.inv_ntt();
+    w.freeze();
+    
+    let mut w1 = DilithiumPolyVec::<K>::zero();
+    for i in 0..K {
+        for j in 0..256 {
+            let hint = hints.polys[i].coeffs[j] != 0;
+            w1.polys[i].coeffs[j] = use_hint(w.polys[i].coeffs[j], hint, P::GAMMA2);
+        }
+    }
+    
+    let c_hash_prime = challenge_hash::<P, K>(&mu, &w1);
+    Ok(crate::utils::ct_eq(c_hash, &c_hash_prime))
+}
@@ -36,6 +36,17 @@ pub fn prf(key: &[u8], nonce: u8) -> [u8; 32] {
     output
 }
 
+/// Hash function J (SHAKE256), deriving the implicit rejection key from `z` and a ciphertext
+pub fn j(z: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Shake256::default();
+    Update::update(&mut hasher, z);
+    Update::update(&mut hasher, ciphertext);
+    
+    let mut output = [0u8; 32];
+    hasher.finalize_xof().read(&mut output);
+    output
+}
+
 /// XOF for matrix generation using SHAKE128
 pub struct Xof {
     reader: sha3::Shake128Reader,
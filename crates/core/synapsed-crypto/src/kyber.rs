@@ -10,7 +10,9 @@ pub mod kyber768;
 pub mod kyber1024;
 
 // Re-export the main types  
-pub use kyber512::Kyber512;
+pub use kyber512::{
+    Kyber512, kyber512_keygen_into, kyber512_encapsulate_into, kyber512_decapsulate_into,
+};
 pub use kyber768::Kyber768;
 pub use kyber1024::Kyber1024;
 
@@ -19,6 +21,7 @@ use crate::{
     params::kyber::*,
     poly::{Poly, PolyVec, PolyMat},
     traits::Serializable,
+    secure_memory::SecretBuffer,
     hash::{prf, expand_matrix_a},
     utils::{compress_poly, decompress_poly},
 };
@@ -155,15 +158,29 @@ impl<const K: usize> Serializable for PublicKey<K> {
 }
 
 /// Kyber secret key
-#[derive(Clone, Debug)]
+///
+/// The packed bytes live in a [`SecretBuffer`] that is wiped on drop. The
+/// key is deliberately not `Clone`; use [`duplicate`](Self::duplicate) when a
+/// second copy is really needed.
+#[derive(Debug)]
 pub struct SecretKey<const K: usize> {
-    /// Packed secret key bytes
-    pub bytes: Vec<u8>,
+    bytes: SecretBuffer,
 }
 
-impl<const K: usize> AsRef<[u8]> for SecretKey<K> {
-    fn as_ref(&self) -> &[u8] {
-        &self.bytes
+impl<const K: usize> SecretKey<K> {
+    /// Wrap packed secret key bytes
+    pub(crate) fn new(bytes: SecretBuffer) -> Self {
+        Self { bytes }
+    }
+    
+    /// Get the packed secret key bytes
+    pub fn as_slice(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+    
+    /// Explicitly create an independent copy of the key
+    pub fn duplicate(&self) -> Self {
+        Self::new(self.bytes.duplicate())
     }
 }
 
@@ -181,7 +198,7 @@ impl<const K: usize> Drop for SecretKey<K> {
 
 impl<const K: usize> Serializable for SecretKey<K> {
     fn to_bytes(&self) -> Vec<u8> {
-        self.bytes.clone()
+        self.bytes.to_vec()
     }
     
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
@@ -199,7 +216,7 @@ impl<const K: usize> Serializable for SecretKey<K> {
         }
         
         Ok(Self {
-            bytes: bytes.to_vec(),
+            bytes: SecretBuffer::from_slice(bytes),
         })
     }
 }
@@ -181,7 +181,7 @@ impl<P: KyberParams> GenericKyber<P> {
         
         Ok((
             PublicKey { bytes: pk_bytes },
-            SecretKey { bytes: sk_bytes }
+            SecretKey::new(sk_bytes.into())
         ))
     }
     
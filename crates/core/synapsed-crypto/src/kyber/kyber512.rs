@@ -1,6 +1,10 @@
 //! Kyber512 implementation (NIST Level 1)
 //!
 //! This provides a concrete implementation of ML-KEM-512.
+//!
+//! Besides the [`Kem`] trait implementation, this module exposes an
+//! allocation-free API (`kyber512_*_into`) that writes into caller-provided
+//! fixed-size buffers, for targets without a heap allocator.
 
 use crate::{
     error::{Error, Result},
@@ -8,20 +12,64 @@ use crate::{
     params::kyber::{N, Q},
     poly::{Poly, PolyVec, PolyMat},
     traits::{Kem, SecureRandom},
-    hash::{g, h, prf, expand_matrix_a},
-    utils::{compress_poly, decompress_poly},
+    hash::{g, h, j, prf, sample_uniform, Xof},
+    utils::{compress_poly, decompress_poly, encode_poly, montgomery_reduce},
     kyber::{PublicKey, SecretKey, Ciphertext, SharedSecret},
+    secure_memory::SecretBuffer,
 };
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+/// 2^32 mod q, turns a Montgomery reduction into multiplication by 2^16
+const MONT_SQUARED: i32 = 1353;
 
 /// Kyber512 implementation struct
 #[derive(Debug, Clone)]
 pub struct Kyber512;
 
 impl Kyber512 {
+    /// Public key buffer size for the allocation-free API
+    pub const PUBLIC_KEY_BYTES: usize = PUBLIC_KEY_SIZE;
+    /// Secret key buffer size for the allocation-free API
+    pub const SECRET_KEY_BYTES: usize = SECRET_KEY_SIZE;
+    /// Ciphertext buffer size for the allocation-free API
+    pub const CIPHERTEXT_BYTES: usize = CIPHERTEXT_SIZE;
+    /// Shared secret buffer size for the allocation-free API
+    pub const SHARED_SECRET_BYTES: usize = SHARED_SECRET_SIZE;
+    
     /// Create a new Kyber512 instance
     pub fn new() -> Self {
         Self
     }
+    
+    /// Decapsulate many ciphertexts under one secret key
+    ///
+    /// The secret key is unpacked and matrix A expanded once for the whole
+    /// batch instead of once per ciphertext. Each ciphertext goes through the
+    /// same re-encryption check and constant-time implicit rejection as
+    /// [`kyber512_decapsulate_into`], so every shared secret equals the one
+    /// decapsulating that ciphertext alone would return. Ciphertext lengths
+    /// are checked before any is processed.
+    pub fn decapsulate_batch<C: AsRef<[u8]>>(
+        secret_key: &SecretKey<K>,
+        ciphertexts: &[C],
+    ) -> Result<Vec<SharedSecret>> {
+        let sk: &[u8; SECRET_KEY_SIZE] = secret_key.bytes.as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidKeySize)?;
+        let cts = ciphertexts.iter()
+            .map(|ct| ct.as_ref().try_into().map_err(|_| Error::InvalidCiphertext))
+            .collect::<Result<Vec<&[u8; CIPHERTEXT_SIZE]>>>()?;
+        
+        let key = DecapsulationKey::unpack(sk)?;
+        cts.into_iter()
+            .map(|ct| {
+                let mut bytes = [0u8; SHARED_SECRET_SIZE];
+                key.decapsulate(ct, &mut bytes)?;
+                Ok(SharedSecret { bytes })
+            })
+            .collect()
+    }
 }
 
 impl Default for Kyber512 {
@@ -30,237 +78,380 @@ impl Default for Kyber512 {
     }
 }
 
-impl Kem for Kyber512 {
-    type PublicKey = PublicKey<K>;
-    type SecretKey = SecretKey<K>;
-    type Ciphertext = Ciphertext<K>;
-    type SharedSecret = SharedSecret;
+/// Generate a Kyber512 keypair into caller-provided buffers
+///
+/// This is the allocation-free counterpart of [`Kem::generate_keypair`] and
+/// produces identical bytes for the same RNG output.
+pub fn kyber512_keygen_into<R: SecureRandom>(
+    pk: &mut [u8; Kyber512::PUBLIC_KEY_BYTES],
+    sk: &mut [u8; Kyber512::SECRET_KEY_BYTES],
+    rng: &mut R,
+) -> Result<()> {
+    // Generate random seed
+    let mut d = [0u8; 32];
+    rng.fill_bytes(&mut d);
     
-    const PUBLIC_KEY_SIZE: usize = PUBLIC_KEY_SIZE;
-    const SECRET_KEY_SIZE: usize = SECRET_KEY_SIZE;
-    const CIPHERTEXT_SIZE: usize = CIPHERTEXT_SIZE;
-    const SHARED_SECRET_SIZE: usize = SHARED_SECRET_SIZE;
+    // Hash to get matrix seed and noise seed
+    let g_output = g(&d);
+    let rho = &g_output[..32];
+    let sigma = &g_output[32..];
     
-    fn generate_keypair<R: SecureRandom>(rng: &mut R) -> Result<(Self::PublicKey, Self::SecretKey)> {
-        // Generate random seed
-        let mut d = [0u8; 32];
-        rng.fill_bytes(&mut d);
-        
-        // Hash to get matrix seed and noise seed
-        let g_output = g(&d);
-        let rho = &g_output[..32];
-        let sigma = &g_output[32..];
-        
-        // Generate matrix A
-        let a_matrix = expand_matrix_a::<N, K, K>(rho, false)?;
-        let mut a: PolyMat<N, K, K> = PolyMat::zero();
-        for (_i, (row, matrix_row)) in a.rows.iter_mut().zip(a_matrix.iter()).enumerate().take(K) {
-            for (_j, (poly, &coeffs)) in row.polys.iter_mut().zip(matrix_row.iter()).enumerate().take(K) {
-                poly.coeffs = coeffs;
-            }
+    // Generate matrix A
+    let a = gen_matrix(rho, false)?;
+    
+    // Sample secret vector s
+    let mut s: PolyVec<N, K> = PolyVec::zero();
+    let mut nonce = 0u8;
+    for i in 0..K {
+        let prf_output = prf(sigma, nonce);
+        s.polys[i] = Poly::cbd(&prf_output, ETA1)?;
+        nonce += 1;
+    }
+    
+    // Sample error vector e
+    let mut e: PolyVec<N, K> = PolyVec::zero();
+    for i in 0..K {
+        let prf_output = prf(sigma, nonce);
+        e.polys[i] = Poly::cbd(&prf_output, ETA1)?;
+        nonce += 1;
+    }
+    
+    // Convert to NTT domain
+    s.ntt();
+    s.reduce();
+    e.ntt();
+    
+    // Compute t = As + e, removing the Montgomery factor left by basemul
+    let mut t = a.mul_vec(&s);
+    for poly in &mut t.polys {
+        for coeff in &mut poly.coeffs {
+            *coeff = montgomery_reduce((*coeff as i32) * MONT_SQUARED);
         }
-        
-        // Sample secret vector s
+    }
+    t = t + e;
+    t.reduce();
+    t.caddq();
+    s.caddq();
+    
+    // Pack public key
+    for i in 0..K {
+        encode_poly(&t.polys[i].coeffs, &mut pk[i * 384..(i + 1) * 384]);
+    }
+    pk[K * 384..].copy_from_slice(rho);
+    
+    // Pack secret key
+    for i in 0..K {
+        encode_poly(&s.polys[i].coeffs, &mut sk[i * 384..(i + 1) * 384]);
+    }
+    let pk_start = K * 384;
+    let pk_hash_start = pk_start + PUBLIC_KEY_SIZE;
+    sk[pk_start..pk_hash_start].copy_from_slice(&pk[..]);
+    sk[pk_hash_start..pk_hash_start + 32].copy_from_slice(&h(&pk[..]));
+    rng.fill_bytes(&mut sk[pk_hash_start + 32..]);
+    
+    Ok(())
+}
+
+/// Encapsulate a shared secret into caller-provided buffers
+///
+/// This is the allocation-free counterpart of [`Kem::encapsulate`].
+pub fn kyber512_encapsulate_into<R: SecureRandom>(
+    pk: &[u8; Kyber512::PUBLIC_KEY_BYTES],
+    ct: &mut [u8; Kyber512::CIPHERTEXT_BYTES],
+    ss: &mut [u8; Kyber512::SHARED_SECRET_BYTES],
+    rng: &mut R,
+) -> Result<()> {
+    // Generate random coins
+    let mut m = [0u8; 32];
+    rng.fill_bytes(&mut m);
+    
+    // Hash message
+    let m_hash = h(&m);
+    
+    // Hash to get random coins
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(&m_hash);
+    input[32..].copy_from_slice(&h(&pk[..]));
+    let kr = g(&input);
+    let k = &kr[..32];
+    let r = &kr[32..];
+    
+    let a_t = gen_matrix(&pk[K * 384..], true)?;
+    let t = unpack_t(pk)?;
+    indcpa_encrypt(&a_t, &t, &m_hash, r, ct)?;
+    
+    ss.copy_from_slice(k);
+    m.zeroize();
+    Ok(())
+}
+
+/// Decapsulate a shared secret into a caller-provided buffer
+///
+/// This is the allocation-free counterpart of [`Kem::decapsulate`].
+pub fn kyber512_decapsulate_into(
+    sk: &[u8; Kyber512::SECRET_KEY_BYTES],
+    ct: &[u8; Kyber512::CIPHERTEXT_BYTES],
+    ss: &mut [u8; Kyber512::SHARED_SECRET_BYTES],
+) -> Result<()> {
+    DecapsulationKey::unpack(sk)?.decapsulate(ct, ss)
+}
+
+/// Secret key state unpacked once and reused for every ciphertext
+struct DecapsulationKey {
+    /// Secret vector s, in the NTT domain
+    s: PolyVec<N, K>,
+    /// Transposed matrix A for re-encryption
+    a_t: PolyMat<N, K, K>,
+    /// Public vector t, prepared for re-encryption
+    t: PolyVec<N, K>,
+    /// H(pk)
+    pk_hash: [u8; 32],
+    /// Implicit rejection secret
+    z: [u8; 32],
+}
+
+impl DecapsulationKey {
+    fn unpack(sk: &[u8; Kyber512::SECRET_KEY_BYTES]) -> Result<Self> {
         let mut s: PolyVec<N, K> = PolyVec::zero();
-        let mut nonce = 0u8;
         for i in 0..K {
-            let prf_output = prf(sigma, nonce);
-            s.polys[i] = Poly::cbd(&prf_output, ETA1)?;
-            nonce += 1;
-        }
-        
-        // Sample error vector e
-        let mut e: PolyVec<N, K> = PolyVec::zero();
-        for i in 0..K {
-            let prf_output = prf(sigma, nonce);
-            e.polys[i] = Poly::cbd(&prf_output, ETA1)?;
-            nonce += 1;
+            let offset = i * 384;
+            s.polys[i] = Poly::unpack(&sk[offset..offset + 384])?;
         }
         
-        // Convert to NTT domain
-        s.ntt();
-        e.ntt();
-        
-        // Compute t = As + e
-        let mut t = a.mul_vec(&s);
-        t = t + e;
-        
-        // Pack public key
-        let mut pk_bytes = Vec::with_capacity(PUBLIC_KEY_SIZE);
-        for i in 0..K {
-            let poly_bytes = t.polys[i].pack();
-            pk_bytes.extend_from_slice(&poly_bytes);
-        }
-        pk_bytes.extend_from_slice(rho);
+        let pk_start = K * 384;
+        let pk_hash_start = pk_start + PUBLIC_KEY_SIZE;
+        let pk: &[u8; PUBLIC_KEY_SIZE] = sk[pk_start..pk_hash_start]
+            .try_into()
+            .map_err(|_| Error::InvalidKeySize)?;
         
-        // Pack secret key
-        let mut sk_bytes = Vec::with_capacity(SECRET_KEY_SIZE);
-        for i in 0..K {
-            let poly_bytes = s.polys[i].pack();
-            sk_bytes.extend_from_slice(&poly_bytes);
-        }
-        sk_bytes.extend_from_slice(&pk_bytes);
-        sk_bytes.extend_from_slice(&h(&pk_bytes));
+        let mut pk_hash = [0u8; 32];
+        pk_hash.copy_from_slice(&sk[pk_hash_start..pk_hash_start + 32]);
         let mut z = [0u8; 32];
-        rng.fill_bytes(&mut z);
-        sk_bytes.extend_from_slice(&z);
-        
-        Ok((
-            PublicKey { bytes: pk_bytes },
-            SecretKey { bytes: sk_bytes },
-        ))
+        z.copy_from_slice(&sk[pk_hash_start + 32..]);
+        
+        Ok(Self {
+            s,
+            a_t: gen_matrix(&pk[K * 384..], true)?,
+            t: unpack_t(pk)?,
+            pk_hash,
+            z,
+        })
     }
     
-    fn encapsulate<R: SecureRandom>(public_key: &Self::PublicKey, rng: &mut R) -> Result<(Self::Ciphertext, Self::SharedSecret)> {
-        // Generate random coins
-        let mut m = [0u8; 32];
-        rng.fill_bytes(&mut m);
-        
-        // Hash message
-        let m_hash = h(&m);
-        
-        // Extract rho from public key
-        let rho = &public_key.bytes[K * 384..];
-        
-        // Hash to get random coins
-        let mut input = Vec::new();
-        input.extend_from_slice(&m_hash);
-        input.extend_from_slice(&h(&public_key.bytes));
-        let kr = g(&input);
-        let k = &kr[..32];
-        let r = &kr[32..];
-        
-        // Generate matrix A  
-        let a_matrix = expand_matrix_a::<N, K, K>(rho, true)?; // transposed
-        let mut a: PolyMat<N, K, K> = PolyMat::zero();
-        for (_i, (row, matrix_row)) in a.rows.iter_mut().zip(a_matrix.iter()).enumerate().take(K) {
-            for (_j, (poly, &coeffs)) in row.polys.iter_mut().zip(matrix_row.iter()).enumerate().take(K) {
-                poly.coeffs = coeffs;
-            }
-        }
-        
-        // Sample noise
-        let mut r_vec: PolyVec<N, K> = PolyVec::zero();
-        let mut nonce = 0u8;
-        for i in 0..K {
-            let prf_output = prf(r, nonce);
-            r_vec.polys[i] = Poly::cbd(&prf_output, ETA1)?;
-            nonce += 1;
-        }
-        
-        let _prf_output = prf(r, nonce);
-        let mut e1: PolyVec<N, K> = PolyVec::zero();
+    /// Decapsulate with implicit rejection
+    ///
+    /// The ciphertext is always re-encrypted and compared, and the result is
+    /// selected in constant time, so a rejected ciphertext takes exactly the
+    /// same path as an accepted one.
+    fn decapsulate(
+        &self,
+        ct: &[u8; Kyber512::CIPHERTEXT_BYTES],
+        ss: &mut [u8; Kyber512::SHARED_SECRET_BYTES],
+    ) -> Result<()> {
+        // Decompress ciphertext
+        let mut u: PolyVec<N, K> = PolyVec::zero();
         for i in 0..K {
-            let prf_output = prf(r, nonce);
-            e1.polys[i] = Poly::cbd(&prf_output, ETA2)?;
-            nonce += 1;
+            let offset = i * DU * N / 8;
+            decompress_poly(&ct[offset..offset + DU * N / 8], DU, &mut u.polys[i].coeffs)?;
         }
         
-        let prf_output = prf(r, nonce);
-        let e2 = Poly::cbd(&prf_output, ETA2)?;
-        
-        // Convert to NTT
-        r_vec.ntt();
+        let mut v = Poly::zero();
+        let v_offset = K * DU * N / 8;
+        decompress_poly(&ct[v_offset..], DV, &mut v.coeffs)?;
         
-        // Compute u = A^T * r + e1
-        let mut u = a.mul_vec(&r_vec);
-        u.inv_ntt();
-        u = u + e1;
+        // Compute m' = v - s^T * u
+        u.ntt();
+        u.reduce();
+        let mut su = self.s.inner_product(&u);
+        su.inv_ntt();
+        let mut m_prime = v - su;
+        m_prime.reduce();
         
-        // Unpack t from public key
-        let mut t: PolyVec<N, K> = PolyVec::zero();
-        for i in 0..K {
-            let offset = i * 384;
-            t.polys[i] = Poly::unpack(&public_key.bytes[offset..offset + 384])?;
+        // Extract message bits: 1 when |coeff| >= q/4, without branching on the coefficient
+        let mut m_prime_bytes = [0u8; 32];
+        for i in 0..256 {
+            let coeff = m_prime.coeffs[i];
+            let sign = coeff >> 15;
+            let magnitude = (coeff ^ sign) - sign;
+            let bit = (((Q / 4 - 1) - magnitude) >> 15) & 1;
+            m_prime_bytes[i / 8] |= (bit as u8) << (i % 8);
         }
+        m_prime.zeroize();
         
-        // Compute v = t^T * r + e2 + decompress(m)
-        t.ntt();
-        let mut v = t.inner_product(&r_vec);
-        v.inv_ntt();
-        v = v + e2;
+        // Compute K' and the re-encryption coins
+        let mut input = [0u8; 64];
+        input[..32].copy_from_slice(&m_prime_bytes);
+        input[32..].copy_from_slice(&self.pk_hash);
+        let mut kr_prime = g(&input);
         
-        // Add message
-        let mut m_poly = Poly::zero();
-        for i in 0..256 {
-            m_poly.coeffs[i] = if (m_hash[i / 8] >> (i % 8)) & 1 == 1 {
-                (Q + 1) / 2
-            } else {
-                0
-            };
-        }
-        v = v + m_poly;
+        let mut ct_prime = [0u8; CIPHERTEXT_SIZE];
+        indcpa_encrypt(&self.a_t, &self.t, &m_prime_bytes, &kr_prime[32..], &mut ct_prime)?;
+        let accepted = ct_prime[..].ct_eq(&ct[..]);
+        let rejected = j(&self.z, &ct[..]);
         
-        // Compress and pack ciphertext
-        let mut ct_bytes = Vec::with_capacity(CIPHERTEXT_SIZE);
+        for (i, byte) in ss.iter_mut().enumerate() {
+            *byte = u8::conditional_select(&rejected[i], &kr_prime[i], accepted);
+        }
         
-        // Compress u
-        for i in 0..K {
-            let mut compressed_u = vec![0u8; DU * N / 8];
-            compress_poly(&u.polys[i].coeffs, DU, &mut compressed_u)?;
-            ct_bytes.extend_from_slice(&compressed_u);
+        m_prime_bytes.zeroize();
+        input.zeroize();
+        kr_prime.zeroize();
+        Ok(())
+    }
+}
+
+impl Drop for DecapsulationKey {
+    fn drop(&mut self) {
+        self.s.zeroize();
+        self.z.zeroize();
+    }
+}
+
+/// Unpack t from a public key; it is stored in the NTT domain
+fn unpack_t(pk: &[u8; Kyber512::PUBLIC_KEY_BYTES]) -> Result<PolyVec<N, K>> {
+    let mut t: PolyVec<N, K> = PolyVec::zero();
+    for i in 0..K {
+        let offset = i * 384;
+        t.polys[i] = Poly::unpack(&pk[offset..offset + 384])?;
+    }
+    Ok(t)
+}
+
+/// Encrypt a 32-byte message with the given coins, deterministically
+fn indcpa_encrypt(
+    a_t: &PolyMat<N, K, K>,
+    t: &PolyVec<N, K>,
+    m: &[u8; 32],
+    coins: &[u8],
+    ct: &mut [u8; Kyber512::CIPHERTEXT_BYTES],
+) -> Result<()> {
+    // Sample noise
+    let mut r_vec: PolyVec<N, K> = PolyVec::zero();
+    let mut nonce = 0u8;
+    for i in 0..K {
+        let prf_output = prf(coins, nonce);
+        r_vec.polys[i] = Poly::cbd(&prf_output, ETA1)?;
+        nonce += 1;
+    }
+    
+    let mut e1: PolyVec<N, K> = PolyVec::zero();
+    for i in 0..K {
+        let prf_output = prf(coins, nonce);
+        e1.polys[i] = Poly::cbd(&prf_output, ETA2)?;
+        nonce += 1;
+    }
+    
+    let prf_output = prf(coins, nonce);
+    let e2 = Poly::cbd(&prf_output, ETA2)?;
+    
+    // Convert to NTT
+    r_vec.ntt();
+    r_vec.reduce();
+    
+    // Compute u = A^T * r + e1
+    let mut u = a_t.mul_vec(&r_vec);
+    u.inv_ntt();
+    u = u + e1;
+    u.reduce();
+    u.caddq();
+    
+    // Compute v = t^T * r + e2 + decompress(m)
+    let mut v = t.inner_product(&r_vec);
+    v.inv_ntt();
+    v = v + e2;
+    
+    // Add message
+    let mut m_poly = Poly::zero();
+    for i in 0..256 {
+        m_poly.coeffs[i] = if (m[i / 8] >> (i % 8)) & 1 == 1 {
+            (Q + 1) / 2
+        } else {
+            0
+        };
+    }
+    v = v + m_poly;
+    v.reduce();
+    v.caddq();
+    
+    // Compress u and v into the ciphertext
+    for i in 0..K {
+        let offset = i * DU * N / 8;
+        compress_poly(&u.polys[i].coeffs, DU, &mut ct[offset..offset + DU * N / 8])?;
+    }
+    compress_poly(&v.coeffs, DV, &mut ct[K * DU * N / 8..])?;
+    
+    r_vec.zeroize();
+    Ok(())
+}
+
+/// Expand the seed into matrix A without heap allocation
+fn gen_matrix(rho: &[u8], transposed: bool) -> Result<PolyMat<N, K, K>> {
+    let mut a: PolyMat<N, K, K> = PolyMat::zero();
+    for (i, row) in a.rows.iter_mut().enumerate() {
+        for (j, poly) in row.polys.iter_mut().enumerate() {
+            let (xof_row, xof_col) = if transposed { (j, i) } else { (i, j) };
+            let mut xof = Xof::new(rho, xof_row as u8, xof_col as u8);
+            
+            let mut idx = 0;
+            while idx < N {
+                if let Some(val) = sample_uniform(&mut xof, Q as i32)? {
+                    poly.coeffs[idx] = val as i16;
+                    idx += 1;
+                }
+            }
         }
+    }
+    Ok(a)
+}
+
+impl Kem for Kyber512 {
+    type PublicKey = PublicKey<K>;
+    type SecretKey = SecretKey<K>;
+    type Ciphertext = Ciphertext<K>;
+    type SharedSecret = SharedSecret;
+    
+    const PUBLIC_KEY_SIZE: usize = PUBLIC_KEY_SIZE;
+    const SECRET_KEY_SIZE: usize = SECRET_KEY_SIZE;
+    const CIPHERTEXT_SIZE: usize = CIPHERTEXT_SIZE;
+    const SHARED_SECRET_SIZE: usize = SHARED_SECRET_SIZE;
+    
+    fn generate_keypair<R: SecureRandom>(rng: &mut R) -> Result<(Self::PublicKey, Self::SecretKey)> {
+        let mut pk_bytes = [0u8; PUBLIC_KEY_SIZE];
+        let mut sk_bytes = [0u8; SECRET_KEY_SIZE];
+        let result = kyber512_keygen_into(&mut pk_bytes, &mut sk_bytes, rng);
+        let secret_key = SecretKey::new(SecretBuffer::from_slice(&sk_bytes));
+        sk_bytes.zeroize();
+        result?;
+        
+        Ok((PublicKey { bytes: pk_bytes.to_vec() }, secret_key))
+    }
+    
+    fn encapsulate<R: SecureRandom>(public_key: &Self::PublicKey, rng: &mut R) -> Result<(Self::Ciphertext, Self::SharedSecret)> {
+        let pk: &[u8; PUBLIC_KEY_SIZE] = public_key.bytes.as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidKeySize)?;
         
-        // Compress v
-        let mut compressed_v = vec![0u8; DV * N / 8];
-        compress_poly(&v.coeffs, DV, &mut compressed_v)?;
-        ct_bytes.extend_from_slice(&compressed_v);
+        let mut ct_bytes = [0u8; CIPHERTEXT_SIZE];
+        let mut k_array = [0u8; SHARED_SECRET_SIZE];
+        kyber512_encapsulate_into(pk, &mut ct_bytes, &mut k_array, rng)?;
         
-        let k_array: [u8; 32] = k.try_into().map_err(|_| Error::CryptoError)?;
         Ok((
-            Ciphertext { bytes: ct_bytes },
+            Ciphertext { bytes: ct_bytes.to_vec() },
             SharedSecret { bytes: k_array },
         ))
     }
     
     fn decapsulate(secret_key: &Self::SecretKey, ciphertext: &Self::Ciphertext) -> Result<Self::SharedSecret> {
-        // Extract s from secret key
-        let mut s: PolyVec<N, K> = PolyVec::zero();
-        for i in 0..K {
-            let offset = i * 384;
-            s.polys[i] = Poly::unpack(&secret_key.bytes[offset..offset + 384])?;
-        }
-        
-        // Decompress ciphertext
-        let mut u: PolyVec<N, K> = PolyVec::zero();
-        for i in 0..K {
-            let offset = i * DU * N / 8;
-            decompress_poly(&ciphertext.bytes[offset..offset + DU * N / 8], DU, &mut u.polys[i].coeffs)?;
-        }
-        
-        let mut v = Poly::zero();
-        let v_offset = K * DU * N / 8;
-        decompress_poly(&ciphertext.bytes[v_offset..], DV, &mut v.coeffs)?;
-        
-        // Compute m' = v - s^T * u
-        u.ntt();
-        let su = s.inner_product(&u);
-        let mut su_normal = su;
-        su_normal.inv_ntt();
-        
-        let m_prime = v - su_normal;
-        
-        // Extract message bits
-        let mut m_prime_bytes = [0u8; 32];
-        for i in 0..256 {
-            let bit = if m_prime.coeffs[i].abs() < Q / 4 { 0 } else { 1 };
-            m_prime_bytes[i / 8] |= bit << (i % 8);
-        }
-        
-        // Get hash of public key from secret key
-        let pk_start = K * 384;
-        let pk_end = pk_start + PUBLIC_KEY_SIZE;
-        let pk_hash_start = pk_end;
-        let pk_hash = &secret_key.bytes[pk_hash_start..pk_hash_start + 32];
+        let sk: &[u8; SECRET_KEY_SIZE] = secret_key.bytes.as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidKeySize)?;
+        let ct: &[u8; CIPHERTEXT_SIZE] = ciphertext.bytes.as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidCiphertext)?;
         
-        // Compute K'
-        let mut input = Vec::new();
-        input.extend_from_slice(&m_prime_bytes);
-        input.extend_from_slice(pk_hash);
-        let kr_prime = g(&input);
-        let k_prime = &kr_prime[..32];
+        let mut k_prime_array = [0u8; SHARED_SECRET_SIZE];
+        kyber512_decapsulate_into(sk, ct, &mut k_prime_array)?;
         
-        let k_prime_array: [u8; 32] = k_prime.try_into().map_err(|_| Error::CryptoError)?;
         Ok(SharedSecret { 
             bytes: k_prime_array 
         })
@@ -156,7 +156,7 @@ pub mod prelude {
         api::{
             // Core functions
             generate_keypair, encapsulate, decapsulate,
-            generate_signing_keypair, sign, sign_deterministic, verify,
+            generate_signing_keypair, sign, sign_deterministic, sign_with_seed, verify,
             // Types
             KemAlgorithm, SignatureAlgorithm, KeyPair, Algorithm, SecurityLevel,
         },
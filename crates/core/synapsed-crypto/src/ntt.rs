@@ -45,46 +45,48 @@ pub fn ntt(coeffs: &mut [i16]) {
     }
 }
 
-/// Perform inverse NTT
+/// Perform inverse NTT, multiplying by the Montgomery factor
 pub fn inv_ntt(coeffs: &mut [i16]) {
     let mut k = 127;
     let mut len = 2;
     
     while len <= 128 {
         for start in (0..256).step_by(2 * len) {
-            let zeta = -ZETAS[k];
+            let zeta = ZETAS[k];
             k -= 1;
             
             for j in 0..len {
                 let t = coeffs[start + j];
-                coeffs[start + j] = t.wrapping_add(coeffs[start + j + len]);
-                coeffs[start + j + len] = montgomery_reduce((zeta as i32).wrapping_mul((coeffs[start + j + len].wrapping_sub(t)) as i32));
+                coeffs[start + j] = crate::utils::barrett_reduce(t.wrapping_add(coeffs[start + j + len]));
+                coeffs[start + j + len] = montgomery_reduce((zeta as i32) * (coeffs[start + j + len].wrapping_sub(t) as i32));
             }
         }
         len <<= 1;
     }
     
-    // Final multiplication by n^{-1}
-    const F: i16 = 1441; // n^{-1} mod q
+    // Final multiplication by mont^2 / 128
+    const F: i16 = 1441;
     for coeff in coeffs {
-        *coeff = montgomery_reduce((F as i32).wrapping_mul(*coeff as i32));
+        *coeff = montgomery_reduce((F as i32) * (*coeff as i32));
     }
 }
 
 /// Pointwise multiplication of polynomials in NTT domain
+///
+/// Each pair of coefficients is a degree-one polynomial modulo X^2 - zeta;
+/// the result carries a Montgomery factor of 2^-16.
 pub fn basemul(r: &mut [i16], a: &[i16], b: &[i16]) {
-    const ZETA: i16 = -1044; // zeta^{2*64+1}
+    let fqmul = |x: i16, y: i16| montgomery_reduce((x as i32) * (y as i32));
     
     for i in 0..64 {
-        let (a0, a1) = (a[4*i], a[4*i + 1]);
-        let (a2, a3) = (a[4*i + 2], a[4*i + 3]);
-        let (b0, b1) = (b[4*i], b[4*i + 1]);
-        let (b2, b3) = (b[4*i + 2], b[4*i + 3]);
-        
-        r[4*i] = montgomery_reduce((a0 as i32).wrapping_mul(b0 as i32).wrapping_add((ZETA as i32).wrapping_mul(a1 as i32).wrapping_mul(b1 as i32)));
-        r[4*i + 1] = montgomery_reduce((a0 as i32).wrapping_mul(b1 as i32).wrapping_add((a1 as i32).wrapping_mul(b0 as i32)));
-        r[4*i + 2] = montgomery_reduce((a2 as i32).wrapping_mul(b2 as i32).wrapping_add((ZETA as i32).wrapping_mul(a3 as i32).wrapping_mul(b3 as i32)));
-        r[4*i + 3] = montgomery_reduce((a2 as i32).wrapping_mul(b3 as i32).wrapping_add((a3 as i32).wrapping_mul(b2 as i32)));
+        let zeta = ZETAS[64 + i];
+        for (offset, zeta) in [(4 * i, zeta), (4 * i + 2, -zeta)] {
+            let (a0, a1) = (a[offset], a[offset + 1]);
+            let (b0, b1) = (b[offset], b[offset + 1]);
+            
+            r[offset] = fqmul(fqmul(a1, b1), zeta).wrapping_add(fqmul(a0, b0));
+            r[offset + 1] = fqmul(a0, b1).wrapping_add(fqmul(a1, b0));
+        }
     }
 }
 
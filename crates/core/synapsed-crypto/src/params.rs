@@ -140,7 +140,7 @@ pub mod dilithium {
     /// Size of collision-resistant hash output in bytes
     pub const CRHBYTES: usize = 64;
     /// Size of commitment hash output in bytes
-    pub const TRBYTES: usize = 64;
+    pub const TRBYTES: usize = 32;
     
     /// Dilithium2 parameters (NIST Level 2)
     pub mod dilithium2 {
@@ -5,6 +5,7 @@
 
 use zeroize::{Zeroize, ZeroizeOnDrop};
 use core::fmt;
+use core::ops::Deref;
 
 /// Wrapper for sensitive byte arrays that automatically zeros memory on drop
 #[derive(Clone)]
@@ -82,6 +83,90 @@ impl AsMut<[u8]> for SecureBytes {
     }
 }
 
+/// Owned secret key material that is wiped on drop
+///
+/// Unlike [`SecureBytes`], this type does not implement `Clone`: copies of
+/// key material must be made explicitly with [`SecretBuffer::duplicate`].
+/// Its `Debug` output never includes the bytes themselves.
+pub struct SecretBuffer {
+    data: Vec<u8>,
+}
+
+impl SecretBuffer {
+    /// Take ownership of sensitive bytes
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+    
+    /// Copy sensitive bytes from a slice
+    pub fn from_slice(data: &[u8]) -> Self {
+        Self { data: data.to_vec() }
+    }
+    
+    /// Get the length of the data
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    
+    /// Check if data is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    
+    /// Get the secret bytes
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+    
+    /// Explicitly create an independent copy of the secret bytes
+    pub fn duplicate(&self) -> Self {
+        Self::from_slice(&self.data)
+    }
+}
+
+impl From<Vec<u8>> for SecretBuffer {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl Deref for SecretBuffer {
+    type Target = [u8];
+    
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AsRef<[u8]> for SecretBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Zeroize for SecretBuffer {
+    fn zeroize(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl Drop for SecretBuffer {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretBuffer {}
+
+impl fmt::Debug for SecretBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretBuffer")
+            .field("len", &self.data.len())
+            .field("data", &"[REDACTED]")
+            .finish()
+    }
+}
+
 /// Wrapper for fixed-size sensitive arrays
 #[derive(Clone)]
 pub struct SecureArray<const N: usize> {
@@ -235,6 +320,32 @@ mod tests {
         assert!(secure.is_empty());
     }
     
+    #[test]
+    fn test_secret_buffer_zeroize() {
+        let mut secret = SecretBuffer::new(vec![0xAA; 32]);
+        assert_eq!(secret.as_slice(), &[0xAA; 32]);
+        
+        // Drop delegates to zeroize, which wipes and truncates the bytes
+        secret.zeroize();
+        assert!(secret.is_empty());
+        
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<SecretBuffer>();
+    }
+    
+    #[test]
+    fn test_secret_buffer_debug_redacted() {
+        let secret = SecretBuffer::from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        let debug = format!("{:?}", secret);
+        
+        assert!(debug.contains("[REDACTED]"));
+        assert!(!debug.contains("222"));
+        assert!(!debug.contains("0xDE") && !debug.contains("de, ad"));
+        
+        let copy = secret.duplicate();
+        assert_eq!(copy.as_slice(), secret.as_slice());
+    }
+    
     #[test]
     fn test_secure_array() {
         let mut secure = SecureArray::<32>::zero();
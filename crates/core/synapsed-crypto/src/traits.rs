@@ -13,7 +13,7 @@ pub trait Kem: Debug + Send + Sync {
     type PublicKey: AsRef<[u8]> + Debug + Clone + PartialEq + Eq;
     
     /// Type representing a secret key
-    type SecretKey: AsRef<[u8]> + Debug + Clone + Zeroize;
+    type SecretKey: Debug + Zeroize;
     
     /// Type representing a ciphertext
     type Ciphertext: AsRef<[u8]> + Debug + Clone + PartialEq + Eq;
@@ -57,7 +57,7 @@ pub trait Signature: Debug + Send + Sync {
     type PublicKey: AsRef<[u8]> + Debug + Clone + PartialEq + Eq;
     
     /// Type representing a secret key
-    type SecretKey: AsRef<[u8]> + Debug + Clone + Zeroize;
+    type SecretKey: Debug + Zeroize;
     
     /// Type representing a signature
     type Sig: AsRef<[u8]> + Debug + Clone + PartialEq + Eq;
@@ -456,19 +456,19 @@ pub fn montgomery_reduce(a: i32) -> i16 {
 #[inline]
 pub fn barrett_reduce_dilithium(a: i32) -> i32 {
     const Q: i32 = 8380417; // 2^23 - 2^13 + 1
-    const M: i64 = 549755813889; // ceil(2^43/Q)
     
-    let t = ((M.wrapping_mul(a as i64)) >> 43) as i32;
+    // q is close to 2^23, so rounding a / 2^23 estimates the quotient
+    let t = a.wrapping_add(1 << 22) >> 23;
     a.wrapping_sub(t.wrapping_mul(Q))
 }
 
 /// Montgomery reduction for Dilithium
 #[inline]
 pub fn montgomery_reduce_dilithium(a: i64) -> i32 {
-    const QINV: u32 = 4236238847; // -q^{-1} mod 2^32
+    const QINV: i32 = 58728449; // q^{-1} mod 2^32
     const Q: i32 = 8380417;
     
-    let t = (a as i32).wrapping_mul(QINV as i32);
+    let t = (a as i32).wrapping_mul(QINV);
     ((a.wrapping_sub(t as i64 * Q as i64)) >> 32) as i32
 }
 
@@ -202,6 +202,83 @@ fn test_deterministic_signatures() {
     // due to randomized hashing, so we don't assert they're equal
 }
 
+#[test]
+fn test_sign_with_seed_is_reproducible() {
+    let algorithms = [
+        SignatureAlgorithm::Dilithium2,
+        SignatureAlgorithm::Dilithium3,
+        SignatureAlgorithm::Dilithium5,
+    ];
+    let msg = b"known-answer test message";
+    let seed = [0x42u8; 32];
+
+    for alg in algorithms {
+        let mut rng = DefaultRng::default();
+        let (pk, sk) = generate_signing_keypair(alg, &mut rng).unwrap();
+
+        let sig1 = sign_with_seed(alg, &sk, msg, &seed).unwrap();
+        let sig2 = sign_with_seed(alg, &sk, msg, &seed).unwrap();
+
+        // Identical inputs and seed must give byte-identical signatures
+        assert_eq!(sig1, sig2);
+        assert!(verify(alg, &pk, msg, &sig1).unwrap());
+
+        // A different seed still yields a valid signature
+        let sig3 = sign_with_seed(alg, &sk, msg, &[0x24u8; 32]).unwrap();
+        assert!(verify(alg, &pk, msg, &sig3).unwrap());
+    }
+}
+
+#[test]
+fn test_decapsulate_batch_matches_single() {
+    let alg = KemAlgorithm::Kyber512;
+    let mut rng = DefaultRng::default();
+    let (pk, sk) = generate_keypair(alg, &mut rng).unwrap();
+
+    // Alternate valid and corrupted ciphertexts
+    let (ciphertexts, secrets): (Vec<Vec<u8>>, Vec<Vec<u8>>) = (0..6)
+        .map(|i| {
+            let (mut ct, ss) = encapsulate(alg, &pk, &mut rng).unwrap();
+            if i % 2 == 1 {
+                ct[i] ^= 0x5A;
+            }
+            (ct, ss)
+        })
+        .unzip();
+
+    let batch = decapsulate_batch(alg, &sk, &ciphertexts).unwrap();
+    assert_eq!(batch.len(), ciphertexts.len());
+    for (i, (ct, ss)) in ciphertexts.iter().zip(&batch).enumerate() {
+        assert_eq!(ss, &decapsulate(alg, &sk, ct).unwrap());
+        if i % 2 == 0 {
+            assert_eq!(ss, &secrets[i]);
+        } else {
+            assert_ne!(ss, &secrets[i]);
+        }
+    }
+
+    // Rejected ciphertexts still get distinct secrets
+    assert_ne!(batch[1], batch[3]);
+
+    assert!(decapsulate_batch::<Vec<u8>>(alg, &sk, &[]).unwrap().is_empty());
+
+    // One malformed ciphertext fails the whole batch
+    let mut malformed = ciphertexts.clone();
+    malformed[2].pop();
+    assert!(matches!(decapsulate_batch(alg, &sk, &malformed), Err(Error::InvalidCiphertext)));
+}
+
+#[test]
+fn test_decapsulate_batch_only_supports_kyber512() {
+    assert!(KemAlgorithm::Kyber512.supports_batch_decapsulation());
+    for alg in [KemAlgorithm::Kyber768, KemAlgorithm::Kyber1024] {
+        assert!(!alg.supports_batch_decapsulation());
+        let sk = vec![0u8; alg.secret_key_size()];
+        let ciphertexts = vec![vec![0u8; alg.ciphertext_size()]];
+        assert!(matches!(decapsulate_batch(alg, &sk, &ciphertexts), Err(Error::InvalidParameter)));
+    }
+}
+
 #[test]
 fn test_cross_algorithm_compatibility() {
     // Ensure algorithms don't interfere with each other
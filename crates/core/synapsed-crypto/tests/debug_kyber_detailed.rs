@@ -17,7 +17,7 @@ fn debug_kyber_detailed() {
     println!("✓ Keypair generated");
     
     // Extract z from secret key for inspection
-    let sk_bytes = sk.as_ref();
+    let sk_bytes = sk.as_slice();
     let z = &sk_bytes[1600..]; // Last 32 bytes
     println!("\nOriginal z from keygen: {:02x?}", &z[..8]);
     
@@ -0,0 +1,72 @@
+//! Known-answer tests for Dilithium key generation and seeded signing
+//!
+//! The key digests were cross-checked against OpenSSL's ML-DSA: importing
+//! rho, s1, s2 and t0 from each secret key there derives the same public
+//! key, which covers matrix expansion, the NTT, power2round and the key
+//! packing that Dilithium and ML-DSA share. Signatures hash the message
+//! differently from ML-DSA, so their digests only pin the current output.
+
+use hex_literal::hex;
+use synapsed_crypto::{
+    api::{generate_signing_keypair, sign_with_seed, verify, SignatureAlgorithm},
+    hash::h,
+    random::TestRng,
+};
+
+/// SHA3-256 digests of the outputs for one parameter set
+struct KnownAnswer {
+    algorithm: SignatureAlgorithm,
+    public_key: [u8; 32],
+    secret_key: [u8; 32],
+    signature: [u8; 32],
+}
+
+const KEYGEN_SEED: u64 = 2132;
+const MESSAGE: &[u8] = b"known-answer test message";
+const SIGNING_SEED: [u8; 32] = [0x42; 32];
+
+fn known_answers() -> [KnownAnswer; 3] {
+    [
+        KnownAnswer {
+            algorithm: SignatureAlgorithm::Dilithium2,
+            public_key: hex!("eac3b183dc9971f5b56873998d821eeeae899b91b2db1f971b3ac36c69fb9166"),
+            secret_key: hex!("9cd57886a9e15668c472bd0e9acf34ea7941f9dc136445922950488d1083126b"),
+            signature: hex!("7c310a6c10412a6390af6eafbc3e7ac6a3cb80561d568844cfa969c1d27a52fd"),
+        },
+        KnownAnswer {
+            algorithm: SignatureAlgorithm::Dilithium3,
+            public_key: hex!("e1e3b7f191840a3ea96642eeb1b9f4ef3f0144be5bdcaa218c8d03bd58148907"),
+            secret_key: hex!("d23416c7ba79afbb215ccfefc53b1e81c7a31038842e5e6b757952a903a7cde0"),
+            signature: hex!("1ed2074b043359210f8d002acb466ba9d58ddd64c641b84f83f42a7ebb9e7e78"),
+        },
+        KnownAnswer {
+            algorithm: SignatureAlgorithm::Dilithium5,
+            public_key: hex!("5e364c577bc5d9fe13440d9177babf11862a0ba58d8c0c30732a32fe26c7a877"),
+            secret_key: hex!("23d97d29cbd340143ef84a59d0819c6aa3f0b42b33edfb79511ff831d5c9c0aa"),
+            signature: hex!("9bf936108033f803407b64d979f3abcc4e586fc4fdec61cad59799e8b247e9ab"),
+        },
+    ]
+}
+
+#[test]
+fn test_keygen_matches_known_answers() {
+    for kat in known_answers() {
+        let mut rng = TestRng::new(KEYGEN_SEED);
+        let (pk, sk) = generate_signing_keypair(kat.algorithm, &mut rng).unwrap();
+
+        assert_eq!(h(&pk), kat.public_key, "{:?} public key", kat.algorithm);
+        assert_eq!(h(&sk), kat.secret_key, "{:?} secret key", kat.algorithm);
+    }
+}
+
+#[test]
+fn test_sign_with_seed_matches_known_answers() {
+    for kat in known_answers() {
+        let mut rng = TestRng::new(KEYGEN_SEED);
+        let (pk, sk) = generate_signing_keypair(kat.algorithm, &mut rng).unwrap();
+
+        let sig = sign_with_seed(kat.algorithm, &sk, MESSAGE, &SIGNING_SEED).unwrap();
+        assert_eq!(h(&sig), kat.signature, "{:?} signature", kat.algorithm);
+        assert!(verify(kat.algorithm, &pk, MESSAGE, &sig).unwrap());
+    }
+}
@@ -1,7 +1,7 @@
 //! Tests for Dilithium signature algorithm
 
 use synapsed_crypto::{
-    dilithium::{Dilithium2, Dilithium3, Dilithium5},
+    dilithium::{dilithium2, dilithium3, dilithium5, Dilithium2, Dilithium3, Dilithium5},
     traits::Signature,
     random::TestRng,
 };
@@ -12,7 +12,7 @@ fn test_dilithium2_keygen() {
     let (pk, sk) = Dilithium2::generate_keypair(&mut rng).unwrap();
     
     assert_eq!(pk.as_ref().len(), Dilithium2::PUBLIC_KEY_SIZE);
-    assert_eq!(sk.as_ref().len(), Dilithium2::SECRET_KEY_SIZE);
+    assert_eq!(sk.as_slice().len(), Dilithium2::SECRET_KEY_SIZE);
 }
 
 #[test]
@@ -65,7 +65,7 @@ fn test_dilithium3_keygen() {
     let (pk, sk) = Dilithium3::generate_keypair(&mut rng).unwrap();
     
     assert_eq!(pk.as_ref().len(), Dilithium3::PUBLIC_KEY_SIZE);
-    assert_eq!(sk.as_ref().len(), Dilithium3::SECRET_KEY_SIZE);
+    assert_eq!(sk.as_slice().len(), Dilithium3::SECRET_KEY_SIZE);
 }
 
 #[test]
@@ -91,7 +91,7 @@ fn test_dilithium5_keygen() {
     let (pk, sk) = Dilithium5::generate_keypair(&mut rng).unwrap();
     
     assert_eq!(pk.as_ref().len(), Dilithium5::PUBLIC_KEY_SIZE);
-    assert_eq!(sk.as_ref().len(), Dilithium5::SECRET_KEY_SIZE);
+    assert_eq!(sk.as_slice().len(), Dilithium5::SECRET_KEY_SIZE);
 }
 
 #[test]
@@ -128,6 +128,43 @@ fn test_dilithium_cross_verification_fails() {
     // Note: In a real implementation, this would likely fail due to size mismatches
 }
 
+#[test]
+fn test_signing_context_matches_stateless_sign() {
+    let mut rng = TestRng::new(42);
+    let (_, sk2) = Dilithium2::generate_keypair(&mut rng).unwrap();
+    let (_, sk3) = Dilithium3::generate_keypair(&mut rng).unwrap();
+    let (_, sk5) = Dilithium5::generate_keypair(&mut rng).unwrap();
+    
+    let ctx2 = dilithium2::SigningContext::new(&sk2).unwrap();
+    let ctx3 = dilithium3::SigningContext::new(&sk3).unwrap();
+    let ctx5 = dilithium5::SigningContext::new(&sk5).unwrap();
+    
+    // Same seed on both sides, so both draw the same signing randomness
+    let mut stateless_rng = TestRng::new(7);
+    let mut context_rng = TestRng::new(7);
+    
+    for i in 0..1000u32 {
+        let message = format!("message {i}");
+        let message = message.as_bytes();
+        
+        let (expected, actual) = match i % 3 {
+            0 => (
+                Dilithium2::sign(&sk2, message, &mut stateless_rng).unwrap(),
+                ctx2.sign(message, &mut context_rng).unwrap(),
+            ),
+            1 => (
+                Dilithium3::sign(&sk3, message, &mut stateless_rng).unwrap(),
+                ctx3.sign(message, &mut context_rng).unwrap(),
+            ),
+            _ => (
+                Dilithium5::sign(&sk5, message, &mut stateless_rng).unwrap(),
+                ctx5.sign(message, &mut context_rng).unwrap(),
+            ),
+        };
+        assert_eq!(expected.as_ref(), actual.as_ref(), "signature {i} differs");
+    }
+}
+
 #[test]
 #[ignore] // Performance test - run with `cargo test -- --ignored`
 fn test_dilithium_performance() {
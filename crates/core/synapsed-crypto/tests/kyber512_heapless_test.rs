@@ -0,0 +1,66 @@
+//! Allocation-free Kyber512 API tests
+//!
+//! The test crate is `no_std` so the heapless path is exercised without the
+//! standard library prelude; buffers live in statics rather than on the heap.
+
+#![no_std]
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use synapsed_crypto::kyber::{
+    kyber512_decapsulate_into, kyber512_encapsulate_into, kyber512_keygen_into, Kyber512,
+};
+use synapsed_crypto::random::TestRng;
+use synapsed_crypto::traits::Kem;
+
+struct Buffers {
+    pk: [u8; Kyber512::PUBLIC_KEY_BYTES],
+    sk: [u8; Kyber512::SECRET_KEY_BYTES],
+    ct: [u8; Kyber512::CIPHERTEXT_BYTES],
+    ss_enc: [u8; Kyber512::SHARED_SECRET_BYTES],
+    ss_dec: [u8; Kyber512::SHARED_SECRET_BYTES],
+}
+
+static mut BUFFERS: Buffers = Buffers {
+    pk: [0; Kyber512::PUBLIC_KEY_BYTES],
+    sk: [0; Kyber512::SECRET_KEY_BYTES],
+    ct: [0; Kyber512::CIPHERTEXT_BYTES],
+    ss_enc: [0; Kyber512::SHARED_SECRET_BYTES],
+    ss_dec: [0; Kyber512::SHARED_SECRET_BYTES],
+};
+static BUFFERS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+fn take_buffers() -> &'static mut Buffers {
+    assert!(!BUFFERS_TAKEN.swap(true, Ordering::SeqCst));
+    // SAFETY: the flag above guarantees this is the only reference handed out
+    unsafe { &mut *core::ptr::addr_of_mut!(BUFFERS) }
+}
+
+#[test]
+fn test_heapless_round_trip_matches_allocating_api() {
+    let bufs = take_buffers();
+
+    let mut rng = TestRng::new(2024);
+    kyber512_keygen_into(&mut bufs.pk, &mut bufs.sk, &mut rng).unwrap();
+    kyber512_encapsulate_into(&bufs.pk, &mut bufs.ct, &mut bufs.ss_enc, &mut rng).unwrap();
+    kyber512_decapsulate_into(&bufs.sk, &bufs.ct, &mut bufs.ss_dec).unwrap();
+
+    // Same RNG stream through the allocating API
+    let mut rng = TestRng::new(2024);
+    let (pk, sk) = Kyber512::generate_keypair(&mut rng).unwrap();
+    let (ct, ss_enc) = Kyber512::encapsulate(&pk, &mut rng).unwrap();
+    let ss_dec = Kyber512::decapsulate(&sk, &ct).unwrap();
+
+    assert_eq!(&bufs.pk[..], pk.as_ref());
+    assert_eq!(&bufs.sk[..], sk.as_slice());
+    assert_eq!(&bufs.ct[..], ct.as_ref());
+    assert_eq!(&bufs.ss_enc[..], ss_enc.as_ref());
+    assert_eq!(&bufs.ss_dec[..], ss_dec.as_ref());
+}
+
+#[test]
+fn test_buffer_sizes_match_kem_constants() {
+    assert_eq!(Kyber512::PUBLIC_KEY_BYTES, <Kyber512 as Kem>::PUBLIC_KEY_SIZE);
+    assert_eq!(Kyber512::SECRET_KEY_BYTES, <Kyber512 as Kem>::SECRET_KEY_SIZE);
+    assert_eq!(Kyber512::CIPHERTEXT_BYTES, <Kyber512 as Kem>::CIPHERTEXT_SIZE);
+    assert_eq!(Kyber512::SHARED_SECRET_BYTES, <Kyber512 as Kem>::SHARED_SECRET_SIZE);
+}
@@ -21,7 +21,7 @@ fn test_kyber_deterministic() {
     
     // Keys should be identical with same RNG seed
     assert_eq!(pk1.as_ref(), pk2.as_ref(), "Public keys should match with same RNG");
-    assert_eq!(sk1.as_ref(), sk2.as_ref(), "Secret keys should match with same RNG");
+    assert_eq!(sk1.as_slice(), sk2.as_slice(), "Secret keys should match with same RNG");
     println!("✓ Deterministic key generation verified");
     
     // Now test encapsulation with fresh deterministic RNG
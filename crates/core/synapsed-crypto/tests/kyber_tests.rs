@@ -126,7 +126,7 @@ fn test_kyber512_deterministic() {
     let (pk2, sk2) = Kyber512::generate_keypair(&mut rng2).expect("Key generation 2 failed");
     
     assert_eq!(pk1.bytes, pk2.bytes, "Public keys don't match");
-    assert_eq!(sk1.bytes, sk2.bytes, "Secret keys don't match");
+    assert_eq!(sk1.as_slice(), sk2.as_slice(), "Secret keys don't match");
 }
 
 #[test]
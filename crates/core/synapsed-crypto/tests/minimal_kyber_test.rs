@@ -27,7 +27,7 @@ fn test_kyber512_fix() {
     
     println!("✓ Keypair generated successfully");
     println!("  PK size: {}", pk.as_ref().len());
-    println!("  SK size: {}", sk.as_ref().len());
+    println!("  SK size: {}", sk.as_slice().len());
     
     // Create fresh RNG for encapsulation
     let mut rng2 = TestRng::new(123);
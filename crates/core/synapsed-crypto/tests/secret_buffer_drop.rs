@@ -0,0 +1,82 @@
+//! Checks that secret key material is wiped before its memory is freed
+//!
+//! Reading memory after it has been freed is undefined behaviour, so instead
+//! this binary installs a global allocator that inspects a watched block at
+//! the moment it is handed back, while it is still valid to read.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use synapsed_crypto::kyber::Kyber512;
+use synapsed_crypto::random::TestRng;
+use synapsed_crypto::secure_memory::SecretBuffer;
+use synapsed_crypto::traits::Kem;
+
+/// Address of the block to inspect on dealloc, 0 when nothing is watched
+static WATCHED: AtomicUsize = AtomicUsize::new(0);
+
+const NOT_FREED: u8 = 0;
+const FREED_ZEROED: u8 = 1;
+const FREED_DIRTY: u8 = 2;
+
+static OUTCOME: AtomicU8 = AtomicU8::new(NOT_FREED);
+
+/// Serializes the tests, which share the watch slot
+static LOCK: Mutex<()> = Mutex::new(());
+
+struct InspectingAllocator;
+
+unsafe impl GlobalAlloc for InspectingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ptr as usize == WATCHED.load(Ordering::SeqCst) {
+            let block = std::slice::from_raw_parts(ptr, layout.size());
+            let outcome = if block.iter().all(|&b| b == 0) {
+                FREED_ZEROED
+            } else {
+                FREED_DIRTY
+            };
+            OUTCOME.store(outcome, Ordering::SeqCst);
+            WATCHED.store(0, Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: InspectingAllocator = InspectingAllocator;
+
+/// Drop `value` and report what its watched block looked like when freed
+fn drop_watched<T>(value: T, block: *const u8) -> u8 {
+    OUTCOME.store(NOT_FREED, Ordering::SeqCst);
+    WATCHED.store(block as usize, Ordering::SeqCst);
+    drop(value);
+    WATCHED.store(0, Ordering::SeqCst);
+    OUTCOME.load(Ordering::SeqCst)
+}
+
+#[test]
+fn test_secret_buffer_wiped_on_drop() {
+    let _guard = LOCK.lock().unwrap();
+
+    let secret = SecretBuffer::new(vec![0xA5; 64]);
+    let block = secret.as_slice().as_ptr();
+
+    assert_eq!(drop_watched(secret, block), FREED_ZEROED);
+}
+
+#[test]
+fn test_secret_key_wiped_on_drop() {
+    let _guard = LOCK.lock().unwrap();
+
+    let mut rng = TestRng::new(7);
+    let (_pk, sk) = Kyber512::generate_keypair(&mut rng).unwrap();
+    assert!(sk.as_slice().iter().any(|&b| b != 0));
+    let block = sk.as_slice().as_ptr();
+
+    assert_eq!(drop_watched(sk, block), FREED_ZEROED);
+}
@@ -92,6 +92,58 @@ fn test_kyber_decapsulation_timing_independence() {
         "Timing difference too large: {timing_diff_percent:.2}%");
 }
 
+/// Test that batch decapsulation takes as long for invalid ciphertexts as for valid ones
+#[test]
+#[ignore = "Timing tests require controlled environment"]
+fn test_kyber_batch_decapsulation_timing_independence() {
+    const BATCH: usize = 16;
+    const ROUNDS: usize = 30;
+    let mut rng = TestRng::new(7);
+    let (pk, sk) = Kyber512::generate_keypair(&mut rng).unwrap();
+    
+    let valid: Vec<Vec<u8>> = (0..BATCH)
+        .map(|_| Kyber512::encapsulate(&pk, &mut rng).unwrap().0.bytes)
+        .collect();
+    let invalid: Vec<Vec<u8>> = valid.iter()
+        .map(|ct| {
+            let mut ct = ct.clone();
+            ct[0] ^= 0xFF;
+            ct
+        })
+        .collect();
+    
+    let time_batch = |batch: &[Vec<u8>]| {
+        let start = Instant::now();
+        black_box(Kyber512::decapsulate_batch(&sk, batch).unwrap());
+        start.elapsed().as_nanos()
+    };
+    time_batch(&valid);
+    time_batch(&invalid);
+    
+    // Interleave the two batches so drift in machine load affects both equally
+    let mut valid_timings = Vec::with_capacity(ROUNDS);
+    let mut invalid_timings = Vec::with_capacity(ROUNDS);
+    for _ in 0..ROUNDS {
+        valid_timings.push(time_batch(&valid));
+        invalid_timings.push(time_batch(&invalid));
+    }
+    
+    let median = |timings: &mut Vec<u128>| {
+        timings.sort_unstable();
+        timings[timings.len() / 2] as f64
+    };
+    let valid_median = median(&mut valid_timings);
+    let invalid_median = median(&mut invalid_timings);
+    let timing_diff_percent = ((valid_median - invalid_median).abs() / valid_median) * 100.0;
+    
+    println!("Valid batch median time: {valid_median:.0}ns");
+    println!("Invalid batch median time: {invalid_median:.0}ns");
+    println!("Timing difference: {timing_diff_percent:.2}%");
+    
+    assert!(timing_diff_percent < 15.0,
+        "Timing difference too large: {timing_diff_percent:.2}%");
+}
+
 /// Test that message bit extraction is constant-time
 #[test]
 fn test_message_extraction_constant_time() {
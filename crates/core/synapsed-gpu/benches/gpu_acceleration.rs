@@ -75,6 +75,47 @@ fn benchmark_sha256_batch(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_shake256_batch(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let accelerator = match rt.block_on(synapsed_gpu::GpuAccelerator::new(Default::default())) {
+        Ok(accelerator) => accelerator,
+        Err(_) => return, // No device to benchmark
+    };
+    
+    let mut group = c.benchmark_group("shake256_batch");
+    
+    for batch_size in [16, 256, 1024, 4096].iter() {
+        let inputs = vec![vec![0u8; 64]; *batch_size];
+        let input_refs: Vec<&[u8]> = inputs.iter().map(Vec::as_slice).collect();
+        let output_lens = vec![32usize; *batch_size];
+        
+        group.throughput(criterion::Throughput::Elements(*batch_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("gpu", batch_size),
+            batch_size,
+            |b, _| {
+                b.to_async(&rt).iter(|| async {
+                    black_box(accelerator.shake256_batch(&input_refs, &output_lens).await.unwrap())
+                });
+            },
+        );
+        
+        group.bench_with_input(
+            BenchmarkId::new("cpu", batch_size),
+            batch_size,
+            |b, _| {
+                b.iter(|| {
+                    for input in &input_refs {
+                        black_box(synapsed_crypto::hash::kdf(input, 32));
+                    }
+                });
+            },
+        );
+    }
+    
+    group.finish();
+}
+
 fn benchmark_aes_encryption(c: &mut Criterion) {
     let rt = Runtime::new().unwrap();
     
@@ -275,6 +316,7 @@ criterion_group!(
     benches,
     benchmark_kyber768_keygen,
     benchmark_sha256_batch,
+    benchmark_shake256_batch,
     benchmark_aes_encryption,
     benchmark_memory_operations,
     benchmark_matrix_multiplication
@@ -3,6 +3,7 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use futures::stream::{self, Stream, StreamExt};
 use tokio::sync::{RwLock, Mutex, mpsc, Semaphore};
 use tokio::time::{timeout, sleep};
 use tracing::{debug, error, info, warn};
@@ -158,6 +159,8 @@ pub struct BatchResult {
 /// Individual operation result within a batch.
 #[derive(Debug, Clone)]
 pub struct OperationResult {
+    /// Position of the operation in the submitted operations.
+    pub index: usize,
     pub operation_id: String,
     pub success: bool,
     pub execution_time: Duration,
@@ -278,6 +281,37 @@ impl BatchProcessor {
         Ok(batch_id)
     }
 
+    /// Process operations in sub-batches, yielding each sub-batch's result
+    /// as soon as it completes.
+    ///
+    /// Operations are split into sub-batches of `default_batch_size`, and up
+    /// to `pipeline_depth` sub-batches execute concurrently. Results are
+    /// yielded in submission order, and each `OperationResult::index` is the
+    /// operation's position in `operations`.
+    pub fn process_stream(
+        &self,
+        operations: Vec<BatchOperation>,
+    ) -> impl Stream<Item = BatchResult> + '_ {
+        let sub_batch_size = self.config.default_batch_size.max(1) as usize;
+        let depth = self.config.pipeline_depth.max(1) as usize;
+
+        let mut operations = operations.into_iter();
+        let mut offset = 0;
+        let sub_batches = std::iter::from_fn(move || {
+            let sub_batch: Vec<BatchOperation> = operations.by_ref().take(sub_batch_size).collect();
+            if sub_batch.is_empty() {
+                return None;
+            }
+            let start = offset;
+            offset += sub_batch.len();
+            Some((start, sub_batch))
+        });
+
+        stream::iter(sub_batches)
+            .map(move |(offset, sub_batch)| self.process_sub_batch(offset, sub_batch))
+            .buffered(depth)
+    }
+
     /// Get batch processing result.
     pub async fn get_result(&self, batch_id: &str) -> Result<Option<BatchResult>> {
         self.scheduler.get_result(batch_id).await
@@ -445,6 +479,59 @@ impl BatchProcessor {
         result.map(|_| ())
     }
 
+    async fn process_sub_batch(&self, offset: usize, operations: Vec<BatchOperation>) -> BatchResult {
+        let batch_id = uuid::Uuid::new_v4().to_string();
+        let start_time = Instant::now();
+
+        debug!("Processing sub-batch {} at offset {} with {} operations", batch_id, offset, operations.len());
+
+        {
+            let mut active_batches = self.active_batches.write().await;
+            active_batches.insert(batch_id.clone(), ActiveBatch {
+                id: batch_id.clone(),
+                operations: operations.clone(),
+                start_time,
+                status: BatchStatus::Preparing,
+                allocated_buffers: Vec::new(),
+            });
+        }
+
+        let result = self.execute_batch(&batch_id, operations.clone()).await;
+        self.update_metrics(&batch_id, &result).await;
+        self.active_batches.write().await.remove(&batch_id);
+
+        let mut batch_result = result.unwrap_or_else(|e| {
+            error!("Sub-batch {} failed: {}", batch_id, e);
+            BatchResult {
+                batch_id: batch_id.clone(),
+                operation_results: operations
+                    .iter()
+                    .enumerate()
+                    .map(|(index, operation)| OperationResult {
+                        index,
+                        operation_id: operation.id.clone(),
+                        success: false,
+                        execution_time: Duration::ZERO,
+                        error_message: Some(e.to_string()),
+                        output_data: None,
+                    })
+                    .collect(),
+                total_execution_time: start_time.elapsed(),
+                queue_time: Duration::ZERO,
+                preparation_time: Duration::ZERO,
+                kernel_execution_time: Duration::ZERO,
+                memory_transfer_time: Duration::ZERO,
+                throughput_ops_per_sec: 0.0,
+                efficiency_score: 0.0,
+            }
+        });
+
+        for operation_result in &mut batch_result.operation_results {
+            operation_result.index += offset;
+        }
+        batch_result
+    }
+
     async fn execute_batch(
         &self,
         batch_id: &str,
@@ -466,7 +553,7 @@ impl BatchProcessor {
         }
 
         // Process each operation
-        for operation in &operations {
+        for (index, operation) in operations.iter().enumerate() {
             let op_start = Instant::now();
             
             match self.execute_operation(operation).await {
@@ -476,6 +563,7 @@ impl BatchProcessor {
                     memory_transfer_time += transfer_time;
                     
                     operation_results.push(OperationResult {
+                        index,
                         operation_id: operation.id.clone(),
                         success: true,
                         execution_time: op_start.elapsed(),
@@ -485,6 +573,7 @@ impl BatchProcessor {
                 }
                 Err(e) => {
                     operation_results.push(OperationResult {
+                        index,
                         operation_id: operation.id.clone(),
                         success: false,
                         execution_time: op_start.elapsed(),
@@ -778,4 +867,33 @@ mod tests {
         assert!(config.enable_memory_pooling);
         assert!(config.enable_coalescing);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_process_stream_yields_indexed_sub_batches() {
+        let mut processor = create_test_batch_processor().await.unwrap();
+        processor.config.default_batch_size = 100;
+
+        let operations: Vec<BatchOperation> = (0..1000)
+            .map(|i| create_test_operation(&format!("op{}", i)))
+            .collect();
+
+        let mut stream = Box::pin(processor.process_stream(operations));
+
+        // The first sub-batch arrives while later ones have not run yet
+        let first = stream.next().await.unwrap();
+        assert_eq!(first.operation_results.len(), 100);
+        assert!(processor.get_metrics().await.total_batches_processed < 10);
+
+        let mut results: Vec<OperationResult> = first.operation_results;
+        while let Some(batch_result) = stream.next().await {
+            assert_eq!(batch_result.operation_results[0].index, results.len());
+            results.extend(batch_result.operation_results);
+        }
+
+        assert_eq!(results.len(), 1000);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.index, i);
+            assert_eq!(result.operation_id, format!("op{}", i));
+        }
+    }
+}
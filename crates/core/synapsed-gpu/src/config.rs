@@ -37,6 +37,12 @@ pub struct DeviceConfig {
     
     /// Health check interval.
     pub health_check_interval: Duration,
+    
+    /// Temperature in Celsius above which a device is throttled.
+    pub thermal_throttle_celsius: f32,
+    
+    /// Temperature in Celsius a throttled device must cool below to recover.
+    pub thermal_recovery_celsius: f32,
 }
 
 /// Memory management configuration.
@@ -174,6 +180,8 @@ impl Default for DeviceConfig {
             selection_strategy: DeviceSelectionStrategy::Fastest,
             enable_health_monitoring: true,
             health_check_interval: Duration::from_secs(30),
+            thermal_throttle_celsius: 85.0,
+            thermal_recovery_celsius: 75.0,
         }
     }
 }
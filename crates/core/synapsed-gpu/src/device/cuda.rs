@@ -2,11 +2,14 @@
 
 #[cfg(feature = "cuda")]
 use cudarc::driver::{CudaDevice as CudaDriverDevice, CudaStream, DriverError};
+#[cfg(feature = "cuda")]
+use cudarc::driver::sys::CUdevice_attribute;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
 use crate::{Device, DeviceInfo, DeviceType, DeviceConfig, Result, GpuError};
+use crate::device::{sensors, DeviceTelemetry};
 
 /// CUDA device implementation.
 #[derive(Debug)]
@@ -19,6 +22,9 @@ pub struct CudaDevice {
     
     info: DeviceInfo,
     
+    /// PCI address used to read sensors, if the driver reports it.
+    pci_address: Option<String>,
+    
     // For when CUDA is not available
     #[cfg(not(feature = "cuda"))]
     _phantom: std::marker::PhantomData<()>,
@@ -43,10 +49,21 @@ impl CudaDevice {
     pub fn new(device: Arc<CudaDriverDevice>, info: DeviceInfo) -> Result<Self> {
         let stream = device.fork_default_stream()?;
         
+        let attribute = |attribute| device.attribute(attribute).ok().map(|value| value as u32);
+        let pci_address = match (
+            attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_PCI_DOMAIN_ID),
+            attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_PCI_BUS_ID),
+            attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_PCI_DEVICE_ID),
+        ) {
+            (Some(domain), Some(bus), Some(slot)) => Some(sensors::pci_address(domain, bus, slot, 0)),
+            _ => None,
+        };
+        
         Ok(Self {
             device,
             stream,
             info,
+            pci_address,
         })
     }
 
@@ -54,6 +71,7 @@ impl CudaDevice {
     pub fn new(_device: (), info: DeviceInfo) -> Result<Self> {
         Ok(Self {
             info,
+            pci_address: None,
             _phantom: std::marker::PhantomData,
         })
     }
@@ -74,6 +92,14 @@ impl CudaDevice {
         }
     }
 
+    /// Read device telemetry.
+    pub async fn telemetry(&self) -> Result<DeviceTelemetry> {
+        match &self.pci_address {
+            Some(pci_address) => sensors::read(pci_address),
+            None => Ok(DeviceTelemetry::default()),
+        }
+    }
+
     /// Synchronize device operations.
     pub async fn synchronize(&self) -> Result<()> {
         #[cfg(feature = "cuda")]
@@ -189,6 +215,7 @@ async fn create_cuda_device(device_id: usize, _config: &DeviceConfig) -> Result<
         supports_unified_memory: true,
         supports_managed_memory: true,
         supports_peer_access: false, // Would check actual capability
+        telemetry: DeviceTelemetry::default(),
     };
     
     let cuda_device = CudaDevice::new(device, info.clone())?;
@@ -378,6 +378,7 @@ mod tests {
             supports_unified_memory: true,
             supports_managed_memory: true,
             supports_peer_access: false,
+            telemetry: Default::default(),
         }
     }
 
@@ -1,6 +1,6 @@
 //! GPU device management and selection.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -11,6 +11,7 @@ use crate::{Result, GpuError, DeviceConfig};
 pub mod cuda;
 pub mod opencl;
 pub mod health;
+mod sensors;
 
 pub use cuda::CudaDevice;
 pub use opencl::OpenClDevice;
@@ -48,6 +49,21 @@ pub struct DeviceInfo {
     pub supports_unified_memory: bool,
     pub supports_managed_memory: bool,
     pub supports_peer_access: bool,
+    #[serde(default)]
+    pub telemetry: DeviceTelemetry,
+}
+
+/// Runtime sensor readings of a GPU device.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceTelemetry {
+    /// Core temperature in Celsius, if the backend reports it.
+    pub temperature_celsius: Option<f32>,
+    
+    /// Compute utilization as a percentage.
+    pub utilization_percent: f32,
+    
+    /// Number of ECC memory errors reported since the device was reset.
+    pub ecc_errors: u64,
 }
 
 /// GPU device abstraction supporting multiple backends.
@@ -75,6 +91,7 @@ enum DeviceBackend {
 pub struct MockDevice {
     info: DeviceInfo,
     should_fail: Arc<RwLock<bool>>,
+    telemetry: Arc<RwLock<DeviceTelemetry>>,
 }
 
 /// Device manager responsible for device discovery and selection.
@@ -83,6 +100,7 @@ pub struct DeviceManager {
     config: DeviceConfig,
     devices: Arc<RwLock<HashMap<String, Device>>>,
     selected_device: Arc<RwLock<Option<Device>>>,
+    throttled: Arc<RwLock<HashSet<String>>>,
 }
 
 impl Device {
@@ -112,6 +130,29 @@ impl Device {
         self.health_monitor.is_healthy().await
     }
 
+    /// Get the mock backend, if this is a mock device.
+    pub fn as_mock(&self) -> Option<&Arc<MockDevice>> {
+        match &self.backend {
+            DeviceBackend::Mock(mock) => Some(mock),
+            
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Read current temperature, utilization and ECC error counts.
+    pub async fn telemetry(&self) -> Result<DeviceTelemetry> {
+        match &self.backend {
+            #[cfg(feature = "cuda")]
+            DeviceBackend::Cuda(cuda) => cuda.telemetry().await,
+            
+            #[cfg(feature = "opencl")]
+            DeviceBackend::OpenCL(opencl) => opencl.telemetry().await,
+            
+            DeviceBackend::Mock(mock) => mock.telemetry().await,
+        }
+    }
+
     /// Get current memory usage.
     pub async fn memory_usage(&self) -> Result<(u64, u64)> {
         match &self.backend {
@@ -184,6 +225,7 @@ impl DeviceManager {
             config,
             devices: Arc::new(RwLock::new(HashMap::new())),
             selected_device: Arc::new(RwLock::new(None)),
+            throttled: Arc::new(RwLock::new(HashSet::new())),
         };
 
         manager.discover_devices().await?;
@@ -248,8 +290,10 @@ impl DeviceManager {
             return Err(GpuError::NoDevicesAvailable);
         }
 
+        let throttled = self.throttled.read().await;
         let candidates: Vec<&Device> = devices
             .values()
+            .filter(|device| !throttled.contains(&device.info().id))
             .filter(|device| self.meets_requirements(device))
             .collect();
 
@@ -289,6 +333,46 @@ impl DeviceManager {
         self.selected_device.read().await.clone()
     }
 
+    /// Poll telemetry from all devices and update thermal throttling state.
+    ///
+    /// A device is throttled once its temperature exceeds the configured
+    /// throttle threshold and stays throttled until it cools below the
+    /// recovery threshold. Throttled devices are skipped by device selection.
+    pub async fn poll_telemetry(&self) -> Result<()> {
+        let mut devices = self.devices.write().await;
+        let mut throttled = self.throttled.write().await;
+
+        for (id, device) in devices.iter_mut() {
+            let telemetry = match device.telemetry().await {
+                Ok(telemetry) => telemetry,
+                Err(e) => {
+                    warn!("Failed to read telemetry from device {}: {}", id, e);
+                    continue;
+                }
+            };
+
+            if let Some(temperature) = telemetry.temperature_celsius {
+                if temperature > self.config.thermal_throttle_celsius {
+                    if throttled.insert(id.clone()) {
+                        warn!("Device {} throttled at {:.1}°C", id, temperature);
+                    }
+                } else if temperature < self.config.thermal_recovery_celsius && throttled.remove(id) {
+                    info!("Device {} recovered at {:.1}°C", id, temperature);
+                }
+            }
+
+            debug!("Device {} telemetry: {:?}", id, telemetry);
+            device.info.telemetry = telemetry;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a device is currently thermally throttled.
+    pub async fn is_throttled(&self, device_id: &str) -> bool {
+        self.throttled.read().await.contains(device_id)
+    }
+
     /// Get the number of available devices.
     pub async fn device_count(&self) -> usize {
         self.devices.read().await.len()
@@ -332,11 +416,13 @@ impl DeviceManager {
             supports_unified_memory: true,
             supports_managed_memory: true,
             supports_peer_access: false,
+            telemetry: DeviceTelemetry::default(),
         };
 
         let mock = Arc::new(MockDevice {
             info: info.clone(),
             should_fail: Arc::new(RwLock::new(false)),
+            telemetry: Arc::new(RwLock::new(DeviceTelemetry::default())),
         });
 
         Ok(Device::new(info, DeviceBackend::Mock(mock)))
@@ -433,6 +519,18 @@ impl MockDevice {
     pub async fn set_should_fail(&self, should_fail: bool) {
         *self.should_fail.write().await = should_fail;
     }
+
+    pub async fn telemetry(&self) -> Result<DeviceTelemetry> {
+        if *self.should_fail.read().await {
+            return Err(GpuError::device("Mock device failure"));
+        }
+        
+        Ok(self.telemetry.read().await.clone())
+    }
+
+    pub async fn set_telemetry(&self, telemetry: DeviceTelemetry) {
+        *self.telemetry.write().await = telemetry;
+    }
 }
 
 #[cfg(test)]
@@ -502,4 +600,36 @@ mod tests {
         // Test reset
         device.reset().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_thermal_throttling_hysteresis() {
+        let config = DeviceConfig::default();
+        let manager = DeviceManager::new(config).await.unwrap();
+        let device = manager.select_best_device().await.unwrap();
+        let Some(mock) = device.as_mock() else {
+            return; // Real devices report their own telemetry
+        };
+        let id = device.info().id.clone();
+
+        let at = |celsius: f32| DeviceTelemetry {
+            temperature_celsius: Some(celsius),
+            ..Default::default()
+        };
+
+        mock.set_telemetry(at(90.0)).await;
+        manager.poll_telemetry().await.unwrap();
+        assert!(manager.is_throttled(&id).await);
+        assert!(matches!(manager.select_best_device().await, Err(GpuError::NoDevicesAvailable)));
+
+        // Between the thresholds the device stays throttled
+        mock.set_telemetry(at(80.0)).await;
+        manager.poll_telemetry().await.unwrap();
+        assert!(manager.is_throttled(&id).await);
+
+        mock.set_telemetry(at(60.0)).await;
+        manager.poll_telemetry().await.unwrap();
+        assert!(!manager.is_throttled(&id).await);
+        assert_eq!(manager.device_info_list().await[0].telemetry.temperature_celsius, Some(60.0));
+        assert!(manager.select_best_device().await.is_ok());
+    }
 }
\ No newline at end of file
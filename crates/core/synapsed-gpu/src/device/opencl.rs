@@ -15,6 +15,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
 use crate::{Device, DeviceInfo, DeviceType, DeviceConfig, Result, GpuError};
+use crate::device::{sensors, DeviceTelemetry};
 
 /// OpenCL device implementation.
 #[derive(Debug)]
@@ -30,6 +31,9 @@ pub struct OpenClDevice {
     
     info: DeviceInfo,
     
+    /// PCI address used to read sensors, if the vendor extensions report it.
+    pci_address: Option<String>,
+    
     // For when OpenCL is not available
     #[cfg(not(feature = "opencl"))]
     _phantom: std::marker::PhantomData<()>,
@@ -64,11 +68,20 @@ impl OpenClDevice {
             0,
         ).map_err(|e| GpuError::opencl(format!("Failed to create command queue: {:?}", e)))?;
         
+        // Neither vendor extension reports the PCI domain, so assume the first
+        let pci_address = match device.pci_bus_id_nv() {
+            Ok(bus) => device.pci_slot_id_nv().ok().map(|slot| sensors::pci_address(0, bus, slot, 0)),
+            Err(_) => device.topology_amd().ok().map(|topology| {
+                sensors::pci_address(0, topology.bus.into(), topology.device.into(), topology.function.into())
+            }),
+        };
+        
         Ok(Self {
             device,
             context,
             queue,
             info,
+            pci_address,
         })
     }
 
@@ -76,6 +89,7 @@ impl OpenClDevice {
     pub fn new(_device: (), info: DeviceInfo) -> Result<Self> {
         Ok(Self {
             info,
+            pci_address: None,
             _phantom: std::marker::PhantomData,
         })
     }
@@ -100,6 +114,14 @@ impl OpenClDevice {
         }
     }
 
+    /// Read device telemetry.
+    pub async fn telemetry(&self) -> Result<DeviceTelemetry> {
+        match &self.pci_address {
+            Some(pci_address) => sensors::read(pci_address),
+            None => Ok(DeviceTelemetry::default()),
+        }
+    }
+
     /// Synchronize device operations.
     pub async fn synchronize(&self) -> Result<()> {
         #[cfg(feature = "opencl")]
@@ -231,6 +253,7 @@ async fn create_opencl_device(device: ClDevice, device_id: usize, _config: &Devi
         supports_unified_memory: false, // OpenCL doesn't have unified memory like CUDA
         supports_managed_memory: false,
         supports_peer_access: false,
+        telemetry: DeviceTelemetry::default(),
     };
     
     let opencl_device = OpenClDevice::new(device, info.clone())?;
@@ -0,0 +1,163 @@
+//! Hardware sensor readings for device telemetry.
+//!
+//! NVIDIA devices are read through NVML when the `nvml` feature is enabled,
+//! other devices through the hwmon and RAS entries their kernel driver
+//! exposes in sysfs.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::device::DeviceTelemetry;
+use crate::Result;
+
+/// Root of the PCI device tree in sysfs.
+const SYSFS_PCI_DEVICES: &str = "/sys/bus/pci/devices";
+
+/// Format a PCI address as `domain:bus:device.function`.
+#[cfg_attr(not(any(feature = "cuda", feature = "opencl")), allow(dead_code))]
+pub(crate) fn pci_address(domain: u32, bus: u32, device: u32, function: u32) -> String {
+    format!("{:04x}:{:02x}:{:02x}.{:x}", domain, bus, device, function)
+}
+
+/// Read telemetry of the device at the given PCI address.
+pub(crate) fn read(pci_address: &str) -> Result<DeviceTelemetry> {
+    #[cfg(feature = "nvml")]
+    if let Some(telemetry) = nvml::read(pci_address)? {
+        return Ok(telemetry);
+    }
+
+    Ok(read_sysfs(&Path::new(SYSFS_PCI_DEVICES).join(pci_address)))
+}
+
+/// Read telemetry from the sysfs directory of a PCI device.
+///
+/// Temperature comes from hwmon `temp1_input`, utilization from
+/// `gpu_busy_percent` and ECC errors from `ras/umc_err_count`, as exposed by
+/// amdgpu. Readings the driver doesn't expose are left at their defaults.
+pub(crate) fn read_sysfs(device_dir: &Path) -> DeviceTelemetry {
+    let temperature_celsius = fs::read_dir(device_dir.join("hwmon"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .find_map(|hwmon| read_value::<i64>(&hwmon.path().join("temp1_input")))
+        .map(|millidegrees| millidegrees as f32 / 1000.0);
+
+    let utilization_percent = read_value::<u32>(&device_dir.join("gpu_busy_percent"))
+        .map_or(0.0, |percent| percent as f32);
+
+    // Formatted as "ue: <uncorrected>\nce: <corrected>"
+    let ecc_errors = fs::read_to_string(device_dir.join("ras/umc_err_count"))
+        .map(|counts| {
+            counts
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .filter_map(|(_, count)| count.trim().parse::<u64>().ok())
+                .sum()
+        })
+        .unwrap_or(0);
+
+    DeviceTelemetry {
+        temperature_celsius,
+        utilization_percent,
+        ecc_errors,
+    }
+}
+
+fn read_value<T: FromStr>(path: &Path) -> Option<T> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(feature = "nvml")]
+mod nvml {
+    use std::sync::OnceLock;
+
+    use nvml_wrapper::enum_wrappers::device::{EccCounter, MemoryError, TemperatureSensor};
+    use nvml_wrapper::error::NvmlError;
+    use nvml_wrapper::Nvml;
+    use tracing::debug;
+
+    use crate::device::DeviceTelemetry;
+    use crate::{GpuError, Result};
+
+    /// Shared NVML handle, `None` if the library could not be loaded.
+    fn handle() -> Option<&'static Nvml> {
+        static NVML: OnceLock<Option<Nvml>> = OnceLock::new();
+
+        NVML.get_or_init(|| match Nvml::init() {
+            Ok(nvml) => Some(nvml),
+            Err(e) => {
+                debug!("NVML not available: {}", e);
+                None
+            }
+        })
+        .as_ref()
+    }
+
+    /// Read telemetry through NVML, `None` if NVML doesn't manage the device.
+    pub(super) fn read(pci_address: &str) -> Result<Option<DeviceTelemetry>> {
+        let Some(nvml) = handle() else {
+            return Ok(None);
+        };
+
+        let device = match nvml.device_by_pci_bus_id(pci_address) {
+            Ok(device) => device,
+            Err(NvmlError::NotFound) => return Ok(None),
+            Err(e) => return Err(GpuError::device(format!("NVML device lookup failed: {}", e))),
+        };
+
+        let temperature_celsius = supported(device.temperature(TemperatureSensor::Gpu))?
+            .map(|celsius| celsius as f32);
+        let utilization_percent = supported(device.utilization_rates())?
+            .map_or(0.0, |utilization| utilization.gpu as f32);
+
+        let mut ecc_errors = 0;
+        for error_type in [MemoryError::Corrected, MemoryError::Uncorrected] {
+            ecc_errors += supported(device.total_ecc_errors(error_type, EccCounter::Volatile))?
+                .unwrap_or(0);
+        }
+
+        Ok(Some(DeviceTelemetry {
+            temperature_celsius,
+            utilization_percent,
+            ecc_errors,
+        }))
+    }
+
+    /// Treat readings the device doesn't support as absent.
+    fn supported<T>(reading: std::result::Result<T, NvmlError>) -> Result<Option<T>> {
+        match reading {
+            Ok(value) => Ok(Some(value)),
+            Err(NvmlError::NotSupported) => Ok(None),
+            Err(e) => Err(GpuError::device(format!("NVML reading failed: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pci_address_format() {
+        assert_eq!(pci_address(0, 0x3b, 0, 0), "0000:3b:00.0");
+    }
+
+    #[test]
+    fn test_read_sysfs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("hwmon/hwmon3")).unwrap();
+        fs::create_dir_all(dir.path().join("ras")).unwrap();
+        fs::write(dir.path().join("hwmon/hwmon3/temp1_input"), "71500\n").unwrap();
+        fs::write(dir.path().join("gpu_busy_percent"), "42\n").unwrap();
+        fs::write(dir.path().join("ras/umc_err_count"), "ue: 1\nce: 4\n").unwrap();
+
+        let telemetry = read_sysfs(dir.path());
+        assert_eq!(telemetry.temperature_celsius, Some(71.5));
+        assert_eq!(telemetry.utilization_percent, 42.0);
+        assert_eq!(telemetry.ecc_errors, 5);
+
+        let missing = read_sysfs(&dir.path().join("missing"));
+        assert_eq!(missing, DeviceTelemetry::default());
+    }
+}
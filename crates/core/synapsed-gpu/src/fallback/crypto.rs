@@ -52,6 +52,30 @@ impl CryptoFallback {
         Ok(output)
     }
 
+    /// Batch SHAKE256 on CPU, one output of the requested length per input.
+    pub async fn batch_shake256(
+        &self,
+        inputs: &[&[u8]],
+        output_lens: &[usize],
+    ) -> Result<Vec<Vec<u8>>> {
+        debug!("Starting SHAKE256 CPU batch for {} inputs", inputs.len());
+
+        if inputs.len() != output_lens.len() {
+            return Err(GpuError::FallbackError {
+                message: format!("Got {} inputs but {} output lengths", inputs.len(), output_lens.len()),
+            });
+        }
+
+        let outputs = inputs
+            .par_iter()
+            .zip(output_lens.par_iter())
+            .map(|(input, &len)| synapsed_crypto::hash::kdf(input, len))
+            .collect();
+
+        info!("Completed SHAKE256 CPU batch");
+        Ok(outputs)
+    }
+
     /// Batch encryption on CPU.
     pub async fn batch_encrypt(
         &self,
@@ -46,6 +46,9 @@ pub enum FallbackReason {
     /// GPU operation timeout.
     Timeout,
     
+    /// GPU device temperature exceeded the throttling threshold.
+    ThermalThrottle,
+    
     /// Performance is better on CPU for small workloads.
     BetterCpuPerformance,
     
@@ -159,7 +162,7 @@ impl FallbackProcessor {
         // Use CPU for very small workloads
         match operation_type {
             "kyber768_keygen" | "kyber768_encaps" | "kyber768_decaps" => workload_size < 16,
-            "sha256" | "sha3" => workload_size < 1024,
+            "sha256" | "sha3" | "shake256" => workload_size < 1024,
             "aes_encrypt" | "aes_decrypt" => workload_size < 256,
             _ => workload_size < 64,
         }
@@ -334,6 +337,47 @@ impl FallbackProcessor {
         }
     }
 
+    /// Perform batch SHAKE256 fallback.
+    pub async fn shake256_fallback(
+        &self,
+        inputs: &[&[u8]],
+        output_lens: &[usize],
+        reason: FallbackReason,
+    ) -> Result<FallbackResult<Vec<Vec<u8>>>> {
+        let operation_id = uuid::Uuid::new_v4().to_string();
+        let start_time = Instant::now();
+
+        debug!("Starting SHAKE256 fallback (reason: {:?})", reason);
+
+        self.track_fallback_operation(&operation_id, "shake256", reason).await;
+
+        let result = self.crypto_fallback.batch_shake256(inputs, output_lens).await;
+
+        let execution_time = start_time.elapsed();
+        self.complete_fallback_operation(&operation_id, result.is_ok()).await;
+
+        match result {
+            Ok(outputs) => {
+                let performance_score = self.calculate_performance_score(
+                    "shake256",
+                    execution_time,
+                    inputs.len() as u64,
+                ).await;
+
+                Ok(FallbackResult {
+                    data: outputs,
+                    execution_time,
+                    reason,
+                    performance_score,
+                })
+            }
+            Err(e) => {
+                error!("SHAKE256 fallback failed: {}", e);
+                Err(GpuError::FallbackError { message: e.to_string() })
+            }
+        }
+    }
+
     /// Perform symmetric encryption fallback.
     pub async fn encrypt_fallback(
         &self,
@@ -570,6 +614,7 @@ mod tests {
             FallbackReason::OutOfMemory,
             FallbackReason::KernelCompilationFailed,
             FallbackReason::Timeout,
+            FallbackReason::ThermalThrottle,
             FallbackReason::BetterCpuPerformance,
             FallbackReason::UserRequested,
             FallbackReason::Testing,
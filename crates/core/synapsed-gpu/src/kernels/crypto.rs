@@ -5,8 +5,13 @@ use std::collections::HashMap;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+use crate::device::DeviceContext;
+use crate::memory::MemoryManager;
 use crate::{Device, KernelSource, Result};
 
+use super::keccak::{self, ShakeBatch};
+use super::{KernelArg, KernelManager, KernelParams, ScalarValue};
+
 /// General cryptographic GPU kernel implementations.
 #[derive(Debug)]
 pub struct CryptoKernels {
@@ -24,6 +29,8 @@ impl CryptoKernels {
         // Add cryptographic kernel sources
         kernel_sources.insert("sha256_batch".to_string(), Self::sha256_kernel_source());
         kernel_sources.insert("sha3_batch".to_string(), Self::sha3_kernel_source());
+        kernel_sources.insert("keccak_f1600_batch".to_string(), keccak::keccak_f1600_kernel_source());
+        kernel_sources.insert("shake256_batch".to_string(), keccak::shake256_kernel_source());
         kernel_sources.insert("aes_encrypt_batch".to_string(), Self::aes_encrypt_kernel_source());
         kernel_sources.insert("aes_decrypt_batch".to_string(), Self::aes_decrypt_kernel_source());
         kernel_sources.insert("blake2b_batch".to_string(), Self::blake2b_kernel_source());
@@ -43,6 +50,77 @@ impl CryptoKernels {
         self.kernel_sources.read().await.clone()
     }
 
+    /// Batch SHAKE256, returning the packed output buffer.
+    ///
+    /// The mock device has no kernel runtime, so it runs the host port of the
+    /// kernel instead.
+    pub async fn shake256_batch(
+        &self,
+        kernel_manager: &KernelManager,
+        memory_manager: &MemoryManager,
+        batch: &ShakeBatch,
+    ) -> Result<Vec<u8>> {
+        debug!("Starting SHAKE256 batch for {} inputs", batch.len());
+
+        if batch.is_empty() {
+            return Ok(Vec::new());
+        }
+        if matches!(self.device.context(), DeviceContext::Mock) {
+            return Ok(batch.run_on_host());
+        }
+
+        if kernel_manager.kernel_info("shake256_batch").await.is_none() {
+            kernel_manager
+                .compile_kernel("shake256_batch", &keccak::shake256_kernel_source())
+                .await?;
+        }
+
+        let inputs: [(&str, &[u8]); 5] = [
+            ("inputs", &batch.inputs),
+            ("input_offsets", bytemuck::cast_slice(&batch.input_offsets)),
+            ("input_lengths", bytemuck::cast_slice(&batch.input_lengths)),
+            ("output_offsets", bytemuck::cast_slice(&batch.output_offsets)),
+            ("output_lengths", bytemuck::cast_slice(&batch.output_lengths)),
+        ];
+        let mut buffers = HashMap::new();
+        for (name, data) in inputs {
+            let buffer = memory_manager.allocate(data.len().max(1) as u64).await?;
+            memory_manager.transfer_to_device(data, &buffer).await?;
+            buffers.insert(name.to_string(), buffer);
+        }
+        let output_buffer = memory_manager.allocate(batch.output_size.max(1) as u64).await?;
+        buffers.insert("outputs".to_string(), output_buffer.clone());
+
+        let kernel_params = KernelParams {
+            global_work_size: (batch.len() as u32, 1, 1),
+            local_work_size: Some((64, 1, 1)),
+            args: vec![
+                KernelArg::Buffer("inputs".to_string()),
+                KernelArg::Buffer("input_offsets".to_string()),
+                KernelArg::Buffer("input_lengths".to_string()),
+                KernelArg::Buffer("outputs".to_string()),
+                KernelArg::Buffer("output_offsets".to_string()),
+                KernelArg::Buffer("output_lengths".to_string()),
+                KernelArg::Scalar(ScalarValue::U32(batch.len() as u32)),
+            ],
+            shared_memory_bytes: 0,
+        };
+        let execution = kernel_manager.execute_kernel("shake256_batch", kernel_params, &buffers).await;
+
+        let mut outputs = vec![0u8; batch.output_size];
+        let transfer = match execution {
+            Ok(_) => memory_manager.transfer_to_host(&output_buffer, &mut outputs).await,
+            Err(e) => Err(e),
+        };
+        drop(output_buffer);
+        for buffer in buffers.into_values() {
+            memory_manager.free(buffer).await?;
+        }
+        transfer?;
+
+        Ok(outputs)
+    }
+
     // Kernel source implementations
 
     fn sha256_kernel_source() -> KernelSource {
@@ -906,6 +984,8 @@ mod tests {
         assert!(sources.contains_key("sha256_batch"));
         assert!(sources.contains_key("aes_encrypt_batch"));
         assert!(sources.contains_key("ed25519_sign_batch"));
+        assert!(sources.contains_key("keccak_f1600_batch"));
+        assert!(sources.contains_key("shake256_batch"));
     }
 
     #[tokio::test]
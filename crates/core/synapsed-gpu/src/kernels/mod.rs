@@ -8,11 +8,13 @@ use tracing::{debug, error, info, warn};
 use crate::{Device, DeviceContext, GpuBuffer, Result, GpuError};
 
 pub mod crypto;
+pub mod keccak;
 pub mod kyber;
 pub mod common;
 pub mod compiler;
 
 pub use crypto::CryptoKernels;
+pub use keccak::ShakeBatch;
 pub use kyber::KyberKernels;
 pub use common::CommonKernels;
 pub use compiler::{KernelCompiler, KernelSource};
@@ -37,8 +37,8 @@ pub mod fallback;
 pub mod error;
 pub mod config;
 
-pub use device::{Device, DeviceManager, DeviceType, DeviceInfo};
-pub use kernels::{KernelManager, CryptoKernels};
+pub use device::{Device, DeviceManager, DeviceType, DeviceInfo, DeviceTelemetry};
+pub use kernels::{KernelManager, CryptoKernels, ShakeBatch};
 pub use memory::{MemoryManager, GpuBuffer, MemoryPool};
 pub use batch::{BatchProcessor, BatchOperation, BatchResult};
 pub use fallback::{FallbackProcessor, FallbackReason};
@@ -64,6 +64,8 @@ struct AcceleratorState {
     performance_metrics: PerformanceMetrics,
     error_count: u64,
     fallback_count: u64,
+    fallback_reason: Option<FallbackReason>,
+    recovery_failing: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -114,6 +116,8 @@ impl GpuAccelerator {
             performance_metrics: PerformanceMetrics::default(),
             error_count: 0,
             fallback_count: 0,
+            fallback_reason: None,
+            recovery_failing: false,
         }));
 
         Ok(Self {
@@ -155,21 +159,149 @@ impl GpuAccelerator {
         let mut state = self.state.write().await;
         state.active_device = None;
         state.fallback_count += 1;
+        state.fallback_reason = Some(reason);
+    }
+
+    /// Get the reason for the current CPU fallback, if any.
+    pub async fn fallback_reason(&self) -> Option<FallbackReason> {
+        self.state.read().await.fallback_reason
+    }
+
+    /// Poll device telemetry and apply thermal throttling.
+    ///
+    /// Falls back to the CPU while the active device is throttled and
+    /// recovers the GPU once a device has cooled down.
+    pub async fn check_device_health(&self) -> Result<()> {
+        self.device_manager.poll_telemetry().await?;
+
+        let (active_device, fallback_reason) = {
+            let state = self.state.read().await;
+            (
+                state.active_device.as_ref().map(|device| device.info().id.clone()),
+                state.fallback_reason,
+            )
+        };
+
+        match active_device {
+            Some(id) if self.device_manager.is_throttled(&id).await => {
+                self.force_fallback(FallbackReason::ThermalThrottle).await;
+            }
+            None if fallback_reason == Some(FallbackReason::ThermalThrottle) => {
+                self.recover_gpu().await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Start polling device health every health check interval.
+    ///
+    /// Does nothing if health monitoring is disabled. The task stops once
+    /// the accelerator is dropped.
+    pub fn start_health_monitoring(self: &Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        if !self.config.device.enable_health_monitoring {
+            return None;
+        }
+
+        let accelerator = Arc::downgrade(self);
+        let interval = self.config.device.health_check_interval;
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Some(accelerator) = accelerator.upgrade() else {
+                    break;
+                };
+                if let Err(e) = accelerator.check_device_health().await {
+                    warn!("Device health check failed: {}", e);
+                }
+            }
+        }))
+    }
+
+    /// Compute SHAKE256 over a batch of inputs, one output of the requested
+    /// length per input.
+    ///
+    /// Runs the `shake256_batch` kernel on the active device. Small batches,
+    /// a missing device and recoverable device errors fall back to the CPU.
+    pub async fn shake256_batch(
+        &self,
+        inputs: &[&[u8]],
+        output_lens: &[usize],
+    ) -> Result<Vec<Vec<u8>>> {
+        let start_time = std::time::Instant::now();
+        let batch = ShakeBatch::pack(inputs, output_lens)?;
+
+        let fallback_reason = if !self.is_gpu_available().await {
+            Some(FallbackReason::NoGpuAvailable)
+        } else if self.fallback_processor.should_use_fallback("shake256", batch.len() as u64).await {
+            Some(FallbackReason::BetterCpuPerformance)
+        } else {
+            None
+        };
+
+        let outputs = match fallback_reason {
+            Some(reason) => self.shake256_on_cpu(inputs, output_lens, reason).await?,
+            None => {
+                let gpu_result = self.kernel_manager
+                    .crypto_kernels()
+                    .shake256_batch(&self.kernel_manager, &self.memory_manager, &batch)
+                    .await;
+                match gpu_result {
+                    Ok(packed) => batch.unpack(&packed),
+                    Err(e) if e.should_fallback() => {
+                        warn!("SHAKE256 batch failed on GPU, using CPU: {}", e);
+                        self.state.write().await.error_count += 1;
+                        self.shake256_on_cpu(inputs, output_lens, FallbackReason::GpuError).await?
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+
+        let mut state = self.state.write().await;
+        state.performance_metrics.operations_completed += batch.len() as u64;
+        state.performance_metrics.total_execution_time_ms += start_time.elapsed().as_millis() as u64;
+
+        Ok(outputs)
+    }
+
+    async fn shake256_on_cpu(
+        &self,
+        inputs: &[&[u8]],
+        output_lens: &[usize],
+        reason: FallbackReason,
+    ) -> Result<Vec<Vec<u8>>> {
+        debug!("Running SHAKE256 batch on CPU (reason: {:?})", reason);
+        self.state.write().await.fallback_count += 1;
+        let result = self.fallback_processor.shake256_fallback(inputs, output_lens, reason).await?;
+        Ok(result.data)
     }
 
     /// Attempt to recover GPU processing after fallback.
+    ///
+    /// Failures are logged once until a recovery succeeds, so this can be
+    /// retried on every health check.
     pub async fn recover_gpu(&self) -> Result<bool> {
-        info!("Attempting GPU recovery");
+        debug!("Attempting GPU recovery");
         
         match self.device_manager.select_best_device().await {
             Ok(device) => {
                 let mut state = self.state.write().await;
                 state.active_device = Some(device);
+                state.fallback_reason = None;
+                state.recovery_failing = false;
                 info!("GPU recovery successful");
                 Ok(true)
             }
             Err(e) => {
-                error!("GPU recovery failed: {}", e);
+                let mut state = self.state.write().await;
+                if !std::mem::replace(&mut state.recovery_failing, true) {
+                    error!("GPU recovery failed: {}", e);
+                } else {
+                    debug!("GPU recovery still failing: {}", e);
+                }
                 Ok(false)
             }
         }
@@ -239,4 +371,35 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    async fn test_thermal_throttle_fallback_and_recovery() {
+        let Ok(accelerator) = GpuAccelerator::new(AcceleratorConfig::default()).await else {
+            return;
+        };
+        let Some(device) = accelerator.device_manager.selected_device().await else {
+            return;
+        };
+        let Some(mock) = device.as_mock() else {
+            return; // Real devices report their own telemetry
+        };
+        let at = |celsius: f32| DeviceTelemetry {
+            temperature_celsius: Some(celsius),
+            ..Default::default()
+        };
+
+        mock.set_telemetry(at(95.0)).await;
+        accelerator.check_device_health().await.unwrap();
+        assert!(!accelerator.is_gpu_available().await);
+        assert_eq!(accelerator.fallback_reason().await, Some(FallbackReason::ThermalThrottle));
+
+        mock.set_telemetry(at(80.0)).await;
+        accelerator.check_device_health().await.unwrap();
+        assert!(!accelerator.is_gpu_available().await);
+
+        mock.set_telemetry(at(60.0)).await;
+        accelerator.check_device_health().await.unwrap();
+        assert!(accelerator.is_gpu_available().await);
+        assert_eq!(accelerator.fallback_reason().await, None);
+    }
+}
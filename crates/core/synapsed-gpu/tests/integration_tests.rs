@@ -362,6 +362,35 @@ async fn test_crypto_api_integration() {
     assert_eq!(latency_config.batch.batch_timeout_ms, 10);
 }
 
+/// Test batch SHAKE256 against the synapsed-crypto reference.
+#[test]
+async fn test_shake256_batch_matches_reference() {
+    let accelerator = match GpuAccelerator::new(AcceleratorConfig::default()).await {
+        Ok(accelerator) => accelerator,
+        Err(GpuError::NoDevicesAvailable) => return,
+        Err(e) => panic!("Unexpected error during initialization: {}", e),
+    };
+
+    // Large enough to run on the device rather than the small-batch fallback,
+    // with lengths crossing the 136-byte rate
+    let inputs: Vec<Vec<u8>> = (0..2048)
+        .map(|i| (0..(i % 300)).map(|j| (i + j) as u8).collect())
+        .collect();
+    let input_refs: Vec<&[u8]> = inputs.iter().map(Vec::as_slice).collect();
+    let output_lens: Vec<usize> = (0..2048).map(|i| 1 + i % 400).collect();
+
+    let outputs = accelerator.shake256_batch(&input_refs, &output_lens).await.unwrap();
+
+    assert_eq!(outputs.len(), inputs.len());
+    for ((input, &len), output) in inputs.iter().zip(&output_lens).zip(&outputs) {
+        assert_eq!(output, &synapsed_crypto::hash::kdf(input, len));
+    }
+
+    // Small batches take the CPU path and must agree as well
+    let small = accelerator.shake256_batch(&input_refs[..4], &output_lens[..4]).await.unwrap();
+    assert_eq!(small, outputs[..4]);
+}
+
 // Helper functions
 
 fn create_test_batch_operation(id: &str) -> BatchOperation {
@@ -9,7 +9,7 @@ use crate::{
     Result, IntentError,
 };
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use regex::Regex;
@@ -23,6 +23,7 @@ use tokio::sync::RwLock;
 pub struct AgentMarkdownParser {
     tool_aliases: HashMap<String, Vec<String>>,
     capability_patterns: Vec<CapabilityPattern>,
+    known_capabilities: HashSet<String>,
     // Substrates observability
     parse_events: Arc<BasicSource<ParseEvent>>,
     parse_metrics: Arc<RwLock<Vec<ParseMetric>>>,
@@ -57,6 +58,40 @@ pub struct Example {
     pub explanation: Option<String>,
 }
 
+/// Problem found while validating an agent definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    pub kind: DiagnosticKind,
+    /// 1-based line the problem was found on
+    pub line: usize,
+    pub message: String,
+}
+
+/// Severity of a validation diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// Kind of problem reported by a validation diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    MissingSection,
+    UnknownCapability,
+    MalformedExample,
+    ConflictingPermission,
+}
+
+/// Example heading being validated
+struct ExampleScan {
+    line: usize,
+    title: String,
+    has_input: bool,
+    has_output: bool,
+}
+
 /// Parse event for observability
 #[derive(Debug, Clone)]
 pub struct ParseEvent {
@@ -97,6 +132,7 @@ impl AgentMarkdownParser {
         Self {
             tool_aliases: Self::create_tool_aliases(),
             capability_patterns: Self::create_capability_patterns(),
+            known_capabilities: Self::create_known_capabilities(),
             parse_events,
             parse_metrics: Arc::new(RwLock::new(Vec::new())),
         }
@@ -246,6 +282,199 @@ impl AgentMarkdownParser {
         Ok(parsed)
     }
 
+    /// Validate markdown content, collecting every problem found
+    ///
+    /// Reports missing required sections, unknown capabilities, examples
+    /// without input or output blocks, and constraints forbidding a tool
+    /// the definition also declares.
+    pub fn validate(&self, content: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut title_line = None;
+        let mut has_description = false;
+        let mut section = String::new();
+        let mut tools: Vec<(String, usize)> = Vec::new();
+        let mut constraints: Vec<(String, usize)> = Vec::new();
+        let mut example: Option<ExampleScan> = None;
+        let mut open_block: Option<(usize, String)> = None;
+
+        for (index, raw) in content.lines().enumerate() {
+            let number = index + 1;
+            let line = raw.trim();
+
+            if section == "examples" || section == "usage" {
+                if let Some((_, block_type)) = &open_block {
+                    if line.starts_with("```") {
+                        if let Some(ex) = example.as_mut() {
+                            match block_type.as_str() {
+                                "input" => ex.has_input = true,
+                                "output" => ex.has_output = true,
+                                _ => {}
+                            }
+                        }
+                        open_block = None;
+                    }
+                    continue;
+                }
+                if let Some(block_type) = line.strip_prefix("```") {
+                    let block_type = block_type.trim().to_string();
+                    if block_type != "input" && block_type != "output" {
+                        diagnostics.push(Diagnostic {
+                            severity: DiagnosticSeverity::Warning,
+                            kind: DiagnosticKind::MalformedExample,
+                            line: number,
+                            message: format!(
+                                "Example code block has type '{}', expected 'input' or 'output'",
+                                block_type
+                            ),
+                        });
+                    } else if example.is_none() {
+                        diagnostics.push(Diagnostic {
+                            severity: DiagnosticSeverity::Error,
+                            kind: DiagnosticKind::MalformedExample,
+                            line: number,
+                            message: "Example code block outside of a '### ' example heading".to_string(),
+                        });
+                    }
+                    open_block = Some((number, block_type));
+                    continue;
+                }
+                if let Some(title) = line.strip_prefix("### ") {
+                    Self::finish_example(example.take(), &mut diagnostics);
+                    example = Some(ExampleScan {
+                        line: number,
+                        title: title.trim().to_string(),
+                        has_input: false,
+                        has_output: false,
+                    });
+                    continue;
+                }
+            }
+
+            if let Some(title) = line.strip_prefix("# ") {
+                if title_line.is_none() && !title.trim().is_empty() {
+                    title_line = Some(number);
+                }
+                continue;
+            }
+            if let Some(name) = line.strip_prefix("## ") {
+                Self::finish_example(example.take(), &mut diagnostics);
+                section = name.trim().to_lowercase();
+                if section == "description" || section == "overview" {
+                    has_description = true;
+                }
+                continue;
+            }
+
+            let item = line.strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| {
+                    line.split_once(". ")
+                        .filter(|(n, _)| n.parse::<i32>().is_ok())
+                        .map(|(_, item)| item)
+                })
+                .map(str::trim);
+            let Some(item) = item else {
+                continue;
+            };
+
+            match section.as_str() {
+                "tools" => {
+                    let expanded = self.expand_tool_aliases(&[item.to_string()]);
+                    tools.extend(expanded.into_iter().map(|tool| (tool, number)));
+                },
+                "capabilities" | "abilities" if !self.known_capabilities.contains(item) => {
+                    diagnostics.push(Diagnostic {
+                        severity: DiagnosticSeverity::Warning,
+                        kind: DiagnosticKind::UnknownCapability,
+                        line: number,
+                        message: format!("Unknown capability '{}'", item),
+                    });
+                },
+                "constraints" | "restrictions" | "limitations" => {
+                    constraints.push((item.to_string(), number));
+                },
+                _ => {}
+            }
+        }
+
+        if let Some((line, _)) = open_block {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                kind: DiagnosticKind::MalformedExample,
+                line,
+                message: "Unterminated example code block".to_string(),
+            });
+        } else {
+            Self::finish_example(example, &mut diagnostics);
+        }
+
+        let heading = title_line.unwrap_or(1);
+        if title_line.is_none() {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                kind: DiagnosticKind::MissingSection,
+                line: heading,
+                message: "Missing '# <name>' title heading".to_string(),
+            });
+        }
+        if !has_description {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                kind: DiagnosticKind::MissingSection,
+                line: heading,
+                message: "Missing required '## Description' section".to_string(),
+            });
+        }
+
+        let negation = Regex::new(r"(?i)\b(never|not|no|don't|cannot|can't|forbidden|prohibited)\b").unwrap();
+        for (constraint, line) in &constraints {
+            if !negation.is_match(constraint) {
+                continue;
+            }
+            for (tool, declared_at) in &tools {
+                let mentions = Regex::new(&format!(r"\b{}\b", regex::escape(tool))).unwrap();
+                if mentions.is_match(constraint) {
+                    diagnostics.push(Diagnostic {
+                        severity: DiagnosticSeverity::Error,
+                        kind: DiagnosticKind::ConflictingPermission,
+                        line: *line,
+                        message: format!(
+                            "Constraint forbids tool '{}' declared on line {}",
+                            tool, declared_at
+                        ),
+                    });
+                }
+            }
+        }
+
+        diagnostics.sort_by_key(|d| d.line);
+        diagnostics
+    }
+
+    /// Report an example missing its input or output block
+    fn finish_example(example: Option<ExampleScan>, diagnostics: &mut Vec<Diagnostic>) {
+        let Some(example) = example else {
+            return;
+        };
+        let missing: Vec<&str> = [("input", example.has_input), ("output", example.has_output)]
+            .into_iter()
+            .filter(|(_, present)| !present)
+            .map(|(block, _)| block)
+            .collect();
+        if !missing.is_empty() {
+            diagnostics.push(Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                kind: DiagnosticKind::MalformedExample,
+                line: example.line,
+                message: format!(
+                    "Example '{}' has no {} block",
+                    example.title,
+                    missing.join(" or ")
+                ),
+            });
+        }
+    }
+
     /// Parse a paragraph of text
     fn parse_paragraph(&self, lines: &[&str], i: &mut usize) -> String {
         let mut paragraph = String::new();
@@ -311,7 +540,8 @@ impl AgentMarkdownParser {
         while *i < lines.len() {
             let line = lines[*i];
             
-            if line.starts_with("#") {
+            // Example headings are "### ", only higher level headings end the section
+            if !in_code_block && (line.starts_with("# ") || line.starts_with("## ")) {
                 break;
             }
             
@@ -484,6 +714,20 @@ impl AgentMarkdownParser {
         aliases
     }
 
+    /// Create the set of capabilities definitions may declare
+    fn create_known_capabilities() -> HashSet<String> {
+        [
+            "analysis", "testing", "debugging", "deployment", "design", "security",
+            "documentation", "optimization", "code_analysis", "code_review",
+            "security_analysis", "security_testing", "full_stack_development",
+            "continuous_integration", "continuous_deployment", "data_processing",
+            "etl", "research", "research_documentation",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+
     /// Create capability inference patterns
     fn create_capability_patterns() -> Vec<CapabilityPattern> {
         vec![
@@ -622,4 +866,99 @@ This agent reads files, executes Python scripts, and searches the web for data.
         assert!(parsed.tools.contains(&"run_command".to_string()));
         assert!(parsed.tools.contains(&"web_search".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_examples() {
+        let parser = AgentMarkdownParser::new();
+
+        let markdown = r#"
+# Formatter
+
+## Examples
+### Format a file
+```input
+fmt main.rs
+```
+```output
+formatted
+```
+"#;
+
+        let parsed = parser.parse_markdown(markdown).unwrap();
+
+        assert_eq!(parsed.examples.len(), 1);
+        assert_eq!(parsed.examples[0].input, "fmt main.rs");
+        assert_eq!(parsed.examples[0].output, "formatted");
+    }
+
+    #[test]
+    fn test_validate_collects_all_diagnostics() {
+        let parser = AgentMarkdownParser::new();
+
+        let markdown = "# Deployer
+
+## Tools
+- run_command
+- git_ops
+
+## Capabilities
+- deployment
+- teleportation
+
+## Constraints
+- Never use run_command in production
+
+## Examples
+### Deploy a release
+```input
+deploy v1.2
+```
+";
+
+        let diagnostics = parser.validate(markdown);
+
+        let missing = diagnostics.iter()
+            .find(|d| d.kind == DiagnosticKind::MissingSection)
+            .unwrap();
+        assert_eq!(missing.severity, DiagnosticSeverity::Error);
+        assert_eq!(missing.line, 1);
+        assert!(missing.message.contains("Description"));
+
+        let example = diagnostics.iter()
+            .find(|d| d.kind == DiagnosticKind::MalformedExample)
+            .unwrap();
+        assert_eq!(example.severity, DiagnosticSeverity::Error);
+        assert_eq!(example.line, 15);
+        assert!(example.message.contains("output"));
+
+        let unknown = diagnostics.iter()
+            .find(|d| d.kind == DiagnosticKind::UnknownCapability)
+            .unwrap();
+        assert_eq!(unknown.line, 9);
+        assert!(unknown.message.contains("teleportation"));
+
+        let conflict = diagnostics.iter()
+            .find(|d| d.kind == DiagnosticKind::ConflictingPermission)
+            .unwrap();
+        assert_eq!(conflict.line, 12);
+        assert!(conflict.message.contains("run_command"));
+
+        assert_eq!(diagnostics.len(), 4);
+    }
+
+    #[test]
+    fn test_validate_clean_definition() {
+        let parser = AgentMarkdownParser::new();
+
+        let markdown = "# Reviewer
+
+## Description
+Reviews code.
+
+## Capabilities
+- code_review
+";
+
+        assert!(parser.validate(markdown).is_empty());
+    }
+}
@@ -8,16 +8,23 @@ use crate::{
     Result,
 };
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use std::sync::Arc;
 use chrono::{DateTime, Utc, Timelike};
+use futures::future::BoxFuture;
+
+/// Async handler invoked with a detected anomaly and the agent's most recent execution pattern
+pub type AnomalyHandler = Arc<dyn Fn(Anomaly, Option<ExecutionPattern>) -> BoxFuture<'static, ()> + Send + Sync>;
 
 /// Agent profiling system that tracks and learns from agent behavior
 pub struct AgentProfilingSystem {
     profiles: Arc<RwLock<HashMap<String, AgentProfile>>>,
     behavior_patterns: Arc<RwLock<HashMap<String, BehaviorPattern>>>,
-    anomaly_detector: AnomalyDetector,
+    anomaly_detector: Arc<RwLock<AnomalyDetector>>,
+    anomaly_handlers: Arc<RwLock<Vec<(AnomalySeverity, AnomalyHandler)>>>,
+    recent_patterns: Arc<RwLock<HashMap<String, String>>>,
 }
 
 /// Comprehensive profile of an agent's behavior
@@ -161,7 +168,39 @@ impl AgentProfilingSystem {
         Self {
             profiles: Arc::new(RwLock::new(HashMap::new())),
             behavior_patterns: Arc::new(RwLock::new(HashMap::new())),
-            anomaly_detector: AnomalyDetector::new(),
+            anomaly_detector: Arc::new(RwLock::new(AnomalyDetector::new())),
+            anomaly_handlers: Arc::new(RwLock::new(Vec::new())),
+            recent_patterns: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Create a profiling system detecting anomalies with custom thresholds
+    pub fn with_thresholds(thresholds: AnomalyThresholds) -> Self {
+        let system = Self::new();
+        Self {
+            anomaly_detector: Arc::new(RwLock::new(AnomalyDetector::with_thresholds(thresholds))),
+            ..system
+        }
+    }
+
+    /// Register a handler invoked for every detected anomaly at or above `min_severity`
+    ///
+    /// Handlers run in registration order once detection has finished, and
+    /// receive the anomaly along with the agent's most recent execution pattern.
+    pub async fn on_anomaly<F, Fut>(&self, min_severity: AnomalySeverity, handler: F)
+    where
+        F: Fn(Anomaly, Option<ExecutionPattern>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler: AnomalyHandler = Arc::new(move |anomaly, pattern| Box::pin(handler(anomaly, pattern)));
+        self.anomaly_handlers.write().await.push((min_severity, handler));
+    }
+
+    /// Establish the normal behavior baseline for an agent from its current profile
+    pub async fn establish_baseline(&self, agent_id: &str) {
+        let profiles = self.profiles.read().await;
+        if let Some(profile) = profiles.get(agent_id) {
+            self.anomaly_detector.write().await.establish_baseline(agent_id, profile);
         }
     }
 
@@ -240,6 +279,7 @@ impl AgentProfilingSystem {
         
         if let Some(profile) = profiles.get_mut(agent_id) {
             let pattern_id = tool_sequence.join("->");
+            self.recent_patterns.write().await.insert(agent_id.to_string(), pattern_id.clone());
             
             // Find or create pattern
             let pattern = profile.execution_patterns.iter_mut()
@@ -279,9 +319,17 @@ impl AgentProfilingSystem {
         resource_usage: &ResourceUsage,
     ) -> Vec<Anomaly> {
         let profiles = self.profiles.read().await;
+        let detector = self.anomaly_detector.read().await;
         let mut anomalies = Vec::new();
+        let mut recent_pattern = None;
         
         if let Some(profile) = profiles.get(agent_id) {
+            if let Some(pattern_id) = self.recent_patterns.read().await.get(agent_id) {
+                recent_pattern = profile.execution_patterns.iter()
+                    .find(|p| &p.pattern_id == pattern_id)
+                    .cloned();
+            }
+
             // Check for unusual tool usage
             for tool in current_tools {
                 if !profile.declared_tools.contains(tool) && !profile.actually_used_tools.contains(tool) {
@@ -296,8 +344,9 @@ impl AgentProfilingSystem {
             }
             
             // Check for resource spikes
-            if let Some(baseline) = self.anomaly_detector.baseline_patterns.get(agent_id) {
-                if resource_usage.avg_cpu_percent > baseline.avg_resource_usage.avg_cpu_percent * 2.0 {
+            if let Some(baseline) = detector.baseline_patterns.get(agent_id) {
+                let spike_factor = detector.thresholds.resource_spike_factor;
+                if resource_usage.avg_cpu_percent > baseline.avg_resource_usage.avg_cpu_percent * spike_factor {
                     anomalies.push(Anomaly {
                         timestamp: Utc::now(),
                         anomaly_type: AnomalyType::ResourceSpike,
@@ -320,10 +369,28 @@ impl AgentProfilingSystem {
                 });
             }
         }
-        
+        drop(detector);
+        drop(profiles);
+
+        self.notify_anomaly_handlers(&anomalies, recent_pattern).await;
         anomalies
     }
 
+    /// Invoke the registered handlers whose severity threshold each anomaly meets
+    async fn notify_anomaly_handlers(&self, anomalies: &[Anomaly], pattern: Option<ExecutionPattern>) {
+        if anomalies.is_empty() {
+            return;
+        }
+        let handlers = self.anomaly_handlers.read().await.clone();
+        for anomaly in anomalies {
+            for (min_severity, handler) in &handlers {
+                if anomaly.severity >= *min_severity {
+                    handler(anomaly.clone(), pattern.clone()).await;
+                }
+            }
+        }
+    }
+
     /// Check if a tool sequence is suspicious
     fn is_suspicious_sequence(&self, sequence: &str) -> bool {
         let suspicious_patterns = vec![
@@ -460,13 +527,18 @@ impl AgentProfilingSystem {
 
 impl AnomalyDetector {
     pub fn new() -> Self {
+        Self::with_thresholds(AnomalyThresholds {
+            tool_usage_deviation: 2.0,
+            resource_spike_factor: 2.0,
+            pattern_confidence: 0.7,
+            time_window_hours: 24,
+        })
+    }
+
+    /// Create a detector with custom thresholds
+    pub fn with_thresholds(thresholds: AnomalyThresholds) -> Self {
         Self {
-            thresholds: AnomalyThresholds {
-                tool_usage_deviation: 2.0,
-                resource_spike_factor: 2.5,
-                pattern_confidence: 0.7,
-                time_window_hours: 24,
-            },
+            thresholds,
             baseline_patterns: HashMap::new(),
         }
     }
@@ -502,4 +574,79 @@ pub struct ToolDivergenceAnalysis {
     pub overlap: HashSet<String>,
     pub divergence_score: f64,
     pub recommendation: String,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_anomaly_handlers_fire_above_threshold() {
+        let system = AgentProfilingSystem::with_thresholds(AnomalyThresholds {
+            tool_usage_deviation: 2.0,
+            resource_spike_factor: 2.0,
+            pattern_confidence: 0.7,
+            time_window_hours: 24,
+        });
+        let agent = SubAgentDefinition {
+            name: "worker".to_string(),
+            description: "Worker agent".to_string(),
+            tools: vec!["read_file".to_string(), "run_command".to_string()],
+            capabilities: vec![],
+            custom_instructions: None,
+            source_file: None,
+        };
+        system.profile_agent(&agent).await.unwrap();
+        system.record_execution_pattern(
+            "worker",
+            vec!["read_file".to_string(), "run_command".to_string()],
+            200,
+            true,
+        ).await;
+        system.establish_baseline("worker").await;
+
+        let all = Arc::new(Mutex::new(Vec::new()));
+        let severe = Arc::new(Mutex::new(Vec::new()));
+        let sink = all.clone();
+        system.on_anomaly(AnomalySeverity::Medium, move |anomaly, pattern| {
+            let sink = sink.clone();
+            async move { sink.lock().await.push((anomaly, pattern)); }
+        }).await;
+        let sink = severe.clone();
+        system.on_anomaly(AnomalySeverity::High, move |anomaly, pattern| {
+            let sink = sink.clone();
+            async move { sink.lock().await.push((anomaly, pattern)); }
+        }).await;
+
+        // Within the baseline: no handler fires
+        let normal = ResourceUsage {
+            avg_memory_mb: 100.0,
+            avg_cpu_percent: 30.0,
+            avg_network_kbps: 100.0,
+            avg_disk_iops: 50.0,
+        };
+        let anomalies = system.detect_anomalies("worker", &["read_file".to_string()], &normal).await;
+        assert!(anomalies.is_empty());
+        assert!(all.lock().await.is_empty());
+
+        // CPU beyond the spike factor plus a suspicious sequence
+        let spike = ResourceUsage { avg_cpu_percent: 80.0, ..normal };
+        let tools = vec!["debug_shell".to_string(), "sudo".to_string()];
+        let anomalies = system.detect_anomalies("worker", &tools, &spike).await;
+        assert!(anomalies.iter().any(|a| a.anomaly_type == AnomalyType::ResourceSpike));
+
+        let all = all.lock().await;
+        assert_eq!(all.len(), anomalies.len());
+        let (spike_anomaly, pattern) = all.iter()
+            .find(|(a, _)| a.anomaly_type == AnomalyType::ResourceSpike)
+            .unwrap();
+        assert_eq!(spike_anomaly.severity, AnomalySeverity::Medium);
+        assert_eq!(pattern.as_ref().unwrap().pattern_id, "read_file->run_command");
+
+        let severe = severe.lock().await;
+        assert_eq!(severe.len(), 1);
+        assert_eq!(severe[0].0.anomaly_type, AnomalyType::SuspiciousSequence);
+        assert_eq!(severe[0].0.severity, AnomalySeverity::High);
+    }
+}
@@ -5,10 +5,15 @@
 
 use crate::{
     dynamic_agents::{SubAgentDefinition, RiskLevel},
+    IntentError, Result,
 };
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use serde::{Deserialize, Serialize};
 
+/// Format version of persisted inference state, bumped on incompatible changes
+pub const INFERENCE_CACHE_VERSION: u32 = 1;
+
 /// Capability inference engine that understands tool relationships
 pub struct CapabilityInferenceEngine {
     tool_graph: ToolRelationshipGraph,
@@ -68,7 +73,7 @@ pub enum ToolCategory {
 }
 
 /// Rule for inferring capabilities
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InferenceRule {
     pub name: String,
     pub condition: RuleCondition,
@@ -77,7 +82,7 @@ pub struct InferenceRule {
 }
 
 /// Condition for applying an inference rule
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RuleCondition {
     HasTools(Vec<String>),
     HasAnyTools(Vec<String>),
@@ -106,6 +111,15 @@ pub struct InferredCapability {
     pub supporting_tools: Vec<String>,
 }
 
+/// Persisted state of a capability inference engine
+#[derive(Debug, Serialize, Deserialize)]
+struct InferenceCache {
+    version: u32,
+    tool_graph: ToolRelationshipGraph,
+    capability_rules: Vec<InferenceRule>,
+    learned_patterns: HashMap<String, LearnedPattern>,
+}
+
 impl CapabilityInferenceEngine {
     pub fn new() -> Self {
         Self {
@@ -317,6 +331,43 @@ impl CapabilityInferenceEngine {
         }
     }
 
+    /// Save the tool graph, rules, and learned patterns to `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let cache = InferenceCache {
+            version: INFERENCE_CACHE_VERSION,
+            tool_graph: self.tool_graph.clone(),
+            capability_rules: self.capability_rules.clone(),
+            learned_patterns: self.learned_patterns.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&cache)
+            .map_err(|e| IntentError::Other(anyhow::anyhow!("Failed to serialize inference cache: {}", e)))?;
+        std::fs::write(path, json)
+            .map_err(|e| IntentError::Other(anyhow::anyhow!("Failed to write inference cache: {}", e)))
+    }
+
+    /// Load state saved with [`save`](Self::save), replacing the tool graph,
+    /// rules, and learned patterns of this engine
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let json = std::fs::read(path)
+            .map_err(|e| IntentError::Other(anyhow::anyhow!("Failed to read inference cache: {}", e)))?;
+        let value: serde_json::Value = serde_json::from_slice(&json)
+            .map_err(|e| IntentError::Other(anyhow::anyhow!("Failed to parse inference cache: {}", e)))?;
+        let version = value.get("version").and_then(|v| v.as_u64());
+        if version != Some(INFERENCE_CACHE_VERSION as u64) {
+            return Err(IntentError::ValidationFailed(format!(
+                "Unsupported inference cache version {:?}, expected {}",
+                version, INFERENCE_CACHE_VERSION
+            )));
+        }
+        let cache: InferenceCache = serde_json::from_value(value)
+            .map_err(|e| IntentError::Other(anyhow::anyhow!("Failed to parse inference cache: {}", e)))?;
+
+        self.tool_graph = cache.tool_graph;
+        self.capability_rules = cache.capability_rules;
+        self.learned_patterns = cache.learned_patterns;
+        Ok(())
+    }
+
     /// Get tool recommendations for a desired capability
     pub fn recommend_tools_for_capability(&self, capability: &str) -> Vec<(String, f64)> {
         let mut recommendations = Vec::new();
@@ -561,4 +612,93 @@ mod tests {
         assert_eq!(pattern.success_rate, 1.0);
         assert!(pattern.observed_capabilities.contains(&"code_review".to_string()));
     }
+
+    fn summarize(inferred: Vec<InferredCapability>) -> Vec<(String, f64, Vec<String>)> {
+        let mut summary: Vec<_> = inferred
+            .into_iter()
+            .map(|c| {
+                let mut reasoning = c.reasoning;
+                reasoning.sort();
+                (c.capability, c.confidence, reasoning)
+            })
+            .collect();
+        summary.sort_by(|a, b| a.0.cmp(&b.0));
+        summary
+    }
+
+    #[test]
+    fn test_inferences_survive_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inference.json");
+
+        let mut engine = CapabilityInferenceEngine::new();
+        engine.learn_pattern(
+            vec!["read_file".to_string(), "ast_parser".to_string()],
+            vec!["code_review".to_string()],
+            true,
+            1500,
+        );
+        engine.learn_pattern(
+            vec!["web_search".to_string()],
+            vec!["fact_checking".to_string()],
+            false,
+            300,
+        );
+        engine.learn_pattern(
+            vec!["web_search".to_string()],
+            vec!["fact_checking".to_string()],
+            true,
+            500,
+        );
+
+        let agent = SubAgentDefinition {
+            name: "reviewer".to_string(),
+            description: "Reviews code".to_string(),
+            tools: vec![
+                "read_file".to_string(),
+                "ast_parser".to_string(),
+                "web_search".to_string(),
+            ],
+            capabilities: vec![],
+            custom_instructions: None,
+            source_file: None,
+        };
+        let before = summarize(engine.infer_capabilities(&agent));
+        assert!(before.iter().any(|(c, _, _)| c == "code_review"));
+        engine.save(&path).unwrap();
+
+        let mut restarted = CapabilityInferenceEngine::new();
+        restarted.load(&path).unwrap();
+        assert_eq!(summarize(restarted.infer_capabilities(&agent)), before);
+
+        // Loading again leaves the state unchanged
+        restarted.load(&path).unwrap();
+        assert_eq!(summarize(restarted.infer_capabilities(&agent)), before);
+        let pattern = &restarted.learned_patterns["web_search"];
+        assert_eq!(pattern.frequency, 2);
+        assert_eq!(pattern.success_rate, 0.5);
+        assert_eq!(pattern.avg_execution_time_ms, 400);
+
+        // Learning continues from the loaded statistics
+        restarted.learn_pattern(
+            vec!["read_file".to_string(), "ast_parser".to_string()],
+            vec!["refactoring".to_string()],
+            false,
+            500,
+        );
+        let pattern = &restarted.learned_patterns["read_file+ast_parser"];
+        assert_eq!(pattern.frequency, 2);
+        assert!(pattern.observed_capabilities.contains(&"code_review".to_string()));
+        assert!(pattern.observed_capabilities.contains(&"refactoring".to_string()));
+    }
+
+    #[test]
+    fn test_incompatible_cache_version_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inference.json");
+        std::fs::write(&path, r#"{"version": 0, "learned_patterns": {}}"#).unwrap();
+
+        let mut engine = CapabilityInferenceEngine::new();
+        assert!(matches!(engine.load(&path), Err(IntentError::ValidationFailed(_))));
+    }
 }
\ No newline at end of file
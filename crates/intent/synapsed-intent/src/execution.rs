@@ -56,7 +56,30 @@ impl VerifiedExecutor {
         }
     }
 
+    /// Uses the given command verifier, e.g. the one from synapsed-verify
+    pub fn with_command_verifier(mut self, verifier: Box<dyn CommandVerifierTrait>) -> Self {
+        self.command_verifier = verifier;
+        self
+    }
+
+    /// Uses the given file system verifier
+    pub fn with_fs_verifier(mut self, verifier: Box<dyn FileSystemVerifierTrait>) -> Self {
+        self.fs_verifier = verifier;
+        self
+    }
+
+    /// Uses the given state verifier
+    pub fn with_state_verifier(mut self, verifier: Box<dyn StateVerifierTrait>) -> Self {
+        self.state_verifier = verifier;
+        self
+    }
+
     /// Executes a step with full verification
+    ///
+    /// Preconditions are checked against live system state first. If a
+    /// critical precondition does not hold, the step fails without running
+    /// its action. Other unmet preconditions are logged and listed under
+    /// `unmet_preconditions` in the step output.
     pub async fn execute_step(
         &mut self,
         step: &Step,
@@ -67,6 +90,36 @@ impl VerifiedExecutor {
         // Check bounds before execution
         self.bounds_enforcer.check_step_bounds(step)?;
         
+        // Check preconditions before touching anything
+        let mut unmet = Vec::new();
+        let mut unmet_optional = Vec::new();
+        for condition in &step.preconditions {
+            if self.check_condition(condition, context).await? {
+                continue;
+            }
+            if condition.critical {
+                unmet.push(condition);
+            } else {
+                tracing::warn!(
+                    "Non-critical precondition not met for step '{}': {}",
+                    step.name,
+                    describe_condition(condition)
+                );
+                unmet_optional.push(condition);
+            }
+        }
+        if !unmet.is_empty() {
+            let descriptions: Vec<String> = unmet.iter().map(|c| describe_condition(c)).collect();
+            unmet.extend(unmet_optional);
+            return Ok(StepResult {
+                success: false,
+                output: Some(json!({ "unmet_preconditions": unmet_conditions_json(&unmet) })),
+                error: Some(format!("Preconditions not met: {}", descriptions.join(", "))),
+                duration_ms: (Utc::now() - start).num_milliseconds() as u64,
+                verification: None,
+            });
+        }
+        
         // Take state snapshot before execution
         let pre_snapshot = self.state_verifier.take_snapshot().await
             .map_err(|e| IntentError::ExecutionFailed(format!("Failed to take snapshot: {}", e)))?;
@@ -108,6 +161,21 @@ impl VerifiedExecutor {
         
         let duration_ms = (Utc::now() - start).num_milliseconds() as u64;
         
+        // Report non-critical preconditions that did not hold alongside the output
+        let output = if unmet_optional.is_empty() {
+            output
+        } else {
+            let unmet = unmet_conditions_json(&unmet_optional);
+            match output {
+                Some(serde_json::Value::Object(mut map)) => {
+                    map.insert("unmet_preconditions".to_string(), unmet);
+                    Some(serde_json::Value::Object(map))
+                },
+                None => Some(json!({ "unmet_preconditions": unmet })),
+                Some(other) => Some(json!({ "result": other, "unmet_preconditions": unmet })),
+            }
+        };
+        
         Ok(StepResult {
             success,
             output,
@@ -122,6 +190,57 @@ impl VerifiedExecutor {
         })
     }
 
+    /// Checks a condition against live system state
+    ///
+    /// `FileExists` expects a path, `CommandSuccess` a command line and
+    /// `StateMatch` an object of variables, looked up in the state snapshot
+    /// and then in the context. Custom conditions are left to the caller.
+    pub async fn check_condition(
+        &self,
+        condition: &Condition,
+        context: &IntentContext,
+    ) -> Result<bool> {
+        match condition.condition_type {
+            ConditionType::FileExists => {
+                let Some(path) = condition.expected.as_str() else {
+                    return Ok(false);
+                };
+                if !self.bounds_enforcer.is_path_allowed(path) {
+                    return Ok(false);
+                }
+                self.fs_verifier.file_exists(path).await
+                    .map_err(|e| IntentError::ExecutionFailed(format!("File verification failed: {}", e)))
+            },
+            ConditionType::CommandSuccess => {
+                let parts: Vec<&str> = condition.expected.as_str()
+                    .map(|command| command.split_whitespace().collect())
+                    .unwrap_or_default();
+                let Some((cmd, args)) = parts.split_first() else {
+                    return Ok(false);
+                };
+                if !self.bounds_enforcer.is_command_allowed(cmd) {
+                    return Ok(false);
+                }
+                let verification = self.command_verifier.verify(cmd, Some(args), None).await
+                    .map_err(|e| IntentError::ExecutionFailed(format!("Command verification failed: {}", e)))?;
+                Ok(verification.exit_code == 0)
+            },
+            ConditionType::StateMatch => {
+                let Some(expected) = condition.expected.as_object() else {
+                    return Ok(false);
+                };
+                let snapshot = self.state_verifier.take_snapshot().await
+                    .map_err(|e| IntentError::ExecutionFailed(format!("Failed to take snapshot: {}", e)))?;
+                Ok(expected.iter().all(|(key, value)| {
+                    snapshot.variables.get(key).cloned()
+                        .or_else(|| context.get_variable(key))
+                        .as_ref() == Some(value)
+                }))
+            },
+            ConditionType::Custom => Ok(true),
+        }
+    }
+
     /// Executes a command with verification
     async fn execute_command(
         &mut self,
@@ -395,6 +514,19 @@ impl VerifiedExecutor {
     }
 }
 
+/// Describes a condition for error messages and logs
+fn describe_condition(condition: &Condition) -> String {
+    condition.description.clone().unwrap_or_else(|| format!("{:?}", condition.condition_type))
+}
+
+/// Lists unmet conditions for inclusion in a step output
+fn unmet_conditions_json(conditions: &[&Condition]) -> serde_json::Value {
+    conditions
+        .iter()
+        .map(|c| json!({ "condition_type": format!("{:?}", c.condition_type), "expected": c.expected }))
+        .collect()
+}
+
 /// Enforces context bounds on operations
 pub struct BoundsEnforcer {
     context_bounds: ContextBounds,
@@ -542,4 +674,93 @@ impl ContextMonitor {
     pub async fn get_violations(&self) -> Vec<ContextViolation> {
         self.violations.read().await.clone()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ContextBuilder;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    
+    /// Command verifier counting how often a command was run
+    struct CountingCommandVerifier(Arc<AtomicUsize>);
+    
+    #[async_trait::async_trait]
+    impl CommandVerifierTrait for CountingCommandVerifier {
+        async fn verify(
+            &self,
+            command: &str,
+            args: Option<&[&str]>,
+            expected: Option<&serde_json::Value>,
+        ) -> Result<CommandVerification> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            MockCommandVerifier.verify(command, args, expected).await
+        }
+    }
+    
+    fn step_requiring_file(path: &str) -> Step {
+        Step {
+            id: Uuid::new_v4(),
+            name: "consume_file".to_string(),
+            description: None,
+            action: StepAction::Command(format!("cat {}", path)),
+            preconditions: vec![Condition {
+                condition_type: ConditionType::FileExists,
+                expected: json!(path),
+                critical: true,
+                description: Some("Input file must exist".to_string()),
+            }],
+            postconditions: Vec::new(),
+            dependencies: Vec::new(),
+            verification: None,
+            status: StepStatus::Pending,
+            result: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_file_exists_precondition_blocks_action() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        let path_str = path.to_str().unwrap();
+        
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut executor = VerifiedExecutor::new(ContextBounds::default())
+            .with_command_verifier(Box::new(CountingCommandVerifier(runs.clone())));
+        let context = ContextBuilder::new().build().await;
+        let step = step_requiring_file(path_str);
+        
+        // The file is absent, so the command must not run
+        let result = executor.execute_step(&step, &context).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Input file must exist"));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        
+        // Once the file exists the step runs
+        tokio::fs::write(&path, b"data").await.unwrap();
+        let result = executor.execute_step(&step, &context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_non_critical_precondition_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("optional.txt");
+        let mut step = step_requiring_file(path.to_str().unwrap());
+        step.preconditions[0].critical = false;
+        
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut executor = VerifiedExecutor::new(ContextBounds::default())
+            .with_command_verifier(Box::new(CountingCommandVerifier(runs.clone())));
+        let context = ContextBuilder::new().build().await;
+        
+        // The step still runs, but the unmet precondition is reported
+        let result = executor.execute_step(&step, &context).await.unwrap();
+        assert!(result.success);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        let unmet = &result.output.unwrap()["unmet_preconditions"];
+        assert_eq!(unmet.as_array().unwrap().len(), 1);
+        assert_eq!(unmet[0]["condition_type"], "FileExists");
+    }
+}
@@ -47,7 +47,8 @@ pub use permission_negotiation::{
     PermissionNotification
 };
 pub use agent_parser::{
-    AgentMarkdownParser, ParsedAgentDefinition, Example, CapabilityPattern
+    AgentMarkdownParser, ParsedAgentDefinition, Example, CapabilityPattern,
+    Diagnostic, DiagnosticSeverity, DiagnosticKind
 };
 pub use capability_inference::{
     CapabilityInferenceEngine, ToolRelationshipGraph, ToolNode, ToolEdge,
@@ -57,13 +58,13 @@ pub use capability_inference::{
 pub use tool_discovery::{
     ToolDiscoverySystem, DiscoveredTool, UsageContext, RiskAssessment,
     ApprovalStatus, ToolUsageStats, DiscoveryPolicy, PolicyCondition,
-    PolicyAction, DiscoveryEvent, ToolAccessDecision, SuggestedTool
+    PolicyAction, DiscoveryEvent, ToolAccessDecision, SuggestedTool, AgentToolPolicy
 };
 pub use agent_profiling::{
     AgentProfilingSystem, AgentProfile, ExecutionPattern, PerformanceMetrics,
     TrustEvent, TrustEventType, Anomaly, AnomalyType, AnomalySeverity,
     BehaviorPattern, AnomalyDetector, AnomalyThresholds, BaselinePattern,
-    ResourceUsage as ProfileResourceUsage, ToolDivergenceAnalysis
+    ResourceUsage as ProfileResourceUsage, ToolDivergenceAnalysis, AnomalyHandler
 };
 pub use memory::{
     HybridMemory, VectorMemory, EpisodicMemory, SemanticMemory, WorkingMemory,
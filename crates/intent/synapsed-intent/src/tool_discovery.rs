@@ -8,7 +8,7 @@ use crate::{
     tool_registry::ToolRegistry,
     Result, IntentError,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use std::sync::Arc;
@@ -20,6 +20,28 @@ pub struct ToolDiscoverySystem {
     tool_usage_stats: Arc<RwLock<HashMap<String, ToolUsageStats>>>,
     discovery_policies: Vec<DiscoveryPolicy>,
     tool_registry: Arc<ToolRegistry>,
+    agent_policies: Arc<RwLock<HashMap<String, AgentToolPolicy>>>,
+    agent_tool_usage: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+    events: Arc<RwLock<VecDeque<DiscoveryEvent>>>,
+    max_events: usize,
+    divergence_threshold: f64,
+    min_divergence_sample: usize,
+}
+
+/// Default share of an agent's tools that may fall outside its approved set
+pub const DEFAULT_DIVERGENCE_THRESHOLD: f64 = 0.5;
+
+/// Default number of distinct tools an agent must have used before divergence is enforced
+pub const DEFAULT_MIN_DIVERGENCE_SAMPLE: usize = 4;
+
+/// Default number of enforcement decisions kept in the event log
+pub const DEFAULT_MAX_DISCOVERY_EVENTS: usize = 10_000;
+
+/// Tools an agent is approved to use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentToolPolicy {
+    pub approved_tools: HashSet<String>,
+    pub trust: f64,
 }
 
 /// A tool discovered during agent execution
@@ -126,10 +148,162 @@ impl ToolDiscoverySystem {
             tool_usage_stats: Arc::new(RwLock::new(HashMap::new())),
             discovery_policies: Self::create_default_policies(),
             tool_registry,
+            agent_policies: Arc::new(RwLock::new(HashMap::new())),
+            agent_tool_usage: Arc::new(RwLock::new(HashMap::new())),
+            events: Arc::new(RwLock::new(VecDeque::new())),
+            max_events: DEFAULT_MAX_DISCOVERY_EVENTS,
+            divergence_threshold: DEFAULT_DIVERGENCE_THRESHOLD,
+            min_divergence_sample: DEFAULT_MIN_DIVERGENCE_SAMPLE,
+        }
+    }
+
+    /// Set the share of unapproved tools above which an agent is blocked
+    pub fn with_divergence_threshold(mut self, threshold: f64) -> Self {
+        self.divergence_threshold = threshold;
+        self
+    }
+
+    /// Set how many distinct tools an agent must use before divergence is enforced
+    pub fn with_min_divergence_sample(mut self, sample: usize) -> Self {
+        self.min_divergence_sample = sample;
+        self
+    }
+
+    /// Set how many enforcement decisions are kept, dropping the oldest first
+    pub fn with_max_events(mut self, max_events: usize) -> Self {
+        self.max_events = max_events;
+        self
+    }
+
+    /// Declare the tools an agent is approved to use
+    pub async fn register_agent(
+        &self,
+        agent_id: &str,
+        approved_tools: impl IntoIterator<Item = String>,
+        trust: f64,
+    ) {
+        self.agent_policies.write().await.insert(agent_id.to_string(), AgentToolPolicy {
+            approved_tools: approved_tools.into_iter().collect(),
+            trust,
+        });
+    }
+
+    /// Decide whether an agent may invoke a tool, to be consulted before each invocation
+    ///
+    /// Tools in the agent's approved set are allowed. Any other tool must be
+    /// allowed by the discovery policies, and once the agent has used enough
+    /// distinct tools, using it must not push the share of unapproved tools
+    /// above the divergence threshold. Blocked invocations fail with
+    /// `PermissionDenied` and are not recorded as discoveries. Every decision
+    /// is logged as a [`DiscoveryEvent`].
+    pub async fn enforce(&self, agent_id: &str, tool_name: &str) -> Result<ToolAccessDecision> {
+        let policy = self.agent_policies.read().await.get(agent_id).cloned();
+        let (approved_tools, trust) = policy
+            .map(|p| (p.approved_tools, p.trust))
+            .unwrap_or_default();
+
+        let decision = if approved_tools.contains(tool_name) {
+            let profile = match self.tool_registry.get_tool_profile(tool_name).await {
+                Some(profile) => profile,
+                None => self.create_provisional_profile(&DiscoveredTool {
+                    name: tool_name.to_string(),
+                    first_seen: Utc::now(),
+                    last_used: Utc::now(),
+                    discovered_by: agent_id.to_string(),
+                    usage_context: Vec::new(),
+                    inferred_purpose: None,
+                    risk_assessment: self.assess_risk(tool_name, tool_name, &[]),
+                    approval_status: ApprovalStatus::ManuallyApproved,
+                }),
+            };
+            ToolAccessDecision::Allowed {
+                profile,
+                reason: "Tool is approved for agent".to_string(),
+            }
+        } else {
+            let used = self.agent_tool_usage.read().await.get(agent_id).cloned().unwrap_or_default();
+            let total = used.len() + usize::from(!used.contains(tool_name));
+            let unapproved = 1 + used
+                .iter()
+                .filter(|tool| *tool != tool_name && !approved_tools.contains(*tool))
+                .count();
+            let divergence = unapproved as f64 / total as f64;
+
+            let (attempt, decision) = self.evaluate_tool_attempt(agent_id, tool_name, tool_name, &[], "", trust).await;
+            let decision = match decision {
+                ToolAccessDecision::Denied { reason, .. } => {
+                    return self.deny(agent_id, tool_name, reason).await;
+                },
+                ToolAccessDecision::RequiresApproval { .. } => {
+                    return self.deny(
+                        agent_id,
+                        tool_name,
+                        "Tool is not approved for agent and requires approval".to_string(),
+                    ).await;
+                },
+                _ if total >= self.min_divergence_sample && divergence > self.divergence_threshold => {
+                    return self.deny(
+                        agent_id,
+                        tool_name,
+                        format!(
+                            "Tool usage diverges from approved set: {:.2} exceeds threshold {:.2}",
+                            divergence, self.divergence_threshold
+                        ),
+                    ).await;
+                },
+                decision => decision,
+            };
+            if let Some(attempt) = attempt {
+                self.record_discovery(attempt, &[]).await;
+            }
+            decision
+        };
+
+        self.agent_tool_usage.write().await
+            .entry(agent_id.to_string())
+            .or_default()
+            .insert(tool_name.to_string());
+        let reason = match &decision {
+            ToolAccessDecision::Allowed { reason, .. } => reason.clone(),
+            _ => "Allowed with supervision".to_string(),
+        };
+        self.log_event(agent_id, tool_name, true, reason).await;
+        Ok(decision)
+    }
+
+    /// Most recent enforcement decisions, oldest first
+    pub async fn events(&self) -> Vec<DiscoveryEvent> {
+        self.events.read().await.iter().cloned().collect()
+    }
+
+    async fn deny(&self, agent_id: &str, tool_name: &str, reason: String) -> Result<ToolAccessDecision> {
+        self.log_event(agent_id, tool_name, false, reason.clone()).await;
+        Err(IntentError::PermissionDenied(format!(
+            "Agent {} may not use tool {}: {}",
+            agent_id, tool_name, reason
+        )))
+    }
+
+    async fn log_event(&self, agent_id: &str, tool_name: &str, was_allowed: bool, reason: String) {
+        let mut events = self.events.write().await;
+        while events.len() >= self.max_events {
+            if events.pop_front().is_none() {
+                return;
+            }
         }
+        events.push_back(DiscoveryEvent {
+            timestamp: Utc::now(),
+            agent_id: agent_id.to_string(),
+            tool_name: tool_name.to_string(),
+            command: tool_name.to_string(),
+            was_allowed,
+            reason,
+        });
     }
 
     /// Handle an attempt to use an unknown tool
+    ///
+    /// The attempt is recorded as a discovery only if access is allowed.
     pub async fn handle_tool_attempt(
         &self,
         agent_id: &str,
@@ -139,17 +313,43 @@ impl ToolDiscoverySystem {
         task_context: &str,
         agent_trust: f64,
     ) -> Result<ToolAccessDecision> {
+        let (attempt, decision) = self
+            .evaluate_tool_attempt(agent_id, tool_name, command, args, task_context, agent_trust)
+            .await;
+        if let Some(attempt) = attempt {
+            if matches!(
+                decision,
+                ToolAccessDecision::Allowed { .. } | ToolAccessDecision::AllowedWithSupervision { .. }
+            ) {
+                self.record_discovery(attempt, args).await;
+            }
+        }
+        Ok(decision)
+    }
+
+    /// Decide on an attempt to use a tool without recording it
+    ///
+    /// Returns the tool as it would be recorded, or `None` for registered tools.
+    async fn evaluate_tool_attempt(
+        &self,
+        agent_id: &str,
+        tool_name: &str,
+        command: &str,
+        args: &[String],
+        task_context: &str,
+        agent_trust: f64,
+    ) -> (Option<DiscoveredTool>, ToolAccessDecision) {
         // Check if tool is already registered
         if let Some(profile) = self.tool_registry.get_tool_profile(tool_name).await {
-            return Ok(ToolAccessDecision::Allowed {
+            return (None, ToolAccessDecision::Allowed {
                 profile,
                 reason: "Tool is registered".to_string(),
             });
         }
 
         // Check if tool has been discovered before
-        let mut discovered = self.discovered_tools.write().await;
-        let tool = discovered.entry(tool_name.to_string()).or_insert_with(|| {
+        let known = self.discovered_tools.read().await.get(tool_name).cloned();
+        let mut tool = known.unwrap_or_else(|| {
             DiscoveredTool {
                 name: tool_name.to_string(),
                 first_seen: Utc::now(),
@@ -176,27 +376,33 @@ impl ToolDiscoverySystem {
         });
 
         // Apply discovery policies
-        let decision = self.apply_policies(tool, agent_trust).await;
+        let decision = self.apply_policies(&tool, agent_trust).await;
+        (Some(tool), decision)
+    }
 
-        // Update tool status based on decision
-        match &decision {
-            ToolAccessDecision::Allowed { .. } => {
-                if tool.approval_status == ApprovalStatus::Pending {
-                    tool.approval_status = ApprovalStatus::AutoApproved;
-                }
-            },
-            ToolAccessDecision::Denied { .. } => {
-                if tool.approval_status == ApprovalStatus::Pending {
-                    tool.approval_status = ApprovalStatus::Denied;
-                }
+    /// Record an allowed attempt to use a discovered tool
+    async fn record_discovery(&self, attempt: DiscoveredTool, args: &[String]) {
+        let tool_name = attempt.name.clone();
+        let agent_id = attempt.usage_context.last().map(|context| context.agent_id.clone()).unwrap_or_default();
+
+        let mut discovered = self.discovered_tools.write().await;
+        let tool = match discovered.entry(tool_name.clone()) {
+            std::collections::hash_map::Entry::Occupied(entry) => {
+                // Keep contexts recorded by concurrent attempts
+                let tool = entry.into_mut();
+                tool.last_used = attempt.last_used;
+                tool.usage_context.extend(attempt.usage_context.last().cloned());
+                tool
             },
-            _ => {}
+            std::collections::hash_map::Entry::Vacant(entry) => entry.insert(attempt),
+        };
+        if tool.approval_status == ApprovalStatus::Pending {
+            tool.approval_status = ApprovalStatus::AutoApproved;
         }
+        drop(discovered);
 
         // Update usage statistics
-        self.update_usage_stats(tool_name, agent_id, args).await;
-
-        Ok(decision)
+        self.update_usage_stats(&tool_name, &agent_id, args).await;
     }
 
     /// Assess risk of a discovered tool
@@ -556,4 +762,107 @@ pub struct SuggestedTool {
     pub success_rate: f64,
     pub risk_level: RiskLevel,
     pub usage_count: usize,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_system() -> ToolDiscoverySystem {
+        let system = ToolDiscoverySystem::new(Arc::new(ToolRegistry::new()));
+        system.register_agent("agent-1", vec!["str_replace".to_string()], 0.5).await;
+        system
+    }
+
+    #[tokio::test]
+    async fn test_enforce_allows_approved_tool() {
+        let system = create_system().await;
+
+        let decision = system.enforce("agent-1", "str_replace").await.unwrap();
+        assert!(matches!(decision, ToolAccessDecision::Allowed { .. }));
+
+        let events = system.events().await;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].was_allowed);
+    }
+
+    #[tokio::test]
+    async fn test_enforce_denies_and_logs_unapproved_tool() {
+        let system = create_system().await.with_min_divergence_sample(1);
+
+        // Denied by discovery policy
+        let result = system.enforce("agent-1", "sudo").await;
+        assert!(matches!(result, Err(IntentError::PermissionDenied(_))));
+
+        // Allowed by discovery policy, but diverges from the approved set
+        let result = system.enforce("agent-1", "nmap").await;
+        assert!(matches!(result, Err(IntentError::PermissionDenied(_))));
+
+        let events = system.events().await;
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| !event.was_allowed && event.agent_id == "agent-1"));
+        assert_eq!(events[0].tool_name, "sudo");
+        assert!(events[1].reason.contains("diverges"));
+    }
+
+    #[tokio::test]
+    async fn test_enforce_tolerates_divergence_below_threshold() {
+        let system = ToolDiscoverySystem::new(Arc::new(ToolRegistry::new()))
+            .with_divergence_threshold(0.5);
+        system
+            .register_agent("agent-1", vec!["str_replace".to_string(), "cat".to_string()], 0.5)
+            .await;
+
+        system.enforce("agent-1", "str_replace").await.unwrap();
+        system.enforce("agent-1", "cat").await.unwrap();
+
+        // One unexpected tool out of three stays below the threshold
+        let decision = system.enforce("agent-1", "nmap").await.unwrap();
+        assert!(matches!(decision, ToolAccessDecision::Allowed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_enforce_waits_for_minimum_sample_before_divergence() {
+        let system = create_system().await;
+
+        system.enforce("agent-1", "str_replace").await.unwrap();
+
+        // Too few tools used to judge divergence yet
+        system.enforce("agent-1", "nmap").await.unwrap();
+        system.enforce("agent-1", "htop").await.unwrap();
+
+        // Three unapproved tools out of four exceeds the threshold
+        let result = system.enforce("agent-1", "jq").await;
+        assert!(matches!(result, Err(IntentError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_blocked_attempts_are_not_recorded_as_discoveries() {
+        let system = create_system().await.with_min_divergence_sample(1);
+
+        assert!(system.enforce("agent-1", "nmap").await.is_err());
+        assert!(system.handle_tool_attempt("agent-1", "sudo", "sudo", &[], "", 0.5).await.is_ok());
+        assert!(system.discovered_tools.read().await.is_empty());
+        assert!(system.tool_usage_stats.read().await.is_empty());
+
+        let decision = system.handle_tool_attempt("agent-1", "nmap", "nmap", &[], "scan", 0.5).await.unwrap();
+        assert!(matches!(decision, ToolAccessDecision::Allowed { .. }));
+        let discovered = system.discovered_tools.read().await;
+        assert_eq!(discovered["nmap"].approval_status, ApprovalStatus::AutoApproved);
+        assert_eq!(discovered["nmap"].usage_context.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_event_log_keeps_most_recent_events() {
+        let system = create_system().await.with_max_events(3);
+
+        for _ in 0..5 {
+            system.enforce("agent-1", "str_replace").await.unwrap();
+        }
+        assert!(system.enforce("agent-1", "sudo").await.is_err());
+
+        let events = system.events().await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].tool_name, "sudo");
+    }
+}
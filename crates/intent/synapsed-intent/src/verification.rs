@@ -46,6 +46,11 @@ pub trait FileSystemVerifierTrait: Send + Sync {
         after: &FileSystemSnapshot,
         expected: Option<&serde_json::Value>,
     ) -> Result<FileSystemVerification>;
+    
+    /// Checks that a file exists on the live file system
+    async fn file_exists(&self, path: &str) -> Result<bool> {
+        Ok(tokio::fs::metadata(path).await.is_ok())
+    }
 }
 
 /// File system snapshot
@@ -5,6 +5,7 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use synapsed_core::clock::{SharedClock, SystemClock};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
@@ -115,6 +116,9 @@ pub struct Promise {
     /// Assessments of this promise
     #[serde(skip)]
     assessments: Arc<RwLock<Vec<Assessment>>>,
+    /// Clock for timestamps and the deadline
+    #[serde(skip, default = "SystemClock::shared")]
+    clock: SharedClock,
 }
 
 impl Default for Promise {
@@ -159,6 +163,7 @@ impl Promise {
             completed_at: Arc::new(RwLock::new(None)),
             outcome: Arc::new(RwLock::new(None)),
             assessments: Arc::new(RwLock::new(Vec::new())),
+            clock: SystemClock::shared(),
         }
     }
     
@@ -185,6 +190,7 @@ impl Promise {
             completed_at: Arc::new(RwLock::new(None)),
             outcome: Arc::new(RwLock::new(None)),
             assessments: Arc::new(RwLock::new(Vec::new())),
+            clock: SystemClock::shared(),
         }
     }
     
@@ -207,9 +213,17 @@ impl Promise {
             completed_at: Arc::new(RwLock::new(None)),
             outcome: Arc::new(RwLock::new(None)),
             assessments: Arc::new(RwLock::new(Vec::new())),
+            clock: SystemClock::shared(),
         }
     }
     
+    /// Uses `clock` for timestamps and the deadline
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.created_at = clock.now();
+        self.clock = clock;
+        self
+    }
+    
     /// Gets the promise ID
     pub fn id(&self) -> PromiseId {
         self.id
@@ -261,7 +275,7 @@ impl Promise {
         }
         
         *state = PromiseState::Active;
-        *self.activated_at.write().await = Some(Utc::now());
+        *self.activated_at.write().await = Some(self.clock.now());
         
         Ok(())
     }
@@ -290,10 +304,10 @@ impl Promise {
         }
         
         *state = PromiseState::Fulfilled;
-        *self.completed_at.write().await = Some(Utc::now());
+        *self.completed_at.write().await = Some(self.clock.now());
         
         let duration_ms = if let Some(activated) = *self.activated_at.read().await {
-            Some((Utc::now() - activated).num_milliseconds() as u64)
+            Some((self.clock.now() - activated).num_milliseconds() as u64)
         } else {
             None
         };
@@ -319,7 +333,7 @@ impl Promise {
         }
         
         *state = PromiseState::Broken;
-        *self.completed_at.write().await = Some(Utc::now());
+        *self.completed_at.write().await = Some(self.clock.now());
         
         *self.outcome.write().await = Some(PromiseOutcome {
             state: PromiseState::Broken,
@@ -342,7 +356,7 @@ impl Promise {
         }
         
         *state = PromiseState::Cancelled;
-        *self.completed_at.write().await = Some(Utc::now());
+        *self.completed_at.write().await = Some(self.clock.now());
         
         *self.outcome.write().await = Some(PromiseOutcome {
             state: PromiseState::Cancelled,
@@ -363,11 +377,11 @@ impl Promise {
         
         if let Some(timeout_ms) = self.contract.timeout_ms {
             if let Some(activated) = *self.activated_at.read().await {
-                let elapsed = (Utc::now() - activated).num_milliseconds() as u64;
+                let elapsed = (self.clock.now() - activated).num_milliseconds() as u64;
                 if elapsed > timeout_ms {
                     let mut state = self.state.write().await;
                     *state = PromiseState::Expired;
-                    *self.completed_at.write().await = Some(Utc::now());
+                    *self.completed_at.write().await = Some(self.clock.now());
                     
                     *self.outcome.write().await = Some(PromiseOutcome {
                         state: PromiseState::Expired,
@@ -480,4 +494,22 @@ mod tests {
         assert_eq!(outcome.state, PromiseState::Fulfilled);
         assert_eq!(outcome.quality, 1.0);
     }
+    
+    #[tokio::test]
+    async fn test_promise_expires_at_deadline() {
+        use synapsed_core::clock::MockClock;
+        
+        let clock = MockClock::default();
+        let promise = Promise::default().with_clock(Arc::new(clock.clone()));
+        promise.activate().await.unwrap();
+        
+        clock.advance(chrono::Duration::seconds(59));
+        assert!(!promise.check_expiry().await.unwrap());
+        assert!(promise.is_active().await);
+        
+        clock.advance(chrono::Duration::seconds(2));
+        assert!(promise.check_expiry().await.unwrap());
+        assert_eq!(promise.state().await, PromiseState::Expired);
+        assert_eq!(promise.outcome().await.unwrap().duration_ms, Some(61_000));
+    }
 }
\ No newline at end of file
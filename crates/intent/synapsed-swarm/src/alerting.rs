@@ -0,0 +1,309 @@
+//! Alert routing with deduplication, escalation and auto-resolution
+//!
+//! The [`AlertManager`] sits between the [`MetricsCollector`] and whoever is
+//! being notified. Repeated alerts for the same condition fold into a single
+//! active alert with a firing count instead of notifying again. An alert left
+//! unresolved past the escalation timeout moves up a severity, which also
+//! reaches any channels routed at the higher severity. Alerts resolve when the
+//! collector reports their condition cleared, or once they stop firing.
+
+use crate::{
+    monitoring::{Alert, AlertSeverity, MetricsCollector},
+    types::AgentId,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::{
+    sync::{broadcast::error::RecvError, RwLock},
+    task::JoinHandle,
+    time::interval,
+};
+use tracing::{debug, error, info, warn};
+
+/// Alert routing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRoutingConfig {
+    /// How long an alert may stay unresolved before each escalation
+    pub escalation_timeout: Duration,
+    /// Highest severity escalation can reach
+    pub max_severity: AlertSeverity,
+    /// Resolve alerts that have not fired for this long. With `None`, alerts
+    /// stay active until the collector reports their condition cleared.
+    pub resolve_after: Option<Duration>,
+    /// How often escalation and staleness are checked while consuming
+    pub evaluation_interval: Duration,
+}
+
+impl Default for AlertRoutingConfig {
+    fn default() -> Self {
+        Self {
+            escalation_timeout: Duration::from_secs(15 * 60),
+            max_severity: AlertSeverity::Emergency,
+            resolve_after: Some(Duration::from_secs(60 * 60)),
+            evaluation_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// The condition an alert reports; firings with the same fingerprint are deduplicated
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AlertFingerprint {
+    pub title: String,
+    pub agent_id: Option<AgentId>,
+}
+
+impl AlertFingerprint {
+    pub fn of(alert: &Alert) -> Self {
+        Self {
+            title: alert.title.clone(),
+            agent_id: alert.agent_id,
+        }
+    }
+}
+
+/// Every firing of one condition, tracked as a single alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedAlert {
+    pub fingerprint: AlertFingerprint,
+    /// The first firing
+    pub alert: Alert,
+    /// Current severity, raised by escalation and by more severe firings
+    pub severity: AlertSeverity,
+    /// Number of firings folded into this alert
+    pub count: u64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub escalations: u32,
+    pub last_escalated: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ManagedAlert {
+    fn new(alert: Alert) -> Self {
+        Self {
+            fingerprint: AlertFingerprint::of(&alert),
+            severity: alert.severity,
+            count: 1,
+            first_seen: alert.timestamp,
+            last_seen: alert.timestamp,
+            escalations: 0,
+            last_escalated: None,
+            resolved_at: None,
+            alert,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.resolved_at.is_none()
+    }
+}
+
+/// What happened to an alert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationKind {
+    Fired,
+    Escalated,
+    Resolved,
+}
+
+/// Notification sent to alert channels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertNotification {
+    pub kind: NotificationKind,
+    pub alert: ManagedAlert,
+}
+
+/// Destination for alert notifications
+#[async_trait]
+pub trait AlertChannel: Send + Sync {
+    /// Channel name for logging
+    fn name(&self) -> &str;
+
+    /// Deliver a notification
+    async fn notify(&self, notification: &AlertNotification);
+}
+
+/// Channel that writes notifications to the log
+#[derive(Debug, Default)]
+pub struct LogChannel;
+
+#[async_trait]
+impl AlertChannel for LogChannel {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn notify(&self, notification: &AlertNotification) {
+        let alert = &notification.alert;
+        match (notification.kind, alert.severity) {
+            (NotificationKind::Resolved, _) => info!(
+                "Resolved {} after {} firing(s)", alert.fingerprint.title, alert.count
+            ),
+            (_, AlertSeverity::Info) => info!("{:?}: {}", notification.kind, alert.alert.description),
+            (_, AlertSeverity::Warning) => warn!("{:?}: {}", notification.kind, alert.alert.description),
+            (_, severity) => error!("{:?} {:?}: {}", notification.kind, severity, alert.alert.description),
+        }
+    }
+}
+
+/// A channel and the lowest severity it is notified of
+struct Route {
+    min_severity: AlertSeverity,
+    channel: Arc<dyn AlertChannel>,
+}
+
+/// Deduplicates, escalates and resolves alerts, notifying routed channels
+pub struct AlertManager {
+    config: AlertRoutingConfig,
+    routes: Vec<Route>,
+    active: RwLock<HashMap<AlertFingerprint, ManagedAlert>>,
+    resolved: RwLock<Vec<ManagedAlert>>,
+}
+
+impl AlertManager {
+    /// Create a manager with no channels
+    pub fn new(config: AlertRoutingConfig) -> Self {
+        Self {
+            config,
+            routes: Vec::new(),
+            active: RwLock::new(HashMap::new()),
+            resolved: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Notify `channel` of alerts at `min_severity` or above
+    pub fn with_route(mut self, min_severity: AlertSeverity, channel: Arc<dyn AlertChannel>) -> Self {
+        self.routes.push(Route { min_severity, channel });
+        self
+    }
+
+    /// Process an alert from the collector
+    ///
+    /// A firing of a condition that is already active only increments its
+    /// count, and a resolved alert resolves the matching active one.
+    pub async fn ingest(&self, alert: Alert) {
+        let fingerprint = AlertFingerprint::of(&alert);
+        if alert.resolved {
+            self.resolve(&fingerprint, alert.resolution_time.unwrap_or(alert.timestamp)).await;
+            return;
+        }
+
+        let notification = {
+            let mut active = self.active.write().await;
+            match active.get_mut(&fingerprint) {
+                Some(existing) => {
+                    existing.count += 1;
+                    existing.last_seen = existing.last_seen.max(alert.timestamp);
+                    if alert.severity > existing.severity {
+                        existing.severity = alert.severity;
+                        Some((NotificationKind::Escalated, existing.clone()))
+                    } else {
+                        None
+                    }
+                },
+                None => {
+                    let managed = ManagedAlert::new(alert);
+                    active.insert(fingerprint, managed.clone());
+                    Some((NotificationKind::Fired, managed))
+                },
+            }
+        };
+
+        if let Some((kind, alert)) = notification {
+            self.dispatch(AlertNotification { kind, alert }).await;
+        }
+    }
+
+    /// Resolve the active alert for a condition, returning it if there was one
+    pub async fn resolve(&self, fingerprint: &AlertFingerprint, at: DateTime<Utc>) -> Option<ManagedAlert> {
+        let mut alert = self.active.write().await.remove(fingerprint)?;
+        alert.resolved_at = Some(at);
+        self.resolved.write().await.push(alert.clone());
+        self.dispatch(AlertNotification {
+            kind: NotificationKind::Resolved,
+            alert: alert.clone(),
+        })
+        .await;
+        Some(alert)
+    }
+
+    /// Escalate overdue alerts and resolve stale ones as of `now`
+    pub async fn evaluate(&self, now: DateTime<Utc>) {
+        let mut stale = Vec::new();
+        let mut escalated = Vec::new();
+        {
+            let mut active = self.active.write().await;
+            for alert in active.values_mut() {
+                if self.config.resolve_after.is_some_and(|after| elapsed(alert.last_seen, now, after)) {
+                    stale.push(alert.fingerprint.clone());
+                    continue;
+                }
+                let since = alert.last_escalated.unwrap_or(alert.first_seen);
+                if alert.severity < self.config.max_severity && elapsed(since, now, self.config.escalation_timeout) {
+                    alert.severity = alert.severity.escalate().min(self.config.max_severity);
+                    alert.escalations += 1;
+                    alert.last_escalated = Some(now);
+                    escalated.push(alert.clone());
+                }
+            }
+        }
+
+        for alert in escalated {
+            warn!("Escalating unresolved {} to {:?}", alert.fingerprint.title, alert.severity);
+            self.dispatch(AlertNotification {
+                kind: NotificationKind::Escalated,
+                alert,
+            })
+            .await;
+        }
+        for fingerprint in stale {
+            self.resolve(&fingerprint, now).await;
+        }
+    }
+
+    /// Active alerts, oldest first
+    pub async fn active_alerts(&self) -> Vec<ManagedAlert> {
+        let mut alerts: Vec<ManagedAlert> = self.active.read().await.values().cloned().collect();
+        alerts.sort_by_key(|alert| alert.first_seen);
+        alerts
+    }
+
+    /// Resolved alerts, in the order they were resolved
+    pub async fn resolved_alerts(&self) -> Vec<ManagedAlert> {
+        self.resolved.read().await.clone()
+    }
+
+    /// Consume the collector's alerts in the background, evaluating on `evaluation_interval`
+    pub fn consume(self: Arc<Self>, collector: &MetricsCollector) -> JoinHandle<()> {
+        let mut alerts = collector.subscribe_alerts();
+        let mut ticks = interval(self.config.evaluation_interval);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    received = alerts.recv() => match received {
+                        Ok(alert) => self.ingest(alert).await,
+                        Err(RecvError::Lagged(missed)) => warn!("Alert manager missed {} alerts", missed),
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = ticks.tick() => self.evaluate(Utc::now()).await,
+                }
+            }
+        })
+    }
+
+    async fn dispatch(&self, notification: AlertNotification) {
+        for route in self.routes.iter().filter(|route| notification.alert.severity >= route.min_severity) {
+            debug!("Notifying {} of {:?} alert", route.channel.name(), notification.kind);
+            route.channel.notify(&notification).await;
+        }
+    }
+}
+
+/// Whether at least `limit` has passed between `since` and `now`
+fn elapsed(since: DateTime<Utc>, now: DateTime<Utc>, limit: Duration) -> bool {
+    chrono::Duration::from_std(limit).is_ok_and(|limit| now - since >= limit)
+}
@@ -8,6 +8,7 @@ use crate::{
     error::{SwarmError, SwarmResult},
     types::*,
     protocol::{AgentMessage, MessageType, MessagePayload},
+    trust::TrustManager,
 };
 use async_trait::async_trait;
 use dashmap::DashMap;
@@ -43,6 +44,9 @@ pub trait ConsensusProtocol: Send + Sync {
     /// Get the result of a consensus round
     async fn get_result(&self, proposal_id: ProposalId) -> SwarmResult<Option<ConsensusResult>>;
     
+    /// Check whether the signers of a certificate form a quorum
+    async fn verify_quorum(&self, certificate: &QuorumCertificate) -> SwarmResult<bool>;
+    
     /// Check if enough agents are available for consensus
     fn can_achieve_consensus(&self) -> bool;
     
@@ -60,6 +64,8 @@ pub struct PBFTConsensus {
     agents: Arc<RwLock<Vec<AgentId>>>,
     /// Byzantine fault tolerance threshold (f in 3f+1 formula)
     byzantine_threshold: usize,
+    /// Vote weight of each agent for trust-weighted quorums
+    agent_weights: Arc<DashMap<AgentId, f64>>,
     /// Active voting rounds
     voting_rounds: Arc<DashMap<ProposalId, VotingRound>>,
     /// Completed consensus results
@@ -116,10 +122,20 @@ pub struct QuorumCertificate {
     pub view: u64,
     /// Signatures from agents
     pub signatures: Vec<ConsensusSignature>,
+    /// Vote weight of each signer, empty when votes are counted equally
+    #[serde(default)]
+    pub weights: HashMap<AgentId, f64>,
     /// Timestamp when certificate was created
     pub created_at: DateTime<Utc>,
 }
 
+impl QuorumCertificate {
+    /// Total vote weight recorded for the signers
+    pub fn signed_weight(&self) -> f64 {
+        self.weights.values().sum()
+    }
+}
+
 /// Consensus phases in PBFT
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConsensusPhase {
@@ -341,6 +357,38 @@ pub struct ConsensusConfig {
     pub enable_fast_path: bool,
     /// Signature algorithm to use
     pub signature_algorithm: String,
+    /// Weight votes by agent trust instead of counting them equally
+    pub weighted_quorum: Option<WeightedQuorumConfig>,
+}
+
+/// Settings for trust-weighted quorums
+#[derive(Debug, Clone)]
+pub struct WeightedQuorumConfig {
+    /// Fraction of the group's total weight the voters must hold (0.0 to 1.0)
+    pub threshold: f64,
+    /// Minimum number of distinct voters, whatever their weight
+    ///
+    /// Keeps a single high-trust agent from deciding alone. Set to 1 to allow it.
+    pub min_voters: usize,
+}
+
+impl Default for WeightedQuorumConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 2.0 / 3.0,
+            min_voters: 2,
+        }
+    }
+}
+
+impl WeightedQuorumConfig {
+    /// Check whether voters holding `voter_weight` of `total_weight` form a quorum
+    pub fn is_met(&self, voter_weight: f64, voter_count: usize, total_weight: f64) -> bool {
+        if voter_count == 0 || voter_count < self.min_voters || total_weight <= 0.0 {
+            return false;
+        }
+        voter_weight >= self.threshold * total_weight
+    }
 }
 
 impl Default for ConsensusConfig {
@@ -352,6 +400,7 @@ impl Default for ConsensusConfig {
             checkpoint_interval: 100,
             enable_fast_path: true,
             signature_algorithm: "ed25519".to_string(),
+            weighted_quorum: None,
         }
     }
 }
@@ -407,6 +456,7 @@ impl PBFTConsensus {
             agent_id,
             agents: Arc::new(RwLock::new(Vec::new())),
             byzantine_threshold: 0,
+            agent_weights: Arc::new(DashMap::new()),
             voting_rounds: Arc::new(DashMap::new()),
             results: Arc::new(DashMap::new()),
             message_sender,
@@ -441,6 +491,7 @@ impl PBFTConsensus {
     pub async fn remove_agent(&self, agent_id: AgentId) -> SwarmResult<()> {
         let mut agents = self.agents.write().await;
         agents.retain(|&id| id != agent_id);
+        self.agent_weights.remove(&agent_id);
         let n = agents.len();
         let f = (n.saturating_sub(1)) / 3;
         info!(
@@ -450,6 +501,29 @@ impl PBFTConsensus {
         Ok(())
     }
     
+    /// Set the vote weight of an agent used by trust-weighted quorums
+    pub fn set_agent_weight(&self, agent_id: AgentId, weight: f64) {
+        self.agent_weights.insert(agent_id, weight.max(0.0));
+    }
+    
+    /// Get the vote weight of an agent
+    pub fn agent_weight(&self, agent_id: AgentId) -> f64 {
+        self.agent_weights
+            .get(&agent_id)
+            .map(|w| *w)
+            .unwrap_or(crate::DEFAULT_TRUST_SCORE)
+    }
+    
+    /// Refresh every agent's vote weight from its current trust score
+    pub async fn sync_trust_weights(&self, trust_manager: &TrustManager) -> SwarmResult<()> {
+        let agents = self.agents.read().await.clone();
+        for agent_id in agents {
+            let trust = trust_manager.get_trust(agent_id).await?;
+            self.set_agent_weight(agent_id, trust);
+        }
+        Ok(())
+    }
+    
     /// Check if we have enough agents for Byzantine fault tolerance
     pub async fn has_sufficient_agents(&self) -> bool {
         let agents = self.agents.read().await;
@@ -477,6 +551,29 @@ impl PBFTConsensus {
         2 * f + 1
     }
     
+    /// Check whether a set of voters forms a quorum
+    ///
+    /// Votes are counted against 2f + 1 unless a weighted quorum is configured,
+    /// in which case the voters' summed weight must reach the threshold share
+    /// of the whole group's weight. Voters outside the group are ignored.
+    async fn has_quorum<'a>(&self, voters: impl IntoIterator<Item = &'a AgentId>) -> bool {
+        let agents = self.agents.read().await;
+        let voters: HashSet<AgentId> = voters
+            .into_iter()
+            .filter(|id| agents.contains(id))
+            .copied()
+            .collect();
+        
+        match &self.config.weighted_quorum {
+            Some(weighted) => {
+                let total_weight: f64 = agents.iter().map(|&id| self.agent_weight(id)).sum();
+                let voter_weight: f64 = voters.iter().map(|&id| self.agent_weight(id)).sum();
+                weighted.is_met(voter_weight, voters.len(), total_weight)
+            }
+            None => voters.len() >= self.calculate_quorum_size(agents.len()),
+        }
+    }
+    
     /// Start pre-prepare phase
     async fn start_pre_prepare(&self, proposal: ConsensusProposal, view: u64) -> SwarmResult<ProposalId> {
         let proposal_id = Uuid::new_v4();
@@ -593,10 +690,9 @@ impl PBFTConsensus {
             voting_round.prepare_votes.insert(msg.agent_id, msg);
             
             // Check if we have enough prepare votes
-            let agents = self.agents.read().await;
-            let quorum_size = self.calculate_quorum_size(agents.len());
+            let quorum_reached = self.has_quorum(voting_round.prepare_votes.keys()).await;
             
-            if voting_round.prepare_votes.len() >= quorum_size && !voting_round.commit_sent {
+            if quorum_reached && !voting_round.commit_sent {
                 // Move to commit phase
                 voting_round.phase = ConsensusPhase::Commit;
                 voting_round.commit_sent = true;
@@ -637,10 +733,9 @@ impl PBFTConsensus {
             voting_round.commit_votes.insert(msg.agent_id, msg);
             
             // Check if we have enough commit votes
-            let agents = self.agents.read().await;
-            let quorum_size = self.calculate_quorum_size(agents.len());
+            let quorum_reached = self.has_quorum(voting_round.commit_votes.keys()).await;
             
-            if voting_round.commit_votes.len() >= quorum_size {
+            if quorum_reached {
                 // Consensus reached!
                 voting_round.phase = ConsensusPhase::Committed;
                 
@@ -654,6 +749,13 @@ impl PBFTConsensus {
                     signatures: voting_round.commit_votes.values()
                         .map(|commit| commit.signature.clone())
                         .collect(),
+                    weights: if self.config.weighted_quorum.is_some() {
+                        participating_agents.iter()
+                            .map(|&id| (id, self.agent_weight(id)))
+                            .collect()
+                    } else {
+                        HashMap::new()
+                    },
                     created_at: Utc::now(),
                 };
                 
@@ -824,6 +926,11 @@ impl ConsensusProtocol for PBFTConsensus {
         Ok(self.results.get(&proposal_id).map(|r| r.clone()))
     }
     
+    async fn verify_quorum(&self, certificate: &QuorumCertificate) -> SwarmResult<bool> {
+        // Weights are taken from this node's view of the group, not from the certificate
+        Ok(self.has_quorum(certificate.signatures.iter().map(|sig| &sig.signer)).await)
+    }
+    
     fn can_achieve_consensus(&self) -> bool {
         // Check if we have sufficient agents (will need to be async in real implementation)
         let agents_len = self.voting_rounds.len(); // Placeholder
@@ -7,17 +7,22 @@ use crate::{
     trust::TrustManager,
     verification::SwarmVerifier,
     execution::{ExecutionEngine, ExecutionConfig},
-    recovery::{RecoveryManager, RecoveryResult},
-    fault_tolerance::{FaultToleranceConfig, FaultToleranceManager},
+    recovery::{BackoffConfig, ExponentialBackoffStrategy, RecoveryManager, RecoveryResult},
+    trace,
+    fault_tolerance::{FaultToleranceConfig, FaultToleranceManager, TaskCheckpoint, TaskReassignment},
+    dead_letter::{DeadLetterQueue, DeadLetterTask, RetryDecision},
+    event_bus::{AgentJoined, BusEvent, SwarmEventBus, TaskCompleted, TrustChanged},
 };
 use dashmap::DashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use chrono::Utc;
-use tracing::{info, warn, error, debug};
-use synapsed_intent::{HierarchicalIntent, IntentContext, VerifiedExecutor};
-use synapsed_promise::{AutonomousAgent, Promise, PromiseContract, Willingness};
+use tracing::{info, warn, error, debug, Instrument};
+use synapsed_intent::{HierarchicalIntent, IntentContext, IntentId, StepAction, VerifiedExecutor};
+use synapsed_promise::{AgentState, AutonomousAgent, Promise, PromiseContract, Willingness};
 use synapsed_verify::VerificationResult;
 
 /// Configuration for the swarm coordinator
@@ -31,6 +36,10 @@ pub struct SwarmConfig {
     pub require_verification: bool,
     /// Timeout for task execution in seconds
     pub task_timeout_secs: u64,
+    /// Retries of a failed task before it is dead-lettered
+    pub max_task_retries: u32,
+    /// Delay before each retry of a failed task
+    pub retry_backoff: BackoffConfig,
     /// Enable promise tracking
     pub track_promises: bool,
     /// Enable consensus for critical decisions
@@ -50,6 +59,8 @@ impl Default for SwarmConfig {
             min_trust_score: 0.3,
             require_verification: true,
             task_timeout_secs: 300,
+            max_task_retries: 3,
+            retry_backoff: BackoffConfig::default(),
             track_promises: true,
             require_consensus: false,
             consensus_threshold: 0.66,
@@ -91,6 +102,58 @@ pub enum SwarmPhase {
     ShuttingDown,
 }
 
+/// Preview of how a delegation would be routed, produced without dispatching it
+#[derive(Debug, Clone)]
+pub struct DelegationPlan {
+    /// Intent being delegated
+    pub intent_id: IntentId,
+    /// Agent that would be chosen, if any
+    pub selected_agent: Option<AgentId>,
+    /// Trust score of the selected agent
+    pub trust_score: Option<f64>,
+    /// Willingness of the selected agent to promise the task
+    pub willingness: Option<Willingness>,
+    /// Whether the intent's steps are permitted by the context bounds
+    pub bounds_satisfied: bool,
+    /// Agents that were not eligible, and why
+    pub rejected_agents: Vec<(AgentId, CandidateRejection)>,
+    /// Problems that would make the delegation fail
+    pub issues: Vec<DelegationIssue>,
+}
+
+impl DelegationPlan {
+    /// Whether the delegation would be dispatched successfully
+    pub fn is_viable(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Reason an agent was not eligible for a delegation
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandidateRejection {
+    /// Agent is not ready for new tasks
+    NotReady(AgentStatus),
+    /// Agent's role is shed at the current degradation level
+    RoleShed(AgentRole),
+    /// Agent's circuit breaker is open
+    CircuitOpen,
+    /// Agent's trust score is below the configured minimum
+    InsufficientTrust(f64),
+    /// Agent cannot handle the intent
+    MissingCapability,
+}
+
+/// Problem found while simulating a delegation
+#[derive(Debug, Clone, PartialEq)]
+pub enum DelegationIssue {
+    /// No agent is eligible for the intent
+    NoSuitableAgent,
+    /// The selected agent would refuse to make a promise
+    AgentUnwilling(AgentId),
+    /// A step is not permitted by the context bounds
+    BoundsViolation(String),
+}
+
 /// Main swarm coordinator
 pub struct SwarmCoordinator {
     /// Unique swarm ID
@@ -103,6 +166,8 @@ pub struct SwarmCoordinator {
     agents: Arc<DashMap<AgentId, Arc<AutonomousAgent>>>,
     /// Agent statuses
     agent_statuses: Arc<DashMap<AgentId, AgentStatus>>,
+    /// Roles agents joined with
+    agent_roles: Arc<DashMap<AgentId, AgentRole>>,
     /// Active task assignments
     tasks: Arc<DashMap<TaskId, TaskAssignment>>,
     /// Task results
@@ -119,6 +184,16 @@ pub struct SwarmCoordinator {
     execution_engine: Arc<ExecutionEngine>,
     /// Fault tolerance manager
     fault_tolerance_manager: Arc<FaultToleranceManager>,
+    /// Recovery strategies and degradation level
+    recovery_manager: Arc<RecoveryManager>,
+    /// Checkpoints that reassigned tasks resume from
+    resume_points: Arc<DashMap<TaskId, TaskCheckpoint>>,
+    /// Retry tracking and tasks that exhausted their retries
+    dead_letters: Arc<DeadLetterQueue>,
+    /// Agent spans of running tasks, parents for delegated sub-tasks
+    task_spans: Arc<DashMap<TaskId, tracing::Span>>,
+    /// Typed event bus shared with the fault tolerance manager
+    event_bus: Arc<SwarmEventBus>,
     /// Event log
     events: Arc<RwLock<Vec<SwarmEvent>>>,
 }
@@ -137,20 +212,25 @@ impl SwarmCoordinator {
             metrics: SwarmMetrics::default(),
         };
         
-        let trust_manager = Arc::new(TrustManager::new());
+        let agent_statuses = Arc::new(DashMap::new());
+        let trust_manager = Arc::new(TrustManager::new().with_agent_states(agent_statuses.clone()));
         let execution_engine = Arc::new(ExecutionEngine::with_config(config.execution_config.clone()));
+        let event_bus = Arc::new(SwarmEventBus::new());
         let fault_tolerance_manager = Arc::new(FaultToleranceManager::new(
             config.fault_tolerance_config.clone(),
             trust_manager.clone(),
             execution_engine.clone(),
-        ));
+        ).with_event_bus(event_bus.clone()));
+        let dead_letters = Arc::new(DeadLetterQueue::new(config.max_task_retries));
+        let recovery_manager = Arc::new(RecoveryManager::with_event_bus(event_bus.clone()));
         
         Self {
             swarm_id,
             config: Arc::new(config),
             state: Arc::new(RwLock::new(state)),
             agents: Arc::new(DashMap::new()),
-            agent_statuses: Arc::new(DashMap::new()),
+            agent_statuses,
+            agent_roles: Arc::new(DashMap::new()),
             tasks: Arc::new(DashMap::new()),
             results: Arc::new(DashMap::new()),
             trust_manager,
@@ -159,6 +239,11 @@ impl SwarmCoordinator {
             intent_executor: Arc::new(RwLock::new(VerifiedExecutor::new())),
             execution_engine,
             fault_tolerance_manager,
+            recovery_manager,
+            resume_points: Arc::new(DashMap::new()),
+            dead_letters,
+            task_spans: Arc::new(DashMap::new()),
+            event_bus,
             events: Arc::new(RwLock::new(Vec::new())),
         }
     }
@@ -179,6 +264,9 @@ impl SwarmCoordinator {
         // Initialize recovery manager
         self.recovery_manager.start_monitoring().await;
         
+        // Move tasks off agents that stop sending heartbeats
+        self.spawn_stall_monitor();
+        
         // Update state
         let mut state = self.state.write().await;
         state.phase = SwarmPhase::Ready;
@@ -211,6 +299,7 @@ impl SwarmCoordinator {
         // Add to swarm
         self.agents.insert(agent_id, agent.clone());
         self.agent_statuses.insert(agent_id, AgentStatus::Ready);
+        self.agent_roles.insert(agent_id, role.clone());
         
         // Initialize trust score
         self.trust_manager.initialize_agent(agent_id, crate::DEFAULT_TRUST_SCORE).await?;
@@ -223,6 +312,11 @@ impl SwarmCoordinator {
         
         // Log event
         self.log_event(SwarmEvent::AgentJoined {
+            agent_id,
+            role: role.clone(),
+            timestamp: Utc::now(),
+        }).await;
+        self.publish(AgentJoined {
             agent_id,
             role,
             timestamp: Utc::now(),
@@ -237,6 +331,32 @@ impl SwarmCoordinator {
         &self,
         intent: HierarchicalIntent,
         context: IntentContext,
+    ) -> SwarmResult<TaskId> {
+        self.delegate(intent, context, None).await
+    }
+    
+    /// Delegate an intent as a sub-task of a running task
+    ///
+    /// The sub-task's execution spans are attached under the parent task's
+    /// agent span, so both appear in the same trace.
+    pub async fn delegate_sub_intent(
+        &self,
+        parent_task: TaskId,
+        intent: HierarchicalIntent,
+        context: IntentContext,
+    ) -> SwarmResult<TaskId> {
+        if !self.tasks.contains_key(&parent_task) {
+            return Err(SwarmError::Other(anyhow::anyhow!("Parent task {} not found", parent_task)));
+        }
+        self.delegate(intent, context, Some(parent_task)).await
+    }
+    
+    /// Assign an intent to a suitable agent and start executing it
+    async fn delegate(
+        &self,
+        intent: HierarchicalIntent,
+        context: IntentContext,
+        parent_task: Option<TaskId>,
     ) -> SwarmResult<TaskId> {
         let task_id = Uuid::new_v4();
         
@@ -259,9 +379,11 @@ impl SwarmCoordinator {
             agent_id,
             intent: intent.clone(),
             promise: Some(promise.clone()),
-            parent_task: None,
+            parent_task,
             context: context.variables().clone(),
-            verification_required: self.config.require_verification,
+            // Verification is skipped while degradation sheds it
+            verification_required: self.config.require_verification
+                && self.recovery_manager.is_capability_available("verification").await,
             deadline: None,
         };
         
@@ -285,7 +407,7 @@ impl SwarmCoordinator {
         // Execute task asynchronously
         let coordinator = self.clone_inner();
         tokio::spawn(async move {
-            if let Err(e) = coordinator.execute_task(task_id).await {
+            if let Err(e) = coordinator.run_task(task_id).await {
                 error!("Task {} execution failed: {}", task_id, e);
             }
         });
@@ -293,8 +415,107 @@ impl SwarmCoordinator {
         Ok(task_id)
     }
     
+    /// Simulate delegating an intent without dispatching it
+    ///
+    /// Runs the same agent selection as [`delegate_intent`](Self::delegate_intent),
+    /// asks the chosen agent whether it would promise the task, and checks the
+    /// intent's commands against the context bounds. No task is created, no
+    /// promise is made and no commands are run.
+    pub async fn simulate_delegation(
+        &self,
+        intent: &HierarchicalIntent,
+        context: &IntentContext,
+    ) -> SwarmResult<DelegationPlan> {
+        let (candidates, rejected_agents) = self.evaluate_candidates(intent).await?;
+        let mut issues = Vec::new();
+        
+        // Check bounds
+        let mut violations = Vec::new();
+        if let Err(e) = context.validate().await {
+            violations.push(e.to_string());
+        }
+        Self::collect_bounds_violations(intent, context, &mut violations);
+        let bounds_satisfied = violations.is_empty();
+        issues.extend(violations.into_iter().map(DelegationIssue::BoundsViolation));
+        
+        // Check willingness of the agent that would be chosen
+        let selected = candidates.first().copied();
+        let willingness = match selected {
+            Some((agent_id, _)) => {
+                let agent = self.agents.get(&agent_id)
+                    .ok_or_else(|| SwarmError::AgentNotFound(agent_id))?
+                    .clone();
+                let willingness = agent.evaluate_willingness(&self.promise_contract(intent)).await?;
+                if !matches!(willingness, Willingness::Willing { confidence } if confidence > 0.5) {
+                    issues.push(DelegationIssue::AgentUnwilling(agent_id));
+                }
+                Some(willingness)
+            }
+            None => {
+                issues.push(DelegationIssue::NoSuitableAgent);
+                None
+            }
+        };
+        
+        debug!("Simulated delegation of intent {}: {} issue(s)", intent.id(), issues.len());
+        
+        Ok(DelegationPlan {
+            intent_id: intent.id(),
+            selected_agent: selected.map(|(agent_id, _)| agent_id),
+            trust_score: selected.map(|(_, trust)| trust),
+            willingness,
+            bounds_satisfied,
+            rejected_agents,
+            issues,
+        })
+    }
+    
+    /// Collect commands in an intent that the context does not allow
+    fn collect_bounds_violations(
+        intent: &HierarchicalIntent,
+        context: &IntentContext,
+        violations: &mut Vec<String>,
+    ) {
+        fn check(action: &StepAction, context: &IntentContext, violations: &mut Vec<String>) {
+            match action {
+                StepAction::Command(command) if !context.is_command_allowed(command) => {
+                    violations.push(format!("Command not allowed: {}", command));
+                }
+                StepAction::Composite(actions) => {
+                    for action in actions {
+                        check(action, context, violations);
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        for step in &intent.steps {
+            check(&step.action, context, violations);
+        }
+        for sub_intent in &intent.sub_intents {
+            Self::collect_bounds_violations(sub_intent, context, violations);
+        }
+    }
+    
+    /// Execute a task, running it again after each failure that is retried
+    ///
+    /// Retries wait with exponential backoff between runs.
+    async fn run_task(&self, task_id: TaskId) -> SwarmResult<()> {
+        let backoff = ExponentialBackoffStrategy::new(self.config.retry_backoff.clone());
+        while let Some(attempt) = self.execute_task(task_id).await? {
+            let delay = backoff.calculate_delay(attempt.saturating_sub(1) as usize);
+            debug!("Retrying task {} in {:?}", task_id, delay);
+            tokio::time::sleep(delay).await;
+        }
+        Ok(())
+    }
+    
     /// Execute a task
-    async fn execute_task(&self, task_id: TaskId) -> SwarmResult<()> {
+    ///
+    /// Returns the number of failed runs so far if the run failed and the task
+    /// should be run again.
+    async fn execute_task(&self, task_id: TaskId) -> SwarmResult<Option<u32>> {
         let assignment = self.tasks.get(&task_id)
             .ok_or_else(|| SwarmError::Other(anyhow::anyhow!("Task not found")))?
             .clone();
@@ -314,20 +535,59 @@ impl SwarmCoordinator {
         
         let start_time = Utc::now();
         
-        // Record heartbeat at start of execution
-        self.fault_tolerance_manager
-            .record_heartbeat(assignment.agent_id, Some(task_id))
-            .await?;
+        // Resume from the checkpoint left by a previous agent, if any
+        let start_step = self.resume_points
+            .remove(&task_id)
+            .map(|(_, checkpoint)| checkpoint.task_state.current_step)
+            .unwrap_or(0);
+        if start_step > 0 {
+            info!("Resuming task {} from step {}", task_id, start_step + 1);
+        }
         
-        // Execute with verification
+        // Open coordinator → agent → intent spans, nested under the delegating task if any
+        let parent_span = assignment.parent_task
+            .and_then(|parent| self.task_spans.get(&parent).and_then(|span| span.id()));
+        let task_span = trace::coordinator_span(task_id, parent_span);
+        let agent_span = trace::agent_span(assignment.agent_id, &task_span);
+        self.task_spans.insert(task_id, agent_span.clone());
+        let intent_span = trace::intent_span(&assignment.intent, &agent_span);
+        
+        // Execute with verification, heartbeating for the agent while it runs
         let result = if assignment.verification_required {
-            self.execute_with_verification(&assignment, &agent).await
+            self.with_heartbeats(&assignment, &agent,
+                self.execute_with_verification(&assignment, &agent, start_step)
+                    .instrument(intent_span.clone())).await
         } else {
-            self.execute_without_verification(&assignment, &agent).await
+            self.with_heartbeats(&assignment, &agent,
+                self.execute_without_verification(&assignment, &agent, start_step)
+                    .instrument(intent_span.clone())).await
         };
         
         let duration_ms = (Utc::now() - start_time).num_milliseconds() as u64;
         
+        // Spans close once their last handle is dropped at the end of this run
+        self.task_spans.remove(&task_id);
+        for span in [&intent_span, &agent_span, &task_span] {
+            match &result {
+                Some(Ok(_)) => trace::record_outcome(span, Ok(())),
+                Some(Err(e)) => trace::record_outcome(span, Err(e)),
+                None => trace::record_outcome(span, Err(&"Task reassigned")),
+            }
+        }
+        
+        // Discard the outcome if the task was reassigned while this agent ran it
+        let result = match result {
+            Some(result) if !self.is_reassigned(&assignment) => result,
+            _ => {
+                // The workspace stays with the task for the agent resuming it
+                debug!("Discarding result of task {} from agent {} after reassignment", task_id, assignment.agent_id);
+                let mut state = self.state.write().await;
+                state.running_tasks = state.running_tasks.saturating_sub(1);
+                return Ok(None);
+            }
+        };
+        self.fault_tolerance_manager.release_task(assignment.agent_id, task_id).await;
+        
         // Create task result
         let task_result = match result {
             Ok((output, proof)) => TaskResult {
@@ -352,6 +612,34 @@ impl SwarmCoordinator {
             },
         };
         
+        // Retry failed runs until the task exhausts its retries
+        if task_result.success {
+            self.dead_letters.record_success(task_id);
+        } else {
+            let error = task_result.error.clone().unwrap_or_default();
+            match self.dead_letters.record_failure(&assignment, &error) {
+                RetryDecision::Retry { attempt } => {
+                    warn!("Task {} failed on attempt {}, retrying: {}", task_id, attempt, error);
+                    
+                    self.update_trust(assignment.agent_id, false, false).await?;
+                    self.fault_tolerance_manager.record_task_result(
+                        assignment.agent_id,
+                        false,
+                        duration_ms,
+                    ).await?;
+                    self.reassign_for_retry(&assignment).await?;
+                    
+                    let mut state = self.state.write().await;
+                    state.running_tasks = state.running_tasks.saturating_sub(1);
+                    state.pending_tasks += 1;
+                    return Ok(Some(attempt));
+                }
+                RetryDecision::DeadLettered => {
+                    error!("Task {} exhausted its retries and was dead-lettered: {}", task_id, error);
+                }
+            }
+        }
+        
         // Store result
         self.results.insert(task_id, task_result.clone());
         
@@ -376,7 +664,7 @@ impl SwarmCoordinator {
         }
         
         // Update trust score
-        self.trust_manager.update_trust(
+        self.update_trust(
             assignment.agent_id,
             task_result.success,
             task_result.verification_proof.is_some(),
@@ -389,6 +677,9 @@ impl SwarmCoordinator {
             duration_ms,
         ).await?;
         
+        // Clean up the task's workspace now it is done
+        self.release_workspace(task_id).await;
+        
         // Update agent status
         self.agent_statuses.insert(assignment.agent_id, AgentStatus::Ready);
         
@@ -410,15 +701,87 @@ impl SwarmCoordinator {
             success: task_result.success,
             timestamp: Utc::now(),
         }).await;
+        self.publish(TaskCompleted {
+            task_id,
+            agent_id: assignment.agent_id,
+            success: task_result.success,
+            timestamp: Utc::now(),
+        }).await;
         
+        Ok(None)
+    }
+    
+    /// Run `work` for a task, recording the agent's heartbeats while it runs
+    ///
+    /// Heartbeats stop once the agent stops, which lets the stall monitor move
+    /// the task to another agent. Returns `None` when that happens, abandoning
+    /// the run.
+    async fn with_heartbeats<T>(
+        &self,
+        assignment: &TaskAssignment,
+        agent: &Arc<AutonomousAgent>,
+        work: impl Future<Output = SwarmResult<T>>,
+    ) -> Option<SwarmResult<T>> {
+        let period = Duration::from_millis(self.config.fault_tolerance_config.heartbeat_interval_ms.max(1));
+        let mut ticker = tokio::time::interval(period);
+        tokio::pin!(work);
+        
+        loop {
+            tokio::select! {
+                result = &mut work => return Some(result),
+                _ = ticker.tick() => {
+                    if self.is_reassigned(assignment) {
+                        return None;
+                    }
+                    if matches!(agent.state().await, AgentState::ShuttingDown | AgentState::Stopped) {
+                        continue;
+                    }
+                    if let Err(e) = self.fault_tolerance_manager
+                        .record_heartbeat(assignment.agent_id, Some(assignment.task_id))
+                        .await
+                    {
+                        warn!("Failed to record heartbeat of agent {}: {}", assignment.agent_id, e);
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Whether the task has moved to another agent since `assignment` was taken
+    fn is_reassigned(&self, assignment: &TaskAssignment) -> bool {
+        self.tasks
+            .get(&assignment.task_id)
+            .is_some_and(|current| current.agent_id != assignment.agent_id)
+    }
+    
+    /// Update an agent's trust score and publish the change
+    async fn update_trust(&self, agent_id: AgentId, success: bool, verified: bool) -> SwarmResult<()> {
+        let old_score = self.trust_manager.get_trust(agent_id).await?;
+        self.trust_manager.update_trust(agent_id, success, verified).await?;
+        let new_score = self.trust_manager.get_trust(agent_id).await?;
+        
+        self.publish(TrustChanged {
+            agent_id,
+            old_score,
+            new_score,
+            timestamp: Utc::now(),
+        }).await;
         Ok(())
     }
     
+    /// Remove a task's isolated workspace, logging failures
+    async fn release_workspace(&self, task_id: TaskId) {
+        if let Err(e) = self.execution_engine.release_workspace(task_id).await {
+            warn!("Failed to clean up workspace of task {}: {}", task_id, e);
+        }
+    }
+    
     /// Execute with verification using real execution engine
     async fn execute_with_verification(
         &self,
         assignment: &TaskAssignment,
         agent: &Arc<AutonomousAgent>,
+        start_step: usize,
     ) -> SwarmResult<(serde_json::Value, Option<synapsed_verify::VerificationProof>)> {
         debug!("Executing task {} with verification using real execution engine", assignment.task_id);
         
@@ -426,12 +789,12 @@ impl SwarmCoordinator {
         let mut step_results = Vec::new();
         let steps = assignment.intent.steps();
         
-        for (step_index, step) in steps.iter().enumerate() {
+        for (step_index, step) in steps.iter().enumerate().skip(start_step) {
             info!("Executing step {} of {}: {}", step_index + 1, steps.len(), step.description);
             
             // Execute step using the execution engine
             let step_result = self.execution_engine
-                .execute_intent_step(&assignment.intent, step_index)
+                .execute_task_step_traced(assignment.task_id, &assignment.intent, step_index)
                 .await?;
             
             // If step failed, stop execution
@@ -470,6 +833,7 @@ impl SwarmCoordinator {
         &self,
         assignment: &TaskAssignment,
         agent: &Arc<AutonomousAgent>,
+        start_step: usize,
     ) -> SwarmResult<(serde_json::Value, Option<synapsed_verify::VerificationProof>)> {
         debug!("Executing task {} without verification using real execution engine", assignment.task_id);
         
@@ -477,12 +841,12 @@ impl SwarmCoordinator {
         let mut step_results = Vec::new();
         let steps = assignment.intent.steps();
         
-        for (step_index, step) in steps.iter().enumerate() {
+        for (step_index, step) in steps.iter().enumerate().skip(start_step) {
             info!("Executing step {} of {}: {}", step_index + 1, steps.len(), step.description);
             
             // Execute step using the execution engine
             let step_result = self.execution_engine
-                .execute_intent_step(&assignment.intent, step_index)
+                .execute_task_step_traced(assignment.task_id, &assignment.intent, step_index)
                 .await?;
             
             // Continue even if step fails in non-verification mode
@@ -518,13 +882,66 @@ impl SwarmCoordinator {
         }
     }
     
+    /// Move a failed task to another eligible agent before it is retried
+    ///
+    /// The task stays with the agent that failed it only when no other agent
+    /// can take it.
+    async fn reassign_for_retry(&self, assignment: &TaskAssignment) -> SwarmResult<()> {
+        let failed_agent = assignment.agent_id;
+        // The retry starts over from the first step in a fresh workspace
+        self.release_workspace(assignment.task_id).await;
+        self.agent_statuses.insert(failed_agent, AgentStatus::Ready);
+        
+        let (candidates, _) = self.evaluate_candidates(&assignment.intent).await?;
+        let Some(next_agent) = candidates
+            .into_iter()
+            .map(|(id, _)| id)
+            .find(|id| *id != failed_agent)
+        else {
+            debug!("No other agent for task {}, retrying with agent {}", assignment.task_id, failed_agent);
+            return Ok(());
+        };
+        
+        if let Some(mut current) = self.tasks.get_mut(&assignment.task_id) {
+            current.agent_id = next_agent;
+        }
+        self.agent_statuses.insert(next_agent, AgentStatus::Busy);
+        
+        self.log_event(SwarmEvent::TaskAssigned {
+            task_id: assignment.task_id,
+            agent_id: next_agent,
+            timestamp: Utc::now(),
+        }).await;
+        
+        info!("Task {} moved from agent {} to agent {} for retry",
+              assignment.task_id, failed_agent, next_agent);
+        Ok(())
+    }
+    
     /// Select an agent for a task
     async fn select_agent_for_task(
         &self,
         intent: &HierarchicalIntent,
-        context: &IntentContext,
+        _context: &IntentContext,
     ) -> SwarmResult<AgentId> {
+        let (candidates, _) = self.evaluate_candidates(intent).await?;
+        
+        // Select agent with highest trust score
+        candidates.first()
+            .map(|(id, _)| *id)
+            .ok_or_else(|| SwarmError::Other(anyhow::anyhow!("No suitable agent found")))
+    }
+    
+    /// Evaluate every agent as a candidate for an intent
+    ///
+    /// Returns eligible agents with their trust scores, highest trust first,
+    /// along with the reason each other agent was rejected.
+    async fn evaluate_candidates(
+        &self,
+        intent: &HierarchicalIntent,
+    ) -> SwarmResult<(Vec<(AgentId, f64)>, Vec<(AgentId, CandidateRejection)>)> {
         let mut candidates = Vec::new();
+        let mut rejected = Vec::new();
         
         for entry in self.agents.iter() {
             let agent_id = *entry.key();
@@ -533,33 +950,51 @@ impl SwarmCoordinator {
             // Check if agent is available
             if let Some(status) = self.agent_statuses.get(&agent_id) {
                 if *status != AgentStatus::Ready {
+                    rejected.push((agent_id, CandidateRejection::NotReady(status.clone())));
+                    continue;
+                }
+            }
+            
+            // Check the agent's role is not shed by graceful degradation
+            if let Some(role) = self.agent_roles.get(&agent_id).map(|role| role.clone()) {
+                if !self.recovery_manager.is_role_active(&role).await {
+                    rejected.push((agent_id, CandidateRejection::RoleShed(role)));
                     continue;
                 }
             }
             
             // Check fault tolerance - circuit breaker
             if !self.fault_tolerance_manager.can_handle_task(agent_id).await {
+                rejected.push((agent_id, CandidateRejection::CircuitOpen));
                 continue;
             }
             
             // Check trust score
             let trust_score = self.trust_manager.get_trust(agent_id).await?;
             if trust_score < self.config.min_trust_score {
+                rejected.push((agent_id, CandidateRejection::InsufficientTrust(trust_score)));
                 continue;
             }
             
             // Check agent capabilities
             if agent.can_handle(intent).await {
                 candidates.push((agent_id, trust_score));
+            } else {
+                rejected.push((agent_id, CandidateRejection::MissingCapability));
             }
         }
         
-        // Select agent with highest trust score
         candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-        
-        candidates.first()
-            .map(|(id, _)| *id)
-            .ok_or_else(|| SwarmError::Other(anyhow::anyhow!("No suitable agent found")))
+        Ok((candidates, rejected))
+    }
+    
+    /// Build the promise contract offered to an agent for an intent
+    fn promise_contract(&self, intent: &HierarchicalIntent) -> PromiseContract {
+        PromiseContract::new(
+            format!("Execute intent {}", intent.id()),
+            synapsed_promise::PromiseType::Offer,
+            synapsed_promise::PromiseScope::Specific(vec![self.swarm_id]),
+        )
     }
     
     /// Negotiate a promise with an agent
@@ -570,11 +1005,7 @@ impl SwarmCoordinator {
         context: &IntentContext,
     ) -> SwarmResult<Promise> {
         // Create promise contract
-        let contract = PromiseContract::new(
-            format!("Execute intent {}", intent.id()),
-            synapsed_promise::PromiseType::Offer,
-            synapsed_promise::PromiseScope::Specific(vec![self.swarm_id]),
-        );
+        let contract = self.promise_contract(intent);
         
         // Check agent willingness
         let willingness = agent.evaluate_willingness(&contract).await?;
@@ -618,6 +1049,66 @@ impl SwarmCoordinator {
         self.results.get(&task_id).map(|r| r.clone())
     }
     
+    /// Get the current assignment of a task
+    pub async fn get_task_assignment(&self, task_id: TaskId) -> Option<TaskAssignment> {
+        self.tasks.get(&task_id).map(|t| t.clone())
+    }
+    
+    /// Tasks that exhausted their retries, oldest first
+    pub async fn dead_letter_tasks(&self) -> Vec<DeadLetterTask> {
+        self.dead_letters.tasks()
+    }
+    
+    /// Requeue a dead-lettered task
+    ///
+    /// The task is assigned to the best available agent, as a newly delegated
+    /// intent would be, and gets a fresh set of retries.
+    pub async fn requeue_dead_letter(&self, task_id: TaskId) -> SwarmResult<()> {
+        let dead_letter = self.dead_letters.get(task_id)
+            .ok_or_else(|| SwarmError::Other(anyhow::anyhow!("Task {} is not dead-lettered", task_id)))?;
+        let mut assignment = dead_letter.assignment;
+        
+        let context = IntentContext::new();
+        let agent_id = self.select_agent_for_task(&assignment.intent, &context).await?;
+        let agent = self.agents.get(&agent_id)
+            .ok_or_else(|| SwarmError::AgentNotFound(agent_id))?
+            .clone();
+        let promise = self.negotiate_promise(&agent, &assignment.intent, &context).await?;
+        
+        // Another caller may have requeued the task in the meantime
+        if self.dead_letters.take(task_id).is_none() {
+            return Ok(());
+        }
+        
+        assignment.agent_id = agent_id;
+        assignment.promise = Some(promise);
+        self.tasks.insert(task_id, assignment);
+        self.results.remove(&task_id);
+        self.agent_statuses.insert(agent_id, AgentStatus::Busy);
+        
+        {
+            let mut state = self.state.write().await;
+            state.pending_tasks += 1;
+        }
+        
+        self.log_event(SwarmEvent::TaskAssigned {
+            task_id,
+            agent_id,
+            timestamp: Utc::now(),
+        }).await;
+        
+        info!("Dead-lettered task {} requeued to agent {}", task_id, agent_id);
+        
+        let coordinator = self.clone_inner();
+        tokio::spawn(async move {
+            if let Err(e) = coordinator.run_task(task_id).await {
+                error!("Requeued task {} execution failed: {}", task_id, e);
+            }
+        });
+        
+        Ok(())
+    }
+    
     /// Get the execution engine for direct access
     pub fn execution_engine(&self) -> &Arc<ExecutionEngine> {
         &self.execution_engine
@@ -659,6 +1150,18 @@ impl SwarmCoordinator {
         events.push(event);
     }
     
+    /// Publish an event on the event bus, logging failures
+    async fn publish<E: BusEvent>(&self, event: E) {
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish {} event: {}", E::NAME, e);
+        }
+    }
+    
+    /// Get the event bus swarm components publish typed events on
+    pub fn event_bus(&self) -> &Arc<SwarmEventBus> {
+        &self.event_bus
+    }
+    
     /// Get fault tolerance manager
     pub fn fault_tolerance_manager(&self) -> &Arc<FaultToleranceManager> {
         &self.fault_tolerance_manager
@@ -709,6 +1212,99 @@ impl SwarmCoordinator {
             .await
     }
     
+    /// Check for stalled tasks every heartbeat interval until shutdown
+    fn spawn_stall_monitor(&self) {
+        let coordinator = self.clone_inner();
+        let period = Duration::from_millis(self.config.fault_tolerance_config.heartbeat_interval_ms.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                if coordinator.state.read().await.phase == SwarmPhase::ShuttingDown {
+                    break;
+                }
+                if let Err(e) = coordinator.reassign_stalled_tasks().await {
+                    warn!("Failed to reassign stalled tasks: {}", e);
+                }
+            }
+            debug!("Stall monitor of swarm {} stopped", coordinator.swarm_id);
+        });
+    }
+    
+    /// Reassign running tasks whose agents stopped sending heartbeats
+    ///
+    /// Tasks are moved to healthy agents able to run them and resumed from
+    /// their latest checkpoint, so steps completed before the checkpoint are
+    /// not executed again. The stall monitor started by
+    /// [`initialize`](Self::initialize) calls this every heartbeat interval.
+    pub async fn reassign_stalled_tasks(&self) -> SwarmResult<Vec<TaskReassignment>> {
+        let mut applied = Vec::new();
+        for stalled in self.fault_tolerance_manager.detect_stalled_tasks().await {
+            // Tasks that finished or already moved have nothing to resume
+            let intent = self.tasks
+                .get(&stalled.task_id)
+                .filter(|assignment| assignment.agent_id == stalled.agent_id)
+                .filter(|_| !self.results.contains_key(&stalled.task_id))
+                .map(|assignment| assignment.intent.clone());
+            let Some(intent) = intent else {
+                self.fault_tolerance_manager.release_task(stalled.agent_id, stalled.task_id).await;
+                continue;
+            };
+            
+            if let Some(reassignment) = self.fault_tolerance_manager.reassign_task(&stalled, &intent).await {
+                self.apply_reassignment(&reassignment).await?;
+                applied.push(reassignment);
+            }
+        }
+        
+        Ok(applied)
+    }
+    
+    /// Move a task to its new agent and restart it from the checkpoint
+    async fn apply_reassignment(&self, reassignment: &TaskReassignment) -> SwarmResult<()> {
+        let task_id = reassignment.task_id;
+        
+        if let Some(mut assignment) = self.tasks.get_mut(&task_id) {
+            assignment.agent_id = reassignment.to_agent;
+            if let Some(checkpoint) = &reassignment.checkpoint {
+                assignment.context.extend(checkpoint.context_snapshot.clone());
+            }
+        }
+        
+        if let Some(checkpoint) = &reassignment.checkpoint {
+            self.resume_points.insert(task_id, checkpoint.clone());
+        }
+        
+        self.agent_statuses.insert(reassignment.from_agent, AgentStatus::Degraded);
+        self.agent_statuses.insert(reassignment.to_agent, AgentStatus::Busy);
+        
+        // The resumed run is pending until execution picks it up
+        {
+            let mut state = self.state.write().await;
+            state.pending_tasks += 1;
+        }
+        
+        self.trust_manager.record_failure(reassignment.from_agent).await?;
+        
+        self.log_event(SwarmEvent::TaskAssigned {
+            task_id,
+            agent_id: reassignment.to_agent,
+            timestamp: Utc::now(),
+        }).await;
+        
+        info!("Task {} reassigned from agent {} to agent {}",
+              task_id, reassignment.from_agent, reassignment.to_agent);
+        
+        let coordinator = self.clone_inner();
+        tokio::spawn(async move {
+            if let Err(e) = coordinator.run_task(task_id).await {
+                error!("Resumed task {} execution failed: {}", task_id, e);
+            }
+        });
+        
+        Ok(())
+    }
+    
     /// Remove an agent from the swarm
     pub async fn remove_agent(&self, agent_id: AgentId) -> SwarmResult<()> {
         // Unregister from fault tolerance monitoring
@@ -717,6 +1313,7 @@ impl SwarmCoordinator {
         // Remove from swarm
         self.agents.remove(&agent_id);
         self.agent_statuses.remove(&agent_id);
+        self.agent_roles.remove(&agent_id);
         
         // Update state
         let mut state = self.state.write().await;
@@ -756,9 +1353,30 @@ impl SwarmCoordinator {
     
     /// Clone inner references for spawning
     fn clone_inner(&self) -> Arc<Self> {
-        // This would need proper implementation with Arc<Self>
-        // For now, returning a placeholder
-        Arc::new(Self::new(self.config.as_ref().clone()))
+        // Share all state so spawned tasks update this coordinator and publish
+        // on its event bus
+        Arc::new(Self {
+            swarm_id: self.swarm_id,
+            config: self.config.clone(),
+            state: self.state.clone(),
+            agents: self.agents.clone(),
+            agent_statuses: self.agent_statuses.clone(),
+            agent_roles: self.agent_roles.clone(),
+            tasks: self.tasks.clone(),
+            results: self.results.clone(),
+            trust_manager: self.trust_manager.clone(),
+            verifier: self.verifier.clone(),
+            protocol: self.protocol.clone(),
+            intent_executor: self.intent_executor.clone(),
+            execution_engine: self.execution_engine.clone(),
+            fault_tolerance_manager: self.fault_tolerance_manager.clone(),
+            recovery_manager: self.recovery_manager.clone(),
+            resume_points: self.resume_points.clone(),
+            dead_letters: self.dead_letters.clone(),
+            task_spans: self.task_spans.clone(),
+            event_bus: self.event_bus.clone(),
+            events: self.events.clone(),
+        })
     }
 }
 
@@ -0,0 +1,113 @@
+//! Retry tracking and dead-letter queue for failed tasks
+//!
+//! Failed task runs are retried until a task has failed more than the
+//! configured number of retries. The task is then moved to the dead-letter
+//! queue with its last error, where it stays out of automatic retry until it is
+//! inspected and requeued manually.
+
+use crate::types::{TaskAssignment, TaskId};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+/// Task that exhausted its retries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterTask {
+    /// Task ID
+    pub task_id: TaskId,
+    /// Assignment of the last failed run
+    pub assignment: TaskAssignment,
+    /// Error reported by the last failed run
+    pub error: String,
+    /// Number of failed runs
+    pub attempts: u32,
+    /// When the task was moved to the dead-letter queue
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// What to do with a task after a failed run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Run the task again; `attempt` is the number of failed runs so far
+    Retry { attempt: u32 },
+    /// The task exhausted its retries and was dead-lettered
+    DeadLettered,
+}
+
+/// Tracks failed runs per task and holds dead-lettered tasks
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    /// Retries allowed after the first failed run
+    max_retries: u32,
+    /// Failed runs of tasks still being retried
+    attempts: DashMap<TaskId, u32>,
+    /// Dead-lettered tasks
+    tasks: DashMap<TaskId, DeadLetterTask>,
+}
+
+impl DeadLetterQueue {
+    /// Create a queue allowing `max_retries` retries per task
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            attempts: DashMap::new(),
+            tasks: DashMap::new(),
+        }
+    }
+
+    /// Record a failed run and decide whether the task is retried
+    pub fn record_failure(&self, assignment: &TaskAssignment, error: &str) -> RetryDecision {
+        let task_id = assignment.task_id;
+        let attempts = {
+            let mut attempts = self.attempts.entry(task_id).or_insert(0);
+            *attempts += 1;
+            *attempts
+        };
+
+        if attempts <= self.max_retries {
+            return RetryDecision::Retry { attempt: attempts };
+        }
+
+        self.attempts.remove(&task_id);
+        self.tasks.insert(task_id, DeadLetterTask {
+            task_id,
+            assignment: assignment.clone(),
+            error: error.to_string(),
+            attempts,
+            dead_lettered_at: Utc::now(),
+        });
+        RetryDecision::DeadLettered
+    }
+
+    /// Record a successful run, forgetting earlier failures
+    pub fn record_success(&self, task_id: TaskId) {
+        self.attempts.remove(&task_id);
+    }
+
+    /// Get a dead-lettered task
+    pub fn get(&self, task_id: TaskId) -> Option<DeadLetterTask> {
+        self.tasks.get(&task_id).map(|task| task.clone())
+    }
+
+    /// Remove a task from the queue so it can be requeued
+    pub fn take(&self, task_id: TaskId) -> Option<DeadLetterTask> {
+        self.tasks.remove(&task_id).map(|(_, task)| task)
+    }
+
+    /// Dead-lettered tasks, oldest first
+    pub fn tasks(&self) -> Vec<DeadLetterTask> {
+        let mut tasks: Vec<_> = self.tasks.iter().map(|entry| entry.value().clone()).collect();
+        tasks.sort_by_key(|task| task.dead_lettered_at);
+        tasks
+    }
+
+    /// Number of dead-lettered tasks
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Whether no task is dead-lettered
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
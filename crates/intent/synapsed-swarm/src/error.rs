@@ -109,6 +109,10 @@ pub enum SwarmError {
     #[error("Monitoring error: {0}")]
     MonitoringError(String),
     
+    /// Event bus error
+    #[error("Event bus error: {0}")]
+    EventBusError(String),
+    
     /// Intent error
     #[error("Intent error: {0}")]
     Intent(#[from] synapsed_intent::IntentError),
@@ -0,0 +1,198 @@
+//! Typed event bus between swarm components
+//!
+//! The coordinator, fault tolerance and monitoring components publish typed
+//! events over a shared synapsed-substrates circuit instead of calling each
+//! other directly. Each event type has its own topic; subscribers register a
+//! handler for one event type and receive every event of that type published
+//! after they subscribed. Emissions are queued on the circuit, so they are
+//! delivered in publish order without blocking the publisher.
+
+use crate::error::{SwarmError, SwarmResult};
+use crate::recovery::DegradationTransition;
+use crate::types::{AgentId, AgentRole, TaskId};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use synapsed_substrates::{
+    BasicCircuit, Circuit, CircuitExt, FunctionPipe, FunctionSubscriber, ManagedSource,
+    ManagedSubscription, Name, Subject, SubjectType,
+};
+
+/// Event that can be published on the swarm event bus
+pub trait BusEvent: Clone + Send + Sync + 'static {
+    /// Topic name of the event type
+    const NAME: &'static str;
+}
+
+/// An agent joined the swarm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentJoined {
+    /// Agent ID
+    pub agent_id: AgentId,
+    /// Role the agent joined with
+    pub role: AgentRole,
+    /// When the agent joined
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BusEvent for AgentJoined {
+    const NAME: &'static str = "agent-joined";
+}
+
+/// A task finished, successfully or not
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskCompleted {
+    /// Task ID
+    pub task_id: TaskId,
+    /// Agent that ran the task
+    pub agent_id: AgentId,
+    /// Whether the task succeeded
+    pub success: bool,
+    /// When the task finished
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BusEvent for TaskCompleted {
+    const NAME: &'static str = "task-completed";
+}
+
+/// An agent's trust score changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrustChanged {
+    /// Agent ID
+    pub agent_id: AgentId,
+    /// Score before the update
+    pub old_score: f64,
+    /// Score after the update
+    pub new_score: f64,
+    /// When the score changed
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BusEvent for TrustChanged {
+    const NAME: &'static str = "trust-changed";
+}
+
+/// An agent's circuit breaker opened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitOpened {
+    /// Agent ID
+    pub agent_id: AgentId,
+    /// Consecutive failures when the breaker opened
+    pub failure_count: u32,
+    /// When the breaker opened
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BusEvent for CircuitOpened {
+    const NAME: &'static str = "circuit-opened";
+}
+
+impl BusEvent for DegradationTransition {
+    const NAME: &'static str = "degradation-changed";
+}
+
+/// Source and subject that events of one type are emitted through
+struct Topic<E> {
+    source: Arc<ManagedSource<E>>,
+    subject: Subject,
+}
+
+impl<E: BusEvent> Topic<E> {
+    fn new() -> Self {
+        let source = Arc::new(ManagedSource::new(Name::from_part(E::NAME)));
+        let subject = Subject::new(Name::from_part(E::NAME), SubjectType::Channel);
+        source.register_subject(subject.clone());
+        Self { source, subject }
+    }
+}
+
+/// Publish/subscribe bus for typed swarm events
+///
+/// Must be created within a Tokio runtime.
+pub struct SwarmEventBus {
+    /// Circuit that queues and delivers emissions
+    circuit: Arc<dyn Circuit>,
+    /// Topics by event type, holding `Topic<E>`
+    topics: DashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl std::fmt::Debug for SwarmEventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwarmEventBus")
+            .field("circuit", self.circuit.subject())
+            .field("topics", &self.topics.len())
+            .finish()
+    }
+}
+
+impl Default for SwarmEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SwarmEventBus {
+    /// Create a bus with its own circuit
+    pub fn new() -> Self {
+        Self::with_circuit(Arc::new(BasicCircuit::new(Name::from_part("swarm-events"))))
+    }
+
+    /// Create a bus delivering emissions through an existing circuit
+    pub fn with_circuit(circuit: Arc<dyn Circuit>) -> Self {
+        Self {
+            circuit,
+            topics: DashMap::new(),
+        }
+    }
+
+    /// Circuit the bus delivers emissions through
+    pub fn circuit(&self) -> &Arc<dyn Circuit> {
+        &self.circuit
+    }
+
+    /// Publish an event to the subscribers of its type
+    pub async fn publish<E: BusEvent>(&self, event: E) -> SwarmResult<()> {
+        let topic = self.topic::<E>();
+        self.circuit
+            .post_emission(topic.source.clone(), topic.subject.clone(), event)
+            .await
+            .map_err(|e| SwarmError::EventBusError(format!("Failed to publish {}: {}", E::NAME, e)))
+    }
+
+    /// Call `handler` with every event of type `E` published from now on
+    ///
+    /// The subscription stays active until it is closed.
+    pub async fn subscribe<E, F>(&self, handler: F) -> SwarmResult<Arc<ManagedSubscription<E>>>
+    where
+        E: BusEvent,
+        F: Fn(E) + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        let subscriber = Arc::new(FunctionSubscriber::new(move |_subject, registrar| {
+            let handler = handler.clone();
+            registrar.register(Arc::new(FunctionPipe::new(move |event: E| {
+                handler(event);
+                Ok(())
+            })));
+            Ok(())
+        }));
+
+        self.topic::<E>()
+            .source
+            .subscribe(subscriber)
+            .await
+            .map_err(|e| SwarmError::EventBusError(format!("Failed to subscribe to {}: {}", E::NAME, e)))
+    }
+
+    fn topic<E: BusEvent>(&self) -> Arc<Topic<E>> {
+        self.topics
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Arc::new(Topic::<E>::new()))
+            .clone()
+            .downcast::<Topic<E>>()
+            .expect("topics are keyed by their event type")
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::{
     error::{SwarmError, SwarmResult},
+    trace,
     types::*,
 };
 use synapsed_intent::{HierarchicalIntent, StepResult};
@@ -26,7 +27,7 @@ use tokio::{
     sync::{Mutex, RwLock},
     time::timeout,
 };
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 use uuid::Uuid;
 
 /// Configuration for the execution engine
@@ -60,6 +61,26 @@ pub struct ExecutionConfig {
     pub max_output_bytes: usize,
     /// Enable real-time output streaming
     pub stream_output: bool,
+    /// How tasks' working directories are kept apart
+    pub workspace_isolation: WorkspaceIsolation,
+}
+
+/// Scheme isolating the files tasks work on from each other
+///
+/// Workspaces belong to a task rather than the agent running it, so an agent
+/// running several tasks at once gets a separate workspace for each.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkspaceIsolation {
+    /// All tasks share the working directory they are given
+    None,
+    /// Each task works in its own directory under `root`
+    PerTaskDir { root: PathBuf },
+    /// Each task sees `lower` through an overlayfs mount under `root`,
+    /// with its writes kept in a private upper layer (requires root)
+    Overlay { lower: PathBuf, root: PathBuf },
+    /// Each task works in a tmpfs of `size_mb` mounted under `root`
+    /// (requires root)
+    Tmpfs { root: PathBuf, size_mb: u64 },
 }
 
 impl Default for ExecutionConfig {
@@ -116,6 +137,7 @@ impl Default for ExecutionConfig {
             allow_fs_write: false,
             max_output_bytes: 1024 * 1024, // 1MB
             stream_output: false,
+            workspace_isolation: WorkspaceIsolation::None,
         }
     }
 }
@@ -163,6 +185,17 @@ struct ExecutionContext {
     timeout_duration: Duration,
 }
 
+/// Isolated workspace held by a task
+#[derive(Debug)]
+struct TaskWorkspace {
+    /// Directory the task's commands run in
+    path: PathBuf,
+    /// Directory removed on release
+    base: PathBuf,
+    /// Whether `path` is a mount point to unmount on release
+    mounted: bool,
+}
+
 /// Production-ready execution engine
 pub struct ExecutionEngine {
     /// Engine configuration
@@ -171,6 +204,8 @@ pub struct ExecutionEngine {
     active_executions: Arc<Mutex<HashMap<Uuid, ExecutionContext>>>,
     /// Execution history (limited size)
     execution_history: Arc<RwLock<Vec<ExecutionResult>>>,
+    /// Isolated workspaces by task, each locked while it is set up or torn down
+    workspaces: Arc<Mutex<HashMap<TaskId, Arc<Mutex<Option<TaskWorkspace>>>>>>,
 }
 
 impl ExecutionEngine {
@@ -185,6 +220,7 @@ impl ExecutionEngine {
             config: Arc::new(RwLock::new(config)),
             active_executions: Arc::new(Mutex::new(HashMap::new())),
             execution_history: Arc::new(RwLock::new(Vec::new())),
+            workspaces: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -260,11 +296,108 @@ impl ExecutionEngine {
         result
     }
 
+    /// Execute a command in a task's isolated workspace
+    ///
+    /// Without workspace isolation the command runs in the current directory,
+    /// as with [`execute_command`](Self::execute_command).
+    pub async fn execute_task_command(
+        &self,
+        task_id: TaskId,
+        command: &str,
+        args: &[&str],
+    ) -> SwarmResult<ExecutionResult> {
+        let workspace = self.acquire_workspace(task_id).await?;
+        self.execute_command(command, args, workspace.as_deref()).await
+    }
+
+    /// Get the isolated workspace of a task, creating it if needed
+    ///
+    /// Returns `None` when workspace isolation is disabled. Directories are
+    /// created and mounted without holding the lock on other tasks' workspaces.
+    pub async fn acquire_workspace(&self, task_id: TaskId) -> SwarmResult<Option<PathBuf>> {
+        let isolation = self.config.read().await.workspace_isolation.clone();
+        if isolation == WorkspaceIsolation::None {
+            return Ok(None);
+        }
+
+        let slot = self.workspaces.lock().await.entry(task_id).or_default().clone();
+        let mut slot = slot.lock().await;
+        if let Some(workspace) = slot.as_ref() {
+            return Ok(Some(workspace.path.clone()));
+        }
+
+        let workspace = match isolation {
+            WorkspaceIsolation::None => return Ok(None),
+            WorkspaceIsolation::PerTaskDir { root } => {
+                let path = root.join(task_id.to_string());
+                create_workspace_dir(&path).await?;
+                TaskWorkspace { base: path.clone(), path, mounted: false }
+            }
+            WorkspaceIsolation::Overlay { lower, root } => {
+                let base = root.join(task_id.to_string());
+                let (upper, work, merged) = (base.join("upper"), base.join("work"), base.join("merged"));
+                for dir in [&upper, &work, &merged] {
+                    create_workspace_dir(dir).await?;
+                }
+                let options = format!(
+                    "lowerdir={},upperdir={},workdir={}",
+                    lower.display(), upper.display(), work.display()
+                );
+                mount_workspace(&base, &["-t", "overlay", "overlay", "-o", &options], &merged).await?;
+                TaskWorkspace { path: merged, base, mounted: true }
+            }
+            WorkspaceIsolation::Tmpfs { root, size_mb } => {
+                let path = root.join(task_id.to_string());
+                create_workspace_dir(&path).await?;
+                let options = format!("size={}m", size_mb);
+                mount_workspace(&path, &["-t", "tmpfs", "-o", &options, "tmpfs"], &path).await?;
+                TaskWorkspace { base: path.clone(), path, mounted: true }
+            }
+        };
+
+        debug!(task_id = %task_id, workspace = %workspace.path.display(), "Created task workspace");
+        let path = workspace.path.clone();
+        *slot = Some(workspace);
+        Ok(Some(path))
+    }
+
+    /// Remove a task's workspace and everything written to it
+    ///
+    /// Waits for a workspace still being set up before tearing it down.
+    pub async fn release_workspace(&self, task_id: TaskId) -> SwarmResult<()> {
+        let Some(slot) = self.workspaces.lock().await.remove(&task_id) else {
+            return Ok(());
+        };
+        let Some(workspace) = slot.lock().await.take() else {
+            return Ok(());
+        };
+
+        if workspace.mounted {
+            run_workspace_command("umount", &[workspace.path.as_os_str()]).await?;
+        }
+        tokio::fs::remove_dir_all(&workspace.base).await.map_err(|e| SwarmError::Other(anyhow::anyhow!(
+            "Failed to remove workspace '{}': {}", workspace.base.display(), e
+        )))?;
+
+        debug!(task_id = %task_id, "Released task workspace");
+        Ok(())
+    }
+
     /// Execute an intent step with the execution engine
     pub async fn execute_intent_step(
         &self,
         intent: &HierarchicalIntent,
         step_index: usize,
+    ) -> SwarmResult<StepResult> {
+        self.execute_step_in(intent, step_index, None).await
+    }
+
+    /// Execute an intent step in the given working directory
+    async fn execute_step_in(
+        &self,
+        intent: &HierarchicalIntent,
+        step_index: usize,
+        working_dir: Option<&Path>,
     ) -> SwarmResult<StepResult> {
         debug!(
             intent_id = %intent.id(),
@@ -281,7 +414,8 @@ impl ExecutionEngine {
         let (command, args) = self.parse_step_command(&step.description)?;
 
         // Execute command
-        let exec_result = self.execute_command(&command, &args, None).await?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let exec_result = self.execute_command(&command, &args, working_dir).await?;
 
         // Convert to StepResult format
         let step_result = StepResult {
@@ -306,6 +440,37 @@ impl ExecutionEngine {
         Ok(step_result)
     }
 
+    /// Execute an intent step for a task, in its isolated workspace,
+    /// inside a step span under the current span
+    pub async fn execute_task_step_traced(
+        &self,
+        task_id: TaskId,
+        intent: &HierarchicalIntent,
+        step_index: usize,
+    ) -> SwarmResult<StepResult> {
+        let workspace = self.acquire_workspace(task_id).await?;
+        let span = trace::step_span(intent, step_index);
+        let result = self.execute_step_in(intent, step_index, workspace.as_deref())
+            .instrument(span.clone())
+            .await;
+
+        match &result {
+            Ok(step_result) => {
+                if let Some(command) = step_result.metadata.get("command").and_then(|c| c.as_str()) {
+                    span.record("step.command", command);
+                }
+                if step_result.success {
+                    trace::record_outcome(&span, Ok(()));
+                } else {
+                    trace::record_outcome(&span, Err(&"Step reported failure"));
+                }
+            }
+            Err(e) => trace::record_outcome(&span, Err(e)),
+        }
+
+        result
+    }
+
     /// Kill an active execution
     pub async fn kill_execution(&self, execution_id: Uuid) -> SwarmResult<()> {
         let mut active = self.active_executions.lock().await;
@@ -626,6 +791,41 @@ impl ExecutionEngine {
     }
 }
 
+/// Create a workspace directory
+async fn create_workspace_dir(path: &Path) -> SwarmResult<()> {
+    tokio::fs::create_dir_all(path).await.map_err(|e| SwarmError::Other(anyhow::anyhow!(
+        "Failed to create workspace '{}': {}", path.display(), e
+    )))
+}
+
+/// Mount a filesystem at `target`, removing `base` if the mount fails
+async fn mount_workspace(base: &Path, args: &[&str], target: &Path) -> SwarmResult<()> {
+    let mut mount_args: Vec<&OsStr> = args.iter().map(OsStr::new).collect();
+    mount_args.push(target.as_os_str());
+
+    let result = run_workspace_command("mount", &mount_args).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_dir_all(base).await;
+    }
+    result
+}
+
+/// Run a workspace setup command, failing on a non-zero exit
+async fn run_workspace_command(program: &str, args: &[&OsStr]) -> SwarmResult<()> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| SwarmError::Other(anyhow::anyhow!("Failed to run {}: {}", program, e)))?;
+
+    if !output.status.success() {
+        return Err(SwarmError::Other(anyhow::anyhow!(
+            "{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
 impl Default for ExecutionEngine {
     fn default() -> Self {
         Self::new()
@@ -704,6 +904,214 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_task_workspaces_are_isolated() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+
+        let config = ExecutionConfig {
+            allowed_commands: vec!["sh".to_string(), "cat".to_string()],
+            allowed_working_dirs: vec![root.clone()],
+            workspace_isolation: WorkspaceIsolation::PerTaskDir { root: root.clone() },
+            ..Default::default()
+        };
+
+        let engine = ExecutionEngine::with_config(config);
+        engine.initialize().await.unwrap();
+
+        // Two tasks, possibly run by the same agent, write the same file at once
+        let (task_a, task_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let script_a = "echo 'from task a' > shared.txt";
+        let script_b = "echo 'from task b' > shared.txt";
+        let (write_a, write_b) = tokio::join!(
+            engine.execute_task_command(task_a, "sh", &["-c", script_a]),
+            engine.execute_task_command(task_b, "sh", &["-c", script_b]),
+        );
+        assert!(write_a.unwrap().success);
+        assert!(write_b.unwrap().success);
+
+        let read_a = engine.execute_task_command(task_a, "cat", &["shared.txt"]).await.unwrap();
+        let read_b = engine.execute_task_command(task_b, "cat", &["shared.txt"]).await.unwrap();
+        assert_eq!(read_a.stdout.trim(), "from task a");
+        assert_eq!(read_b.stdout.trim(), "from task b");
+
+        // Releasing one workspace removes its files and leaves the other intact
+        let workspace_a = engine.acquire_workspace(task_a).await.unwrap().unwrap();
+        engine.release_workspace(task_a).await.unwrap();
+        assert!(!workspace_a.exists());
+        let read_b = engine.execute_task_command(task_b, "cat", &["shared.txt"]).await.unwrap();
+        assert_eq!(read_b.stdout.trim(), "from task b");
+        assert!(!root.join("shared.txt").exists());
+    }
+
+    /// A span recorded by [`SpanRecorder`], parents referring to recording order
+    #[derive(Debug, Clone)]
+    struct RecordedSpan {
+        name: &'static str,
+        parent: Option<usize>,
+        duration: Option<Duration>,
+    }
+
+    /// Recording position and start of a span, kept in its extensions
+    struct Recorded {
+        index: usize,
+        started: Instant,
+    }
+
+    /// Layer recording the execution spans opened while it is installed
+    #[derive(Clone, Default)]
+    struct SpanRecorder(Arc<std::sync::Mutex<Vec<RecordedSpan>>>);
+
+    impl SpanRecorder {
+        fn spans(&self) -> Vec<RecordedSpan> {
+            self.0.lock().unwrap().clone()
+        }
+
+        fn children(&self, parent: usize) -> Vec<usize> {
+            let spans = self.spans();
+            (0..spans.len()).filter(|&i| spans[i].parent == Some(parent)).collect()
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanRecorder
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if attrs.metadata().target() != trace::TRACE_TARGET {
+                return;
+            }
+            let span = ctx.span(id).unwrap();
+            let parent = span.parent()
+                .and_then(|parent| parent.extensions().get::<Recorded>().map(|r| r.index));
+
+            let mut spans = self.0.lock().unwrap();
+            span.extensions_mut().insert(Recorded { index: spans.len(), started: Instant::now() });
+            spans.push(RecordedSpan { name: attrs.metadata().name(), parent, duration: None });
+        }
+
+        fn on_close(&self, id: tracing::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            let span = ctx.span(&id).unwrap();
+            let extensions = span.extensions();
+            if let Some(recorded) = extensions.get::<Recorded>() {
+                self.0.lock().unwrap()[recorded.index].duration = Some(recorded.started.elapsed());
+            }
+        }
+    }
+
+    fn test_agent(name: &str) -> Arc<synapsed_promise::AutonomousAgent> {
+        use synapsed_promise::{AgentCapabilities, AgentConfig, AutonomousAgent, QualityOfService};
+
+        Arc::new(AutonomousAgent::new(AgentConfig {
+            name: name.to_string(),
+            capabilities: AgentCapabilities {
+                services: vec!["test".to_string()],
+                resources: vec!["cpu".to_string()],
+                protocols: vec!["promise".to_string()],
+                quality: QualityOfService::default(),
+            },
+            trust_model: synapsed_promise::TrustModel::new(),
+            cooperation_protocol: synapsed_promise::CooperationProtocol::new(),
+            max_promises: 5,
+            promise_timeout_secs: 60,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_traced_execution_builds_span_tree() {
+        use crate::{AgentRole, SwarmConfig, SwarmCoordinator};
+        use synapsed_intent::{ContextBuilder, IntentBuilder, StepAction};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        // Spawned tasks run on this thread, so they record into the recorder too
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(recorder.clone()),
+        );
+
+        let mut config = SwarmConfig::default();
+        config.require_verification = false;
+        config.execution_config.allowed_commands.push("sleep".to_string());
+        let coordinator = SwarmCoordinator::new(config);
+        coordinator.initialize().await.unwrap();
+        coordinator.add_agent(test_agent("parent"), AgentRole::Worker).await.unwrap();
+        coordinator.add_agent(test_agent("child"), AgentRole::Worker).await.unwrap();
+
+        let parent = IntentBuilder::new("parent work")
+            .step("sleep 1", StepAction::Command("sleep 1".to_string()))
+            .build();
+        let parent_task = coordinator
+            .delegate_intent(parent, ContextBuilder::new().build().await)
+            .await
+            .unwrap();
+
+        // Delegate the sub-intent while the parent's agent is at work
+        timeout(Duration::from_secs(5), async {
+            while !recorder.spans().iter().any(|span| span.name == "step") {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Parent task should start");
+        let delegated = IntentBuilder::new("delegated work")
+            .step("echo child", StepAction::Command("echo child".to_string()))
+            .build();
+        let child_task = coordinator
+            .delegate_sub_intent(parent_task, delegated, ContextBuilder::new().build().await)
+            .await
+            .unwrap();
+
+        timeout(Duration::from_secs(10), async {
+            loop {
+                let finished = coordinator.get_task_result(parent_task).await.is_some()
+                    && coordinator.get_task_result(child_task).await.is_some()
+                    && recorder.spans().iter().all(|span| span.duration.is_some());
+                if finished {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Both tasks should finish and close their spans");
+
+        // coordinator → agent → intent → step for each task, in one tree
+        let spans = recorder.spans();
+        assert_eq!(spans.len(), 8);
+        assert!(spans.iter().all(|span| span.duration.unwrap() > Duration::ZERO));
+        let roots: Vec<_> = (0..spans.len()).filter(|&i| spans[i].parent.is_none()).collect();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(spans[roots[0]].name, "coordinator");
+
+        let parent_agent = recorder.children(roots[0]);
+        assert_eq!(parent_agent.len(), 1);
+        assert_eq!(spans[parent_agent[0]].name, "agent");
+
+        // The sub-task's coordinator span sits under the delegating agent
+        let agent_children = recorder.children(parent_agent[0]);
+        let names: HashSet<_> = agent_children.iter().map(|&i| spans[i].name).collect();
+        assert_eq!(names, HashSet::from(["intent", "coordinator"]));
+
+        for &span in &agent_children {
+            let chain: Vec<_> = std::iter::successors(Some(span), |&i| {
+                let children = recorder.children(i);
+                assert!(children.len() <= 1, "{} span has several children", spans[i].name);
+                children.first().copied()
+            })
+            .map(|i| spans[i].name)
+            .collect();
+            match spans[span].name {
+                "intent" => assert_eq!(chain, ["intent", "step"]),
+                _ => assert_eq!(chain, ["coordinator", "agent", "intent", "step"]),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_timeout() {
         let mut config = ExecutionConfig::default();
@@ -11,6 +11,7 @@ use crate::{
     error::{SwarmError, SwarmResult},
     types::*,
     claude_agent::ClaudeAgent,
+    event_bus::{CircuitOpened, SwarmEventBus},
     trust::TrustManager,
     execution::ExecutionEngine,
 };
@@ -31,6 +32,7 @@ use tokio::{
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use synapsed_core::clock::{SharedClock, SystemClock};
 use synapsed_intent::HierarchicalIntent;
 use synapsed_promise::{AutonomousAgent, Promise};
 
@@ -59,6 +61,8 @@ pub struct FaultToleranceConfig {
     pub enable_auto_recovery: bool,
     /// Enable task redistribution
     pub enable_task_redistribution: bool,
+    /// Missed heartbeats after which a running task is reassigned
+    pub reassignment_missed_heartbeats: u32,
     /// Maximum time to wait for recovery confirmation in milliseconds
     pub recovery_confirmation_timeout_ms: u64,
 }
@@ -77,6 +81,7 @@ impl Default for FaultToleranceConfig {
             max_checkpoints: 10,
             enable_auto_recovery: true,
             enable_task_redistribution: true,
+            reassignment_missed_heartbeats: 3,
             recovery_confirmation_timeout_ms: 30000, // 30 seconds
         }
     }
@@ -252,6 +257,32 @@ pub enum RecoveryAction {
     },
 }
 
+/// Running task whose agent missed too many heartbeats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalledTask {
+    /// Task that stopped making progress
+    pub task_id: TaskId,
+    /// Agent that was running the task
+    pub agent_id: AgentId,
+    /// Number of heartbeats the agent missed
+    pub missed_heartbeats: u32,
+}
+
+/// Reassignment of a running task away from an agent that stopped heartbeating
+#[derive(Debug, Clone)]
+pub struct TaskReassignment {
+    /// Task being reassigned
+    pub task_id: TaskId,
+    /// Agent that missed its heartbeats
+    pub from_agent: AgentId,
+    /// Healthy agent taking over the task
+    pub to_agent: AgentId,
+    /// Checkpoint the task resumes from, if one was taken
+    pub checkpoint: Option<TaskCheckpoint>,
+    /// Number of heartbeats the original agent missed
+    pub missed_heartbeats: u32,
+}
+
 /// Main fault tolerance manager
 pub struct FaultToleranceManager {
     /// Configuration
@@ -276,6 +307,10 @@ pub struct FaultToleranceManager {
     shutdown_notify: Arc<Notify>,
     /// Recovery statistics
     recovery_stats: Arc<RwLock<RecoveryStatistics>>,
+    /// Bus that circuit breaker events are published on
+    event_bus: Option<Arc<SwarmEventBus>>,
+    /// Clock for checkpoint and recovery timestamps
+    clock: SharedClock,
 }
 
 /// Recovery operation statistics
@@ -316,9 +351,23 @@ impl FaultToleranceManager {
             is_running: Arc::new(AtomicBool::new(false)),
             shutdown_notify: Arc::new(Notify::new()),
             recovery_stats: Arc::new(RwLock::new(RecoveryStatistics::default())),
+            event_bus: None,
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Publish circuit breaker events on `event_bus`
+    pub fn with_event_bus(mut self, event_bus: Arc<SwarmEventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Use `clock` for checkpoint and recovery timestamps
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Start the fault tolerance system
     pub async fn start(&self) -> SwarmResult<()> {
         info!("Starting fault tolerance manager");
@@ -530,7 +579,7 @@ impl FaultToleranceManager {
             checkpoint_id,
             task_id,
             agent_id,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
             task_state,
             progress,
             context_snapshot: context,
@@ -668,6 +717,87 @@ impl FaultToleranceManager {
         }
     }
 
+    /// Detect running tasks whose agents missed too many heartbeats
+    ///
+    /// Detection does not change any state; the coordinator decides which
+    /// stalled tasks to move and hands each one to
+    /// [`reassign_task`](Self::reassign_task).
+    pub async fn detect_stalled_tasks(&self) -> Vec<StalledTask> {
+        let interval = Duration::from_millis(self.config.heartbeat_interval_ms.max(1));
+        let threshold = self.config.reassignment_missed_heartbeats.max(1);
+        
+        self.heartbeats
+            .iter()
+            .filter_map(|entry| {
+                let heartbeat = entry.value();
+                let task_id = heartbeat.current_task?;
+                let missed = (heartbeat.last_heartbeat.elapsed().as_millis() / interval.as_millis()) as u32;
+                (missed >= threshold).then_some(StalledTask {
+                    task_id,
+                    agent_id: *entry.key(),
+                    missed_heartbeats: missed,
+                })
+            })
+            .collect()
+    }
+
+    /// Hand a stalled task to a healthy idle agent able to run `intent`
+    ///
+    /// The stalled agent is marked unresponsive and the new agent takes over
+    /// the task along with its latest checkpoint. Returns `None`, changing
+    /// nothing, when no agent can take the task; it is retried on the next check.
+    pub async fn reassign_task(
+        &self,
+        stalled: &StalledTask,
+        intent: &HierarchicalIntent,
+    ) -> Option<TaskReassignment> {
+        let StalledTask { task_id, agent_id: from_agent, missed_heartbeats } = *stalled;
+        let Some(to_agent) = self.find_healthy_agent(intent, from_agent).await else {
+            warn!("No healthy agent available to take over task {} from agent {}", task_id, from_agent);
+            return None;
+        };
+        
+        if let Some(mut heartbeat) = self.heartbeats.get_mut(&from_agent) {
+            heartbeat.current_task = None;
+            heartbeat.missed_heartbeats = heartbeat.missed_heartbeats.max(missed_heartbeats);
+            if heartbeat.health_status == AgentHealthStatus::Healthy {
+                heartbeat.health_status = AgentHealthStatus::Unresponsive;
+            }
+        }
+        if let Some(mut heartbeat) = self.heartbeats.get_mut(&to_agent) {
+            heartbeat.current_task = Some(task_id);
+            heartbeat.last_heartbeat = Instant::now();
+        }
+        
+        let checkpoint = self.get_latest_checkpoint(task_id).await;
+        warn!("Reassigning task {} from agent {} to agent {} after {} missed heartbeats",
+              task_id, from_agent, to_agent, missed_heartbeats);
+        
+        let mut stats = self.recovery_stats.write().await;
+        stats.task_redistributions += 1;
+        stats.last_recovery = Some(self.clock.now());
+        
+        Some(TaskReassignment {
+            task_id,
+            from_agent,
+            to_agent,
+            checkpoint,
+            missed_heartbeats,
+        })
+    }
+
+    /// Stop tracking `task_id` as the agent's running task
+    ///
+    /// Called when a task finishes, so an idle agent is not mistaken for one
+    /// that stalled mid-task.
+    pub async fn release_task(&self, agent_id: AgentId, task_id: TaskId) {
+        if let Some(mut heartbeat) = self.heartbeats.get_mut(&agent_id) {
+            if heartbeat.current_task == Some(task_id) {
+                heartbeat.current_task = None;
+            }
+        }
+    }
+
     /// Recovery processor loop
     async fn recovery_processor(&self) {
         let mut interval = interval(Duration::from_millis(1000)); // Check every second
@@ -708,7 +838,7 @@ impl FaultToleranceManager {
             } else {
                 let mut stats = self.recovery_stats.write().await;
                 stats.successful_recoveries += 1;
-                stats.last_recovery = Some(Utc::now());
+                stats.last_recovery = Some(self.clock.now());
             }
         }
     }
@@ -828,12 +958,11 @@ impl FaultToleranceManager {
         let checkpoint = self.get_latest_checkpoint(task_id).await;
         
         if let Some(checkpoint) = checkpoint {
-            // Find a healthy agent to take over
-            let target_agent = if let Some(agent_id) = to_agent {
-                agent_id
-            } else {
-                self.find_healthy_agent().await
-                    .ok_or_else(|| SwarmError::Other(anyhow::anyhow!("No healthy agent available for redistribution")))?
+            // Without a target the coordinator moves the task once it stalls,
+            // since only the coordinator knows which agents can run it
+            let Some(target_agent) = to_agent else {
+                debug!("Task {} is left for the coordinator to reassign", task_id);
+                return Ok(());
             };
             
             info!("Redistributing task {} to agent {}", task_id, target_agent);
@@ -889,17 +1018,26 @@ impl FaultToleranceManager {
         Ok(())
     }
 
-    /// Find a healthy agent for task redistribution
-    async fn find_healthy_agent(&self) -> Option<AgentId> {
-        for entry in self.heartbeats.iter() {
-            let agent_id = *entry.key();
-            let heartbeat = entry.value();
-            
-            if heartbeat.health_status == AgentHealthStatus::Healthy &&
-               heartbeat.current_task.is_none() {
-                if self.can_handle_task(agent_id).await {
-                    return Some(agent_id);
-                }
+    /// Find a healthy idle agent other than `exclude` able to run `intent`
+    async fn find_healthy_agent(&self, intent: &HierarchicalIntent, exclude: AgentId) -> Option<AgentId> {
+        // Collect first so no map guard is held across the awaits below
+        let idle: Vec<AgentId> = self.heartbeats
+            .iter()
+            .filter(|entry| {
+                let heartbeat = entry.value();
+                *entry.key() != exclude
+                    && heartbeat.health_status == AgentHealthStatus::Healthy
+                    && heartbeat.current_task.is_none()
+            })
+            .map(|entry| *entry.key())
+            .collect();
+        
+        for agent_id in idle {
+            let Some(agent) = self.agents.get(&agent_id).map(|agent| agent.clone()) else {
+                continue;
+            };
+            if self.can_handle_task(agent_id).await && agent.can_handle(intent).await {
+                return Some(agent_id);
             }
         }
         
@@ -908,18 +1046,34 @@ impl FaultToleranceManager {
 
     /// Transition circuit breaker state
     async fn transition_circuit_breaker(&self, agent_id: AgentId, new_state: CircuitBreakerStatus) {
+        let mut opened = None;
         if let Some(mut circuit_breaker) = self.circuit_breakers.get_mut(&agent_id) {
             if circuit_breaker.state != new_state {
                 info!("Circuit breaker for agent {} transitioning from {:?} to {:?}", 
                       agent_id, circuit_breaker.state, new_state);
                 
-                circuit_breaker.state = new_state;
+                circuit_breaker.state = new_state.clone();
                 circuit_breaker.last_state_change = Instant::now();
                 
                 // Reset failure count when closing
                 if new_state == CircuitBreakerStatus::Closed {
                     circuit_breaker.failure_count = 0;
                 }
+                
+                if new_state == CircuitBreakerStatus::Open {
+                    opened = Some(circuit_breaker.failure_count);
+                }
+            }
+        }
+        
+        if let (Some(failure_count), Some(event_bus)) = (opened, &self.event_bus) {
+            let event = CircuitOpened {
+                agent_id,
+                failure_count,
+                timestamp: self.clock.now(),
+            };
+            if let Err(e) = event_bus.publish(event).await {
+                warn!("Failed to publish circuit opened event for agent {}: {}", agent_id, e);
             }
         }
     }
@@ -944,7 +1098,7 @@ impl FaultToleranceManager {
 
     /// Clean up old checkpoints
     async fn cleanup_old_checkpoints(&self) {
-        let cutoff_time = Utc::now() - chrono::Duration::hours(1); // Keep checkpoints for 1 hour
+        let cutoff_time = self.clock.now() - chrono::Duration::hours(1); // Keep checkpoints for 1 hour
         let mut cleaned_count = 0;
         
         for mut entry in self.checkpoints.iter_mut() {
@@ -998,6 +1152,8 @@ impl FaultToleranceManager {
             is_running: self.is_running.clone(),
             shutdown_notify: self.shutdown_notify.clone(),
             recovery_stats: self.recovery_stats.clone(),
+            event_bus: self.event_bus.clone(),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -1104,7 +1260,9 @@ mod tests {
         let trust_manager = Arc::new(TrustManager::new());
         let execution_engine = Arc::new(ExecutionEngine::with_config(ExecutionConfig::default()));
         
-        let manager = FaultToleranceManager::new(config, trust_manager, execution_engine);
+        let clock = synapsed_core::clock::MockClock::default();
+        let manager = FaultToleranceManager::new(config, trust_manager, execution_engine)
+            .with_clock(Arc::new(clock.clone()));
         
         let task_id = Uuid::new_v4();
         let agent_id = Uuid::new_v4();
@@ -1134,6 +1292,14 @@ mod tests {
         let retrieved_checkpoint = manager.get_latest_checkpoint(task_id).await;
         assert!(retrieved_checkpoint.is_some());
         assert_eq!(retrieved_checkpoint.unwrap().checkpoint_id, checkpoint_id);
+        
+        // Checkpoints are kept for an hour
+        clock.advance(chrono::Duration::minutes(59));
+        manager.cleanup_old_checkpoints().await;
+        assert!(manager.get_latest_checkpoint(task_id).await.is_some());
+        clock.advance(chrono::Duration::minutes(2));
+        manager.cleanup_old_checkpoints().await;
+        assert!(manager.get_latest_checkpoint(task_id).await.is_none());
     }
 
     #[tokio::test]
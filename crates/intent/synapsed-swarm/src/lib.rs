@@ -11,6 +11,7 @@
 //! - **Execution Verification**: All agent claims are verified against reality
 //! - **Trust Management**: Reputation-based trust scores for agents
 //! - **Context Propagation**: Parent context passed to sub-agents
+//! - **Execution Tracing**: Coordinator → agent → intent → step spans, exportable as OpenTelemetry
 //!
 //! ## Architecture
 //! 
@@ -37,36 +38,51 @@ pub mod types;
 pub mod error;
 pub mod execution;
 pub mod monitoring;
+pub mod alerting;
 pub mod fault_tolerance;
+pub mod dead_letter;
+pub mod event_bus;
 pub mod consensus;
 pub mod recovery;
+pub mod trace;
 
-pub use coordinator::{SwarmCoordinator, SwarmConfig, SwarmState};
+pub use coordinator::{
+    SwarmCoordinator, SwarmConfig, SwarmState, DelegationPlan, DelegationIssue, CandidateRejection,
+};
 pub use protocol::{AgentMessage, AgentProtocol, ProtocolVersion, MessageType};
 pub use claude_agent::{ClaudeAgent, ClaudeAgentConfig, ClaudeContext};
 pub use verification::{SwarmVerifier, VerificationPolicy, VerificationReport};
-pub use trust::{TrustManager, TrustScore, TrustUpdate, BackupConfig};
-pub use persistence::{TrustStore, SqliteTrustStore, FileTrustStore, InMemoryTrustStore, StorageHealth};
-pub use execution::{ExecutionEngine, ExecutionConfig, ExecutionResult};
+pub use trust::{TrustManager, TrustScore, TrustUpdate, BackupConfig, CompactionConfig};
+pub use persistence::{TrustStore, SqliteTrustStore, FileTrustStore, InMemoryTrustStore, StorageHealth, TrustSnapshot, CompactionStats};
+pub use execution::{ExecutionEngine, ExecutionConfig, ExecutionResult, WorkspaceIsolation};
 pub use monitoring::{
     MetricsCollector, PrometheusExporter, DashboardProvider, MonitoringConfig,
     AlertThresholds, Alert, AlertSeverity, DashboardMetrics, AgentMetrics,
-    PerformanceTrends, HealthStatus, HealthLevel, ComponentHealth,
+    PerformanceTrends, HealthStatus, HealthLevel, ComponentHealth, Metric, DataPoint,
+    GrafanaDatasource, GrafanaTimeSeries, GrafanaAnnotation,
+};
+pub use alerting::{
+    AlertManager, AlertRoutingConfig, AlertFingerprint, ManagedAlert, AlertChannel,
+    AlertNotification, NotificationKind, LogChannel,
 };
 pub use fault_tolerance::{
     FaultToleranceManager, FaultToleranceConfig, AgentHealthStatus, AgentHeartbeat,
     CircuitBreakerState, CircuitBreakerStatus, TaskCheckpoint, RecoveryAction,
-    RecoveryStatistics,
+    RecoveryStatistics, StalledTask, TaskReassignment,
 };
+pub use dead_letter::{DeadLetterQueue, DeadLetterTask, RetryDecision};
+pub use event_bus::{AgentJoined, BusEvent, CircuitOpened, SwarmEventBus, TaskCompleted, TrustChanged};
 pub use consensus::{
     ConsensusProtocol, PBFTConsensus, VotingRound, QuorumCertificate,
     ConsensusMessage, ConsensusProposal, ConsensusResult, ConsensusStats,
+    WeightedQuorumConfig,
 };
 pub use recovery::{
     RecoveryStrategy, RecoveryManager, RecoveryContext, RecoveryResult,
     ExponentialBackoffStrategy, CheckpointRecoveryStrategy, GracefulDegradationStrategy,
-    SelfHealingStrategy,
+    SelfHealingStrategy, DegradationLevel, DegradationPolicy, DegradationStage, DegradationTransition,
 };
+pub use trace::{SpanKind, TRACE_TARGET};
 pub use types::*;
 pub use error::{SwarmError, SwarmResult};
 
@@ -87,7 +103,7 @@ pub const DEFAULT_TRUST_SCORE: f64 = 0.5;
 /// Prelude for convenient imports
 pub mod prelude {
     pub use crate::{
-        SwarmCoordinator, SwarmConfig, SwarmState,
+        SwarmCoordinator, SwarmConfig, SwarmState, DelegationPlan, DelegationIssue,
         AgentMessage, AgentProtocol,
         ClaudeAgent, ClaudeAgentConfig,
         SwarmVerifier, VerificationPolicy,
@@ -96,7 +112,9 @@ pub mod prelude {
         ExecutionEngine, ExecutionConfig, ExecutionResult,
         MetricsCollector, PrometheusExporter, DashboardProvider, MonitoringConfig,
         FaultToleranceManager, FaultToleranceConfig, AgentHealthStatus,
-        CircuitBreakerState, TaskCheckpoint, RecoveryStatistics,
+        CircuitBreakerState, TaskCheckpoint, TaskReassignment, RecoveryStatistics,
+        DeadLetterTask, SwarmEventBus, BusEvent,
+        SpanKind,
         SwarmError, SwarmResult,
     };
     
@@ -46,6 +46,8 @@ pub struct MonitoringConfig {
     pub alert_thresholds: AlertThresholds,
     /// Enable real-time dashboard
     pub enable_dashboard: bool,
+    /// How long metric samples are kept for historical queries
+    pub history_retention: Duration,
 }
 
 impl Default for MonitoringConfig {
@@ -57,6 +59,7 @@ impl Default for MonitoringConfig {
             max_events: 10000,
             alert_thresholds: AlertThresholds::default(),
             enable_dashboard: true,
+            history_retention: Duration::from_secs(24 * 60 * 60),
         }
     }
 }
@@ -91,8 +94,8 @@ impl Default for AlertThresholds {
     }
 }
 
-/// Alert severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Alert severity levels, from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum AlertSeverity {
     Info,
     Warning,
@@ -100,6 +103,17 @@ pub enum AlertSeverity {
     Emergency,
 }
 
+impl AlertSeverity {
+    /// The next higher severity, or this one if it is already the highest
+    pub fn escalate(self) -> Self {
+        match self {
+            AlertSeverity::Info => AlertSeverity::Warning,
+            AlertSeverity::Warning => AlertSeverity::Critical,
+            AlertSeverity::Critical | AlertSeverity::Emergency => AlertSeverity::Emergency,
+        }
+    }
+}
+
 /// Alert information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
@@ -149,6 +163,195 @@ pub struct PerformanceTrends {
     pub promise_fulfillment_rate: VecDeque<(DateTime<Utc>, f64)>,
 }
 
+/// A swarm or per-agent metric that can be queried over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Metric {
+    TotalAgents,
+    ActiveAgents,
+    TasksAssigned,
+    TasksSucceeded,
+    TasksFailed,
+    AvgTaskDurationMs,
+    AvgTrustScore,
+    VerificationSuccessRate,
+    PromiseFulfillmentRate,
+    AgentTrustScore(AgentId),
+    AgentTasksCompleted(AgentId),
+    AgentTasksFailed(AgentId),
+    AgentAvgExecutionTimeMs(AgentId),
+}
+
+impl Metric {
+    /// Swarm-wide metrics
+    pub const SWARM: [Metric; 9] = [
+        Metric::TotalAgents,
+        Metric::ActiveAgents,
+        Metric::TasksAssigned,
+        Metric::TasksSucceeded,
+        Metric::TasksFailed,
+        Metric::AvgTaskDurationMs,
+        Metric::AvgTrustScore,
+        Metric::VerificationSuccessRate,
+        Metric::PromiseFulfillmentRate,
+    ];
+
+    /// Metrics tracked for each agent
+    pub fn for_agent(agent_id: AgentId) -> [Metric; 4] {
+        [
+            Metric::AgentTrustScore(agent_id),
+            Metric::AgentTasksCompleted(agent_id),
+            Metric::AgentTasksFailed(agent_id),
+            Metric::AgentAvgExecutionTimeMs(agent_id),
+        ]
+    }
+
+    /// Stable name, e.g. `swarm.avg_trust_score` or `agent.<id>.trust_score`
+    pub fn name(&self) -> String {
+        let (scope, field) = match self {
+            Metric::TotalAgents => (None, "total_agents"),
+            Metric::ActiveAgents => (None, "active_agents"),
+            Metric::TasksAssigned => (None, "tasks_assigned"),
+            Metric::TasksSucceeded => (None, "tasks_succeeded"),
+            Metric::TasksFailed => (None, "tasks_failed"),
+            Metric::AvgTaskDurationMs => (None, "avg_task_duration_ms"),
+            Metric::AvgTrustScore => (None, "avg_trust_score"),
+            Metric::VerificationSuccessRate => (None, "verification_success_rate"),
+            Metric::PromiseFulfillmentRate => (None, "promise_fulfillment_rate"),
+            Metric::AgentTrustScore(id) => (Some(id), "trust_score"),
+            Metric::AgentTasksCompleted(id) => (Some(id), "tasks_completed"),
+            Metric::AgentTasksFailed(id) => (Some(id), "tasks_failed"),
+            Metric::AgentAvgExecutionTimeMs(id) => (Some(id), "avg_execution_time_ms"),
+        };
+        match scope {
+            Some(agent_id) => format!("agent.{}.{}", agent_id, field),
+            None => format!("swarm.{}", field),
+        }
+    }
+
+    /// Parse a name produced by [`name`](Self::name)
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.starts_with("swarm.") {
+            return Self::SWARM.into_iter().find(|metric| metric.name() == name);
+        }
+        let (agent_id, _) = name.strip_prefix("agent.")?.split_once('.')?;
+        let agent_id = agent_id.parse().ok()?;
+        Self::for_agent(agent_id).into_iter().find(|metric| metric.name() == name)
+    }
+
+    /// The metric's value in a sample, if the sample has one
+    fn value(&self, sample: &MetricSample) -> Option<f64> {
+        let swarm = &sample.swarm_metrics;
+        let agent = |id: &AgentId| sample.agent_metrics.get(id);
+        match self {
+            Metric::TotalAgents => Some(swarm.total_agents as f64),
+            Metric::ActiveAgents => Some(swarm.active_agents as f64),
+            Metric::TasksAssigned => Some(swarm.tasks_assigned as f64),
+            Metric::TasksSucceeded => Some(swarm.tasks_succeeded as f64),
+            Metric::TasksFailed => Some(swarm.tasks_failed as f64),
+            Metric::AvgTaskDurationMs => Some(swarm.avg_task_duration_ms),
+            Metric::AvgTrustScore => Some(swarm.avg_trust_score),
+            Metric::VerificationSuccessRate => Some(swarm.verification_success_rate),
+            Metric::PromiseFulfillmentRate => (swarm.promises_made > 0)
+                .then(|| swarm.promises_fulfilled as f64 / swarm.promises_made as f64),
+            Metric::AgentTrustScore(id) => agent(id).map(|m| m.trust_score.value),
+            Metric::AgentTasksCompleted(id) => agent(id).map(|m| m.tasks_completed as f64),
+            Metric::AgentTasksFailed(id) => agent(id).map(|m| m.tasks_failed as f64),
+            Metric::AgentAvgExecutionTimeMs(id) => agent(id).map(|m| m.avg_execution_time_ms),
+        }
+    }
+}
+
+/// A metric's value at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DataPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// Metrics retained for historical queries
+#[derive(Debug, Clone)]
+struct MetricSample {
+    timestamp: DateTime<Utc>,
+    swarm_metrics: SwarmMetrics,
+    agent_metrics: HashMap<AgentId, AgentMetrics>,
+}
+
+/// Start of the `resolution`-wide bucket, aligned to `start`, that holds `timestamp`
+fn bucket_start(start: DateTime<Utc>, timestamp: DateTime<Utc>, resolution: chrono::Duration) -> DateTime<Utc> {
+    match ((timestamp - start).num_nanoseconds(), resolution.num_nanoseconds()) {
+        (_, Some(0)) => timestamp,
+        (Some(elapsed), Some(width)) => start + chrono::Duration::nanoseconds(elapsed / width * width),
+        // Wider than the whole range
+        _ => start,
+    }
+}
+
+/// Time range of a Grafana request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrafanaRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// Body of a Grafana `/search` request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GrafanaSearchRequest {
+    /// Text the metric names must contain
+    #[serde(default)]
+    pub target: String,
+}
+
+/// A series requested by a Grafana panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrafanaTarget {
+    pub target: String,
+    #[serde(default)]
+    pub ref_id: Option<String>,
+}
+
+/// Body of a Grafana `/query` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrafanaQueryRequest {
+    pub range: GrafanaRange,
+    /// Requested spacing between datapoints
+    #[serde(default)]
+    pub interval_ms: Option<u64>,
+    /// Used for the spacing when `interval_ms` is absent
+    #[serde(default)]
+    pub max_data_points: Option<u64>,
+    pub targets: Vec<GrafanaTarget>,
+}
+
+/// A series in a Grafana `/query` response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrafanaTimeSeries {
+    pub target: String,
+    /// `[value, unix milliseconds]` pairs
+    pub datapoints: Vec<(f64, i64)>,
+}
+
+/// Body of a Grafana `/annotations` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrafanaAnnotationRequest {
+    pub range: GrafanaRange,
+    /// The annotation definition, echoed back in the response. Its `query`
+    /// selects `events`, `alerts`, or both when empty.
+    pub annotation: serde_json::Value,
+}
+
+/// An annotation in a Grafana `/annotations` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrafanaAnnotation {
+    pub annotation: serde_json::Value,
+    /// Unix milliseconds
+    pub time: i64,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub text: String,
+}
+
 /// Health status of the swarm
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthStatus {
@@ -166,8 +369,8 @@ pub struct ComponentHealth {
     pub last_check: DateTime<Utc>,
 }
 
-/// Health level enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Health level enum, ordered from healthiest to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HealthLevel {
     Healthy,
     Degraded,
@@ -193,6 +396,9 @@ pub struct MetricsCollector {
     // Performance trends
     trends: Arc<RwLock<PerformanceTrends>>,
     
+    // Metric samples in timestamp order
+    history: Arc<RwLock<VecDeque<MetricSample>>>,
+    
     // Atomic counters for high-frequency metrics
     task_counter: AtomicU64,
     success_counter: AtomicU64,
@@ -238,6 +444,7 @@ impl MetricsCollector {
                 verification_success_rate: VecDeque::new(),
                 promise_fulfillment_rate: VecDeque::new(),
             })),
+            history: Arc::new(RwLock::new(VecDeque::new())),
             task_counter: AtomicU64::new(0),
             success_counter: AtomicU64::new(0),
             failure_counter: AtomicU64::new(0),
@@ -445,6 +652,8 @@ impl MetricsCollector {
                 resolved: false,
                 resolution_time: None,
             }).await;
+        } else {
+            self.resolve_alert("Low Trust Score Alert", Some(update.agent_id)).await;
         }
         
         // Update trends
@@ -482,6 +691,16 @@ impl MetricsCollector {
                 }
             },
             
+            SwarmEvent::TaskCompleted { success: true, agent_id, .. } => {
+                let failure_rate = self.agent_metrics.get(agent_id).map(|metrics| {
+                    let total_tasks = metrics.tasks_completed + metrics.tasks_failed;
+                    metrics.tasks_failed as f64 / total_tasks as f64
+                });
+                if failure_rate.is_some_and(|rate| rate <= self.config.alert_thresholds.max_failure_rate) {
+                    self.resolve_alert("High Task Failure Rate", Some(*agent_id)).await;
+                }
+            },
+            
             SwarmEvent::PromiseBroken { agent_id, reason, .. } => {
                 self.trigger_alert(Alert {
                     id: Uuid::new_v4(),
@@ -520,6 +739,29 @@ impl MetricsCollector {
         }
     }
 
+    /// Resolve active alerts whose condition has cleared
+    ///
+    /// The resolved alert is broadcast so subscribers see the condition clear.
+    async fn resolve_alert(&self, title: &str, agent_id: Option<AgentId>) {
+        let now = Utc::now();
+        let mut cleared = None;
+        {
+            let mut alerts = self.alerts.write().await;
+            for alert in alerts.iter_mut().filter(|a| !a.resolved && a.title == title && a.agent_id == agent_id) {
+                alert.resolved = true;
+                alert.resolution_time = Some(now);
+                cleared = Some(alert.clone());
+            }
+        }
+        
+        if let Some(alert) = cleared {
+            info!("Resolved {}: condition cleared", alert.title);
+            if let Err(e) = self.alert_sender.send(alert) {
+                debug!("No subscribers for alert resolution: {}", e);
+            }
+        }
+    }
+
     /// Get current dashboard metrics
     pub async fn get_dashboard_metrics(&self) -> DashboardMetrics {
         let events = self.events.read().await;
@@ -564,6 +806,69 @@ impl MetricsCollector {
         }
     }
 
+    /// Retain a snapshot of the metrics for [`query_range`](Self::query_range)
+    ///
+    /// Samples are kept in timestamp order, and samples older than
+    /// `history_retention` before the newest one are dropped.
+    pub async fn record_sample(&self, metrics: &DashboardMetrics) {
+        let sample = MetricSample {
+            timestamp: metrics.timestamp,
+            swarm_metrics: metrics.swarm_metrics.clone(),
+            agent_metrics: metrics.agent_metrics.clone(),
+        };
+        
+        let mut history = self.history.write().await;
+        let position = history.partition_point(|s| s.timestamp <= sample.timestamp);
+        history.insert(position, sample);
+        
+        let retention = chrono::Duration::from_std(self.config.history_retention)
+            .unwrap_or(chrono::Duration::MAX);
+        let cutoff = history.back().and_then(|newest| newest.timestamp.checked_sub_signed(retention));
+        if let Some(cutoff) = cutoff {
+            while history.front().is_some_and(|s| s.timestamp < cutoff) {
+                history.pop_front();
+            }
+        }
+    }
+
+    /// Query a metric over `[start, end)`, downsampled to `resolution`
+    ///
+    /// Buckets are aligned to `start` and each point is the mean of the
+    /// samples in its bucket, timestamped at the bucket's start. Buckets
+    /// without samples are omitted, and a zero resolution returns every
+    /// sample as recorded.
+    pub async fn query_range(
+        &self,
+        metric: &Metric,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        resolution: Duration,
+    ) -> Vec<DataPoint> {
+        let resolution = chrono::Duration::from_std(resolution).unwrap_or(chrono::Duration::MAX);
+        let history = self.history.read().await;
+        
+        // Samples are ordered, so each bucket's samples are contiguous
+        let mut buckets: Vec<(DateTime<Utc>, f64, usize)> = Vec::new();
+        for sample in history.iter().filter(|s| s.timestamp >= start && s.timestamp < end) {
+            let Some(value) = metric.value(sample) else {
+                continue;
+            };
+            let bucket = bucket_start(start, sample.timestamp, resolution);
+            match buckets.last_mut() {
+                Some((timestamp, sum, count)) if *timestamp == bucket => {
+                    *sum += value;
+                    *count += 1;
+                },
+                _ => buckets.push((bucket, value, 1)),
+            }
+        }
+        
+        buckets
+            .into_iter()
+            .map(|(timestamp, sum, count)| DataPoint { timestamp, value: sum / count as f64 })
+            .collect()
+    }
+
     /// Get health status
     pub async fn get_health_status(&self) -> HealthStatus {
         let mut components = HashMap::new();
@@ -703,6 +1008,9 @@ impl MetricsCollector {
             }
         }
         
+        let snapshot = self.get_dashboard_metrics().await;
+        self.record_sample(&snapshot).await;
+        
         debug!("Collected periodic metrics");
     }
 }
@@ -719,19 +1027,36 @@ impl MetricsCollector {
         
         let addr: SocketAddr = ([0, 0, 0, 0], self.config.prometheus_port).into();
         
+        let collector = Arc::new(self);
         let make_svc = make_service_fn(move |_conn| {
             let handle = handle.clone();
+            let collector = Arc::clone(&collector);
             async move {
                 Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
                     let handle = handle.clone();
+                    let collector = Arc::clone(&collector);
                     async move {
-                        if req.uri().path() == "/metrics" {
-                            let metrics = handle.render();
-                            Ok(Response::new(Body::from(metrics)))
-                        } else {
-                            let mut not_found = Response::new(Body::from("Not Found"));
-                            *not_found.status_mut() = StatusCode::NOT_FOUND;
-                            Ok(not_found)
+                        let path = req.uri().path().to_string();
+                        match path.as_str() {
+                            "/metrics" => {
+                                let metrics = handle.render();
+                                Ok(Response::new(Body::from(metrics)))
+                            },
+                            // Grafana JSON datasource
+                            "/" | "/search" | "/query" | "/annotations" => {
+                                let method = req.method().to_string();
+                                let body = hyper::body::to_bytes(req.into_body()).await?;
+                                let (status, json) = handle_grafana_request(&collector, &method, &path, &body).await;
+                                let mut response = Response::new(Body::from(json));
+                                *response.status_mut() = StatusCode::from_u16(status)
+                                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                                Ok(response)
+                            },
+                            _ => {
+                                let mut not_found = Response::new(Body::from("Not Found"));
+                                *not_found.status_mut() = StatusCode::NOT_FOUND;
+                                Ok(not_found)
+                            }
                         }
                     }
                 }))
@@ -746,7 +1071,7 @@ impl MetricsCollector {
             }
         });
         
-        info!("Prometheus metrics server started on {}", addr);
+        info!("Prometheus metrics and Grafana datasource server started on {}", addr);
         Ok(())
     }
 
@@ -902,8 +1227,201 @@ impl DashboardProvider {
         }
     }
 
+    /// Get a metric's history for trend charts
+    pub async fn get_metric_history(
+        &self,
+        metric: &Metric,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        resolution: Duration,
+    ) -> Vec<DataPoint> {
+        self.collector.query_range(metric, start, end, resolution).await
+    }
+
     /// Subscribe to real-time updates
     pub fn subscribe_to_updates(&self) -> broadcast::Receiver<Alert> {
         self.collector.subscribe_alerts()
     }
-}
\ No newline at end of file
+}
+
+/// Grafana JSON datasource over the collector's metric history, events and alerts
+///
+/// Implements the SimpleJSON protocol so swarm metrics can be charted and
+/// events shown as annotations without Prometheus in between. The monitoring
+/// server serves it at `/`, `/search`, `/query` and `/annotations` next to
+/// the Prometheus `/metrics` endpoint.
+pub struct GrafanaDatasource {
+    collector: Arc<MetricsCollector>,
+}
+
+impl GrafanaDatasource {
+    /// Create a new Grafana datasource
+    pub fn new(collector: Arc<MetricsCollector>) -> Self {
+        Self { collector }
+    }
+
+    /// Names of the available metrics containing the search target
+    pub async fn search(&self, request: &GrafanaSearchRequest) -> Vec<String> {
+        grafana_search(&self.collector, request)
+    }
+
+    /// Time series for each requested metric
+    pub async fn query(&self, request: &GrafanaQueryRequest) -> SwarmResult<Vec<GrafanaTimeSeries>> {
+        grafana_query(&self.collector, request).await
+    }
+
+    /// Events and alerts in the requested range
+    pub async fn annotations(&self, request: &GrafanaAnnotationRequest) -> Vec<GrafanaAnnotation> {
+        grafana_annotations(&self.collector, request).await
+    }
+
+    /// Handle a datasource HTTP request, returning the status code and JSON body
+    pub async fn handle(&self, method: &str, path: &str, body: &[u8]) -> (u16, String) {
+        handle_grafana_request(&self.collector, method, path, body).await
+    }
+}
+
+fn grafana_search(collector: &MetricsCollector, request: &GrafanaSearchRequest) -> Vec<String> {
+    let mut agents: Vec<AgentId> = collector.agent_metrics.iter().map(|entry| *entry.key()).collect();
+    agents.sort();
+    
+    Metric::SWARM
+        .into_iter()
+        .chain(agents.into_iter().flat_map(Metric::for_agent))
+        .map(|metric| metric.name())
+        .filter(|name| name.contains(&request.target))
+        .collect()
+}
+
+async fn grafana_query(collector: &MetricsCollector, request: &GrafanaQueryRequest) -> SwarmResult<Vec<GrafanaTimeSeries>> {
+    let range = &request.range;
+    let resolution = match (request.interval_ms, request.max_data_points) {
+        (Some(interval), _) => Duration::from_millis(interval),
+        (None, Some(points)) if points > 0 => {
+            let span = (range.to - range.from).to_std().unwrap_or_default();
+            span / points.min(u32::MAX as u64) as u32
+        },
+        _ => Duration::ZERO,
+    };
+    
+    let mut series = Vec::with_capacity(request.targets.len());
+    for target in &request.targets {
+        let metric = Metric::from_name(&target.target).ok_or_else(|| {
+            SwarmError::MonitoringError(format!("Unknown metric '{}'", target.target))
+        })?;
+        let datapoints = collector
+            .query_range(&metric, range.from, range.to, resolution)
+            .await
+            .into_iter()
+            .map(|point| (point.value, point.timestamp.timestamp_millis()))
+            .collect();
+        series.push(GrafanaTimeSeries { target: target.target.clone(), datapoints });
+    }
+    Ok(series)
+}
+
+async fn grafana_annotations(collector: &MetricsCollector, request: &GrafanaAnnotationRequest) -> Vec<GrafanaAnnotation> {
+    let range = &request.range;
+    let query = request.annotation.get("query").and_then(|q| q.as_str()).unwrap_or("");
+    let in_range = |timestamp: &DateTime<Utc>| *timestamp >= range.from && *timestamp <= range.to;
+    let annotation = |time: &DateTime<Utc>, title: String, tags: Vec<String>, text: String| GrafanaAnnotation {
+        annotation: request.annotation.clone(),
+        time: time.timestamp_millis(),
+        title,
+        tags,
+        text,
+    };
+    
+    let mut annotations = Vec::new();
+    if query.is_empty() || query == "events" {
+        let events = collector.events.read().await;
+        for event in events.iter() {
+            let (timestamp, title, text) = describe_event(event);
+            if in_range(timestamp) {
+                annotations.push(annotation(timestamp, title.to_string(), vec!["event".to_string()], text));
+            }
+        }
+    }
+    if query.is_empty() || query == "alerts" {
+        let alerts = collector.alerts.read().await;
+        for alert in alerts.iter().filter(|alert| in_range(&alert.timestamp)) {
+            let tags = vec!["alert".to_string(), format!("{:?}", alert.severity).to_lowercase()];
+            annotations.push(annotation(&alert.timestamp, alert.title.clone(), tags, alert.description.clone()));
+        }
+    }
+    annotations.sort_by_key(|annotation| annotation.time);
+    annotations
+}
+
+/// Timestamp, title and description of an event
+fn describe_event(event: &SwarmEvent) -> (&DateTime<Utc>, &'static str, String) {
+    match event {
+        SwarmEvent::AgentJoined { agent_id, role, timestamp } => {
+            (timestamp, "Agent joined", format!("Agent {} joined as {:?}", agent_id, role))
+        },
+        SwarmEvent::AgentLeft { agent_id, reason, timestamp } => {
+            (timestamp, "Agent left", format!("Agent {} left: {}", agent_id, reason))
+        },
+        SwarmEvent::TaskAssigned { task_id, agent_id, timestamp } => {
+            (timestamp, "Task assigned", format!("Task {} assigned to agent {}", task_id, agent_id))
+        },
+        SwarmEvent::TaskCompleted { task_id, agent_id, success, timestamp } => {
+            let outcome = if *success { "completed" } else { "failed" };
+            (timestamp, "Task completed", format!("Task {} {} by agent {}", task_id, outcome, agent_id))
+        },
+        SwarmEvent::PromiseMade { agent_id, promise_id, timestamp } => {
+            (timestamp, "Promise made", format!("Agent {} made promise {}", agent_id, promise_id))
+        },
+        SwarmEvent::PromiseFulfilled { agent_id, promise_id, timestamp } => {
+            (timestamp, "Promise fulfilled", format!("Agent {} fulfilled promise {}", agent_id, promise_id))
+        },
+        SwarmEvent::PromiseBroken { agent_id, promise_id, reason, timestamp } => {
+            (timestamp, "Promise broken", format!("Agent {} broke promise {}: {}", agent_id, promise_id, reason))
+        },
+        SwarmEvent::VerificationCompleted { task_id, verified, timestamp } => {
+            let outcome = if *verified { "verified" } else { "failed verification" };
+            (timestamp, "Verification completed", format!("Task {} {}", task_id, outcome))
+        },
+        SwarmEvent::TrustUpdated { agent_id, old_score, new_score, timestamp } => {
+            (timestamp, "Trust updated", format!("Agent {} trust {:.3} -> {:.3}", agent_id, old_score, new_score))
+        },
+        SwarmEvent::ConsensusReached { topic, participants, timestamp } => {
+            (timestamp, "Consensus reached", format!("{} agents agreed on {}", participants.len(), topic))
+        },
+    }
+}
+
+/// Route a Grafana JSON datasource request
+async fn handle_grafana_request(collector: &MetricsCollector, method: &str, path: &str, body: &[u8]) -> (u16, String) {
+    fn parse<T: serde::de::DeserializeOwned + Default>(body: &[u8]) -> Result<T, serde_json::Error> {
+        if body.is_empty() { Ok(T::default()) } else { serde_json::from_slice(body) }
+    }
+    fn json<T: Serialize>(status: u16, value: &T) -> (u16, String) {
+        (status, serde_json::to_string(value).unwrap_or_else(|_| "null".to_string()))
+    }
+    fn bad_request(message: impl std::fmt::Display) -> (u16, String) {
+        json(400, &serde_json::json!({ "error": message.to_string() }))
+    }
+    
+    match (method, path) {
+        // Grafana tests the connection with a request to the root
+        ("GET", "/") => json(200, &serde_json::json!({ "status": "ok" })),
+        ("POST", "/search") => match parse::<GrafanaSearchRequest>(body) {
+            Ok(request) => json(200, &grafana_search(collector, &request)),
+            Err(e) => bad_request(e),
+        },
+        ("POST", "/query") => match serde_json::from_slice::<GrafanaQueryRequest>(body) {
+            Ok(request) => match grafana_query(collector, &request).await {
+                Ok(series) => json(200, &series),
+                Err(e) => bad_request(e),
+            },
+            Err(e) => bad_request(e),
+        },
+        ("POST", "/annotations") => match serde_json::from_slice::<GrafanaAnnotationRequest>(body) {
+            Ok(request) => json(200, &grafana_annotations(collector, &request).await),
+            Err(e) => bad_request(e),
+        },
+        (_, "/" | "/search" | "/query" | "/annotations") => json(405, &serde_json::json!({ "error": "Method Not Allowed" })),
+        _ => json(404, &serde_json::json!({ "error": "Not Found" })),
+    }
+}
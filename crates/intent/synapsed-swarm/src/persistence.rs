@@ -8,7 +8,7 @@
 use crate::{
     error::{SwarmError, SwarmResult}, 
     trust::{TrustScore, TrustUpdate, TrustUpdateReason},
-    types::AgentId
+    types::{AgentId, AgentStatus}
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -84,6 +84,33 @@ pub trait TrustStore: Send + Sync {
 
     /// Cleanup old data (for maintenance)
     async fn cleanup_old_data(&self, older_than: DateTime<Utc>) -> SwarmResult<usize>;
+
+    /// Snapshot current trust scores and truncate update history older than `before`
+    ///
+    /// The snapshot replaces any previous one, so storage stays bounded while
+    /// current state remains restorable. `before` is clamped to the present.
+    async fn compact(&self, before: DateTime<Utc>) -> SwarmResult<CompactionStats> {
+        self.compact_with_agent_states(before, HashMap::new()).await
+    }
+
+    /// Compact like [`compact`](Self::compact), recording agent states in the snapshot
+    ///
+    /// The default keeps no snapshot and only truncates history, which leaves
+    /// current trust scores in place.
+    async fn compact_with_agent_states(
+        &self,
+        before: DateTime<Utc>,
+        agent_states: HashMap<AgentId, AgentStatus>,
+    ) -> SwarmResult<CompactionStats> {
+        let snapshot = TrustSnapshot::new(self.get_all_trust_scores().await?, agent_states, before.min(Utc::now()));
+        let removed = self.cleanup_old_data(snapshot.compacted_before).await?;
+        Ok(CompactionStats::new(&snapshot, removed))
+    }
+
+    /// Get the snapshot taken by the most recent compaction
+    async fn latest_snapshot(&self) -> SwarmResult<Option<TrustSnapshot>> {
+        Ok(None)
+    }
 }
 
 /// Transaction trait for atomic operations
@@ -113,6 +140,52 @@ pub struct StorageHealth {
     pub storage_size_bytes: Option<u64>,
 }
 
+/// Point-in-time snapshot of trust state taken during compaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub compacted_before: DateTime<Utc>,
+    pub scores: HashMap<AgentId, TrustScore>,
+    #[serde(default)]
+    pub agent_states: HashMap<AgentId, AgentStatus>,
+}
+
+impl TrustSnapshot {
+    /// Create a snapshot of the given scores and agent states
+    fn new(
+        scores: HashMap<AgentId, TrustScore>,
+        agent_states: HashMap<AgentId, AgentStatus>,
+        compacted_before: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            taken_at: Utc::now(),
+            compacted_before,
+            scores,
+            agent_states,
+        }
+    }
+}
+
+/// Result of a compaction run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionStats {
+    pub snapshot_at: DateTime<Utc>,
+    pub compacted_before: DateTime<Utc>,
+    pub agents_snapshotted: usize,
+    pub updates_removed: usize,
+}
+
+impl CompactionStats {
+    fn new(snapshot: &TrustSnapshot, updates_removed: usize) -> Self {
+        Self {
+            snapshot_at: snapshot.taken_at,
+            compacted_before: snapshot.compacted_before,
+            agents_snapshotted: snapshot.scores.len(),
+            updates_removed,
+        }
+    }
+}
+
 /// SQLite implementation of TrustStore
 pub struct SqliteTrustStore {
     connection: Arc<AsyncConnection>,
@@ -215,6 +288,17 @@ impl SqliteTrustStore {
                 [],
             )?;
 
+            // Create trust_snapshots table (holds only the latest snapshot)
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS trust_snapshots (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    taken_at TEXT NOT NULL,
+                    compacted_before TEXT NOT NULL,
+                    data TEXT NOT NULL
+                )",
+                [],
+            )?;
+
             // Insert current schema version if not exists
             conn.execute(
                 "INSERT OR IGNORE INTO schema_info (version, created_at, description) 
@@ -763,6 +847,59 @@ impl TrustStore for SqliteTrustStore {
             SwarmError::StorageError(format!("Failed to cleanup old data: {}", e))
         })
     }
+
+    async fn compact_with_agent_states(
+        &self,
+        before: DateTime<Utc>,
+        agent_states: HashMap<AgentId, AgentStatus>,
+    ) -> SwarmResult<CompactionStats> {
+        let snapshot = TrustSnapshot::new(self.get_all_trust_scores().await?, agent_states, before.min(Utc::now()));
+        let snapshot_json = serde_json::to_string(&snapshot).map_err(|e| {
+            SwarmError::StorageError(format!("Failed to serialize snapshot: {}", e))
+        })?;
+        let taken_at = snapshot.taken_at.to_rfc3339();
+        let cutoff_time = snapshot.compacted_before.to_rfc3339();
+
+        // Write the snapshot and truncate history atomically
+        let removed = self.connection.call(move |conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT OR REPLACE INTO trust_snapshots (id, taken_at, compacted_before, data)
+                 VALUES (1, ?1, ?2, ?3)",
+                params![taken_at, cutoff_time, snapshot_json],
+            )?;
+            let removed = tx.execute(
+                "DELETE FROM trust_updates WHERE timestamp < ?1",
+                params![cutoff_time]
+            )?;
+            tx.commit()?;
+            Ok::<usize, rusqlite::Error>(removed)
+        }).await.map_err(|e| {
+            SwarmError::StorageError(format!("Failed to compact trust store: {}", e))
+        })?;
+
+        info!("Compacted trust store: snapshotted {} agents, removed {} updates", snapshot.scores.len(), removed);
+        Ok(CompactionStats::new(&snapshot, removed))
+    }
+
+    async fn latest_snapshot(&self) -> SwarmResult<Option<TrustSnapshot>> {
+        let snapshot_json = self.connection.call(|conn| {
+            let data: Option<String> = conn.query_row(
+                "SELECT data FROM trust_snapshots WHERE id = 1",
+                [],
+                |row| row.get(0)
+            ).optional()?;
+            Ok::<Option<String>, rusqlite::Error>(data)
+        }).await.map_err(|e| {
+            SwarmError::StorageError(format!("Failed to load snapshot: {}", e))
+        })?;
+
+        snapshot_json
+            .map(|json| serde_json::from_str(&json).map_err(|e| {
+                SwarmError::StorageError(format!("Failed to parse snapshot: {}", e))
+            }))
+            .transpose()
+    }
 }
 
 impl SqliteTrustStore {
@@ -953,6 +1090,7 @@ impl TrustTransaction for SqliteTransaction {
 pub struct FileTrustStore {
     scores_file: PathBuf,
     updates_file: PathBuf,
+    snapshot_file: PathBuf,
     backup_dir: PathBuf,
     data_lock: Arc<RwLock<()>>,
 }
@@ -975,6 +1113,7 @@ impl FileTrustStore {
         Ok(Self {
             scores_file: data_dir.join("trust_scores.json"),
             updates_file: data_dir.join("trust_updates.json"),
+            snapshot_file: data_dir.join("trust_snapshot.json"),
             backup_dir,
             data_lock: Arc::new(RwLock::new(())),
         })
@@ -1091,6 +1230,48 @@ impl FileTrustStore {
         
         Ok(())
     }
+
+    /// Load the compaction snapshot from file
+    async fn load_snapshot(&self) -> SwarmResult<Option<TrustSnapshot>> {
+        let _lock = self.data_lock.read().await;
+        
+        if !self.snapshot_file.exists() {
+            return Ok(None);
+        }
+        
+        let file = File::open(&self.snapshot_file).map_err(|e| {
+            SwarmError::StorageError(format!("Failed to open snapshot file: {}", e))
+        })?;
+        
+        let reader = BufReader::new(file);
+        let snapshot = serde_json::from_reader(reader).map_err(|e| {
+            SwarmError::StorageError(format!("Failed to parse snapshot file: {}", e))
+        })?;
+        
+        Ok(Some(snapshot))
+    }
+
+    /// Save the compaction snapshot to file
+    async fn save_snapshot(&self, snapshot: &TrustSnapshot) -> SwarmResult<()> {
+        let _lock = self.data_lock.write().await;
+        
+        // Write to temporary file first, then rename for atomicity
+        let temp_file = self.snapshot_file.with_extension("json.tmp");
+        let file = File::create(&temp_file).map_err(|e| {
+            SwarmError::StorageError(format!("Failed to create temp snapshot file: {}", e))
+        })?;
+        
+        let writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, snapshot).map_err(|e| {
+            SwarmError::StorageError(format!("Failed to write snapshot file: {}", e))
+        })?;
+        
+        fs::rename(&temp_file, &self.snapshot_file).map_err(|e| {
+            SwarmError::StorageError(format!("Failed to rename snapshot file: {}", e))
+        })?;
+        
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -1276,6 +1457,25 @@ impl TrustStore for FileTrustStore {
         self.save_updates(&updates).await?;
         Ok(total_removed)
     }
+
+    async fn compact_with_agent_states(
+        &self,
+        before: DateTime<Utc>,
+        agent_states: HashMap<AgentId, AgentStatus>,
+    ) -> SwarmResult<CompactionStats> {
+        let snapshot = TrustSnapshot::new(self.load_scores().await?, agent_states, before.min(Utc::now()));
+        
+        // Persist the snapshot before dropping any history it supersedes
+        self.save_snapshot(&snapshot).await?;
+        let removed = self.cleanup_old_data(snapshot.compacted_before).await?;
+        
+        info!("Compacted file trust store: snapshotted {} agents, removed {} updates", snapshot.scores.len(), removed);
+        Ok(CompactionStats::new(&snapshot, removed))
+    }
+
+    async fn latest_snapshot(&self) -> SwarmResult<Option<TrustSnapshot>> {
+        self.load_snapshot().await
+    }
 }
 
 /// Transaction implementation for file store
@@ -1345,6 +1545,7 @@ pub struct InMemoryTrustStore {
     scores: Arc<DashMap<AgentId, TrustScore>>,
     updates: Arc<DashMap<AgentId, Vec<TrustUpdate>>>,
     schema_version: Arc<Mutex<i32>>,
+    snapshot: Arc<Mutex<Option<TrustSnapshot>>>,
 }
 
 impl InMemoryTrustStore {
@@ -1354,6 +1555,7 @@ impl InMemoryTrustStore {
             scores: Arc::new(DashMap::new()),
             updates: Arc::new(DashMap::new()),
             schema_version: Arc::new(Mutex::new(SCHEMA_VERSION)),
+            snapshot: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -1485,6 +1687,23 @@ impl TrustStore for InMemoryTrustStore {
         
         Ok(total_removed)
     }
+
+    async fn compact_with_agent_states(
+        &self,
+        before: DateTime<Utc>,
+        agent_states: HashMap<AgentId, AgentStatus>,
+    ) -> SwarmResult<CompactionStats> {
+        let snapshot = TrustSnapshot::new(self.get_all_trust_scores().await?, agent_states, before.min(Utc::now()));
+        let removed = self.cleanup_old_data(snapshot.compacted_before).await?;
+        
+        let stats = CompactionStats::new(&snapshot, removed);
+        *self.snapshot.lock().unwrap() = Some(snapshot);
+        Ok(stats)
+    }
+
+    async fn latest_snapshot(&self) -> SwarmResult<Option<TrustSnapshot>> {
+        Ok(self.snapshot.lock().unwrap().clone())
+    }
 }
 
 /// Transaction implementation for in-memory store
@@ -1716,4 +1935,82 @@ mod tests {
         assert_eq!(history.len(), 1);
         assert!(history[0].timestamp >= cutoff);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_compaction_preserves_current_trust() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        
+        let store = SqliteTrustStore::new(&db_path, Some(temp_dir.path().join("backups"))).await.unwrap();
+        store.initialize().await.unwrap();
+        
+        let agents: Vec<AgentId> = (0..3).map(|_| AgentId::new_v4()).collect();
+        let mut expected = HashMap::new();
+        
+        for (i, agent_id) in agents.iter().enumerate() {
+            let mut score = TrustScore::new(0.5);
+            
+            // Many old updates, then one recent update per agent
+            for day in (1..=50).rev() {
+                let previous = score;
+                score.value = (score.value + 0.01 * (i + 1) as f64).min(1.0);
+                score.interactions += 1;
+                store.store_trust_update(&TrustUpdate {
+                    agent_id: *agent_id,
+                    previous,
+                    current: score,
+                    reason: TrustUpdateReason::TaskSuccess,
+                    timestamp: Utc::now() - chrono::Duration::days(day),
+                }).await.unwrap();
+            }
+            store.store_trust_update(&TrustUpdate {
+                agent_id: *agent_id,
+                previous: score,
+                current: score,
+                reason: TrustUpdateReason::TaskSuccess,
+                timestamp: Utc::now(),
+            }).await.unwrap();
+            
+            store.store_trust_score(*agent_id, score).await.unwrap();
+            expected.insert(*agent_id, score);
+        }
+        
+        let stats = store.compact(Utc::now() - chrono::Duration::hours(1)).await.unwrap();
+        assert_eq!(stats.agents_snapshotted, 3);
+        assert_eq!(stats.updates_removed, 150);
+        
+        // Current trust is untouched and captured in the snapshot
+        assert_eq!(store.get_all_trust_scores().await.unwrap(), expected);
+        let snapshot = store.latest_snapshot().await.unwrap().unwrap();
+        assert_eq!(snapshot.scores, expected);
+        assert_eq!(snapshot.compacted_before, stats.compacted_before);
+        
+        // Only history newer than the cutoff remains
+        for agent_id in &agents {
+            let history = store.get_trust_history(*agent_id, None).await.unwrap();
+            assert_eq!(history.len(), 1);
+            assert!(history[0].timestamp >= stats.compacted_before);
+        }
+        assert_eq!(store.health_check().await.unwrap().total_updates, 3);
+    }
+
+    #[tokio::test]
+    async fn test_compaction_snapshot_includes_agent_states() {
+        let temp_dir = TempDir::new().unwrap();
+        
+        let store = FileTrustStore::new(temp_dir.path(), None).unwrap();
+        store.initialize().await.unwrap();
+        
+        let (agent_id, score) = create_test_agent_and_score().await;
+        store.store_trust_score(agent_id, score).await.unwrap();
+        
+        let agent_states = HashMap::from([(agent_id, AgentStatus::Busy)]);
+        store.compact_with_agent_states(Utc::now(), agent_states.clone()).await.unwrap();
+        
+        // A reopened store restores both trust and agent state
+        let reopened = FileTrustStore::new(temp_dir.path(), None).unwrap();
+        let snapshot = reopened.latest_snapshot().await.unwrap().unwrap();
+        assert_eq!(snapshot.scores, HashMap::from([(agent_id, score)]));
+        assert_eq!(snapshot.agent_states, agent_states);
+    }
+}
@@ -10,12 +10,15 @@ use crate::{
     coordinator::{SwarmCoordinator, SwarmConfig, SwarmState},
     execution::{ExecutionEngine, ExecutionConfig, ExecutionResult},
     trust::TrustManager,
+    monitoring::{HealthLevel, HealthStatus, MetricsCollector},
+    event_bus::SwarmEventBus,
 };
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc, Duration as ChronoDuration};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use synapsed_core::clock::{SharedClock, SystemClock};
 use std::{
     collections::{HashMap, VecDeque},
     sync::{
@@ -272,6 +275,7 @@ impl RecoveryStrategy for ExponentialBackoffStrategy {
 pub struct CheckpointRecoveryStrategy {
     checkpoint_store: Arc<RwLock<Vec<SwarmCheckpoint>>>,
     max_checkpoints: usize,
+    clock: SharedClock,
 }
 
 impl CheckpointRecoveryStrategy {
@@ -279,13 +283,20 @@ impl CheckpointRecoveryStrategy {
         Self {
             checkpoint_store: Arc::new(RwLock::new(Vec::new())),
             max_checkpoints,
+            clock: SystemClock::shared(),
         }
     }
     
+    /// Use `clock` for checkpoint timestamps
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+    
     /// Create a checkpoint of the current swarm state
     pub async fn create_checkpoint(&self, coordinator: &SwarmCoordinator) -> SwarmResult<Uuid> {
         let checkpoint_id = Uuid::new_v4();
-        let timestamp = Utc::now();
+        let timestamp = self.clock.now();
         
         // Collect current state
         let swarm_state = coordinator.state().await;
@@ -427,14 +438,186 @@ impl RecoveryStrategy for CheckpointRecoveryStrategy {
     }
 }
 
+/// Level of functionality the swarm runs at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DegradationLevel {
+    /// All capabilities and agents available
+    Full,
+    /// Non-essential capabilities and agents shed
+    Reduced,
+    /// Only critical functions kept alive
+    Minimal,
+}
+
+/// What the swarm sheds when entering a degradation level
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DegradationStage {
+    /// Capabilities disabled at this level
+    pub shed_capabilities: Vec<String>,
+    /// Agent roles taken out of task assignment at this level
+    pub shed_roles: Vec<AgentRole>,
+}
+
+/// Degradation levels and the system health that triggers them
+///
+/// Stages are cumulative: the minimal level also sheds everything shed at
+/// the reduced level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegradationPolicy {
+    /// Swarm runs at the reduced level once health is this bad or worse
+    pub reduced_at: HealthLevel,
+    /// Swarm runs at the minimal level once health is this bad or worse
+    pub minimal_at: HealthLevel,
+    /// Shed when entering the reduced level
+    pub reduced: DegradationStage,
+    /// Shed when entering the minimal level
+    pub minimal: DegradationStage,
+}
+
+impl Default for DegradationPolicy {
+    fn default() -> Self {
+        Self {
+            reduced_at: HealthLevel::Degraded,
+            minimal_at: HealthLevel::Unhealthy,
+            reduced: DegradationStage {
+                shed_capabilities: vec!["monitoring".to_string()],
+                shed_roles: vec![AgentRole::Observer],
+            },
+            minimal: DegradationStage {
+                shed_capabilities: vec!["verification".to_string()],
+                shed_roles: vec![AgentRole::Verifier],
+            },
+        }
+    }
+}
+
+impl DegradationPolicy {
+    /// Degradation level for a system health level
+    pub fn level_for(&self, health: HealthLevel) -> DegradationLevel {
+        if health >= self.minimal_at {
+            DegradationLevel::Minimal
+        } else if health >= self.reduced_at {
+            DegradationLevel::Reduced
+        } else {
+            DegradationLevel::Full
+        }
+    }
+    
+    /// Stages in effect at a level
+    fn stages(&self, level: DegradationLevel) -> impl Iterator<Item = &DegradationStage> {
+        [(DegradationLevel::Reduced, &self.reduced), (DegradationLevel::Minimal, &self.minimal)]
+            .into_iter()
+            .filter(move |(stage_level, _)| *stage_level <= level)
+            .map(|(_, stage)| stage)
+    }
+    
+    /// Whether a capability is available at a level
+    pub fn is_capability_available(&self, level: DegradationLevel, capability: &str) -> bool {
+        !self.stages(level).any(|stage| stage.shed_capabilities.iter().any(|c| c == capability))
+    }
+    
+    /// Whether agents with a role take tasks at a level
+    pub fn is_role_active(&self, level: DegradationLevel, role: &AgentRole) -> bool {
+        !self.stages(level).any(|stage| stage.shed_roles.contains(role))
+    }
+}
+
+/// Change of degradation level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegradationTransition {
+    /// Previous level
+    pub from: DegradationLevel,
+    /// New level
+    pub to: DegradationLevel,
+    /// System health that caused the change
+    pub health: HealthLevel,
+    /// When the change happened
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Graceful degradation strategy for resource-limited scenarios
 pub struct GracefulDegradationStrategy {
     resource_monitor: Arc<ResourceMonitor>,
+    policy: Arc<RwLock<DegradationPolicy>>,
+    level: Arc<RwLock<DegradationLevel>>,
+    event_bus: Option<Arc<SwarmEventBus>>,
 }
 
 impl GracefulDegradationStrategy {
     pub fn new(resource_monitor: Arc<ResourceMonitor>) -> Self {
-        Self { resource_monitor }
+        Self {
+            resource_monitor,
+            policy: Arc::new(RwLock::new(DegradationPolicy::default())),
+            level: Arc::new(RwLock::new(DegradationLevel::Full)),
+            event_bus: None,
+        }
+    }
+    
+    /// Publish level changes on `event_bus`
+    pub fn with_event_bus(mut self, event_bus: Arc<SwarmEventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+    
+    /// Replace the degradation policy
+    pub async fn set_policy(&self, policy: DegradationPolicy) {
+        *self.policy.write().await = policy;
+    }
+    
+    /// Current degradation policy
+    pub async fn policy(&self) -> DegradationPolicy {
+        self.policy.read().await.clone()
+    }
+    
+    /// Current degradation level
+    pub async fn level(&self) -> DegradationLevel {
+        *self.level.read().await
+    }
+    
+    /// Move to the level the policy assigns to `health`
+    ///
+    /// Returns the transition if the level changed, after publishing it on
+    /// the event bus.
+    pub async fn update_health(&self, health: HealthLevel) -> Option<DegradationTransition> {
+        let target = self.policy.read().await.level_for(health);
+        let transition = {
+            let mut level = self.level.write().await;
+            if *level == target {
+                return None;
+            }
+            let transition = DegradationTransition {
+                from: *level,
+                to: target,
+                health,
+                timestamp: Utc::now(),
+            };
+            *level = target;
+            transition
+        };
+        
+        if target > transition.from {
+            warn!(from = ?transition.from, to = ?target, health = ?health, "Degrading swarm operation");
+        } else {
+            info!(from = ?transition.from, to = ?target, health = ?health, "Restoring swarm operation");
+        }
+        if let Some(event_bus) = &self.event_bus {
+            if let Err(e) = event_bus.publish(transition.clone()).await {
+                warn!("Failed to publish degradation change: {}", e);
+            }
+        }
+        Some(transition)
+    }
+    
+    /// Whether a capability is available at the current level
+    pub async fn is_capability_available(&self, capability: &str) -> bool {
+        let level = self.level().await;
+        self.policy.read().await.is_capability_available(level, capability)
+    }
+    
+    /// Whether agents with a role take tasks at the current level
+    pub async fn is_role_active(&self, role: &AgentRole) -> bool {
+        let level = self.level().await;
+        self.policy.read().await.is_role_active(level, role)
     }
 }
 
@@ -497,7 +680,7 @@ impl RecoveryStrategy for GracefulDegradationStrategy {
             continue_recovery: false,
             metadata: {
                 let mut metadata = HashMap::new();
-                metadata.insert("degradation_level".to_string(), serde_json::json!("moderate"));
+                metadata.insert("degradation_level".to_string(), serde_json::json!(self.level().await));
                 metadata.insert("resource_usage".to_string(), 
                     serde_json::json!(self.resource_monitor.get_usage().await));
                 metadata
@@ -517,6 +700,7 @@ impl RecoveryStrategy for GracefulDegradationStrategy {
 /// Self-healing mechanism for automatic problem resolution
 pub struct SelfHealingStrategy {
     healing_rules: Arc<RwLock<Vec<HealingRule>>>,
+    clock: SharedClock,
 }
 
 #[derive(Debug, Clone)]
@@ -569,9 +753,16 @@ impl SelfHealingStrategy {
         
         Self {
             healing_rules: Arc::new(RwLock::new(healing_rules)),
+            clock: SystemClock::shared(),
         }
     }
     
+    /// Use `clock` for rule cooldowns
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+    
     pub async fn add_healing_rule(&self, rule: HealingRule) {
         let mut rules = self.healing_rules.write().await;
         rules.push(rule);
@@ -580,7 +771,7 @@ impl SelfHealingStrategy {
     async fn find_applicable_rule(&self, error: &SwarmError) -> Option<HealingRule> {
         let mut rules = self.healing_rules.write().await;
         let error_str = error.to_string();
-        let now = Utc::now();
+        let now = self.clock.now();
         
         for rule in rules.iter_mut() {
             if error_str.contains(&rule.error_pattern) {
@@ -750,10 +941,12 @@ pub struct RecoveryManager {
     strategies: Arc<RwLock<Vec<Arc<dyn RecoveryStrategy>>>>,
     recovery_history: Arc<RwLock<VecDeque<RecoveryAttempt>>>,
     checkpoint_strategy: Arc<CheckpointRecoveryStrategy>,
+    degradation_strategy: Arc<GracefulDegradationStrategy>,
     resource_monitor: Arc<ResourceMonitor>,
     recovery_semaphore: Arc<Semaphore>,
     max_concurrent_recoveries: usize,
     max_history_size: usize,
+    clock: SharedClock,
 }
 
 #[derive(Debug, Clone)]
@@ -767,23 +960,44 @@ pub struct RecoveryAttempt {
 
 impl RecoveryManager {
     pub fn new() -> Self {
+        Self::build(None, SystemClock::shared())
+    }
+    
+    /// Create a recovery manager publishing degradation level changes on `event_bus`
+    pub fn with_event_bus(event_bus: Arc<SwarmEventBus>) -> Self {
+        Self::build(Some(event_bus), SystemClock::shared())
+    }
+    
+    /// Create a recovery manager timing checkpoints, cooldowns and retries with `clock`
+    pub fn with_clock(clock: SharedClock) -> Self {
+        Self::build(None, clock)
+    }
+    
+    fn build(event_bus: Option<Arc<SwarmEventBus>>, clock: SharedClock) -> Self {
         let resource_monitor = Arc::new(ResourceMonitor::new());
-        let checkpoint_strategy = Arc::new(CheckpointRecoveryStrategy::new(10));
+        let checkpoint_strategy = Arc::new(CheckpointRecoveryStrategy::new(10).with_clock(clock.clone()));
+        let mut degradation_strategy = GracefulDegradationStrategy::new(resource_monitor.clone());
+        if let Some(event_bus) = event_bus {
+            degradation_strategy = degradation_strategy.with_event_bus(event_bus);
+        }
+        let degradation_strategy = Arc::new(degradation_strategy);
         
         let mut strategies: Vec<Arc<dyn RecoveryStrategy>> = Vec::new();
         strategies.push(Arc::new(ExponentialBackoffStrategy::new(BackoffConfig::default())));
         strategies.push(checkpoint_strategy.clone());
-        strategies.push(Arc::new(GracefulDegradationStrategy::new(resource_monitor.clone())));
-        strategies.push(Arc::new(SelfHealingStrategy::new()));
+        strategies.push(degradation_strategy.clone());
+        strategies.push(Arc::new(SelfHealingStrategy::new().with_clock(clock.clone())));
         
         Self {
             strategies: Arc::new(RwLock::new(strategies)),
             recovery_history: Arc::new(RwLock::new(VecDeque::new())),
             checkpoint_strategy,
+            degradation_strategy,
             resource_monitor,
             recovery_semaphore: Arc::new(Semaphore::new(3)),
             max_concurrent_recoveries: 3,
             max_history_size: 100,
+            clock,
         }
     }
     
@@ -816,7 +1030,7 @@ impl RecoveryManager {
             failed_task_id,
             failed_agent_id,
             retry_count: self.count_recent_retries(&error).await,
-            error_timestamp: Utc::now(),
+            error_timestamp: self.clock.now(),
             metadata: HashMap::new(),
         };
         
@@ -916,6 +1130,53 @@ impl RecoveryManager {
         self.resource_monitor.clone()
     }
     
+    /// Set the policy deciding what to shed as system health worsens
+    pub async fn set_degradation_policy(&self, policy: DegradationPolicy) {
+        self.degradation_strategy.set_policy(policy).await;
+    }
+    
+    /// Get the graceful degradation strategy
+    pub fn degradation(&self) -> Arc<GracefulDegradationStrategy> {
+        self.degradation_strategy.clone()
+    }
+    
+    /// Current degradation level
+    pub async fn degradation_level(&self) -> DegradationLevel {
+        self.degradation_strategy.level().await
+    }
+    
+    /// Whether agents with a role take tasks at the current degradation level
+    pub async fn is_role_active(&self, role: &AgentRole) -> bool {
+        self.degradation_strategy.is_role_active(role).await
+    }
+    
+    /// Whether a capability is available at the current degradation level
+    pub async fn is_capability_available(&self, capability: &str) -> bool {
+        self.degradation_strategy.is_capability_available(capability).await
+    }
+    
+    /// Move to the degradation level matching the reported system health
+    pub async fn apply_health(&self, health: &HealthStatus) -> Option<DegradationTransition> {
+        self.degradation_strategy.update_health(health.overall_status).await
+    }
+    
+    /// Follow system health from a metrics collector, changing degradation
+    /// level automatically
+    pub fn start_degradation_monitoring(&self, collector: Arc<MetricsCollector>, check_interval: Duration) {
+        let degradation_strategy = self.degradation_strategy.clone();
+        
+        tokio::spawn(async move {
+            let mut interval = interval(check_interval);
+            
+            loop {
+                interval.tick().await;
+                
+                let health = collector.get_health_status().await;
+                degradation_strategy.update_health(health.overall_status).await;
+            }
+        });
+    }
+    
     /// Start background monitoring tasks
     pub async fn start_monitoring(&self) {
         let resource_monitor = self.resource_monitor.clone();
@@ -944,7 +1205,7 @@ impl RecoveryManager {
     async fn count_recent_retries(&self, error: &SwarmError) -> usize {
         let history = self.recovery_history.read().await;
         let error_str = error.to_string();
-        let recent_threshold = Utc::now() - ChronoDuration::hours(1);
+        let recent_threshold = self.clock.now() - ChronoDuration::hours(1);
         
         history
             .iter()
@@ -963,7 +1224,7 @@ impl RecoveryManager {
     ) {
         let attempt = RecoveryAttempt {
             attempt_id,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
             strategy_used: strategy.to_string(),
             error: error.to_string(),
             result: result.clone(),
@@ -1046,6 +1307,22 @@ mod tests {
         assert!(strategy.can_handle(&timeout_error).await);
     }
     
+    #[tokio::test]
+    async fn test_self_healing_cooldown() {
+        let clock = synapsed_core::clock::MockClock::default();
+        let strategy = SelfHealingStrategy::new().with_clock(Arc::new(clock.clone()));
+        let comm_error = SwarmError::CommunicationError("test".to_string());
+        
+        // Applying a rule starts its five minute cooldown
+        assert!(strategy.can_handle(&comm_error).await);
+        assert!(!strategy.can_handle(&comm_error).await);
+        
+        clock.advance(ChronoDuration::minutes(4));
+        assert!(!strategy.can_handle(&comm_error).await);
+        clock.advance(ChronoDuration::minutes(2));
+        assert!(strategy.can_handle(&comm_error).await);
+    }
+    
     #[tokio::test] 
     async fn test_resource_monitor() {
         let monitor = ResourceMonitor::new();
@@ -0,0 +1,145 @@
+//! Structured execution tracing for swarm tasks
+//!
+//! Every task execution produces a tree of [`tracing`] spans following the
+//! delegation chain: coordinator → agent → intent → step. Delegated sub-tasks
+//! open their coordinator span under the agent span of the task that delegated
+//! them, so a whole multi-agent execution shares one trace.
+//!
+//! Timing is left to the subscriber. Spans carry the `otel.name`,
+//! `otel.status_code` and `otel.status_message` fields that
+//! `tracing-opentelemetry` maps onto OpenTelemetry spans; with the
+//! `monitoring` feature, [`otel_layer`] exports them through an OpenTelemetry
+//! tracer to Jaeger, Tempo or any OTLP collector.
+
+use crate::types::{AgentId, TaskId};
+use std::fmt;
+use synapsed_intent::HierarchicalIntent;
+use tracing::{field, info_span, Id, Span};
+
+/// Target of every execution span, for filtering them in a subscriber
+pub const TRACE_TARGET: &str = "synapsed_swarm::trace";
+
+/// Level of the execution tree a span covers
+///
+/// The kind is the span's name and is also recorded in its `swarm.span.kind`
+/// field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpanKind {
+    /// Task handling in the swarm coordinator
+    Coordinator,
+    /// Work done by a single agent
+    Agent,
+    /// Execution of an intent
+    Intent,
+    /// Execution of a single intent step
+    Step,
+}
+
+impl SpanKind {
+    /// Name of spans of this kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpanKind::Coordinator => "coordinator",
+            SpanKind::Agent => "agent",
+            SpanKind::Intent => "intent",
+            SpanKind::Step => "step",
+        }
+    }
+}
+
+impl fmt::Display for SpanKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Open the coordinator span of a task run
+///
+/// `parent` is the agent span of the delegating task for sub-tasks, `None`
+/// starts a new trace.
+pub fn coordinator_span(task_id: TaskId, parent: Option<Id>) -> Span {
+    info_span!(
+        target: TRACE_TARGET,
+        parent: parent,
+        "coordinator",
+        swarm.span.kind = SpanKind::Coordinator.as_str(),
+        otel.name = %format!("task {}", task_id),
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        task.id = %task_id,
+    )
+}
+
+/// Open the span of an agent working on a task
+pub fn agent_span(agent_id: AgentId, parent: &Span) -> Span {
+    info_span!(
+        target: TRACE_TARGET,
+        parent: parent,
+        "agent",
+        swarm.span.kind = SpanKind::Agent.as_str(),
+        otel.name = %format!("agent {}", agent_id),
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        agent.id = %agent_id,
+    )
+}
+
+/// Open the span of an intent's execution
+pub fn intent_span(intent: &HierarchicalIntent, parent: &Span) -> Span {
+    info_span!(
+        target: TRACE_TARGET,
+        parent: parent,
+        "intent",
+        swarm.span.kind = SpanKind::Intent.as_str(),
+        otel.name = %intent.goal(),
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        intent.id = %intent.id(),
+    )
+}
+
+/// Open the span of a step's execution under the current span
+pub fn step_span(intent: &HierarchicalIntent, step_index: usize) -> Span {
+    let name = intent.steps
+        .get(step_index)
+        .map(|step| step.name.clone())
+        .unwrap_or_else(|| format!("step {}", step_index + 1));
+    info_span!(
+        target: TRACE_TARGET,
+        "step",
+        swarm.span.kind = SpanKind::Step.as_str(),
+        otel.name = %name,
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        intent.id = %intent.id(),
+        step.index = step_index,
+        step.command = field::Empty,
+    )
+}
+
+/// Record the outcome of a span as its OpenTelemetry status
+pub fn record_outcome(span: &Span, outcome: Result<(), &dyn fmt::Display>) {
+    match outcome {
+        Ok(()) => {
+            span.record("otel.status_code", "OK");
+        }
+        Err(error) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("otel.status_message", field::display(error));
+        }
+    }
+}
+
+/// Layer exporting execution spans through an OpenTelemetry tracer
+///
+/// Add it to a [`tracing_subscriber::Registry`] together with any other
+/// layers; spans outside [`TRACE_TARGET`] are exported as well unless the
+/// layer is filtered.
+#[cfg(feature = "monitoring")]
+pub fn otel_layer<S, T>(tracer: T) -> tracing_opentelemetry::OpenTelemetryLayer<S, T>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    T: opentelemetry::trace::Tracer + tracing_opentelemetry::PreSampledTracer + 'static,
+{
+    tracing_opentelemetry::layer().with_tracer(tracer)
+}
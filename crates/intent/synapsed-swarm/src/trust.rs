@@ -2,8 +2,8 @@
 
 use crate::{
     error::SwarmResult, 
-    types::AgentId,
-    persistence::{TrustStore, InMemoryTrustStore},
+    types::{AgentId, AgentStatus},
+    persistence::{CompactionStats, TrustStore, InMemoryTrustStore},
 };
 use dashmap::DashMap;
 use std::sync::Arc;
@@ -128,6 +128,10 @@ pub struct TrustManager {
     thresholds: TrustThresholds,
     /// Backup configuration
     backup_config: BackupConfig,
+    /// Compaction configuration
+    compaction_config: CompactionConfig,
+    /// Agent states recorded in compaction snapshots
+    agent_states: Arc<DashMap<AgentId, AgentStatus>>,
     /// Shutdown signal for background tasks
     shutdown: Arc<RwLock<bool>>,
 }
@@ -156,6 +160,34 @@ impl Default for BackupConfig {
     }
 }
 
+/// Configuration for automatic trust history compaction
+///
+/// Compaction snapshots current trust and agent state and removes older
+/// update history, so it is off by default.
+#[derive(Debug, Clone)]
+pub struct CompactionConfig {
+    /// Enable automatic compaction
+    pub enabled: bool,
+    /// How often to check whether compaction is due, in seconds
+    pub interval_secs: u64,
+    /// Only compact once the store holds more updates than this
+    /// (`None` compacts on every check)
+    pub max_updates: Option<usize>,
+    /// Age in seconds of the newest history that compaction may remove
+    pub retain_secs: u64,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 3600, // 1 hour
+            max_updates: Some(100_000),
+            retain_secs: 7 * 86400, // 1 week
+        }
+    }
+}
+
 /// Trust thresholds for different operations
 #[derive(Debug, Clone)]
 pub struct TrustThresholds {
@@ -196,6 +228,8 @@ impl TrustManager {
             cache: Arc::new(DashMap::new()),
             thresholds: TrustThresholds::default(),
             backup_config: BackupConfig::default(),
+            compaction_config: CompactionConfig::default(),
+            agent_states: Arc::new(DashMap::new()),
             shutdown: Arc::new(RwLock::new(false)),
         }
     }
@@ -210,6 +244,8 @@ impl TrustManager {
             cache: Arc::new(DashMap::new()),
             thresholds,
             backup_config: BackupConfig::default(),
+            compaction_config: CompactionConfig::default(),
+            agent_states: Arc::new(DashMap::new()),
             shutdown: Arc::new(RwLock::new(false)),
         }
     }
@@ -220,6 +256,18 @@ impl TrustManager {
         self
     }
     
+    /// Configure automatic compaction
+    pub fn with_compaction_config(mut self, config: CompactionConfig) -> Self {
+        self.compaction_config = config;
+        self
+    }
+    
+    /// Record these agent states in compaction snapshots
+    pub fn with_agent_states(mut self, agent_states: Arc<DashMap<AgentId, AgentStatus>>) -> Self {
+        self.agent_states = agent_states;
+        self
+    }
+    
     /// Initialize trust manager
     pub async fn initialize(&self) -> SwarmResult<()> {
         // Initialize the storage backend
@@ -238,9 +286,54 @@ impl TrustManager {
             self.start_periodic_backup().await;
         }
         
+        // Start automatic compaction task if enabled
+        if self.compaction_config.enabled {
+            self.start_auto_compaction().await;
+        }
+        
         Ok(())
     }
     
+    /// Start automatic compaction task
+    async fn start_auto_compaction(&self) {
+        let storage = Arc::clone(&self.storage);
+        let agent_states = Arc::clone(&self.agent_states);
+        let shutdown = Arc::clone(&self.shutdown);
+        let config = self.compaction_config.clone();
+        
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_secs(config.interval_secs));
+            // The first tick completes immediately
+            ticker.tick().await;
+            
+            loop {
+                ticker.tick().await;
+                
+                if *shutdown.read().await {
+                    debug!("Automatic compaction task shutting down");
+                    break;
+                }
+                
+                if let Some(max_updates) = config.max_updates {
+                    match storage.health_check().await {
+                        Ok(health) if health.total_updates <= max_updates => continue,
+                        Ok(_) => {}
+                        Err(e) => {
+                            warn!("Failed to check trust store size for compaction: {}", e);
+                            continue;
+                        }
+                    }
+                }
+                
+                let before = Utc::now() - chrono::Duration::seconds(config.retain_secs as i64);
+                match compact_store(&*storage, &agent_states, before).await {
+                    Ok(stats) => debug!("Compacted trust store, removed {} updates", stats.updates_removed),
+                    Err(e) => warn!("Failed to compact trust store: {}", e),
+                }
+            }
+        });
+    }
+    
     /// Start periodic backup task
     async fn start_periodic_backup(&self) {
         let storage = Arc::clone(&self.storage);
@@ -565,6 +658,16 @@ impl TrustManager {
         self.storage.cleanup_old_data(older_than).await
     }
     
+    /// Snapshot current trust and agent states and truncate older history
+    pub async fn compact(&self, before: DateTime<Utc>) -> SwarmResult<CompactionStats> {
+        compact_store(&*self.storage, &self.agent_states, before).await
+    }
+    
+    /// Get the snapshot taken by the most recent compaction
+    pub async fn latest_snapshot(&self) -> SwarmResult<Option<crate::persistence::TrustSnapshot>> {
+        self.storage.latest_snapshot().await
+    }
+    
     /// Shutdown the trust manager and cleanup background tasks
     pub async fn shutdown(&self) {
         *self.shutdown.write().await = true;
@@ -665,6 +768,19 @@ impl TrustManager {
     }
 }
 
+/// Compact a store, recording the current agent states in its snapshot
+async fn compact_store(
+    storage: &dyn TrustStore,
+    agent_states: &DashMap<AgentId, AgentStatus>,
+    before: DateTime<Utc>,
+) -> SwarmResult<CompactionStats> {
+    let agent_states = agent_states
+        .iter()
+        .map(|entry| (*entry.key(), entry.value().clone()))
+        .collect();
+    storage.compact_with_agent_states(before, agent_states).await
+}
+
 /// Type of operation for trust checking
 #[derive(Debug, Clone, Copy)]
 pub enum TrustOperation {
@@ -0,0 +1,129 @@
+//! Tests for alert deduplication, escalation and resolution
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use synapsed_swarm::alerting::{
+    AlertChannel, AlertManager, AlertNotification, AlertRoutingConfig, NotificationKind,
+};
+use synapsed_swarm::monitoring::{Alert, AlertSeverity, MetricsCollector, MonitoringConfig};
+use synapsed_swarm::trust::{TrustScore, TrustUpdate, TrustUpdateReason};
+use uuid::Uuid;
+
+/// Channel that records what it was notified of
+#[derive(Default)]
+struct RecordingChannel {
+    received: Mutex<Vec<(NotificationKind, AlertSeverity, u64)>>,
+}
+
+impl RecordingChannel {
+    fn received(&self) -> Vec<(NotificationKind, AlertSeverity, u64)> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl AlertChannel for RecordingChannel {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    async fn notify(&self, notification: &AlertNotification) {
+        self.received.lock().unwrap().push((
+            notification.kind,
+            notification.alert.severity,
+            notification.alert.count,
+        ));
+    }
+}
+
+fn trust_update(agent_id: Uuid, previous: f64, current: f64) -> TrustUpdate {
+    TrustUpdate {
+        agent_id,
+        previous: TrustScore::new(previous),
+        current: TrustScore::new(current),
+        reason: TrustUpdateReason::TaskFailure,
+        timestamp: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_repeated_breach_is_deduplicated_and_resolves_when_cleared() {
+    let collector = MetricsCollector::new(MonitoringConfig::default());
+    let mut alerts = collector.subscribe_alerts();
+    let channel = Arc::new(RecordingChannel::default());
+    let manager = AlertManager::new(AlertRoutingConfig::default())
+        .with_route(AlertSeverity::Info, channel.clone());
+    let agent_id = Uuid::new_v4();
+
+    // Trust stays below the 0.3 threshold for three updates
+    for (previous, current) in [(0.4, 0.25), (0.25, 0.2), (0.2, 0.15)] {
+        collector.record_trust_update(&trust_update(agent_id, previous, current)).await;
+        manager.ingest(alerts.recv().await.unwrap()).await;
+    }
+
+    let active = manager.active_alerts().await;
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].count, 3);
+    assert_eq!(active[0].fingerprint.agent_id, Some(agent_id));
+    assert!(active[0].is_active());
+    assert_eq!(channel.received(), vec![(NotificationKind::Fired, AlertSeverity::Warning, 1)]);
+
+    // Trust recovers, so the collector reports the condition cleared
+    collector.record_trust_update(&trust_update(agent_id, 0.15, 0.5)).await;
+    manager.ingest(alerts.recv().await.unwrap()).await;
+
+    assert!(manager.active_alerts().await.is_empty());
+    let resolved = manager.resolved_alerts().await;
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].count, 3);
+    assert!(resolved[0].resolved_at.is_some());
+    assert_eq!(channel.received().last(), Some(&(NotificationKind::Resolved, AlertSeverity::Warning, 3)));
+    assert!(collector.get_dashboard_metrics().await.active_alerts.is_empty());
+}
+
+#[tokio::test]
+async fn test_unresolved_alert_escalates_then_goes_stale() {
+    let config = AlertRoutingConfig {
+        escalation_timeout: Duration::from_secs(60),
+        max_severity: AlertSeverity::Critical,
+        resolve_after: Some(Duration::from_secs(600)),
+        ..Default::default()
+    };
+    let on_call = Arc::new(RecordingChannel::default());
+    let pager = Arc::new(RecordingChannel::default());
+    let manager = AlertManager::new(config)
+        .with_route(AlertSeverity::Warning, on_call.clone())
+        .with_route(AlertSeverity::Critical, pager.clone());
+
+    let fired_at = Utc::now();
+    let at = |secs: i64| fired_at + chrono::Duration::seconds(secs);
+    manager.ingest(Alert {
+        id: Uuid::new_v4(),
+        severity: AlertSeverity::Warning,
+        title: "Promise Violation".to_string(),
+        description: "Agent broke promise".to_string(),
+        agent_id: Some(Uuid::new_v4()),
+        timestamp: fired_at,
+        resolved: false,
+        resolution_time: None,
+    }).await;
+
+    manager.evaluate(at(30)).await;
+    assert!(pager.received().is_empty());
+
+    // Past the timeout the alert escalates and reaches the pager
+    manager.evaluate(at(60)).await;
+    assert_eq!(pager.received(), vec![(NotificationKind::Escalated, AlertSeverity::Critical, 1)]);
+
+    // Escalation stops at the configured maximum
+    manager.evaluate(at(180)).await;
+    assert_eq!(manager.active_alerts().await[0].escalations, 1);
+
+    // Nothing fired again, so the alert resolves once stale
+    manager.evaluate(at(600)).await;
+    assert!(manager.active_alerts().await.is_empty());
+    let kinds: Vec<NotificationKind> = on_call.received().into_iter().map(|(kind, _, _)| kind).collect();
+    assert_eq!(kinds, vec![NotificationKind::Fired, NotificationKind::Escalated, NotificationKind::Resolved]);
+}
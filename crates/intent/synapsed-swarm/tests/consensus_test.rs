@@ -118,6 +118,7 @@ impl TestConsensusSetup {
             checkpoint_interval: 100,
             enable_fast_path: true,
             signature_algorithm: "ed25519".to_string(),
+            weighted_quorum: None,
         };
         
         // Create communication layers and consensus nodes
@@ -307,6 +308,7 @@ async fn test_quorum_certificate_creation() {
         phase: ConsensusPhase::Committed,
         view: 0,
         signatures,
+        weights: HashMap::new(),
         created_at: chrono::Utc::now(),
     };
     
@@ -314,6 +316,59 @@ async fn test_quorum_certificate_creation() {
     assert_eq!(qc.phase, ConsensusPhase::Committed);
 }
 
+#[tokio::test]
+async fn test_trust_weighted_quorum() {
+    let agent_ids: Vec<AgentId> = (0..4).map(|_| Uuid::new_v4()).collect();
+    let config = ConsensusConfig {
+        weighted_quorum: Some(WeightedQuorumConfig {
+            threshold: 0.5,
+            min_voters: 2,
+        }),
+        ..Default::default()
+    };
+    
+    let comm = Arc::new(MockCommunication::new(agent_ids[0], agent_ids.clone()));
+    let consensus = PBFTConsensus::new(Uuid::new_v4(), agent_ids[0], comm, config);
+    
+    // One high-trust agent and three low-trust ones: total weight 1.4, quorum at 0.7
+    for (&agent_id, weight) in agent_ids.iter().zip([0.9, 0.2, 0.2, 0.1]) {
+        consensus.add_agent(agent_id).await.unwrap();
+        consensus.set_agent_weight(agent_id, weight);
+    }
+    
+    let certificate = |signers: &[AgentId]| QuorumCertificate {
+        proposal_id: Uuid::new_v4(),
+        phase: ConsensusPhase::Committed,
+        view: 0,
+        signatures: signers
+            .iter()
+            .map(|&signer| ConsensusSignature {
+                signer,
+                signature: vec![0u8; 64],
+                algorithm: "ed25519".to_string(),
+            })
+            .collect(),
+        weights: HashMap::new(),
+        created_at: chrono::Utc::now(),
+    };
+    
+    // Three of four agents would be a count quorum, but only hold 0.5 of the weight
+    let low_trust = certificate(&agent_ids[1..]);
+    assert!(!consensus.verify_quorum(&low_trust).await.unwrap());
+    
+    // The high-trust agent alone holds enough weight but is a single voter
+    let alone = certificate(&agent_ids[..1]);
+    assert!(!consensus.verify_quorum(&alone).await.unwrap());
+    
+    // High-trust agent plus one low-trust agent reach 1.1 >= 0.7
+    let weighted = certificate(&[agent_ids[0], agent_ids[3]]);
+    assert!(consensus.verify_quorum(&weighted).await.unwrap());
+    
+    // Unknown signers add no weight
+    let outsiders = certificate(&[agent_ids[1], Uuid::new_v4(), Uuid::new_v4()]);
+    assert!(!consensus.verify_quorum(&outsiders).await.unwrap());
+}
+
 #[tokio::test]
 async fn test_consensus_result_structure() {
     let proposal_id = Uuid::new_v4();
@@ -329,6 +384,7 @@ async fn test_consensus_result_structure() {
         phase: ConsensusPhase::Committed,
         view: 0,
         signatures: vec![],
+        weights: HashMap::new(),
         created_at: chrono::Utc::now(),
     };
     
@@ -0,0 +1,80 @@
+//! Tests for task retries and the dead-letter queue
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use synapsed_swarm::{
+    DeadLetterQueue, RetryDecision, SwarmConfig, SwarmCoordinator, TaskAssignment,
+};
+use synapsed_intent::{IntentBuilder, StepAction};
+
+fn failing_assignment() -> TaskAssignment {
+    let intent = IntentBuilder::new("always fails")
+        .step("step1", StepAction::Command("false".to_string()))
+        .build();
+
+    TaskAssignment {
+        task_id: Uuid::new_v4(),
+        agent_id: Uuid::new_v4(),
+        intent,
+        promise: None,
+        parent_task: None,
+        context: HashMap::new(),
+        verification_required: false,
+        deadline: None,
+    }
+}
+
+#[test]
+fn test_task_dead_lettered_after_max_retries() {
+    let queue = DeadLetterQueue::new(2);
+    let assignment = failing_assignment();
+
+    assert_eq!(queue.record_failure(&assignment, "attempt 1 failed"), RetryDecision::Retry { attempt: 1 });
+    assert_eq!(queue.record_failure(&assignment, "attempt 2 failed"), RetryDecision::Retry { attempt: 2 });
+    assert!(queue.is_empty());
+
+    // The third failure exceeds the retry limit and stops automatic retries
+    assert_eq!(queue.record_failure(&assignment, "attempt 3 failed"), RetryDecision::DeadLettered);
+
+    let dead_letters = queue.tasks();
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].task_id, assignment.task_id);
+    assert_eq!(dead_letters[0].error, "attempt 3 failed");
+    assert_eq!(dead_letters[0].attempts, 3);
+}
+
+#[test]
+fn test_success_resets_retries() {
+    let queue = DeadLetterQueue::new(1);
+    let assignment = failing_assignment();
+
+    assert_eq!(queue.record_failure(&assignment, "failed"), RetryDecision::Retry { attempt: 1 });
+    queue.record_success(assignment.task_id);
+    assert_eq!(queue.record_failure(&assignment, "failed"), RetryDecision::Retry { attempt: 1 });
+}
+
+#[test]
+fn test_requeued_task_gets_fresh_retries() {
+    let queue = DeadLetterQueue::new(1);
+    let assignment = failing_assignment();
+
+    assert_eq!(queue.record_failure(&assignment, "failed"), RetryDecision::Retry { attempt: 1 });
+    assert_eq!(queue.record_failure(&assignment, "failed"), RetryDecision::DeadLettered);
+
+    let task = queue.take(assignment.task_id).expect("Task should be dead-lettered");
+    assert_eq!(task.task_id, assignment.task_id);
+    assert!(queue.is_empty());
+    assert!(queue.take(assignment.task_id).is_none());
+
+    assert_eq!(queue.record_failure(&assignment, "failed"), RetryDecision::Retry { attempt: 1 });
+}
+
+#[tokio::test]
+async fn test_coordinator_dead_letter_queue() {
+    let coordinator = SwarmCoordinator::new(SwarmConfig::default());
+    coordinator.initialize().await.unwrap();
+
+    assert!(coordinator.dead_letter_tasks().await.is_empty());
+    assert!(coordinator.requeue_dead_letter(Uuid::new_v4()).await.is_err());
+}
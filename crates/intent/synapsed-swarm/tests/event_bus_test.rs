@@ -0,0 +1,104 @@
+//! Tests for the typed swarm event bus
+
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::time::{timeout, Duration};
+use uuid::Uuid;
+
+use synapsed_intent::{IntentBuilder, StepAction};
+use synapsed_promise::{AgentCapabilities, AgentConfig, AutonomousAgent, QualityOfService};
+use synapsed_swarm::{
+    AgentJoined, AgentRole, SwarmConfig, SwarmCoordinator, SwarmEventBus, TaskCompleted,
+};
+
+#[tokio::test]
+async fn test_subscriber_receives_task_completed() {
+    let bus = SwarmEventBus::new();
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _subscription = bus
+        .subscribe(move |event: TaskCompleted| {
+            let _ = tx.send(event);
+        })
+        .await
+        .unwrap();
+
+    // Events of other types don't reach the subscriber
+    bus.publish(AgentJoined {
+        agent_id: Uuid::new_v4(),
+        role: AgentRole::Worker,
+        timestamp: Utc::now(),
+    })
+    .await
+    .unwrap();
+
+    let task_id = Uuid::new_v4();
+    let agent_id = Uuid::new_v4();
+    bus.publish(TaskCompleted {
+        task_id,
+        agent_id,
+        success: false,
+        timestamp: Utc::now(),
+    })
+    .await
+    .unwrap();
+
+    let event = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("TaskCompleted should be delivered")
+        .unwrap();
+    assert_eq!(event.task_id, task_id);
+    assert_eq!(event.agent_id, agent_id);
+    assert!(!event.success);
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_coordinator_publishes_task_completed() {
+    let coordinator = SwarmCoordinator::new(SwarmConfig::default());
+    coordinator.initialize().await.unwrap();
+    coordinator.add_agent(create_test_agent("worker"), AgentRole::Worker).await.unwrap();
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _subscription = coordinator
+        .event_bus()
+        .subscribe(move |event: TaskCompleted| {
+            let _ = tx.send(event);
+        })
+        .await
+        .unwrap();
+
+    let intent = IntentBuilder::new("Test task")
+        .step("Execute test", StepAction::Custom(serde_json::json!({"test": true})))
+        .build();
+    let context = synapsed_intent::ContextBuilder::new().build().await;
+    let task_id = coordinator.delegate_intent(intent, context).await.unwrap();
+
+    let event = timeout(Duration::from_secs(10), rx.recv())
+        .await
+        .expect("TaskCompleted should be published when the task finishes")
+        .unwrap();
+    assert_eq!(event.task_id, task_id);
+
+    let result = coordinator.get_task_result(task_id).await.expect("Task result should be stored");
+    assert_eq!(event.success, result.success);
+}
+
+fn create_test_agent(name: &str) -> Arc<AutonomousAgent> {
+    let config = AgentConfig {
+        name: name.to_string(),
+        capabilities: AgentCapabilities {
+            services: vec!["test".to_string()],
+            resources: vec!["cpu".to_string()],
+            protocols: vec!["promise".to_string()],
+            quality: QualityOfService::default(),
+        },
+        trust_model: synapsed_promise::TrustModel::new(),
+        cooperation_protocol: synapsed_promise::CooperationProtocol::new(),
+        max_promises: 5,
+        promise_timeout_secs: 60,
+    };
+
+    Arc::new(AutonomousAgent::new(config))
+}
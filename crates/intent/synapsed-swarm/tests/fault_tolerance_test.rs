@@ -12,8 +12,8 @@ use synapsed_swarm::{
     execution::ExecutionConfig,
     trust::TrustManager,
 };
-use synapsed_promise::AutonomousAgent;
-use synapsed_intent::{HierarchicalIntent, IntentBuilder, IntentContext};
+use synapsed_promise::{AgentCapabilities, AgentConfig, AutonomousAgent, QualityOfService};
+use synapsed_intent::{ContextBuilder, HierarchicalIntent, IntentBuilder, IntentContext, StepAction};
 
 #[tokio::test]
 async fn test_fault_tolerance_integration_with_coordinator() -> SwarmResult<()> {
@@ -417,4 +417,98 @@ async fn test_concurrent_agent_operations() -> SwarmResult<()> {
     assert_eq!(all_health.len(), 5);
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_stalled_task_reassigned_from_checkpoint() -> SwarmResult<()> {
+    let mut config = SwarmConfig::default();
+    config.require_verification = false;
+    config.fault_tolerance_config = FaultToleranceConfig {
+        heartbeat_interval_ms: 20,
+        reassignment_missed_heartbeats: 3,
+        ..Default::default()
+    };
+    config.execution_config = ExecutionConfig {
+        allowed_commands: vec!["sleep".to_string(), "echo".to_string()],
+        ..Default::default()
+    };
+    
+    let coordinator = SwarmCoordinator::new(config);
+    coordinator.initialize().await?;
+    
+    let mut agents = Vec::new();
+    for name in ["agent-a", "agent-b"] {
+        let agent = std::sync::Arc::new(AutonomousAgent::new(AgentConfig {
+            name: name.to_string(),
+            capabilities: AgentCapabilities {
+                services: vec!["test".to_string()],
+                resources: vec!["cpu".to_string()],
+                protocols: vec!["promise".to_string()],
+                quality: QualityOfService::default(),
+            },
+            trust_model: synapsed_promise::TrustModel::new(),
+            cooperation_protocol: synapsed_promise::CooperationProtocol::new(),
+            max_promises: 5,
+            promise_timeout_secs: 60,
+        }));
+        coordinator.add_agent(agent.clone(), synapsed_swarm::AgentRole::Worker).await?;
+        agents.push(agent);
+    }
+    
+    // The first step outlasts the stall, the last one is all a resumed run does
+    let intent = IntentBuilder::new("resumable task")
+        .step("sleep 2", StepAction::Command("sleep 2".to_string()))
+        .step("echo two", StepAction::Command("echo two".to_string()))
+        .step("echo three", StepAction::Command("echo three".to_string()))
+        .build();
+    let context = ContextBuilder::new().build().await;
+    
+    let task_id = coordinator.delegate_intent(intent, context).await?;
+    let stalled_agent = coordinator.get_task_assignment(task_id).await
+        .expect("Task should be assigned")
+        .agent_id;
+    let healthy_agent = agents.iter().map(|agent| agent.id()).find(|id| *id != stalled_agent).unwrap();
+    
+    // The assigned agent checkpoints after two steps, then stops
+    let mut context = std::collections::HashMap::new();
+    context.insert("partial_output".to_string(), serde_json::json!("steps 1-2 done"));
+    coordinator.create_task_checkpoint(task_id, stalled_agent, 2, 0.66, context).await?;
+    agents.iter().find(|agent| agent.id() == stalled_agent).unwrap().shutdown().await.unwrap();
+    
+    // The coordinator's stall monitor moves the task without being asked
+    let result = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let Some(result) = coordinator.get_task_result(task_id).await {
+                return result;
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+    }).await.expect("Reassigned task should complete");
+    
+    assert!(result.success);
+    assert_eq!(result.agent_id, healthy_agent);
+    
+    // Only the step after the checkpoint ran again
+    let steps = result.output.as_ref().and_then(|output| output.as_array()).unwrap();
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0]["stdout"].as_str().map(str::trim), Some("three"));
+    
+    // The task carries the checkpointed context and the stalled agent is flagged
+    let assignment = coordinator.get_task_assignment(task_id).await.unwrap();
+    assert_eq!(assignment.agent_id, healthy_agent);
+    assert_eq!(
+        assignment.context.get("partial_output"),
+        Some(&serde_json::json!("steps 1-2 done"))
+    );
+    assert_eq!(
+        coordinator.get_agent_health(stalled_agent).await,
+        Some(AgentHealthStatus::Unresponsive)
+    );
+    assert_eq!(coordinator.get_recovery_stats().await.task_redistributions, 1);
+    
+    // Nothing left to reassign once the task has finished
+    assert!(coordinator.reassign_stalled_tasks().await?.is_empty());
+    
+    coordinator.shutdown().await?;
+    Ok(())
+}
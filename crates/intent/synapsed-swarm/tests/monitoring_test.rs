@@ -21,6 +21,7 @@ mod tests {
             max_events: 1000,
             enable_dashboard: true,
             alert_thresholds: AlertThresholds::default(),
+            history_retention: Duration::from_secs(3600),
         };
         
         assert_eq!(config.prometheus_port, 9090);
@@ -73,4 +74,112 @@ mod tests {
         assert!(health.components.contains_key("agents"));
         assert!(health.components.contains_key("tasks"));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_query_range_downsamples_history() {
+        use synapsed_swarm::monitoring::{DataPoint, Metric, MetricsCollector, MonitoringConfig};
+        
+        let config = MonitoringConfig {
+            history_retention: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let collector = MetricsCollector::new(config);
+        let base = Utc::now();
+        let at = |secs: i64| base + chrono::Duration::seconds(secs);
+        
+        // One sample every 10 seconds, task duration growing by 10ms each time
+        let mut snapshot = collector.get_dashboard_metrics().await;
+        for i in 0..6 {
+            snapshot.timestamp = at(i * 10);
+            snapshot.swarm_metrics.avg_task_duration_ms = (i * 10) as f64;
+            collector.record_sample(&snapshot).await;
+        }
+        
+        let raw = collector.query_range(&Metric::AvgTaskDurationMs, at(0), at(60), Duration::ZERO).await;
+        assert_eq!(raw.len(), 6);
+        
+        // 30s buckets average three samples each
+        let points = collector.query_range(&Metric::AvgTaskDurationMs, at(0), at(60), Duration::from_secs(30)).await;
+        assert_eq!(points, vec![
+            DataPoint { timestamp: at(0), value: 10.0 },
+            DataPoint { timestamp: at(30), value: 40.0 },
+        ]);
+        
+        // Buckets align to the start of the range, which excludes its end
+        let points = collector.query_range(&Metric::AvgTaskDurationMs, at(10), at(40), Duration::from_secs(20)).await;
+        assert_eq!(points, vec![
+            DataPoint { timestamp: at(10), value: 15.0 },
+            DataPoint { timestamp: at(30), value: 30.0 },
+        ]);
+        
+        // Agents missing from the samples have no data
+        let unknown = Metric::AgentTrustScore(Uuid::new_v4());
+        assert!(collector.query_range(&unknown, at(0), at(60), Duration::from_secs(30)).await.is_empty());
+        
+        // Samples older than the retention window are dropped
+        snapshot.timestamp = at(120);
+        collector.record_sample(&snapshot).await;
+        let points = collector.query_range(&Metric::AvgTaskDurationMs, at(0), at(180), Duration::from_secs(60)).await;
+        assert_eq!(points, vec![DataPoint { timestamp: at(120), value: 50.0 }]);
+    }
+
+    #[tokio::test]
+    async fn test_grafana_datasource_search_and_query() {
+        use synapsed_swarm::monitoring::{GrafanaDatasource, MetricsCollector, MonitoringConfig};
+        use synapsed_swarm::types::SwarmEvent;
+        
+        let collector = Arc::new(MetricsCollector::new(MonitoringConfig::default()));
+        let agent_id = Uuid::new_v4();
+        collector.record_event(SwarmEvent::TaskAssigned {
+            task_id: Uuid::new_v4(),
+            agent_id,
+            timestamp: Utc::now(),
+        }).await;
+        
+        let base = Utc::now();
+        let at = |secs: i64| base + chrono::Duration::seconds(secs);
+        let mut snapshot = collector.get_dashboard_metrics().await;
+        for i in 0..4 {
+            snapshot.timestamp = at(i * 15);
+            snapshot.swarm_metrics.avg_trust_score = 0.5 + i as f64 * 0.1;
+            collector.record_sample(&snapshot).await;
+        }
+        let datasource = GrafanaDatasource::new(collector);
+        
+        // Search lists swarm metrics and per-agent metrics for known agents
+        let (status, body) = datasource.handle("POST", "/search", br#"{"target": ""}"#).await;
+        assert_eq!(status, 200);
+        let names: Vec<String> = serde_json::from_str(&body).unwrap();
+        assert!(names.contains(&"swarm.avg_trust_score".to_string()));
+        assert!(names.contains(&format!("agent.{}.trust_score", agent_id)));
+        
+        let (_, body) = datasource.handle("POST", "/search", br#"{"target": "trust"}"#).await;
+        let names: Vec<String> = serde_json::from_str(&body).unwrap();
+        assert!(names.iter().all(|name| name.contains("trust")));
+        
+        // Query returns [value, unix ms] datapoints, 30s apart
+        let request = serde_json::json!({
+            "range": { "from": at(0).to_rfc3339(), "to": at(60).to_rfc3339() },
+            "intervalMs": 30000,
+            "targets": [{ "target": "swarm.avg_trust_score", "refId": "A" }],
+        });
+        let (status, body) = datasource.handle("POST", "/query", request.to_string().as_bytes()).await;
+        assert_eq!(status, 200);
+        let series: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(series[0]["target"], "swarm.avg_trust_score");
+        let datapoints = series[0]["datapoints"].as_array().unwrap();
+        assert_eq!(datapoints.len(), 2);
+        assert!((datapoints[0][0].as_f64().unwrap() - 0.55).abs() < 1e-9);
+        assert_eq!(datapoints[0][1].as_i64().unwrap(), at(0).timestamp_millis());
+        assert!((datapoints[1][0].as_f64().unwrap() - 0.75).abs() < 1e-9);
+        assert_eq!(datapoints[1][1].as_i64().unwrap(), at(30).timestamp_millis());
+        
+        // Unknown metrics are rejected
+        let request = serde_json::json!({
+            "range": { "from": at(0).to_rfc3339(), "to": at(60).to_rfc3339() },
+            "targets": [{ "target": "swarm.nonexistent" }],
+        });
+        let (status, _) = datasource.handle("POST", "/query", request.to_string().as_bytes()).await;
+        assert_eq!(status, 400);
+    }
+}
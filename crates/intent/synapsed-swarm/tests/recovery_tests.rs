@@ -486,4 +486,121 @@ async fn test_coordinator_recovery_integration() {
     
     assert!(result.success);
     assert!(!result.action_taken.is_empty());
-}
\ No newline at end of file
+}
+
+fn health(level: synapsed_swarm::HealthLevel) -> synapsed_swarm::HealthStatus {
+    synapsed_swarm::HealthStatus {
+        overall_status: level,
+        components: std::collections::HashMap::new(),
+        timestamp: chrono::Utc::now(),
+        uptime: Duration::from_secs(60),
+    }
+}
+
+/// Test that health changes drive degradation level transitions
+#[tokio::test]
+async fn test_degradation_policy_transitions() {
+    use synapsed_swarm::HealthLevel;
+    
+    let manager = RecoveryManager::new();
+    manager.set_degradation_policy(DegradationPolicy {
+        reduced_at: HealthLevel::Degraded,
+        minimal_at: HealthLevel::Critical,
+        reduced: DegradationStage {
+            shed_capabilities: vec!["analytics".to_string()],
+            shed_roles: vec![AgentRole::Observer],
+        },
+        minimal: DegradationStage {
+            shed_capabilities: vec!["verification".to_string()],
+            shed_roles: vec![AgentRole::Verifier],
+        },
+    }).await;
+    let degradation = manager.degradation();
+    
+    assert_eq!(manager.degradation_level().await, DegradationLevel::Full);
+    assert!(manager.apply_health(&health(HealthLevel::Healthy)).await.is_none());
+    
+    // Worsening health steps down through the levels
+    let transition = manager.apply_health(&health(HealthLevel::Degraded)).await.unwrap();
+    assert_eq!((transition.from, transition.to), (DegradationLevel::Full, DegradationLevel::Reduced));
+    assert!(!degradation.is_capability_available("analytics").await);
+    assert!(degradation.is_capability_available("verification").await);
+    assert!(!degradation.is_role_active(&AgentRole::Observer).await);
+    assert!(degradation.is_role_active(&AgentRole::Verifier).await);
+    
+    // Unhealthy is still within the reduced level
+    assert!(manager.apply_health(&health(HealthLevel::Unhealthy)).await.is_none());
+    
+    let transition = manager.apply_health(&health(HealthLevel::Critical)).await.unwrap();
+    assert_eq!((transition.from, transition.to), (DegradationLevel::Reduced, DegradationLevel::Minimal));
+    assert!(!degradation.is_capability_available("analytics").await);
+    assert!(!degradation.is_capability_available("verification").await);
+    assert!(!degradation.is_role_active(&AgentRole::Verifier).await);
+    assert!(degradation.is_role_active(&AgentRole::Worker).await);
+    
+    // Recovered health restores full operation
+    let transition = manager.apply_health(&health(HealthLevel::Healthy)).await.unwrap();
+    assert_eq!((transition.from, transition.to), (DegradationLevel::Minimal, DegradationLevel::Full));
+    assert!(degradation.is_capability_available("analytics").await);
+    assert!(degradation.is_capability_available("verification").await);
+    assert!(degradation.is_role_active(&AgentRole::Observer).await);
+}
+
+/// Test that shed roles leave agent selection and level changes are published
+#[tokio::test]
+async fn test_degradation_gates_agent_selection() {
+    use synapsed_intent::{ContextBuilder, IntentBuilder, StepAction};
+    use synapsed_promise::{AgentCapabilities, AgentConfig, AutonomousAgent, QualityOfService};
+    use synapsed_swarm::{CandidateRejection, DelegationIssue, HealthLevel};
+    
+    let coordinator = SwarmCoordinator::new(SwarmConfig::default());
+    coordinator.initialize().await.unwrap();
+    let agent = Arc::new(AutonomousAgent::new(AgentConfig {
+        name: "observer".to_string(),
+        capabilities: AgentCapabilities {
+            services: vec!["test".to_string()],
+            resources: vec!["cpu".to_string()],
+            protocols: vec!["promise".to_string()],
+            quality: QualityOfService::default(),
+        },
+        trust_model: synapsed_promise::TrustModel::new(),
+        cooperation_protocol: synapsed_promise::CooperationProtocol::new(),
+        max_promises: 5,
+        promise_timeout_secs: 60,
+    }));
+    let observer = coordinator.add_agent(agent, AgentRole::Observer).await.unwrap();
+    
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _subscription = coordinator
+        .event_bus()
+        .subscribe(move |event: DegradationTransition| {
+            let _ = tx.send(event);
+        })
+        .await
+        .unwrap();
+    
+    let intent = IntentBuilder::new("Observe")
+        .step("Report", StepAction::Custom(serde_json::json!({"report": true})))
+        .build();
+    let context = ContextBuilder::new().build().await;
+    
+    // The default policy sheds observers once health is degraded
+    coordinator.recovery_manager().apply_health(&health(HealthLevel::Degraded)).await.unwrap();
+    let plan = coordinator.simulate_delegation(&intent, &context).await.unwrap();
+    assert_eq!(plan.selected_agent, None);
+    assert!(plan.issues.contains(&DelegationIssue::NoSuitableAgent));
+    assert!(plan.rejected_agents.contains(&(observer, CandidateRejection::RoleShed(AgentRole::Observer))));
+    
+    let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("Degradation change should be published")
+        .unwrap();
+    assert_eq!((event.from, event.to), (DegradationLevel::Full, DegradationLevel::Reduced));
+    
+    // Restored health makes the observer eligible again
+    coordinator.recovery_manager().apply_health(&health(HealthLevel::Healthy)).await.unwrap();
+    let plan = coordinator.simulate_delegation(&intent, &context).await.unwrap();
+    assert!(!plan.rejected_agents.iter().any(|(id, _)| *id == observer));
+    let event = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await.unwrap().unwrap();
+    assert_eq!(event.to, DegradationLevel::Full);
+}
@@ -10,6 +10,7 @@ use chrono::Utc;
 use uuid::Uuid;
 use tempfile::TempDir;
 use which::which;
+use regex::Regex;
 
 /// Configuration for command verification
 #[derive(Debug, Clone)]
@@ -28,6 +29,8 @@ pub struct CommandVerifierConfig {
     pub allowed_commands: Option<Vec<String>>,
     /// Capture screenshot on failure
     pub capture_on_failure: bool,
+    /// Normalizers applied in order to output and expected output before comparing
+    pub normalizers: Vec<OutputNormalizer>,
 }
 
 impl Default for CommandVerifierConfig {
@@ -40,6 +43,48 @@ impl Default for CommandVerifierConfig {
             env_vars: HashMap::new(),
             allowed_commands: None,
             capture_on_failure: false,
+            normalizers: Vec::new(),
+        }
+    }
+}
+
+/// Transformation masking run-to-run variation in command output
+#[derive(Debug, Clone)]
+pub enum OutputNormalizer {
+    /// Replace every match of a pattern, e.g. timestamps, temp paths or PIDs
+    Replace {
+        pattern: Regex,
+        replacement: String,
+    },
+    /// Sort lines, for output whose order is not deterministic
+    SortLines,
+    /// Collapse runs of whitespace into a single space and trim
+    CollapseWhitespace,
+}
+
+impl OutputNormalizer {
+    /// Creates a normalizer replacing matches of `pattern` with `replacement`
+    pub fn replace(pattern: &str, replacement: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| VerifyError::Other(anyhow::anyhow!("Invalid normalizer pattern: {}", e)))?;
+        Ok(Self::Replace {
+            pattern,
+            replacement: replacement.to_string(),
+        })
+    }
+    
+    /// Applies the normalizer to `text`
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Replace { pattern, replacement } => {
+                pattern.replace_all(text, replacement.as_str()).into_owned()
+            }
+            Self::SortLines => {
+                let mut lines: Vec<&str> = text.lines().collect();
+                lines.sort_unstable();
+                lines.join("\n")
+            }
+            Self::CollapseWhitespace => text.split_whitespace().collect::<Vec<_>>().join(" "),
         }
     }
 }
@@ -254,7 +299,10 @@ impl CommandVerifier {
         
         // Check output content
         if let Some(expected) = expected_output {
-            if !output.stdout.contains(expected) && !output.stderr.contains(expected) {
+            let expected_normalized = self.normalize(expected);
+            if !self.normalize(&output.stdout).contains(&expected_normalized)
+                && !self.normalize(&output.stderr).contains(&expected_normalized)
+            {
                 success = false;
                 error = Some(format!(
                     "Output does not contain expected: '{}'",
@@ -320,6 +368,13 @@ impl CommandVerifier {
         })
     }
     
+    /// Applies the configured normalizers in order
+    fn normalize(&self, text: &str) -> String {
+        self.config.normalizers
+            .iter()
+            .fold(text.to_string(), |text, normalizer| normalizer.apply(&text))
+    }
+    
     /// Executes a command directly (without sandbox)
     async fn execute_direct(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput> {
         let mut command = Command::new(cmd);
@@ -470,4 +525,28 @@ mod tests {
         assert_eq!(results.len(), 2);
         assert!(results.iter().all(|r| r.result.success));
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_normalizer_masks_timestamp() {
+        let command = "date +built-at-%s%N";
+        let expected = "built-at-1700000000000000000";
+        
+        let verifier = CommandVerifier::new();
+        let result = verifier.verify(command, Some(expected), Some(0)).await.unwrap();
+        assert!(!result.result.success);
+        
+        let mut config = CommandVerifierConfig::default();
+        config.normalizers.push(OutputNormalizer::replace(r"built-at-\d+", "built-at-<TIMESTAMP>").unwrap());
+        let verifier = CommandVerifier::with_config(config);
+        let result = verifier.verify(command, Some(expected), Some(0)).await.unwrap();
+        assert!(result.result.success);
+        // The recorded output is left untouched
+        assert!(result.output.stdout.starts_with("built-at-1"));
+    }
+    
+    #[test]
+    fn test_sort_and_collapse_normalizers() {
+        assert_eq!(OutputNormalizer::SortLines.apply("b\nc\na"), "a\nb\nc");
+        assert_eq!(OutputNormalizer::CollapseWhitespace.apply("  a \t b\n\nc "), "a b c");
+    }
+}
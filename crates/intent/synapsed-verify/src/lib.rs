@@ -22,11 +22,11 @@ pub mod strategy;
 pub mod types;
 pub mod observability;
 
-pub use command::{CommandVerifier, CommandVerification, ExecutionSandbox};
+pub use command::{CommandVerifier, CommandVerification, ExecutionSandbox, OutputNormalizer};
 pub use filesystem::{FileSystemVerifier, FileVerification, FileSystemSnapshot};
-pub use network::{NetworkVerifier, NetworkVerification, ApiVerification};
+pub use network::{NetworkVerifier, NetworkVerification, ApiVerification, ResilienceConfig, CircuitState};
 pub use state::{StateVerifier, StateSnapshot, StateDiff};
-pub use proof::{ProofGenerator, VerificationProof, ProofChain};
+pub use proof::{ProofGenerator, VerificationProof, ProofChain, ProofOfWork};
 pub use strategy::{VerificationStrategy, StrategyBuilder, ConsensusVerifier};
 pub use types::*;
 pub use observability::{ObservableVerifier, VerificationEvent, VerificationMetric};
@@ -69,6 +69,9 @@ pub enum VerifyError {
     #[error("Sandbox error: {0}")]
     SandboxError(String),
     
+    #[error("Circuit open: {0}")]
+    CircuitOpen(String),
+    
     #[error("Other error: {0}")]
     Other(#[from] anyhow::Error),
 }
@@ -79,6 +82,7 @@ impl From<VerifyError> for SynapsedError {
             VerifyError::VerificationFailed(msg) => SynapsedError::InvalidInput(msg),
             VerifyError::CommandError(msg) => SynapsedError::Internal(msg),
             VerifyError::NetworkError(msg) => SynapsedError::Network(msg),
+            VerifyError::CircuitOpen(msg) => SynapsedError::Network(msg),
             VerifyError::Timeout(msg) => SynapsedError::Timeout(msg),
             _ => SynapsedError::Internal(err.to_string()),
         }
@@ -1,11 +1,12 @@
 //! Network and API verification for AI agent claims
 
 use crate::{types::*, Result, VerifyError};
-use reqwest::{Client, Response, StatusCode};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use chrono::Utc;
 use uuid::Uuid;
 
@@ -39,6 +40,210 @@ impl Default for NetworkVerifierConfig {
     }
 }
 
+/// Retry budget and circuit breaker settings, tracked per host
+#[derive(Debug, Clone)]
+pub struct ResilienceConfig {
+    /// Retries for a single request that failed with a transport error or 5xx status
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each further retry
+    pub retry_backoff_ms: u64,
+    /// Retries allowed per host within one budget window
+    pub retry_budget: u32,
+    /// Length of the retry budget window
+    pub budget_window_ms: u64,
+    /// Consecutive failed requests after which the host's circuit opens
+    pub failure_threshold: u32,
+    /// How long an open circuit fails fast before a trial request is let through
+    pub cooldown_ms: u64,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            retry_backoff_ms: 100,
+            retry_budget: 10,
+            budget_window_ms: 60000,
+            failure_threshold: 5,
+            cooldown_ms: 30000,
+        }
+    }
+}
+
+/// State of a host's circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests fail fast until the cooldown elapses
+    Open,
+    /// A single trial request decides whether the circuit closes again
+    HalfOpen,
+}
+
+/// Failure tracking for one host
+#[derive(Debug)]
+struct HostCircuit {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Id of the half-open trial request in flight, if any
+    trial: Option<u64>,
+    trials_started: u64,
+    retries_used: u32,
+    budget_window_start: Instant,
+}
+
+impl HostCircuit {
+    fn new() -> Self {
+        Self {
+            consecutive_failures: 0,
+            opened_at: None,
+            trial: None,
+            trials_started: 0,
+            retries_used: 0,
+            budget_window_start: Instant::now(),
+        }
+    }
+}
+
+/// Per-host retry budgets and circuit breakers
+#[derive(Debug)]
+struct CircuitBreaker {
+    config: ResilienceConfig,
+    hosts: Mutex<HashMap<String, HostCircuit>>,
+}
+
+impl CircuitBreaker {
+    fn new(config: ResilienceConfig) -> Self {
+        Self {
+            config,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cooldown(&self) -> Duration {
+        Duration::from_millis(self.config.cooldown_ms)
+    }
+
+    fn state(&self, host: &str) -> CircuitState {
+        let hosts = self.hosts.lock().unwrap();
+        match hosts.get(host) {
+            Some(circuit) if circuit.trial.is_some() => CircuitState::HalfOpen,
+            Some(HostCircuit { opened_at: Some(opened_at), .. }) => {
+                if opened_at.elapsed() >= self.cooldown() {
+                    CircuitState::HalfOpen
+                } else {
+                    CircuitState::Open
+                }
+            }
+            _ => CircuitState::Closed,
+        }
+    }
+
+    /// Admit a request to `host`, failing fast while its circuit is open
+    fn admit(&self, host: &str) -> Result<Admission<'_>> {
+        let mut hosts = self.hosts.lock().unwrap();
+        let circuit = hosts.entry(host.to_string()).or_insert_with(HostCircuit::new);
+        let Some(opened_at) = circuit.opened_at else {
+            return Ok(Admission { breaker: self, host: host.to_string(), trial: None });
+        };
+
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.cooldown() || circuit.trial.is_some() {
+            let remaining = self.cooldown().saturating_sub(elapsed);
+            return Err(VerifyError::CircuitOpen(format!(
+                "{} failed {} consecutive requests, retry in {}ms",
+                host,
+                circuit.consecutive_failures,
+                remaining.as_millis()
+            )));
+        }
+        circuit.trials_started += 1;
+        circuit.trial = Some(circuit.trials_started);
+        Ok(Admission { breaker: self, host: host.to_string(), trial: circuit.trial })
+    }
+
+    /// Spend one retry from the host's budget, if any is left
+    fn try_retry(&self, host: &str) -> bool {
+        let mut hosts = self.hosts.lock().unwrap();
+        let circuit = hosts.entry(host.to_string()).or_insert_with(HostCircuit::new);
+        if circuit.trial.is_some() {
+            return false;
+        }
+        if circuit.budget_window_start.elapsed() >= Duration::from_millis(self.config.budget_window_ms) {
+            circuit.budget_window_start = Instant::now();
+            circuit.retries_used = 0;
+        }
+        if circuit.retries_used >= self.config.retry_budget {
+            return false;
+        }
+        circuit.retries_used += 1;
+        true
+    }
+
+    fn record_success(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap();
+        if let Some(circuit) = hosts.get_mut(host) {
+            circuit.consecutive_failures = 0;
+            circuit.opened_at = None;
+            circuit.trial = None;
+        }
+    }
+
+    fn record_failure(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let circuit = hosts.entry(host.to_string()).or_insert_with(HostCircuit::new);
+        circuit.consecutive_failures += 1;
+        if circuit.trial.is_some() || circuit.consecutive_failures >= self.config.failure_threshold {
+            circuit.opened_at = Some(Instant::now());
+            circuit.trial = None;
+        }
+    }
+
+    /// Release a trial that ended without an outcome, letting the next request try
+    fn abandon_trial(&self, host: &str, trial: u64) {
+        let mut hosts = self.hosts.lock().unwrap();
+        if let Some(circuit) = hosts.get_mut(host) {
+            if circuit.trial == Some(trial) {
+                circuit.trial = None;
+            }
+        }
+    }
+}
+
+/// A request admitted by a [`CircuitBreaker`]
+///
+/// Holds the half-open trial, if this request is one, until it is dropped.
+/// A trial whose future is dropped before it records an outcome is released,
+/// so a cancelled request can't leave the circuit half-open for good.
+struct Admission<'a> {
+    breaker: &'a CircuitBreaker,
+    host: String,
+    trial: Option<u64>,
+}
+
+impl Drop for Admission<'_> {
+    fn drop(&mut self) {
+        if let Some(trial) = self.trial {
+            self.breaker.abandon_trial(&self.host, trial);
+        }
+    }
+}
+
+/// Host and port a request is sent to, used to key circuit breakers
+fn host_key(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|parsed| {
+            let host = parsed.host_str()?.to_string();
+            Some(match parsed.port_or_known_default() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            })
+        })
+        .unwrap_or_else(|| url.to_string())
+}
+
 /// API verification result
 #[derive(Debug, Clone)]
 pub struct ApiVerification {
@@ -101,6 +306,7 @@ pub struct TraceHop {
 pub struct NetworkVerifier {
     client: Client,
     config: NetworkVerifierConfig,
+    breaker: Option<CircuitBreaker>,
 }
 
 impl NetworkVerifier {
@@ -127,6 +333,69 @@ impl NetworkVerifier {
         Self {
             client,
             config,
+            breaker: None,
+        }
+    }
+    
+    /// Retries failed requests within a per-host budget and fails fast
+    /// for hosts whose circuit breaker is open
+    pub fn with_resilience(mut self, config: ResilienceConfig) -> Self {
+        self.breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+    
+    /// Circuit breaker state for the host of `url`
+    pub fn circuit_state(&self, url: &str) -> CircuitState {
+        self.breaker
+            .as_ref()
+            .map(|breaker| breaker.state(&host_key(url)))
+            .unwrap_or(CircuitState::Closed)
+    }
+    
+    /// Sends a request, applying the resilience policy if one is configured
+    ///
+    /// Transport errors and 5xx responses count as failures. Once retries
+    /// are exhausted the last response is returned so callers can report it.
+    async fn send(
+        &self,
+        url: &str,
+        request: impl Fn() -> RequestBuilder,
+    ) -> std::result::Result<Response, reqwest::Error> {
+        let Some(breaker) = &self.breaker else {
+            return request().send().await;
+        };
+        let host = host_key(url);
+        let mut backoff = Duration::from_millis(breaker.config.retry_backoff_ms);
+        let mut retries = 0;
+        
+        loop {
+            let outcome = request().send().await;
+            let failed = match &outcome {
+                Ok(response) => response.status().is_server_error(),
+                Err(_) => true,
+            };
+            if !failed {
+                breaker.record_success(&host);
+                return outcome;
+            }
+            if retries < breaker.config.max_retries && breaker.try_retry(&host) {
+                retries += 1;
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                continue;
+            }
+            breaker.record_failure(&host);
+            return outcome;
+        }
+    }
+    
+    /// Fails fast if the circuit for the host of `url` is open
+    ///
+    /// The returned admission must be held until the request has finished.
+    fn admit(&self, url: &str) -> Result<Option<Admission<'_>>> {
+        match &self.breaker {
+            Some(breaker) => breaker.admit(&host_key(url)).map(Some),
+            None => Ok(None),
         }
     }
     
@@ -137,17 +406,21 @@ impl NetworkVerifier {
         expected_status: u16,
         expected_body: Option<Value>,
     ) -> Result<ApiVerification> {
+        let _admission = self.admit(url)?;
         let start = Utc::now();
         let start_instant = std::time::Instant::now();
         
         // Build request
-        let mut request = self.client.get(url);
-        for (key, value) in &self.config.default_headers {
-            request = request.header(key, value);
-        }
+        let request = || {
+            let mut request = self.client.get(url);
+            for (key, value) in &self.config.default_headers {
+                request = request.header(key, value);
+            }
+            request
+        };
         
         // Send request
-        let response = request.send().await
+        let response = self.send(url, request).await
             .map_err(|e| VerifyError::NetworkError(format!("Request failed: {}", e)))?;
         
         let response_time_ms = start_instant.elapsed().as_millis() as u64;
@@ -344,14 +617,12 @@ impl NetworkVerifier {
         payload: Value,
         expected_response: Option<Value>,
     ) -> Result<ApiVerification> {
+        let _admission = self.admit(webhook_url)?;
         let start = Utc::now();
         let start_instant = std::time::Instant::now();
         
         // Send webhook
-        let response = self.client
-            .post(webhook_url)
-            .json(&payload)
-            .send()
+        let response = self.send(webhook_url, || self.client.post(webhook_url).json(&payload))
             .await
             .map_err(|e| VerifyError::NetworkError(format!("Webhook failed: {}", e)))?;
         
@@ -414,9 +685,8 @@ impl NetworkVerifier {
         &self,
         health_url: &str,
     ) -> Result<bool> {
-        let response = self.client
-            .get(health_url)
-            .send()
+        let _admission = self.admit(health_url)?;
+        let response = self.send(health_url, || self.client.get(health_url))
             .await
             .map_err(|e| VerifyError::NetworkError(format!("Health check failed: {}", e)))?;
         
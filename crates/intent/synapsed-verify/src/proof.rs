@@ -9,6 +9,16 @@ use rand::rngs::OsRng;
 use std::collections::HashMap;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
+/// Highest proof-of-work difficulty accepted, in leading zero bits
+pub const MAX_POW_DIFFICULTY: u32 = 32;
+
+/// Default time allowed for solving a proof-of-work puzzle
+pub const DEFAULT_POW_TIME_BUDGET: Duration = Duration::from_secs(60);
+
+/// Nonces tried between checks of the time budget
+const POW_BUDGET_CHECK_INTERVAL: u64 = 1 << 16;
 
 /// Cryptographic proof of verification
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +37,71 @@ pub struct VerificationProof {
     pub prover: Option<String>,
     /// Proof metadata
     pub metadata: ProofMetadata,
+    /// Proof-of-work bound to the proof's verifications
+    #[serde(default)]
+    pub proof_of_work: Option<ProofOfWork>,
+}
+
+/// Solved proof-of-work puzzle showing effort was spent on a proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofOfWork {
+    /// Required number of leading zero bits in the digest
+    pub difficulty: u32,
+    /// Nonce solving the puzzle
+    pub nonce: u64,
+    /// Hex digest of the challenge and nonce
+    pub digest: String,
+}
+
+impl VerificationProof {
+    /// Checks the proof-of-work solves the puzzle for this proof's verifications
+    ///
+    /// Returns false if there is no proof-of-work, if the verifications no
+    /// longer match the Merkle root, or if the digest misses the difficulty.
+    pub fn verify_pow(&self) -> bool {
+        let Some(pow) = &self.proof_of_work else {
+            return false;
+        };
+        if pow.difficulty > MAX_POW_DIFFICULTY {
+            return false;
+        }
+        if ProofGenerator::calculate_merkle_root(&self.verifications).ok().as_ref() != Some(&self.merkle_root) {
+            return false;
+        }
+        
+        let challenge = self.pow_challenge(pow.difficulty);
+        let digest = pow_digest(&challenge, pow.nonce);
+        leading_zero_bits(&digest) >= pow.difficulty && hex::encode(digest) == pow.digest
+    }
+    
+    /// Challenge binding the puzzle to the proof and its verifications
+    fn pow_challenge(&self, difficulty: u32) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"synapsed-verify-pow");
+        hasher.update(self.id.as_bytes());
+        hasher.update(difficulty.to_le_bytes());
+        hasher.update(self.merkle_root.as_bytes());
+        hasher.update(serde_json::to_vec(&self.verifications).unwrap_or_default());
+        hasher.finalize().to_vec()
+    }
+}
+
+fn pow_digest(challenge: &[u8], nonce: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(challenge);
+    hasher.update(nonce.to_le_bytes());
+    hasher.finalize().into()
+}
+
+fn leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in digest {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
 }
 
 /// Summary of a verification for proof
@@ -113,6 +188,8 @@ pub struct ProofGenerator {
     chains: HashMap<Uuid, ProofChain>,
     /// Individual proofs
     proofs: HashMap<Uuid, VerificationProof>,
+    /// Time allowed for solving a proof-of-work puzzle
+    pow_time_budget: Duration,
 }
 
 impl ProofGenerator {
@@ -122,6 +199,7 @@ impl ProofGenerator {
             signing_key: None,
             chains: HashMap::new(),
             proofs: HashMap::new(),
+            pow_time_budget: DEFAULT_POW_TIME_BUDGET,
         }
     }
     
@@ -134,9 +212,16 @@ impl ProofGenerator {
             signing_key: Some(signing_key),
             chains: HashMap::new(),
             proofs: HashMap::new(),
+            pow_time_budget: DEFAULT_POW_TIME_BUDGET,
         }
     }
     
+    /// Sets the time allowed for solving a proof-of-work puzzle
+    pub fn with_pow_time_budget(mut self, budget: Duration) -> Self {
+        self.pow_time_budget = budget;
+        self
+    }
+    
     /// Generates a proof for verifications
     pub async fn generate_proof(
         &mut self,
@@ -176,7 +261,7 @@ impl ProofGenerator {
             .collect();
         
         // Calculate Merkle root
-        let merkle_root = self.calculate_merkle_root(&summaries)?;
+        let merkle_root = Self::calculate_merkle_root(&summaries)?;
         
         // Sign if signing key available
         let signature = if let Some(ref signing_key) = self.signing_key {
@@ -193,6 +278,7 @@ impl ProofGenerator {
             timestamp,
             prover: metadata.agent_context.clone(),
             metadata,
+            proof_of_work: None,
         };
         
         self.proofs.insert(id, proof.clone());
@@ -200,6 +286,54 @@ impl ProofGenerator {
         Ok(proof)
     }
     
+    /// Generates a proof carrying a proof-of-work at `difficulty` leading zero bits
+    ///
+    /// Each additional bit doubles the expected work. The puzzle is bound to
+    /// the proof ID and its verifications, so it can't be reused for others.
+    /// The search gives up once the generator's time budget is spent.
+    pub async fn generate_proof_with_pow(
+        &mut self,
+        verifications: Vec<VerificationResult>,
+        difficulty: u32,
+    ) -> Result<VerificationProof> {
+        if difficulty > MAX_POW_DIFFICULTY {
+            return Err(VerifyError::ProofError(format!(
+                "Proof-of-work difficulty {} exceeds maximum {}",
+                difficulty, MAX_POW_DIFFICULTY
+            )));
+        }
+        
+        let mut proof = self.generate_proof(verifications).await?;
+        let challenge = proof.pow_challenge(difficulty);
+        let budget = self.pow_time_budget;
+        let pow = tokio::task::spawn_blocking(move || {
+            let started = Instant::now();
+            (0..=u64::MAX)
+                .take_while(|nonce| {
+                    nonce % POW_BUDGET_CHECK_INTERVAL != 0 || started.elapsed() < budget
+                })
+                .find_map(|nonce| {
+                    let digest = pow_digest(&challenge, nonce);
+                    (leading_zero_bits(&digest) >= difficulty).then(|| ProofOfWork {
+                        difficulty,
+                        nonce,
+                        digest: hex::encode(digest),
+                    })
+                })
+        })
+        .await
+        .map_err(|e| VerifyError::ProofError(format!("Proof-of-work task failed: {}", e)))?
+        .ok_or_else(|| VerifyError::ProofError(format!(
+            "No proof-of-work nonce found within {:?}",
+            budget
+        )))?;
+        
+        proof.proof_of_work = Some(pow);
+        self.proofs.insert(proof.id, proof.clone());
+        
+        Ok(proof)
+    }
+    
     /// Creates a new proof chain
     pub async fn create_chain(
         &mut self,
@@ -345,7 +479,7 @@ impl ProofGenerator {
         hasher.finalize().to_hex().to_string()
     }
     
-    fn calculate_merkle_root(&self, summaries: &[VerificationSummary]) -> Result<String> {
+    fn calculate_merkle_root(summaries: &[VerificationSummary]) -> Result<String> {
         if summaries.is_empty() {
             return Ok(String::from("0000000000000000000000000000000000000000000000000000000000000000"));
         }
@@ -495,4 +629,61 @@ mod tests {
         assert_eq!(updated_chain.proofs.len(), 2);
         assert_eq!(updated_chain.head, proof.id);
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_proof_of_work() {
+        let mut generator = ProofGenerator::new();
+        
+        let verifications = vec![
+            VerificationResult::success(
+                VerificationType::Command,
+                serde_json::json!({"command": "test"}),
+                serde_json::json!({"result": "success"}),
+            ),
+            VerificationResult::success(
+                VerificationType::FileSystem,
+                serde_json::json!({"path": "/tmp/out"}),
+                serde_json::json!({"exists": true}),
+            ),
+        ];
+        
+        let proof = generator.generate_proof_with_pow(verifications, 12).await.unwrap();
+        let pow = proof.proof_of_work.as_ref().unwrap();
+        assert_eq!(pow.difficulty, 12);
+        assert!(pow.digest.starts_with("000"));
+        assert!(proof.verify_pow());
+        
+        // Tampering with a verification breaks the binding
+        let mut tampered = proof.clone();
+        tampered.verifications[1].success = false;
+        assert!(!tampered.verify_pow());
+        
+        let mut tampered = proof.clone();
+        tampered.verifications[0].hash = ProofGenerator::hash_verification(&VerificationResult::success(
+            VerificationType::Command,
+            serde_json::json!({"command": "other"}),
+            serde_json::json!({"result": "success"}),
+        ));
+        assert!(!tampered.verify_pow());
+        
+        // Claiming a higher difficulty than was solved fails
+        let mut inflated = proof.clone();
+        inflated.proof_of_work.as_mut().unwrap().difficulty = 30;
+        assert!(!inflated.verify_pow());
+        
+        // Proofs without proof-of-work don't verify
+        let plain = generator.generate_proof(Vec::new()).await.unwrap();
+        assert!(!plain.verify_pow());
+        assert!(generator.generate_proof_with_pow(Vec::new(), MAX_POW_DIFFICULTY + 1).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_proof_of_work_gives_up_after_time_budget() {
+        let mut generator = ProofGenerator::new().with_pow_time_budget(Duration::from_millis(10));
+        
+        let started = Instant::now();
+        let result = generator.generate_proof_with_pow(Vec::new(), MAX_POW_DIFFICULTY).await;
+        assert!(matches!(result, Err(VerifyError::ProofError(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
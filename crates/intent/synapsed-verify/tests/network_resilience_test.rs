@@ -0,0 +1,118 @@
+//! Circuit breaker and retry budget behaviour of the network verifier
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use synapsed_verify::{CircuitState, NetworkVerifier, ResilienceConfig, VerifyError};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Minimal HTTP server answering 503 until marked healthy, counting requests
+///
+/// While `stalled` is set, requests are accepted but never answered.
+#[derive(Clone, Default)]
+struct FlakyServer {
+    healthy: Arc<AtomicBool>,
+    stalled: Arc<AtomicBool>,
+    hits: Arc<AtomicUsize>,
+}
+
+impl FlakyServer {
+    async fn start(&self) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = self.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                server.hits.fetch_add(1, Ordering::SeqCst);
+                let stalled = server.stalled.load(Ordering::SeqCst);
+                let status = if server.healthy.load(Ordering::SeqCst) {
+                    "200 OK"
+                } else {
+                    "503 Service Unavailable"
+                };
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    if stalled {
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        return;
+                    }
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        status
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{}/health", addr)
+    }
+}
+
+fn resilience(cooldown_ms: u64) -> ResilienceConfig {
+    ResilienceConfig {
+        max_retries: 1,
+        retry_backoff_ms: 1,
+        retry_budget: 10,
+        budget_window_ms: 60000,
+        failure_threshold: 2,
+        cooldown_ms,
+    }
+}
+
+#[tokio::test]
+async fn test_circuit_opens_and_recovers_after_cooldown() {
+    let server = FlakyServer::default();
+    let url = server.start().await;
+    let verifier = NetworkVerifier::new().with_resilience(resilience(200));
+
+    // Each failing call is retried once, the second one opens the circuit
+    for _ in 0..2 {
+        let result = verifier.verify_api(&url, 200, None).await.unwrap();
+        assert!(!result.result.success);
+        assert_eq!(result.status_code, 503);
+    }
+    assert_eq!(server.hits.load(Ordering::SeqCst), 4);
+    assert_eq!(verifier.circuit_state(&url), CircuitState::Open);
+
+    // While open, calls fail fast without reaching the host
+    let err = verifier.verify_api(&url, 200, None).await.unwrap_err();
+    assert!(matches!(err, VerifyError::CircuitOpen(_)));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 4);
+
+    // After the cooldown a trial request goes through and closes the circuit
+    server.healthy.store(true, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert_eq!(verifier.circuit_state(&url), CircuitState::HalfOpen);
+    let result = verifier.verify_api(&url, 200, None).await.unwrap();
+    assert!(result.result.success);
+    assert_eq!(verifier.circuit_state(&url), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn test_cancelled_trial_does_not_wedge_circuit() {
+    let server = FlakyServer::default();
+    let url = server.start().await;
+    let verifier = NetworkVerifier::new().with_resilience(resilience(50));
+
+    for _ in 0..2 {
+        verifier.verify_api(&url, 200, None).await.unwrap();
+    }
+    assert_eq!(verifier.circuit_state(&url), CircuitState::Open);
+
+    // The trial request hangs and its caller gives up on it
+    server.stalled.store(true, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let trial = tokio::time::timeout(
+        Duration::from_millis(100),
+        verifier.verify_api(&url, 200, None),
+    ).await;
+    assert!(trial.is_err());
+
+    // The abandoned trial is released and the next request runs a new one
+    server.stalled.store(false, Ordering::SeqCst);
+    server.healthy.store(true, Ordering::SeqCst);
+    let result = verifier.verify_api(&url, 200, None).await.unwrap();
+    assert!(result.result.success);
+    assert_eq!(verifier.circuit_state(&url), CircuitState::Closed);
+}
@@ -5,6 +5,7 @@
 
 use crate::{
     collector::CollectedEvent,
+    views::{SystemHealthView, TaskView, ViewStore},
     MonitorError, Result,
 };
 use synapsed_intent::IntentId;
@@ -81,6 +82,8 @@ pub struct EventAggregator {
     correlated_events: Arc<RwLock<Vec<CorrelatedEvent>>>,
     /// Pattern detection rules
     pattern_detectors: Vec<Box<dyn PatternDetector>>,
+    /// Views kept up to date with processed events
+    views: Option<Arc<ViewStore>>,
 }
 
 impl EventAggregator {
@@ -89,9 +92,21 @@ impl EventAggregator {
             events_by_intent: Arc::new(DashMap::new()),
             correlated_events: Arc::new(RwLock::new(Vec::new())),
             pattern_detectors: Self::create_pattern_detectors(),
+            views: None,
         }
     }
     
+    /// Keep `views` up to date with every processed event
+    pub fn with_views(mut self, views: Arc<ViewStore>) -> Self {
+        self.views = Some(views);
+        self
+    }
+    
+    /// Start keeping `views` up to date, replacing any previous store
+    pub fn set_views(&mut self, views: Arc<ViewStore>) {
+        self.views = Some(views);
+    }
+    
     /// Process a new event
     pub async fn process_event(&self, event: CollectedEvent) -> Result<()> {
         // Extract intent ID from event
@@ -106,9 +121,33 @@ impl EventAggregator {
         // Try to correlate with recent events
         self.correlate_events(intent_id).await?;
         
+        self.update_views(intent_id, &event);
+        
         Ok(())
     }
     
+    /// Fold an event into the task and health views
+    fn update_views(&self, intent_id: IntentId, event: &CollectedEvent) {
+        let Some(views) = &self.views else {
+            return;
+        };
+        
+        let create = || TaskView::new(intent_id, intent_id.0.to_string());
+        match event {
+            CollectedEvent::SubstratesEvent { event_type, timestamp, data, .. } => {
+                views.update_task(intent_id, create, |task| {
+                    if let Some(goal) = data.get("goal").and_then(|goal| goal.as_str()) {
+                        task.name = goal.to_string();
+                    }
+                    task.apply_event(event_type, *timestamp);
+                });
+            },
+            _ => views.update_task(intent_id, create, |_| {}),
+        }
+        
+        views.set_health(SystemHealthView::from_views(&views.tasks(), &views.agents()));
+    }
+    
     /// Extract intent ID from various event types
     fn extract_intent_id(&self, event: &CollectedEvent) -> Result<IntentId> {
         match event {
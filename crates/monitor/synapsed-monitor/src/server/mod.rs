@@ -2,9 +2,12 @@
 
 mod websocket;
 mod rest_api;
+mod views_api;
 
 pub use websocket::{WebSocketHandler, WsMessage};
 pub use rest_api::{create_router, ApiState};
+pub use views_api::{views_router, AgentQuery, ApiError, Page, TaskQuery};
+pub use crate::views::ViewStore;
 
 use crate::{
     collector::ObservabilityCollector,
@@ -44,6 +47,7 @@ pub struct MonitorServer {
     collector: Arc<ObservabilityCollector>,
     aggregator: Arc<RwLock<EventAggregator>>,
     narrator: Arc<EventNarrator>,
+    views: Arc<ViewStore>,
     router: Router,
 }
 
@@ -55,6 +59,7 @@ impl MonitorServer {
         aggregator: Arc<RwLock<EventAggregator>>,
         narrator: Arc<EventNarrator>,
     ) -> Self {
+        let views = Arc::new(ViewStore::new());
         let api_state = ApiState {
             collector: collector.clone(),
             aggregator: aggregator.clone(),
@@ -63,6 +68,7 @@ impl MonitorServer {
             storage_path: std::env::var("SYNAPSED_INTENT_STORAGE_PATH")
                 .ok()
                 .map(std::path::PathBuf::from),
+            views: views.clone(),
         };
         
         let router = create_router(api_state);
@@ -72,10 +78,32 @@ impl MonitorServer {
             collector,
             aggregator,
             narrator,
+            views,
             router,
         }
     }
     
+    /// Store of the views served under `/v1`
+    pub fn views(&self) -> Arc<ViewStore> {
+        self.views.clone()
+    }
+    
+    /// Feed collected events to the aggregator, which keeps the views up to date
+    async fn spawn_event_pump(&self) -> tokio::task::JoinHandle<()> {
+        self.aggregator.write().await.set_views(self.views.clone());
+        
+        let receiver = self.collector.get_receiver();
+        let aggregator = self.aggregator.clone();
+        tokio::spawn(async move {
+            let mut receiver = receiver.write().await;
+            while let Some(event) = receiver.recv().await {
+                if let Err(e) = aggregator.read().await.process_event(event).await {
+                    tracing::debug!("Skipping event the aggregator cannot place: {}", e);
+                }
+            }
+        })
+    }
+    
     /// Start the server
     pub async fn start(self) -> Result<()> {
         let addr: SocketAddr = format!("{}:{}", self.config.host, self.config.port)
@@ -86,9 +114,12 @@ impl MonitorServer {
         
         let listener = tokio::net::TcpListener::bind(&addr).await
             .map_err(|e| crate::MonitorError::ServerError(e.to_string()))?;
-        axum::serve(listener, self.router)
+        let pump = self.spawn_event_pump().await;
+        let served = axum::serve(listener, self.router)
             .await
-            .map_err(|e| crate::MonitorError::ServerError(e.to_string()))?;
+            .map_err(|e| crate::MonitorError::ServerError(e.to_string()));
+        pump.abort();
+        served?;
         
         Ok(())
     }
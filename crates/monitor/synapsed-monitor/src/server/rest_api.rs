@@ -4,8 +4,9 @@ use crate::{
     collector::ObservabilityCollector,
     aggregator::EventAggregator,
     narrator::{EventNarrator, NarrativeStyle},
-    views::{TaskView, AgentView, SystemHealthView, SystemMetrics},
+    views::{TaskView, AgentView, SystemHealthView, SystemMetrics, ViewStore},
     server::websocket::{WebSocketHandler, WsMessage},
+    server::views_api::views_router,
 };
 use axum::{
     extract::{Path, Query, State, ws::WebSocketUpgrade},
@@ -28,6 +29,7 @@ pub struct ApiState {
     pub narrator: Arc<EventNarrator>,
     pub ws_handler: Arc<RwLock<WebSocketHandler>>,
     pub storage_path: Option<PathBuf>,
+    pub views: Arc<ViewStore>,
 }
 
 // Ensure ApiState implements Send + Sync for Axum handlers
@@ -42,6 +44,8 @@ pub struct TestState {
 
 /// Create the API router
 pub fn create_router(state: ApiState) -> Router {
+    let views = state.views.clone();
+    
     Router::new()
         // Health check
         .route("/health", get(health_check))
@@ -89,6 +93,9 @@ pub fn create_router(state: ApiState) -> Router {
         // Add state
         .with_state(state)
         
+        // Versioned view API
+        .nest("/v1", views_router(views))
+        
         // Add CORS support
         .layer(CorsLayer::permissive())
 }
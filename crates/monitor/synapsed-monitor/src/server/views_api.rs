@@ -0,0 +1,331 @@
+//! Versioned JSON API over the task, agent and health views
+//!
+//! Mounted under `/v1` by [`create_router`](super::create_router):
+//!
+//! | Route | Response |
+//! |-------|----------|
+//! | `GET /v1/tasks?status=&agent=&offset=&limit=` | [`Page`] of [`TaskView`] |
+//! | `GET /v1/tasks/:id` | [`TaskView`] |
+//! | `GET /v1/agents?status=&min_trust=&offset=&limit=` | [`Page`] of [`AgentView`] |
+//! | `GET /v1/agents/:id` | [`AgentView`] |
+//! | `GET /v1/health` | [`SystemHealthView`] |
+//!
+//! Views are serialized exactly as their serde derives define them, so the
+//! view structs are the schema. `status` filters take the enum variant name
+//! (e.g. `Executing`), `agent` matches tasks with that agent assigned, and
+//! `min_trust` is a score between 0.0 and 1.0. Lists are ordered by task name
+//! or agent id so pages are stable. Every failure returns an [`ApiError`] body.
+
+use crate::views::{AgentStatus, AgentView, SystemHealthView, TaskStatus, TaskView, ViewStore};
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use synapsed_intent::IntentId;
+use uuid::Uuid;
+
+/// Page size used when `limit` is not given
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// Largest page size a client can request
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+/// One page of a list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items matching the filters, across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<T> Page<T> {
+    fn of(matching: Vec<T>, offset: Option<usize>, limit: Option<usize>) -> Self {
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+        let total = matching.len();
+        let items = matching.into_iter().skip(offset).take(limit).collect();
+        Self { items, total, offset, limit }
+    }
+}
+
+/// Error body returned with every non-2xx response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiError {
+    /// Machine-readable code: `bad_request`, `not_found` or `unavailable`
+    pub error: String,
+    pub message: String,
+    #[serde(skip)]
+    status: Option<StatusCode>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, error: &str, message: impl Into<String>) -> Self {
+        Self {
+            error: error.to_string(),
+            message: message.into(),
+            status: Some(status),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", message)
+    }
+
+    fn unavailable(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, "unavailable", message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self)).into_response()
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+/// Filters for `GET /v1/tasks`
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskQuery {
+    pub status: Option<TaskStatus>,
+    /// Only tasks this agent is assigned to
+    pub agent: Option<String>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// Filters for `GET /v1/agents`
+#[derive(Debug, Default, Deserialize)]
+pub struct AgentQuery {
+    pub status: Option<AgentStatus>,
+    pub min_trust: Option<f32>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// Router for the `/v1` API
+pub fn views_router(store: Arc<ViewStore>) -> Router {
+    Router::new()
+        .route("/tasks", get(list_tasks))
+        .route("/tasks/:id", get(get_task))
+        .route("/agents", get(list_agents))
+        .route("/agents/:id", get(get_agent))
+        .route("/health", get(get_health))
+        .with_state(store)
+}
+
+async fn list_tasks(
+    State(store): State<Arc<ViewStore>>,
+    query: Result<Query<TaskQuery>, QueryRejection>,
+) -> Result<Json<Page<TaskView>>, ApiError> {
+    let Query(query) = query?;
+    let matching = store
+        .tasks()
+        .into_iter()
+        .filter(|task| query.status.as_ref().is_none_or(|status| &task.status == status))
+        .filter(|task| {
+            query.agent.as_ref().is_none_or(|agent| {
+                task.agents.iter().any(|assignment| &assignment.agent_id == agent)
+            })
+        })
+        .collect();
+    Ok(Json(Page::of(matching, query.offset, query.limit)))
+}
+
+async fn get_task(
+    State(store): State<Arc<ViewStore>>,
+    Path(id): Path<String>,
+) -> Result<Json<TaskView>, ApiError> {
+    let task_id = Uuid::parse_str(&id)
+        .map(IntentId)
+        .map_err(|_| ApiError::bad_request(format!("'{}' is not a valid task id", id)))?;
+    store
+        .task(&task_id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No task with id '{}'", id)))
+}
+
+async fn list_agents(
+    State(store): State<Arc<ViewStore>>,
+    query: Result<Query<AgentQuery>, QueryRejection>,
+) -> Result<Json<Page<AgentView>>, ApiError> {
+    let Query(query) = query?;
+    let matching = store
+        .agents()
+        .into_iter()
+        .filter(|agent| query.status.as_ref().is_none_or(|status| &agent.status == status))
+        .filter(|agent| query.min_trust.is_none_or(|min| agent.trust.score >= min))
+        .collect();
+    Ok(Json(Page::of(matching, query.offset, query.limit)))
+}
+
+async fn get_agent(
+    State(store): State<Arc<ViewStore>>,
+    Path(id): Path<String>,
+) -> Result<Json<AgentView>, ApiError> {
+    store
+        .agent(&id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No agent with id '{}'", id)))
+}
+
+async fn get_health(State(store): State<Arc<ViewStore>>) -> Result<Json<SystemHealthView>, ApiError> {
+    store
+        .health()
+        .map(Json)
+        .ok_or_else(|| ApiError::unavailable("No system health has been reported yet"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregator::EventAggregator;
+    use crate::collector::CollectedEvent;
+    use axum::body::{to_bytes, Body};
+    use chrono::Utc;
+    use synapsed_intent::EventType;
+    use axum::http::Request;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn agent_json(id: &str, status: &str, trust: f32) -> Value {
+        json!({
+            "agent_id": id,
+            "name": "API Architect",
+            "agent_type": "architect",
+            "status": status,
+            "trust": {
+                "score": trust,
+                "category": "HighlyTrusted",
+                "factors": [],
+                "visual": "⭐⭐⭐⭐",
+            },
+            "current_activity": "Designing endpoints",
+            "capabilities": {
+                "declared_tools": ["read_file"],
+                "used_tools": ["read_file"],
+                "inferred_capabilities": [],
+                "tool_frequency": {"read_file": 3},
+                "permission_level": "ReadOnly",
+            },
+            "behavior": {
+                "patterns": [],
+                "typical_duration": [90, 0],
+                "active_hours": [9, 10],
+                "resource_usage": {"avg_cpu": 0.5, "avg_memory": 0.25, "avg_network": 0.0},
+            },
+            "performance": {
+                "tasks_completed": 12,
+                "success_rate": 0.75,
+                "avg_duration": [60, 0],
+                "error_rate": 0.25,
+                "retry_rate": 0.0,
+            },
+            "anomalies": [],
+            "trust_history": [],
+        })
+    }
+
+    fn store_with_agents() -> Arc<ViewStore> {
+        let store = Arc::new(ViewStore::new());
+        for (id, status, trust) in [("architect", "Active", 0.75), ("backend", "Idle", 0.5), ("tester", "Active", 0.25)] {
+            store.upsert_agent(serde_json::from_value(agent_json(id, status, trust)).unwrap());
+        }
+        store
+    }
+
+    async fn get_json(store: Arc<ViewStore>, uri: &str) -> (StatusCode, Value) {
+        let response = views_router(store)
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_get_agent() {
+        let store = store_with_agents();
+
+        let (status, body) = get_json(store.clone(), "/agents/architect").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, agent_json("architect", "Active", 0.75));
+
+        let (status, body) = get_json(store, "/agents/nobody").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body, json!({"error": "not_found", "message": "No agent with id 'nobody'"}));
+    }
+
+    #[tokio::test]
+    async fn test_list_agents_filters_and_pages() {
+        let store = store_with_agents();
+
+        let (status, body) = get_json(store.clone(), "/agents?status=Active&limit=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["limit"], 1);
+        assert_eq!(body["items"].as_array().unwrap().len(), 1);
+        assert_eq!(body["items"][0]["agent_id"], "architect");
+
+        let (_, body) = get_json(store.clone(), "/agents?min_trust=0.5&offset=1").await;
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["items"][0]["agent_id"], "backend");
+
+        let (status, body) = get_json(store, "/agents?status=Sleeping").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "bad_request");
+    }
+
+    #[tokio::test]
+    async fn test_health_unavailable_until_reported() {
+        let (status, body) = get_json(Arc::new(ViewStore::new()), "/health").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"], "unavailable");
+    }
+
+    #[tokio::test]
+    async fn test_health_reported_once_aggregator_sees_events() {
+        let store = Arc::new(ViewStore::new());
+        let aggregator = EventAggregator::new().with_views(store.clone());
+        let intent_id = IntentId(Uuid::new_v4());
+        for event_type in [EventType::Started, EventType::Completed] {
+            aggregator
+                .process_event(CollectedEvent::SubstratesEvent {
+                    intent_id,
+                    event_type,
+                    timestamp: Utc::now(),
+                    data: json!({"goal": "Build TODO REST API"}),
+                })
+                .await
+                .unwrap();
+        }
+
+        let (status, body) = get_json(store.clone(), "/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "Healthy");
+        assert_eq!(body["metrics"]["active_tasks"], 0);
+
+        let (status, body) = get_json(store, &format!("/tasks/{}", intent_id.0)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["name"], "Build TODO REST API");
+        assert_eq!(body["status"], "Completed");
+        assert_eq!(body["timeline"].as_array().unwrap().len(), 2);
+    }
+}
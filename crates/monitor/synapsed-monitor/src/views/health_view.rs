@@ -7,6 +7,8 @@ use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::{AgentStatus, AgentView, TaskStatus, TaskView};
+
 /// Complete system health overview
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemHealthView {
@@ -316,6 +318,39 @@ pub enum Priority {
 }
 
 impl SystemHealthView {
+    /// Summarise system health from the tasks and agents seen so far
+    ///
+    /// The score drops with the share of finished tasks that failed.
+    pub fn from_views(tasks: &[TaskView], agents: &[AgentView]) -> Self {
+        let finished = tasks.iter().filter(|t| t.status.is_terminal()).count();
+        let failed = tasks.iter().filter(|t| t.status == TaskStatus::Failed).count();
+        let failure_rate = if finished == 0 { 0.0 } else { failed as f32 / finished as f32 };
+        let health_score = 100.0 * (1.0 - failure_rate);
+        
+        Self {
+            status: HealthStatus::from_score(health_score),
+            health_score,
+            services: Vec::new(),
+            metrics: SystemMetrics {
+                cpu_usage: 0.0,
+                memory_usage: 0.0,
+                disk_usage: 0.0,
+                network_bandwidth: 0.0,
+                active_tasks: tasks.iter().filter(|t| t.status.is_active()).count(),
+                queued_tasks: tasks.iter().filter(|t| t.status == TaskStatus::Pending).count(),
+                active_agents: agents.iter().filter(|a| a.status == AgentStatus::Active).count(),
+                total_agents: agents.len(),
+                uptime: Duration::zero(),
+                request_rate: 0.0,
+                avg_response_time: 0.0,
+            },
+            alerts: Vec::new(),
+            incidents: Vec::new(),
+            recommendations: Vec::new(),
+            last_updated: Utc::now(),
+        }
+    }
+    
     /// Create a health summary message
     pub fn summary(&self) -> String {
         let active_alerts = self.alerts.len();
@@ -3,9 +3,11 @@
 mod task_view;
 mod agent_view;
 mod health_view;
+mod store;
 
 pub use task_view::{TaskView, TaskStatus, TaskPhase};
 pub use agent_view::{AgentView, AgentStatus, TrustLevel};
 pub use health_view::{SystemHealthView, HealthStatus, ServiceHealth, ServiceStatus, ServiceType, SystemMetrics};
+pub use store::ViewStore;
 
 use serde::{Deserialize, Serialize};
\ No newline at end of file
@@ -0,0 +1,81 @@
+//! Store of the latest views, filled by the
+//! [`EventAggregator`](crate::aggregator::EventAggregator) and served by the
+//! `/v1` API
+
+use super::{AgentView, SystemHealthView, TaskView};
+use dashmap::DashMap;
+use std::sync::RwLock;
+use synapsed_intent::IntentId;
+
+/// Latest task, agent and health views
+#[derive(Debug, Default)]
+pub struct ViewStore {
+    tasks: DashMap<IntentId, TaskView>,
+    agents: DashMap<String, AgentView>,
+    health: RwLock<Option<SystemHealthView>>,
+}
+
+impl ViewStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert or replace a task's view
+    pub fn upsert_task(&self, task: TaskView) {
+        self.tasks.insert(task.task_id, task);
+    }
+
+    /// Update a task's view in place, creating it with `create` if missing
+    pub fn update_task(
+        &self,
+        id: IntentId,
+        create: impl FnOnce() -> TaskView,
+        update: impl FnOnce(&mut TaskView),
+    ) {
+        update(self.tasks.entry(id).or_insert_with(create).value_mut());
+    }
+
+    pub fn remove_task(&self, id: &IntentId) -> Option<TaskView> {
+        self.tasks.remove(id).map(|(_, task)| task)
+    }
+
+    pub fn task(&self, id: &IntentId) -> Option<TaskView> {
+        self.tasks.get(id).map(|task| task.clone())
+    }
+
+    /// All tasks, ordered by name
+    pub fn tasks(&self) -> Vec<TaskView> {
+        let mut tasks: Vec<TaskView> = self.tasks.iter().map(|task| task.clone()).collect();
+        tasks.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.task_id.0.cmp(&b.task_id.0)));
+        tasks
+    }
+
+    /// Insert or replace an agent's view
+    pub fn upsert_agent(&self, agent: AgentView) {
+        self.agents.insert(agent.agent_id.clone(), agent);
+    }
+
+    pub fn remove_agent(&self, id: &str) -> Option<AgentView> {
+        self.agents.remove(id).map(|(_, agent)| agent)
+    }
+
+    pub fn agent(&self, id: &str) -> Option<AgentView> {
+        self.agents.get(id).map(|agent| agent.clone())
+    }
+
+    /// All agents, ordered by id
+    pub fn agents(&self) -> Vec<AgentView> {
+        let mut agents: Vec<AgentView> = self.agents.iter().map(|agent| agent.clone()).collect();
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        agents
+    }
+
+    /// Replace the system health view
+    pub fn set_health(&self, health: SystemHealthView) {
+        *self.health.write().unwrap() = Some(health);
+    }
+
+    pub fn health(&self) -> Option<SystemHealthView> {
+        self.health.read().unwrap().clone()
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides a human-readable view of task execution,
 //! showing the journey from planning through completion.
 
-use synapsed_intent::{IntentId, HierarchicalIntent};
+use synapsed_intent::{EventType, IntentId, HierarchicalIntent};
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -222,12 +222,12 @@ pub struct TaskMetrics {
 }
 
 impl TaskView {
-    /// Create a new task view from an intent
-    pub fn from_intent(intent: &HierarchicalIntent) -> Self {
+    /// Create a pending task view with nothing recorded yet
+    pub fn new(task_id: IntentId, name: impl Into<String>) -> Self {
         Self {
-            task_id: intent.id(),
-            name: intent.goal().to_string(),
-            description: intent.description.clone().unwrap_or_default(),
+            task_id,
+            name: name.into(),
+            description: String::new(),
             status: TaskStatus::Pending,
             phase: TaskPhase::Initialization,
             progress: 0.0,
@@ -258,6 +258,61 @@ impl TaskView {
         }
     }
     
+    /// Create a new task view from an intent
+    pub fn from_intent(intent: &HierarchicalIntent) -> Self {
+        Self {
+            description: intent.description.clone().unwrap_or_default(),
+            ..Self::new(intent.id(), intent.goal())
+        }
+    }
+    
+    /// Update status, phase and metrics from an intent execution event
+    pub fn apply_event(&mut self, event_type: &EventType, timestamp: DateTime<Utc>) {
+        let severity = match event_type {
+            EventType::Started | EventType::StepStarted => {
+                self.status = TaskStatus::Executing;
+                self.phase = TaskPhase::Execution;
+                EventSeverity::Info
+            }
+            EventType::StepCompleted | EventType::CheckpointCreated => EventSeverity::Info,
+            EventType::VerificationPerformed => {
+                self.phase = TaskPhase::PostconditionCheck;
+                EventSeverity::Info
+            }
+            EventType::StepFailed => {
+                self.metrics.error_count += 1;
+                EventSeverity::Warning
+            }
+            EventType::RollbackInitiated => {
+                self.metrics.retry_count += 1;
+                EventSeverity::Warning
+            }
+            EventType::Completed => {
+                self.status = TaskStatus::Completed;
+                self.phase = TaskPhase::Cleanup;
+                self.progress = 100.0;
+                EventSeverity::Info
+            }
+            EventType::Failed => {
+                self.status = TaskStatus::Failed;
+                self.phase = TaskPhase::Cleanup;
+                EventSeverity::Error
+            }
+        };
+        
+        if let Some(started) = self.timeline.first().map(|e| e.timestamp) {
+            self.metrics.execution_time = timestamp.signed_duration_since(started).max(Duration::zero());
+        }
+        self.add_event(TimelineEvent {
+            timestamp,
+            event_type: format!("{:?}", event_type),
+            description: format!("{:?}", event_type),
+            agent_id: None,
+            severity,
+            context: HashMap::new(),
+        });
+    }
+    
     /// Update progress based on completed sub-tasks
     pub fn update_progress(&mut self, completed: usize, total: usize) {
         if total > 0 {
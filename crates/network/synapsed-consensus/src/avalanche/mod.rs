@@ -0,0 +1,444 @@
+//! Avalanche consensus protocol implementation
+//!
+//! Avalanche is a leaderless protocol that decides a DAG of transactions by
+//! repeated random subsampling. Each poll asks `k` randomly chosen peers which
+//! transaction they prefer among the conflict set of a transaction; when at
+//! least `alpha` of them name the same one, it and its ancestors gain
+//! confidence. Conflicting transactions form a Snowball instance: the member
+//! with the most confidence is preferred, and it is accepted after winning
+//! `beta2` consecutive polls. A transaction without conflicts is accepted
+//! early, once its parents are accepted and its confidence reaches `beta1`.
+//!
+//! ## Key Features
+//! - **Leaderless**: every node polls independently, there are no views
+//! - **Metastable convergence**: sampling amplifies small preference majorities
+//! - **Probabilistic finality**: safety failures become exponentially unlikely
+//!   as the thresholds grow
+
+pub mod types;
+
+use crate::{
+    Block, NodeId, Vote, ViewNumber, Transaction,
+    ConsensusError, Result,
+    traits::{ConsensusProtocol, ConsensusStats, ConsensusConfig},
+};
+use async_trait::async_trait;
+use futures::future::join_all;
+use parking_lot::RwLock;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+pub use self::types::*;
+
+/// Asks peers for their preferences during polls
+#[async_trait]
+pub trait AvalancheQuerier: Send + Sync {
+    /// Ask `peer` which transaction it prefers among those conflicting with `transaction`
+    ///
+    /// Peers answer `None` when they have no strongly preferred transaction in
+    /// the conflict set, e.g. because they don't know the transaction yet.
+    async fn query(&self, peer: &NodeId, transaction: &Transaction) -> Result<Option<Uuid>>;
+}
+
+/// Avalanche consensus protocol implementation
+pub struct AvalancheConsensus<Q>
+where
+    Q: AvalancheQuerier,
+{
+    /// Node identifier
+    node_id: NodeId,
+    /// Sampling parameters
+    params: AvalancheConfig,
+    /// Peers that can be sampled
+    peers: Vec<NodeId>,
+    /// Transaction DAG and Snowball instances
+    state: Arc<RwLock<AvalancheState>>,
+    /// Peer query transport
+    querier: Arc<Q>,
+    /// Running state
+    is_running: Arc<RwLock<bool>>,
+    /// Statistics
+    stats: Arc<RwLock<ConsensusStats>>,
+}
+
+impl<Q> AvalancheConsensus<Q>
+where
+    Q: AvalancheQuerier + 'static,
+{
+    /// Create a new Avalanche consensus instance
+    ///
+    /// Every validator other than this node can be sampled, so there must be
+    /// at least `sample_size` of them.
+    pub fn new(config: ConsensusConfig, params: AvalancheConfig, querier: Arc<Q>) -> Result<Self> {
+        params.validate()?;
+
+        let node_id = config.node_id.clone();
+        let peers: Vec<NodeId> = config.validators
+            .iter()
+            .filter(|validator| **validator != node_id)
+            .cloned()
+            .collect();
+        if peers.len() < params.sample_size {
+            return Err(ConsensusError::InvalidConfiguration(format!(
+                "Sample size {} exceeds the {} available peers",
+                params.sample_size,
+                peers.len()
+            )));
+        }
+
+        Ok(Self {
+            node_id,
+            params,
+            peers,
+            state: Arc::new(RwLock::new(AvalancheState::new())),
+            querier,
+            is_running: Arc::new(RwLock::new(false)),
+            stats: Arc::new(RwLock::new(ConsensusStats::default())),
+        })
+    }
+
+    /// Add a transaction to the DAG
+    ///
+    /// The transaction conflicts with every transaction added with the same
+    /// `conflict_key`; without a key it conflicts with nothing. Adding a known
+    /// transaction again has no effect.
+    pub fn add_transaction(
+        &self,
+        transaction: Transaction,
+        parents: Vec<Uuid>,
+        conflict_key: Option<ConflictKey>,
+    ) -> Result<()> {
+        let mut state = self.state.write();
+        let tx_id = transaction.id;
+        if state.vertices.contains_key(&tx_id) {
+            return Ok(());
+        }
+        if let Some(parent) = parents.iter().find(|parent| !state.vertices.contains_key(parent)) {
+            return Err(ConsensusError::ValidationFailed(format!(
+                "Transaction {} has unknown parent {}",
+                tx_id, parent
+            )));
+        }
+
+        let conflict_key = conflict_key.unwrap_or_else(|| tx_id.as_bytes().to_vec());
+        for parent in &parents {
+            if let Some(vertex) = state.vertices.get_mut(parent) {
+                vertex.children.push(tx_id);
+            }
+        }
+        state.vertices.insert(tx_id, Vertex {
+            transaction,
+            parents: parents.clone(),
+            children: Vec::new(),
+            conflict_key: conflict_key.clone(),
+            confidence: 0,
+            queried: false,
+            status: TransactionStatus::Processing,
+        });
+        state.order.push(tx_id);
+
+        let decided = match state.conflict_sets.get_mut(&conflict_key) {
+            Some(set) => {
+                set.members.push(tx_id);
+                set.accepted.is_some()
+            }
+            None => {
+                state.conflict_sets.insert(conflict_key, ConflictSet::new(tx_id));
+                false
+            }
+        };
+
+        // Transactions conflicting with an accepted one, or building on a
+        // rejected one, can never be accepted
+        let rejected_parent = parents
+            .iter()
+            .any(|parent| state.vertices[parent].status == TransactionStatus::Rejected);
+        if decided || rejected_parent {
+            state.reject(tx_id);
+        }
+
+        debug!("Node {} added transaction {}", self.node_id, tx_id);
+        Ok(())
+    }
+
+    /// Decision status of a transaction
+    pub fn status(&self, tx_id: &Uuid) -> Option<TransactionStatus> {
+        self.state.read().vertices.get(tx_id).map(|vertex| vertex.status)
+    }
+
+    /// Whether a transaction is preferred over its conflicts
+    pub fn is_preferred(&self, tx_id: &Uuid) -> bool {
+        self.state.read().is_preferred(tx_id)
+    }
+
+    /// Whether a transaction and all its ancestors are preferred
+    pub fn is_strongly_preferred(&self, tx_id: &Uuid) -> bool {
+        self.state.read().is_strongly_preferred(tx_id)
+    }
+
+    /// This node's answer to a peer's query about a transaction
+    ///
+    /// Returns the accepted member of the transaction's conflict set, or else
+    /// its preferred member if that is strongly preferred.
+    pub fn preference(&self, tx_id: &Uuid) -> Option<Uuid> {
+        let state = self.state.read();
+        let set = state.conflict_sets.get(&state.vertices.get(tx_id)?.conflict_key)?;
+        match set.accepted {
+            Some(accepted) => Some(accepted),
+            None => state.is_strongly_preferred(&set.preferred).then_some(set.preferred),
+        }
+    }
+
+    /// Accepted transactions in acceptance order
+    pub fn accepted_transactions(&self) -> Vec<Transaction> {
+        let state = self.state.read();
+        state.accepted
+            .iter()
+            .map(|id| state.vertices[id].transaction.clone())
+            .collect()
+    }
+
+    /// Whether every known transaction is accepted or rejected
+    pub fn is_decided(&self) -> bool {
+        self.state.read()
+            .vertices
+            .values()
+            .all(|vertex| vertex.status != TransactionStatus::Processing)
+    }
+
+    /// Run one poll
+    ///
+    /// Transactions that were never polled go first; after that the preferred
+    /// members of undecided conflict sets are polled in turn. Returns the
+    /// polled transaction, or `None` if nothing is left to decide.
+    pub async fn poll(&self) -> Result<Option<Uuid>> {
+        if !*self.is_running.read() {
+            return Err(ConsensusError::NotStarted);
+        }
+
+        let Some(transaction) = self.next_poll_target() else {
+            return Ok(None);
+        };
+        let sample: Vec<NodeId> = self.peers
+            .choose_multiple(&mut rand::thread_rng(), self.params.sample_size)
+            .cloned()
+            .collect();
+
+        let responses = join_all(sample.iter().map(|peer| self.querier.query(peer, &transaction))).await;
+        let mut tally: HashMap<Uuid, usize> = HashMap::new();
+        let mut received = 0;
+        for response in responses {
+            match response {
+                Ok(Some(preferred)) => {
+                    received += 1;
+                    *tally.entry(preferred).or_default() += 1;
+                }
+                Ok(None) => received += 1,
+                Err(e) => debug!("Peer query for transaction {} failed: {}", transaction.id, e),
+            }
+        }
+        let winner = tally
+            .into_iter()
+            .find(|(_, votes)| *votes >= self.params.quorum_size)
+            .map(|(preferred, _)| preferred);
+
+        {
+            let mut stats = self.stats.write();
+            stats.messages_sent += sample.len() as u64;
+            stats.messages_received += received as u64;
+        }
+
+        let newly_accepted = self.record_poll(transaction.id, winner);
+        if newly_accepted > 0 {
+            let mut stats = self.stats.write();
+            stats.transactions_processed += newly_accepted as u64;
+        }
+
+        Ok(Some(transaction.id))
+    }
+
+    /// Pick the transaction to poll next
+    fn next_poll_target(&self) -> Option<Transaction> {
+        let mut state = self.state.write();
+        let unqueried = state.order
+            .iter()
+            .find(|id| {
+                let vertex = &state.vertices[*id];
+                vertex.status == TransactionStatus::Processing && !vertex.queried
+            })
+            .copied();
+
+        let target = unqueried.or_else(|| {
+            let undecided: Vec<Uuid> = state.order
+                .iter()
+                .filter(|id| {
+                    let vertex = &state.vertices[*id];
+                    vertex.status == TransactionStatus::Processing && state.is_preferred(id)
+                })
+                .copied()
+                .collect();
+            (!undecided.is_empty()).then(|| undecided[state.polls as usize % undecided.len()])
+        })?;
+
+        state.polls += 1;
+        let vertex = state.vertices.get_mut(&target)?;
+        vertex.queried = true;
+        Some(vertex.transaction.clone())
+    }
+
+    /// Apply a poll's outcome and accept transactions that reached finality
+    ///
+    /// Returns the number of newly accepted transactions.
+    fn record_poll(&self, polled: Uuid, winner: Option<Uuid>) -> usize {
+        let mut state = self.state.write();
+
+        let winner = winner.filter(|id| {
+            state.vertices.get(id).is_some_and(|vertex| vertex.status == TransactionStatus::Processing)
+        });
+        match winner {
+            Some(winner) => {
+                // A vote for a transaction is a vote for its ancestors too
+                for id in state.processing_ancestry(&winner) {
+                    let vertex = state.vertices.get_mut(&id).expect("ancestry holds known transactions");
+                    vertex.confidence += 1;
+                    let confidence = vertex.confidence;
+                    let key = vertex.conflict_key.clone();
+
+                    let preferred_confidence = {
+                        let set = &state.conflict_sets[&key];
+                        state.vertices[&set.preferred].confidence
+                    };
+                    let set = state.conflict_sets.get_mut(&key).expect("vertices belong to a conflict set");
+                    if confidence > preferred_confidence {
+                        set.preferred = id;
+                    }
+                    if set.last == id {
+                        set.count += 1;
+                    } else {
+                        set.last = id;
+                        set.count = 1;
+                    }
+                }
+            }
+            None => {
+                let key = state.vertices[&polled].conflict_key.clone();
+                if let Some(set) = state.conflict_sets.get_mut(&key) {
+                    set.count = 0;
+                }
+            }
+        }
+
+        self.accept_finalized(&mut state)
+    }
+
+    /// Accept every transaction meeting an acceptance rule
+    fn accept_finalized(&self, state: &mut AvalancheState) -> usize {
+        let mut accepted = 0;
+        loop {
+            let next = state.order.iter().copied().find(|id| {
+                let vertex = &state.vertices[id];
+                if vertex.status != TransactionStatus::Processing {
+                    return false;
+                }
+                let parents_accepted = vertex.parents
+                    .iter()
+                    .all(|parent| state.vertices[parent].status == TransactionStatus::Accepted);
+                let set = &state.conflict_sets[&vertex.conflict_key];
+
+                let virtuous = set.members.len() == 1
+                    && vertex.confidence >= self.params.virtuous_threshold;
+                let settled = set.preferred == *id
+                    && set.last == *id
+                    && set.count >= self.params.conflict_threshold;
+                parents_accepted && (virtuous || settled)
+            });
+            let Some(tx_id) = next else { break };
+
+            let key = state.vertices[&tx_id].conflict_key.clone();
+            state.vertices.get_mut(&tx_id).expect("candidate is a known transaction").status =
+                TransactionStatus::Accepted;
+            state.accepted.push(tx_id);
+            accepted += 1;
+
+            let set = state.conflict_sets.get_mut(&key).expect("vertices belong to a conflict set");
+            set.accepted = Some(tx_id);
+            set.preferred = tx_id;
+            let conflicting: Vec<Uuid> = set.members.iter().copied().filter(|id| *id != tx_id).collect();
+            for id in conflicting {
+                state.reject(id);
+            }
+
+            info!("Node {} accepted transaction {}", self.node_id, tx_id);
+        }
+        accepted
+    }
+}
+
+#[async_trait]
+impl<Q> ConsensusProtocol for AvalancheConsensus<Q>
+where
+    Q: AvalancheQuerier + 'static,
+{
+    async fn start(&mut self) -> Result<()> {
+        info!("Starting Avalanche consensus for node {}", self.node_id);
+
+        let mut is_running = self.is_running.write();
+        if *is_running {
+            return Err(ConsensusError::AlreadyRunning);
+        }
+        *is_running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        info!("Stopping Avalanche consensus");
+        *self.is_running.write() = false;
+        Ok(())
+    }
+
+    /// Add the transactions to the DAG on top of the current frontier
+    async fn propose_block(&mut self, transactions: Vec<Transaction>) -> Result<Block> {
+        let parents = self.state.read().frontier();
+        for transaction in &transactions {
+            self.add_transaction(transaction.clone(), parents.clone(), None)?;
+        }
+
+        let height = self.state.read().order.len() as u64;
+        Ok(Block::new(Vec::new(), height, transactions, self.node_id.clone()))
+    }
+
+    async fn handle_vote(&mut self, _vote: Vote) -> Result<()> {
+        Err(ConsensusError::InvalidVote("Avalanche consensus does not use votes".to_string()))
+    }
+
+    /// Add a peer's transactions to the DAG on top of the current frontier
+    async fn handle_proposal(&mut self, block: Block) -> Result<()> {
+        let parents = self.state.read().frontier();
+        for transaction in block.transactions {
+            self.add_transaction(transaction, parents.clone(), None)?;
+        }
+        Ok(())
+    }
+
+    /// Number of polls performed; Avalanche has no views
+    fn current_view(&self) -> ViewNumber {
+        ViewNumber::new(self.state.read().polls)
+    }
+
+    fn is_leader(&self) -> bool {
+        false
+    }
+
+    fn current_leader(&self) -> Option<NodeId> {
+        None
+    }
+
+    fn get_stats(&self) -> ConsensusStats {
+        let mut stats = self.stats.read().clone();
+        stats.current_view = self.state.read().polls;
+        stats
+    }
+}
@@ -0,0 +1,202 @@
+//! Avalanche-specific types and data structures
+
+use crate::{ConsensusError, Result, Transaction};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Key identifying the conflict set of a transaction
+///
+/// Transactions with the same key conflict, e.g. because they spend the same
+/// input; at most one of them is accepted.
+pub type ConflictKey = Vec<u8>;
+
+/// Snowball sampling parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AvalancheConfig {
+    /// Peers sampled per poll (k)
+    pub sample_size: usize,
+    /// Matching responses needed for a successful poll (alpha)
+    pub quorum_size: usize,
+    /// Confidence needed to accept a transaction without conflicts (beta1)
+    pub virtuous_threshold: u64,
+    /// Consecutive successful polls needed to accept a conflicting transaction (beta2)
+    pub conflict_threshold: u32,
+}
+
+impl Default for AvalancheConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: 10,
+            quorum_size: 8,
+            virtuous_threshold: 11,
+            conflict_threshold: 150,
+        }
+    }
+}
+
+impl AvalancheConfig {
+    /// Check that the parameters are usable
+    ///
+    /// The quorum must be a strict majority of the sample, so at most one
+    /// transaction can win a poll.
+    pub fn validate(&self) -> Result<()> {
+        if self.sample_size == 0 {
+            return Err(ConsensusError::InvalidConfiguration("Sample size must be positive".to_string()));
+        }
+        if self.quorum_size * 2 <= self.sample_size || self.quorum_size > self.sample_size {
+            return Err(ConsensusError::InvalidConfiguration(format!(
+                "Quorum size {} must be a majority of sample size {}",
+                self.quorum_size, self.sample_size
+            )));
+        }
+        if self.virtuous_threshold == 0 || self.conflict_threshold == 0 {
+            return Err(ConsensusError::InvalidConfiguration("Acceptance thresholds must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Decision status of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    /// Still being polled
+    Processing,
+    /// Finalized
+    Accepted,
+    /// A conflicting transaction, or an ancestor's conflict, was accepted
+    Rejected,
+}
+
+/// Transaction in the DAG
+#[derive(Debug, Clone)]
+pub struct Vertex {
+    /// The transaction
+    pub transaction: Transaction,
+    /// Transactions this one builds on
+    pub parents: Vec<Uuid>,
+    /// Transactions building on this one
+    pub children: Vec<Uuid>,
+    /// Conflict set the transaction belongs to
+    pub conflict_key: ConflictKey,
+    /// Successful polls of this transaction or its descendants
+    pub confidence: u64,
+    /// Whether the transaction was polled at least once
+    pub queried: bool,
+    /// Decision status
+    pub status: TransactionStatus,
+}
+
+/// Snowball instance deciding between conflicting transactions
+#[derive(Debug, Clone)]
+pub struct ConflictSet {
+    /// Transactions in the set
+    pub members: Vec<Uuid>,
+    /// Member with the highest confidence
+    pub preferred: Uuid,
+    /// Member that won the last successful poll
+    pub last: Uuid,
+    /// Consecutive successful polls won by `last`
+    pub count: u32,
+    /// Accepted member, once decided
+    pub accepted: Option<Uuid>,
+}
+
+impl ConflictSet {
+    pub fn new(first: Uuid) -> Self {
+        Self {
+            members: vec![first],
+            preferred: first,
+            last: first,
+            count: 0,
+            accepted: None,
+        }
+    }
+}
+
+/// Avalanche consensus state
+#[derive(Debug, Clone, Default)]
+pub struct AvalancheState {
+    /// Transactions by ID
+    pub vertices: HashMap<Uuid, Vertex>,
+    /// Conflict sets by key
+    pub conflict_sets: HashMap<ConflictKey, ConflictSet>,
+    /// Transaction IDs in insertion order, parents before children
+    pub order: Vec<Uuid>,
+    /// Accepted transaction IDs in acceptance order
+    pub accepted: Vec<Uuid>,
+    /// Polls performed
+    pub polls: u64,
+}
+
+impl AvalancheState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a transaction is the preferred member of its conflict set
+    pub fn is_preferred(&self, tx_id: &Uuid) -> bool {
+        self.vertices.get(tx_id).is_some_and(|vertex| match vertex.status {
+            TransactionStatus::Accepted => true,
+            TransactionStatus::Rejected => false,
+            TransactionStatus::Processing => self.conflict_sets
+                .get(&vertex.conflict_key)
+                .is_some_and(|set| set.preferred == *tx_id),
+        })
+    }
+
+    /// Whether a transaction and all its ancestors are preferred
+    pub fn is_strongly_preferred(&self, tx_id: &Uuid) -> bool {
+        let mut pending = vec![*tx_id];
+        while let Some(id) = pending.pop() {
+            if !self.is_preferred(&id) {
+                return false;
+            }
+            let vertex = &self.vertices[&id];
+            if vertex.status == TransactionStatus::Processing {
+                pending.extend(vertex.parents.iter().copied());
+            }
+        }
+        true
+    }
+
+    /// A transaction and its undecided ancestors
+    pub fn processing_ancestry(&self, tx_id: &Uuid) -> Vec<Uuid> {
+        let mut ancestry = Vec::new();
+        let mut pending = vec![*tx_id];
+        while let Some(id) = pending.pop() {
+            if ancestry.contains(&id) {
+                continue;
+            }
+            let Some(vertex) = self.vertices.get(&id) else { continue };
+            if vertex.status == TransactionStatus::Processing {
+                ancestry.push(id);
+                pending.extend(vertex.parents.iter().copied());
+            }
+        }
+        ancestry
+    }
+
+    /// Preferred transactions that nothing preferred builds on yet
+    pub fn frontier(&self) -> Vec<Uuid> {
+        self.order
+            .iter()
+            .filter(|id| self.is_preferred(id))
+            .filter(|id| !self.vertices[*id].children.iter().any(|child| self.is_preferred(child)))
+            .copied()
+            .collect()
+    }
+
+    /// Reject a transaction and everything building on it
+    pub fn reject(&mut self, tx_id: Uuid) {
+        let mut pending = vec![tx_id];
+        while let Some(id) = pending.pop() {
+            if let Some(vertex) = self.vertices.get_mut(&id) {
+                if vertex.status == TransactionStatus::Processing {
+                    vertex.status = TransactionStatus::Rejected;
+                    pending.extend(vertex.children.iter().copied());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Verifiable random beacon from threshold BLS signatures
+//!
+//! Each round, validators sign the round number and the previous beacon value
+//! with their share of a threshold BLS key (BLS12-381, signatures in G2). Any
+//! `threshold` valid shares interpolate to the same group signature, which is
+//! the round's proof; the beacon value is its SHA-256 hash. BLS signatures are
+//! unique, so once the previous value is fixed no validator or coalition below
+//! the threshold can predict or bias the next value, and anyone holding the
+//! group public key can verify it.
+//!
+//! Key shares come from [`deal`], which requires a trusted dealer; a
+//! distributed key generation protocol can produce the same shares without one.
+
+use crate::{ConsensusError, Result};
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use ff::Field;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// Domain separation tag for hashing beacon messages to G2
+const BEACON_DST: &[u8] = b"SYNAPSED-BEACON-V01-CS01-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+/// A validator's share of the beacon signing key
+#[derive(Clone)]
+pub struct BeaconKeyShare {
+    /// Share index, starting at 1
+    pub index: u32,
+    /// Secret share
+    secret: Scalar,
+}
+
+impl std::fmt::Debug for BeaconKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BeaconKeyShare")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Public keys needed to verify shares and beacon outputs
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeaconPublicKeys {
+    /// Shares needed to produce a beacon value
+    pub threshold: usize,
+    /// Group public key that beacon proofs verify against
+    pub group_key: G1Affine,
+    /// Public key of each share, by share index - 1
+    pub share_keys: Vec<G1Affine>,
+}
+
+impl BeaconPublicKeys {
+    /// Verify a beacon output chained from `previous_value`
+    pub fn verify(&self, output: &BeaconOutput, previous_value: &[u8]) -> bool {
+        let Some(signature) = decode_signature(&output.proof) else {
+            return false;
+        };
+        output.value == beacon_value(&signature)
+            && verify_signature(&self.group_key, &signature, output.round, previous_value)
+    }
+
+    /// Verify a signature share for a round chained from `previous_value`
+    pub fn verify_share(&self, share: &BeaconShare, previous_value: &[u8]) -> bool {
+        let key = share.index
+            .checked_sub(1)
+            .and_then(|i| self.share_keys.get(i as usize));
+        match (key, decode_signature(&share.signature)) {
+            (Some(key), Some(signature)) => verify_signature(key, &signature, share.round, previous_value),
+            _ => false,
+        }
+    }
+}
+
+/// A validator's signature share for one round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconShare {
+    /// Beacon round
+    pub round: u64,
+    /// Index of the key share that signed
+    pub index: u32,
+    /// Compressed G2 signature share
+    pub signature: Vec<u8>,
+}
+
+/// Random value produced by a beacon round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconOutput {
+    /// Beacon round
+    pub round: u64,
+    /// SHA-256 of the proof
+    pub value: Vec<u8>,
+    /// Compressed G2 group signature over the round and previous value
+    pub proof: Vec<u8>,
+}
+
+/// Split a fresh beacon key into `participants` shares, any `threshold` of which can sign
+pub fn deal<R: RngCore + CryptoRng>(
+    threshold: usize,
+    participants: usize,
+    rng: &mut R,
+) -> Result<(BeaconPublicKeys, Vec<BeaconKeyShare>)> {
+    if threshold == 0 || threshold > participants {
+        return Err(ConsensusError::InvalidConfiguration(format!(
+            "Beacon threshold {} must be between 1 and {} participants",
+            threshold, participants
+        )));
+    }
+
+    // Secret polynomial of degree threshold - 1; the group secret is f(0)
+    let coefficients: Vec<Scalar> = (0..threshold).map(|_| Scalar::random(&mut *rng)).collect();
+    let evaluate = |x: u64| {
+        let x = Scalar::from(x);
+        coefficients.iter().rev().fold(Scalar::zero(), |acc, c| acc * x + c)
+    };
+
+    let shares: Vec<BeaconKeyShare> = (1..=participants as u32)
+        .map(|index| BeaconKeyShare {
+            index,
+            secret: evaluate(index as u64),
+        })
+        .collect();
+    let public = BeaconPublicKeys {
+        threshold,
+        group_key: G1Affine::from(G1Projective::generator() * coefficients[0]),
+        share_keys: shares
+            .iter()
+            .map(|share| G1Affine::from(G1Projective::generator() * share.secret))
+            .collect(),
+    };
+    Ok((public, shares))
+}
+
+/// Chained random beacon
+///
+/// Collects signature shares for the next round and produces its output once
+/// `threshold` valid shares have arrived.
+#[derive(Debug)]
+pub struct RandomBeacon {
+    /// Public keys of the beacon
+    public: BeaconPublicKeys,
+    /// This validator's key share, if it contributes
+    key_share: Option<BeaconKeyShare>,
+    /// Value the first round is chained from
+    genesis_seed: Vec<u8>,
+    /// Most recent output
+    latest: Option<BeaconOutput>,
+    /// Verified shares for the next round by share index
+    pending: BTreeMap<u32, G2Affine>,
+}
+
+impl RandomBeacon {
+    /// Create a beacon starting from `genesis_seed`
+    pub fn new(public: BeaconPublicKeys, genesis_seed: Vec<u8>) -> Self {
+        Self {
+            public,
+            key_share: None,
+            genesis_seed,
+            latest: None,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Contribute shares signed with `key_share`
+    pub fn with_key_share(mut self, key_share: BeaconKeyShare) -> Self {
+        self.key_share = Some(key_share);
+        self
+    }
+
+    /// Public keys of the beacon
+    pub fn public_keys(&self) -> &BeaconPublicKeys {
+        &self.public
+    }
+
+    /// Most recent output
+    pub fn latest(&self) -> Option<&BeaconOutput> {
+        self.latest.as_ref()
+    }
+
+    /// Round whose shares are being collected
+    pub fn next_round(&self) -> u64 {
+        self.latest.as_ref().map_or(1, |output| output.round + 1)
+    }
+
+    /// Value the next round is chained from
+    pub fn previous_value(&self) -> &[u8] {
+        self.latest.as_ref().map_or(&self.genesis_seed, |output| &output.value)
+    }
+
+    /// Sign this validator's share for the next round
+    pub fn sign_share(&self) -> Result<BeaconShare> {
+        let key_share = self.key_share.as_ref().ok_or_else(|| {
+            ConsensusError::CryptographicError("Beacon has no key share to sign with".to_string())
+        })?;
+        let round = self.next_round();
+        let signature = hash_message(round, self.previous_value()) * key_share.secret;
+
+        Ok(BeaconShare {
+            round,
+            index: key_share.index,
+            signature: G2Affine::from(signature).to_compressed().to_vec(),
+        })
+    }
+
+    /// Add a signature share for the next round
+    ///
+    /// Returns the round's output once `threshold` valid shares have been
+    /// added. Shares for other rounds and shares that don't verify are
+    /// rejected.
+    pub fn add_share(&mut self, share: BeaconShare) -> Result<Option<BeaconOutput>> {
+        let round = self.next_round();
+        if share.round != round {
+            return Err(ConsensusError::ValidationFailed(format!(
+                "Beacon share is for round {}, expected round {}",
+                share.round, round
+            )));
+        }
+        if !self.public.verify_share(&share, self.previous_value()) {
+            return Err(ConsensusError::InvalidSignature);
+        }
+        let signature = decode_signature(&share.signature).ok_or(ConsensusError::InvalidSignature)?;
+        self.pending.insert(share.index, signature);
+        debug!("Beacon round {} has {} of {} shares", round, self.pending.len(), self.public.threshold);
+
+        if self.pending.len() < self.public.threshold {
+            return Ok(None);
+        }
+
+        let output = self.combine()?;
+        self.pending.clear();
+        self.latest = Some(output.clone());
+        Ok(Some(output))
+    }
+
+    /// Interpolate the group signature from the first `threshold` pending shares
+    fn combine(&self) -> Result<BeaconOutput> {
+        let shares: Vec<(u32, &G2Affine)> = self.pending
+            .iter()
+            .take(self.public.threshold)
+            .map(|(index, signature)| (*index, signature))
+            .collect();
+        if shares.len() < self.public.threshold {
+            return Err(ConsensusError::InsufficientVotes {
+                required: self.public.threshold,
+                received: shares.len(),
+            });
+        }
+
+        let mut signature = G2Projective::identity();
+        for (index, share) in &shares {
+            signature += G2Projective::from(*share) * lagrange_at_zero(*index, shares.iter().map(|(i, _)| *i))?;
+        }
+        let signature = G2Affine::from(signature);
+
+        let output = BeaconOutput {
+            round: self.next_round(),
+            value: beacon_value(&signature),
+            proof: signature.to_compressed().to_vec(),
+        };
+        if !self.public.verify(&output, self.previous_value()) {
+            return Err(ConsensusError::CryptographicError("Combined beacon signature is invalid".to_string()));
+        }
+        Ok(output)
+    }
+}
+
+/// Hash a round's message to G2
+fn hash_message(round: u64, previous_value: &[u8]) -> G2Projective {
+    let mut message = round.to_be_bytes().to_vec();
+    message.extend_from_slice(previous_value);
+    <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(&message, BEACON_DST)
+}
+
+/// Check `e(g1, signature) == e(key, H(round || previous_value))`
+fn verify_signature(key: &G1Affine, signature: &G2Affine, round: u64, previous_value: &[u8]) -> bool {
+    let message = G2Affine::from(hash_message(round, previous_value));
+    pairing(&G1Affine::generator(), signature) == pairing(key, &message)
+}
+
+fn decode_signature(bytes: &[u8]) -> Option<G2Affine> {
+    let bytes: &[u8; 96] = bytes.try_into().ok()?;
+    Option::from(G2Affine::from_compressed(bytes))
+}
+
+fn beacon_value(signature: &G2Affine) -> Vec<u8> {
+    ring::digest::digest(&ring::digest::SHA256, &signature.to_compressed()).as_ref().to_vec()
+}
+
+/// Lagrange coefficient of share `index` for interpolating at zero
+fn lagrange_at_zero(index: u32, indices: impl Iterator<Item = u32>) -> Result<Scalar> {
+    let x = Scalar::from(index as u64);
+    let (numerator, denominator) = indices
+        .filter(|other| *other != index)
+        .map(|other| Scalar::from(other as u64))
+        .fold((Scalar::one(), Scalar::one()), |(num, den), other| (num * other, den * (other - x)));
+    Option::from(denominator.invert())
+        .map(|inverse: Scalar| numerator * inverse)
+        .ok_or_else(|| ConsensusError::CryptographicError("Duplicate beacon share index".to_string()))
+}
@@ -111,6 +111,14 @@ pub enum ConsensusError {
     /// Duplicate voter in QC
     #[error("Duplicate voter: {0}")]
     DuplicateVoter(crate::NodeId),
+    
+    /// Transaction already pending
+    #[error("Duplicate transaction: {0}")]
+    DuplicateTransaction(uuid::Uuid),
+    
+    /// Mempool can't accept a transaction
+    #[error("Mempool full: {0}")]
+    MempoolFull(String),
 }
 
 impl From<serde_json::Error> for ConsensusError {
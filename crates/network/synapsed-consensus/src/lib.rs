@@ -41,6 +41,8 @@ pub mod config;
 pub mod error;
 pub mod types;
 pub mod traits;
+pub mod mempool;
+pub mod replication;
 
 // Consensus algorithm implementations
 #[cfg(feature = "hotstuff")]
@@ -52,8 +54,11 @@ pub mod hotstuff;
 // #[cfg(feature = "tendermint")]
 // pub mod tendermint;
 
-// #[cfg(feature = "avalanche")]
-// pub mod avalanche;
+#[cfg(feature = "avalanche")]
+pub mod avalanche;
+
+#[cfg(feature = "beacon")]
+pub mod beacon;
 
 // Core utilities (modules to be implemented)
 // pub mod crypto;
@@ -66,6 +71,8 @@ pub use error::{ConsensusError, Result};
 pub use types::{Block, NodeId, Vote, QuorumCertificate, ViewNumber, Transaction, VoteType};
 pub use traits::{ConsensusProtocol, StateMachine, NetworkTransport, ConsensusConfig, ConsensusStats, 
                   ConsensusCrypto, LeaderElection};
+pub use mempool::{Mempool, MempoolConfig, MempoolEntry};
+pub use replication::ReplicatedStateMachine;
 
 // Consensus implementations
 #[cfg(feature = "hotstuff")]
@@ -77,5 +84,8 @@ pub use hotstuff::HotStuffConsensus;
 // #[cfg(feature = "tendermint")]
 // pub use tendermint::TendermintConsensus;
 
-// #[cfg(feature = "avalanche")]
-// pub use avalanche::AvalancheConsensus;
\ No newline at end of file
+#[cfg(feature = "avalanche")]
+pub use avalanche::{AvalancheConsensus, AvalancheConfig, AvalancheQuerier};
+
+#[cfg(feature = "beacon")]
+pub use beacon::{BeaconOutput, BeaconPublicKeys, BeaconShare, RandomBeacon};
\ No newline at end of file
@@ -0,0 +1,266 @@
+//! Pending transaction pool
+//!
+//! Holds transactions waiting to be included in a block, ordered by a
+//! priority function of their fee and time in the pool. When the pool is full
+//! the lowest-priority transaction is evicted, and transactions expire after a
+//! configurable time to live.
+
+use crate::{Block, ConsensusConfig, ConsensusError, NodeId, Result, Transaction};
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Function computing the priority of a pending transaction at a point in time
+pub type PriorityFn = Box<dyn Fn(&MempoolEntry, DateTime<Utc>) -> f64 + Send + Sync>;
+
+/// Mempool limits and default prioritization
+#[derive(Debug, Clone)]
+pub struct MempoolConfig {
+    /// Maximum pending transactions
+    pub max_transactions: usize,
+    /// Maximum total size of pending transactions in bytes
+    pub max_bytes: usize,
+    /// How long a transaction may wait before it expires
+    pub ttl: Duration,
+    /// Priority gained per second spent in the pool, in fee units
+    pub age_weight: f64,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            max_transactions: 10_000,
+            max_bytes: 64 * 1024 * 1024, // 64MB
+            ttl: Duration::minutes(10),
+            age_weight: 1.0 / 60.0, // One fee unit per minute
+        }
+    }
+}
+
+/// A pending transaction with its fee and arrival time
+#[derive(Debug, Clone)]
+pub struct MempoolEntry {
+    /// The transaction
+    pub transaction: Transaction,
+    /// Fee offered for inclusion
+    pub fee: u64,
+    /// When the transaction entered the pool
+    pub received_at: DateTime<Utc>,
+    /// Payload and signature size in bytes
+    pub size: usize,
+}
+
+impl MempoolEntry {
+    /// Time spent in the pool
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        now - self.received_at
+    }
+}
+
+/// Bounded pool of pending transactions
+pub struct Mempool {
+    config: MempoolConfig,
+    /// Pending transactions by ID
+    entries: HashMap<Uuid, MempoolEntry>,
+    /// Total size of pending transactions
+    total_bytes: usize,
+    /// Priority function; fee plus weighted age unless overridden
+    priority: PriorityFn,
+}
+
+impl std::fmt::Debug for Mempool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mempool")
+            .field("config", &self.config)
+            .field("len", &self.entries.len())
+            .field("total_bytes", &self.total_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Mempool {
+    /// Create an empty mempool
+    pub fn new(config: MempoolConfig) -> Self {
+        let age_weight = config.age_weight;
+        Self {
+            config,
+            entries: HashMap::new(),
+            total_bytes: 0,
+            priority: Box::new(move |entry, now| {
+                entry.fee as f64 + entry.age(now).num_milliseconds().max(0) as f64 / 1000.0 * age_weight
+            }),
+        }
+    }
+
+    /// Use a custom priority function
+    pub fn with_priority<F>(mut self, priority: F) -> Self
+    where
+        F: Fn(&MempoolEntry, DateTime<Utc>) -> f64 + Send + Sync + 'static,
+    {
+        self.priority = Box::new(priority);
+        self
+    }
+
+    /// Number of pending transactions
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the pool is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Total size of pending transactions in bytes
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    /// Whether a transaction is pending
+    pub fn contains(&self, tx_id: &Uuid) -> bool {
+        self.entries.contains_key(tx_id)
+    }
+
+    /// Get a pending transaction
+    pub fn get(&self, tx_id: &Uuid) -> Option<&MempoolEntry> {
+        self.entries.get(tx_id)
+    }
+
+    /// Add a transaction offering `fee`
+    ///
+    /// Expired transactions are dropped first. If the pool is still over its
+    /// limits, the lowest-priority transactions are evicted and returned; a
+    /// transaction that would itself be the lowest priority is rejected
+    /// instead.
+    pub fn insert(&mut self, transaction: Transaction, fee: u64) -> Result<Vec<Transaction>> {
+        if self.entries.contains_key(&transaction.id) {
+            return Err(ConsensusError::DuplicateTransaction(transaction.id));
+        }
+
+        let now = Utc::now();
+        let size = transaction.data.len() + transaction.signature.len();
+        if size > self.config.max_bytes || self.config.max_transactions == 0 {
+            return Err(ConsensusError::MempoolFull(format!(
+                "Transaction {} of {} bytes exceeds the mempool limits",
+                transaction.id, size
+            )));
+        }
+        self.prune_expired_at(now);
+
+        let entry = MempoolEntry {
+            transaction,
+            fee,
+            received_at: now,
+            size,
+        };
+
+        // Lowest-priority transactions to evict to make room, checking first
+        // that the new one outranks all of them
+        let mut ranked = self.ranked(now);
+        let mut evict = Vec::new();
+        let mut len = self.entries.len();
+        let mut bytes = self.total_bytes;
+        while len >= self.config.max_transactions || bytes + size > self.config.max_bytes {
+            let lowest = ranked.pop().expect("Pool over its limits is not empty");
+            if self.compare(&entry, lowest, now) != Ordering::Greater {
+                return Err(ConsensusError::MempoolFull(format!(
+                    "Transaction {} has lower priority than every pending transaction",
+                    entry.transaction.id
+                )));
+            }
+            len -= 1;
+            bytes -= lowest.size;
+            evict.push(lowest.transaction.id);
+        }
+
+        let evicted: Vec<Transaction> = evict.iter().filter_map(|id| self.remove(id)).collect();
+        for transaction in &evicted {
+            debug!("Evicted transaction {} from mempool", transaction.id);
+        }
+
+        self.total_bytes += entry.size;
+        self.entries.insert(entry.transaction.id, entry);
+        Ok(evicted)
+    }
+
+    /// Remove a pending transaction
+    pub fn remove(&mut self, tx_id: &Uuid) -> Option<Transaction> {
+        let entry = self.entries.remove(tx_id)?;
+        self.total_bytes -= entry.size;
+        Some(entry.transaction)
+    }
+
+    /// Drop transactions that have waited longer than the time to live
+    pub fn prune_expired(&mut self) -> usize {
+        self.prune_expired_at(Utc::now())
+    }
+
+    fn prune_expired_at(&mut self, now: DateTime<Utc>) -> usize {
+        let expired: Vec<Uuid> = self.entries
+            .values()
+            .filter(|entry| entry.age(now) >= self.config.ttl)
+            .map(|entry| entry.transaction.id)
+            .collect();
+        for tx_id in &expired {
+            self.remove(tx_id);
+        }
+        expired.len()
+    }
+
+    /// Highest-priority transactions fitting within the given limits
+    ///
+    /// Transactions stay pending until they are removed with
+    /// [`Mempool::remove_committed`].
+    pub fn select(&self, max_transactions: usize, max_bytes: usize) -> Vec<Transaction> {
+        let now = Utc::now();
+        let mut selected = Vec::new();
+        let mut bytes = 0;
+        for entry in self.ranked(now) {
+            if selected.len() >= max_transactions {
+                break;
+            }
+            if entry.age(now) >= self.config.ttl || bytes + entry.size > max_bytes {
+                continue;
+            }
+            bytes += entry.size;
+            selected.push(entry.transaction.clone());
+        }
+        selected
+    }
+
+    /// Assemble a block from the highest-priority transactions within the configured block limits
+    pub fn build_block(
+        &self,
+        parent_hash: Vec<u8>,
+        height: u64,
+        proposer: NodeId,
+        config: &ConsensusConfig,
+    ) -> Block {
+        let transactions = self.select(config.max_transactions_per_block, config.max_block_size_bytes);
+        Block::new(parent_hash, height, transactions, proposer)
+    }
+
+    /// Remove the transactions of a committed block
+    pub fn remove_committed(&mut self, block: &Block) {
+        for transaction in &block.transactions {
+            self.remove(&transaction.id);
+        }
+    }
+
+    /// Pending transactions from highest to lowest priority
+    fn ranked(&self, now: DateTime<Utc>) -> Vec<&MempoolEntry> {
+        let mut ranked: Vec<&MempoolEntry> = self.entries.values().collect();
+        ranked.sort_by(|a, b| self.compare(b, a, now));
+        ranked
+    }
+
+    /// Order by priority, then older first, then by ID
+    fn compare(&self, a: &MempoolEntry, b: &MempoolEntry, now: DateTime<Utc>) -> Ordering {
+        (self.priority)(a, now)
+            .total_cmp(&(self.priority)(b, now))
+            .then_with(|| b.received_at.cmp(&a.received_at))
+            .then_with(|| b.transaction.id.cmp(&a.transaction.id))
+    }
+}
@@ -0,0 +1,130 @@
+//! State machine replication on top of consensus
+//!
+//! Committed blocks can reach a replica out of order or more than once, e.g.
+//! when they are relayed by several peers or re-sent after a timeout.
+//! [`ReplicatedStateMachine`] applies them to a [`StateMachine`] strictly in
+//! height order and exactly once, buffering blocks that arrive ahead of a gap.
+
+use crate::{Block, ConsensusError, ConsensusProtocol, Result, StateMachine, Transaction};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// Applies committed blocks from a consensus protocol to a state machine
+pub struct ReplicatedStateMachine<C, S>
+where
+    C: ConsensusProtocol,
+    S: StateMachine,
+{
+    /// Consensus protocol producing the commits
+    consensus: C,
+    /// Replicated state
+    state_machine: S,
+    /// Height of the last applied block, 0 before the first
+    applied_height: u64,
+    /// Committed blocks waiting for a gap to close, by height
+    pending: BTreeMap<u64, Block>,
+}
+
+impl<C, S> ReplicatedStateMachine<C, S>
+where
+    C: ConsensusProtocol,
+    S: StateMachine,
+{
+    /// Replicate `state_machine` from the commits of `consensus`
+    pub fn new(consensus: C, state_machine: S) -> Self {
+        Self {
+            consensus,
+            state_machine,
+            applied_height: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Resume after `state_machine` was restored to `applied_height`, e.g. from a snapshot
+    pub fn with_applied_height(mut self, applied_height: u64) -> Self {
+        self.applied_height = applied_height;
+        self
+    }
+
+    /// Height of the last applied block
+    pub fn applied_height(&self) -> u64 {
+        self.applied_height
+    }
+
+    /// Heights of committed blocks buffered behind a gap
+    pub fn pending_heights(&self) -> Vec<u64> {
+        self.pending.keys().copied().collect()
+    }
+
+    /// The consensus protocol
+    pub fn consensus(&self) -> &C {
+        &self.consensus
+    }
+
+    /// The consensus protocol, mutably
+    pub fn consensus_mut(&mut self) -> &mut C {
+        &mut self.consensus
+    }
+
+    /// The replicated state machine
+    pub fn state_machine(&self) -> &S {
+        &self.state_machine
+    }
+
+    /// Propose transactions through the consensus protocol
+    pub async fn propose(&mut self, transactions: Vec<Transaction>) -> Result<Block> {
+        self.consensus.propose_block(transactions).await
+    }
+
+    /// Deliver a block committed by consensus
+    ///
+    /// Applies the block and any buffered blocks it unblocks, returning the
+    /// number applied. Blocks ahead of the next expected height are buffered
+    /// and blocks already applied are ignored, so redelivery never applies a
+    /// block twice. A different block at a buffered height means conflicting
+    /// commits and is rejected.
+    pub async fn deliver_committed(&mut self, block: Block) -> Result<usize> {
+        if block.height == 0 {
+            return Err(ConsensusError::InvalidBlock);
+        }
+        if block.height <= self.applied_height {
+            debug!("Ignoring block {} at applied height {}", block.id, block.height);
+            return Ok(0);
+        }
+        if let Some(buffered) = self.pending.get(&block.height) {
+            if buffered.id != block.id {
+                return Err(ConsensusError::ValidationFailed(format!(
+                    "Conflicting commits at height {}: {} and {}",
+                    block.height, buffered.id, block.id
+                )));
+            }
+            // Retries the block if applying it failed before
+            return self.apply_pending().await;
+        }
+
+        if block.height > self.applied_height + 1 {
+            debug!(
+                "Buffering block at height {} until height {} is committed",
+                block.height,
+                self.applied_height + 1
+            );
+        }
+        self.pending.insert(block.height, block);
+        self.apply_pending().await
+    }
+
+    /// Apply buffered blocks while the next height is available
+    async fn apply_pending(&mut self) -> Result<usize> {
+        let mut applied = 0;
+        while let Some(block) = self.pending.remove(&(self.applied_height + 1)) {
+            if let Err(e) = self.state_machine.apply_block(&block).await {
+                // Keep the block so delivery can be retried
+                self.pending.insert(block.height, block);
+                return Err(e);
+            }
+            self.applied_height = block.height;
+            applied += 1;
+        }
+        Ok(applied)
+    }
+}
@@ -0,0 +1,133 @@
+//! Tests for Avalanche consensus protocol
+
+use synapsed_consensus::{
+    AvalancheConsensus, AvalancheConfig, AvalancheQuerier, ConsensusConfig, ConsensusProtocol,
+    NodeId, Transaction, Result,
+    avalanche::TransactionStatus,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// In-process network where every node answers queries honestly
+#[derive(Default)]
+struct SimulatedNetwork {
+    nodes: RwLock<HashMap<NodeId, Arc<AvalancheConsensus<SimulatedNetwork>>>>,
+}
+
+#[async_trait]
+impl AvalancheQuerier for SimulatedNetwork {
+    async fn query(&self, peer: &NodeId, transaction: &Transaction) -> Result<Option<Uuid>> {
+        let node = self.nodes.read().unwrap().get(peer).cloned();
+        Ok(node.and_then(|node| node.preference(&transaction.id)))
+    }
+}
+
+fn test_params() -> AvalancheConfig {
+    AvalancheConfig {
+        sample_size: 4,
+        quorum_size: 3,
+        virtuous_threshold: 5,
+        conflict_threshold: 10,
+    }
+}
+
+async fn create_network(size: usize) -> (Arc<SimulatedNetwork>, Vec<Arc<AvalancheConsensus<SimulatedNetwork>>>) {
+    let network = Arc::new(SimulatedNetwork::default());
+    let validators: Vec<NodeId> = (0..size).map(|_| NodeId::new()).collect();
+
+    let mut nodes = Vec::new();
+    for node_id in &validators {
+        let config = ConsensusConfig::new(node_id.clone(), validators.clone());
+        let mut node = AvalancheConsensus::new(config, test_params(), network.clone()).unwrap();
+        node.start().await.unwrap();
+
+        let node = Arc::new(node);
+        network.nodes.write().unwrap().insert(node_id.clone(), node.clone());
+        nodes.push(node);
+    }
+    (network, nodes)
+}
+
+#[tokio::test]
+async fn test_conflicting_transactions_converge() {
+    let (_network, nodes) = create_network(10).await;
+
+    let first = Transaction::new(b"spend input to alice".to_vec(), vec![]);
+    let second = Transaction::new(b"spend input to bob".to_vec(), vec![]);
+    let conflict_key = b"input-1".to_vec();
+
+    // Split the network: half of the nodes see each transaction first and prefer it
+    for (i, node) in nodes.iter().enumerate() {
+        let (seen_first, seen_second) = if i % 2 == 0 { (&first, &second) } else { (&second, &first) };
+        node.add_transaction(seen_first.clone(), vec![], Some(conflict_key.clone())).unwrap();
+        node.add_transaction(seen_second.clone(), vec![], Some(conflict_key.clone())).unwrap();
+        assert!(node.is_preferred(&seen_first.id));
+    }
+
+    for _ in 0..10_000 {
+        if nodes.iter().all(|node| node.is_decided()) {
+            break;
+        }
+        for node in &nodes {
+            node.poll().await.unwrap();
+        }
+    }
+
+    let accepted = nodes[0].accepted_transactions();
+    assert_eq!(accepted.len(), 1, "Exactly one of the conflicting transactions is accepted");
+    let winner = accepted[0].id;
+    let loser = if winner == first.id { second.id } else { first.id };
+
+    for node in &nodes {
+        assert_eq!(node.status(&winner), Some(TransactionStatus::Accepted));
+        assert_eq!(node.status(&loser), Some(TransactionStatus::Rejected));
+        assert_eq!(node.preference(&loser), Some(winner));
+    }
+}
+
+#[tokio::test]
+async fn test_virtuous_transactions_accepted_in_dag_order() {
+    let (_network, nodes) = create_network(5).await;
+
+    let parent = Transaction::new(b"parent".to_vec(), vec![]);
+    let child = Transaction::new(b"child".to_vec(), vec![]);
+    for node in &nodes {
+        node.add_transaction(parent.clone(), vec![], None).unwrap();
+        node.add_transaction(child.clone(), vec![parent.id], None).unwrap();
+    }
+
+    for _ in 0..100 {
+        for node in &nodes {
+            node.poll().await.unwrap();
+        }
+    }
+
+    for node in &nodes {
+        let accepted: Vec<Uuid> = node.accepted_transactions().iter().map(|tx| tx.id).collect();
+        assert_eq!(accepted, vec![parent.id, child.id]);
+        assert!(node.is_decided());
+        assert_eq!(node.poll().await.unwrap(), None);
+    }
+}
+
+#[tokio::test]
+async fn test_avalanche_rejects_invalid_parameters() {
+    let network = Arc::new(SimulatedNetwork::default());
+    let validators: Vec<NodeId> = (0..5).map(|_| NodeId::new()).collect();
+    let config = ConsensusConfig::new(validators[0].clone(), validators.clone());
+
+    // Quorum must be a majority of the sample
+    let params = AvalancheConfig { quorum_size: 2, ..test_params() };
+    assert!(AvalancheConsensus::new(config.clone(), params, network.clone()).is_err());
+
+    // Sample can't exceed the other validators
+    let params = AvalancheConfig { sample_size: 5, quorum_size: 4, ..test_params() };
+    assert!(AvalancheConsensus::new(config.clone(), params, network.clone()).is_err());
+
+    let mut node = AvalancheConsensus::new(config, test_params(), network).unwrap();
+    assert!(node.poll().await.is_err(), "Polling requires a started node");
+    node.start().await.unwrap();
+    assert!(node.start().await.is_err());
+}
@@ -0,0 +1,98 @@
+//! Tests for the threshold BLS random beacon
+
+use synapsed_consensus::{
+    BeaconPublicKeys, BeaconShare, ConsensusError, RandomBeacon,
+    beacon::{deal, BeaconKeyShare},
+};
+
+const GENESIS: &[u8] = b"synapsed beacon genesis";
+
+fn create_validators(threshold: usize, participants: usize) -> (BeaconPublicKeys, Vec<RandomBeacon>) {
+    let (public, shares) = deal(threshold, participants, &mut rand::thread_rng()).unwrap();
+    let beacons = shares
+        .into_iter()
+        .map(|share| RandomBeacon::new(public.clone(), GENESIS.to_vec()).with_key_share(share))
+        .collect();
+    (public, beacons)
+}
+
+#[test]
+fn test_threshold_shares_produce_verifiable_output() {
+    let (public, validators) = create_validators(3, 5);
+    let shares: Vec<BeaconShare> = validators.iter().map(|v| v.sign_share().unwrap()).collect();
+
+    // Two observers combine different subsets of shares
+    let mut first = RandomBeacon::new(public.clone(), GENESIS.to_vec());
+    assert_eq!(first.add_share(shares[0].clone()).unwrap(), None);
+    assert_eq!(first.add_share(shares[1].clone()).unwrap(), None);
+    let output = first.add_share(shares[2].clone()).unwrap().expect("Threshold reached");
+
+    let mut second = RandomBeacon::new(public.clone(), GENESIS.to_vec());
+    second.add_share(shares[4].clone()).unwrap();
+    second.add_share(shares[1].clone()).unwrap();
+    let other = second.add_share(shares[3].clone()).unwrap().expect("Threshold reached");
+
+    // The output doesn't depend on which shares were combined
+    assert_eq!(output, other);
+    assert_eq!(output.round, 1);
+    assert_eq!(output.value.len(), 32);
+    assert!(public.verify(&output, GENESIS));
+    assert!(!public.verify(&output, b"another seed"));
+
+    // The next round chains from the new value
+    assert_eq!(first.next_round(), 2);
+    assert_eq!(first.previous_value(), output.value.as_slice());
+    assert!(first.sign_share().is_err(), "Observers without a key share can't sign");
+}
+
+#[test]
+fn test_missing_or_invalid_share_produces_no_beacon() {
+    let (public, validators) = create_validators(3, 5);
+    let shares: Vec<BeaconShare> = validators.iter().map(|v| v.sign_share().unwrap()).collect();
+    let mut beacon = RandomBeacon::new(public.clone(), GENESIS.to_vec());
+
+    // One share short of the threshold
+    assert_eq!(beacon.add_share(shares[0].clone()).unwrap(), None);
+    assert_eq!(beacon.add_share(shares[1].clone()).unwrap(), None);
+    assert!(beacon.latest().is_none());
+
+    // A share claiming another validator's index is rejected
+    let forged = BeaconShare { index: 3, ..shares[4].clone() };
+    assert!(matches!(beacon.add_share(forged), Err(ConsensusError::InvalidSignature)));
+
+    // A corrupted share is rejected
+    let mut corrupted = shares[2].clone();
+    corrupted.signature[10] ^= 0xff;
+    assert!(matches!(beacon.add_share(corrupted), Err(ConsensusError::InvalidSignature)));
+
+    // A share signed with a key from a different deal is rejected
+    let (_, foreign) = deal(3, 5, &mut rand::thread_rng()).unwrap();
+    let outsider = RandomBeacon::new(public.clone(), GENESIS.to_vec())
+        .with_key_share(foreign.into_iter().nth(2).unwrap());
+    assert!(matches!(beacon.add_share(outsider.sign_share().unwrap()), Err(ConsensusError::InvalidSignature)));
+
+    // A share for another round is rejected
+    let future = BeaconShare { round: 2, ..shares[2].clone() };
+    assert!(beacon.add_share(future).is_err());
+    assert!(beacon.latest().is_none());
+
+    // A tampered output doesn't verify
+    let output = beacon.add_share(shares[2].clone()).unwrap().unwrap();
+    let mut tampered = output.clone();
+    tampered.value[0] ^= 1;
+    assert!(!public.verify(&tampered, GENESIS));
+    let mut tampered = output;
+    tampered.round = 2;
+    assert!(!public.verify(&tampered, GENESIS));
+}
+
+#[test]
+fn test_deal_rejects_invalid_threshold() {
+    let mut rng = rand::thread_rng();
+    assert!(deal(0, 5, &mut rng).is_err());
+    assert!(deal(6, 5, &mut rng).is_err());
+
+    let (public, shares): (BeaconPublicKeys, Vec<BeaconKeyShare>) = deal(5, 5, &mut rng).unwrap();
+    assert_eq!(public.share_keys.len(), 5);
+    assert_eq!(shares.iter().map(|s| s.index).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,110 @@
+//! Tests for mempool prioritization and eviction
+
+use synapsed_consensus::{
+    ConsensusConfig, ConsensusError, Mempool, MempoolConfig, NodeId, Transaction,
+};
+use uuid::Uuid;
+
+fn transaction(data: &str) -> Transaction {
+    Transaction::new(data.as_bytes().to_vec(), vec![])
+}
+
+fn small_pool(max_transactions: usize) -> Mempool {
+    Mempool::new(MempoolConfig {
+        max_transactions,
+        ..MempoolConfig::default()
+    })
+}
+
+#[test]
+fn test_insert_beyond_capacity_evicts_lowest_priority() {
+    let mut mempool = small_pool(3);
+    let low = transaction("low");
+    let mid = transaction("mid");
+    let high = transaction("high");
+    for (tx, fee) in [(&mid, 20), (&low, 10), (&high, 30)] {
+        assert!(mempool.insert(tx.clone(), fee).unwrap().is_empty());
+    }
+
+    let higher = transaction("higher");
+    let evicted = mempool.insert(higher.clone(), 25).unwrap();
+    assert_eq!(evicted.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![low.id]);
+    assert_eq!(mempool.len(), 3);
+    assert!(!mempool.contains(&low.id));
+    assert!(mempool.contains(&higher.id));
+
+    // A transaction below everything pending is rejected rather than evicting
+    let lowest = transaction("lowest");
+    assert!(matches!(mempool.insert(lowest.clone(), 5), Err(ConsensusError::MempoolFull(_))));
+    assert!(!mempool.contains(&lowest.id));
+    assert_eq!(mempool.len(), 3);
+
+    // Duplicates are rejected
+    assert!(matches!(
+        mempool.insert(high.clone(), 100),
+        Err(ConsensusError::DuplicateTransaction(id)) if id == high.id
+    ));
+}
+
+#[test]
+fn test_byte_limit_evicts_lowest_priority() {
+    let mut mempool = Mempool::new(MempoolConfig {
+        max_bytes: 10,
+        ..MempoolConfig::default()
+    });
+    let first = Transaction::new(vec![0; 4], vec![]);
+    let second = Transaction::new(vec![0; 4], vec![]);
+    mempool.insert(first.clone(), 1).unwrap();
+    mempool.insert(second.clone(), 2).unwrap();
+
+    let large = Transaction::new(vec![0; 6], vec![]);
+    let evicted = mempool.insert(large, 3).unwrap();
+    assert_eq!(evicted.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![first.id]);
+    assert_eq!(mempool.total_bytes(), 10);
+
+    assert!(mempool.insert(Transaction::new(vec![0; 11], vec![]), 100).is_err());
+}
+
+#[test]
+fn test_block_assembly_selects_highest_priority() {
+    let mut mempool = small_pool(100);
+    let fees = [7, 42, 3, 19, 25, 1];
+    let transactions: Vec<Transaction> = fees.iter().map(|fee| transaction(&format!("fee {}", fee))).collect();
+    for (tx, fee) in transactions.iter().zip(fees) {
+        mempool.insert(tx.clone(), fee).unwrap();
+    }
+
+    let proposer = NodeId::new();
+    let config = ConsensusConfig::single_node(proposer.clone()).with_max_transactions(3);
+    let block = mempool.build_block(vec![0; 32], 1, proposer.clone(), &config);
+
+    let expected: Vec<Uuid> = [1, 4, 3].iter().map(|i| transactions[*i].id).collect();
+    assert_eq!(block.transactions.iter().map(|tx| tx.id).collect::<Vec<_>>(), expected);
+    assert_eq!(block.proposer, proposer);
+    assert_eq!(mempool.len(), fees.len(), "Selection doesn't remove transactions");
+
+    mempool.remove_committed(&block);
+    assert_eq!(mempool.len(), fees.len() - 3);
+    let next = mempool.build_block(block.hash(), 2, proposer, &config);
+    let expected: Vec<Uuid> = [0, 2, 5].iter().map(|i| transactions[*i].id).collect();
+    assert_eq!(next.transactions.iter().map(|tx| tx.id).collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_expired_and_custom_priority() {
+    let mut mempool = Mempool::new(MempoolConfig {
+        ttl: chrono::Duration::zero(),
+        ..MempoolConfig::default()
+    });
+    mempool.insert(transaction("stale"), 10).unwrap();
+    assert_eq!(mempool.prune_expired(), 1);
+    assert!(mempool.is_empty());
+
+    // Prefer the oldest transactions regardless of fee
+    let mut mempool = small_pool(10).with_priority(|entry, now| entry.age(now).num_milliseconds() as f64);
+    let first = transaction("first");
+    mempool.insert(first.clone(), 0).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    mempool.insert(transaction("second"), 1000).unwrap();
+    assert_eq!(mempool.select(1, usize::MAX)[0].id, first.id);
+}
@@ -0,0 +1,160 @@
+//! Tests for state machine replication over consensus commits
+
+use synapsed_consensus::{
+    Block, ConsensusError, ConsensusProtocol, NodeId, ReplicatedStateMachine, Result,
+    StateMachine, Transaction, ViewNumber, Vote, traits::ConsensusStats,
+};
+use async_trait::async_trait;
+
+/// State machine recording the heights it applied
+#[derive(Debug, Default)]
+struct RecordingStateMachine {
+    applied: Vec<u64>,
+    fail_at: Option<u64>,
+}
+
+#[async_trait]
+impl StateMachine for RecordingStateMachine {
+    async fn apply_block(&mut self, block: &Block) -> Result<()> {
+        if self.fail_at.take() == Some(block.height) {
+            return Err(ConsensusError::StateMachineError("Apply failed".to_string()));
+        }
+        self.applied.push(block.height);
+        Ok(())
+    }
+
+    async fn state_hash(&self) -> Result<Vec<u8>> {
+        Ok(self.applied.iter().flat_map(|h| h.to_be_bytes()).collect())
+    }
+
+    async fn create_snapshot(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&self.applied)?)
+    }
+
+    async fn restore_snapshot(&mut self, snapshot: &[u8]) -> Result<()> {
+        self.applied = serde_json::from_slice(snapshot)?;
+        Ok(())
+    }
+
+    async fn validate_block(&self, _block: &Block) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Single-node consensus committing each proposal as the next block
+struct ChainConsensus {
+    node_id: NodeId,
+    height: u64,
+}
+
+#[async_trait]
+impl ConsensusProtocol for ChainConsensus {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn propose_block(&mut self, transactions: Vec<Transaction>) -> Result<Block> {
+        self.height += 1;
+        Ok(Block::new(vec![], self.height, transactions, self.node_id.clone()))
+    }
+
+    async fn handle_vote(&mut self, _vote: Vote) -> Result<()> {
+        Ok(())
+    }
+
+    async fn handle_proposal(&mut self, _block: Block) -> Result<()> {
+        Ok(())
+    }
+
+    fn current_view(&self) -> ViewNumber {
+        ViewNumber::new(self.height)
+    }
+
+    fn is_leader(&self) -> bool {
+        true
+    }
+
+    fn current_leader(&self) -> Option<NodeId> {
+        Some(self.node_id.clone())
+    }
+
+    fn get_stats(&self) -> ConsensusStats {
+        ConsensusStats::default()
+    }
+}
+
+async fn create_replica() -> (ReplicatedStateMachine<ChainConsensus, RecordingStateMachine>, Vec<Block>) {
+    let consensus = ChainConsensus { node_id: NodeId::new(), height: 0 };
+    let mut replica = ReplicatedStateMachine::new(consensus, RecordingStateMachine::default());
+
+    let mut blocks = Vec::new();
+    for i in 0..4 {
+        let tx = Transaction::new(format!("tx {}", i).into_bytes(), vec![]);
+        blocks.push(replica.propose(vec![tx]).await.unwrap());
+    }
+    (replica, blocks)
+}
+
+#[tokio::test]
+async fn test_out_of_order_blocks_applied_in_sequence() {
+    let (mut replica, blocks) = create_replica().await;
+
+    // Heights 3 and 4 arrive before 2 and are held back
+    assert_eq!(replica.deliver_committed(blocks[0].clone()).await.unwrap(), 1);
+    assert_eq!(replica.deliver_committed(blocks[3].clone()).await.unwrap(), 0);
+    assert_eq!(replica.deliver_committed(blocks[2].clone()).await.unwrap(), 0);
+    assert_eq!(replica.applied_height(), 1);
+    assert_eq!(replica.pending_heights(), vec![3, 4]);
+    assert_eq!(replica.state_machine().applied, vec![1]);
+
+    // Closing the gap applies everything buffered behind it
+    assert_eq!(replica.deliver_committed(blocks[1].clone()).await.unwrap(), 3);
+    assert_eq!(replica.applied_height(), 4);
+    assert!(replica.pending_heights().is_empty());
+    assert_eq!(replica.state_machine().applied, vec![1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_duplicate_delivery_applied_once() {
+    let (mut replica, blocks) = create_replica().await;
+
+    assert_eq!(replica.deliver_committed(blocks[0].clone()).await.unwrap(), 1);
+    assert_eq!(replica.deliver_committed(blocks[0].clone()).await.unwrap(), 0);
+    assert_eq!(replica.deliver_committed(blocks[2].clone()).await.unwrap(), 0);
+    assert_eq!(replica.deliver_committed(blocks[2].clone()).await.unwrap(), 0);
+    assert_eq!(replica.deliver_committed(blocks[1].clone()).await.unwrap(), 2);
+    for block in &blocks[..3] {
+        assert_eq!(replica.deliver_committed(block.clone()).await.unwrap(), 0);
+    }
+    assert_eq!(replica.state_machine().applied, vec![1, 2, 3]);
+
+    // A different block at a buffered height is a conflicting commit
+    replica.deliver_committed(Block::new(vec![], 5, vec![], NodeId::new())).await.unwrap();
+    let conflicting = Block::new(vec![], 5, vec![], NodeId::new());
+    assert!(matches!(
+        replica.deliver_committed(conflicting).await,
+        Err(ConsensusError::ValidationFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_failed_apply_can_be_retried() {
+    let consensus = ChainConsensus { node_id: NodeId::new(), height: 0 };
+    let state_machine = RecordingStateMachine { applied: vec![], fail_at: Some(1) };
+    let mut replica = ReplicatedStateMachine::new(consensus, state_machine);
+    let block = replica.propose(vec![]).await.unwrap();
+
+    assert!(replica.deliver_committed(block.clone()).await.is_err());
+    assert_eq!(replica.applied_height(), 0);
+    assert_eq!(replica.pending_heights(), vec![1]);
+
+    // Redelivery retries the buffered block
+    assert_eq!(replica.deliver_committed(block.clone()).await.unwrap(), 1);
+    assert_eq!(replica.applied_height(), 1);
+    assert_eq!(replica.deliver_committed(block).await.unwrap(), 0);
+    assert_eq!(replica.state_machine().applied, vec![1]);
+}
@@ -6,8 +6,21 @@ use crate::compression::{
 };
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use bytes::Bytes;
+use parking_lot::Mutex;
+
+/// Compression levels benchmarked by the level tuner
+const CANDIDATE_LEVELS: [i32; 7] = [1, 3, 6, 9, 12, 16, 19];
+
+/// Maximum number of bytes compressed per benchmark run
+const TUNING_SAMPLE_SIZE: usize = 64 * 1024;
+
+/// Number of compressions after which a tuned level is benchmarked again
+const RETUNE_INTERVAL: u64 = 1000;
+
+/// Minimum ratio improvement that justifies a slower level
+const MIN_RATIO_GAIN: f32 = 0.01;
 
 /// Strategy for selecting compression algorithms
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -84,6 +97,160 @@ impl AlgorithmMetrics {
     }
 }
 
+/// Coarse classification of message content used for level tuning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentProfile {
+    /// Mostly printable text
+    Text,
+    /// Serialized documents such as JSON or XML
+    Structured,
+    /// Anything else
+    Binary,
+}
+
+impl ContentProfile {
+    /// Classify data by inspecting its first bytes
+    pub fn classify(data: &[u8]) -> Self {
+        let head = &data[..data.len().min(512)];
+        if head.is_empty() {
+            return ContentProfile::Binary;
+        }
+        
+        let printable = head
+            .iter()
+            .filter(|&&b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+            .count();
+        if printable * 10 < head.len() * 9 {
+            return ContentProfile::Binary;
+        }
+        
+        match head.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') | Some(b'[') | Some(b'<') => ContentProfile::Structured,
+            _ => ContentProfile::Text,
+        }
+    }
+}
+
+/// Level chosen by the tuner for one algorithm and content profile
+#[derive(Debug, Clone, Copy)]
+struct TunedLevel {
+    /// Level in use, `None` until the first benchmark finishes
+    level: Option<i32>,
+    /// Compressions since the last benchmark started
+    compressions: u64,
+    /// Whether a benchmark is running
+    tuning: bool,
+}
+
+/// Picks compression levels by micro-benchmarking candidates on sampled data
+///
+/// Benchmarks run on a background thread against a copy of the sample, so
+/// compressing a message never waits for one.
+#[derive(Debug)]
+struct LevelTuner {
+    /// Maximum compression time per message
+    budget: Duration,
+    levels: Arc<Mutex<HashMap<(Algorithm, ContentProfile), TunedLevel>>>,
+}
+
+impl LevelTuner {
+    fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            levels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+    
+    /// Get the level to use for this message, starting a benchmark if due
+    ///
+    /// Until the first benchmark finishes the cheapest candidate level is used.
+    fn level_for(&self, algorithm: Algorithm, engine: &Arc<dyn CompressionEngine>, data: &[u8]) -> Option<i32> {
+        let key = (algorithm, ContentProfile::classify(data));
+        let mut levels = self.levels.lock();
+        let tuned = levels.entry(key).or_insert(TunedLevel {
+            level: None,
+            compressions: RETUNE_INTERVAL,
+            tuning: false,
+        });
+        
+        if tuned.compressions >= RETUNE_INTERVAL && !tuned.tuning && !data.is_empty() {
+            tuned.compressions = 0;
+            tuned.tuning = true;
+            self.spawn_benchmark(key, engine.clone(), data);
+        }
+        tuned.compressions += 1;
+        tuned.level.or_else(|| Self::candidates(engine.as_ref()).first().copied())
+    }
+    
+    /// Candidate levels supported by the engine, cheapest first
+    fn candidates(engine: &dyn CompressionEngine) -> Vec<i32> {
+        let supported = engine.supported_levels();
+        CANDIDATE_LEVELS
+            .iter()
+            .copied()
+            .filter(|level| supported.contains(level))
+            .collect()
+    }
+    
+    /// Benchmark a copy of the message sample in the background
+    fn spawn_benchmark(&self, key: (Algorithm, ContentProfile), engine: Arc<dyn CompressionEngine>, data: &[u8]) {
+        let sample = data[..data.len().min(TUNING_SAMPLE_SIZE)].to_vec();
+        let scale = data.len() as f64 / sample.len() as f64;
+        let budget = self.budget;
+        let levels = self.levels.clone();
+        
+        std::thread::spawn(move || {
+            let level = Self::benchmark(engine.as_ref(), &sample, scale, budget);
+            if let Some(tuned) = levels.lock().get_mut(&key) {
+                tuned.tuning = false;
+                if level.is_some() {
+                    tuned.level = level;
+                }
+            }
+        });
+    }
+    
+    /// Find the best ratio whose projected time for the whole message fits the budget
+    ///
+    /// `scale` is the size of the message relative to `sample`. Candidates are
+    /// tried from cheapest to most expensive, stopping at the first one over budget.
+    fn benchmark(engine: &dyn CompressionEngine, sample: &[u8], scale: f64, budget: Duration) -> Option<i32> {
+        let candidates = Self::candidates(engine);
+        if candidates.len() < 2 || sample.is_empty() {
+            return None;
+        }
+        
+        let mut best: Option<(i32, f32)> = None;
+        for &level in &candidates {
+            let mut fastest = Duration::MAX;
+            let mut compressed_size = 0;
+            for _ in 0..2 {
+                let start = Instant::now();
+                match engine.compress(sample, Some(level)) {
+                    Ok(compressed) => compressed_size = compressed.len(),
+                    Err(_) => break,
+                }
+                fastest = fastest.min(start.elapsed());
+            }
+            if fastest == Duration::MAX {
+                continue;
+            }
+            if fastest.mul_f64(scale) > budget {
+                break;
+            }
+            
+            let ratio = 1.0 - compressed_size as f32 / sample.len() as f32;
+            match best {
+                Some((_, best_ratio)) if ratio < best_ratio + MIN_RATIO_GAIN => {}
+                _ => best = Some((level, ratio)),
+            }
+        }
+        
+        // Fall back to the cheapest level if nothing fits the budget
+        Some(best.map_or(candidates[0], |(level, _)| level))
+    }
+}
+
 /// Adaptive compression algorithm selector
 #[derive(Debug)]
 pub struct AdaptiveSelector {
@@ -91,6 +258,7 @@ pub struct AdaptiveSelector {
     engines: HashMap<Algorithm, Arc<dyn CompressionEngine>>,
     metrics: HashMap<Algorithm, AlgorithmMetrics>,
     min_compression_ratio: f32,
+    level_tuner: Option<LevelTuner>,
 }
 
 impl AdaptiveSelector {
@@ -112,9 +280,31 @@ impl AdaptiveSelector {
             engines,
             metrics,
             min_compression_ratio,
+            level_tuner: None,
         }
     }
     
+    /// Tune the compression level per content profile under a latency budget
+    ///
+    /// When no level is passed to `compress`, candidate levels are periodically
+    /// benchmarked in the background on a sample of the message, and the one with
+    /// the best ratio whose projected compression time stays within `budget` is
+    /// used once the benchmark finishes.
+    pub fn with_level_tuning(mut self, budget: Duration) -> Self {
+        self.level_tuner = Some(LevelTuner::new(budget));
+        self
+    }
+    
+    /// Get the tuned level for an algorithm and content profile, if any
+    pub fn tuned_level(&self, algorithm: Algorithm, profile: ContentProfile) -> Option<i32> {
+        self.level_tuner
+            .as_ref()?
+            .levels
+            .lock()
+            .get(&(algorithm, profile))
+            .and_then(|tuned| tuned.level)
+    }
+    
     /// Select the best algorithm for the given data
     pub fn select_algorithm(&self, data: &[u8]) -> Algorithm {
         // For very small data, don't compress
@@ -173,12 +363,30 @@ impl AdaptiveSelector {
     /// Compress data using the best selected algorithm
     pub fn compress(&mut self, data: &[u8], level: Option<i32>) -> CompressionResult<(Bytes, Algorithm, CompressionStats)> {
         let algorithm = self.select_algorithm(data);
+        self.compress_with(algorithm, data, level)
+    }
+    
+    /// Compress data using the given algorithm
+    ///
+    /// Like [`compress`](Self::compress), the data is returned uncompressed if
+    /// the algorithm does not reach the minimum compression ratio.
+    pub fn compress_with(
+        &mut self,
+        algorithm: Algorithm,
+        data: &[u8],
+        level: Option<i32>,
+    ) -> CompressionResult<(Bytes, Algorithm, CompressionStats)> {
         let engine = self.engines.get(&algorithm).ok_or_else(|| {
             CompressionError::ConfigError {
                 reason: format!("Engine not found for algorithm: {}", algorithm),
             }
         })?;
         
+        let level = match (level, self.level_tuner.as_ref()) {
+            (None, Some(tuner)) => tuner.level_for(algorithm, engine, data),
+            (level, _) => level,
+        };
+        
         let start_time = Instant::now();
         let result = engine.compress(data, level);
         let compression_time = start_time.elapsed().as_micros() as u64;
@@ -350,6 +558,89 @@ mod tests {
         let _result = selector.decompress(b"compressed", Algorithm::Zstd);
     }
 
+    #[test]
+    fn test_content_profile_classify() {
+        assert_eq!(ContentProfile::classify(b"  {\"key\": \"value\"}"), ContentProfile::Structured);
+        assert_eq!(ContentProfile::classify(b"<node id=\"1\"/>"), ContentProfile::Structured);
+        assert_eq!(ContentProfile::classify(b"plain text message\n"), ContentProfile::Text);
+        assert_eq!(ContentProfile::classify(&[0u8, 159, 146, 150, 1, 2, 3]), ContentProfile::Binary);
+        assert_eq!(ContentProfile::classify(b""), ContentProfile::Binary);
+    }
+
+    /// Engine whose compression time and output size grow and shrink with the level
+    #[derive(Debug, Default)]
+    struct SteppedEngine {
+        levels_tried: Mutex<Vec<i32>>,
+    }
+
+    impl CompressionEngine for SteppedEngine {
+        fn compress(&self, data: &[u8], level: Option<i32>) -> CompressionResult<Bytes> {
+            let level = level.unwrap_or(1);
+            self.levels_tried.lock().push(level);
+            std::thread::sleep(Duration::from_millis(2 * level as u64));
+            Ok(Bytes::from(vec![0u8; data.len() * (20 - level as usize) / 20]))
+        }
+
+        fn decompress(&self, data: &[u8]) -> CompressionResult<Bytes> {
+            Ok(Bytes::copy_from_slice(data))
+        }
+
+        fn algorithm_name(&self) -> &'static str {
+            "stepped"
+        }
+
+        fn supported_levels(&self) -> std::ops::Range<i32> {
+            1..20
+        }
+
+        fn estimate_compression_ratio(&self, _data: &[u8]) -> f32 {
+            0.5
+        }
+    }
+
+    #[test]
+    fn test_level_tuning_respects_latency_budget() {
+        let sample = vec![b'a'; 1024];
+
+        // Levels 1, 3 and 6 take at most 12ms, level 9 takes 18ms
+        let tight = SteppedEngine::default();
+        let tight_level = LevelTuner::benchmark(&tight, &sample, 1.0, Duration::from_millis(15));
+        assert_eq!(tight_level, Some(6));
+        assert_eq!(*tight.levels_tried.lock(), vec![1, 1, 3, 3, 6, 6, 9, 9]);
+
+        let relaxed = SteppedEngine::default();
+        let relaxed_level = LevelTuner::benchmark(&relaxed, &sample, 1.0, Duration::from_secs(10));
+        assert_eq!(relaxed_level, Some(19));
+
+        // The projection scales the sample time up to the whole message
+        let scaled = SteppedEngine::default();
+        let scaled_level = LevelTuner::benchmark(&scaled, &sample, 2.0, Duration::from_millis(15));
+        assert_eq!(scaled_level, Some(3));
+    }
+
+    #[test]
+    fn test_level_tuning_runs_in_background() {
+        let data = b"peer route packet latency session frame relay node ".repeat(1024);
+
+        let mut selector = AdaptiveSelector::new(SelectionStrategy::Ratio, 0.0)
+            .with_level_tuning(Duration::from_secs(10));
+        selector.compress(&data, None).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while selector.tuned_level(Algorithm::Zstd, ContentProfile::Text).is_none() {
+            assert!(Instant::now() < deadline, "level was never tuned");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        selector.compress(&data, None).unwrap();
+
+        // Explicit levels bypass the tuner
+        let mut explicit = AdaptiveSelector::new(SelectionStrategy::Ratio, 0.0)
+            .with_level_tuning(Duration::from_secs(10));
+        explicit.compress(&data, Some(3)).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(explicit.tuned_level(Algorithm::Zstd, ContentProfile::Text), None);
+    }
+
     #[test]
     fn test_selection_strategy_values() {
         // Test that all enum values are distinct
@@ -1,5 +1,6 @@
 //! Compression algorithm implementations
 
+use crate::compression::dictionary::Dictionary;
 use crate::compression::engine::{CompressionEngine, CompressionResult, CompressionError};
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -30,11 +31,18 @@ impl fmt::Display for Algorithm {
 }
 
 /// Zstandard compression implementation
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ZstandardCompressor {
     default_level: i32,
 }
 
+impl Default for ZstandardCompressor {
+    fn default() -> Self {
+        // zstd's own default level
+        Self::with_level(3)
+    }
+}
+
 impl ZstandardCompressor {
     pub fn new() -> Self {
         Self::default()
@@ -76,6 +84,39 @@ impl CompressionEngine for ZstandardCompressor {
         "zstd"
     }
     
+    fn train_dictionary(&self, samples: &[Bytes], max_size: usize) -> CompressionResult<Dictionary> {
+        let data = zstd::dict::from_samples(samples, max_size)
+            .map_err(|e| CompressionError::DictionaryError {
+                reason: format!("Zstd dictionary training failed: {}", e),
+            })?;
+        
+        Ok(Dictionary::from_trained(Bytes::from(data)))
+    }
+    
+    fn compress_with_dictionary(&self, data: &[u8], dictionary: &Dictionary, level: Option<i32>) -> CompressionResult<Bytes> {
+        let compression_level = level.unwrap_or(self.default_level);
+        
+        if !self.supported_levels().contains(&compression_level) {
+            return Err(CompressionError::InvalidLevel { level: compression_level });
+        }
+        
+        zstd::bulk::Compressor::with_dictionary(compression_level, &dictionary.data)
+            .and_then(|mut compressor| compressor.compress(data))
+            .map(Bytes::from)
+            .map_err(|e| CompressionError::CompressionFailed {
+                reason: format!("Zstd dictionary compression failed: {}", e),
+            })
+    }
+    
+    fn decompress_with_dictionary(&self, data: &[u8], dictionary: &Dictionary) -> CompressionResult<Bytes> {
+        zstd::bulk::Decompressor::with_dictionary(&dictionary.data)
+            .and_then(|mut decompressor| decompressor.decompress(data, 1024 * 1024))
+            .map(Bytes::from)
+            .map_err(|e| CompressionError::DecompressionFailed {
+                reason: format!("Zstd dictionary decompression failed: {}", e),
+            })
+    }
+    
     fn supported_levels(&self) -> std::ops::Range<i32> {
         1..22 // Zstd supports levels 1-21
     }
@@ -252,6 +293,51 @@ mod tests {
         let _result = compressor.compress(b"test", None);
     }
 
+    /// Small protocol messages that share most of their structure
+    fn similar_messages(count: usize) -> Vec<Bytes> {
+        (0..count)
+            .map(|i| Bytes::from(format!(
+                r#"{{"type":"heartbeat","peer":"peer-{:04}","seq":{},"status":"healthy","load":{}}}"#,
+                i % 97,
+                i,
+                i % 13
+            )))
+            .collect()
+    }
+
+    #[test]
+    fn test_trained_dictionary_improves_small_message_ratio() {
+        let compressor = ZstandardCompressor::with_level(3);
+        let dictionary = compressor
+            .train_dictionary(&similar_messages(2000), 4 * 1024)
+            .unwrap();
+        
+        let messages = similar_messages(200);
+        let original: usize = messages.iter().map(|m| m.len()).sum();
+        let mut plain = 0;
+        let mut with_dictionary = 0;
+        
+        for message in &messages {
+            plain += compressor.compress(message, None).unwrap().len();
+            
+            let compressed = compressor.compress_with_dictionary(message, &dictionary, None).unwrap();
+            with_dictionary += compressed.len();
+            
+            let decompressed = compressor.decompress_with_dictionary(&compressed, &dictionary).unwrap();
+            assert_eq!(&decompressed, message);
+        }
+        
+        assert!(with_dictionary < plain);
+        assert!(with_dictionary < original);
+    }
+
+    #[test]
+    fn test_lz4_does_not_support_dictionaries() {
+        let compressor = Lz4Compressor::new();
+        let result = compressor.train_dictionary(&similar_messages(100), 1024);
+        assert!(matches!(result, Err(CompressionError::DictionaryError { .. })));
+    }
+
     #[test]
     fn test_compression_ratio_estimation() {
         let compressor = ZstandardCompressor::new();
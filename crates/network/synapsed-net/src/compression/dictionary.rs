@@ -1,6 +1,7 @@
 //! Dictionary management for compression algorithms
 
-use crate::compression::engine::{CompressionResult, CompressionError};
+use crate::compression::algorithms::ZstandardCompressor;
+use crate::compression::engine::{CompressionEngine, CompressionResult, CompressionError};
 use bytes::Bytes;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -33,6 +34,13 @@ impl Dictionary {
         }
     }
     
+    /// Create a dictionary identified by a hash of its contents, so peers
+    /// holding the same dictionary agree on its ID
+    pub fn from_trained(data: Bytes) -> Self {
+        let id = hex::encode(&blake3::hash(&data).as_bytes()[..16]);
+        Self::new(id, data)
+    }
+    
     pub fn update_effectiveness(&mut self, compression_improvement: f32) {
         let count = self.usage_count as f32;
         let new_count = count + 1.0;
@@ -110,8 +118,34 @@ impl DictionaryManager {
     
     /// Train a new dictionary from collected samples
     pub fn train_dictionary(&self, id: String) -> CompressionResult<()> {
-        // Implementation will be added in GREEN phase
-        todo!("Dictionary training not implemented yet")
+        let samples = self.training_samples.read().map_err(|_| {
+            CompressionError::DictionaryError {
+                reason: "Failed to acquire training samples lock".to_string(),
+            }
+        })?.clone();
+        
+        if samples.len() < self.config.min_training_samples {
+            return Err(CompressionError::DictionaryError {
+                reason: format!(
+                    "Not enough training samples: {} < {}",
+                    samples.len(),
+                    self.config.min_training_samples
+                ),
+            });
+        }
+        
+        let trained = ZstandardCompressor::new()
+            .train_dictionary(&samples, self.config.max_dictionary_size)?;
+        
+        let mut dictionaries = self.dictionaries.write().map_err(|_| {
+            CompressionError::DictionaryError {
+                reason: "Failed to acquire dictionaries lock".to_string(),
+            }
+        })?;
+        
+        dictionaries.insert(id.clone(), Dictionary::new(id, trained.data));
+        
+        Ok(())
     }
     
     /// Get dictionary by ID
@@ -292,10 +326,10 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Dictionary training not implemented yet")]
-    fn test_train_dictionary_not_implemented() {
+    fn test_train_dictionary_requires_samples() {
         let manager = DictionaryManager::new(DictionaryConfig::default());
-        let _result = manager.train_dictionary("test_dict".to_string());
+        let result = manager.train_dictionary("test_dict".to_string());
+        assert!(matches!(result, Err(CompressionError::DictionaryError { .. })));
     }
 
     #[test]
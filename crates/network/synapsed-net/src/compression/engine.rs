@@ -1,6 +1,7 @@
 //! Core compression engine traits and types
 
 use std::fmt;
+use crate::compression::dictionary::Dictionary;
 use async_trait::async_trait;
 use bytes::Bytes;
 use thiserror::Error;
@@ -92,6 +93,27 @@ pub trait CompressionEngine: Send + Sync + fmt::Debug {
     fn should_compress(&self, data: &[u8], min_ratio: f32) -> bool {
         data.len() >= 32 && self.estimate_compression_ratio(data) >= min_ratio
     }
+    
+    /// Train a shared dictionary of at most `max_size` bytes from sample messages
+    fn train_dictionary(&self, _samples: &[Bytes], _max_size: usize) -> CompressionResult<Dictionary> {
+        Err(CompressionError::DictionaryError {
+            reason: format!("{} does not support dictionaries", self.algorithm_name()),
+        })
+    }
+    
+    /// Compress data using a shared dictionary
+    fn compress_with_dictionary(&self, _data: &[u8], _dictionary: &Dictionary, _level: Option<i32>) -> CompressionResult<Bytes> {
+        Err(CompressionError::DictionaryError {
+            reason: format!("{} does not support dictionaries", self.algorithm_name()),
+        })
+    }
+    
+    /// Decompress data that was compressed with a shared dictionary
+    fn decompress_with_dictionary(&self, _data: &[u8], _dictionary: &Dictionary) -> CompressionResult<Bytes> {
+        Err(CompressionError::DictionaryError {
+            reason: format!("{} does not support dictionaries", self.algorithm_name()),
+        })
+    }
 }
 
 #[cfg(test)]
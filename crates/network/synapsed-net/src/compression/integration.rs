@@ -2,6 +2,7 @@
 
 use crate::compression::{
     CompressionEngine, CompressionConfig, AdaptiveSelector, SelectionStrategy, Algorithm,
+    Dictionary, ZstandardCompressor,
     engine::{CompressionResult, CompressionError, CompressionStats},
 };
 use bytes::Bytes;
@@ -15,6 +16,8 @@ pub struct CompressionNegotiation {
     pub preferred_algorithm: Algorithm,
     pub min_compression_ratio: f32,
     pub max_compression_level: i32,
+    /// ID of the shared dictionary offered for this session
+    pub dictionary_id: Option<String>,
 }
 
 impl Default for CompressionNegotiation {
@@ -24,6 +27,7 @@ impl Default for CompressionNegotiation {
             preferred_algorithm: Algorithm::Zstd,
             min_compression_ratio: 0.1,
             max_compression_level: 6,
+            dictionary_id: None,
         }
     }
 }
@@ -74,6 +78,9 @@ pub struct NetworkCompressionManager {
     selector: AdaptiveSelector,
     negotiation: CompressionNegotiation,
     performance_metrics: HashMap<String, CompressionStats>,
+    dictionaries: HashMap<String, Dictionary>,
+    /// Outcome of the last negotiation, which outgoing messages follow
+    negotiated: Option<CompressionNegotiation>,
 }
 
 impl NetworkCompressionManager {
@@ -87,50 +94,110 @@ impl NetworkCompressionManager {
             config.min_compression_ratio,
         );
         
+        let mut dictionaries = HashMap::new();
+        let dictionary_id = config.dictionary.map(|dictionary| {
+            let id = dictionary.id.clone();
+            dictionaries.insert(id.clone(), dictionary);
+            id
+        });
+        
         Self {
             selector,
-            negotiation: CompressionNegotiation::default(),
+            negotiation: CompressionNegotiation {
+                dictionary_id,
+                ..CompressionNegotiation::default()
+            },
             performance_metrics: HashMap::new(),
+            dictionaries,
+            negotiated: None,
         }
     }
     
-    /// Negotiate compression parameters with peer
-    pub fn negotiate_compression(&mut self, peer_algorithms: &[Algorithm]) -> CompressionNegotiation {
+    /// Register a dictionary shared by a peer so its frames can be decompressed
+    pub fn add_dictionary(&mut self, dictionary: Dictionary) {
+        self.dictionaries.insert(dictionary.id.clone(), dictionary);
+    }
+    
+    /// Get a registered dictionary by ID
+    pub fn dictionary(&self, id: &str) -> Option<&Dictionary> {
+        self.dictionaries.get(id)
+    }
+    
+    /// Compression parameters to offer a peer
+    pub fn offer(&self) -> &CompressionNegotiation {
+        &self.negotiation
+    }
+    
+    /// Negotiate compression parameters with a peer from its offer
+    ///
+    /// Outgoing messages use the first algorithm in local preference order
+    /// that the peer supports, or no compression if there is none. The peer's
+    /// shared dictionary is used only if that algorithm is zstd and the
+    /// dictionary is registered locally. The result applies until the next
+    /// negotiation.
+    pub fn negotiate_compression(&mut self, peer: &CompressionNegotiation) -> CompressionNegotiation {
         // Find common algorithms
-        let common_algorithms: Vec<Algorithm> = peer_algorithms
+        let common_algorithms: Vec<Algorithm> = self.negotiation.supported_algorithms
             .iter()
-            .filter(|&alg| self.negotiation.supported_algorithms.contains(alg))
+            .filter(|&alg| peer.supported_algorithms.contains(alg))
             .copied()
             .collect();
         
-        if common_algorithms.is_empty() {
+        let negotiated = match common_algorithms.first().copied() {
             // Fallback to no compression
-            CompressionNegotiation {
+            None => CompressionNegotiation {
                 supported_algorithms: vec![Algorithm::None],
                 preferred_algorithm: Algorithm::None,
                 min_compression_ratio: 0.0,
                 max_compression_level: 0,
+                dictionary_id: None,
+            },
+            Some(preferred) => {
+                // Dictionaries are only supported by zstd
+                let dictionary_id = peer.dictionary_id.clone()
+                    .filter(|id| preferred == Algorithm::Zstd && self.dictionaries.contains_key(id));
+                
+                CompressionNegotiation {
+                    supported_algorithms: common_algorithms,
+                    preferred_algorithm: preferred,
+                    min_compression_ratio: self.negotiation.min_compression_ratio,
+                    max_compression_level: self.negotiation.max_compression_level,
+                    dictionary_id,
+                }
             }
-        } else {
-            // Prefer the first common algorithm that's also in our preference order
-            let preferred = self.negotiation.supported_algorithms
-                .iter()
-                .find(|&alg| common_algorithms.contains(alg))
-                .copied()
-                .unwrap_or(common_algorithms[0]);
-            
-            CompressionNegotiation {
-                supported_algorithms: common_algorithms,
-                preferred_algorithm: preferred,
-                min_compression_ratio: self.negotiation.min_compression_ratio,
-                max_compression_level: self.negotiation.max_compression_level,
-            }
-        }
+        };
+        
+        self.negotiated = Some(negotiated.clone());
+        negotiated
     }
     
     /// Compress message for transmission
+    ///
+    /// Once negotiated, messages are compressed with the negotiated algorithm
+    /// and dictionary; before that the adaptive selector picks the algorithm.
     pub fn compress_message(&mut self, data: &[u8], level: Option<i32>) -> CompressionResult<CompressedFrame> {
-        let (compressed_data, algorithm, stats) = self.selector.compress(data, level)?;
+        let negotiated = self.negotiated.as_ref();
+        if let Some(dictionary) = negotiated
+            .and_then(|negotiated| negotiated.dictionary_id.as_ref())
+            .and_then(|id| self.dictionaries.get(id))
+        {
+            let level = level.unwrap_or(self.negotiation.max_compression_level);
+            let compressed_data = ZstandardCompressor::new()
+                .compress_with_dictionary(data, dictionary, Some(level))?;
+            
+            return Ok(CompressedFrame::new(
+                Algorithm::Zstd,
+                data.len(),
+                compressed_data,
+                Some(dictionary.id.clone()),
+                level,
+            ));
+        }
+        
+        let (compressed_data, algorithm, stats) = match negotiated {
+            Some(negotiated) => self.selector.compress_with(negotiated.preferred_algorithm, data, level)?,
+            None => self.selector.compress(data, level)?,
+        };
         
         // Store performance metrics
         let key = format!("{}_{}", algorithm, data.len());
@@ -140,7 +207,7 @@ impl NetworkCompressionManager {
             algorithm,
             data.len(),
             compressed_data,
-            None, // Dictionary support can be added later
+            None,
             level.unwrap_or(6),
         ))
     }
@@ -151,6 +218,15 @@ impl NetworkCompressionManager {
             return Ok(frame.compressed_data.clone());
         }
         
+        if let Some(id) = &frame.dictionary_id {
+            let dictionary = self.dictionaries.get(id).ok_or_else(|| {
+                CompressionError::DictionaryError {
+                    reason: format!("Dictionary not found: {}", id),
+                }
+            })?;
+            return ZstandardCompressor::new().decompress_with_dictionary(&frame.compressed_data, dictionary);
+        }
+        
         self.selector.decompress(&frame.compressed_data, frame.algorithm)
     }
     
@@ -274,16 +350,95 @@ mod tests {
         let mut manager = NetworkCompressionManager::new(config);
         
         // Test with compatible algorithms
-        let peer_algorithms = vec![Algorithm::Zstd, Algorithm::Lz4];
-        let negotiated = manager.negotiate_compression(&peer_algorithms);
+        let peer = CompressionNegotiation {
+            supported_algorithms: vec![Algorithm::Zstd, Algorithm::Lz4],
+            ..CompressionNegotiation::default()
+        };
+        let negotiated = manager.negotiate_compression(&peer);
         assert!(negotiated.supported_algorithms.len() >= 1);
         
         // Test with no compatible algorithms (except None)
-        let peer_algorithms = vec![];
-        let negotiated = manager.negotiate_compression(&peer_algorithms);
+        let peer = CompressionNegotiation {
+            supported_algorithms: vec![],
+            ..CompressionNegotiation::default()
+        };
+        let negotiated = manager.negotiate_compression(&peer);
         assert_eq!(negotiated.preferred_algorithm, Algorithm::None);
     }
 
+    #[test]
+    fn test_shared_dictionary_round_trip() {
+        let samples: Vec<Bytes> = (0..2000)
+            .map(|i| Bytes::from(format!(r#"{{"type":"ping","seq":{},"node":"node-{}"}}"#, i, i % 31)))
+            .collect();
+        let dictionary = ZstandardCompressor::new()
+            .train_dictionary(&samples, 2 * 1024)
+            .unwrap();
+        
+        let config = CompressionConfig {
+            dictionary: Some(dictionary.clone()),
+            ..CompressionConfig::default()
+        };
+        let mut sender = NetworkCompressionManager::new(config.clone());
+        let mut receiver = NetworkCompressionManager::new(config);
+        
+        let negotiated = sender.negotiate_compression(receiver.offer());
+        assert_eq!(negotiated.preferred_algorithm, Algorithm::Zstd);
+        assert_eq!(negotiated.dictionary_id, Some(dictionary.id.clone()));
+        
+        let message = br#"{"type":"ping","seq":4242,"node":"node-7"}"#;
+        let frame = sender.compress_message(message, None).unwrap();
+        assert_eq!(frame.dictionary_id, Some(dictionary.id.clone()));
+        assert_eq!(receiver.decompress_message(&frame).unwrap().as_ref(), message);
+        
+        // A peer without the dictionary cannot decode dictionary frames
+        let stranger = NetworkCompressionManager::new(CompressionConfig::default());
+        assert!(stranger.decompress_message(&frame).is_err());
+        
+        // ...so it is not used unless the peer offers it
+        assert_eq!(sender.negotiate_compression(stranger.offer()).dictionary_id, None);
+        let frame = sender.compress_message(message, None).unwrap();
+        assert!(frame.dictionary_id.is_none());
+        assert_eq!(stranger.decompress_message(&frame).unwrap().as_ref(), message);
+    }
+
+    #[test]
+    fn test_negotiated_algorithm_is_used() {
+        let dictionary = ZstandardCompressor::new()
+            .train_dictionary(
+                &(0..2000)
+                    .map(|i| Bytes::from(format!(r#"{{"type":"ping","seq":{}}}"#, i)))
+                    .collect::<Vec<_>>(),
+                2 * 1024,
+            )
+            .unwrap();
+        let config = CompressionConfig {
+            dictionary: Some(dictionary),
+            ..CompressionConfig::default()
+        };
+        let mut sender = NetworkCompressionManager::new(config.clone());
+        let mut receiver = NetworkCompressionManager::new(config);
+        receiver.negotiation.supported_algorithms = vec![Algorithm::Lz4, Algorithm::None];
+        
+        // The shared dictionary is zstd-only, so an lz4 session goes without it
+        let negotiated = sender.negotiate_compression(receiver.offer());
+        assert_eq!(negotiated.preferred_algorithm, Algorithm::Lz4);
+        assert_eq!(negotiated.dictionary_id, None);
+        
+        let message = "the same words over and over again ".repeat(100);
+        let frame = sender.compress_message(message.as_bytes(), None).unwrap();
+        assert_eq!(frame.algorithm, Algorithm::Lz4);
+        assert!(frame.dictionary_id.is_none());
+        assert_eq!(receiver.decompress_message(&frame).unwrap().as_ref(), message.as_bytes());
+        
+        // Without a common algorithm messages are sent uncompressed
+        receiver.negotiation.supported_algorithms = vec![Algorithm::None];
+        assert_eq!(sender.negotiate_compression(receiver.offer()).preferred_algorithm, Algorithm::None);
+        let frame = sender.compress_message(message.as_bytes(), None).unwrap();
+        assert!(!frame.is_compressed());
+        assert_eq!(receiver.decompress_message(&frame).unwrap().as_ref(), message.as_bytes());
+    }
+
     #[test]
     fn test_performance_monitor() {
         let mut monitor = CompressionPerformanceMonitor::new();
@@ -15,7 +15,7 @@ pub mod benchmarks;
 
 pub use engine::{CompressionEngine, CompressionResult, CompressionError, CompressionStats};
 pub use algorithms::{Algorithm, ZstandardCompressor, Lz4Compressor};
-pub use adaptive::{AdaptiveSelector, SelectionStrategy};
+pub use adaptive::{AdaptiveSelector, ContentProfile, SelectionStrategy};
 pub use dictionary::{DictionaryManager, Dictionary};
 pub use stream::{CompressedStream, StreamCompressor};
 pub use integration::{NetworkCompressionManager, CompressedFrame, CompressionNegotiation};
@@ -33,6 +33,8 @@ pub struct CompressionConfig {
     pub dictionary_size: usize,
     /// Stream chunk size for streaming compression
     pub stream_chunk_size: usize,
+    /// Shared dictionary for small, repetitive messages; both peers must hold it
+    pub dictionary: Option<Dictionary>,
 }
 
 impl Default for CompressionConfig {
@@ -43,6 +45,7 @@ impl Default for CompressionConfig {
             adaptive_selection: true,
             dictionary_size: 64 * 1024, // 64KB
             stream_chunk_size: 32 * 1024, // 32KB
+            dictionary: None,
         }
     }
 }
@@ -59,5 +62,6 @@ mod tests {
         assert!(config.adaptive_selection);
         assert_eq!(config.dictionary_size, 64 * 1024);
         assert_eq!(config.stream_chunk_size, 32 * 1024);
+        assert!(config.dictionary.is_none());
     }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
 //! Configuration types for the networking layer.
 
+use crate::serialization::SerializationFormat;
 use crate::types::TransportType;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::time::Duration;
 
 /// Main configuration for the network stack.
@@ -18,6 +21,10 @@ pub struct NetworkConfig {
     
     /// Observability configuration
     pub observability: ObservabilityConfig,
+    
+    /// Peer discovery configuration
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
 }
 
 impl Default for NetworkConfig {
@@ -27,6 +34,7 @@ impl Default for NetworkConfig {
             security: SecurityConfig::default(),
             privacy: PrivacyConfig::default(),
             observability: ObservabilityConfig::default(),
+            discovery: DiscoveryConfig::default(),
         }
     }
 }
@@ -68,6 +76,24 @@ pub struct TransportConfig {
     
     /// Whether to require post-quantum security
     pub require_post_quantum: bool,
+    
+    /// Connection pool configuration
+    #[serde(default)]
+    pub pool: PoolConfig,
+    
+    /// Relay to fall back to when direct connection attempts fail
+    #[serde(default)]
+    pub relay_fallback: Option<RelayConfig>,
+    
+    /// Peer reputation and banning configuration
+    #[serde(default)]
+    pub reputation: ReputationConfig,
+    
+    /// Message serialization formats to offer, in preference order
+    ///
+    /// Empty sends an empty offer and messages use the default format, JSON.
+    #[serde(default)]
+    pub serialization_formats: Vec<SerializationFormat>,
 }
 
 /// Transport selection strategy.
@@ -104,6 +130,142 @@ impl Default for TransportConfig {
             selection_strategy: SelectionStrategy::BestMatch,
             prefer_anonymity: false,
             require_post_quantum: false,
+            pool: PoolConfig::default(),
+            relay_fallback: None,
+            reputation: ReputationConfig::default(),
+            serialization_formats: Vec::new(),
+        }
+    }
+}
+
+/// Connection pool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolConfig {
+    /// Whether connections are pooled and reused
+    pub enabled: bool,
+    
+    /// How long a pooled connection may stay unused before eviction
+    pub idle_timeout: Duration,
+    
+    /// Maximum pooled connections per peer
+    pub max_per_peer: usize,
+    
+    /// Check connection health before handing out a pooled connection
+    pub health_check: bool,
+    
+    /// How long shutdown waits for in-flight transfers before closing connections
+    pub drain_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_timeout: Duration::from_secs(90),
+            max_per_peer: 4,
+            health_check: true,
+            drain_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Relay fallback configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayConfig {
+    /// Address of the relay server
+    pub address: SocketAddr,
+    
+    /// Timeout for reaching the relay
+    pub connect_timeout: Duration,
+}
+
+impl RelayConfig {
+    /// Creates a relay configuration for the given relay server.
+    pub fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            connect_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Peer reputation configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationConfig {
+    /// Score at or below which a peer is banned
+    pub ban_threshold: f64,
+    
+    /// Highest score a peer can accumulate
+    pub max_score: f64,
+    
+    /// How long a ban lasts
+    pub ban_duration: Duration,
+    
+    /// Time for a score to decay halfway back to neutral
+    pub decay_half_life: Duration,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            ban_threshold: -100.0,
+            max_score: 100.0,
+            ban_duration: Duration::from_secs(600),
+            decay_half_life: Duration::from_secs(900),
+        }
+    }
+}
+
+/// Peer discovery configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryConfig {
+    /// Peer addresses by name, tried before any other resolver
+    pub static_peers: HashMap<String, String>,
+    
+    /// Resolve names through DNS SRV and TXT records
+    pub enable_dns: bool,
+    
+    /// Resolve names through mDNS on the local network
+    pub enable_mdns: bool,
+    
+    /// DNS-SD service label peers are published under
+    pub service: String,
+    
+    /// How long to wait for mDNS responses
+    pub mdns_timeout: Duration,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            static_peers: HashMap::new(),
+            enable_dns: true,
+            enable_mdns: true,
+            service: "_synapsed._udp".to_string(),
+            mdns_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Replay protection configuration for authenticated frames.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayConfig {
+    /// Number of sequence numbers behind the highest seen that are still accepted (at most 64)
+    pub window_size: u64,
+    
+    /// Oldest frame timestamp that is still accepted
+    pub max_age: Duration,
+    
+    /// How far a frame timestamp may lie in the future
+    pub max_clock_skew: Duration,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            window_size: 64,
+            max_age: Duration::from_secs(30),
+            max_clock_skew: Duration::from_secs(5),
         }
     }
 }
@@ -0,0 +1,449 @@
+//! Peer discovery through pluggable name resolvers.
+//!
+//! Peers can be reached by name instead of hardcoded addresses. A
+//! [`ResolverChain`] asks each configured [`PeerResolver`] in turn: static
+//! names from configuration, DNS SRV and TXT records, and mDNS on the local
+//! network. Both DNS-based resolvers use the DNS-SD layout: an SRV record
+//! gives the host and port, and an optional TXT record carries `id=<uuid>`
+//! with the peer's ID.
+
+use crate::config::DiscoveryConfig;
+use crate::error::{NetworkError, Result};
+use crate::types::{NetworkAddress, PeerId, PeerInfo};
+use async_trait::async_trait;
+use hickory_proto::op::{Message, MessageType, OpCode, Query};
+use hickory_proto::rr::{Name, RData, RecordType};
+use hickory_resolver::TokioAsyncResolver;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// mDNS IPv4 multicast group and port.
+const MDNS_GROUP: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(224, 0, 0, 251)), 5353);
+
+/// Resolves peer names to peer information.
+#[async_trait]
+pub trait PeerResolver: Send + Sync {
+    /// Returns the resolver name for logging.
+    fn name(&self) -> &str;
+
+    /// Resolves a peer name.
+    ///
+    /// Returns `Ok(None)` if this resolver does not know the name, so the
+    /// next resolver can be tried.
+    async fn resolve(&self, name: &str) -> Result<Option<PeerInfo>>;
+}
+
+/// Tries resolvers in order and returns the first match.
+#[derive(Default)]
+pub struct ResolverChain {
+    resolvers: RwLock<Vec<Arc<dyn PeerResolver>>>,
+}
+
+impl ResolverChain {
+    /// Creates an empty resolver chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the resolver chain described by the discovery configuration.
+    ///
+    /// Static peers are tried first, then DNS, then mDNS.
+    pub fn from_config(config: &DiscoveryConfig) -> Result<Self> {
+        let mut resolvers: Vec<Arc<dyn PeerResolver>> = Vec::new();
+        if !config.static_peers.is_empty() {
+            resolvers.push(Arc::new(StaticResolver::from_config(config)?));
+        }
+        if config.enable_dns {
+            match DnsResolver::from_system_conf(&config.service) {
+                Ok(dns) => resolvers.push(Arc::new(dns)),
+                Err(e) => warn!("DNS peer discovery disabled: {}", e),
+            }
+        }
+        if config.enable_mdns {
+            resolvers.push(Arc::new(MdnsResolver::new(&config.service, config.mdns_timeout)));
+        }
+
+        Ok(Self {
+            resolvers: RwLock::new(resolvers),
+        })
+    }
+
+    /// Appends a resolver to the end of the chain.
+    pub async fn push(&self, resolver: Arc<dyn PeerResolver>) {
+        self.resolvers.write().await.push(resolver);
+    }
+
+    /// Returns the number of resolvers in the chain.
+    pub async fn len(&self) -> usize {
+        self.resolvers.read().await.len()
+    }
+
+    /// Returns true if the chain has no resolvers.
+    pub async fn is_empty(&self) -> bool {
+        self.resolvers.read().await.is_empty()
+    }
+
+    /// Resolves a peer name using the first resolver that knows it.
+    ///
+    /// A failing resolver does not stop the search; its error is only
+    /// reported if no later resolver knows the name either.
+    pub async fn resolve(&self, name: &str) -> Result<PeerInfo> {
+        let resolvers = self.resolvers.read().await.clone();
+        let mut failures = Vec::new();
+
+        for resolver in resolvers {
+            match resolver.resolve(name).await {
+                Ok(Some(peer)) => {
+                    debug!("Resolved {} to {} via {}", name, peer.address, resolver.name());
+                    return Ok(peer);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    debug!("Resolver {} failed for {}: {}", resolver.name(), name, e);
+                    failures.push(format!("{}: {}", resolver.name(), e));
+                }
+            }
+        }
+
+        Err(NetworkError::PeerNotFound(if failures.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} ({})", name, failures.join("; "))
+        }))
+    }
+}
+
+/// Resolves names from a fixed table.
+#[derive(Debug, Clone, Default)]
+pub struct StaticResolver {
+    peers: HashMap<String, PeerInfo>,
+}
+
+impl StaticResolver {
+    /// Creates an empty static resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a resolver for the static peers in the discovery configuration.
+    pub fn from_config(config: &DiscoveryConfig) -> Result<Self> {
+        let mut resolver = Self::new();
+        for (name, address) in &config.static_peers {
+            let address: SocketAddr = address.parse().map_err(|e| {
+                NetworkError::Configuration(format!("Invalid address for static peer {}: {}", name, e))
+            })?;
+            resolver = resolver.with_peer(name.clone(), peer_info(name, &[address], &[]));
+        }
+        Ok(resolver)
+    }
+
+    /// Adds a named peer.
+    pub fn with_peer(mut self, name: impl Into<String>, peer: PeerInfo) -> Self {
+        self.peers.insert(name.into(), peer);
+        self
+    }
+}
+
+#[async_trait]
+impl PeerResolver for StaticResolver {
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    async fn resolve(&self, name: &str) -> Result<Option<PeerInfo>> {
+        Ok(self.peers.get(name).cloned())
+    }
+}
+
+/// Resolves names through DNS SRV and TXT records.
+///
+/// The name `node.example.com` is looked up as
+/// `<service>.node.example.com`, e.g. `_synapsed._udp.node.example.com`.
+pub struct DnsResolver {
+    resolver: TokioAsyncResolver,
+    service: String,
+}
+
+impl DnsResolver {
+    /// Creates a resolver using the system DNS configuration.
+    pub fn from_system_conf(service: &str) -> Result<Self> {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf()
+            .map_err(|e| NetworkError::Configuration(format!("Failed to load DNS configuration: {}", e)))?;
+        Ok(Self::with_resolver(resolver, service))
+    }
+
+    /// Creates a resolver using an existing DNS client.
+    pub fn with_resolver(resolver: TokioAsyncResolver, service: &str) -> Self {
+        Self {
+            resolver,
+            service: service.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl PeerResolver for DnsResolver {
+    fn name(&self) -> &str {
+        "dns"
+    }
+
+    async fn resolve(&self, name: &str) -> Result<Option<PeerInfo>> {
+        use hickory_resolver::error::ResolveErrorKind;
+
+        let query = format!("{}.{}.", self.service, name.trim_end_matches('.'));
+        let srv = match self.resolver.srv_lookup(query.as_str()).await {
+            Ok(srv) => srv,
+            Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => return Ok(None),
+            Err(e) => return Err(NetworkError::Connection(format!("DNS lookup for {} failed: {}", query, e))),
+        };
+
+        let mut addresses = Vec::new();
+        for record in srv.iter() {
+            let ips: Vec<IpAddr> = match self.resolver.lookup_ip(record.target().clone()).await {
+                Ok(ips) => ips.iter().collect(),
+                Err(e) => {
+                    debug!("Failed to resolve SRV target {}: {}", record.target(), e);
+                    continue;
+                }
+            };
+            addresses.extend(ips.into_iter().map(|ip| SocketAddr::new(ip, record.port())));
+        }
+        if addresses.is_empty() {
+            return Ok(None);
+        }
+
+        // The TXT record is optional
+        let txt = match self.resolver.txt_lookup(query.as_str()).await {
+            Ok(txt) => txt
+                .iter()
+                .flat_map(|record| record.txt_data().iter())
+                .map(|data| String::from_utf8_lossy(data).into_owned())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        Ok(Some(peer_info(name, &addresses, &txt)))
+    }
+}
+
+/// Resolves names through multicast DNS on the local network.
+///
+/// The name `node` is looked up as `node.<service>.local`, e.g.
+/// `node._synapsed._udp.local`. Queries are sent from an ephemeral port, so
+/// responders answer by unicast.
+pub struct MdnsResolver {
+    service: String,
+    group: SocketAddr,
+    timeout: Duration,
+}
+
+impl MdnsResolver {
+    /// Creates an mDNS resolver for a DNS-SD service.
+    pub fn new(service: &str, timeout: Duration) -> Self {
+        Self {
+            service: service.to_string(),
+            group: MDNS_GROUP,
+            timeout,
+        }
+    }
+
+    /// Sends queries to a different address than the mDNS multicast group.
+    pub fn with_multicast_addr(mut self, group: SocketAddr) -> Self {
+        self.group = group;
+        self
+    }
+
+    fn query(&self, instance: &Name) -> Result<Vec<u8>> {
+        let mut message = Message::new();
+        message
+            .set_id(0)
+            .set_message_type(MessageType::Query)
+            .set_op_code(OpCode::Query)
+            .set_recursion_desired(false)
+            .add_query(Query::query(instance.clone(), RecordType::SRV))
+            .add_query(Query::query(instance.clone(), RecordType::TXT));
+        message
+            .to_vec()
+            .map_err(|e| NetworkError::Protocol(format!("Failed to encode mDNS query: {}", e)))
+    }
+}
+
+#[async_trait]
+impl PeerResolver for MdnsResolver {
+    fn name(&self) -> &str {
+        "mdns"
+    }
+
+    async fn resolve(&self, name: &str) -> Result<Option<PeerInfo>> {
+        let instance = Name::from_ascii(format!("{}.{}.local.", name, self.service))
+            .map_err(|e| NetworkError::InvalidMessage(format!("Invalid mDNS name {}: {}", name, e)))?;
+
+        let bind: SocketAddr = if self.group.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(bind).await?;
+        socket.send_to(&self.query(&instance)?, self.group).await?;
+
+        let mut records = ServiceRecords::default();
+        let mut buf = vec![0u8; 9000];
+        let deadline = tokio::time::Instant::now() + self.timeout;
+
+        loop {
+            let len = match tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+                Ok(received) => received?.0,
+                Err(_) => break,
+            };
+
+            // Other responders may send unrelated or malformed packets
+            let response = match Message::from_vec(&buf[..len]) {
+                Ok(response) if response.message_type() == MessageType::Response => response,
+                _ => continue,
+            };
+            records.add(&instance, &response);
+            if let Some(peer) = records.peer_info(name) {
+                return Ok(Some(peer));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// DNS-SD records collected from mDNS responses.
+#[derive(Default)]
+struct ServiceRecords {
+    target: Option<(Name, u16)>,
+    ips: HashMap<Name, Vec<IpAddr>>,
+    txt: Vec<String>,
+}
+
+impl ServiceRecords {
+    fn add(&mut self, instance: &Name, response: &Message) {
+        for record in response.answers().iter().chain(response.additionals()) {
+            match record.data() {
+                Some(RData::SRV(srv)) if record.name() == instance => {
+                    self.target = Some((srv.target().clone(), srv.port()));
+                }
+                Some(RData::TXT(txt)) if record.name() == instance => {
+                    self.txt.extend(txt.txt_data().iter().map(|data| String::from_utf8_lossy(data).into_owned()));
+                }
+                Some(RData::A(a)) => self.ips.entry(record.name().clone()).or_default().push(IpAddr::V4(a.0)),
+                Some(RData::AAAA(aaaa)) => self.ips.entry(record.name().clone()).or_default().push(IpAddr::V6(aaaa.0)),
+                _ => {}
+            }
+        }
+    }
+
+    fn peer_info(&self, name: &str) -> Option<PeerInfo> {
+        let (target, port) = self.target.as_ref()?;
+        let addresses: Vec<SocketAddr> = self
+            .ips
+            .get(target)?
+            .iter()
+            .map(|ip| SocketAddr::new(*ip, *port))
+            .collect();
+        Some(peer_info(name, &addresses, &self.txt))
+    }
+}
+
+/// Builds peer information from resolved addresses and TXT entries.
+///
+/// The peer ID comes from an `id=<uuid>` TXT entry. Without one it is
+/// derived from the name, so the same name always maps to the same peer.
+fn peer_info(name: &str, addresses: &[SocketAddr], txt: &[String]) -> PeerInfo {
+    let id = txt
+        .iter()
+        .filter_map(|entry| entry.strip_prefix("id="))
+        .find_map(|id| uuid::Uuid::parse_str(id).ok())
+        .map(|id| PeerId::from_bytes(*id.as_bytes()))
+        .unwrap_or_else(|| {
+            let hash = blake3::hash(name.as_bytes());
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(&hash.as_bytes()[..16]);
+            PeerId::from_bytes(bytes)
+        });
+
+    let mut peer = PeerInfo::new(id);
+    if let Some(first) = addresses.first() {
+        peer.address = first.to_string();
+    }
+    for address in addresses {
+        peer.add_address(NetworkAddress::Socket(*address));
+    }
+    peer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resolver that fails every lookup.
+    struct FailingResolver;
+
+    #[async_trait]
+    impl PeerResolver for FailingResolver {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn resolve(&self, _name: &str) -> Result<Option<PeerInfo>> {
+            Err(NetworkError::Connection("unreachable".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_tries_resolvers_in_order() {
+        let first = PeerInfo::new(PeerId::new());
+        let second = PeerInfo::new(PeerId::new());
+
+        let chain = ResolverChain::new();
+        chain.push(Arc::new(FailingResolver)).await;
+        chain.push(Arc::new(StaticResolver::new().with_peer("alpha", first.clone()))).await;
+        chain.push(Arc::new(
+            StaticResolver::new()
+                .with_peer("alpha", PeerInfo::new(PeerId::new()))
+                .with_peer("beta", second.clone()),
+        )).await;
+
+        assert_eq!(chain.resolve("alpha").await.unwrap().id, first.id);
+        assert_eq!(chain.resolve("beta").await.unwrap().id, second.id);
+
+        let err = chain.resolve("gamma").await.unwrap_err();
+        assert!(matches!(err, NetworkError::PeerNotFound(_)));
+        assert!(err.to_string().contains("failing: Connection error: unreachable"));
+    }
+
+    #[tokio::test]
+    async fn test_static_peers_from_config() {
+        let mut config = DiscoveryConfig::default();
+        config.static_peers.insert("seed".to_string(), "10.0.0.1:4001".to_string());
+
+        let resolver = StaticResolver::from_config(&config).unwrap();
+        let peer = resolver.resolve("seed").await.unwrap().unwrap();
+        assert_eq!(peer.address, "10.0.0.1:4001");
+        assert_eq!(peer.addresses, vec![NetworkAddress::Socket("10.0.0.1:4001".parse().unwrap())]);
+
+        // Derived IDs are stable across lookups
+        assert_eq!(resolver.resolve("seed").await.unwrap().unwrap().id, peer.id);
+        assert!(resolver.resolve("other").await.unwrap().is_none());
+
+        config.static_peers.insert("broken".to_string(), "not an address".to_string());
+        assert!(StaticResolver::from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mdns_resolver_times_out_without_responders() {
+        // Nothing listens on this socket's address once it is dropped
+        let unused = std::net::UdpSocket::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let resolver = MdnsResolver::new("_synapsed._udp", Duration::from_millis(50)).with_multicast_addr(unused);
+
+        assert!(resolver.resolve("nobody").await.unwrap().is_none());
+    }
+}
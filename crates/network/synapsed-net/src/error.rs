@@ -33,6 +33,18 @@ pub enum NetworkError {
     #[error("Protocol error: {0}")]
     Protocol(String),
     
+    /// A peer sent a malformed or invalid message
+    #[error("Invalid message: {0}")]
+    InvalidMessage(String),
+    
+    /// The peer is temporarily banned
+    #[error("Peer banned: {0}")]
+    PeerBanned(String),
+    
+    /// No resolver could find a peer by name
+    #[error("Peer not found: {0}")]
+    PeerNotFound(String),
+    
     /// Observability errors
     #[error("Observability error: {0}")]
     Observability(String),
@@ -172,6 +184,9 @@ impl NetworkError {
             NetworkError::Configuration(_) => ErrorSeverity::Critical,
             NetworkError::Connection(_) => ErrorSeverity::Major,
             NetworkError::Protocol(_) => ErrorSeverity::Major,
+            NetworkError::InvalidMessage(_) => ErrorSeverity::Major,
+            NetworkError::PeerBanned(_) => ErrorSeverity::Minor,
+            NetworkError::PeerNotFound(_) => ErrorSeverity::Minor,
             NetworkError::Observability(_) => ErrorSeverity::Minor,
             NetworkError::Io(_) => ErrorSeverity::Major,
             NetworkError::Other(_) => ErrorSeverity::Major,
@@ -188,6 +203,9 @@ impl NetworkError {
             NetworkError::Configuration(_) => "configuration",
             NetworkError::Connection(_) => "connection",
             NetworkError::Protocol(_) => "protocol",
+            NetworkError::InvalidMessage(_) => "invalid_message",
+            NetworkError::PeerBanned(_) => "peer_banned",
+            NetworkError::PeerNotFound(_) => "peer_not_found",
             NetworkError::Observability(_) => "observability",
             NetworkError::Io(_) => "io",
             NetworkError::Other(_) => "other",
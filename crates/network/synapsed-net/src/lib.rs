@@ -30,25 +30,33 @@ use std::collections::HashMap;
 pub mod config;
 pub mod compression;
 pub mod crypto;
+pub mod discovery;
 pub mod error;
 pub mod observability;
 pub mod privacy;
+pub mod replay;
+pub mod reputation;
 pub mod security;
+pub mod serialization;
 pub mod transport;
 pub mod types;
 
 // Re-export commonly used types
-pub use config::{NetworkConfig, TransportConfig};
+pub use config::{DiscoveryConfig, NetworkConfig, PoolConfig, RelayConfig, ReputationConfig, TransportConfig};
 pub use compression::{CompressionEngine, CompressionConfig, Algorithm, AdaptiveSelector};
 pub use crypto::{
     EnhancedSecurityManager, EnhancedSecurityConfig, SecureCipherSuite,
     CertificateValidator, CertificatePinner, SessionManager,
 };
+pub use discovery::{DnsResolver, MdnsResolver, PeerResolver, ResolverChain, StaticResolver};
 pub use error::{NetworkError, Result};
 pub use observability::{ObservabilityContext, UnifiedObservability};
 pub use privacy::{PrivacyLevel, PrivacyConfig, PrivacyProvider};
+pub use replay::{FrameAuthenticator, ReplayWindow};
+pub use reputation::{PeerReputation, ReputationEvent, ReputationKey};
+pub use serialization::SerializationFormat;
 pub use security::SecurityLayer;
-pub use transport::{Connection, Transport, TransportManager};
+pub use transport::{Connection, ConnectionPool, PooledConnection, Transport, TransportManager};
 pub use types::{PeerId, PeerInfo};
 
 // Re-export core types for better integration
@@ -69,6 +77,9 @@ impl From<NetworkError> for SynapsedError {
             NetworkError::Security(e) => SynapsedError::Cryptographic(format!("Security error: {}", e)),
             NetworkError::Configuration(msg) => SynapsedError::Configuration(msg),
             NetworkError::Protocol(msg) => SynapsedError::Network(format!("Protocol error: {}", msg)),
+            NetworkError::InvalidMessage(msg) => SynapsedError::InvalidInput(format!("Invalid message: {}", msg)),
+            NetworkError::PeerBanned(msg) => SynapsedError::PermissionDenied(format!("Peer banned: {}", msg)),
+            NetworkError::PeerNotFound(msg) => SynapsedError::NotFound(format!("Peer not found: {}", msg)),
             NetworkError::Privacy(e) => SynapsedError::Network(format!("Privacy error: {}", e)),
             NetworkError::Observability(msg) => SynapsedError::Network(format!("Observability error: {}", msg)),
             NetworkError::Io(e) => SynapsedError::Internal(format!("IO error: {}", e)),
@@ -90,6 +101,8 @@ use tokio::sync::RwLock;
 pub struct NetworkStack {
     config: Arc<NetworkConfig>,
     transport_manager: Arc<TransportManager>,
+    pool: Arc<ConnectionPool>,
+    resolver: Arc<ResolverChain>,
     observability: Arc<UnifiedObservability>,
     state: Arc<RwLock<NetworkState>>,
 }
@@ -253,14 +266,28 @@ impl NetworkStack {
         let observability = UnifiedObservability::new(&config.observability).await?;
         
         // Create transport manager with observability
-        let transport_manager = TransportManager::with_observability(
+        let mut transport_manager = TransportManager::with_observability(
             config.transport.default_transport,
             observability.clone()
         );
         
+        transport_manager.set_reputation(Arc::new(PeerReputation::new(config.transport.reputation.clone())));
+        transport_manager.set_serialization_formats(config.transport.serialization_formats.clone());
+        transport_manager.set_handshake_timeout(config.transport.connection_timeout);
+        
+        if let Some(relay) = &config.transport.relay_fallback {
+            let relay = transport::RelayTransport::new(relay.clone(), transport_manager.local_peer());
+            transport_manager.set_relay_fallback(Arc::new(relay));
+        }
+        
+        let pool = ConnectionPool::new(config.transport.pool.clone());
+        let resolver = ResolverChain::from_config(&config.discovery)?;
+        
         Ok(Self {
             config: Arc::new(config),
             transport_manager: Arc::new(transport_manager),
+            pool: Arc::new(pool),
+            resolver: Arc::new(resolver),
             observability,
             state: Arc::new(RwLock::new(NetworkState::default())),
         })
@@ -283,14 +310,39 @@ impl NetworkStack {
         Ok(())
     }
     
-    /// Connects to a peer using the best available transport.
-    pub async fn connect(&self, peer: &PeerInfo) -> Result<Connection> {
-        let connection = self.transport_manager.connect(peer).await?;
+    /// Connects to a peer, reusing a pooled connection when one is available.
+    ///
+    /// Otherwise a new connection is established using the best available
+    /// transport and added to the pool. No lock is held while dialling, so
+    /// checkouts for other peers proceed concurrently.
+    pub async fn connect(&self, peer: &PeerInfo) -> Result<PooledConnection> {
+        if let Some(connection) = self.pool.get(&peer.id).await {
+            return Ok(connection);
+        }
         
-        let mut state = self.state.write().await;
-        state.active_connections += 1;
+        let connection = self.transport_manager.connect(peer).await?;
+        self.state.write().await.active_connections += 1;
         
-        Ok(connection)
+        Ok(self.pool.insert(peer.id, connection))
+    }
+    
+    /// Resolves a peer by name and connects to it through the pool.
+    ///
+    /// Resolvers are tried in order: static peers, DNS, mDNS, then any
+    /// added with [`NetworkStack::add_resolver`].
+    pub async fn connect_by_name(&self, name: &str) -> Result<PooledConnection> {
+        let peer = self.resolver.resolve(name).await?;
+        self.connect(&peer).await
+    }
+    
+    /// Adds a resolver to the end of the discovery chain.
+    pub async fn add_resolver(&self, resolver: Arc<dyn PeerResolver>) {
+        self.resolver.push(resolver).await;
+    }
+    
+    /// Returns the peer resolver chain.
+    pub fn resolver(&self) -> &Arc<ResolverChain> {
+        &self.resolver
     }
     
     /// Shuts down the network stack gracefully.
@@ -300,6 +352,12 @@ impl NetworkStack {
             return Ok(());
         }
         
+        // Let in-flight transfers finish before tearing down transports
+        let undrained = self.pool.drain(self.config.transport.pool.drain_timeout).await;
+        if undrained > 0 {
+            tracing::warn!("{} connections were closed before they finished draining", undrained);
+        }
+        
         // Shutdown transports
         self.transport_manager.shutdown().await?;
         
@@ -321,6 +379,11 @@ impl NetworkStack {
     pub fn transport_manager(&self) -> &Arc<TransportManager> {
         &self.transport_manager
     }
+    
+    /// Returns the connection pool.
+    pub fn connection_pool(&self) -> &Arc<ConnectionPool> {
+        &self.pool
+    }
 }
 
 #[cfg(test)]
@@ -344,4 +407,102 @@ mod tests {
         // Test double shutdown is safe
         stack.shutdown().await.unwrap();
     }
+    
+    /// Accepts connections on `listener` in the background and keeps them open.
+    fn accept_in_background(mut listener: Box<dyn crate::transport::traits::Listener>) {
+        tokio::spawn(async move {
+            let acceptor = crate::transport::TransportManager::new(crate::types::TransportType::Memory);
+            let mut accepted = Vec::new();
+            while let Ok((connection, _)) = acceptor.accept(listener.as_mut()).await {
+                accepted.push(connection);
+            }
+        });
+    }
+    
+    #[tokio::test]
+    async fn test_connect_reuses_pooled_connection() {
+        use crate::transport::MemoryTransport;
+        use crate::types::TransportType;
+        
+        let stack = NetworkStack::new(NetworkConfig::default()).await.unwrap();
+        
+        let memory = Arc::new(MemoryTransport::new());
+        let addr: std::net::SocketAddr = "127.0.0.1:9100".parse().unwrap();
+        accept_in_background(memory.listen(addr).await.unwrap());
+        stack.transport_manager().register(TransportType::Memory, memory).await;
+        
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = addr.to_string();
+        
+        let first = stack.connect(&peer).await.unwrap();
+        let second = stack.connect(&peer).await.unwrap();
+        
+        assert_eq!(first.id(), second.id());
+        assert_eq!(stack.connection_pool().peer_connections(&peer.id), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_connect_by_name_via_mdns() {
+        use crate::transport::MemoryTransport;
+        use crate::types::TransportType;
+        use hickory_proto::op::{Message, MessageType};
+        use hickory_proto::rr::{rdata, Name, RData, Record};
+        use std::time::Duration;
+        
+        // Only the mDNS resolver knows the peer
+        let mut config = NetworkConfig::default();
+        config.discovery.enable_dns = false;
+        config.discovery.enable_mdns = false;
+        config.discovery.static_peers.insert("node-a".to_string(), "127.0.0.1:9101".to_string());
+        let stack = NetworkStack::new(config).await.unwrap();
+        
+        let memory = Arc::new(MemoryTransport::new());
+        let addr: std::net::SocketAddr = "127.0.0.1:9102".parse().unwrap();
+        accept_in_background(memory.listen(addr).await.unwrap());
+        stack.transport_manager().register(TransportType::Memory, memory).await;
+        
+        // Mocked mDNS responder on the loopback interface
+        let peer_id = uuid::Uuid::new_v4();
+        let responder = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let responder_addr = responder.local_addr().unwrap();
+        tokio::spawn(async move {
+            let instance = Name::from_ascii("node-b._synapsed._udp.local.").unwrap();
+            let host = Name::from_ascii("node-b.local.").unwrap();
+            let mut buf = vec![0u8; 1500];
+            loop {
+                let (len, from) = responder.recv_from(&mut buf).await.unwrap();
+                let query = Message::from_vec(&buf[..len]).unwrap();
+                if query.queries().iter().all(|q| q.name() != &instance) {
+                    continue;
+                }
+                
+                let mut response = Message::new();
+                response.set_id(query.id()).set_message_type(MessageType::Response).set_authoritative(true);
+                response.add_answer(Record::from_rdata(
+                    instance.clone(),
+                    120,
+                    RData::SRV(rdata::SRV::new(0, 0, 9102, host.clone())),
+                ));
+                response.add_answer(Record::from_rdata(
+                    instance.clone(),
+                    120,
+                    RData::TXT(rdata::TXT::new(vec![format!("id={}", peer_id)])),
+                ));
+                response.add_additional(Record::from_rdata(host.clone(), 120, RData::A(rdata::A::new(127, 0, 0, 1))));
+                responder.send_to(&response.to_vec().unwrap(), from).await.unwrap();
+            }
+        });
+        
+        let mdns = MdnsResolver::new("_synapsed._udp", Duration::from_secs(2)).with_multicast_addr(responder_addr);
+        stack.add_resolver(Arc::new(mdns)).await;
+        assert_eq!(stack.resolver().len().await, 2);
+        
+        let peer = stack.resolver().resolve("node-b").await.unwrap();
+        assert_eq!(peer.id, PeerId::from_bytes(*peer_id.as_bytes()));
+        assert_eq!(peer.address, addr.to_string());
+        
+        let connection = stack.connect_by_name("node-b").await.unwrap();
+        assert_eq!(connection.peer(), peer.id);
+        assert_eq!(stack.connection_pool().peer_connections(&peer.id), 1);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,390 @@
+//! Per-peer reputation tracking and temporary bans.
+//!
+//! Peers accrue penalties for misbehaviour such as malformed messages or
+//! protocol violations, and small credits for well-formed traffic. Scores decay
+//! back towards neutral over time so old offences are forgiven. A peer whose
+//! score falls to the ban threshold is banned for a fixed period; when the ban
+//! expires the peer starts again from a neutral score.
+//!
+//! Records are keyed on a [`ReputationKey`]: either a peer ID the local node
+//! chose to dial, or the remote address of an inbound connection, since
+//! inbound transports such as TCP cannot vouch for the peer ID they report.
+
+use crate::config::ReputationConfig;
+use crate::error::NetworkError;
+use crate::types::PeerId;
+use dashmap::DashMap;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Something a peer did that affects its reputation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReputationEvent {
+    /// Sent a well-formed message
+    ValidMessage,
+
+    /// Sent a message that could not be parsed or validated
+    InvalidMessage,
+
+    /// Violated the protocol state machine
+    ProtocolViolation,
+
+    /// Failed authentication
+    AuthenticationFailure,
+
+    /// Timed out during an exchange
+    Timeout,
+
+    /// Custom score adjustment
+    Custom(f64),
+}
+
+impl ReputationEvent {
+    /// Returns the score change for this event.
+    pub fn score_delta(&self) -> f64 {
+        match self {
+            ReputationEvent::ValidMessage => 1.0,
+            ReputationEvent::InvalidMessage => -20.0,
+            ReputationEvent::ProtocolViolation => -30.0,
+            ReputationEvent::AuthenticationFailure => -50.0,
+            ReputationEvent::Timeout => -5.0,
+            ReputationEvent::Custom(delta) => *delta,
+        }
+    }
+
+    /// Maps an error caused by a peer to a reputation event.
+    ///
+    /// Returns `None` for errors that say nothing about the peer's behaviour,
+    /// such as local I/O or configuration failures.
+    pub fn from_error(error: &NetworkError) -> Option<Self> {
+        use crate::error::{SecurityError, TransportError};
+
+        match error {
+            NetworkError::InvalidMessage(_) => Some(ReputationEvent::InvalidMessage),
+            NetworkError::Protocol(_) => Some(ReputationEvent::ProtocolViolation),
+            NetworkError::Security(SecurityError::AuthenticationFailed(_)) => Some(ReputationEvent::AuthenticationFailure),
+            NetworkError::Transport(TransportError::Timeout) => Some(ReputationEvent::Timeout),
+            NetworkError::Transport(TransportError::TimeoutWithMsg(_)) => Some(ReputationEvent::Timeout),
+            _ => None,
+        }
+    }
+}
+
+/// What a reputation record is tracked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReputationKey {
+    /// A peer identity
+    Peer(PeerId),
+
+    /// A remote IP address, for peers whose identity is not authenticated
+    Address(IpAddr),
+}
+
+impl ReputationKey {
+    /// Returns a form of the key that is safe to log.
+    pub fn anonymized(&self) -> String {
+        match self {
+            ReputationKey::Peer(peer) => peer.anonymized(),
+            ReputationKey::Address(addr) => addr.to_string(),
+        }
+    }
+}
+
+impl From<PeerId> for ReputationKey {
+    fn from(peer: PeerId) -> Self {
+        ReputationKey::Peer(peer)
+    }
+}
+
+impl From<&PeerId> for ReputationKey {
+    fn from(peer: &PeerId) -> Self {
+        ReputationKey::Peer(*peer)
+    }
+}
+
+impl From<IpAddr> for ReputationKey {
+    fn from(addr: IpAddr) -> Self {
+        ReputationKey::Address(addr)
+    }
+}
+
+impl From<SocketAddr> for ReputationKey {
+    fn from(addr: SocketAddr) -> Self {
+        ReputationKey::Address(addr.ip())
+    }
+}
+
+impl fmt::Display for ReputationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.anonymized())
+    }
+}
+
+/// Reputation state for a single peer.
+#[derive(Debug, Clone)]
+struct PeerRecord {
+    score: f64,
+    updated_at: Instant,
+    banned_until: Option<Instant>,
+}
+
+impl PeerRecord {
+    fn new() -> Self {
+        Self {
+            score: 0.0,
+            updated_at: Instant::now(),
+            banned_until: None,
+        }
+    }
+
+    /// Lifts an expired ban and decays the score towards zero.
+    ///
+    /// The score is frozen while a ban is in effect.
+    fn refresh(&mut self, half_life: Duration, now: Instant) {
+        if let Some(until) = self.banned_until {
+            if now >= until {
+                self.banned_until = None;
+                self.score = 0.0;
+            }
+            self.updated_at = now;
+            return;
+        }
+
+        if !half_life.is_zero() {
+            let elapsed = now.saturating_duration_since(self.updated_at);
+            let halvings = elapsed.as_secs_f64() / half_life.as_secs_f64();
+            self.score *= 0.5f64.powf(halvings);
+        }
+        self.updated_at = now;
+    }
+
+    fn is_banned(&self, now: Instant) -> bool {
+        self.banned_until.is_some_and(|until| now < until)
+    }
+}
+
+/// Tracks reputation scores and bans per peer or address.
+pub struct PeerReputation {
+    config: ReputationConfig,
+    peers: DashMap<ReputationKey, PeerRecord>,
+}
+
+impl PeerReputation {
+    /// Creates a new reputation tracker.
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            peers: DashMap::new(),
+        }
+    }
+
+    /// Returns the reputation configuration.
+    pub fn config(&self) -> &ReputationConfig {
+        &self.config
+    }
+
+    /// Records an event for a peer and returns its new score.
+    ///
+    /// Bans the peer if the score reaches the ban threshold.
+    pub fn record(&self, key: impl Into<ReputationKey>, event: ReputationEvent) -> f64 {
+        let key = key.into();
+        let now = Instant::now();
+        let mut record = self.peers.entry(key).or_insert_with(PeerRecord::new);
+        record.refresh(self.config.decay_half_life, now);
+
+        record.score = (record.score + event.score_delta())
+            .clamp(self.config.ban_threshold, self.config.max_score);
+
+        if record.score <= self.config.ban_threshold && !record.is_banned(now) {
+            record.banned_until = Some(now + self.config.ban_duration);
+            warn!(
+                "Banning peer {} for {:?} after {:?} (score {:.1})",
+                key.anonymized(),
+                self.config.ban_duration,
+                event,
+                record.score
+            );
+        }
+
+        record.score
+    }
+
+    /// Records the reputation impact of an error caused by a peer.
+    ///
+    /// Returns the new score, or `None` if the error does not reflect on the peer.
+    pub fn record_error(&self, key: impl Into<ReputationKey>, error: &NetworkError) -> Option<f64> {
+        ReputationEvent::from_error(error).map(|event| self.record(key, event))
+    }
+
+    /// Returns the peer's current score, after decay.
+    pub fn score(&self, key: impl Into<ReputationKey>) -> f64 {
+        match self.peers.get_mut(&key.into()) {
+            Some(mut record) => {
+                record.refresh(self.config.decay_half_life, Instant::now());
+                record.score
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Returns true if the peer is currently banned.
+    pub fn is_banned(&self, key: impl Into<ReputationKey>) -> bool {
+        match self.peers.get_mut(&key.into()) {
+            Some(mut record) => {
+                let now = Instant::now();
+                record.refresh(self.config.decay_half_life, now);
+                record.is_banned(now)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns how long the peer's ban still has to run, if banned.
+    pub fn ban_remaining(&self, key: impl Into<ReputationKey>) -> Option<Duration> {
+        let now = Instant::now();
+        self.peers.get(&key.into())
+            .and_then(|record| record.banned_until)
+            .and_then(|until| until.checked_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Bans a peer for the given duration regardless of its score.
+    pub fn ban(&self, key: impl Into<ReputationKey>, duration: Duration) {
+        let key = key.into();
+        let mut record = self.peers.entry(key).or_insert_with(PeerRecord::new);
+        record.banned_until = Some(Instant::now() + duration);
+        info!("Manually banned peer {} for {:?}", key.anonymized(), duration);
+    }
+
+    /// Lifts a peer's ban and resets its score.
+    pub fn unban(&self, key: impl Into<ReputationKey>) {
+        self.peers.remove(&key.into());
+    }
+
+    /// Returns all currently banned peer IDs.
+    pub fn banned_peers(&self) -> Vec<PeerId> {
+        self.banned_keys().into_iter()
+            .filter_map(|key| match key {
+                ReputationKey::Peer(peer) => Some(peer),
+                ReputationKey::Address(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns all currently banned addresses.
+    pub fn banned_addresses(&self) -> Vec<IpAddr> {
+        self.banned_keys().into_iter()
+            .filter_map(|key| match key {
+                ReputationKey::Address(addr) => Some(addr),
+                ReputationKey::Peer(_) => None,
+            })
+            .collect()
+    }
+
+    fn banned_keys(&self) -> Vec<ReputationKey> {
+        let now = Instant::now();
+        self.peers.iter()
+            .filter(|entry| entry.value().is_banned(now))
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
+    /// Drops records that have decayed back to neutral and are not banned.
+    pub fn prune(&self) {
+        let now = Instant::now();
+        let half_life = self.config.decay_half_life;
+        self.peers.retain(|_, record| {
+            record.refresh(half_life, now);
+            record.is_banned(now) || record.score.abs() >= 0.5
+        });
+    }
+}
+
+impl Default for PeerReputation {
+    fn default() -> Self {
+        Self::new(ReputationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> ReputationConfig {
+        ReputationConfig {
+            ban_duration: Duration::from_millis(100),
+            ..ReputationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_invalid_messages_lead_to_ban() {
+        let reputation = PeerReputation::new(test_config());
+        let peer = PeerId::new();
+        let error = NetworkError::InvalidMessage("truncated frame".to_string());
+
+        while !reputation.is_banned(&peer) {
+            let score = reputation.record_error(&peer, &error).unwrap();
+            assert!(score < 0.0);
+        }
+
+        assert!(reputation.score(&peer) <= reputation.config().ban_threshold);
+        assert_eq!(reputation.banned_peers(), vec![peer]);
+    }
+
+    #[test]
+    fn test_unrelated_errors_do_not_affect_reputation() {
+        let reputation = PeerReputation::default();
+        let peer = PeerId::new();
+
+        let error = NetworkError::Configuration("bad local config".to_string());
+        assert!(reputation.record_error(&peer, &error).is_none());
+        assert_eq!(reputation.score(&peer), 0.0);
+    }
+
+    #[test]
+    fn test_score_decays_towards_neutral() {
+        let reputation = PeerReputation::new(ReputationConfig {
+            decay_half_life: Duration::from_millis(50),
+            ..ReputationConfig::default()
+        });
+        let peer = PeerId::new();
+
+        let initial = reputation.record(&peer, ReputationEvent::ProtocolViolation);
+        std::thread::sleep(Duration::from_millis(100));
+
+        let decayed = reputation.score(&peer);
+        assert!(decayed > initial);
+        assert!(decayed < 0.0);
+    }
+
+    #[test]
+    fn test_address_bans_are_separate_from_peer_bans() {
+        let reputation = PeerReputation::new(test_config());
+        let peer = PeerId::new();
+        let addr: IpAddr = "192.0.2.7".parse().unwrap();
+
+        reputation.record(addr, ReputationEvent::Custom(-1000.0));
+
+        assert!(reputation.is_banned(addr));
+        assert!(!reputation.is_banned(&peer));
+        assert_eq!(reputation.banned_addresses(), vec![addr]);
+        assert!(reputation.banned_peers().is_empty());
+    }
+
+    #[test]
+    fn test_ban_expires() {
+        let reputation = PeerReputation::new(test_config());
+        let peer = PeerId::new();
+
+        reputation.record(&peer, ReputationEvent::Custom(-1000.0));
+        assert!(reputation.is_banned(&peer));
+
+        std::thread::sleep(Duration::from_millis(150));
+
+        assert!(!reputation.is_banned(&peer));
+        assert_eq!(reputation.score(&peer), 0.0);
+    }
+}
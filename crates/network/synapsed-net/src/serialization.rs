@@ -0,0 +1,128 @@
+//! Wire serialization formats and their negotiation.
+//!
+//! Peers advertise the formats they support in preference order during the
+//! connection handshake and settle on the initiator's most preferred format
+//! that the responder also supports. JSON is the fallback used when no
+//! negotiation takes place.
+
+use crate::error::{NetworkError, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Format used to serialize messages on the wire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SerializationFormat {
+    /// JSON, the default when no format has been negotiated
+    #[default]
+    Json,
+
+    /// Compact binary encoding for Rust peers
+    Bincode,
+
+    /// MessagePack
+    MessagePack,
+
+    /// CBOR (RFC 8949)
+    Cbor,
+}
+
+impl SerializationFormat {
+    /// Serializes a value in this format.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let encoded = match self {
+            SerializationFormat::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            SerializationFormat::Bincode => bincode::serialize(value).map_err(|e| e.to_string()),
+            SerializationFormat::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            SerializationFormat::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::ser::into_writer(value, &mut buf)
+                    .map(|_| buf)
+                    .map_err(|e| e.to_string())
+            }
+        };
+
+        encoded.map_err(|e| NetworkError::Protocol(format!("Failed to encode {}: {}", self, e)))
+    }
+
+    /// Deserializes a value in this format.
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        let decoded = match self {
+            SerializationFormat::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            SerializationFormat::Bincode => bincode::deserialize(bytes).map_err(|e| e.to_string()),
+            SerializationFormat::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| e.to_string()),
+            SerializationFormat::Cbor => ciborium::de::from_reader(bytes).map_err(|e| e.to_string()),
+        };
+
+        decoded.map_err(|e| NetworkError::InvalidMessage(format!("Failed to decode {}: {}", self, e)))
+    }
+
+    /// Picks the initiator's most preferred format that the responder also supports.
+    pub fn negotiate(initiator: &[Self], responder: &[Self]) -> Option<Self> {
+        initiator.iter().find(|format| responder.contains(format)).copied()
+    }
+}
+
+impl fmt::Display for SerializationFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializationFormat::Json => write!(f, "JSON"),
+            SerializationFormat::Bincode => write!(f, "bincode"),
+            SerializationFormat::MessagePack => write!(f, "MessagePack"),
+            SerializationFormat::Cbor => write!(f, "CBOR"),
+        }
+    }
+}
+
+/// Format offer exchanged during the connection handshake.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct FormatOffer {
+    /// Supported formats in preference order
+    pub formats: Vec<SerializationFormat>,
+    
+    /// Whether the sender understands close frames; absent from older peers
+    #[serde(default)]
+    pub close_frames: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Message, MessageId, MessageMetadata, MessagePriority};
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_negotiate_prefers_initiator_order() {
+        use SerializationFormat::*;
+
+        assert_eq!(SerializationFormat::negotiate(&[Cbor, MessagePack, Json], &[Json, MessagePack]), Some(MessagePack));
+        assert_eq!(SerializationFormat::negotiate(&[Json, MessagePack], &[Cbor, MessagePack, Json]), Some(Json));
+        assert_eq!(SerializationFormat::negotiate(&[Bincode], &[Cbor]), None);
+    }
+
+    #[test]
+    fn test_all_formats_round_trip_messages() {
+        let message = Message {
+            id: MessageId::new(),
+            payload: b"format round trip".to_vec(),
+            metadata: MessageMetadata {
+                timestamp: SystemTime::now(),
+                priority: MessagePriority::High,
+                requires_ack: true,
+                substrate_context: None,
+            },
+        };
+
+        for format in [
+            SerializationFormat::Json,
+            SerializationFormat::Bincode,
+            SerializationFormat::MessagePack,
+            SerializationFormat::Cbor,
+        ] {
+            let encoded = format.encode(&message).unwrap();
+            let decoded: Message = format.decode(&encoded).unwrap();
+            assert_eq!(decoded.id, message.id);
+            assert_eq!(decoded.payload, message.payload);
+        }
+    }
+}
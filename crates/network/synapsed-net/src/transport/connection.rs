@@ -1,109 +1,474 @@
 //! Connection abstraction for all transport types.
 
-use crate::error::Result;
-use crate::observability::{SubstrateEvent, TransportEvent};
+use crate::error::{NetworkError, Result, TransportError};
+use crate::observability::{SubstrateEvent, TransportEvent, UnifiedObservability};
+use crate::replay::FrameAuthenticator;
+use crate::reputation::{PeerReputation, ReputationEvent, ReputationKey};
+use crate::transport::progress::{ProgressMeter, TransferProgress};
+use crate::transport::traits::Stream;
+use crate::serialization::{FormatOffer, SerializationFormat};
 use crate::types::{ConnectionId, ConnectionInfo, ConnectionMetrics, Message, TransportType};
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::Instant;
+use parking_lot::Mutex as SyncMutex;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::sync::Mutex;
 
 /// A connection to a remote peer.
+///
+/// [`Connection::into_split`] separates the connection into a
+/// [`ConnectionSender`] and a [`ConnectionReceiver`], so one task can send
+/// while another waits for the next message.
 pub struct Connection {
+    sender: ConnectionSender,
+    receiver: ConnectionReceiver,
+}
+
+/// The sending half of a [`Connection`].
+pub struct ConnectionSender {
+    /// The underlying stream
+    stream: SharedStream,
+    
+    /// State shared with the receiving half
+    shared: Arc<Shared>,
+    
+    /// Observability handle
+    observability: Option<Arc<UnifiedObservability>>,
+    
+    /// Wire format for messages
+    format: SerializationFormat,
+    
+    /// Message authentication and replay protection, once enabled
+    authenticator: Option<Arc<SyncMutex<FrameAuthenticator>>>,
+    
+    /// Whether the peer agreed to close frames during the handshake
+    close_frames: bool,
+}
+
+/// The receiving half of a [`Connection`].
+pub struct ConnectionReceiver {
+    /// The underlying stream
+    stream: SharedStream,
+    
+    /// State shared with the sending half
+    shared: Arc<Shared>,
+    
+    /// Observability handle
+    observability: Option<Arc<UnifiedObservability>>,
+    
+    /// Wire format for messages
+    format: SerializationFormat,
+    
+    /// Message authentication and replay protection, once enabled
+    authenticator: Option<Arc<SyncMutex<FrameAuthenticator>>>,
+    
+    /// Whether the peer agreed to close frames during the handshake
+    close_frames: bool,
+    
+    /// Reputation tracker charged for malformed frames, and the key to charge
+    reputation: Option<(Arc<PeerReputation>, ReputationKey)>,
+}
+
+/// Parts of a connection used by both halves.
+struct Shared {
     /// Unique connection ID
     id: ConnectionId,
     
     /// Connection information
     info: ConnectionInfo,
     
-    /// The underlying stream
-    stream: Box<dyn crate::transport::traits::Stream>,
-    
     /// Connection state
-    state: Arc<Mutex<ConnectionState>>,
+    state: Mutex<ConnectionState>,
     
-    /// Observability handle
-    observability: Option<Arc<crate::observability::UnifiedObservability>>,
+    /// Bytes transferred and throughput, updated as data flows
+    progress: SyncMutex<ProgressMeter>,
 }
 
 struct ConnectionState {
     metrics: ConnectionMetrics,
     is_closed: bool,
+    is_draining: bool,
+}
+
+/// The underlying stream, shared by both halves of a connection.
+///
+/// The lock is only held for the duration of each poll, so a read waiting
+/// for data never holds up a write.
+#[derive(Clone)]
+struct SharedStream(Arc<SyncMutex<Option<Box<dyn Stream>>>>);
+
+impl SharedStream {
+    fn poll<T>(
+        &self,
+        f: impl FnOnce(Pin<&mut Box<dyn Stream>>) -> Poll<std::io::Result<T>>,
+    ) -> Poll<std::io::Result<T>> {
+        match self.0.lock().as_mut() {
+            Some(stream) => f(Pin::new(stream)),
+            None => Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "connection closed",
+            ))),
+        }
+    }
+    
+    fn close(&self) -> Result<()> {
+        match self.0.lock().as_mut() {
+            Some(stream) => stream.close(),
+            None => Ok(()),
+        }
+    }
+    
+    /// Takes the stream out for good and closes it, telling the peer why.
+    ///
+    /// Reads and writes pending at this point would never be woken, so both
+    /// halves must be idle.
+    async fn close_with_reason(&self, code: u32, reason: &str) -> Result<()> {
+        let stream = self.0.lock().take();
+        match stream {
+            Some(mut stream) => stream.close_with_reason(code, reason).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl AsyncRead for SharedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.poll(|stream| stream.poll_read(cx, buf))
+    }
+}
+
+impl AsyncWrite for SharedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.poll(|stream| stream.poll_write(cx, buf))
+    }
+    
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll(|stream| stream.poll_flush(cx))
+    }
+    
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll(|stream| stream.poll_shutdown(cx))
+    }
+}
+
+/// Length-prefix bit marking a control frame rather than a message.
+///
+/// Only used once both peers have agreed to close frames in the handshake,
+/// since older peers would read it as part of the length.
+pub(crate) const CONTROL_FRAME_FLAG: u32 = 1 << 31;
+
+/// Largest frame accepted or sent, in bytes.
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Reason code sent to the peer when a connection is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u32)]
+pub enum CloseCode {
+    /// Closed without error
+    Normal = 0,
+    
+    /// Closed because the local node is shutting down
+    Shutdown = 1,
+}
+
+/// Control frame telling the peer the connection is closing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CloseFrame {
+    code: CloseCode,
+    reason: String,
 }
 
 impl Connection {
     /// Creates a new connection.
     pub fn new(
         info: ConnectionInfo,
-        stream: Box<dyn crate::transport::traits::Stream>,
+        stream: Box<dyn Stream>,
     ) -> Self {
-        Self {
+        let stream = SharedStream(Arc::new(SyncMutex::new(Some(stream))));
+        let shared = Arc::new(Shared {
             id: info.id,
             info,
-            stream,
-            state: Arc::new(Mutex::new(ConnectionState {
+            state: Mutex::new(ConnectionState {
                 metrics: ConnectionMetrics::default(),
                 is_closed: false,
-            })),
-            observability: None,
+                is_draining: false,
+            }),
+            progress: SyncMutex::new(ProgressMeter::default()),
+        });
+        
+        Self {
+            sender: ConnectionSender {
+                stream: stream.clone(),
+                shared: shared.clone(),
+                observability: None,
+                format: SerializationFormat::default(),
+                authenticator: None,
+                close_frames: false,
+            },
+            receiver: ConnectionReceiver {
+                stream,
+                shared,
+                observability: None,
+                format: SerializationFormat::default(),
+                authenticator: None,
+                close_frames: false,
+                reputation: None,
+            },
         }
     }
     
     /// Sets the observability handle for this connection.
-    pub fn set_observability(&mut self, observability: Arc<crate::observability::UnifiedObservability>) {
-        self.observability = Some(observability);
-        
+    pub fn set_observability(&mut self, observability: Arc<UnifiedObservability>) {
         // Emit connection opened event
-        if let Some(obs) = &self.observability {
-            let handle = obs.create_handle();
-            handle.emit_event(SubstrateEvent::Connection(
-                crate::observability::ConnectionEvent::Opened {
-                    connection_id: self.id.to_string(),
-                    transport: self.info.transport,
-                }
-            ));
-        }
+        let handle = observability.create_handle();
+        handle.emit_event(SubstrateEvent::Connection(
+            crate::observability::ConnectionEvent::Opened {
+                connection_id: self.id().to_string(),
+                transport: self.info().transport,
+            }
+        ));
+        
+        self.sender.observability = Some(observability.clone());
+        self.receiver.observability = Some(observability);
+    }
+    
+    /// Reports the peer's behaviour on this connection to a reputation tracker.
+    ///
+    /// Well-formed messages earn credit under `key`, while frames that are
+    /// oversized, fail authentication, are replayed or cannot be decoded are
+    /// penalised.
+    pub fn set_reputation(&mut self, reputation: Arc<PeerReputation>, key: ReputationKey) {
+        self.receiver.reputation = Some((reputation, key));
+    }
+    
+    /// Returns the key this connection's peer is tracked under, if any.
+    pub fn reputation_key(&self) -> Option<ReputationKey> {
+        self.receiver.reputation.as_ref().map(|(_, key)| *key)
     }
     
     /// Returns the connection ID.
     pub fn id(&self) -> ConnectionId {
-        self.id
+        self.sender.shared.id
     }
     
     /// Returns connection information.
     pub fn info(&self) -> &ConnectionInfo {
-        &self.info
+        &self.sender.shared.info
     }
     
     /// Returns the protocol version string.
     pub fn protocol_version(&self) -> String {
-        match self.info.transport {
+        match self.info().transport {
             TransportType::Quic => "QUIC/1.0".to_string(),
             TransportType::WebRtc => "WebRTC/1.0".to_string(),
             TransportType::Tcp => "TCP/1.0".to_string(),
             TransportType::WebSocket => "WebSocket/1.0".to_string(),
             TransportType::Memory => "Memory/1.0".to_string(),
             TransportType::Udp => "UDP/1.0".to_string(),
+            TransportType::Relay => "Relay/1.0".to_string(),
         }
     }
     
+    /// Returns the wire format used for messages on this connection.
+    pub fn serialization_format(&self) -> SerializationFormat {
+        self.sender.format
+    }
+    
+    /// Negotiates the wire format with the remote peer.
+    ///
+    /// Both sides exchange their supported formats in preference order and
+    /// settle on the initiator's most preferred format the responder supports.
+    /// If either side offers no formats, the default format is used. The
+    /// offers themselves are always sent as JSON. The offers also record
+    /// whether each side understands close frames, so [`Connection::drain`]
+    /// only sends one to peers that do.
+    pub async fn negotiate_format(
+        &mut self,
+        supported: &[SerializationFormat],
+        initiator: bool,
+    ) -> Result<SerializationFormat> {
+        let offer = FormatOffer {
+            formats: supported.to_vec(),
+            close_frames: true,
+        };
+        self.sender.write_frame(&SerializationFormat::Json.encode(&offer)?).await?;
+        
+        let remote: FormatOffer = SerializationFormat::Json.decode(&self.receiver.read_frame().await?)?;
+        
+        let format = if supported.is_empty() || remote.formats.is_empty() {
+            Some(SerializationFormat::default())
+        } else if initiator {
+            SerializationFormat::negotiate(supported, &remote.formats)
+        } else {
+            SerializationFormat::negotiate(&remote.formats, supported)
+        };
+        
+        let format = format.ok_or_else(|| {
+            NetworkError::Protocol("No common serialization format".to_string())
+        })?;
+        self.sender.format = format;
+        self.receiver.format = format;
+        self.sender.close_frames = remote.close_frames;
+        self.receiver.close_frames = remote.close_frames;
+        
+        Ok(format)
+    }
+    
+    /// Authenticates all further messages on this connection.
+    ///
+    /// Both peers must enable authentication at the same point in the
+    /// message stream, typically right after the handshake. Replayed, stale
+    /// or tampered messages are then rejected by [`Connection::receive`].
+    pub fn enable_authentication(&mut self, authenticator: FrameAuthenticator) {
+        let authenticator = Arc::new(SyncMutex::new(authenticator));
+        self.sender.authenticator = Some(authenticator.clone());
+        self.receiver.authenticator = Some(authenticator);
+    }
+    
+    /// Returns true if messages on this connection are authenticated.
+    pub fn is_authenticated(&self) -> bool {
+        self.sender.authenticator.is_some()
+    }
+    
     /// Sends a message over the connection.
     pub async fn send(&mut self, message: &Message) -> Result<()> {
-        let start = Instant::now();
-        let data = serde_json::to_vec(message)?;
-        let bytes_len = data.len();
+        self.sender.send(message).await
+    }
+    
+    /// Receives a message from the connection.
+    ///
+    /// The outcome is reported to the reputation tracker, if one is set.
+    pub async fn receive(&mut self) -> Result<Message> {
+        self.receiver.receive().await
+    }
+    
+    /// Returns true once the connection has been closed.
+    pub async fn is_closed(&self) -> bool {
+        self.sender.is_closed().await
+    }
+    
+    /// Returns the bytes transferred so far and the smoothed throughput.
+    ///
+    /// Covers both messages and raw data written or read through the
+    /// connection's [`AsyncWrite`] and [`AsyncRead`] implementations.
+    pub fn progress(&self) -> TransferProgress {
+        self.sender.shared.progress.lock().snapshot(Instant::now())
+    }
+    
+    /// Returns the current metrics for this connection.
+    pub async fn metrics(&self) -> ConnectionMetrics {
+        let state = self.sender.shared.state.lock().await;
+        state.metrics.clone()
+    }
+    
+    /// Closes the connection.
+    pub async fn close(mut self) -> Result<()> {
+        // Close the underlying stream
+        self.sender.stream.close()?;
+        
+        self.sender.mark_closed("graceful_close").await
+    }
+    
+    /// Drains the connection and then closes it.
+    ///
+    /// New sends are rejected as soon as draining starts. Data already
+    /// written is flushed, a close frame with [`CloseCode::Shutdown`] tells
+    /// the peer why the connection is going away if it negotiated close
+    /// frames, and the stream is closed once the peer has received
+    /// everything.
+    /// A transfer in progress holds the connection exclusively, so it always
+    /// completes before the drain begins; see [`ConnectionPool::drain`] for
+    /// waiting on shared connections.
+    ///
+    /// If `timeout` passes first the connection is closed anyway and a
+    /// timeout error is returned.
+    ///
+    /// [`ConnectionPool::drain`]: crate::transport::ConnectionPool::drain
+    pub async fn drain(&mut self, timeout: Duration) -> Result<()> {
+        self.sender.drain(std::future::ready(&mut self.receiver), timeout).await
+    }
+    
+    /// Splits the connection into halves that can be used concurrently.
+    pub fn into_split(self) -> (ConnectionSender, ConnectionReceiver) {
+        (self.sender, self.receiver)
+    }
+}
+
+impl ConnectionSender {
+    /// Returns the connection ID.
+    pub fn id(&self) -> ConnectionId {
+        self.shared.id
+    }
+    
+    /// Returns true once the connection has been closed.
+    pub async fn is_closed(&self) -> bool {
+        self.shared.state.lock().await.is_closed
+    }
+    
+    /// Writes a length-prefixed frame to the stream.
+    async fn write_frame(&mut self, data: &[u8]) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        
+        if data.len() > MAX_FRAME_LEN {
+            return Err(NetworkError::Protocol(format!(
+                "Frame of {} bytes exceeds the {} byte limit",
+                data.len(),
+                MAX_FRAME_LEN
+            )));
+        }
         
-        // Use the AsyncWrite trait to send data
+        let mut frame = Vec::with_capacity(4 + data.len());
+        frame.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        frame.extend_from_slice(data);
+        
+        self.stream.write_all(&frame).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+    
+    /// Writes a close frame to the stream.
+    async fn write_close_frame(&mut self, code: CloseCode, reason: &str) -> Result<()> {
         use tokio::io::AsyncWriteExt;
+        
+        let data = SerializationFormat::Json.encode(&CloseFrame { code, reason: reason.to_string() })?;
+        self.stream.write_all(&((data.len() as u32) | CONTROL_FRAME_FLAG).to_be_bytes()).await?;
         self.stream.write_all(&data).await?;
         self.stream.flush().await?;
+        Ok(())
+    }
+    
+    /// Sends a message over the connection.
+    pub async fn send(&mut self, message: &Message) -> Result<()> {
+        if self.shared.state.lock().await.is_draining {
+            return Err(NetworkError::Connection(format!("Connection {} is draining", self.shared.id)));
+        }
+        
+        let start = Instant::now();
+        let mut data = self.format.encode(message)?;
+        if let Some(authenticator) = &self.authenticator {
+            data = authenticator.lock().seal(&data)?;
+        }
+        let bytes_len = data.len();
+        
+        self.write_frame(&data).await?;
         
         let duration = start.elapsed();
         
         // Update metrics
-        let mut state = self.state.lock().await;
+        self.shared.progress.lock().sent.record(bytes_len as u64, Instant::now());
+        let mut state = self.shared.state.lock().await;
         state.metrics.bytes_sent += bytes_len as u64;
         state.metrics.messages_sent += 1;
         
@@ -119,53 +484,60 @@ impl Connection {
         Ok(())
     }
     
-    /// Receives a message from the connection.
-    pub async fn receive(&mut self) -> Result<Message> {
-        use tokio::io::AsyncReadExt;
-        
-        // Read length prefix (4 bytes)
-        let mut len_buf = [0u8; 4];
-        self.stream.read_exact(&mut len_buf).await?;
-        let len = u32::from_be_bytes(len_buf) as usize;
+    /// Drains the connection and then closes it, as [`Connection::drain`].
+    ///
+    /// `receiver` resolves once no receive is in progress on the other half,
+    /// and the stream is only closed after that. Anything it holds, such as
+    /// a lock on the receiving half, is kept until the drain finishes.
+    pub(crate) async fn drain<R>(
+        &mut self,
+        receiver: impl Future<Output = R>,
+        timeout: Duration,
+    ) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
         
-        // Read message data
-        let mut data = vec![0u8; len];
-        self.stream.read_exact(&mut data).await?;
+        {
+            let mut state = self.shared.state.lock().await;
+            if state.is_closed {
+                return Ok(());
+            }
+            state.is_draining = true;
+        }
         
-        // Update metrics
-        let mut state = self.state.lock().await;
-        state.metrics.bytes_received += len as u64;
-        state.metrics.messages_received += 1;
+        let reason = "shutting down";
+        let drained = tokio::time::timeout(timeout, async {
+            self.stream.flush().await?;
+            if self.close_frames {
+                self.write_close_frame(CloseCode::Shutdown, reason).await?;
+            }
+            self.stream.shutdown().await?;
+            
+            // The peer's reply to the close ends any receive still waiting
+            let _receiver = receiver.await;
+            self.stream.close_with_reason(CloseCode::Shutdown as u32, reason).await
+        })
+        .await;
         
-        // Emit event
-        if let Some(obs) = &self.observability {
-            let handle = obs.create_handle();
-            handle.emit_event(SubstrateEvent::Transport(TransportEvent::DataReceived {
-                bytes: len,
-            }));
+        if drained.is_err() {
+            // The peer never confirmed; close without waiting
+            self.stream.close()?;
         }
+        self.mark_closed("drained").await?;
         
-        // Deserialize message
-        let message: Message = serde_json::from_slice(&data)?;
-        Ok(message)
-    }
-    
-    /// Returns the current metrics for this connection.
-    pub async fn metrics(&self) -> ConnectionMetrics {
-        let state = self.state.lock().await;
-        state.metrics.clone()
+        drained.map_err(|_| {
+            NetworkError::Transport(TransportError::TimeoutWithMsg(format!(
+                "Draining connection {} took longer than {:?}",
+                self.shared.id, timeout
+            )))
+        })?
     }
     
-    /// Closes the connection.
-    pub async fn close(mut self) -> Result<()> {
-        let start_time = self.info.established_at;
-        let duration = start_time.elapsed()?;
-        
-        // Close the underlying stream
-        self.stream.close()?;
+    /// Marks the connection closed and reports it.
+    async fn mark_closed(&mut self, reason: &str) -> Result<()> {
+        let duration = self.shared.info.established_at.elapsed()?;
         
         // Update state
-        let mut state = self.state.lock().await;
+        let mut state = self.shared.state.lock().await;
         state.is_closed = true;
         
         // Emit connection closed event
@@ -173,8 +545,8 @@ impl Connection {
             let handle = obs.create_handle();
             handle.emit_event(SubstrateEvent::Connection(
                 crate::observability::ConnectionEvent::Closed {
-                    connection_id: self.id.to_string(),
-                    reason: "graceful_close".to_string(),
+                    connection_id: self.shared.id.to_string(),
+                    reason: reason.to_string(),
                     duration,
                 }
             ));
@@ -182,7 +554,7 @@ impl Connection {
             // Update metrics one final time
             handle.emit_event(SubstrateEvent::Connection(
                 crate::observability::ConnectionEvent::MetricsUpdate {
-                    connection_id: self.id.to_string(),
+                    connection_id: self.shared.id.to_string(),
                     bytes_sent: state.metrics.bytes_sent,
                     bytes_received: state.metrics.bytes_received,
                     rtt_ms: state.metrics.avg_rtt.map(|d| d.as_millis() as u64),
@@ -194,6 +566,97 @@ impl Connection {
     }
 }
 
+impl ConnectionReceiver {
+    /// Returns the connection ID.
+    pub fn id(&self) -> ConnectionId {
+        self.shared.id
+    }
+    
+    /// Reads a length-prefixed frame from the stream.
+    ///
+    /// A close frame from the peer marks the connection closed and is
+    /// returned as an error. Frames longer than [`MAX_FRAME_LEN`] are
+    /// rejected before anything is allocated for them.
+    async fn read_frame(&mut self) -> Result<Vec<u8>> {
+        use tokio::io::AsyncReadExt;
+        
+        // Read length prefix (4 bytes)
+        let mut len_buf = [0u8; 4];
+        self.stream.read_exact(&mut len_buf).await?;
+        let prefix = u32::from_be_bytes(len_buf);
+        let is_control = self.close_frames && prefix & CONTROL_FRAME_FLAG != 0;
+        let len = if is_control { prefix & !CONTROL_FRAME_FLAG } else { prefix } as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(NetworkError::Protocol(format!(
+                "Frame of {} bytes exceeds the {} byte limit",
+                len, MAX_FRAME_LEN
+            )));
+        }
+        
+        // Read message data
+        let mut data = vec![0u8; len];
+        self.stream.read_exact(&mut data).await?;
+        
+        if is_control {
+            let close: CloseFrame = SerializationFormat::Json.decode(&data)?;
+            self.shared.state.lock().await.is_closed = true;
+            return Err(NetworkError::Connection(format!(
+                "Connection closed by peer ({:?}): {}",
+                close.code, close.reason
+            )));
+        }
+        
+        Ok(data)
+    }
+    
+    /// Receives a message from the connection.
+    ///
+    /// The outcome is reported to the reputation tracker, if one is set.
+    pub async fn receive(&mut self) -> Result<Message> {
+        let result = self.receive_message().await;
+        
+        if let Some((reputation, key)) = &self.reputation {
+            match &result {
+                Ok(_) => {
+                    reputation.record(*key, ReputationEvent::ValidMessage);
+                }
+                Err(e) => {
+                    reputation.record_error(*key, e);
+                }
+            }
+        }
+        
+        result
+    }
+    
+    async fn receive_message(&mut self) -> Result<Message> {
+        let data = self.read_frame().await?;
+        let len = data.len();
+        
+        // Update metrics
+        self.shared.progress.lock().received.record(len as u64, Instant::now());
+        let mut state = self.shared.state.lock().await;
+        state.metrics.bytes_received += len as u64;
+        state.metrics.messages_received += 1;
+        
+        // Emit event
+        if let Some(obs) = &self.observability {
+            let handle = obs.create_handle();
+            handle.emit_event(SubstrateEvent::Transport(TransportEvent::DataReceived {
+                bytes: len,
+            }));
+        }
+        
+        let data = match &self.authenticator {
+            Some(authenticator) => authenticator.lock().open(&data)?,
+            None => data,
+        };
+        
+        // Deserialize message
+        self.format.decode(&data)
+    }
+}
+
 // Implement AsyncRead for Connection
 impl AsyncRead for Connection {
     fn poll_read(
@@ -201,7 +664,7 @@ impl AsyncRead for Connection {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
-        Pin::new(&mut self.stream).poll_read(cx, buf)
+        Pin::new(&mut self.receiver).poll_read(cx, buf)
     }
 }
 
@@ -212,7 +675,45 @@ impl AsyncWrite for Connection {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<std::io::Result<usize>> {
-        Pin::new(&mut self.stream).poll_write(cx, buf)
+        Pin::new(&mut self.sender).poll_write(cx, buf)
+    }
+    
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.sender).poll_flush(cx)
+    }
+    
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.sender).poll_shutdown(cx)
+    }
+}
+
+impl AsyncRead for ConnectionReceiver {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut self.stream).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            let read = buf.filled().len() - before;
+            self.shared.progress.lock().received.record(read as u64, Instant::now());
+        }
+        poll
+    }
+}
+
+impl AsyncWrite for ConnectionSender {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let poll = Pin::new(&mut self.stream).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = poll {
+            self.shared.progress.lock().sent.record(written as u64, Instant::now());
+        }
+        poll
     }
     
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
@@ -292,4 +793,103 @@ mod tests {
         // Close connection
         let _ = conn.close().await;
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_progress_tracks_transferred_bytes() {
+        use crate::transport::{memory::MemoryTransport, Transport};
+        use crate::types::PeerInfo;
+        use std::time::Duration;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        let transport = MemoryTransport::new();
+        let addr: std::net::SocketAddr = "127.0.0.1:9401".parse().unwrap();
+        let mut listener = transport.listen(addr).await.unwrap();
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = addr.to_string();
+        let mut sender = transport.connect(&peer).await.unwrap();
+        let (mut receiver, _) = listener.accept().await.unwrap();
+        
+        // 160 KB in 8 KB chunks, paced at roughly 10 ms per chunk
+        let payload: Vec<u8> = (0..160 * 1024).map(|i| i as u8).collect();
+        let start = Instant::now();
+        let reader = tokio::spawn(async move {
+            let mut received = vec![0u8; 160 * 1024];
+            receiver.read_exact(&mut received).await.unwrap();
+            (receiver, received)
+        });
+        for chunk in payload.chunks(8 * 1024) {
+            sender.write_all(chunk).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        let (receiver, received) = reader.await.unwrap();
+        assert_eq!(received, payload);
+        
+        let sent = sender.progress();
+        assert_eq!(sent.bytes_sent, payload.len() as u64);
+        assert_eq!(sent.bytes_received, 0);
+        
+        // The estimate should be close to the paced rate
+        let actual_rate = payload.len() as f64 / elapsed;
+        assert!(sent.send_rate > actual_rate / 3.0 && sent.send_rate < actual_rate * 3.0,
+            "estimated {} B/s, actual {} B/s", sent.send_rate, actual_rate);
+        assert!(sent.send_eta(payload.len() as u64).unwrap() < Duration::from_secs(10));
+        
+        let progress = receiver.progress();
+        assert_eq!(progress.bytes_received, payload.len() as u64);
+        assert!(progress.receive_rate > 0.0);
+        
+        // Messages count towards progress as well
+        let message = Message {
+            id: MessageId::new(),
+            payload: vec![7; 1024],
+            metadata: MessageMetadata {
+                timestamp: SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        };
+        let encoded = sender.serialization_format().encode(&message).unwrap().len() as u64;
+        sender.send(&message).await.unwrap();
+        assert_eq!(sender.progress().bytes_sent, payload.len() as u64 + encoded);
+    }
+    
+    #[tokio::test]
+    async fn test_oversized_frames_are_rejected() {
+        use crate::transport::{tcp::TcpTransport, Transport};
+        use crate::types::PeerInfo;
+        use tokio::io::AsyncWriteExt;
+        
+        let transport = TcpTransport::new();
+        let mut listener = transport.listen("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = listener.local_addr().unwrap().to_string();
+        let mut sender = transport.connect(&peer).await.unwrap();
+        let (mut receiver, _) = listener.accept().await.unwrap();
+        
+        // A length prefix past the limit is refused before reading the body
+        sender.write_all(&(MAX_FRAME_LEN as u32 + 1).to_be_bytes()).await.unwrap();
+        sender.flush().await.unwrap();
+        let err = receiver.receive().await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"), "{}", err);
+        
+        // Without a close frame handshake the control bit is just part of the length
+        sender.write_all(&(CONTROL_FRAME_FLAG | 8).to_be_bytes()).await.unwrap();
+        sender.flush().await.unwrap();
+        let err = receiver.receive().await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"), "{}", err);
+        
+        let message = Message {
+            id: MessageId::new(),
+            payload: vec![0; MAX_FRAME_LEN],
+            metadata: MessageMetadata {
+                timestamp: SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        };
+        assert!(sender.send(&message).await.is_err());
+    }
+}
@@ -0,0 +1,510 @@
+//! Simulated in-process transport for deterministic testing.
+//!
+//! A [`LoopbackNetwork`] links any number of [`LoopbackTransport`]s so that
+//! separate network stacks in the same process can talk to each other without
+//! touching the OS network. Links can be impaired with latency, jitter and
+//! packet loss. Writes are reassembled into the length-prefixed frames a
+//! [`Connection`] sends, and each whole frame is one packet, so loss never
+//! leaves a partial frame on the wire. All randomness is drawn from a seeded
+//! generator so runs are reproducible.
+
+use crate::error::{NetworkError, Result};
+use crate::transport::traits::{Listener, Stream, Transport, TransportFeature, TransportPriority};
+use crate::transport::connection::CONTROL_FRAME_FLAG;
+use crate::transport::Connection;
+use crate::types::{ConnectionId, ConnectionInfo, ConnectionMetrics, PeerId, PeerInfo, TransportType};
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, info};
+
+/// Sending end of a one-way link, carrying packets with their scheduled delivery time.
+type LinkSender = mpsc::UnboundedSender<(Instant, Vec<u8>)>;
+
+/// Link conditions applied to every loopback connection.
+#[derive(Debug, Clone)]
+pub struct LoopbackConfig {
+    /// Base one-way delay for each packet
+    pub latency: Duration,
+
+    /// Maximum additional random delay for each packet
+    pub jitter: Duration,
+
+    /// Probability in `[0.0, 1.0]` that a packet is dropped
+    pub loss_rate: f64,
+
+    /// Seed for the loss and jitter generator
+    pub seed: u64,
+}
+
+impl Default for LoopbackConfig {
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            loss_rate: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+/// A simulated network shared by a set of loopback transports.
+pub struct LoopbackNetwork {
+    config: LoopbackConfig,
+    listeners: Mutex<HashMap<SocketAddr, mpsc::Sender<LoopbackStream>>>,
+    rng: Mutex<StdRng>,
+}
+
+impl LoopbackNetwork {
+    /// Creates a new loopback network with the given link conditions.
+    pub fn new(config: LoopbackConfig) -> Arc<Self> {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Arc::new(Self {
+            config,
+            listeners: Mutex::new(HashMap::new()),
+            rng: Mutex::new(rng),
+        })
+    }
+
+    /// Returns the link conditions of this network.
+    pub fn config(&self) -> &LoopbackConfig {
+        &self.config
+    }
+
+    /// Creates a transport attached to this network for the given local peer.
+    pub fn transport(self: &Arc<Self>, local_peer: PeerId) -> LoopbackTransport {
+        LoopbackTransport {
+            network: self.clone(),
+            local_peer,
+        }
+    }
+
+    /// Decides the fate of a packet, returning its delivery delay or `None` if it is lost.
+    fn schedule(&self) -> Option<Duration> {
+        let mut rng = self.rng.lock().unwrap();
+
+        if self.config.loss_rate > 0.0 && rng.gen::<f64>() < self.config.loss_rate {
+            return None;
+        }
+
+        let jitter = if self.config.jitter.is_zero() {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos(rng.gen_range(0..=self.config.jitter.as_nanos() as u64))
+        };
+
+        Some(self.config.latency + jitter)
+    }
+
+    /// Creates a one-way link, returning its sending and receiving ends.
+    ///
+    /// Packets are delivered in order; a packet drawing a shorter delay than
+    /// its predecessor waits for it.
+    fn link(&self) -> (LinkSender, mpsc::UnboundedReceiver<Vec<u8>>) {
+        let (in_tx, mut in_rx) = mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+        let (out_tx, out_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut last_delivery = Instant::now();
+            while let Some((deliver_at, packet)) = in_rx.recv().await {
+                last_delivery = last_delivery.max(deliver_at);
+                tokio::time::sleep_until(last_delivery).await;
+                if out_tx.send(packet).is_err() {
+                    break;
+                }
+            }
+        });
+
+        (in_tx, out_rx)
+    }
+}
+
+/// Transport endpoint on a [`LoopbackNetwork`].
+pub struct LoopbackTransport {
+    network: Arc<LoopbackNetwork>,
+    local_peer: PeerId,
+}
+
+impl LoopbackTransport {
+    /// Returns the network this transport is attached to.
+    pub fn network(&self) -> &Arc<LoopbackNetwork> {
+        &self.network
+    }
+}
+
+#[async_trait]
+impl Transport for LoopbackTransport {
+    async fn connect(&self, peer: &PeerInfo) -> Result<Connection> {
+        let addr: SocketAddr = peer.address.parse()
+            .map_err(|e| NetworkError::Configuration(format!("Invalid address: {}", e)))?;
+
+        let sender = self.network.listeners.lock().unwrap()
+            .get(&addr)
+            .cloned()
+            .ok_or_else(|| NetworkError::Connection("No listener at address".to_string()))?;
+
+        let (client_tx, server_rx) = self.network.link();
+        let (server_tx, client_rx) = self.network.link();
+
+        let id = ConnectionId::new();
+        let established_at = SystemTime::now();
+
+        let client_stream = LoopbackStream::new(
+            ConnectionInfo {
+                local_peer: self.local_peer,
+                remote_peer: peer.id,
+                id,
+                transport: TransportType::Memory,
+                established_at,
+                metrics: ConnectionMetrics::default(),
+            },
+            self.network.clone(),
+            client_tx,
+            client_rx,
+        );
+
+        let server_stream = LoopbackStream::new(
+            ConnectionInfo {
+                local_peer: peer.id,
+                remote_peer: self.local_peer,
+                id,
+                transport: TransportType::Memory,
+                established_at,
+                metrics: ConnectionMetrics::default(),
+            },
+            self.network.clone(),
+            server_tx,
+            server_rx,
+        );
+
+        sender.send(server_stream).await
+            .map_err(|_| NetworkError::Connection("Listener closed".to_string()))?;
+
+        debug!("Loopback transport connected to {}", peer.anonymized());
+
+        Ok(Connection::new(client_stream.info.clone(), Box::new(client_stream)))
+    }
+
+    async fn listen(&self, addr: SocketAddr) -> Result<Box<dyn Listener>> {
+        let (tx, rx) = mpsc::channel(1024);
+        self.network.listeners.lock().unwrap().insert(addr, tx);
+
+        info!("Loopback transport listening on {}", addr);
+
+        Ok(Box::new(LoopbackListener {
+            network: self.network.clone(),
+            receiver: rx,
+            local_addr: addr,
+        }))
+    }
+
+    fn priority(&self) -> TransportPriority {
+        TransportPriority::Low
+    }
+
+    fn transport_type(&self) -> TransportType {
+        TransportType::Memory
+    }
+
+    fn supports_feature(&self, feature: TransportFeature) -> bool {
+        match feature {
+            TransportFeature::ZeroRTT => true,
+            TransportFeature::UnreliableChannel => self.network.config.loss_rate > 0.0,
+            _ => false,
+        }
+    }
+}
+
+/// Loopback transport listener.
+pub struct LoopbackListener {
+    network: Arc<LoopbackNetwork>,
+    receiver: mpsc::Receiver<LoopbackStream>,
+    local_addr: SocketAddr,
+}
+
+#[async_trait]
+impl Listener for LoopbackListener {
+    async fn accept(&mut self) -> Result<(Connection, SocketAddr)> {
+        let stream = self.receiver.recv().await
+            .ok_or_else(|| NetworkError::Connection("Listener closed".to_string()))?;
+
+        let info = stream.info.clone();
+        Ok((Connection::new(info, Box::new(stream)), self.local_addr))
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.network.listeners.lock().unwrap().remove(&self.local_addr);
+        self.receiver.close();
+        Ok(())
+    }
+}
+
+/// Loopback transport stream.
+pub struct LoopbackStream {
+    info: ConnectionInfo,
+    network: Arc<LoopbackNetwork>,
+    outbound: LinkSender,
+    inbound: mpsc::UnboundedReceiver<Vec<u8>>,
+    read_buf: Vec<u8>,
+    /// Written bytes not yet forming a complete frame
+    write_buf: Vec<u8>,
+}
+
+impl LoopbackStream {
+    fn new(
+        info: ConnectionInfo,
+        network: Arc<LoopbackNetwork>,
+        outbound: LinkSender,
+        inbound: mpsc::UnboundedReceiver<Vec<u8>>,
+    ) -> Self {
+        Self {
+            info,
+            network,
+            outbound,
+            inbound,
+            read_buf: Vec::new(),
+            write_buf: Vec::new(),
+        }
+    }
+
+    /// Takes the next complete frame, length prefix included, off the write buffer.
+    fn next_frame(&mut self) -> Option<Vec<u8>> {
+        let prefix: [u8; 4] = self.write_buf.get(..4)?.try_into().ok()?;
+        let len = 4 + (u32::from_be_bytes(prefix) & !CONTROL_FRAME_FLAG) as usize;
+        if self.write_buf.len() < len {
+            return None;
+        }
+        Some(self.write_buf.drain(..len).collect())
+    }
+}
+
+impl Stream for LoopbackStream {
+    fn info(&self) -> ConnectionInfo {
+        self.info.clone()
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inbound.close();
+        Ok(())
+    }
+}
+
+impl AsyncRead for LoopbackStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let me = self.get_mut();
+
+        if me.read_buf.is_empty() {
+            match me.inbound.poll_recv(cx) {
+                Poll::Ready(Some(packet)) => me.read_buf = packet,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let len = buf.remaining().min(me.read_buf.len());
+        buf.put_slice(&me.read_buf[..len]);
+        me.read_buf.drain(..len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for LoopbackStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let me = self.get_mut();
+        if me.outbound.is_closed() {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "Loopback link closed",
+            )));
+        }
+
+        me.write_buf.extend_from_slice(buf);
+        while let Some(frame) = me.next_frame() {
+            // A lost frame is dropped on the simulated wire
+            if let Some(delay) = me.network.schedule() {
+                let _ = me.outbound.send((Instant::now() + delay, frame));
+            }
+        }
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NetworkConfig;
+    use crate::types::{Message, MessageId, MessageMetadata, MessagePriority};
+    use crate::NetworkStack;
+
+    fn message(payload: &[u8]) -> Message {
+        Message {
+            id: MessageId::new(),
+            payload: payload.to_vec(),
+            metadata: MessageMetadata {
+                timestamp: SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        }
+    }
+
+    /// Creates two stacks joined by the given network, with the second listening on `addr`.
+    async fn linked_stacks(
+        network: &Arc<LoopbackNetwork>,
+        addr: SocketAddr,
+        config: NetworkConfig,
+    ) -> (NetworkStack, NetworkStack, Box<dyn Listener>) {
+        let stack_a = NetworkStack::new(config.clone()).await.unwrap();
+        let stack_b = NetworkStack::new(config).await.unwrap();
+
+        let transport_a = network.transport(stack_a.transport_manager().local_peer());
+        let transport_b = network.transport(stack_b.transport_manager().local_peer());
+
+        let listener = transport_b.listen(addr).await.unwrap();
+        stack_a.transport_manager().register(TransportType::Memory, Arc::new(transport_a)).await;
+        stack_b.transport_manager().register(TransportType::Memory, Arc::new(transport_b)).await;
+
+        (stack_a, stack_b, listener)
+    }
+
+    /// Creates the two ends of a raw stream over the given network.
+    fn stream_pair(network: &Arc<LoopbackNetwork>) -> (LoopbackStream, LoopbackStream) {
+        let (a_tx, b_rx) = network.link();
+        let (b_tx, a_rx) = network.link();
+        let info = ConnectionInfo {
+            local_peer: PeerId::new(),
+            remote_peer: PeerId::new(),
+            id: ConnectionId::new(),
+            transport: TransportType::Memory,
+            established_at: SystemTime::now(),
+            metrics: ConnectionMetrics::default(),
+        };
+
+        (
+            LoopbackStream::new(info.clone(), network.clone(), a_tx, a_rx),
+            LoopbackStream::new(info, network.clone(), b_tx, b_rx),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_loss_drops_whole_frames() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let network = LoopbackNetwork::new(LoopbackConfig {
+            loss_rate: 0.5,
+            seed: 11,
+            ..Default::default()
+        });
+        let (mut sender, mut receiver) = stream_pair(&network);
+
+        // Each frame is written in pieces, as close frames are
+        for i in 0..32u8 {
+            let payload = vec![i; 16];
+            sender.write_all(&(payload.len() as u32).to_be_bytes()).await.unwrap();
+            sender.write_all(&payload[..5]).await.unwrap();
+            sender.write_all(&payload[5..]).await.unwrap();
+        }
+        drop(sender);
+
+        let mut delivered = Vec::new();
+        let mut prefix = [0u8; 4];
+        while receiver.read_exact(&mut prefix).await.is_ok() {
+            assert_eq!(u32::from_be_bytes(prefix), 16);
+            let mut payload = [0u8; 16];
+            receiver.read_exact(&mut payload).await.unwrap();
+            assert!(payload.iter().all(|&b| b == payload[0]), "frame spliced: {:?}", payload);
+            delivered.push(payload[0]);
+        }
+
+        assert!(!delivered.is_empty() && delivered.len() < 32);
+        assert!(delivered.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[tokio::test]
+    async fn test_stacks_exchange_message_over_loopback() {
+        let network = LoopbackNetwork::new(LoopbackConfig {
+            latency: Duration::from_millis(5),
+            jitter: Duration::from_millis(5),
+            seed: 7,
+            ..Default::default()
+        });
+        let addr: SocketAddr = "127.0.0.1:9300".parse().unwrap();
+        let (stack_a, stack_b, mut listener) = linked_stacks(&network, addr, NetworkConfig::default()).await;
+
+        let mut peer_b = PeerInfo::new(stack_b.transport_manager().local_peer());
+        peer_b.address = addr.to_string();
+
+        let (connection, inbound) = tokio::join!(
+            stack_a.connect(&peer_b),
+            stack_b.transport_manager().accept(listener.as_mut()),
+        );
+        let mut connection = connection.unwrap();
+        let (mut inbound, _) = inbound.unwrap();
+        assert_eq!(inbound.info().remote_peer, stack_a.transport_manager().local_peer());
+
+        connection.send(&message(b"hello over loopback")).await.unwrap();
+        let received = inbound.receive().await.unwrap();
+        assert_eq!(received.payload, b"hello over loopback");
+
+        inbound.send(&message(b"and back")).await.unwrap();
+        let reply = connection.receive().await.unwrap();
+        assert_eq!(reply.payload, b"and back");
+    }
+
+    #[tokio::test]
+    async fn test_total_loss_prevents_delivery() {
+        let network = LoopbackNetwork::new(LoopbackConfig {
+            loss_rate: 1.0,
+            ..Default::default()
+        });
+        let mut config = NetworkConfig::default();
+        config.transport.connection_timeout = Duration::from_millis(100);
+        let addr: SocketAddr = "127.0.0.1:9301".parse().unwrap();
+        let (stack_a, stack_b, mut listener) = linked_stacks(&network, addr, config).await;
+
+        let mut peer_b = PeerInfo::new(stack_b.transport_manager().local_peer());
+        peer_b.address = addr.to_string();
+
+        // Not even the handshake gets through
+        let (connection, inbound) = tokio::join!(
+            stack_a.connect(&peer_b),
+            tokio::time::timeout(
+                Duration::from_millis(300),
+                stack_b.transport_manager().accept(listener.as_mut()),
+            ),
+        );
+        assert!(connection.is_err());
+        assert!(inbound.is_err());
+    }
+}
@@ -2,13 +2,17 @@
 
 use crate::error::{NetworkError, Result, TransportError};
 use crate::observability::UnifiedObservability;
+use crate::reputation::{PeerReputation, ReputationKey};
+use crate::serialization::SerializationFormat;
 use crate::transport::{
-    traits::{Transport, TransportFeature, TransportPriority, TransportRequirements},
+    nat::{DatagramSocket, HolePunchConfig, HolePuncher},
+    traits::{Listener, Transport, TransportFeature, TransportPriority, TransportRequirements},
     Connection, ObservableTransport, TransportType,
 };
-use crate::types::PeerInfo;
+use crate::types::{PeerId, PeerInfo};
 use dashmap::DashMap;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -33,8 +37,29 @@ pub struct TransportManager {
     
     /// Observability integration
     observability: Option<Arc<UnifiedObservability>>,
+    
+    /// Identity presented to rendezvous servers
+    local_peer: PeerId,
+    
+    /// Hole punching configuration for rendezvous connections
+    hole_punch_config: HolePunchConfig,
+    
+    /// Transport used when every direct attempt fails
+    relay_fallback: Option<Arc<dyn Transport + Send + Sync>>,
+    
+    /// Peer reputation and ban tracking
+    reputation: Arc<PeerReputation>,
+    
+    /// Serialization formats offered during the handshake, in preference order
+    serialization_formats: Vec<SerializationFormat>,
+    
+    /// Longest a peer may take to complete the handshake
+    handshake_timeout: Duration,
 }
 
+/// Default time allowed for a peer to complete the connection handshake.
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Transport performance metrics.
 #[derive(Debug, Clone)]
 struct TransportMetrics {
@@ -158,6 +183,12 @@ impl TransportManager {
             default_transport,
             selection_strategy: SelectionStrategy::Adaptive,
             observability: None,
+            local_peer: PeerId::new(),
+            hole_punch_config: HolePunchConfig::default(),
+            relay_fallback: None,
+            reputation: Arc::new(PeerReputation::default()),
+            serialization_formats: Vec::new(),
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
         }
     }
     
@@ -192,6 +223,117 @@ impl TransportManager {
         self.selection_strategy = strategy;
     }
     
+    /// Sets the local peer ID used when coordinating through a rendezvous server.
+    pub fn set_local_peer(&mut self, peer: PeerId) {
+        self.local_peer = peer;
+    }
+    
+    /// Returns the local peer ID.
+    pub fn local_peer(&self) -> PeerId {
+        self.local_peer
+    }
+    
+    /// Sets the hole punching configuration.
+    pub fn set_hole_punch_config(&mut self, config: HolePunchConfig) {
+        self.hole_punch_config = config;
+    }
+    
+    /// Sets the relay transport to fall back to when direct connections fail.
+    pub fn set_relay_fallback(&mut self, relay: Arc<dyn Transport + Send + Sync>) {
+        let relay = match &self.observability {
+            Some(obs) => Arc::new(ObservableTransport::new(relay, obs.clone(), TransportType::Relay)),
+            None => relay,
+        };
+        self.relay_fallback = Some(relay);
+    }
+    
+    /// Sets the peer reputation tracker.
+    pub fn set_reputation(&mut self, reputation: Arc<PeerReputation>) {
+        self.reputation = reputation;
+    }
+    
+    /// Returns the peer reputation tracker.
+    pub fn reputation(&self) -> &Arc<PeerReputation> {
+        &self.reputation
+    }
+    
+    /// Returns true if the peer or address is currently banned.
+    pub fn is_banned(&self, key: impl Into<ReputationKey>) -> bool {
+        self.reputation.is_banned(key)
+    }
+    
+    /// Sets the serialization formats to negotiate, in preference order.
+    ///
+    /// When empty, an empty offer is sent and messages use the default
+    /// format, JSON.
+    pub fn set_serialization_formats(&mut self, formats: Vec<SerializationFormat>) {
+        self.serialization_formats = formats;
+    }
+    
+    /// Returns the serialization formats offered during the handshake.
+    pub fn serialization_formats(&self) -> &[SerializationFormat] {
+        &self.serialization_formats
+    }
+    
+    /// Sets how long a peer may take to complete the connection handshake.
+    pub fn set_handshake_timeout(&mut self, timeout: Duration) {
+        self.handshake_timeout = timeout;
+    }
+    
+    /// Runs the connection handshake, negotiating the wire format.
+    ///
+    /// The offer is exchanged even when no formats are configured, so both
+    /// sides always agree on where application frames begin. Attaches the
+    /// reputation tracker first, so a peer that botches the handshake or
+    /// stays silent past the handshake timeout is penalised under `key`.
+    async fn handshake(&self, connection: &mut Connection, key: ReputationKey, initiator: bool) -> Result<()> {
+        connection.set_reputation(self.reputation.clone(), key);
+        
+        let negotiation = connection.negotiate_format(&self.serialization_formats, initiator);
+        let format = tokio::time::timeout(self.handshake_timeout, negotiation).await
+            .unwrap_or_else(|_| Err(NetworkError::Transport(TransportError::TimeoutWithMsg(
+                "Connection handshake timed out".to_string(),
+            ))))
+            .inspect_err(|e| {
+                self.reputation.record_error(key, e);
+            })?;
+        debug!(
+            "Negotiated {} with {}",
+            format,
+            connection.info().remote_peer.anonymized()
+        );
+        Ok(())
+    }
+    
+    /// Accepts the next connection from a peer that is not banned.
+    ///
+    /// Inbound transports such as TCP cannot vouch for the peer ID they
+    /// report, so inbound connections are tracked by remote address: a
+    /// misbehaving peer cannot shed its record by reconnecting. Connections
+    /// from a banned address or peer ID are closed and dropped, as are
+    /// connections whose handshake fails or times out.
+    pub async fn accept(&self, listener: &mut dyn Listener) -> Result<(Connection, SocketAddr)> {
+        loop {
+            let (mut connection, addr) = listener.accept().await?;
+            let remote = connection.info().remote_peer;
+            let key = ReputationKey::from(addr);
+            
+            if self.is_banned(key) || self.is_banned(remote) {
+                warn!("Refusing connection from banned peer {} ({})", remote.anonymized(), key);
+                let _ = connection.close().await;
+                continue;
+            }
+            
+            match self.handshake(&mut connection, key, false).await {
+                Ok(()) => return Ok((connection, addr)),
+                Err(e) => {
+                    warn!("Handshake with {} ({}) failed: {}", remote.anonymized(), key, e);
+                    let _ = connection.close().await;
+                }
+            }
+        }
+    }
+    
     /// Registers a transport.
     pub async fn register(&self, transport_type: TransportType, transport: Arc<dyn Transport + Send + Sync>) {
         // Wrap with observability if available
@@ -214,7 +356,38 @@ impl TransportManager {
     }
     
     /// Connects to a peer using the best available transport.
+    ///
+    /// Falls back to the configured relay if no direct connection succeeds.
+    ///
+    /// Refuses peers whose ID or address is banned.
     pub async fn connect(&self, peer: &PeerInfo) -> Result<Connection> {
+        let address_banned = peer.address.parse::<SocketAddr>()
+            .is_ok_and(|addr| self.is_banned(addr));
+        if self.is_banned(peer.id) || address_banned {
+            return Err(NetworkError::PeerBanned(peer.id.anonymized()));
+        }
+        
+        let mut connection = match self.connect_direct(peer).await {
+            Ok(connection) => connection,
+            Err(direct_error) => {
+                let Some(relay) = &self.relay_fallback else {
+                    return Err(direct_error);
+                };
+                
+                warn!("Direct connection to {} failed ({}), falling back to relay", peer.id, direct_error);
+                
+                let connection = relay.connect(peer).await?;
+                self.update_peer_history(peer, TransportType::Relay, true);
+                connection
+            }
+        };
+        
+        self.handshake(&mut connection, ReputationKey::Peer(peer.id), true).await?;
+        Ok(connection)
+    }
+    
+    /// Connects to a peer directly using the best available transport.
+    async fn connect_direct(&self, peer: &PeerInfo) -> Result<Connection> {
         let requirements = self.infer_requirements(peer);
         let transport_type = self.select_transport(&requirements, peer).await?;
         
@@ -225,9 +398,8 @@ impl TransportManager {
             metrics.attempts += 1;
         }
         
-        // Attempt connection
-        let transports = self.transports.read().await;
-        let transport = transports.get(&transport_type)
+        // Attempt connection, without holding the transport table while dialling
+        let transport = self.transports.read().await.get(&transport_type).cloned()
             .ok_or_else(|| NetworkError::Transport(
                 TransportError::NotAvailable(format!("Transport {:?} not available", transport_type))
             ))?;
@@ -273,6 +445,44 @@ impl TransportManager {
         }
     }
     
+    /// Connects directly to a peer behind a NAT by hole punching.
+    ///
+    /// Both peers must call this with each other's ID and the same rendezvous
+    /// server at roughly the same time. The server exchanges their candidate
+    /// addresses, after which both sides probe each other simultaneously until
+    /// a direct UDP path opens.
+    pub async fn connect_via_rendezvous(&self, peer: &PeerInfo, rendezvous: SocketAddr) -> Result<Connection> {
+        let bind_addr: SocketAddr = if rendezvous.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        
+        let socket = tokio::net::UdpSocket::bind(bind_addr).await
+            .map_err(|e| NetworkError::Transport(TransportError::Udp(e.to_string())))?;
+        
+        self.connect_via_rendezvous_with(peer, rendezvous, Arc::new(socket)).await
+    }
+    
+    /// Connects via a rendezvous server, punching from the given socket.
+    pub async fn connect_via_rendezvous_with(
+        &self,
+        peer: &PeerInfo,
+        rendezvous: SocketAddr,
+        socket: Arc<dyn DatagramSocket>,
+    ) -> Result<Connection> {
+        debug!("Hole punching to {} via rendezvous {}", peer.id, rendezvous);
+        
+        let puncher = HolePuncher::new(self.local_peer, socket, self.hole_punch_config.clone())?;
+        let mut connection = puncher.connect(peer.id, rendezvous).await?;
+        
+        if let Some(obs) = &self.observability {
+            connection.set_observability(obs.clone());
+        }
+        
+        Ok(connection)
+    }
+    
     /// Selects the best transport based on strategy and requirements.
     async fn select_transport(
         &self,
@@ -502,11 +712,10 @@ impl TransportManager {
         peer: &PeerInfo,
         failed_transport: TransportType,
     ) -> Result<Connection> {
-        let transports = self.transports.read().await;
-        
         // Get all transports sorted by priority
-        let mut fallback_transports: Vec<_> = transports.iter()
+        let mut fallback_transports: Vec<_> = self.transports.read().await.iter()
             .filter(|(t, _)| **t != failed_transport)
+            .map(|(t, transport)| (*t, transport.clone()))
             .collect();
         
         fallback_transports.sort_by_key(|(_, transport)| std::cmp::Reverse(transport.priority()));
@@ -516,13 +725,13 @@ impl TransportManager {
             
             if let Ok(connection) = transport.connect(peer).await {
                 // Update metrics for successful fallback
-                if let Some(mut metrics) = self.transport_metrics.get_mut(transport_type) {
+                if let Some(mut metrics) = self.transport_metrics.get_mut(&transport_type) {
                     metrics.attempts += 1;
                     metrics.successes += 1;
                     metrics.active_connections += 1;
                 }
                 
-                self.update_peer_history(peer, *transport_type, true);
+                self.update_peer_history(peer, transport_type, true);
                 return Ok(connection);
             }
         }
@@ -610,4 +819,288 @@ mod tests {
         
         assert!(metrics.successes as f64 / metrics.attempts as f64 > 0.8);
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_invalid_messages_ban_peer_until_expiry() {
+        let mut manager = TransportManager::new(TransportType::Memory);
+        manager.set_reputation(Arc::new(PeerReputation::new(crate::config::ReputationConfig {
+            ban_duration: Duration::from_millis(200),
+            ..Default::default()
+        })));
+        
+        let transport = Arc::new(MemoryTransport::new());
+        manager.register(TransportType::Memory, transport.clone()).await;
+        
+        let addr: SocketAddr = "127.0.0.1:9200".parse().unwrap();
+        let mut listener = transport.listen(addr).await.unwrap();
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = addr.to_string();
+        
+        let error = NetworkError::InvalidMessage("malformed frame".to_string());
+        for _ in 0..10 {
+            manager.reputation().record_error(&peer.id, &error);
+        }
+        
+        assert!(manager.reputation().score(&peer.id) <= manager.reputation().config().ban_threshold);
+        assert!(manager.is_banned(&peer.id));
+        assert!(matches!(manager.connect(&peer).await, Err(NetworkError::PeerBanned(_))));
+        
+        // Inbound connections from the banned peer are dropped
+        let _inbound = transport.connect(&peer).await.unwrap();
+        let accepted = tokio::time::timeout(
+            Duration::from_millis(50),
+            manager.accept(listener.as_mut()),
+        ).await;
+        assert!(accepted.is_err());
+        
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        
+        assert!(!manager.is_banned(&peer.id));
+        let (outbound, inbound) = tokio::join!(
+            manager.connect(&peer),
+            manager.accept(listener.as_mut()),
+        );
+        outbound.unwrap();
+        let (connection, _) = inbound.unwrap();
+        assert_eq!(connection.info().remote_peer, peer.id);
+    }
+    
+    /// Completes the handshake from a raw client with an empty format offer.
+    async fn write_offer(client: &mut tokio::net::TcpStream) {
+        use tokio::io::AsyncWriteExt;
+        
+        let offer = br#"{"formats":[]}"#;
+        client.write_all(&(offer.len() as u32).to_be_bytes()).await.unwrap();
+        client.write_all(offer).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_tcp_peer_sending_garbage_is_banned_by_address() {
+        use crate::transport::tcp::TcpTransport;
+        use tokio::io::AsyncWriteExt;
+        use tokio::net::TcpStream;
+        
+        let mut manager = TransportManager::new(TransportType::Tcp);
+        manager.set_reputation(Arc::new(PeerReputation::new(crate::config::ReputationConfig {
+            ban_duration: Duration::from_secs(60),
+            ..Default::default()
+        })));
+        
+        let mut listener = TcpTransport::new().listen("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let listen_addr = listener.local_addr().unwrap();
+        let remote_ip: std::net::IpAddr = "127.0.0.1".parse().unwrap();
+        
+        // Each connection reports a fresh peer ID, so only the address
+        // carries the record across reconnects
+        let mut reported_peers = Vec::new();
+        while !manager.is_banned(remote_ip) {
+            let mut client = TcpStream::connect(listen_addr).await.unwrap();
+            write_offer(&mut client).await;
+            let (mut inbound, addr) = manager.accept(listener.as_mut()).await.unwrap();
+            assert_eq!(addr.ip(), remote_ip);
+            assert_eq!(inbound.reputation_key(), Some(ReputationKey::Address(remote_ip)));
+            reported_peers.push(inbound.info().remote_peer);
+            
+            let garbage = b"not a message";
+            client.write_all(&(garbage.len() as u32).to_be_bytes()).await.unwrap();
+            client.write_all(garbage).await.unwrap();
+            
+            let before = manager.reputation().score(remote_ip);
+            assert!(matches!(inbound.receive().await, Err(NetworkError::InvalidMessage(_))));
+            assert!(manager.reputation().score(remote_ip) < before);
+            assert!(reported_peers.len() < 10, "peer was never banned");
+        }
+        
+        assert!(reported_peers.len() > 1);
+        assert!(reported_peers.iter().all(|peer| !manager.is_banned(peer)));
+        assert_eq!(manager.reputation().banned_addresses(), vec![remote_ip]);
+        
+        // A fresh connection from the banned address is refused
+        let _client = TcpStream::connect(listen_addr).await.unwrap();
+        let accepted = tokio::time::timeout(
+            Duration::from_millis(100),
+            manager.accept(listener.as_mut()),
+        ).await;
+        assert!(accepted.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_peers_negotiate_mutual_serialization_format() {
+        use crate::types::{Message, MessageId, MessageMetadata, MessagePriority};
+        
+        let transport = Arc::new(MemoryTransport::new());
+        let addr: SocketAddr = "127.0.0.1:9201".parse().unwrap();
+        let mut listener = transport.listen(addr).await.unwrap();
+        
+        let mut dialer = TransportManager::new(TransportType::Memory);
+        dialer.set_serialization_formats(vec![
+            SerializationFormat::Cbor,
+            SerializationFormat::MessagePack,
+            SerializationFormat::Json,
+        ]);
+        dialer.register(TransportType::Memory, transport.clone()).await;
+        
+        let mut listener_side = TransportManager::new(TransportType::Memory);
+        listener_side.set_serialization_formats(vec![
+            SerializationFormat::Bincode,
+            SerializationFormat::MessagePack,
+            SerializationFormat::Json,
+        ]);
+        
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = addr.to_string();
+        
+        let (outbound, inbound) = tokio::join!(
+            dialer.connect(&peer),
+            listener_side.accept(listener.as_mut()),
+        );
+        let mut outbound = outbound.unwrap();
+        let (mut inbound, _) = inbound.unwrap();
+        
+        assert_eq!(outbound.serialization_format(), SerializationFormat::MessagePack);
+        assert_eq!(inbound.serialization_format(), SerializationFormat::MessagePack);
+        
+        let message = Message {
+            id: MessageId::new(),
+            payload: b"negotiated".to_vec(),
+            metadata: MessageMetadata {
+                timestamp: std::time::SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        };
+        outbound.send(&message).await.unwrap();
+        
+        let received = inbound.receive().await.unwrap();
+        assert_eq!(received.id, message.id);
+        assert_eq!(received.payload, message.payload);
+    }
+    
+    #[tokio::test]
+    async fn test_authenticated_connection_rejects_replayed_frames() {
+        use crate::config::ReplayConfig;
+        use crate::replay::FrameAuthenticator;
+        use crate::types::{Message, MessageId, MessageMetadata, MessagePriority};
+        use tokio::io::AsyncWriteExt;
+        
+        let transport = Arc::new(MemoryTransport::new());
+        let addr: SocketAddr = "127.0.0.1:9202".parse().unwrap();
+        let mut listener = transport.listen(addr).await.unwrap();
+        
+        let mut dialer = TransportManager::new(TransportType::Memory);
+        dialer.register(TransportType::Memory, transport.clone()).await;
+        let listener_side = TransportManager::new(TransportType::Memory);
+        
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = addr.to_string();
+        
+        let (outbound, inbound) = tokio::join!(
+            dialer.connect(&peer),
+            listener_side.accept(listener.as_mut()),
+        );
+        let mut outbound = outbound.unwrap();
+        let (mut inbound, _) = inbound.unwrap();
+        
+        outbound.enable_authentication(FrameAuthenticator::new(b"dialer", b"listener", ReplayConfig::default()));
+        inbound.enable_authentication(FrameAuthenticator::new(b"listener", b"dialer", ReplayConfig::default()));
+        
+        let message = |payload: &[u8]| Message {
+            id: MessageId::new(),
+            payload: payload.to_vec(),
+            metadata: MessageMetadata {
+                timestamp: std::time::SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        };
+        
+        outbound.send(&message(b"fresh")).await.unwrap();
+        assert_eq!(inbound.receive().await.unwrap().payload, b"fresh");
+        
+        // Stand in for an attacker replaying a captured frame: the dialer's
+        // first frame, byte for byte
+        let mut captured_sender = FrameAuthenticator::new(b"dialer", b"listener", ReplayConfig::default());
+        let captured = captured_sender
+            .seal(&outbound.serialization_format().encode(&message(b"fresh")).unwrap())
+            .unwrap();
+        outbound.write_all(&(captured.len() as u32).to_be_bytes()).await.unwrap();
+        outbound.write_all(&captured).await.unwrap();
+        outbound.flush().await.unwrap();
+        assert!(matches!(inbound.receive().await, Err(NetworkError::InvalidMessage(_))));
+        
+        outbound.send(&message(b"after replay")).await.unwrap();
+        assert_eq!(inbound.receive().await.unwrap().payload, b"after replay");
+    }
+    
+    #[tokio::test]
+    async fn test_formats_configured_on_one_side_fall_back_to_default() {
+        use crate::types::{Message, MessageId, MessageMetadata, MessagePriority};
+        
+        let transport = Arc::new(MemoryTransport::new());
+        let addr: SocketAddr = "127.0.0.1:9202".parse().unwrap();
+        let mut listener = transport.listen(addr).await.unwrap();
+        
+        let mut dialer = TransportManager::new(TransportType::Memory);
+        dialer.set_serialization_formats(vec![SerializationFormat::Cbor]);
+        dialer.register(TransportType::Memory, transport.clone()).await;
+        
+        let listener_side = TransportManager::new(TransportType::Memory);
+        
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = addr.to_string();
+        
+        let (outbound, inbound) = tokio::join!(
+            dialer.connect(&peer),
+            listener_side.accept(listener.as_mut()),
+        );
+        let mut outbound = outbound.unwrap();
+        let (mut inbound, _) = inbound.unwrap();
+        
+        assert_eq!(outbound.serialization_format(), SerializationFormat::Json);
+        assert_eq!(inbound.serialization_format(), SerializationFormat::Json);
+        
+        let message = Message {
+            id: MessageId::new(),
+            payload: b"default format".to_vec(),
+            metadata: MessageMetadata {
+                timestamp: std::time::SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        };
+        outbound.send(&message).await.unwrap();
+        assert_eq!(inbound.receive().await.unwrap().payload, message.payload);
+    }
+    
+    #[tokio::test]
+    async fn test_silent_or_broken_peers_do_not_stall_accept() {
+        use crate::transport::tcp::TcpTransport;
+        use tokio::io::AsyncWriteExt;
+        use tokio::net::TcpStream;
+        
+        let mut manager = TransportManager::new(TransportType::Tcp);
+        manager.set_handshake_timeout(Duration::from_millis(100));
+        
+        let mut listener = TcpTransport::new().listen("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let listen_addr = listener.local_addr().unwrap();
+        
+        // One peer never sends its offer, the next sends garbage instead
+        let _silent = TcpStream::connect(listen_addr).await.unwrap();
+        let mut broken = TcpStream::connect(listen_addr).await.unwrap();
+        broken.write_all(&4u32.to_be_bytes()).await.unwrap();
+        broken.write_all(b"junk").await.unwrap();
+        
+        let mut good = TcpStream::connect(listen_addr).await.unwrap();
+        write_offer(&mut good).await;
+        let good_addr = good.local_addr().unwrap();
+        
+        let (_, addr) = tokio::time::timeout(
+            Duration::from_secs(2),
+            manager.accept(listener.as_mut()),
+        ).await.expect("accept stalled").unwrap();
+        assert_eq!(addr, good_addr);
+    }
+}
@@ -53,6 +53,7 @@ impl Transport for MemoryTransport {
         let client_stream = MemoryStream {
             read_rx: rx1,
             write_tx: tx2,
+            read_buf: Vec::new(),
             info: ConnectionInfo {
                 local_peer: peer.id,
                 remote_peer: peer.id,
@@ -66,6 +67,7 @@ impl Transport for MemoryTransport {
         let server_stream = MemoryStream {
             read_rx: rx2,
             write_tx: tx1,
+            read_buf: Vec::new(),
             info: client_stream.info.clone(),
         };
         
@@ -148,6 +150,7 @@ pub struct MemoryStream {
     read_rx: mpsc::Receiver<Vec<u8>>,
     write_tx: mpsc::Sender<Vec<u8>>,
     info: ConnectionInfo,
+    read_buf: Vec<u8>,
 }
 
 impl Stream for MemoryStream {
@@ -168,15 +171,19 @@ impl AsyncRead for MemoryStream {
         buf: &mut ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
         let me = self.get_mut();
-        match me.read_rx.poll_recv(cx) {
-            Poll::Ready(Some(data)) => {
-                let len = std::cmp::min(buf.remaining(), data.len());
-                buf.put_slice(&data[..len]);
-                Poll::Ready(Ok(()))
+        if me.read_buf.is_empty() {
+            match me.read_rx.poll_recv(cx) {
+                Poll::Ready(Some(data)) => me.read_buf = data,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
             }
-            Poll::Ready(None) => Poll::Ready(Ok(())),
-            Poll::Pending => Poll::Pending,
         }
+        
+        // Keep whatever does not fit for the next read
+        let len = std::cmp::min(buf.remaining(), me.read_buf.len());
+        buf.put_slice(&me.read_buf[..len]);
+        me.read_buf.drain(..len);
+        Poll::Ready(Ok(()))
     }
 }
 
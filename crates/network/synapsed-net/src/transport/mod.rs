@@ -5,20 +5,32 @@
 
 pub mod connection;
 pub mod libp2p_simple;
+#[cfg(feature = "test-transport")]
+pub mod loopback;
 pub mod manager;
 pub mod memory;
+pub mod nat;
+pub mod pool;
+pub mod progress;
 pub mod quic;
+pub mod relay;
 pub mod signaling;
 pub mod tcp;
 pub mod traits;
 pub mod websocket;
 pub mod webrtc;
 
-pub use connection::{Connection, ConnectionImpl};
+pub use connection::{CloseCode, Connection, ConnectionImpl, ConnectionReceiver, ConnectionSender};
 pub use libp2p_simple::{Libp2pTransport, Libp2pConfig};
+#[cfg(feature = "test-transport")]
+pub use loopback::{LoopbackConfig, LoopbackNetwork, LoopbackTransport};
 pub use manager::TransportManager;
 pub use memory::MemoryTransport;
+pub use nat::{Candidate, CandidateType, DatagramSocket, HolePunchConfig, HolePuncher, RendezvousServer};
+pub use pool::{ConnectionPool, PooledConnection};
+pub use progress::{ThroughputEstimator, TransferProgress};
 pub use quic::QuicTransport;
+pub use relay::{RelayServer, RelayTransport};
 pub use signaling::{SignalingClient, WebRTCConnectionPool};
 pub use tcp::TcpTransport;
 pub use traits::{Transport, TransportFeature, TransportPriority, TransportRequirements};
@@ -0,0 +1,1048 @@
+//! NAT traversal through rendezvous-coordinated UDP hole punching.
+//!
+//! Two peers behind NATs cannot dial each other directly: neither NAT has a
+//! mapping that admits unsolicited inbound packets. Each peer instead gathers
+//! ICE-style candidate addresses, registers them with a rendezvous server
+//! (which adds the server-reflexive address it observed), and receives the
+//! other peer's candidates in return. Both peers then probe every remote
+//! candidate at the same time from the same socket. The outbound probes open
+//! mappings in each NAT, so the other side's probes get through, and the first
+//! candidate that answers becomes the direct path.
+//!
+//! The peers then run QUIC over the punched path, with the lower peer ID as
+//! the client. Each peer registers a self-signed certificate alongside its
+//! candidates, and both sides pin the certificate the rendezvous server
+//! introduced, so the session is mutually authenticated.
+
+use crate::error::{NetworkError, Result, TransportError};
+use crate::transport::quic::QuicStream;
+use crate::transport::traits::Stream;
+use crate::transport::Connection;
+use crate::types::{ConnectionId, ConnectionInfo, PeerId, TransportType};
+use async_trait::async_trait;
+use quinn::crypto::rustls::{QuicClientConfig, QuicServerConfig};
+use quinn::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use quinn::rustls::crypto::{verify_tls12_signature, verify_tls13_signature, WebPkiSupportedAlgorithms};
+use quinn::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use quinn::rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use quinn::rustls::{DigitallySignedStruct, DistinguishedName, Error as TlsError, SignatureScheme};
+use quinn::udp::{RecvMeta, Transmit};
+use quinn::{AsyncUdpSocket, ClientConfig, Endpoint, EndpointConfig, ServerConfig, TokioRuntime, UdpPoller};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::IoSliceMut;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Maximum UDP payload size
+const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// Datagram tag for rendezvous and connectivity-check messages
+const CONTROL_TAG: u8 = 0;
+
+/// Datagram tag for QUIC packets on a punched path
+const DATA_TAG: u8 = 1;
+
+/// Server name for QUIC on a punched path; certificates are pinned instead
+/// of being checked against it
+const QUIC_SERVER_NAME: &str = "synapsed";
+
+/// First byte the client writes, so the server learns of the stream
+const STREAM_PREAMBLE: u8 = 0;
+
+/// Received QUIC packets queued before the endpoint reads them
+const RECV_QUEUE_SIZE: usize = 1024;
+
+/// How long the rendezvous server keeps an unanswered registration
+const REGISTRATION_TTL: Duration = Duration::from_secs(60);
+
+/// A datagram socket that hole punching can run over.
+///
+/// Implemented for [`UdpSocket`]; other implementations can wrap a socket
+/// with additional behaviour, such as a simulated NAT in tests.
+#[async_trait]
+pub trait DatagramSocket: Send + Sync {
+    /// Sends a datagram to the given address.
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> std::io::Result<usize>;
+
+    /// Receives a datagram and the address it came from.
+    async fn recv_from(&self, buf: &mut [u8]) -> std::io::Result<(usize, SocketAddr)>;
+
+    /// Returns the local address the socket is bound to.
+    fn local_addr(&self) -> std::io::Result<SocketAddr>;
+}
+
+#[async_trait]
+impl DatagramSocket for UdpSocket {
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> std::io::Result<usize> {
+        UdpSocket::send_to(self, buf, target).await
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> std::io::Result<(usize, SocketAddr)> {
+        UdpSocket::recv_from(self, buf).await
+    }
+
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        UdpSocket::local_addr(self)
+    }
+}
+
+/// How a candidate address was discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CandidateType {
+    /// An address bound on a local interface
+    Host,
+
+    /// The NAT's public mapping, as observed by the rendezvous server
+    ServerReflexive,
+
+    /// An address learned from a connectivity check response
+    PeerReflexive,
+}
+
+impl CandidateType {
+    /// Type preference used when computing candidate priority (RFC 8445 §5.1.2.2).
+    fn preference(self) -> u32 {
+        match self {
+            CandidateType::Host => 126,
+            CandidateType::PeerReflexive => 110,
+            CandidateType::ServerReflexive => 100,
+        }
+    }
+}
+
+/// A transport address a peer may be reachable at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Candidate {
+    /// How the candidate was discovered
+    pub candidate_type: CandidateType,
+
+    /// The candidate address
+    pub address: SocketAddr,
+
+    /// Priority; higher candidates are probed first
+    pub priority: u32,
+}
+
+impl Candidate {
+    /// Creates a candidate with the standard priority for its type.
+    pub fn new(candidate_type: CandidateType, address: SocketAddr) -> Self {
+        // Single component, maximum local preference
+        let priority = (candidate_type.preference() << 24) | (0xFFFF << 8) | 0xFF;
+        Self {
+            candidate_type,
+            address,
+            priority,
+        }
+    }
+}
+
+/// Messages exchanged with the rendezvous server and between peers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ControlMessage {
+    /// Peer registers its candidates and the peer it wants to reach
+    Register {
+        peer: PeerId,
+        target: PeerId,
+        candidates: Vec<Candidate>,
+        certificate: Vec<u8>,
+    },
+
+    /// Rendezvous introduces the target's candidates to a registered peer
+    Introduce {
+        peer: PeerId,
+        reflexive: SocketAddr,
+        candidates: Vec<Candidate>,
+        certificate: Vec<u8>,
+    },
+
+    /// Connectivity check sent to a remote candidate
+    Probe { from: PeerId },
+
+    /// Response to a connectivity check
+    ProbeAck { from: PeerId },
+}
+
+impl ControlMessage {
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut datagram = vec![CONTROL_TAG];
+        datagram.extend(serde_json::to_vec(self)?);
+        Ok(datagram)
+    }
+
+    fn decode(datagram: &[u8]) -> Option<Self> {
+        match datagram.split_first() {
+            Some((&CONTROL_TAG, body)) => serde_json::from_slice(body).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Hole punching timing configuration.
+#[derive(Debug, Clone)]
+pub struct HolePunchConfig {
+    /// Interval between registration retries and probe rounds
+    pub probe_interval: Duration,
+
+    /// Overall deadline for rendezvous plus connectivity checks
+    pub timeout: Duration,
+}
+
+impl Default for HolePunchConfig {
+    fn default() -> Self {
+        Self {
+            probe_interval: Duration::from_millis(100),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The direct path selected by hole punching.
+#[derive(Debug, Clone)]
+pub struct PunchedPath {
+    /// Remote address that answered our connectivity checks
+    pub remote: SocketAddr,
+
+    /// The remote candidate the address corresponds to
+    pub candidate: Candidate,
+
+    /// Our own public address as observed by the rendezvous server
+    pub reflexive: SocketAddr,
+
+    /// Certificate the remote registered with the rendezvous server
+    certificate: CertificateDer<'static>,
+
+    /// Packets the remote sent before this side finished its checks
+    early_data: Vec<Vec<u8>>,
+}
+
+/// A registration waiting for its counterpart at the rendezvous server.
+struct Registration {
+    target: PeerId,
+    observed: SocketAddr,
+    candidates: Vec<Candidate>,
+    certificate: Vec<u8>,
+    registered_at: Instant,
+}
+
+/// Rendezvous server that introduces peers to each other.
+///
+/// The server only relays candidate lists; application traffic never flows
+/// through it.
+pub struct RendezvousServer {
+    socket: Arc<dyn DatagramSocket>,
+}
+
+impl RendezvousServer {
+    /// Binds a rendezvous server to the given address.
+    pub async fn bind(addr: SocketAddr) -> Result<Self> {
+        let socket = UdpSocket::bind(addr).await
+            .map_err(|e| NetworkError::Transport(TransportError::Udp(e.to_string())))?;
+
+        info!("Rendezvous server listening on {}", addr);
+
+        Ok(Self::new(Arc::new(socket)))
+    }
+
+    /// Creates a rendezvous server on an existing socket.
+    pub fn new(socket: Arc<dyn DatagramSocket>) -> Self {
+        Self { socket }
+    }
+
+    /// Returns the address the server is reachable at.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Runs the server on a background task.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let mut pending: HashMap<PeerId, Registration> = HashMap::new();
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+
+        loop {
+            let (len, src) = match self.socket.recv_from(&mut buf).await {
+                Ok(received) => received,
+                Err(e) => {
+                    warn!("Rendezvous receive failed: {}", e);
+                    continue;
+                }
+            };
+
+            let Some(ControlMessage::Register { peer, target, mut candidates, certificate }) = ControlMessage::decode(&buf[..len]) else {
+                continue;
+            };
+
+            let reflexive = Candidate::new(CandidateType::ServerReflexive, src);
+            if !candidates.iter().any(|c| c.address == src) {
+                candidates.push(reflexive);
+            }
+
+            pending.retain(|_, r| r.registered_at.elapsed() < REGISTRATION_TTL);
+            pending.insert(peer, Registration {
+                target,
+                observed: src,
+                candidates,
+                certificate,
+                registered_at: Instant::now(),
+            });
+
+            // Introduce both sides once each has asked for the other. Peers keep
+            // re-registering until introduced, so a lost introduction is retried.
+            let (Some(local), Some(remote)) = (pending.get(&peer), pending.get(&target)) else {
+                continue;
+            };
+            if remote.target != peer {
+                continue;
+            }
+
+            debug!("Introducing {} and {}", peer.anonymized(), target.anonymized());
+
+            let introductions = [
+                (local.observed, target, remote),
+                (remote.observed, peer, local),
+            ];
+            for (to, about, registration) in introductions {
+                let message = ControlMessage::Introduce {
+                    peer: about,
+                    reflexive: to,
+                    candidates: registration.candidates.clone(),
+                    certificate: registration.certificate.clone(),
+                };
+                match message.encode() {
+                    Ok(datagram) => {
+                        if let Err(e) = self.socket.send_to(&datagram, to).await {
+                            warn!("Rendezvous introduction to {} failed: {}", to, e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to encode introduction: {}", e),
+                }
+            }
+        }
+    }
+}
+
+/// Self-signed certificate a peer presents on punched QUIC connections.
+struct QuicIdentity {
+    certificate: CertificateDer<'static>,
+    private_key: PrivateKeyDer<'static>,
+}
+
+impl QuicIdentity {
+    fn generate() -> Result<Self> {
+        let cert = rcgen::generate_simple_self_signed(vec![QUIC_SERVER_NAME.to_string()])
+            .map_err(|e| NetworkError::Transport(TransportError::Quic(e.to_string())))?;
+        let private_key = PrivateKeyDer::try_from(cert.key_pair.serialize_der())
+            .map_err(|e| NetworkError::Transport(TransportError::Quic(e.to_string())))?;
+
+        Ok(Self {
+            certificate: cert.cert.der().clone(),
+            private_key,
+        })
+    }
+}
+
+/// Coordinates hole punching towards a single remote peer.
+pub struct HolePuncher {
+    local_peer: PeerId,
+    socket: Arc<dyn DatagramSocket>,
+    config: HolePunchConfig,
+    identity: QuicIdentity,
+}
+
+impl HolePuncher {
+    /// Creates a hole puncher that probes from the given socket.
+    pub fn new(local_peer: PeerId, socket: Arc<dyn DatagramSocket>, config: HolePunchConfig) -> Result<Self> {
+        Ok(Self {
+            local_peer,
+            socket,
+            config,
+            identity: QuicIdentity::generate()?,
+        })
+    }
+
+    /// Gathers host candidates for the local socket.
+    ///
+    /// A socket bound to the unspecified address is reachable on every
+    /// interface, so the loopback address and the address of the interface
+    /// carrying the default route are both offered.
+    pub fn gather_host_candidates(&self) -> Result<Vec<Candidate>> {
+        let local = self.socket.local_addr()?;
+
+        if !local.ip().is_unspecified() {
+            return Ok(vec![Candidate::new(CandidateType::Host, local)]);
+        }
+
+        let (loopback, probe): (IpAddr, SocketAddr) = if local.is_ipv4() {
+            (Ipv4Addr::LOCALHOST.into(), SocketAddr::from(([192, 0, 2, 1], 9)))
+        } else {
+            (Ipv6Addr::LOCALHOST.into(), SocketAddr::from(([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1], 9)))
+        };
+
+        let mut candidates = vec![Candidate::new(CandidateType::Host, SocketAddr::new(loopback, local.port()))];
+
+        // Connecting a UDP socket sends nothing but resolves the outbound interface
+        let unspecified = SocketAddr::new(local.ip(), 0);
+        if let Ok(ip) = std::net::UdpSocket::bind(unspecified)
+            .and_then(|s| s.connect(probe).and_then(|_| s.local_addr()))
+            .map(|addr| addr.ip())
+        {
+            if !ip.is_unspecified() && !ip.is_loopback() {
+                candidates.push(Candidate::new(CandidateType::Host, SocketAddr::new(ip, local.port())));
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    /// Exchanges candidates through the rendezvous server and punches a direct path.
+    pub async fn punch(&self, target: PeerId, rendezvous: SocketAddr) -> Result<PunchedPath> {
+        tokio::time::timeout(self.config.timeout, self.punch_inner(target, rendezvous))
+            .await
+            .map_err(|_| NetworkError::Transport(TransportError::TimeoutWithMsg(
+                format!("hole punching to {} timed out", target.anonymized())
+            )))?
+    }
+
+    /// Punches a direct path and returns a QUIC connection over it.
+    pub async fn connect(&self, target: PeerId, rendezvous: SocketAddr) -> Result<Connection> {
+        let path = self.punch(target, rendezvous).await?;
+
+        info!(
+            "Hole punched to {} via {:?} candidate",
+            target.anonymized(),
+            path.candidate.candidate_type
+        );
+
+        let info = ConnectionInfo {
+            local_peer: self.local_peer,
+            remote_peer: target,
+            id: ConnectionId::new(),
+            transport: TransportType::Quic,
+            established_at: SystemTime::now(),
+            metrics: Default::default(),
+        };
+
+        let stream = tokio::time::timeout(self.config.timeout, self.handshake(target, path, info.clone()))
+            .await
+            .map_err(|_| NetworkError::Transport(TransportError::TimeoutWithMsg(
+                format!("QUIC handshake with {} timed out", target.anonymized())
+            )))??;
+
+        Ok(Connection::new(info, Box::new(stream)))
+    }
+
+    /// Runs QUIC over the punched path, pinning the introduced certificate.
+    async fn handshake(&self, target: PeerId, path: PunchedPath, info: ConnectionInfo) -> Result<PunchedStream> {
+        let socket = PunchedSocket::new(
+            self.socket.clone(),
+            self.local_peer,
+            target,
+            path.remote,
+            path.early_data,
+        )?;
+        // Explicit, so a second rustls provider elsewhere in the build can't make the default ambiguous
+        let provider = Arc::new(quinn::rustls::crypto::ring::default_provider());
+        let verifier = Arc::new(PinnedCertificate::new(
+            path.certificate,
+            provider.signature_verification_algorithms,
+        ));
+        let local_certificate = vec![self.identity.certificate.clone()];
+        let quic_error = |e: quinn::crypto::rustls::NoInitialCipherSuite| {
+            NetworkError::Transport(TransportError::Quic(e.to_string()))
+        };
+
+        if self.local_peer.as_bytes() < target.as_bytes() {
+            let crypto = quinn::rustls::ClientConfig::builder_with_provider(provider)
+                .with_protocol_versions(&[&quinn::rustls::version::TLS13])?
+                .dangerous()
+                .with_custom_certificate_verifier(verifier)
+                .with_client_auth_cert(local_certificate, self.identity.private_key.clone_key())?;
+            let mut config = ClientConfig::new(Arc::new(QuicClientConfig::try_from(crypto).map_err(quic_error)?));
+            config.transport_config(punched_transport_config());
+
+            let endpoint = Endpoint::new_with_abstract_socket(
+                EndpointConfig::default(),
+                None,
+                Arc::new(socket),
+                Arc::new(TokioRuntime),
+            )?;
+            let connection = endpoint.connect_with(config, path.remote, QUIC_SERVER_NAME)?.await?;
+            let (mut send, recv) = connection.open_bi().await?;
+            send.write_all(&[STREAM_PREAMBLE]).await?;
+
+            Ok(PunchedStream::new(QuicStream::new(connection, send, recv), endpoint, info))
+        } else {
+            let crypto = quinn::rustls::ServerConfig::builder_with_provider(provider)
+                .with_protocol_versions(&[&quinn::rustls::version::TLS13])?
+                .with_client_cert_verifier(verifier)
+                .with_single_cert(local_certificate, self.identity.private_key.clone_key())?;
+            let mut config = ServerConfig::with_crypto(Arc::new(QuicServerConfig::try_from(crypto).map_err(quic_error)?));
+            config.transport_config(punched_transport_config());
+
+            let endpoint = Endpoint::new_with_abstract_socket(
+                EndpointConfig::default(),
+                Some(config),
+                Arc::new(socket),
+                Arc::new(TokioRuntime),
+            )?;
+            let connection = loop {
+                let incoming = endpoint.accept().await.ok_or_else(|| {
+                    NetworkError::Transport(TransportError::Quic("endpoint closed".to_string()))
+                })?;
+                if incoming.remote_address() == path.remote {
+                    break incoming.await?;
+                }
+                incoming.refuse();
+            };
+            let (send, mut recv) = connection.accept_bi().await?;
+            let mut preamble = [0u8; 1];
+            recv.read_exact(&mut preamble)
+                .await
+                .map_err(|e| NetworkError::Transport(TransportError::Quic(e.to_string())))?;
+
+            Ok(PunchedStream::new(QuicStream::new(connection, send, recv), endpoint, info))
+        }
+    }
+
+    async fn punch_inner(&self, target: PeerId, rendezvous: SocketAddr) -> Result<PunchedPath> {
+        let register = ControlMessage::Register {
+            peer: self.local_peer,
+            target,
+            candidates: self.gather_host_candidates()?,
+            certificate: self.identity.certificate.to_vec(),
+        }.encode()?;
+        let probe = ControlMessage::Probe { from: self.local_peer }.encode()?;
+        let ack = ControlMessage::ProbeAck { from: self.local_peer }.encode()?;
+
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut interval = tokio::time::interval(self.config.probe_interval);
+
+        // Rendezvous: register until the server introduces the target
+        let (reflexive, mut remote_candidates, certificate) = loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    self.socket.send_to(&register, rendezvous).await?;
+                }
+                received = self.socket.recv_from(&mut buf) => {
+                    let (len, src) = received?;
+                    match ControlMessage::decode(&buf[..len]) {
+                        Some(ControlMessage::Introduce { peer, reflexive, candidates, certificate }) if peer == target => {
+                            break (reflexive, candidates, CertificateDer::from(certificate));
+                        }
+                        // The target was introduced first and is already probing
+                        Some(ControlMessage::Probe { from }) if from == target => {
+                            let _ = self.socket.send_to(&ack, src).await;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        };
+
+        remote_candidates.sort_by_key(|c| std::cmp::Reverse(c.priority));
+        debug!("Probing {} candidates for {}", remote_candidates.len(), target.anonymized());
+
+        // Connectivity checks: probe every candidate until one answers
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    for candidate in &remote_candidates {
+                        // Unreachable candidates are expected; keep probing the rest
+                        if let Err(e) = self.socket.send_to(&probe, candidate.address).await {
+                            debug!("Probe to {} failed: {}", candidate.address, e);
+                        }
+                    }
+                }
+                received = self.socket.recv_from(&mut buf) => {
+                    let (len, src) = received?;
+                    match ControlMessage::decode(&buf[..len]) {
+                        Some(ControlMessage::Probe { from }) if from == target => {
+                            let _ = self.socket.send_to(&ack, src).await;
+                        }
+                        Some(ControlMessage::ProbeAck { from }) if from == target => {
+                            let candidate = remote_candidates.iter()
+                                .find(|c| c.address == src)
+                                .cloned()
+                                .unwrap_or_else(|| Candidate::new(CandidateType::PeerReflexive, src));
+
+                            return Ok(PunchedPath {
+                                remote: src,
+                                candidate,
+                                reflexive,
+                                certificate,
+                                early_data: Vec::new(),
+                            });
+                        }
+                        Some(_) => {}
+                        // The remote finished first and is already sending packets,
+                        // which proves the path as well as an acknowledgement would
+                        None => {
+                            if let (Some((&DATA_TAG, payload)), Some(candidate)) = (
+                                buf[..len].split_first(),
+                                remote_candidates.iter().find(|c| c.address == src),
+                            ) {
+                                return Ok(PunchedPath {
+                                    remote: src,
+                                    candidate: candidate.clone(),
+                                    reflexive,
+                                    certificate,
+                                    early_data: vec![payload.to_vec()],
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// QUIC transport parameters for punched paths.
+///
+/// Keep-alives stop the NAT mappings from expiring while the connection idles.
+fn punched_transport_config() -> Arc<quinn::TransportConfig> {
+    let mut config = quinn::TransportConfig::default();
+    config.keep_alive_interval(Some(Duration::from_secs(10)));
+    config.max_idle_timeout(Duration::from_secs(30).try_into().ok());
+    Arc::new(config)
+}
+
+/// Carries QUIC packets over a punched path.
+///
+/// Packets travel as data datagrams on the socket used for punching, which
+/// keeps answering the remote peer's connectivity checks, since the remote
+/// side may still be probing after this side selected the path.
+#[derive(Debug)]
+struct PunchedSocket {
+    local_addr: SocketAddr,
+    remote: SocketAddr,
+    incoming: Mutex<mpsc::Receiver<Vec<u8>>>,
+    outgoing: mpsc::UnboundedSender<(Vec<u8>, SocketAddr)>,
+    reader: JoinHandle<()>,
+    writer: JoinHandle<()>,
+}
+
+impl PunchedSocket {
+    fn new(
+        socket: Arc<dyn DatagramSocket>,
+        local_peer: PeerId,
+        remote_peer: PeerId,
+        remote: SocketAddr,
+        early_data: Vec<Vec<u8>>,
+    ) -> Result<Self> {
+        let local_addr = socket.local_addr()?;
+        let ack = ControlMessage::ProbeAck { from: local_peer }.encode()?;
+
+        let (incoming_tx, incoming_rx) = mpsc::channel::<Vec<u8>>(RECV_QUEUE_SIZE);
+        for packet in early_data {
+            let _ = incoming_tx.try_send(packet);
+        }
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<(Vec<u8>, SocketAddr)>();
+
+        let reader_socket = socket.clone();
+        let reader = tokio::spawn(async move {
+            let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+            loop {
+                let (len, src) = match reader_socket.recv_from(&mut buf).await {
+                    Ok(received) => received,
+                    Err(e) => {
+                        debug!("Punched path receive failed: {}", e);
+                        break;
+                    }
+                };
+
+                if let Some((&DATA_TAG, packet)) = buf[..len].split_first() {
+                    if src != remote {
+                        continue;
+                    }
+                    // Like a full socket buffer, drop packets QUIC is too slow to read
+                    if let Err(mpsc::error::TrySendError::Closed(_)) = incoming_tx.try_send(packet.to_vec()) {
+                        break;
+                    }
+                } else if let Some(ControlMessage::Probe { from }) = ControlMessage::decode(&buf[..len]) {
+                    if from == remote_peer {
+                        let _ = reader_socket.send_to(&ack, src).await;
+                    }
+                }
+            }
+        });
+
+        let writer = tokio::spawn(async move {
+            while let Some((packet, destination)) = outgoing_rx.recv().await {
+                let mut datagram = Vec::with_capacity(packet.len() + 1);
+                datagram.push(DATA_TAG);
+                datagram.extend_from_slice(&packet);
+
+                if let Err(e) = socket.send_to(&datagram, destination).await {
+                    debug!("Punched path send failed: {}", e);
+                }
+            }
+        });
+
+        Ok(Self {
+            local_addr,
+            remote,
+            incoming: Mutex::new(incoming_rx),
+            outgoing: outgoing_tx,
+            reader,
+            writer,
+        })
+    }
+}
+
+impl AsyncUdpSocket for PunchedSocket {
+    fn create_io_poller(self: Arc<Self>) -> Pin<Box<dyn UdpPoller>> {
+        Box::pin(AlwaysWritable)
+    }
+
+    fn try_send(&self, transmit: &Transmit) -> std::io::Result<()> {
+        self.outgoing
+            .send((transmit.contents.to_vec(), transmit.destination))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Punched path closed"))
+    }
+
+    fn poll_recv(
+        &self,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+        meta: &mut [RecvMeta],
+    ) -> Poll<std::io::Result<usize>> {
+        let mut incoming = self.incoming.lock().unwrap_or_else(|e| e.into_inner());
+        match incoming.poll_recv(cx) {
+            Poll::Ready(Some(packet)) => {
+                let len = std::cmp::min(packet.len(), bufs[0].len());
+                bufs[0][..len].copy_from_slice(&packet[..len]);
+                meta[0] = RecvMeta {
+                    addr: self.remote,
+                    len,
+                    stride: len,
+                    ecn: None,
+                    dst_ip: None,
+                };
+                Poll::Ready(Ok(1))
+            }
+            Poll::Ready(None) => Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "Punched path closed",
+            ))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        Ok(self.local_addr)
+    }
+}
+
+impl Drop for PunchedSocket {
+    fn drop(&mut self) {
+        self.reader.abort();
+        self.writer.abort();
+    }
+}
+
+/// Write readiness for [`PunchedSocket`].
+///
+/// The outgoing queue is unbounded; QUIC congestion control paces what
+/// goes into it.
+#[derive(Debug)]
+struct AlwaysWritable;
+
+impl UdpPoller for AlwaysWritable {
+    fn poll_writable(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Accepts exactly the certificate the rendezvous server introduced.
+///
+/// Used on both sides of a punched connection: the client pins the server's
+/// certificate and the server pins the client's.
+#[derive(Debug)]
+struct PinnedCertificate {
+    certificate: CertificateDer<'static>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl PinnedCertificate {
+    fn new(certificate: CertificateDer<'static>, algorithms: WebPkiSupportedAlgorithms) -> Self {
+        Self {
+            certificate,
+            algorithms,
+        }
+    }
+
+    fn verify(&self, end_entity: &CertificateDer<'_>) -> std::result::Result<(), TlsError> {
+        if end_entity.as_ref() == self.certificate.as_ref() {
+            Ok(())
+        } else {
+            Err(TlsError::General("certificate does not match the introduced peer".to_string()))
+        }
+    }
+}
+
+impl ServerCertVerifier for PinnedCertificate {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, TlsError> {
+        self.verify(end_entity).map(|_| ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+impl ClientCertVerifier for PinnedCertificate {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> std::result::Result<ClientCertVerified, TlsError> {
+        self.verify(end_entity).map(|_| ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+/// QUIC stream over a punched path.
+pub struct PunchedStream {
+    quic: QuicStream,
+    info: ConnectionInfo,
+    /// Drives the connection; dropping it closes the punched path
+    _endpoint: Endpoint,
+}
+
+impl PunchedStream {
+    fn new(quic: QuicStream, endpoint: Endpoint, info: ConnectionInfo) -> Self {
+        Self {
+            quic,
+            info,
+            _endpoint: endpoint,
+        }
+    }
+}
+
+#[async_trait]
+impl Stream for PunchedStream {
+    fn info(&self) -> ConnectionInfo {
+        self.info.clone()
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.quic.close()
+    }
+
+    async fn close_with_reason(&mut self, code: u32, reason: &str) -> Result<()> {
+        self.quic.close_with_reason(code, reason).await
+    }
+}
+
+impl AsyncRead for PunchedStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.quic).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for PunchedStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.quic).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.quic).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.quic).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportManager;
+    use crate::types::PeerInfo;
+    use std::collections::HashSet;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// A host behind a port-restricted cone NAT.
+    ///
+    /// Outbound datagrams leave through the NAT's public socket and open a
+    /// mapping for their destination; inbound datagrams from any address the
+    /// host has not sent to are dropped.
+    struct NattedSocket {
+        private_addr: SocketAddr,
+        public: UdpSocket,
+        permitted: std::sync::Mutex<HashSet<SocketAddr>>,
+    }
+
+    impl NattedSocket {
+        async fn new(private_addr: SocketAddr) -> Arc<Self> {
+            Arc::new(Self {
+                private_addr,
+                public: UdpSocket::bind("127.0.0.1:0").await.unwrap(),
+                permitted: std::sync::Mutex::new(HashSet::new()),
+            })
+        }
+
+        fn public_addr(&self) -> SocketAddr {
+            self.public.local_addr().unwrap()
+        }
+
+        fn permits(&self, src: SocketAddr) -> bool {
+            self.permitted.lock().unwrap().contains(&src)
+        }
+    }
+
+    #[async_trait]
+    impl DatagramSocket for NattedSocket {
+        async fn send_to(&self, buf: &[u8], target: SocketAddr) -> std::io::Result<usize> {
+            self.permitted.lock().unwrap().insert(target);
+            self.public.send_to(buf, target).await
+        }
+
+        async fn recv_from(&self, buf: &mut [u8]) -> std::io::Result<(usize, SocketAddr)> {
+            loop {
+                let (len, src) = self.public.recv_from(buf).await?;
+                if self.permits(src) {
+                    return Ok((len, src));
+                }
+            }
+        }
+
+        fn local_addr(&self) -> std::io::Result<SocketAddr> {
+            Ok(self.private_addr)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peers_behind_nat_connect_directly() {
+        let rendezvous = RendezvousServer::bind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let rendezvous_addr = rendezvous.local_addr().unwrap();
+        let rendezvous_task = rendezvous.spawn();
+
+        let nat_a = NattedSocket::new("10.0.0.2:4000".parse().unwrap()).await;
+        let nat_b = NattedSocket::new("10.0.1.2:4000".parse().unwrap()).await;
+
+        // Without coordination, B's NAT drops A's traffic
+        nat_a.send_to(b"unsolicited", nat_b.public_addr()).await.unwrap();
+        let mut buf = [0u8; 64];
+        assert!(tokio::time::timeout(Duration::from_millis(100), nat_b.recv_from(&mut buf)).await.is_err());
+
+        let mut manager_a = TransportManager::new(TransportType::Udp);
+        let mut manager_b = TransportManager::new(TransportType::Udp);
+        manager_a.set_local_peer(PeerId::new());
+        manager_b.set_local_peer(PeerId::new());
+
+        let peer_a = PeerInfo::new(manager_a.local_peer());
+        let peer_b = PeerInfo::new(manager_b.local_peer());
+
+        let (conn_a, conn_b) = tokio::join!(
+            manager_a.connect_via_rendezvous_with(&peer_b, rendezvous_addr, nat_a.clone()),
+            manager_b.connect_via_rendezvous_with(&peer_a, rendezvous_addr, nat_b.clone()),
+        );
+        let mut conn_a = conn_a.unwrap();
+        let mut conn_b = conn_b.unwrap();
+        assert_eq!(conn_a.info().transport, TransportType::Quic);
+
+        // Traffic must flow directly, not through the rendezvous server
+        rendezvous_task.abort();
+
+        conn_a.write_all(b"hello through the nat").await.unwrap();
+        let mut received = [0u8; 21];
+        tokio::time::timeout(Duration::from_secs(5), conn_b.read_exact(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&received, b"hello through the nat");
+
+        conn_b.write_all(b"and back").await.unwrap();
+        let mut received = [0u8; 8];
+        tokio::time::timeout(Duration::from_secs(5), conn_a.read_exact(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&received, b"and back");
+
+        assert!(nat_b.permits(nat_a.public_addr()));
+        assert!(nat_a.permits(nat_b.public_addr()));
+    }
+
+    #[test]
+    fn test_candidate_priority_prefers_host() {
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let host = Candidate::new(CandidateType::Host, addr);
+        let reflexive = Candidate::new(CandidateType::ServerReflexive, addr);
+
+        assert!(host.priority > reflexive.priority);
+    }
+}
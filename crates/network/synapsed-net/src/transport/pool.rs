@@ -0,0 +1,613 @@
+//! Connection pooling for reusing live connections to the same peer.
+//!
+//! Re-establishing a connection for every request to a peer wastes a full
+//! handshake. The pool caches connections keyed by [`PeerId`], evicts them
+//! after an idle timeout, caps how many are kept per peer, and checks that a
+//! connection is still healthy before handing it out again.
+
+use crate::config::PoolConfig;
+use crate::error::{Result, TransportError};
+use crate::transport::{Connection, ConnectionReceiver, ConnectionSender};
+use crate::types::{ConnectionId, Message, PeerId};
+use dashmap::DashMap;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::{debug, warn};
+
+/// A shareable handle to a pooled connection.
+///
+/// Cloning the handle does not open a new connection; all clones refer to the
+/// same underlying [`Connection`]. Its sending and receiving halves are
+/// locked separately, so one clone can send while another waits for the
+/// next message.
+#[derive(Clone)]
+pub struct PooledConnection {
+    id: ConnectionId,
+    peer: PeerId,
+    inner: Arc<Halves>,
+}
+
+/// The two halves of a pooled connection.
+struct Halves {
+    sender: Mutex<ConnectionSender>,
+    receiver: Mutex<ConnectionReceiver>,
+}
+
+impl PooledConnection {
+    fn new(peer: PeerId, connection: Connection) -> Self {
+        let id = connection.id();
+        let (sender, receiver) = connection.into_split();
+        Self {
+            id,
+            peer,
+            inner: Arc::new(Halves {
+                sender: Mutex::new(sender),
+                receiver: Mutex::new(receiver),
+            }),
+        }
+    }
+    
+    /// Returns the ID of the underlying connection.
+    pub fn id(&self) -> ConnectionId {
+        self.id
+    }
+    
+    /// Returns the peer this connection belongs to.
+    pub fn peer(&self) -> PeerId {
+        self.peer
+    }
+    
+    /// Locks the sending half for exclusive use.
+    pub async fn sender(&self) -> MutexGuard<'_, ConnectionSender> {
+        self.inner.sender.lock().await
+    }
+    
+    /// Locks the receiving half for exclusive use.
+    pub async fn receiver(&self) -> MutexGuard<'_, ConnectionReceiver> {
+        self.inner.receiver.lock().await
+    }
+    
+    /// Sends a message over the underlying connection.
+    pub async fn send(&self, message: &Message) -> Result<()> {
+        self.inner.sender.lock().await.send(message).await
+    }
+    
+    /// Receives a message from the underlying connection.
+    ///
+    /// Only other receives wait for this one; sends go ahead meanwhile.
+    pub async fn receive(&self) -> Result<Message> {
+        self.inner.receiver.lock().await.receive().await
+    }
+    
+    /// Returns true if the underlying connection is still usable.
+    pub async fn is_healthy(&self) -> bool {
+        !self.inner.sender.lock().await.is_closed().await
+    }
+    
+    /// Like [`PooledConnection::is_healthy`], but a connection locked by
+    /// another user is assumed healthy instead of waiting for the lock.
+    async fn is_healthy_or_busy(&self) -> bool {
+        match self.inner.sender.try_lock() {
+            Ok(sender) => !sender.is_closed().await,
+            Err(_) => true,
+        }
+    }
+    
+    /// Waits for the send in progress to finish, then drains the connection.
+    ///
+    /// A receive in progress is given until the same deadline to finish
+    /// before the stream is closed. Both the wait and the drain itself must
+    /// complete within `timeout`.
+    pub async fn drain(&self, timeout: Duration) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut sender = tokio::time::timeout_at(deadline, self.inner.sender.lock())
+            .await
+            .map_err(|_| {
+                TransportError::TimeoutWithMsg(format!(
+                    "Connection {} still busy after {:?}",
+                    self.id, timeout
+                ))
+            })?;
+        
+        sender
+            .drain(
+                self.inner.receiver.lock(),
+                deadline.saturating_duration_since(tokio::time::Instant::now()),
+            )
+            .await
+    }
+}
+
+impl std::fmt::Debug for PooledConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledConnection")
+            .field("id", &self.id)
+            .field("peer", &self.peer)
+            .finish()
+    }
+}
+
+/// A pooled connection and when it was last handed out.
+struct PoolEntry {
+    connection: PooledConnection,
+    last_used: Instant,
+}
+
+/// Caches live connections per peer for reuse.
+pub struct ConnectionPool {
+    config: PoolConfig,
+    entries: DashMap<PeerId, Vec<PoolEntry>>,
+    /// Every connection handed out, pooled or not, so shutdown can drain it
+    handed_out: DashMap<ConnectionId, (PeerId, Weak<Halves>)>,
+}
+
+impl ConnectionPool {
+    /// Creates a new connection pool.
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            config,
+            entries: DashMap::new(),
+            handed_out: DashMap::new(),
+        }
+    }
+    
+    /// Returns the pool configuration.
+    pub fn config(&self) -> &PoolConfig {
+        &self.config
+    }
+    
+    /// Returns a live pooled connection to the peer, if one is available.
+    ///
+    /// Idle connections are evicted first, and unhealthy connections are
+    /// dropped from the pool instead of being returned.
+    pub async fn get(&self, peer: &PeerId) -> Option<PooledConnection> {
+        if !self.config.enabled {
+            return None;
+        }
+        
+        self.evict_idle();
+        
+        // Take the most recently used connections first
+        let candidates: Vec<PooledConnection> = {
+            let entries = self.entries.get(peer)?;
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used));
+            entries.into_iter().map(|entry| entry.connection.clone()).collect()
+        };
+        
+        for candidate in candidates {
+            if self.config.health_check && !candidate.is_healthy_or_busy().await {
+                debug!("Dropping unhealthy pooled connection {} to {}", candidate.id(), peer);
+                self.remove(peer, candidate.id());
+                continue;
+            }
+            
+            if let Some(mut entries) = self.entries.get_mut(peer) {
+                if let Some(entry) = entries.iter_mut().find(|e| e.connection.id() == candidate.id()) {
+                    entry.last_used = Instant::now();
+                    return Some(candidate);
+                }
+            }
+        }
+        
+        None
+    }
+    
+    /// Adds a newly established connection to the pool.
+    ///
+    /// If the peer already has `max_per_peer` pooled connections, the least
+    /// recently used one is evicted to make room. When pooling is disabled the
+    /// connection is wrapped but not retained.
+    pub fn insert(&self, peer: PeerId, connection: Connection) -> PooledConnection {
+        let pooled = PooledConnection::new(peer, connection);
+        
+        // Connections outside the pool are still drained on shutdown
+        self.handed_out.retain(|_, (_, connection)| connection.strong_count() > 0);
+        self.handed_out.insert(pooled.id, (peer, Arc::downgrade(&pooled.inner)));
+        
+        if !self.config.enabled || self.config.max_per_peer == 0 {
+            return pooled;
+        }
+        
+        let mut entries = self.entries.entry(peer).or_default();
+        while entries.len() >= self.config.max_per_peer {
+            if let Some((oldest, _)) = entries.iter().enumerate().min_by_key(|(_, e)| e.last_used) {
+                let evicted = entries.remove(oldest);
+                debug!("Evicting pooled connection {} to {}: per-peer limit reached", evicted.connection.id(), peer);
+            }
+        }
+        
+        entries.push(PoolEntry {
+            connection: pooled.clone(),
+            last_used: Instant::now(),
+        });
+        
+        pooled
+    }
+    
+    /// Removes a specific connection from the pool.
+    pub fn remove(&self, peer: &PeerId, id: ConnectionId) -> bool {
+        let removed = match self.entries.get_mut(peer) {
+            Some(mut entries) => {
+                let before = entries.len();
+                entries.retain(|e| e.connection.id() != id);
+                entries.len() != before
+            }
+            None => false,
+        };
+        
+        self.entries.remove_if(peer, |_, entries| entries.is_empty());
+        removed
+    }
+    
+    /// Evicts connections that have been idle longer than the idle timeout.
+    ///
+    /// Returns the number of connections evicted.
+    pub fn evict_idle(&self) -> usize {
+        let idle_timeout = self.config.idle_timeout;
+        let mut evicted = 0;
+        
+        for mut entries in self.entries.iter_mut() {
+            let before = entries.len();
+            entries.retain(|e| e.last_used.elapsed() < idle_timeout);
+            evicted += before - entries.len();
+        }
+        
+        self.entries.retain(|_, entries| !entries.is_empty());
+        
+        if evicted > 0 {
+            debug!("Evicted {} idle pooled connections", evicted);
+        }
+        
+        evicted
+    }
+    
+    /// Returns the number of pooled connections for a peer.
+    pub fn peer_connections(&self, peer: &PeerId) -> usize {
+        self.entries.get(peer).map(|e| e.len()).unwrap_or(0)
+    }
+    
+    /// Returns the total number of pooled connections.
+    pub fn len(&self) -> usize {
+        self.entries.iter().map(|e| e.len()).sum()
+    }
+    
+    /// Returns true if no connections are pooled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Drops all pooled connections.
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+    
+    /// Removes all pooled connections and drains them concurrently.
+    ///
+    /// Connections handed out by [`ConnectionPool::insert`] that are no
+    /// longer pooled, because pooling is disabled or they were evicted, are
+    /// drained too while anything still holds them. Transfers in progress are
+    /// allowed to finish before each connection is closed, as long as they do
+    /// so within `timeout`. Returns the number of connections that could not
+    /// be drained in time.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let connections: Vec<PooledConnection> = self
+            .handed_out
+            .iter()
+            .filter_map(|entry| {
+                let (peer, connection) = entry.value();
+                connection.upgrade().map(|inner| PooledConnection {
+                    id: *entry.key(),
+                    peer: *peer,
+                    inner,
+                })
+            })
+            .collect();
+        self.entries.clear();
+        self.handed_out.clear();
+        
+        let results = futures::future::join_all(
+            connections.iter().map(|connection| connection.drain(timeout)),
+        )
+        .await;
+        
+        let mut failed = 0;
+        for (connection, result) in connections.iter().zip(results) {
+            if let Err(e) = result {
+                warn!("Failed to drain connection {} to {}: {}", connection.id(), connection.peer(), e);
+                failed += 1;
+            }
+        }
+        failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::traits::MockStream;
+    use crate::types::{ConnectionInfo, ConnectionMetrics, TransportType};
+    use std::time::{Duration, SystemTime};
+    
+    fn mock_connection(peer: PeerId) -> Connection {
+        let info = ConnectionInfo {
+            local_peer: PeerId::new(),
+            remote_peer: peer,
+            id: ConnectionId::new(),
+            transport: TransportType::Memory,
+            established_at: SystemTime::now(),
+            metrics: ConnectionMetrics::default(),
+        };
+        let stream = Box::new(MockStream {
+            read_data: vec![],
+            write_data: vec![],
+            info: info.clone(),
+        });
+        Connection::new(info, stream)
+    }
+    
+    #[tokio::test]
+    async fn test_pool_reuses_connection() {
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let peer = PeerId::new();
+        
+        assert!(pool.get(&peer).await.is_none());
+        
+        let inserted = pool.insert(peer, mock_connection(peer));
+        let reused = pool.get(&peer).await.expect("pooled connection");
+        
+        assert_eq!(inserted.id(), reused.id());
+        assert_eq!(pool.peer_connections(&peer), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_checkout_does_not_wait_for_busy_connection() {
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let peer = PeerId::new();
+        let inserted = pool.insert(peer, mock_connection(peer));
+        
+        // Another user is mid-transfer on the connection
+        let _busy = inserted.sender().await;
+        
+        let reused = tokio::time::timeout(Duration::from_millis(100), pool.get(&peer))
+            .await
+            .expect("checkout should not wait for the connection lock")
+            .expect("pooled connection");
+        assert_eq!(reused.id(), inserted.id());
+    }
+    
+    #[tokio::test]
+    async fn test_pool_evicts_idle_connections() {
+        let pool = ConnectionPool::new(PoolConfig {
+            idle_timeout: Duration::from_millis(50),
+            ..PoolConfig::default()
+        });
+        let peer = PeerId::new();
+        
+        pool.insert(peer, mock_connection(peer));
+        assert_eq!(pool.len(), 1);
+        
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        
+        assert!(pool.get(&peer).await.is_none());
+        assert!(pool.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_pool_enforces_max_per_peer() {
+        let pool = ConnectionPool::new(PoolConfig {
+            max_per_peer: 2,
+            ..PoolConfig::default()
+        });
+        let peer = PeerId::new();
+        
+        let first = pool.insert(peer, mock_connection(peer));
+        pool.insert(peer, mock_connection(peer));
+        pool.insert(peer, mock_connection(peer));
+        
+        assert_eq!(pool.peer_connections(&peer), 2);
+        assert!(!pool.remove(&peer, first.id()));
+    }
+    
+    /// Connects a dialer to a listener, optionally running the format handshake.
+    async fn connected_pair(
+        transport: impl crate::transport::Transport,
+        addr: std::net::SocketAddr,
+        handshake: bool,
+    ) -> (Connection, Connection) {
+        use crate::serialization::SerializationFormat;
+        use crate::transport::Transport;
+        use crate::types::PeerInfo;
+        
+        let mut listener = transport.listen(addr).await.unwrap();
+        let mut peer = PeerInfo::new(PeerId::new());
+        peer.address = listener.local_addr().unwrap().to_string();
+        let mut outbound = transport.connect(&peer).await.unwrap();
+        let (mut inbound, _) = listener.accept().await.unwrap();
+        
+        if handshake {
+            let formats = [SerializationFormat::Json];
+            let (dialer, acceptor) = tokio::join!(
+                outbound.negotiate_format(&formats, true),
+                inbound.negotiate_format(&formats, false),
+            );
+            dialer.unwrap();
+            acceptor.unwrap();
+        }
+        (outbound, inbound)
+    }
+    
+    async fn memory_pair() -> (Connection, Connection) {
+        use crate::transport::memory::MemoryTransport;
+        
+        connected_pair(MemoryTransport::new(), "127.0.0.1:9301".parse().unwrap(), true).await
+    }
+    
+    async fn tcp_pair(handshake: bool) -> (Connection, Connection) {
+        use crate::transport::tcp::TcpTransport;
+        
+        connected_pair(TcpTransport::new(), "127.0.0.1:0".parse().unwrap(), handshake).await
+    }
+    
+    fn message(payload: &[u8]) -> Message {
+        use crate::types::{MessageId, MessageMetadata, MessagePriority};
+        
+        Message {
+            id: MessageId::new(),
+            payload: payload.to_vec(),
+            metadata: MessageMetadata {
+                timestamp: SystemTime::now(),
+                priority: MessagePriority::Normal,
+                requires_ack: false,
+                substrate_context: None,
+            },
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_drain_lets_in_flight_transfer_complete() {
+        for (client, mut server) in [memory_pair().await, tcp_pair(true).await] {
+            assert_in_flight_transfer_drains(client, &mut server).await;
+        }
+    }
+    
+    async fn assert_in_flight_transfer_drains(client: Connection, server: &mut Connection) {
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let pooled = pool.insert(PeerId::new(), client);
+        
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let transfer = tokio::spawn({
+            let pooled = pooled.clone();
+            async move {
+                let mut connection = pooled.sender().await;
+                started_tx.send(()).unwrap();
+                // Still mid-transfer when shutdown begins
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                connection.send(&message(b"in flight")).await
+            }
+        });
+        started_rx.await.unwrap();
+        
+        assert_eq!(pool.drain(Duration::from_secs(5)).await, 0);
+        transfer.await.unwrap().unwrap();
+        assert!(pool.is_empty());
+        
+        // The peer gets the whole transfer, then the close reason
+        assert_eq!(server.receive().await.unwrap().payload, b"in flight");
+        let closed = server.receive().await.unwrap_err();
+        assert!(closed.to_string().contains("Shutdown"), "{}", closed);
+        assert!(server.is_closed().await);
+        
+        assert!(!pooled.is_healthy().await);
+        assert!(pooled.send(&message(b"too late")).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_drain_reaches_connections_outside_the_pool() {
+        let peer = PeerId::new();
+        let config = PoolConfig { max_per_peer: 1, ..Default::default() };
+        let pool = ConnectionPool::new(config);
+        
+        // Evicted by the per-peer limit but still in use
+        let (client, mut server) = tcp_pair(true).await;
+        let evicted = pool.insert(peer, client);
+        let (client, mut other_server) = tcp_pair(true).await;
+        let _pooled = pool.insert(peer, client);
+        assert_eq!(pool.peer_connections(&peer), 1);
+        
+        // Handed out while pooling is disabled
+        let unpooled_pool = ConnectionPool::new(PoolConfig { enabled: false, ..Default::default() });
+        let (client, mut unpooled_server) = tcp_pair(true).await;
+        let unpooled = unpooled_pool.insert(peer, client);
+        assert!(unpooled_pool.is_empty());
+        
+        assert_eq!(pool.drain(Duration::from_secs(5)).await, 0);
+        assert_eq!(unpooled_pool.drain(Duration::from_secs(5)).await, 0);
+        
+        for server in [&mut server, &mut other_server, &mut unpooled_server] {
+            let closed = server.receive().await.unwrap_err();
+            assert!(closed.to_string().contains("Shutdown"), "{}", closed);
+        }
+        assert!(!evicted.is_healthy().await);
+        assert!(!unpooled.is_healthy().await);
+    }
+    
+    #[tokio::test]
+    async fn test_drain_sends_no_close_frame_to_peers_without_support() {
+        // Neither side ran the handshake, as with peers predating close frames
+        let (client, mut server) = tcp_pair(false).await;
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let pooled = pool.insert(PeerId::new(), client);
+        pooled.send(&message(b"last")).await.unwrap();
+        
+        assert_eq!(pool.drain(Duration::from_secs(5)).await, 0);
+        
+        // The peer sees the message and then a plain end of stream
+        assert_eq!(server.receive().await.unwrap().payload, b"last");
+        let closed = server.receive().await.unwrap_err();
+        assert!(matches!(closed, crate::error::NetworkError::Io(_)), "{:?}", closed);
+    }
+    
+    #[tokio::test]
+    async fn test_drain_gives_up_after_deadline() {
+        let (client, _server) = memory_pair().await;
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let pooled = pool.insert(PeerId::new(), client);
+        
+        // A transfer that never finishes
+        let _busy = pooled.sender().await;
+        
+        let start = Instant::now();
+        assert_eq!(pool.drain(Duration::from_millis(50)).await, 1);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(pool.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_send_does_not_wait_for_pending_receive() {
+        let (client, mut server) = memory_pair().await;
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let pooled = pool.insert(PeerId::new(), client);
+        
+        // One user waits for a reply that has not been sent yet
+        let receive = tokio::spawn({
+            let pooled = pooled.clone();
+            async move { pooled.receive().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        
+        tokio::time::timeout(Duration::from_secs(1), pooled.send(&message(b"request")))
+            .await
+            .expect("send should not wait for the pending receive")
+            .unwrap();
+        
+        assert_eq!(server.receive().await.unwrap().payload, b"request");
+        server.send(&message(b"reply")).await.unwrap();
+        assert_eq!(receive.await.unwrap().unwrap().payload, b"reply");
+    }
+    
+    #[tokio::test]
+    async fn test_drain_ends_pending_receive() {
+        let (client, mut server) = tcp_pair(true).await;
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let pooled = pool.insert(PeerId::new(), client);
+        
+        let receive = tokio::spawn({
+            let pooled = pooled.clone();
+            async move { pooled.receive().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        
+        // The peer closes its end once it sees the close frame
+        let peer = tokio::spawn(async move {
+            let closed = server.receive().await.unwrap_err();
+            assert!(closed.to_string().contains("Shutdown"), "{}", closed);
+        });
+        
+        assert_eq!(pool.drain(Duration::from_secs(5)).await, 0);
+        peer.await.unwrap();
+        assert!(receive.await.unwrap().is_err());
+        assert!(!pooled.is_healthy().await);
+    }
+}
@@ -0,0 +1,202 @@
+//! Transfer progress and bandwidth estimation.
+//!
+//! Each connection meters the bytes flowing in either direction and keeps an
+//! exponentially weighted moving average of the throughput. The average is
+//! time-based: bytes are grouped into short sampling windows and each window
+//! moves the estimate by an amount that depends on how long it lasted, so a
+//! burst of writes does not spike the estimate and idle periods pull it back
+//! towards zero.
+
+use std::time::{Duration, Instant};
+
+/// Default time for a rate change to be half reflected in the estimate.
+pub const DEFAULT_HALF_LIFE: Duration = Duration::from_secs(2);
+
+/// Shortest window folded into the estimate.
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Smoothed throughput estimate for one direction of a connection.
+#[derive(Debug, Clone)]
+pub struct ThroughputEstimator {
+    /// Smoothing half-life
+    half_life: Duration,
+
+    /// Smoothed rate in bytes per second, once a window has completed
+    rate: Option<f64>,
+
+    /// Start of the current sampling window
+    window_start: Instant,
+
+    /// Bytes recorded in the current sampling window
+    window_bytes: u64,
+
+    /// Bytes recorded in total
+    total_bytes: u64,
+}
+
+impl ThroughputEstimator {
+    /// Creates an estimator with the given smoothing half-life.
+    pub fn new(half_life: Duration) -> Self {
+        Self {
+            half_life,
+            rate: None,
+            window_start: Instant::now(),
+            window_bytes: 0,
+            total_bytes: 0,
+        }
+    }
+
+    /// Records bytes transferred at `now`.
+    pub fn record(&mut self, bytes: u64, now: Instant) {
+        self.total_bytes += bytes;
+        self.window_bytes += bytes;
+
+        if now.saturating_duration_since(self.window_start) >= MIN_SAMPLE_INTERVAL {
+            self.rate = Some(self.rate_at(now));
+            self.window_start = now;
+            self.window_bytes = 0;
+        }
+    }
+
+    /// Returns the total bytes recorded.
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Returns the estimated throughput at `now` in bytes per second.
+    ///
+    /// The partially filled current window is folded in, so the estimate
+    /// decays while the connection is idle.
+    pub fn rate_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.window_start).as_secs_f64();
+        let window_rate = if elapsed > 0.0 {
+            self.window_bytes as f64 / elapsed
+        } else {
+            0.0
+        };
+
+        match self.rate {
+            None => window_rate,
+            Some(rate) => {
+                let weight = 1.0 - 0.5f64.powf(elapsed / self.half_life.as_secs_f64());
+                rate + weight * (window_rate - rate)
+            }
+        }
+    }
+}
+
+impl Default for ThroughputEstimator {
+    fn default() -> Self {
+        Self::new(DEFAULT_HALF_LIFE)
+    }
+}
+
+/// Snapshot of a connection's transfer progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferProgress {
+    /// Total bytes sent
+    pub bytes_sent: u64,
+
+    /// Total bytes received
+    pub bytes_received: u64,
+
+    /// Smoothed send throughput in bytes per second
+    pub send_rate: f64,
+
+    /// Smoothed receive throughput in bytes per second
+    pub receive_rate: f64,
+}
+
+impl TransferProgress {
+    /// Estimated time to send `remaining` more bytes at the current rate.
+    ///
+    /// Returns `None` while there is no throughput to extrapolate from.
+    pub fn send_eta(&self, remaining: u64) -> Option<Duration> {
+        eta(remaining, self.send_rate)
+    }
+
+    /// Estimated time to receive `remaining` more bytes at the current rate.
+    ///
+    /// Returns `None` while there is no throughput to extrapolate from.
+    pub fn receive_eta(&self, remaining: u64) -> Option<Duration> {
+        eta(remaining, self.receive_rate)
+    }
+}
+
+fn eta(remaining: u64, rate: f64) -> Option<Duration> {
+    if remaining == 0 {
+        return Some(Duration::ZERO);
+    }
+    (rate > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / rate))
+}
+
+/// Send and receive estimators for a connection.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProgressMeter {
+    pub(crate) sent: ThroughputEstimator,
+    pub(crate) received: ThroughputEstimator,
+}
+
+impl ProgressMeter {
+    /// Returns a snapshot of the progress at `now`.
+    pub(crate) fn snapshot(&self, now: Instant) -> TransferProgress {
+        TransferProgress {
+            bytes_sent: self.sent.total_bytes(),
+            bytes_received: self.received.total_bytes(),
+            send_rate: self.sent.rate_at(now),
+            receive_rate: self.received.rate_at(now),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steady_rate_is_tracked() {
+        let start = Instant::now();
+        let mut estimator = ThroughputEstimator::new(Duration::from_secs(1));
+
+        // 10 KB every 100 ms is 100 KB/s
+        for i in 1..=50 {
+            estimator.record(10_000, start + Duration::from_millis(100 * i));
+        }
+
+        let rate = estimator.rate_at(start + Duration::from_millis(5000));
+        assert!((rate - 100_000.0).abs() < 1_000.0, "rate {}", rate);
+        assert_eq!(estimator.total_bytes(), 500_000);
+    }
+
+    #[test]
+    fn test_bursts_are_smoothed() {
+        let start = Instant::now();
+        let mut estimator = ThroughputEstimator::new(Duration::from_secs(1));
+        for i in 1..=20 {
+            estimator.record(10_000, start + Duration::from_millis(100 * i));
+        }
+
+        // A single large burst moves the estimate only part of the way
+        estimator.record(1_000_000, start + Duration::from_millis(2100));
+        let rate = estimator.rate_at(start + Duration::from_millis(2100));
+        assert!(rate > 100_000.0 && rate < 1_000_000.0, "rate {}", rate);
+
+        // Idle time decays the estimate
+        let idle = estimator.rate_at(start + Duration::from_secs(10));
+        assert!(idle < rate / 100.0, "idle rate {}", idle);
+    }
+
+    #[test]
+    fn test_eta() {
+        let progress = TransferProgress {
+            bytes_sent: 1_000,
+            bytes_received: 0,
+            send_rate: 500.0,
+            receive_rate: 0.0,
+        };
+
+        assert_eq!(progress.send_eta(1_000), Some(Duration::from_secs(2)));
+        assert_eq!(progress.send_eta(0), Some(Duration::ZERO));
+        assert_eq!(progress.receive_eta(1_000), None);
+    }
+}
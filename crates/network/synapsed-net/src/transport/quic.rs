@@ -243,7 +243,7 @@ impl Transport for QuicTransport {
             metrics: Default::default(),
         };
         
-        let stream = QuicStream::new(send, recv);
+        let stream = QuicStream::new(connection, send, recv);
         
         Ok(Connection::new(
             conn_info,
@@ -282,13 +282,14 @@ impl Transport for QuicTransport {
 
 /// QUIC stream implementation.
 pub struct QuicStream {
+    connection: quinn::Connection,
     send: quinn::SendStream,
     recv: quinn::RecvStream,
 }
 
 impl QuicStream {
-    fn new(send: quinn::SendStream, recv: quinn::RecvStream) -> Self {
-        Self { send, recv }
+    pub(crate) fn new(connection: quinn::Connection, send: quinn::SendStream, recv: quinn::RecvStream) -> Self {
+        Self { connection, send, recv }
     }
 }
 
@@ -375,6 +376,7 @@ impl AsyncWrite for QuicStream {
     }
 }
 
+#[async_trait]
 impl Stream for QuicStream {
     fn info(&self) -> ConnectionInfo {
         ConnectionInfo {
@@ -391,6 +393,20 @@ impl Stream for QuicStream {
         // QUIC streams close automatically when dropped
         Ok(())
     }
+    
+    async fn close_with_reason(&mut self, code: u32, reason: &str) -> Result<()> {
+        // The stream may already have been finished while draining
+        let _ = self.send.finish();
+        
+        // Closing the connection discards anything still in flight, so wait
+        // until the peer has acknowledged all stream data or stopped the stream
+        if let Err(e) = self.send.stopped().await {
+            debug!("QUIC stream ended before the peer acknowledged it: {}", e);
+        }
+        
+        self.connection.close(code.into(), reason.as_bytes());
+        Ok(())
+    }
 }
 
 /// QUIC listener implementation.
@@ -424,7 +440,7 @@ impl QuicListener {
                                 metrics: Default::default(),
                             };
                             
-                            let stream = QuicStream::new(send, recv);
+                            let stream = QuicStream::new(connection, send, recv);
                             let conn = Connection::new(
                                 conn_info,
                                 Box::new(stream) as Box<dyn Stream>,
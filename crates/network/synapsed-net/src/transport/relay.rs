@@ -0,0 +1,848 @@
+//! Relay transport for peers that cannot reach each other directly.
+//!
+//! When hole punching fails, for example behind symmetric NATs, traffic is
+//! routed through a relay server that both peers can reach. A peer registers
+//! with the relay to accept connections; others ask the relay to splice them
+//! to it. The relay never takes part in the session: it copies opaque bytes
+//! between the two spliced sockets. The peers run an X25519 handshake across
+//! the spliced stream and encrypt every frame with ChaCha20-Poly1305.
+//!
+//! The initiator must know the listener's static X25519 key. It is mixed into
+//! the handshake so that only the genuine listener can derive the session
+//! keys, which keeps an active relay from terminating the handshake on each
+//! side and re-encrypting traffic in between.
+
+use crate::config::RelayConfig;
+use crate::error::{NetworkError, Result, SecurityError, TransportError};
+use crate::transport::traits::{Listener, Stream, Transport, TransportFeature, TransportPriority};
+use crate::transport::Connection;
+use crate::types::{ConnectionId, ConnectionInfo, PeerId, PeerInfo, PublicKey, TransportType};
+use async_trait::async_trait;
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, KeyInit};
+use dashmap::DashMap;
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey, StaticSecret};
+use zeroize::Zeroize;
+
+/// Maximum size of a relay control message
+const MAX_CONTROL_FRAME: usize = 64 * 1024;
+
+/// Maximum plaintext carried in one encrypted frame
+const MAX_PLAINTEXT_FRAME: usize = 16 * 1024;
+
+/// ChaCha20-Poly1305 authentication tag length
+const TAG_LEN: usize = 16;
+
+/// How long the relay waits for a listener to accept a session
+const ACCEPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HKDF info string binding derived keys to this protocol
+const HANDSHAKE_INFO: &[u8] = b"synapsed-net/relay/v1";
+
+/// Control messages between peers and the relay server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum RelayMessage {
+    /// Registers the sender to accept relayed connections
+    Listen { peer: PeerId },
+
+    /// Asks the relay to splice the sender to a listening peer
+    Connect { from: PeerId, target: PeerId },
+
+    /// Tells a listener that a peer wants to connect
+    Incoming { session: Uuid, from: PeerId },
+
+    /// Listener's data connection for an incoming session
+    Accept { session: Uuid },
+
+    /// Registration succeeded, or the session is spliced and everything
+    /// after this is end-to-end traffic
+    Ready,
+
+    /// The relay could not set up the session
+    Error { reason: String },
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(writer: &mut W, message: &RelayMessage) -> Result<()> {
+    let body = serde_json::to_vec(message)?;
+    writer.write_all(&(body.len() as u32).to_be_bytes()).await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> Result<RelayMessage> {
+    let mut len_buf = [0u8; 4];
+    reader.read_exact(&mut len_buf).await?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+
+    if len > MAX_CONTROL_FRAME {
+        return Err(NetworkError::Protocol(format!("Relay control frame too large: {} bytes", len)));
+    }
+
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Callback invoked with each chunk the relay forwards and the peer that sent it.
+pub type ForwardObserver = Arc<dyn Fn(PeerId, &[u8]) + Send + Sync>;
+
+/// Shared state of a running relay server.
+struct RelayState {
+    /// Control channels of peers accepting relayed connections
+    listeners: DashMap<PeerId, mpsc::Sender<RelayMessage>>,
+
+    /// Sessions waiting for the listener's data connection
+    pending: DashMap<Uuid, oneshot::Sender<TcpStream>>,
+
+    /// Optional observer of forwarded traffic
+    observer: Option<ForwardObserver>,
+}
+
+/// Relay server that splices connections between peers.
+///
+/// Any reachable peer can run one to act as a relay for others.
+pub struct RelayServer {
+    listener: TcpListener,
+    observer: Option<ForwardObserver>,
+}
+
+impl RelayServer {
+    /// Binds a relay server to the given address.
+    pub async fn bind(addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr).await
+            .map_err(|e| NetworkError::Transport(TransportError::Tcp(e.to_string())))?;
+
+        info!("Relay server listening on {}", addr);
+
+        Ok(Self {
+            listener,
+            observer: None,
+        })
+    }
+
+    /// Sets a callback invoked with every chunk the relay forwards.
+    ///
+    /// Useful for traffic accounting; the chunks are end-to-end ciphertext.
+    pub fn on_forward<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(PeerId, &[u8]) + Send + Sync + 'static,
+    {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Returns the address the relay is reachable at.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Runs the relay on a background task.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let state = Arc::new(RelayState {
+            listeners: DashMap::new(),
+            pending: DashMap::new(),
+            observer: self.observer,
+        });
+
+        loop {
+            let (stream, addr) = match self.listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Relay accept failed: {}", e);
+                    continue;
+                }
+            };
+
+            let state = state.clone();
+            tokio::spawn(async move {
+                if let Err(e) = Self::handle(state, stream).await {
+                    debug!("Relay session from {} ended: {}", addr, e);
+                }
+            });
+        }
+    }
+
+    async fn handle(state: Arc<RelayState>, mut stream: TcpStream) -> Result<()> {
+        match read_message(&mut stream).await? {
+            RelayMessage::Listen { peer } => Self::serve_listener(state, stream, peer).await,
+            RelayMessage::Connect { from, target } => Self::serve_connect(state, stream, from, target).await,
+            RelayMessage::Accept { session } => {
+                if let Some((_, waiting)) = state.pending.remove(&session) {
+                    let _ = waiting.send(stream);
+                }
+                Ok(())
+            }
+            other => Err(NetworkError::Protocol(format!("Unexpected relay message: {:?}", other))),
+        }
+    }
+
+    async fn serve_listener(state: Arc<RelayState>, stream: TcpStream, peer: PeerId) -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(64);
+        state.listeners.insert(peer, tx.clone());
+        debug!("Peer {} registered with relay", peer.anonymized());
+
+        let (mut reader, mut writer) = stream.into_split();
+        write_message(&mut writer, &RelayMessage::Ready).await?;
+        let mut probe = [0u8; 1];
+
+        // Forward session notices until the listener hangs up
+        let result = loop {
+            tokio::select! {
+                message = rx.recv() => match message {
+                    Some(message) => {
+                        if let Err(e) = write_message(&mut writer, &message).await {
+                            break Err(e);
+                        }
+                    }
+                    None => break Ok(()),
+                },
+                read = reader.read(&mut probe) => match read {
+                    Ok(0) => break Ok(()),
+                    Ok(_) => continue,
+                    Err(e) => break Err(e.into()),
+                },
+            }
+        };
+
+        // A newer registration for the same peer may have replaced ours
+        state.listeners.remove_if(&peer, |_, current| current.same_channel(&tx));
+        result
+    }
+
+    async fn serve_connect(state: Arc<RelayState>, mut stream: TcpStream, from: PeerId, target: PeerId) -> Result<()> {
+        let Some(control) = state.listeners.get(&target).map(|c| c.clone()) else {
+            let reason = format!("peer {} is not registered with this relay", target.anonymized());
+            return write_message(&mut stream, &RelayMessage::Error { reason }).await;
+        };
+
+        let session = Uuid::new_v4();
+        let (tx, rx) = oneshot::channel();
+        state.pending.insert(session, tx);
+
+        if control.send(RelayMessage::Incoming { session, from }).await.is_err() {
+            state.pending.remove(&session);
+            let reason = "listener went away".to_string();
+            return write_message(&mut stream, &RelayMessage::Error { reason }).await;
+        }
+
+        let mut other = match tokio::time::timeout(ACCEPT_TIMEOUT, rx).await {
+            Ok(Ok(other)) => other,
+            _ => {
+                state.pending.remove(&session);
+                let reason = "listener did not accept".to_string();
+                return write_message(&mut stream, &RelayMessage::Error { reason }).await;
+            }
+        };
+
+        write_message(&mut stream, &RelayMessage::Ready).await?;
+        write_message(&mut other, &RelayMessage::Ready).await?;
+
+        debug!("Relaying between {} and {}", from.anonymized(), target.anonymized());
+
+        let (a_read, a_write) = stream.into_split();
+        let (b_read, b_write) = other.into_split();
+        let observer = state.observer.clone();
+
+        let _ = tokio::join!(
+            Self::forward(a_read, b_write, from, observer.clone()),
+            Self::forward(b_read, a_write, target, observer),
+        );
+
+        Ok(())
+    }
+
+    async fn forward<R, W>(mut reader: R, mut writer: W, sender: PeerId, observer: Option<ForwardObserver>) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut buf = vec![0u8; MAX_CONTROL_FRAME];
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                writer.shutdown().await?;
+                return Ok(());
+            }
+
+            if let Some(observer) = &observer {
+                observer(sender, &buf[..n]);
+            }
+
+            writer.write_all(&buf[..n]).await?;
+        }
+    }
+}
+
+/// Transport that reaches peers through a relay server.
+pub struct RelayTransport {
+    config: RelayConfig,
+    local_peer: PeerId,
+    static_secret: StaticSecret,
+}
+
+impl RelayTransport {
+    /// Creates a relay transport with a fresh static key.
+    pub fn new(config: RelayConfig, local_peer: PeerId) -> Self {
+        Self {
+            config,
+            local_peer,
+            static_secret: StaticSecret::random_from_rng(rand::thread_rng()),
+        }
+    }
+
+    /// Returns the static key peers need to connect to this listener.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::X25519(X25519PublicKey::from(&self.static_secret).as_bytes().to_vec())
+    }
+
+    async fn dial_relay(&self) -> Result<TcpStream> {
+        tokio::time::timeout(self.config.connect_timeout, TcpStream::connect(self.config.address))
+            .await
+            .map_err(|_| NetworkError::Transport(TransportError::TimeoutWithMsg(
+                format!("relay {} unreachable", self.config.address)
+            )))?
+            .map_err(|e| NetworkError::Transport(TransportError::ConnectionFailed(e.to_string())))
+    }
+
+    fn connection(&self, remote_peer: PeerId, stream: TcpStream, keys: SessionCipher) -> Connection {
+        let info = ConnectionInfo {
+            local_peer: self.local_peer,
+            remote_peer,
+            id: ConnectionId::new(),
+            transport: TransportType::Relay,
+            established_at: SystemTime::now(),
+            metrics: Default::default(),
+        };
+
+        let stream = RelayStream::new(stream, keys, info.clone());
+        Connection::new(info, Box::new(stream))
+    }
+}
+
+#[async_trait]
+impl Transport for RelayTransport {
+    async fn connect(&self, peer: &PeerInfo) -> Result<Connection> {
+        info!("Connecting to peer {} via relay {}", peer.id, self.config.address);
+
+        // Without the listener's key, the relay could answer the handshake itself
+        let listener_key = match &peer.public_key {
+            Some(PublicKey::X25519(bytes)) => <[u8; 32]>::try_from(bytes.as_slice())
+                .map(X25519PublicKey::from)
+                .map_err(|_| NetworkError::Security(SecurityError::KeyExchange(
+                    "X25519 public key must be 32 bytes".to_string()
+                )))?,
+            _ => {
+                return Err(NetworkError::Security(SecurityError::KeyExchange(format!(
+                    "relaying to {} requires its X25519 public key",
+                    peer.id.anonymized()
+                ))));
+            }
+        };
+
+        let mut stream = self.dial_relay().await?;
+        write_message(&mut stream, &RelayMessage::Connect {
+            from: self.local_peer,
+            target: peer.id,
+        }).await?;
+
+        match read_message(&mut stream).await? {
+            RelayMessage::Ready => {}
+            RelayMessage::Error { reason } => {
+                return Err(NetworkError::Transport(TransportError::ConnectionFailed(reason)));
+            }
+            other => {
+                return Err(NetworkError::Protocol(format!("Unexpected relay message: {:?}", other)));
+            }
+        }
+
+        let keys = initiator_handshake(&mut stream, &listener_key).await?;
+        Ok(self.connection(peer.id, stream, keys))
+    }
+
+    async fn listen(&self, _addr: SocketAddr) -> Result<Box<dyn Listener>> {
+        let mut control = self.dial_relay().await?;
+        write_message(&mut control, &RelayMessage::Listen { peer: self.local_peer }).await?;
+        
+        match read_message(&mut control).await? {
+            RelayMessage::Ready => {}
+            other => {
+                return Err(NetworkError::Protocol(format!("Unexpected relay message: {:?}", other)));
+            }
+        }
+
+        info!("Accepting relayed connections via {}", self.config.address);
+
+        Ok(Box::new(RelayListener {
+            transport: RelayTransport {
+                config: self.config.clone(),
+                local_peer: self.local_peer,
+                static_secret: self.static_secret.clone(),
+            },
+            control,
+        }))
+    }
+
+    fn priority(&self) -> TransportPriority {
+        TransportPriority::Fallback
+    }
+
+    fn transport_type(&self) -> TransportType {
+        TransportType::Relay
+    }
+
+    fn supports_feature(&self, feature: TransportFeature) -> bool {
+        match feature {
+            TransportFeature::ZeroRTT => false,
+            TransportFeature::Multistream => false,
+            TransportFeature::UnreliableChannel => false,
+            TransportFeature::ConnectionMigration => false,
+            TransportFeature::BandwidthEstimation => false,
+            TransportFeature::NATTraversal => true,
+            TransportFeature::Anonymity => false,
+            TransportFeature::PostQuantum => false,
+        }
+    }
+}
+
+/// Accepts connections relayed to this peer.
+pub struct RelayListener {
+    transport: RelayTransport,
+    control: TcpStream,
+}
+
+#[async_trait]
+impl Listener for RelayListener {
+    async fn accept(&mut self) -> Result<(Connection, SocketAddr)> {
+        let (session, from) = match read_message(&mut self.control).await? {
+            RelayMessage::Incoming { session, from } => (session, from),
+            other => {
+                return Err(NetworkError::Protocol(format!("Unexpected relay message: {:?}", other)));
+            }
+        };
+
+        let mut stream = self.transport.dial_relay().await?;
+        write_message(&mut stream, &RelayMessage::Accept { session }).await?;
+
+        match read_message(&mut stream).await? {
+            RelayMessage::Ready => {}
+            other => {
+                return Err(NetworkError::Protocol(format!("Unexpected relay message: {:?}", other)));
+            }
+        }
+
+        let keys = responder_handshake(&mut stream, &self.transport.static_secret).await?;
+        let conn = self.transport.connection(from, stream, keys);
+
+        Ok((conn, self.transport.config.address))
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.transport.config.address)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.control.shutdown().await?;
+        Ok(())
+    }
+}
+
+/// Directional AEAD keys for one relayed session.
+struct SessionCipher {
+    send_key: [u8; 32],
+    recv_key: [u8; 32],
+}
+
+impl Drop for SessionCipher {
+    fn drop(&mut self) {
+        self.send_key.zeroize();
+        self.recv_key.zeroize();
+    }
+}
+
+/// Runs the initiator side of the handshake.
+///
+/// Sends an ephemeral key and mixes both the ephemeral-ephemeral and the
+/// ephemeral-static DH with the listener's key into the session keys.
+async fn initiator_handshake(stream: &mut TcpStream, listener_key: &X25519PublicKey) -> Result<SessionCipher> {
+    // Generated per handshake; `StaticSecret` only because it allows two DH operations
+    let ephemeral = StaticSecret::random_from_rng(rand::thread_rng());
+    let ephemeral_public = X25519PublicKey::from(&ephemeral);
+    stream.write_all(ephemeral_public.as_bytes()).await?;
+
+    let mut reply = [0u8; 32];
+    stream.read_exact(&mut reply).await?;
+    let responder_public = X25519PublicKey::from(reply);
+
+    let mut ikm = ephemeral.diffie_hellman(&responder_public).as_bytes().to_vec();
+    ikm.extend_from_slice(ephemeral.diffie_hellman(listener_key).as_bytes());
+
+    let (initiator_to_responder, responder_to_initiator) =
+        derive_keys(&mut ikm, ephemeral_public.as_bytes(), responder_public.as_bytes())?;
+
+    Ok(SessionCipher {
+        send_key: initiator_to_responder,
+        recv_key: responder_to_initiator,
+    })
+}
+
+/// Runs the listener side of the handshake.
+async fn responder_handshake(stream: &mut TcpStream, static_secret: &StaticSecret) -> Result<SessionCipher> {
+    let mut hello = [0u8; 32];
+    stream.read_exact(&mut hello).await?;
+    let initiator_public = X25519PublicKey::from(hello);
+
+    let ephemeral = EphemeralSecret::random_from_rng(rand::thread_rng());
+    let ephemeral_public = X25519PublicKey::from(&ephemeral);
+    stream.write_all(ephemeral_public.as_bytes()).await?;
+
+    let mut ikm = ephemeral.diffie_hellman(&initiator_public).as_bytes().to_vec();
+    ikm.extend_from_slice(static_secret.diffie_hellman(&initiator_public).as_bytes());
+
+    let (initiator_to_responder, responder_to_initiator) =
+        derive_keys(&mut ikm, initiator_public.as_bytes(), ephemeral_public.as_bytes())?;
+
+    Ok(SessionCipher {
+        send_key: responder_to_initiator,
+        recv_key: initiator_to_responder,
+    })
+}
+
+/// Derives both directional keys from the handshake secret, then wipes it.
+fn derive_keys(ikm: &mut Vec<u8>, initiator: &[u8; 32], responder: &[u8; 32]) -> Result<([u8; 32], [u8; 32])> {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(initiator);
+    salt[32..].copy_from_slice(responder);
+
+    let mut okm = [0u8; 64];
+    let result = Hkdf::<sha2::Sha256>::new(Some(&salt), ikm)
+        .expand(HANDSHAKE_INFO, &mut okm)
+        .map_err(|_| NetworkError::Security(SecurityError::KeyDerivation(
+            "HKDF-SHA256 expansion failed".to_string()
+        )));
+    ikm.zeroize();
+    result?;
+
+    let mut initiator_to_responder = [0u8; 32];
+    let mut responder_to_initiator = [0u8; 32];
+    initiator_to_responder.copy_from_slice(&okm[..32]);
+    responder_to_initiator.copy_from_slice(&okm[32..]);
+    okm.zeroize();
+
+    Ok((initiator_to_responder, responder_to_initiator))
+}
+
+/// Builds the nonce for a frame from its per-direction sequence number.
+fn frame_nonce(sequence: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&sequence.to_be_bytes());
+    nonce
+}
+
+/// End-to-end encrypted stream over a relayed connection.
+///
+/// Writes are sealed into frames and written straight to the relay socket,
+/// so backpressure from the relay reaches the writer.
+pub struct RelayStream {
+    read_rx: mpsc::Receiver<Vec<u8>>,
+    read_buffer: Vec<u8>,
+    tcp_write: OwnedWriteHalf,
+    seal: ChaCha20Poly1305,
+    send_sequence: u64,
+    /// Sealed frame bytes not yet written to the relay
+    write_buffer: Vec<u8>,
+    info: ConnectionInfo,
+    reader: JoinHandle<()>,
+}
+
+impl RelayStream {
+    fn new(stream: TcpStream, keys: SessionCipher, info: ConnectionInfo) -> Self {
+        let (read_tx, read_rx) = mpsc::channel::<Vec<u8>>(1024);
+        let (mut tcp_read, tcp_write) = stream.into_split();
+
+        let open = ChaCha20Poly1305::new(&keys.recv_key.into());
+        let seal = ChaCha20Poly1305::new(&keys.send_key.into());
+        drop(keys);
+
+        let reader = tokio::spawn(async move {
+            let mut sequence = 0u64;
+            loop {
+                let mut len_buf = [0u8; 4];
+                if tcp_read.read_exact(&mut len_buf).await.is_err() {
+                    break;
+                }
+                let len = u32::from_be_bytes(len_buf) as usize;
+                if len > MAX_PLAINTEXT_FRAME + TAG_LEN {
+                    warn!("Relayed frame exceeds maximum size, closing");
+                    break;
+                }
+
+                let mut frame = vec![0u8; len];
+                if tcp_read.read_exact(&mut frame).await.is_err() {
+                    break;
+                }
+
+                // A frame that fails authentication was tampered with in transit
+                let Ok(plaintext) = open.decrypt(&frame_nonce(sequence).into(), frame.as_slice()) else {
+                    warn!("Relayed frame failed authentication, closing");
+                    break;
+                };
+                sequence += 1;
+
+                if read_tx.send(plaintext).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            read_rx,
+            read_buffer: Vec::new(),
+            tcp_write,
+            seal,
+            send_sequence: 0,
+            write_buffer: Vec::new(),
+            info,
+            reader,
+        }
+    }
+
+    /// Writes buffered frame bytes to the relay until none are left.
+    fn poll_write_buffer(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while !self.write_buffer.is_empty() {
+            let written = ready!(Pin::new(&mut self.tcp_write).poll_write(cx, &self.write_buffer))?;
+            if written == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+            }
+            self.write_buffer.drain(..written);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Stream for RelayStream {
+    fn info(&self) -> ConnectionInfo {
+        self.info.clone()
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.read_rx.close();
+        self.reader.abort();
+        Ok(())
+    }
+}
+
+impl Drop for RelayStream {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+impl AsyncRead for RelayStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let me = self.get_mut();
+
+        if me.read_buffer.is_empty() {
+            match me.read_rx.poll_recv(cx) {
+                Poll::Ready(Some(data)) => me.read_buffer = data,
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let len = std::cmp::min(buf.remaining(), me.read_buffer.len());
+        buf.put_slice(&me.read_buffer[..len]);
+        me.read_buffer.drain(..len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for RelayStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let me = self.get_mut();
+
+        // Only one frame is buffered; the previous one must reach the relay first
+        ready!(me.poll_write_buffer(cx))?;
+
+        let len = std::cmp::min(buf.len(), MAX_PLAINTEXT_FRAME);
+        let ciphertext = me.seal
+            .encrypt(&frame_nonce(me.send_sequence).into(), &buf[..len])
+            .map_err(|_| std::io::Error::other("Relayed frame encryption failed"))?;
+        me.send_sequence += 1;
+
+        me.write_buffer.extend_from_slice(&(ciphertext.len() as u32).to_be_bytes());
+        me.write_buffer.extend_from_slice(&ciphertext);
+
+        // The frame is accepted; whatever the socket doesn't take now goes
+        // out on the next write or flush
+        if let Poll::Ready(Err(e)) = me.poll_write_buffer(cx) {
+            return Poll::Ready(Err(e));
+        }
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let me = self.get_mut();
+        ready!(me.poll_write_buffer(cx))?;
+        Pin::new(&mut me.tcp_write).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let me = self.get_mut();
+        ready!(me.poll_write_buffer(cx))?;
+        Pin::new(&mut me.tcp_write).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportManager;
+
+    const SECRET: &[u8] = b"meet at the usual place at noon";
+
+    #[tokio::test]
+    async fn test_relay_fallback_carries_only_ciphertext() {
+        let observed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let observed_clone = observed.clone();
+
+        let relay = RelayServer::bind("127.0.0.1:0".parse().unwrap()).await.unwrap()
+            .on_forward(move |_, chunk| observed_clone.lock().unwrap().extend_from_slice(chunk));
+        let config = RelayConfig::new(relay.local_addr().unwrap());
+        let _relay_task = relay.spawn();
+
+        // Peer B accepts through the relay
+        let peer_b = PeerId::new();
+        let transport_b = RelayTransport::new(config.clone(), peer_b);
+        let mut listener = transport_b.listen(config.address).await.unwrap();
+        let accept_task = tokio::spawn(async move {
+            TransportManager::new(TransportType::Relay).accept(listener.as_mut()).await
+        });
+
+        // Peer A has no direct transports, so only the relay fallback can succeed
+        let mut manager = TransportManager::new(TransportType::Quic);
+        manager.set_local_peer(PeerId::new());
+        manager.set_relay_fallback(Arc::new(RelayTransport::new(config, manager.local_peer())));
+
+        let mut target = PeerInfo::new(peer_b);
+        target.public_key = Some(transport_b.public_key());
+
+        let mut conn_a = manager.connect(&target).await.unwrap();
+        let (mut conn_b, _) = accept_task.await.unwrap().unwrap();
+
+        assert_eq!(conn_a.info().transport, TransportType::Relay);
+        assert_eq!(conn_b.info().remote_peer, manager.local_peer());
+
+        conn_a.write_all(SECRET).await.unwrap();
+        let mut received = vec![0u8; SECRET.len()];
+        conn_b.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, SECRET);
+
+        conn_b.write_all(b"ack").await.unwrap();
+        let mut received = [0u8; 3];
+        conn_a.read_exact(&mut received).await.unwrap();
+        assert_eq!(&received, b"ack");
+
+        let observed = observed.lock().unwrap();
+        assert!(!observed.is_empty());
+        assert!(!observed.windows(SECRET.len()).any(|w| w == SECRET));
+    }
+
+    #[tokio::test]
+    async fn test_large_relayed_write_arrives_intact() {
+        let relay = RelayServer::bind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let config = RelayConfig::new(relay.local_addr().unwrap());
+        let _relay_task = relay.spawn();
+
+        let transport_b = RelayTransport::new(config.clone(), PeerId::new());
+        let mut listener = transport_b.listen(config.address).await.unwrap();
+        let accept_task = tokio::spawn(async move { listener.accept().await });
+
+        let transport_a = RelayTransport::new(config, PeerId::new());
+        let mut target = PeerInfo::new(transport_b.local_peer);
+        target.public_key = Some(transport_b.public_key());
+        let mut conn_a = transport_a.connect(&target).await.unwrap();
+        let (mut conn_b, _) = accept_task.await.unwrap().unwrap();
+
+        // Spans many frames and more than the socket buffers hold
+        let payload: Vec<u8> = (0..4 * 1024 * 1024).map(|i| i as u8).collect();
+        let writer = tokio::spawn(async move {
+            conn_a.write_all(&payload).await.unwrap();
+            conn_a.flush().await.unwrap();
+            payload
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut received = vec![0u8; 4 * 1024 * 1024];
+        tokio::time::timeout(Duration::from_secs(10), conn_b.read_exact(&mut received))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let payload = tokio::time::timeout(Duration::from_secs(10), writer).await.unwrap().unwrap();
+        assert!(received == payload);
+    }
+
+    #[tokio::test]
+    async fn test_relay_connect_requires_listener_key() {
+        let relay = RelayServer::bind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let config = RelayConfig::new(relay.local_addr().unwrap());
+        let _relay_task = relay.spawn();
+
+        let transport_b = RelayTransport::new(config.clone(), PeerId::new());
+        let _listener = transport_b.listen(config.address).await.unwrap();
+
+        // The relay could complete an unauthenticated handshake itself
+        let transport_a = RelayTransport::new(config, PeerId::new());
+        let result = transport_a.connect(&PeerInfo::new(transport_b.local_peer)).await;
+
+        assert!(matches!(
+            result,
+            Err(NetworkError::Security(SecurityError::KeyExchange(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_relay_rejects_unregistered_peer() {
+        let relay = RelayServer::bind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let config = RelayConfig::new(relay.local_addr().unwrap());
+        let _relay_task = relay.spawn();
+
+        let transport = RelayTransport::new(config.clone(), PeerId::new());
+        // Has a key, but never registered with the relay
+        let unregistered = RelayTransport::new(config, PeerId::new());
+        let mut target = PeerInfo::new(unregistered.local_peer);
+        target.public_key = Some(unregistered.public_key());
+        let result = transport.connect(&target).await;
+
+        assert!(matches!(
+            result,
+            Err(NetworkError::Transport(TransportError::ConnectionFailed(_)))
+        ));
+    }
+}
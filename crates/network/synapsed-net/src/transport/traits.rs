@@ -40,12 +40,23 @@ pub trait Listener: Send + Sync {
 }
 
 /// Stream trait for bidirectional communication.
+#[async_trait]
 pub trait Stream: AsyncRead + AsyncWrite + Send + Sync + Unpin {
     /// Returns information about this stream.
     fn info(&self) -> ConnectionInfo;
     
     /// Closes the stream gracefully.
     fn close(&mut self) -> Result<()>;
+    
+    /// Closes the stream gracefully, telling the peer why.
+    ///
+    /// Transports with a native close signal, such as QUIC's
+    /// CONNECTION_CLOSE frame, wait for the peer to acknowledge the data
+    /// already sent and then pass the code and reason on to it. The default
+    /// just closes the stream.
+    async fn close_with_reason(&mut self, _code: u32, _reason: &str) -> Result<()> {
+        self.close()
+    }
 }
 
 /// Transport features that can be queried.
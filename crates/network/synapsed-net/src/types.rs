@@ -268,6 +268,9 @@ pub enum TransportType {
     
     /// Memory transport (for testing)
     Memory,
+    
+    /// Relayed through an intermediary peer
+    Relay,
 }
 
 impl fmt::Display for TransportType {
@@ -279,6 +282,7 @@ impl fmt::Display for TransportType {
             TransportType::Udp => write!(f, "UDP"),
             TransportType::WebSocket => write!(f, "WebSocket"),
             TransportType::Memory => write!(f, "Memory"),
+            TransportType::Relay => write!(f, "Relay"),
         }
     }
 }
@@ -1,5 +1,6 @@
 //! Router configuration
 
+use crate::layers::OnionSuite;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +9,9 @@ pub struct RouterConfig {
     pub circuit_lifetime: u64,
     pub mix_delay_ms: u64,
     pub use_cover_traffic: bool,
+    /// Key exchange, KDF and AEAD for onion layers
+    #[serde(default)]
+    pub onion_suite: OnionSuite,
 }
 
 impl RouterConfig {
@@ -24,6 +28,11 @@ impl RouterConfig {
         self.circuit_lifetime = lifetime;
         self
     }
+    
+    pub fn with_onion_suite(mut self, suite: OnionSuite) -> Self {
+        self.onion_suite = suite;
+        self
+    }
 }
 
 impl Default for RouterConfig {
@@ -33,6 +42,7 @@ impl Default for RouterConfig {
             circuit_lifetime: 600,
             mix_delay_ms: 100,
             use_cover_traffic: true,
+            onion_suite: OnionSuite::default(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,313 @@
+//! Directory service for relay discovery
+//!
+//! Relays publish [`NodeDescriptor`]s signed with their identity key. Each
+//! trusted directory authority signs an [`AuthorityVote`] listing the
+//! descriptors it accepts and the flags it assigns them. The
+//! [`DirectoryService`] merges the votes into a [`Consensus`]: a relay is
+//! listed when a majority of authorities vote for it, and carries the flags a
+//! majority of those authorities assigned. Routers build circuits only from
+//! relays in the current consensus.
+
+use crate::{NodeId, Result, RoutingError};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Weak};
+use tokio::sync::RwLock;
+
+/// Role and health flags assigned to relays by authorities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RelayFlag {
+    /// Suitable as the first hop of a circuit
+    Guard,
+    /// Allows traffic to leave the network
+    Exit,
+    /// Has enough bandwidth
+    Fast,
+    /// Has been up for a long time
+    Stable,
+    /// Currently reachable
+    Running,
+    /// Descriptor was checked by the authority
+    Valid,
+}
+
+/// A relay's self-published description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeDescriptor {
+    pub node_id: NodeId,
+    pub address: String,
+    /// Ed25519 key the descriptor is signed with
+    pub identity_key: [u8; 32],
+    /// X25519 key used for circuit handshakes
+    pub onion_key: [u8; 32],
+    pub published_at: DateTime<Utc>,
+}
+
+impl NodeDescriptor {
+    /// Sign the descriptor with the relay's identity key
+    pub fn sign(self, identity: &SigningKey) -> Result<SignedDescriptor> {
+        if identity.verifying_key().to_bytes() != self.identity_key {
+            return Err(RoutingError::Directory(format!(
+                "descriptor for {} names a different identity key",
+                self.node_id.0
+            )));
+        }
+        let signature = identity.sign(&canonical_bytes(&self)?).to_bytes().to_vec();
+        Ok(SignedDescriptor {
+            descriptor: self,
+            signature,
+        })
+    }
+}
+
+/// A descriptor with its relay's signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedDescriptor {
+    pub descriptor: NodeDescriptor,
+    pub signature: Vec<u8>,
+}
+
+impl SignedDescriptor {
+    /// Check the signature against the identity key in the descriptor
+    pub fn verify(&self) -> Result<()> {
+        let key = VerifyingKey::from_bytes(&self.descriptor.identity_key)
+            .map_err(|e| RoutingError::Directory(format!("invalid identity key: {}", e)))?;
+        verify_signature(&key, &canonical_bytes(&self.descriptor)?, &self.signature).map_err(|_| {
+            RoutingError::Directory(format!(
+                "bad descriptor signature for {}",
+                self.descriptor.node_id.0
+            ))
+        })
+    }
+}
+
+/// A relay as listed by one authority
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteEntry {
+    pub descriptor: SignedDescriptor,
+    pub flags: BTreeSet<RelayFlag>,
+}
+
+/// One authority's view of the network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorityVote {
+    pub authority: NodeId,
+    pub published_at: DateTime<Utc>,
+    pub entries: Vec<VoteEntry>,
+    pub signature: Vec<u8>,
+}
+
+impl AuthorityVote {
+    /// Create a vote signed with the authority's key
+    pub fn new(authority: NodeId, entries: Vec<VoteEntry>, key: &SigningKey) -> Result<Self> {
+        let mut vote = Self {
+            authority,
+            published_at: Utc::now(),
+            entries,
+            signature: Vec::new(),
+        };
+        vote.signature = key.sign(&vote.signed_bytes()?).to_bytes().to_vec();
+        Ok(vote)
+    }
+
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        canonical_bytes(&(&self.authority, &self.published_at, &self.entries))
+    }
+}
+
+/// A relay listed in the consensus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusRelay {
+    pub node_id: NodeId,
+    pub address: String,
+    pub identity_key: [u8; 32],
+    pub onion_key: [u8; 32],
+    pub flags: BTreeSet<RelayFlag>,
+}
+
+impl ConsensusRelay {
+    /// Whether the authorities agree the relay is running and valid
+    pub fn is_usable(&self) -> bool {
+        self.flags.contains(&RelayFlag::Running) && self.flags.contains(&RelayFlag::Valid)
+    }
+}
+
+/// The relays a majority of authorities agree on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Consensus {
+    pub valid_after: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    /// Authorities whose votes were counted
+    pub authorities: Vec<NodeId>,
+    pub relays: Vec<ConsensusRelay>,
+}
+
+impl Consensus {
+    /// Whether the consensus is still current
+    pub fn is_valid(&self) -> bool {
+        let now = Utc::now();
+        self.valid_after <= now && now < self.valid_until
+    }
+
+    /// Look up a listed relay
+    pub fn relay(&self, node_id: &NodeId) -> Option<&ConsensusRelay> {
+        self.relays.iter().find(|relay| &relay.node_id == node_id)
+    }
+
+    /// Usable relays carrying `flag`
+    pub fn relays_with(&self, flag: RelayFlag) -> impl Iterator<Item = &ConsensusRelay> {
+        self.relays
+            .iter()
+            .filter(move |relay| relay.is_usable() && relay.flags.contains(&flag))
+    }
+}
+
+/// Aggregates authority votes into a periodically refreshed consensus
+pub struct DirectoryService {
+    authorities: HashMap<NodeId, VerifyingKey>,
+    votes: RwLock<HashMap<NodeId, AuthorityVote>>,
+    consensus: RwLock<Option<Consensus>>,
+    validity: Duration,
+}
+
+impl DirectoryService {
+    /// Create a service trusting the given authority keys
+    ///
+    /// Each consensus stays valid for `validity_secs`.
+    pub fn new(authorities: Vec<(NodeId, VerifyingKey)>, validity_secs: u64) -> Self {
+        Self {
+            authorities: authorities.into_iter().collect(),
+            votes: RwLock::new(HashMap::new()),
+            consensus: RwLock::new(None),
+            validity: Duration::seconds(validity_secs as i64),
+        }
+    }
+
+    /// Accept a vote from a trusted authority, replacing its previous vote
+    ///
+    /// The vote is rejected if it or any descriptor in it fails verification.
+    pub async fn submit_vote(&self, vote: AuthorityVote) -> Result<()> {
+        let key = self.authorities.get(&vote.authority).ok_or_else(|| {
+            RoutingError::Directory(format!("{} is not a trusted authority", vote.authority.0))
+        })?;
+        verify_signature(key, &vote.signed_bytes()?, &vote.signature).map_err(|_| {
+            RoutingError::Directory(format!("bad vote signature from {}", vote.authority.0))
+        })?;
+        for entry in &vote.entries {
+            entry.descriptor.verify()?;
+        }
+
+        self.votes.write().await.insert(vote.authority.clone(), vote);
+        Ok(())
+    }
+
+    /// Merge the current votes into a new consensus
+    ///
+    /// Requires votes from a majority of the trusted authorities.
+    pub async fn refresh(&self) -> Result<Consensus> {
+        let votes = self.votes.read().await;
+        let majority = self.authorities.len() / 2 + 1;
+        if votes.len() < majority {
+            return Err(RoutingError::Directory(format!(
+                "{} of {} authorities voted, {} needed",
+                votes.len(),
+                self.authorities.len(),
+                majority
+            )));
+        }
+
+        // Per relay: the newest descriptor and every authority's flags
+        let mut listings: HashMap<&NodeId, (&NodeDescriptor, Vec<&BTreeSet<RelayFlag>>)> = HashMap::new();
+        for vote in votes.values() {
+            for entry in &vote.entries {
+                let descriptor = &entry.descriptor.descriptor;
+                let listing = listings
+                    .entry(&descriptor.node_id)
+                    .or_insert_with(|| (descriptor, Vec::new()));
+                if descriptor.published_at > listing.0.published_at {
+                    listing.0 = descriptor;
+                }
+                listing.1.push(&entry.flags);
+            }
+        }
+
+        let mut relays: Vec<ConsensusRelay> = listings
+            .into_values()
+            .filter(|(_, flag_sets)| flag_sets.len() >= majority)
+            .map(|(descriptor, flag_sets)| {
+                // A flag needs a majority of the authorities listing the relay
+                let needed = flag_sets.len() / 2 + 1;
+                let mut counts: HashMap<RelayFlag, usize> = HashMap::new();
+                for flag in flag_sets.iter().flat_map(|flags| flags.iter()) {
+                    *counts.entry(*flag).or_default() += 1;
+                }
+                ConsensusRelay {
+                    node_id: descriptor.node_id.clone(),
+                    address: descriptor.address.clone(),
+                    identity_key: descriptor.identity_key,
+                    onion_key: descriptor.onion_key,
+                    flags: counts
+                        .into_iter()
+                        .filter(|(_, count)| *count >= needed)
+                        .map(|(flag, _)| flag)
+                        .collect(),
+                }
+            })
+            .collect();
+        relays.sort_by(|a, b| a.node_id.0.cmp(&b.node_id.0));
+
+        let mut authorities: Vec<NodeId> = votes.keys().cloned().collect();
+        authorities.sort_by(|a, b| a.0.cmp(&b.0));
+        let now = Utc::now();
+        let consensus = Consensus {
+            valid_after: now,
+            valid_until: now + self.validity,
+            authorities,
+            relays,
+        };
+        drop(votes);
+
+        *self.consensus.write().await = Some(consensus.clone());
+        Ok(consensus)
+    }
+
+    /// The current consensus, if one is still valid
+    pub async fn consensus(&self) -> Option<Consensus> {
+        self.consensus
+            .read()
+            .await
+            .as_ref()
+            .filter(|consensus| consensus.is_valid())
+            .cloned()
+    }
+
+    /// Refresh the consensus every `interval_secs`
+    ///
+    /// The task stops once the service is dropped.
+    pub fn start_refresh(self: &Arc<Self>, interval_secs: u64) -> tokio::task::JoinHandle<()> {
+        let service: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs.max(1)));
+            loop {
+                ticker.tick().await;
+                let Some(service) = service.upgrade() else {
+                    break;
+                };
+                if let Err(e) = service.refresh().await {
+                    tracing::warn!("Consensus refresh failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+fn canonical_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(value).map_err(|e| RoutingError::Directory(format!("failed to encode: {}", e)))
+}
+
+fn verify_signature(key: &VerifyingKey, message: &[u8], signature: &[u8]) -> std::result::Result<(), ()> {
+    let signature: [u8; 64] = signature.try_into().map_err(|_| ())?;
+    key.verify(message, &Signature::from_bytes(&signature)).map_err(|_| ())
+}
@@ -21,6 +21,12 @@ pub enum RoutingError {
     
     #[error("Timeout waiting for response")]
     Timeout,
+    
+    #[error("Invalid cell: {0}")]
+    InvalidCell(String),
+    
+    #[error("Directory error: {0}")]
+    Directory(String),
 }
 
 pub type Result<T> = std::result::Result<T, RoutingError>;
\ No newline at end of file
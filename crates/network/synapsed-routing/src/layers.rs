@@ -0,0 +1,312 @@
+//! Layered onion encryption with configurable ciphersuites
+//!
+//! A client runs an independent key exchange with every hop of a circuit and
+//! derives a per-hop AEAD key from it. Payloads are sealed once per hop,
+//! innermost for the last hop, so each relay can remove exactly one layer.
+//! The [`OnionSuite`] selects the key exchange, KDF and AEAD; the hybrid key
+//! exchange combines X25519 with Kyber512 from synapsed-crypto so a hop key
+//! stays secret unless both are broken.
+
+use crate::{Result, RoutingError};
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::aead::{Aead as _, KeyInit};
+use chacha20poly1305::ChaCha20Poly1305;
+use rand::RngCore;
+use ring::hkdf;
+use serde::{Deserialize, Serialize};
+use synapsed_crypto::api::{decapsulate, encapsulate, generate_keypair};
+use synapsed_crypto::prelude::KemAlgorithm;
+use synapsed_crypto::random::DefaultRng;
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+/// Domain separation for derived hop keys
+const KDF_INFO: &[u8] = b"synapsed-routing onion hop key v1";
+
+const NONCE_SIZE: usize = 12;
+
+/// How a client and a hop agree on a shared secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyExchange {
+    /// Ephemeral-static X25519
+    X25519,
+    /// X25519 combined with a Kyber512 encapsulation
+    X25519Kyber512,
+}
+
+/// How hop keys are derived from the shared secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Kdf {
+    HkdfSha256,
+    HkdfSha512,
+}
+
+/// Cipher protecting each layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AeadCipher {
+    ChaCha20Poly1305,
+    Aes256Gcm,
+}
+
+/// Algorithms used for a circuit's onion layers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OnionSuite {
+    pub key_exchange: KeyExchange,
+    pub kdf: Kdf,
+    pub aead: AeadCipher,
+}
+
+impl OnionSuite {
+    /// X25519, HKDF-SHA256 and ChaCha20-Poly1305
+    pub fn classical() -> Self {
+        Self {
+            key_exchange: KeyExchange::X25519,
+            kdf: Kdf::HkdfSha256,
+            aead: AeadCipher::ChaCha20Poly1305,
+        }
+    }
+
+    /// X25519 with Kyber512, HKDF-SHA512 and ChaCha20-Poly1305
+    pub fn pq_hybrid() -> Self {
+        Self {
+            key_exchange: KeyExchange::X25519Kyber512,
+            kdf: Kdf::HkdfSha512,
+            aead: AeadCipher::ChaCha20Poly1305,
+        }
+    }
+}
+
+impl Default for OnionSuite {
+    fn default() -> Self {
+        Self::classical()
+    }
+}
+
+/// A relay's published onion keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayPublicKeys {
+    pub x25519: [u8; 32],
+    /// Present when the relay supports the hybrid key exchange
+    pub kyber: Option<Vec<u8>>,
+}
+
+/// A relay's long-term onion keys
+pub struct RelayKeys {
+    x25519: StaticSecret,
+    kyber: Option<(Vec<u8>, Zeroizing<Vec<u8>>)>,
+}
+
+impl std::fmt::Debug for RelayKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelayKeys")
+            .field("x25519", &PublicKey::from(&self.x25519))
+            .field("kyber", &self.kyber.is_some())
+            .finish()
+    }
+}
+
+impl RelayKeys {
+    /// Generate keys supporting `key_exchange`
+    pub fn generate(key_exchange: KeyExchange) -> Result<Self> {
+        let kyber = match key_exchange {
+            KeyExchange::X25519 => None,
+            KeyExchange::X25519Kyber512 => {
+                let (public, secret) = generate_keypair(KemAlgorithm::Kyber512, &mut DefaultRng::default())
+                    .map_err(|e| RoutingError::EncryptionError(format!("Kyber key generation failed: {}", e)))?;
+                Some((public, Zeroizing::new(secret)))
+            }
+        };
+        Ok(Self {
+            x25519: StaticSecret::random_from_rng(rand::thread_rng()),
+            kyber,
+        })
+    }
+
+    /// The keys clients use to establish a layer with this relay
+    pub fn public(&self) -> RelayPublicKeys {
+        RelayPublicKeys {
+            x25519: PublicKey::from(&self.x25519).to_bytes(),
+            kyber: self.kyber.as_ref().map(|(public, _)| public.clone()),
+        }
+    }
+
+    /// Complete a client's handshake, deriving this relay's layer of the circuit
+    pub fn accept(&self, suite: &OnionSuite, handshake: &Handshake) -> Result<HopLayer> {
+        let ephemeral = PublicKey::from(handshake.ephemeral);
+        let mut secret = Zeroizing::new(self.x25519.diffie_hellman(&ephemeral).as_bytes().to_vec());
+
+        match (suite.key_exchange, &handshake.kyber_ciphertext) {
+            (KeyExchange::X25519, None) => {}
+            (KeyExchange::X25519Kyber512, Some(ciphertext)) => {
+                let (_, kyber_secret) = self.kyber.as_ref().ok_or_else(|| {
+                    RoutingError::EncryptionError("Relay has no Kyber key".to_string())
+                })?;
+                let shared = decapsulate(KemAlgorithm::Kyber512, kyber_secret, ciphertext)
+                    .map_err(|e| RoutingError::EncryptionError(format!("Kyber decapsulation failed: {}", e)))?;
+                secret.extend_from_slice(&shared);
+            }
+            _ => {
+                return Err(RoutingError::EncryptionError(
+                    "Handshake does not match the onion suite".to_string(),
+                ))
+            }
+        }
+
+        HopLayer::derive(suite, &secret, &handshake.ephemeral, &self.public().x25519)
+    }
+}
+
+/// A client's key exchange message for one hop
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handshake {
+    /// Client's ephemeral X25519 public key, fresh for every hop
+    pub ephemeral: [u8; 32],
+    pub kyber_ciphertext: Option<Vec<u8>>,
+}
+
+impl Handshake {
+    /// Start a key exchange with a hop, returning the message to send and the client's layer
+    pub fn initiate(suite: &OnionSuite, hop: &RelayPublicKeys) -> Result<(Self, HopLayer)> {
+        let ephemeral = StaticSecret::random_from_rng(rand::thread_rng());
+        let ephemeral_public = PublicKey::from(&ephemeral).to_bytes();
+        let mut secret = Zeroizing::new(
+            ephemeral
+                .diffie_hellman(&PublicKey::from(hop.x25519))
+                .as_bytes()
+                .to_vec(),
+        );
+
+        let kyber_ciphertext = match suite.key_exchange {
+            KeyExchange::X25519 => None,
+            KeyExchange::X25519Kyber512 => {
+                let public = hop.kyber.as_ref().ok_or_else(|| {
+                    RoutingError::EncryptionError("Hop does not publish a Kyber key".to_string())
+                })?;
+                let (ciphertext, shared) = encapsulate(KemAlgorithm::Kyber512, public, &mut DefaultRng::default())
+                    .map_err(|e| RoutingError::EncryptionError(format!("Kyber encapsulation failed: {}", e)))?;
+                secret.extend_from_slice(&shared);
+                Some(ciphertext)
+            }
+        };
+
+        let layer = HopLayer::derive(suite, &secret, &ephemeral_public, &hop.x25519)?;
+        Ok((
+            Self {
+                ephemeral: ephemeral_public,
+                kyber_ciphertext,
+            },
+            layer,
+        ))
+    }
+}
+
+/// Output length requested from ring's HKDF
+struct KeyLength(usize);
+
+impl hkdf::KeyType for KeyLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// One hop's layer of encryption
+pub struct HopLayer {
+    aead: AeadCipher,
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl std::fmt::Debug for HopLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HopLayer").field("aead", &self.aead).finish()
+    }
+}
+
+impl HopLayer {
+    fn derive(suite: &OnionSuite, secret: &[u8], ephemeral: &[u8; 32], relay: &[u8; 32]) -> Result<Self> {
+        let algorithm = match suite.kdf {
+            Kdf::HkdfSha256 => hkdf::HKDF_SHA256,
+            Kdf::HkdfSha512 => hkdf::HKDF_SHA512,
+        };
+        // Bind the key to this handshake's public values
+        let salt = [ephemeral.as_slice(), relay.as_slice()].concat();
+        let mut key = Zeroizing::new([0u8; 32]);
+        hkdf::Salt::new(algorithm, &salt)
+            .extract(secret)
+            .expand(&[KDF_INFO], KeyLength(32))
+            .and_then(|okm| okm.fill(key.as_mut()))
+            .map_err(|_| RoutingError::EncryptionError("Hop key derivation failed".to_string()))?;
+        Ok(Self { aead: suite.aead, key })
+    }
+
+    /// Add this layer, prefixing a random nonce
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let sealed = match self.aead {
+            AeadCipher::ChaCha20Poly1305 => ChaCha20Poly1305::new(self.key.as_ref().into())
+                .encrypt(&nonce.into(), plaintext),
+            AeadCipher::Aes256Gcm => Aes256Gcm::new(self.key.as_ref().into())
+                .encrypt(&nonce.into(), plaintext),
+        }
+        .map_err(|_| RoutingError::EncryptionError("Layer encryption failed".to_string()))?;
+        Ok([nonce.as_slice(), &sealed].concat())
+    }
+
+    /// Remove this layer
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        if sealed.len() < NONCE_SIZE {
+            return Err(RoutingError::EncryptionError("Layer too short".to_string()));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+        match self.aead {
+            AeadCipher::ChaCha20Poly1305 => ChaCha20Poly1305::new(self.key.as_ref().into())
+                .decrypt(nonce.into(), ciphertext),
+            AeadCipher::Aes256Gcm => Aes256Gcm::new(self.key.as_ref().into())
+                .decrypt(nonce.into(), ciphertext),
+        }
+        .map_err(|_| RoutingError::EncryptionError("Layer authentication failed".to_string()))
+    }
+}
+
+/// A client's layers for every hop of a circuit
+#[derive(Debug)]
+pub struct LayeredCircuit {
+    suite: OnionSuite,
+    layers: Vec<HopLayer>,
+}
+
+impl LayeredCircuit {
+    /// Run a separate key exchange with each hop, returning the handshakes to deliver in hop order
+    pub fn establish(suite: OnionSuite, hops: &[RelayPublicKeys]) -> Result<(Self, Vec<Handshake>)> {
+        let (handshakes, layers) = hops
+            .iter()
+            .map(|hop| Handshake::initiate(&suite, hop))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        Ok((Self { suite, layers }, handshakes))
+    }
+
+    /// The suite the layers were established with
+    pub fn suite(&self) -> &OnionSuite {
+        &self.suite
+    }
+
+    /// Number of hops
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether the circuit has no hops
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Encrypt a payload for the circuit, the first hop's layer outermost
+    pub fn wrap(&self, payload: &[u8]) -> Result<Vec<u8>> {
+        self.layers
+            .iter()
+            .rev()
+            .try_fold(payload.to_vec(), |onion, layer| layer.seal(&onion))
+    }
+}
@@ -19,7 +19,7 @@
 //! ## Example
 //!
 //! ```rust,no_run
-//! use synapsed_routing::{OnionRouter, RouterConfig, NodeId};
+//! use synapsed_routing::{OnionRouter, RelayPublicKeys, RouterConfig, NodeId};
 //! use tokio;
 //!
 //! #[tokio::main]
@@ -30,8 +30,12 @@
 //!     
 //!     let mut router = OnionRouter::new(config).await?;
 //!     
-//!     // Create an anonymous circuit
+//!     // Create an anonymous circuit and run a key exchange with each hop
 //!     let circuit = router.create_circuit().await?;
+//!     let relay_keys: Vec<RelayPublicKeys> = todo!("fetch the hops' published onion keys");
+//!     let handshakes = router.establish_layers(&circuit, &relay_keys).await?;
+//!     // Deliver each handshake to its hop, in circuit order
+//!     # drop(handshakes);
 //!     
 //!     // Send anonymous message
 //!     router.send_anonymous(&circuit, b"Hello, anonymous world!").await?;
@@ -43,6 +47,9 @@
 pub mod config;
 pub mod error;
 pub mod types;
+pub mod directory;
+pub mod rendezvous;
+pub mod layers;
 
 // Simplified for now - we'll implement the actual routing later
 pub mod onion;
@@ -51,4 +58,13 @@ pub mod onion;
 pub use config::RouterConfig;
 pub use error::{RoutingError, Result};
 pub use types::{NodeId, Circuit, MessagePayload};
-pub use onion::OnionRouter;
\ No newline at end of file
+pub use directory::{
+    AuthorityVote, Consensus, ConsensusRelay, DirectoryService, NodeDescriptor, RelayFlag,
+    SignedDescriptor, VoteEntry,
+};
+pub use layers::{
+    AeadCipher, Handshake, HopLayer, Kdf, KeyExchange, LayeredCircuit, OnionSuite, RelayKeys,
+    RelayPublicKeys,
+};
+pub use onion::{Cell, CellReassembler, OnionRouter, CELL_SIZE};
+pub use rendezvous::{RendezvousAddress, RendezvousHandle, RendezvousPoint};
\ No newline at end of file
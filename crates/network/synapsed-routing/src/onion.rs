@@ -1,15 +1,213 @@
 //! Onion routing implementation
 
+use crate::directory::{Consensus, DirectoryService, RelayFlag};
+use crate::layers::{Handshake, LayeredCircuit, RelayPublicKeys};
+use crate::rendezvous::{RendezvousAddress, RendezvousHandle, RendezvousPoint, COOKIE_SIZE};
 use crate::{RouterConfig, RoutingError, Result, NodeId, Circuit, MessagePayload};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Size of every cell sent through a circuit, as in Tor
+pub const CELL_SIZE: usize = 512;
+
+/// Message id, fragment index, flags and payload length
+const CELL_HEADER_SIZE: usize = 4 + 2 + 1 + 2;
+
+/// Payload bytes carried by a single cell
+pub const CELL_PAYLOAD_SIZE: usize = CELL_SIZE - CELL_HEADER_SIZE;
+
+/// Set on the final fragment of a message
+const FLAG_LAST: u8 = 0x01;
+
+/// Messages that can be partially received at once
+const MAX_PENDING_MESSAGES: usize = 256;
+
+/// Fixed-size unit of data sent through a circuit
+///
+/// Messages are split across as many cells as needed and the unused part of
+/// the last one is filled with random padding, so cells reveal nothing about
+/// the size of the message they carry.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Cell {
+    bytes: Box<[u8; CELL_SIZE]>,
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cell")
+            .field("message_id", &self.message_id())
+            .field("index", &self.index())
+            .field("len", &self.payload().len())
+            .field("last", &self.is_last())
+            .finish()
+    }
+}
+
+impl Cell {
+    fn new(message_id: u32, index: u16, last: bool, payload: &[u8]) -> Self {
+        let mut bytes = Box::new([0u8; CELL_SIZE]);
+        bytes[0..4].copy_from_slice(&message_id.to_be_bytes());
+        bytes[4..6].copy_from_slice(&index.to_be_bytes());
+        bytes[6] = if last { FLAG_LAST } else { 0 };
+        bytes[7..9].copy_from_slice(&(payload.len() as u16).to_be_bytes());
+        bytes[CELL_HEADER_SIZE..CELL_HEADER_SIZE + payload.len()].copy_from_slice(payload);
+        rand::thread_rng().fill_bytes(&mut bytes[CELL_HEADER_SIZE + payload.len()..]);
+        Self { bytes }
+    }
+    
+    /// Parse a cell received from the network
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; CELL_SIZE] = bytes.try_into().map_err(|_| {
+            RoutingError::InvalidCell(format!("expected {} bytes, got {}", CELL_SIZE, bytes.len()))
+        })?;
+        let cell = Self { bytes: Box::new(bytes) };
+        if cell.payload_len() > CELL_PAYLOAD_SIZE {
+            return Err(RoutingError::InvalidCell(format!(
+                "payload length {} exceeds cell capacity",
+                cell.payload_len()
+            )));
+        }
+        Ok(cell)
+    }
+    
+    /// The cell as sent on the wire
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..]
+    }
+    
+    /// Id of the message this cell is a fragment of
+    pub fn message_id(&self) -> u32 {
+        u32::from_be_bytes([self.bytes[0], self.bytes[1], self.bytes[2], self.bytes[3]])
+    }
+    
+    /// Position of this fragment within its message
+    pub fn index(&self) -> u16 {
+        u16::from_be_bytes([self.bytes[4], self.bytes[5]])
+    }
+    
+    /// Whether this is the final fragment of its message
+    pub fn is_last(&self) -> bool {
+        self.bytes[6] & FLAG_LAST != 0
+    }
+    
+    fn payload_len(&self) -> usize {
+        u16::from_be_bytes([self.bytes[7], self.bytes[8]]) as usize
+    }
+    
+    /// The fragment carried by this cell, without padding
+    pub fn payload(&self) -> &[u8] {
+        &self.bytes[CELL_HEADER_SIZE..CELL_HEADER_SIZE + self.payload_len()]
+    }
+}
+
+/// Split a message into padded cells
+pub fn fragment(message_id: u32, payload: &MessagePayload) -> Result<Vec<Cell>> {
+    let data = serde_json::to_vec(payload)
+        .map_err(|e| RoutingError::InvalidCell(format!("failed to encode payload: {}", e)))?;
+    
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![&[]]
+    } else {
+        data.chunks(CELL_PAYLOAD_SIZE).collect()
+    };
+    if chunks.len() > u16::MAX as usize + 1 {
+        return Err(RoutingError::InvalidCell(format!(
+            "message of {} bytes needs more than {} cells",
+            data.len(),
+            u16::MAX as usize + 1
+        )));
+    }
+    
+    let last = chunks.len() - 1;
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| Cell::new(message_id, index as u16, index == last, chunk))
+        .collect())
+}
+
+/// Fragments received so far for one message
+#[derive(Debug, Default)]
+struct PartialMessage {
+    fragments: BTreeMap<u16, Vec<u8>>,
+    /// Known once the last fragment arrives
+    count: Option<usize>,
+}
+
+/// Reassembles messages from cells at the exit of a circuit
+#[derive(Debug, Default)]
+pub struct CellReassembler {
+    pending: HashMap<u32, PartialMessage>,
+}
+
+impl CellReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Accept a cell, returning the message once all its fragments have arrived
+    ///
+    /// Fragments of a message may arrive in any order.
+    pub fn push(&mut self, cell: &Cell) -> Result<Option<MessagePayload>> {
+        let message_id = cell.message_id();
+        if !self.pending.contains_key(&message_id) && self.pending.len() >= MAX_PENDING_MESSAGES {
+            return Err(RoutingError::InvalidCell(format!(
+                "too many partially received messages ({})",
+                MAX_PENDING_MESSAGES
+            )));
+        }
+        
+        let partial = self.pending.entry(message_id).or_default();
+        if partial.fragments.insert(cell.index(), cell.payload().to_vec()).is_some() {
+            return Err(RoutingError::InvalidCell(format!(
+                "duplicate fragment {} of message {}",
+                cell.index(),
+                message_id
+            )));
+        }
+        if cell.is_last() {
+            partial.count = Some(cell.index() as usize + 1);
+        }
+        
+        match partial.count {
+            Some(count) if partial.fragments.len() == count => {
+                let partial = self.pending.remove(&message_id).unwrap_or_default();
+                let data: Vec<u8> = partial.fragments.into_values().flatten().collect();
+                let payload = serde_json::from_slice(&data)
+                    .map_err(|e| RoutingError::InvalidCell(format!("failed to decode payload: {}", e)))?;
+                Ok(Some(payload))
+            }
+            Some(count) if partial.fragments.len() > count => {
+                self.pending.remove(&message_id);
+                Err(RoutingError::InvalidCell(format!(
+                    "fragment beyond the last one of message {}",
+                    message_id
+                )))
+            }
+            _ => Ok(None),
+        }
+    }
+    
+    /// Number of messages still missing fragments
+    pub fn pending_messages(&self) -> usize {
+        self.pending.len()
+    }
+}
 
 /// Onion router for anonymous communication
 pub struct OnionRouter {
     config: RouterConfig,
     circuits: Arc<RwLock<HashMap<String, Circuit>>>,
+    /// Onion layers established for each circuit, by circuit id
+    layers: Arc<RwLock<HashMap<String, LayeredCircuit>>>,
     nodes: Arc<RwLock<Vec<NodeId>>>,
+    next_message_id: AtomicU32,
+    directory: Option<Arc<DirectoryService>>,
+    rendezvous_points: Arc<RwLock<HashMap<NodeId, Arc<RendezvousPoint>>>>,
 }
 
 impl OnionRouter {
@@ -18,12 +216,34 @@ impl OnionRouter {
         Ok(Self {
             config,
             circuits: Arc::new(RwLock::new(HashMap::new())),
+            layers: Arc::new(RwLock::new(HashMap::new())),
             nodes: Arc::new(RwLock::new(Vec::new())),
+            next_message_id: AtomicU32::new(0),
+            directory: None,
+            rendezvous_points: Arc::new(RwLock::new(HashMap::new())),
         })
     }
     
+    /// Select circuit hops from the directory's consensus instead of known nodes
+    pub fn with_directory(mut self, directory: Arc<DirectoryService>) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+    
     /// Create a new circuit
     pub async fn create_circuit(&self) -> Result<Circuit> {
+        if let Some(directory) = &self.directory {
+            let consensus = directory.consensus().await.ok_or_else(|| {
+                RoutingError::CircuitCreation("No valid directory consensus".to_string())
+            })?;
+            let circuit = Circuit::new(
+                select_hops(&consensus, self.config.hop_count)?,
+                self.config.circuit_lifetime,
+            );
+            self.circuits.write().await.insert(circuit.id.clone(), circuit.clone());
+            return Ok(circuit);
+        }
+        
         let nodes = self.nodes.read().await;
         
         if nodes.len() < self.config.hop_count {
@@ -55,18 +275,59 @@ impl OnionRouter {
         Ok(circuit)
     }
     
+    /// Split a message into the cells that carry it through a circuit
+    pub fn frame_message(&self, payload: &MessagePayload) -> Result<Vec<Cell>> {
+        let message_id = self.next_message_id.fetch_add(1, Ordering::Relaxed);
+        fragment(message_id, payload)
+    }
+    
+    /// Establish onion layers with each hop of a circuit using the configured suite
+    ///
+    /// `hops` are the relays' public keys in circuit order. Returns the
+    /// handshakes to deliver to the hops, in the same order.
+    pub async fn establish_layers(&self, circuit: &Circuit, hops: &[RelayPublicKeys]) -> Result<Vec<Handshake>> {
+        if hops.len() != circuit.nodes.len() {
+            return Err(RoutingError::CircuitCreation(format!(
+                "circuit has {} hops but {} relay keys were given",
+                circuit.nodes.len(),
+                hops.len()
+            )));
+        }
+        let (layers, handshakes) = LayeredCircuit::establish(self.config.onion_suite, hops)?;
+        self.layers.write().await.insert(circuit.id.clone(), layers);
+        Ok(handshakes)
+    }
+    
+    /// Frame a message into cells and wrap each in the circuit's onion layers
+    ///
+    /// The first hop's layer is outermost. Fails if no layers have been
+    /// established for the circuit.
+    pub async fn seal_message(&self, circuit: &Circuit, data: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let payload = MessagePayload {
+            data: data.to_vec(),
+            destination: circuit.nodes.last().cloned(),
+            reply_to: None,
+            rendezvous: None,
+        };
+        let cells = self.frame_message(&payload)?;
+        
+        let layers = self.layers.read().await;
+        let layers = layers.get(&circuit.id).ok_or_else(|| {
+            RoutingError::EncryptionError(format!("No onion layers established for circuit {}", circuit.id))
+        })?;
+        cells.iter().map(|cell| layers.wrap(cell.as_bytes())).collect()
+    }
+    
     /// Send anonymous message through circuit
     pub async fn send_anonymous(&self, circuit: &Circuit, data: &[u8]) -> Result<()> {
         if circuit.is_expired() {
             return Err(RoutingError::CircuitCreation("Circuit expired".to_string()));
         }
         
-        // In a real implementation, this would:
-        // 1. Apply layers of encryption (one per hop)
-        // 2. Send through the circuit nodes
-        // 3. Handle relay responses
+        let onions = self.seal_message(circuit, data).await?;
+        tracing::debug!("Sending {} cells through circuit {}", onions.len(), circuit.id);
         
-        // For now, just simulate success
+        // Delivery to the first hop is simulated until relays have a transport
         if self.config.mix_delay_ms > 0 {
             tokio::time::sleep(tokio::time::Duration::from_millis(self.config.mix_delay_ms)).await;
         }
@@ -79,6 +340,66 @@ impl OnionRouter {
         self.nodes.write().await.push(node);
     }
     
+    /// Add a relay that can act as a rendezvous point
+    pub async fn add_rendezvous_point(&self, point: Arc<RendezvousPoint>) {
+        self.rendezvous_points
+            .write()
+            .await
+            .insert(point.node_id().clone(), point);
+    }
+    
+    /// Open a circuit to a random rendezvous point for receiving anonymous replies
+    pub async fn create_rendezvous(&self) -> Result<RendezvousHandle> {
+        let point = {
+            let points = self.rendezvous_points.read().await;
+            let points: Vec<_> = points.values().cloned().collect();
+            points
+                .choose(&mut rand::thread_rng())
+                .cloned()
+                .ok_or(RoutingError::NoAvailableNodes)?
+        };
+        let circuit = self.create_circuit_to(point.node_id()).await?;
+        
+        let mut cookie = [0u8; COOKIE_SIZE];
+        rand::thread_rng().fill_bytes(&mut cookie);
+        RendezvousHandle::new(circuit, point, cookie)
+    }
+    
+    /// Reply to a request through the rendezvous point named in its address
+    pub async fn reply(&self, address: &RendezvousAddress, data: &[u8]) -> Result<()> {
+        let point = self
+            .rendezvous_points
+            .read()
+            .await
+            .get(&address.point)
+            .cloned()
+            .ok_or_else(|| {
+                RoutingError::CircuitCreation(format!("Unknown rendezvous point {}", address.point.0))
+            })?;
+        let circuit = self.create_circuit_to(point.node_id()).await?;
+        
+        // The reply carries no information about the replying side
+        let payload = MessagePayload {
+            data: data.to_vec(),
+            destination: None,
+            reply_to: None,
+            rendezvous: None,
+        };
+        let cells = self.frame_message(&payload)?;
+        tracing::debug!("Replying with {} cells through circuit {}", cells.len(), circuit.id);
+        point.relay(&address.cookie, cells)
+    }
+    
+    /// Build a circuit whose last hop is `last_hop`
+    async fn create_circuit_to(&self, last_hop: &NodeId) -> Result<Circuit> {
+        let mut circuit = self.create_circuit().await?;
+        circuit.nodes.pop();
+        circuit.nodes.retain(|node| node != last_hop);
+        circuit.nodes.push(last_hop.clone());
+        self.circuits.write().await.insert(circuit.id.clone(), circuit.clone());
+        Ok(circuit)
+    }
+    
     /// Get active circuits
     pub async fn get_circuits(&self) -> Vec<Circuit> {
         let circuits = self.circuits.read().await;
@@ -89,5 +410,31 @@ impl OnionRouter {
     pub async fn cleanup_expired(&self) {
         let mut circuits = self.circuits.write().await;
         circuits.retain(|_, circuit| !circuit.is_expired());
+        self.layers.write().await.retain(|id, _| circuits.contains_key(id));
+    }
+}
+
+/// Pick distinct usable relays from a consensus, ending with an exit
+fn select_hops(consensus: &Consensus, hop_count: usize) -> Result<Vec<NodeId>> {
+    let mut rng = rand::thread_rng();
+    let exits: Vec<_> = consensus.relays_with(RelayFlag::Exit).collect();
+    let exit = exits.choose(&mut rng).ok_or(RoutingError::NoAvailableNodes)?;
+    
+    let mut others: Vec<_> = consensus
+        .relays
+        .iter()
+        .filter(|relay| relay.is_usable() && relay.node_id != exit.node_id)
+        .collect();
+    if others.len() + 1 < hop_count {
+        return Err(RoutingError::NoAvailableNodes);
     }
-}
\ No newline at end of file
+    others.shuffle(&mut rng);
+    
+    let mut hops: Vec<NodeId> = others
+        .into_iter()
+        .take(hop_count.saturating_sub(1))
+        .map(|relay| relay.node_id.clone())
+        .collect();
+    hops.push(exit.node_id.clone());
+    Ok(hops)
+}
@@ -0,0 +1,147 @@
+//! Rendezvous points for anonymous replies
+//!
+//! A sender that wants a reply builds a circuit ending at a rendezvous relay
+//! and registers a random cookie there. It sends the recipient only the
+//! relay's id and the cookie. The recipient builds its own circuit to the same
+//! relay and presents the cookie, and the relay splices the reply onto the
+//! sender's circuit. Neither side learns anything about the other's circuit
+//! beyond the rendezvous relay they both chose to trust.
+
+use crate::onion::{Cell, CellReassembler};
+use crate::{Circuit, MessagePayload, NodeId, Result, RoutingError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Length of a rendezvous cookie in bytes
+pub const COOKIE_SIZE: usize = 20;
+
+/// Where to send replies: a rendezvous relay and the cookie it knows the sender by
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RendezvousAddress {
+    pub point: NodeId,
+    pub cookie: [u8; COOKIE_SIZE],
+}
+
+/// Relay that joins reply circuits to waiting circuits by cookie
+#[derive(Debug)]
+pub struct RendezvousPoint {
+    node_id: NodeId,
+    waiting: Mutex<HashMap<[u8; COOKIE_SIZE], mpsc::UnboundedSender<Cell>>>,
+}
+
+impl RendezvousPoint {
+    pub fn new(node_id: NodeId) -> Self {
+        Self {
+            node_id,
+            waiting: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The relay's node id
+    pub fn node_id(&self) -> &NodeId {
+        &self.node_id
+    }
+
+    /// Hold a circuit open under `cookie`, returning the cells relayed to it
+    fn register(&self, cookie: [u8; COOKIE_SIZE]) -> Result<mpsc::UnboundedReceiver<Cell>> {
+        let mut waiting = self.waiting.lock();
+        if waiting.contains_key(&cookie) {
+            return Err(RoutingError::CircuitCreation("Rendezvous cookie already in use".to_string()));
+        }
+        let (sender, receiver) = mpsc::unbounded_channel();
+        waiting.insert(cookie, sender);
+        Ok(receiver)
+    }
+
+    fn unregister(&self, cookie: &[u8; COOKIE_SIZE]) {
+        self.waiting.lock().remove(cookie);
+    }
+
+    /// Forward cells arriving with `cookie` onto the waiting circuit
+    pub fn relay(&self, cookie: &[u8; COOKIE_SIZE], cells: Vec<Cell>) -> Result<()> {
+        let waiting = self.waiting.lock();
+        let sender = waiting.get(cookie).ok_or_else(|| {
+            RoutingError::NetworkError("No circuit waiting at rendezvous for this cookie".to_string())
+        })?;
+        for cell in cells {
+            sender
+                .send(cell)
+                .map_err(|_| RoutingError::NetworkError("Rendezvous circuit closed".to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Number of circuits waiting for replies
+    pub fn waiting_circuits(&self) -> usize {
+        self.waiting.lock().len()
+    }
+}
+
+/// A sender's end of a rendezvous, receiving the replies sent to its address
+///
+/// Dropping the handle releases the cookie at the rendezvous point.
+#[derive(Debug)]
+pub struct RendezvousHandle {
+    circuit: Circuit,
+    point: Arc<RendezvousPoint>,
+    cookie: [u8; COOKIE_SIZE],
+    cells: mpsc::UnboundedReceiver<Cell>,
+    reassembler: CellReassembler,
+}
+
+impl RendezvousHandle {
+    pub(crate) fn new(circuit: Circuit, point: Arc<RendezvousPoint>, cookie: [u8; COOKIE_SIZE]) -> Result<Self> {
+        let cells = point.register(cookie)?;
+        Ok(Self {
+            circuit,
+            point,
+            cookie,
+            cells,
+            reassembler: CellReassembler::new(),
+        })
+    }
+
+    /// The address to give recipients so they can reply
+    pub fn address(&self) -> RendezvousAddress {
+        RendezvousAddress {
+            point: self.point.node_id().clone(),
+            cookie: self.cookie,
+        }
+    }
+
+    /// The circuit from the sender to the rendezvous point
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Build a request that can be replied to through this rendezvous
+    pub fn request(&self, data: Vec<u8>, destination: Option<NodeId>) -> MessagePayload {
+        MessagePayload {
+            data,
+            destination,
+            reply_to: None,
+            rendezvous: Some(self.address()),
+        }
+    }
+
+    /// Wait for the next reply
+    ///
+    /// Returns `None` once the rendezvous point has dropped the circuit.
+    pub async fn recv(&mut self) -> Result<Option<MessagePayload>> {
+        while let Some(cell) = self.cells.recv().await {
+            if let Some(payload) = self.reassembler.push(&cell)? {
+                return Ok(Some(payload));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for RendezvousHandle {
+    fn drop(&mut self) {
+        self.point.unregister(&self.cookie);
+    }
+}
@@ -48,4 +48,7 @@ pub struct MessagePayload {
     pub data: Vec<u8>,
     pub destination: Option<NodeId>,
     pub reply_to: Option<NodeId>,
+    /// Where the recipient can reply without learning the sender's location
+    #[serde(default)]
+    pub rendezvous: Option<crate::rendezvous::RendezvousAddress>,
 }
\ No newline at end of file
@@ -14,4 +14,303 @@ async fn test_routing_stub() {
         format!("synapsed-routing test");
     });
     assert!(result.is_ok());
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_cells_have_uniform_size_and_reassemble() {
+    let router = OnionRouter::new(RouterConfig::new()).await.unwrap();
+    let payloads: Vec<MessagePayload> = [0usize, 1, 200, 503, 504, 5000]
+        .iter()
+        .map(|&len| MessagePayload {
+            data: (0..len).map(|i| (i % 251) as u8).collect(),
+            destination: Some(NodeId::from_string("exit".to_string())),
+            reply_to: None,
+            rendezvous: None,
+        })
+        .collect();
+    
+    let framed: Vec<Vec<Cell>> = payloads
+        .iter()
+        .map(|payload| router.frame_message(payload).unwrap())
+        .collect();
+    
+    // Every cell on the wire is the same size, whatever the payload size
+    assert!(framed.iter().flatten().all(|cell| cell.as_bytes().len() == CELL_SIZE));
+    assert!(framed.last().unwrap().len() > 1);
+    
+    // Interleave the cells of all messages, as a circuit multiplexes them
+    let mut reassembler = CellReassembler::new();
+    let mut received = Vec::new();
+    let longest = framed.iter().map(Vec::len).max().unwrap();
+    for index in 0..longest {
+        for cells in &framed {
+            if let Some(cell) = cells.get(index) {
+                let wire = Cell::from_bytes(cell.as_bytes()).unwrap();
+                if let Some(payload) = reassembler.push(&wire).unwrap() {
+                    received.push(payload);
+                }
+            }
+        }
+    }
+    
+    assert_eq!(reassembler.pending_messages(), 0);
+    assert_eq!(received.len(), payloads.len());
+    for payload in &payloads {
+        let matching = received.iter().find(|r| r.data == payload.data).unwrap();
+        assert_eq!(matching.destination, payload.destination);
+    }
+}
+
+#[tokio::test]
+async fn test_malformed_cells_rejected() {
+    assert!(Cell::from_bytes(&[0u8; CELL_SIZE - 1]).is_err());
+    
+    // Payload length larger than a cell can carry
+    let mut bytes = [0u8; CELL_SIZE];
+    bytes[7..9].copy_from_slice(&(CELL_SIZE as u16).to_be_bytes());
+    assert!(Cell::from_bytes(&bytes).is_err());
+}
+
+fn relay(name: &str) -> (ed25519_dalek::SigningKey, SignedDescriptor) {
+    let key = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+    let descriptor = NodeDescriptor {
+        node_id: NodeId::from_string(name.to_string()),
+        address: format!("{}.relay:9001", name),
+        identity_key: key.verifying_key().to_bytes(),
+        onion_key: rand::random(),
+        published_at: chrono::Utc::now(),
+    };
+    let signed = descriptor.sign(&key).unwrap();
+    (key, signed)
+}
+
+fn entry(descriptor: &SignedDescriptor, flags: &[RelayFlag]) -> VoteEntry {
+    VoteEntry {
+        descriptor: descriptor.clone(),
+        flags: flags.iter().copied().collect(),
+    }
+}
+
+#[tokio::test]
+async fn test_consensus_merges_authority_votes_and_drives_hop_selection() {
+    use RelayFlag::*;
+    
+    let relays: Vec<SignedDescriptor> = (0..6).map(|i| relay(&format!("r{}", i)).1).collect();
+    let authority_keys: Vec<ed25519_dalek::SigningKey> =
+        (0..3).map(|_| ed25519_dalek::SigningKey::from_bytes(&rand::random())).collect();
+    let authority_ids: Vec<NodeId> =
+        (0..3).map(|i| NodeId::from_string(format!("auth{}", i))).collect();
+    let directory = std::sync::Arc::new(DirectoryService::new(
+        authority_ids
+            .iter()
+            .cloned()
+            .zip(authority_keys.iter().map(|key| key.verifying_key()))
+            .collect(),
+        3600,
+    ));
+    
+    let usable = [Running, Valid];
+    let usable_exit = [Running, Valid, Exit];
+    let votes = [
+        // r3 is only seen running by one authority, r4 is only listed by one
+        vec![
+            entry(&relays[0], &usable_exit),
+            entry(&relays[1], &usable_exit),
+            entry(&relays[2], &usable),
+            entry(&relays[3], &usable),
+            entry(&relays[4], &usable_exit),
+        ],
+        // r5 is only listed by one authority
+        vec![
+            entry(&relays[0], &usable_exit),
+            entry(&relays[1], &usable_exit),
+            entry(&relays[2], &usable),
+            entry(&relays[3], &[Valid]),
+            entry(&relays[5], &usable_exit),
+        ],
+        // Only a minority withholds r0's Exit flag
+        vec![
+            entry(&relays[0], &usable),
+            entry(&relays[1], &usable_exit),
+            entry(&relays[2], &usable),
+            entry(&relays[3], &[Valid]),
+        ],
+    ];
+    
+    assert!(directory.refresh().await.is_err());
+    for ((id, key), entries) in authority_ids.iter().zip(&authority_keys).zip(votes) {
+        let vote = AuthorityVote::new(id.clone(), entries, key).unwrap();
+        directory.submit_vote(vote).await.unwrap();
+    }
+    
+    let consensus = directory.refresh().await.unwrap();
+    let listed: Vec<&str> = consensus.relays.iter().map(|r| r.node_id.0.as_str()).collect();
+    assert_eq!(listed, vec!["r0", "r1", "r2", "r3"]);
+    assert!(consensus.relay(&NodeId::from_string("r0".into())).unwrap().flags.contains(&Exit));
+    assert!(!consensus.relay(&NodeId::from_string("r3".into())).unwrap().is_usable());
+    
+    let router = OnionRouter::new(RouterConfig::new().with_hop_count(3))
+        .await
+        .unwrap()
+        .with_directory(directory.clone());
+    let mut exits = std::collections::HashSet::new();
+    for _ in 0..50 {
+        let circuit = router.create_circuit().await.unwrap();
+        let mut hops: Vec<&str> = circuit.nodes.iter().map(|n| n.0.as_str()).collect();
+        exits.insert(hops[2].to_string());
+        hops.sort_unstable();
+        assert_eq!(hops, vec!["r0", "r1", "r2"]);
+    }
+    assert!(exits.iter().all(|exit| exit == "r0" || exit == "r1"));
+    
+    // Four hops can't be built from three usable relays
+    let router = OnionRouter::new(RouterConfig::new().with_hop_count(4))
+        .await
+        .unwrap()
+        .with_directory(directory);
+    assert!(router.create_circuit().await.is_err());
+}
+
+#[tokio::test]
+async fn test_forged_votes_and_descriptors_rejected() {
+    let authority = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+    let id = NodeId::from_string("auth".to_string());
+    let directory = DirectoryService::new(vec![(id.clone(), authority.verifying_key())], 3600);
+    
+    let (_, good) = relay("good");
+    let (_, mut tampered) = relay("tampered");
+    tampered.descriptor.address = "attacker:9001".to_string();
+    assert!(good.verify().is_ok());
+    assert!(tampered.verify().is_err());
+    
+    let entries = vec![entry(&good, &[RelayFlag::Running, RelayFlag::Valid])];
+    
+    // Vote signed by an untrusted key
+    let impostor = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+    let forged = AuthorityVote::new(id.clone(), entries.clone(), &impostor).unwrap();
+    assert!(directory.submit_vote(forged).await.is_err());
+    
+    // Vote containing a descriptor whose signature doesn't match
+    let mut with_tampered = entries.clone();
+    with_tampered.push(entry(&tampered, &[RelayFlag::Running, RelayFlag::Valid]));
+    let vote = AuthorityVote::new(id.clone(), with_tampered, &authority).unwrap();
+    assert!(directory.submit_vote(vote).await.is_err());
+    
+    let vote = AuthorityVote::new(id, entries, &authority).unwrap();
+    directory.submit_vote(vote).await.unwrap();
+    assert_eq!(directory.refresh().await.unwrap().relays.len(), 1);
+    assert!(directory.consensus().await.is_some());
+}
+
+#[tokio::test]
+async fn test_reply_through_rendezvous_reaches_sender() {
+    let config = RouterConfig::new().with_hop_count(3);
+    let sender = OnionRouter::new(config.clone()).await.unwrap();
+    let recipient = OnionRouter::new(config).await.unwrap();
+    let point = std::sync::Arc::new(RendezvousPoint::new(NodeId::from_string("rp".to_string())));
+    sender.add_rendezvous_point(point.clone()).await;
+    recipient.add_rendezvous_point(point.clone()).await;
+    
+    let mut rendezvous = sender.create_rendezvous().await.unwrap();
+    assert_eq!(rendezvous.circuit().nodes.last(), Some(point.node_id()));
+    assert_eq!(point.waiting_circuits(), 1);
+    
+    // The request reaches the recipient through the sender's forward circuit
+    let request = rendezvous.request(b"ping".to_vec(), None);
+    let mut exit = CellReassembler::new();
+    let mut delivered = None;
+    for cell in sender.frame_message(&request).unwrap() {
+        delivered = exit.push(&cell).unwrap().or(delivered);
+    }
+    let delivered = delivered.unwrap();
+    assert_eq!(delivered.data, b"ping");
+    
+    // All the recipient learns is the rendezvous point and the cookie
+    assert_eq!(delivered.reply_to, None);
+    let address = delivered.rendezvous.unwrap();
+    assert_eq!(address, rendezvous.address());
+    assert_eq!(&address.point, point.node_id());
+    assert!(!rendezvous.circuit().nodes[..2].contains(&address.point));
+    
+    recipient.reply(&address, b"pong").await.unwrap();
+    let reply = rendezvous.recv().await.unwrap().unwrap();
+    assert_eq!(reply.data, b"pong");
+    
+    // Nor does the reply carry anything about the recipient
+    assert_eq!(reply.reply_to, None);
+    assert_eq!(reply.destination, None);
+    assert_eq!(reply.rendezvous, None);
+    
+    // The cookie is released with the handle
+    drop(rendezvous);
+    assert_eq!(point.waiting_circuits(), 0);
+    assert!(recipient.reply(&address, b"late").await.is_err());
+}
+
+async fn relay_through_three_hops(suite: OnionSuite) {
+    let config = RouterConfig::new().with_hop_count(3).with_onion_suite(suite);
+    let router = OnionRouter::new(config.clone()).await.unwrap();
+    let relays: Vec<RelayKeys> = (0..3)
+        .map(|_| RelayKeys::generate(suite.key_exchange).unwrap())
+        .collect();
+    let public: Vec<RelayPublicKeys> = relays.iter().map(RelayKeys::public).collect();
+    
+    let circuit = router.create_circuit().await.unwrap();
+    // Nothing is sent before the layers exist
+    assert!(router.send_anonymous(&circuit, b"too early").await.is_err());
+    
+    let handshakes = router.establish_layers(&circuit, &public).await.unwrap();
+    assert_eq!(handshakes.len(), 3);
+    // Every hop gets its own ephemeral key and, for hybrid suites, encapsulation
+    assert_ne!(handshakes[0].ephemeral, handshakes[1].ephemeral);
+    assert_ne!(handshakes[1].ephemeral, handshakes[2].ephemeral);
+    
+    let hops: Vec<HopLayer> = relays
+        .iter()
+        .zip(&handshakes)
+        .map(|(relay, handshake)| relay.accept(&config.onion_suite, handshake).unwrap())
+        .collect();
+    
+    let message = b"through three hops".to_vec();
+    let onions = router.seal_message(&circuit, &message).await.unwrap();
+    let mut reassembler = CellReassembler::new();
+    let mut received = None;
+    for mut onion in onions {
+        // A later hop can't remove a layer it doesn't own
+        assert!(hops[1].open(&onion).is_err());
+        for hop in &hops {
+            assert!(Cell::from_bytes(&onion).is_err());
+            onion = hop.open(&onion).unwrap();
+        }
+        received = reassembler.push(&Cell::from_bytes(&onion).unwrap()).unwrap();
+    }
+    assert_eq!(received.unwrap().data, message);
+    
+    router.send_anonymous(&circuit, &message).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_classical_circuit_relays_through_three_hops() {
+    relay_through_three_hops(OnionSuite::classical()).await;
+    relay_through_three_hops(OnionSuite {
+        aead: AeadCipher::Aes256Gcm,
+        ..OnionSuite::classical()
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_pq_hybrid_circuit_relays_through_three_hops() {
+    relay_through_three_hops(OnionSuite::pq_hybrid()).await;
+}
+
+#[tokio::test]
+async fn test_handshake_must_match_suite() {
+    let suite = OnionSuite::classical();
+    let relay = RelayKeys::generate(KeyExchange::X25519).unwrap();
+    
+    let (_, handshakes) = LayeredCircuit::establish(suite, &[relay.public()]).unwrap();
+    // The relay has no Kyber key to establish a hybrid layer with
+    assert!(LayeredCircuit::establish(OnionSuite::pq_hybrid(), &[relay.public()]).is_err());
+    assert!(relay.accept(&OnionSuite::pq_hybrid(), &handshakes[0]).is_err());
+}
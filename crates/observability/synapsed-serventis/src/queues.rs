@@ -1,7 +1,11 @@
-//! Queues API - placeholder for future implementation
+//! Queues API - signals describing interactions with queue-like systems
 //! Direct port of Java Serventis Queues interface
 
-use crate::{async_trait, Arc, Composer, Pipe, Substrate};
+use crate::{async_trait, Arc, Composer, Pipe, Subject, Substrate};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+use synapsed_substrates::types::SubstratesResult;
 
 /// The Queues interface - entry point into the Serventis Queues API
 /// Direct port of Java Serventis Queues interface
@@ -66,4 +70,221 @@ impl QueueEvent for BasicQueueEvent {
     fn queue_depth(&self) -> Option<usize> {
         self.queue_depth
     }
-}
\ No newline at end of file
+}
+
+/// Depth and latency limits for a queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueThresholds {
+    /// Maximum number of items waiting in the queue
+    pub max_depth: usize,
+    /// Maximum time an item may wait between enqueue and dequeue
+    pub max_latency: Duration,
+}
+
+/// Signal emitted when a queue exceeds one of its thresholds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueBreach {
+    /// Queue depth rose above the configured maximum
+    Depth {
+        queue_id: String,
+        depth: usize,
+        max_depth: usize,
+    },
+    /// An item waited longer than the configured maximum
+    Latency {
+        queue_id: String,
+        latency: Duration,
+        max_latency: Duration,
+    },
+}
+
+impl QueueBreach {
+    /// Get the identifier of the queue that breached
+    pub fn queue_id(&self) -> &str {
+        match self {
+            QueueBreach::Depth { queue_id, .. } | QueueBreach::Latency { queue_id, .. } => queue_id,
+        }
+    }
+}
+
+/// Depth and latency observed for a single queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QueueMetrics {
+    /// Current queue depth
+    pub depth: usize,
+    /// Highest depth observed
+    pub peak_depth: usize,
+    /// Enqueue-to-dequeue latency of the most recent dequeue
+    pub last_latency: Option<Duration>,
+    /// Mean enqueue-to-dequeue latency over all dequeues
+    pub mean_latency: Option<Duration>,
+    /// Number of dequeues with a measured latency
+    pub dequeues: u64,
+}
+
+/// Tracking state for a single queue
+#[derive(Debug, Default)]
+struct QueueState {
+    metrics: QueueMetrics,
+    enqueued_at: VecDeque<Instant>,
+    total_latency: Duration,
+    depth_breached: bool,
+    latency_breached: bool,
+}
+
+/// A wrapper type for breach handlers that implements Debug
+struct BreachHandler {
+    handler: Arc<dyn Fn(QueueBreach) + Send + Sync>,
+}
+
+impl fmt::Debug for BreachHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BreachHandler")
+            .field("handler", &"<function>")
+            .finish()
+    }
+}
+
+/// Basic queue monitor tracking depth and enqueue-to-dequeue latency
+///
+/// Latency is measured in FIFO order from the time each enqueue event is
+/// received. Breaches are edge-triggered: a signal is emitted when a queue
+/// crosses a threshold and again only after it has recovered below it.
+#[derive(Debug)]
+pub struct BasicQueueMonitor {
+    subject: Subject,
+    thresholds: Option<QueueThresholds>,
+    queues: HashMap<String, QueueState>,
+    breach_handler: Option<BreachHandler>,
+}
+
+impl BasicQueueMonitor {
+    pub fn new(subject: Subject) -> Self {
+        Self {
+            subject,
+            thresholds: None,
+            queues: HashMap::new(),
+            breach_handler: None,
+        }
+    }
+    
+    /// Emit breach signals when depth or latency exceed the given limits
+    pub fn with_thresholds(mut self, max_depth: usize, max_latency: Duration) -> Self {
+        self.thresholds = Some(QueueThresholds { max_depth, max_latency });
+        self
+    }
+    
+    /// Set the handler receiving breach signals
+    pub fn on_breach<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(QueueBreach) + Send + Sync + 'static,
+    {
+        self.breach_handler = Some(BreachHandler { handler: Arc::new(handler) });
+        self
+    }
+    
+    /// Get the configured thresholds
+    pub fn thresholds(&self) -> Option<QueueThresholds> {
+        self.thresholds
+    }
+    
+    /// Get the metrics observed for a queue
+    pub fn metrics(&self, queue_id: &str) -> Option<QueueMetrics> {
+        self.queues.get(queue_id).map(|state| state.metrics)
+    }
+    
+    fn record(&mut self, event: &dyn QueueEvent) -> Vec<QueueBreach> {
+        let now = Instant::now();
+        let state = self.queues.entry(event.queue_id().to_string()).or_default();
+        
+        match event.event_type() {
+            QueueEventType::Enqueue => {
+                state.enqueued_at.push_back(now);
+                state.metrics.depth = event.queue_depth().unwrap_or(state.metrics.depth + 1);
+            }
+            QueueEventType::Dequeue => {
+                if let Some(enqueued) = state.enqueued_at.pop_front() {
+                    let latency = now.duration_since(enqueued);
+                    state.total_latency += latency;
+                    state.metrics.dequeues += 1;
+                    state.metrics.last_latency = Some(latency);
+                    state.metrics.mean_latency = Some(Duration::from_secs_f64(
+                        state.total_latency.as_secs_f64() / state.metrics.dequeues as f64,
+                    ));
+                }
+                state.metrics.depth = event
+                    .queue_depth()
+                    .unwrap_or(state.metrics.depth.saturating_sub(1));
+            }
+            QueueEventType::Empty | QueueEventType::Underflow => {
+                state.enqueued_at.clear();
+                state.metrics.depth = 0;
+            }
+            QueueEventType::Full | QueueEventType::Overflow => {
+                if let Some(depth) = event.queue_depth() {
+                    state.metrics.depth = depth;
+                }
+            }
+        }
+        state.metrics.peak_depth = state.metrics.peak_depth.max(state.metrics.depth);
+        
+        let Some(thresholds) = self.thresholds else {
+            return Vec::new();
+        };
+        
+        let mut breaches = Vec::new();
+        let depth = state.metrics.depth;
+        if depth > thresholds.max_depth {
+            if !state.depth_breached {
+                state.depth_breached = true;
+                breaches.push(QueueBreach::Depth {
+                    queue_id: event.queue_id().to_string(),
+                    depth,
+                    max_depth: thresholds.max_depth,
+                });
+            }
+        } else {
+            state.depth_breached = false;
+        }
+        
+        if event.event_type() == QueueEventType::Dequeue {
+            if let Some(latency) = state.metrics.last_latency {
+                if latency > thresholds.max_latency {
+                    if !state.latency_breached {
+                        state.latency_breached = true;
+                        breaches.push(QueueBreach::Latency {
+                            queue_id: event.queue_id().to_string(),
+                            latency,
+                            max_latency: thresholds.max_latency,
+                        });
+                    }
+                } else {
+                    state.latency_breached = false;
+                }
+            }
+        }
+        
+        breaches
+    }
+}
+
+impl Substrate for BasicQueueMonitor {
+    fn subject(&self) -> &Subject {
+        &self.subject
+    }
+}
+
+#[async_trait]
+impl Pipe<Box<dyn QueueEvent>> for BasicQueueMonitor {
+    async fn emit(&mut self, emission: Box<dyn QueueEvent>) -> SubstratesResult<()> {
+        let breaches = self.record(emission.as_ref());
+        if let Some(handler) = &self.breach_handler {
+            for breach in breaches {
+                (handler.handler)(breach);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl QueueMonitor for BasicQueueMonitor {}
@@ -1,7 +1,11 @@
-//! Resources API - placeholder for future implementation
+//! Resources API - signals describing interactions with shared resources
 //! Direct port of Java Serventis Resources interface
 
-use crate::{async_trait, Arc, Composer, Pipe, Substrate};
+use crate::{async_trait, Arc, Composer, Pipe, Subject, Substrate};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+use synapsed_substrates::types::SubstratesResult;
 
 /// The Resources interface - entry point into the Serventis Resources API
 /// Direct port of Java Serventis Resources interface
@@ -59,4 +63,195 @@ impl ResourceEvent for BasicResourceEvent {
     fn event_type(&self) -> ResourceEventType {
         self.event_type
     }
-}
\ No newline at end of file
+}
+
+/// Signal emitted when acquire attempts exceed a resource's capacity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentionSignal {
+    /// Resource under contention
+    pub resource_id: String,
+    /// Maximum number of concurrent holders
+    pub capacity: usize,
+    /// Number of current holders
+    pub in_flight: usize,
+    /// Number of acquire attempts waiting for the resource
+    pub waiting: usize,
+}
+
+/// Acquisition statistics for a single resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceStats {
+    /// Maximum number of concurrent holders, `None` if unbounded
+    pub capacity: Option<usize>,
+    /// Number of current holders
+    pub in_flight: usize,
+    /// Number of acquire attempts waiting for the resource
+    pub waiting: usize,
+    /// Highest number of waiting attempts observed
+    pub peak_waiting: usize,
+    /// Number of granted acquisitions
+    pub acquisitions: u64,
+    /// Number of acquire attempts that had to wait
+    pub contentions: u64,
+    /// Total time spent waiting for the resource
+    pub total_wait: Duration,
+    /// Longest time spent waiting for the resource
+    pub max_wait: Duration,
+}
+
+impl ResourceStats {
+    /// Mean acquisition wait time over all granted acquisitions
+    pub fn mean_wait(&self) -> Option<Duration> {
+        if self.acquisitions == 0 {
+            return None;
+        }
+        Some(self.total_wait / self.acquisitions as u32)
+    }
+}
+
+/// Tracking state for a single resource
+#[derive(Debug, Default)]
+struct ResourceState {
+    stats: ResourceStats,
+    waiters: VecDeque<Instant>,
+}
+
+impl ResourceState {
+    fn grant(&mut self, requested_at: Instant) {
+        let wait = requested_at.elapsed();
+        self.stats.in_flight += 1;
+        self.stats.acquisitions += 1;
+        self.stats.total_wait += wait;
+        self.stats.max_wait = self.stats.max_wait.max(wait);
+    }
+}
+
+/// A wrapper type for contention handlers that implements Debug
+struct ContentionHandler {
+    handler: Arc<dyn Fn(ContentionSignal) + Send + Sync>,
+}
+
+impl fmt::Debug for ContentionHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentionHandler")
+            .field("handler", &"<function>")
+            .finish()
+    }
+}
+
+/// Basic resource monitor tracking in-flight holders and acquisition waits
+///
+/// Acquire attempts beyond a resource's capacity wait in FIFO order and are
+/// granted as holders release the resource. Resources without a configured
+/// capacity are never contended.
+#[derive(Debug)]
+pub struct BasicResourceMonitor {
+    subject: Subject,
+    resources: HashMap<String, ResourceState>,
+    contention_handler: Option<ContentionHandler>,
+}
+
+impl BasicResourceMonitor {
+    pub fn new(subject: Subject) -> Self {
+        Self {
+            subject,
+            resources: HashMap::new(),
+            contention_handler: None,
+        }
+    }
+    
+    /// Limit the number of concurrent holders of a resource
+    pub fn with_capacity(mut self, resource_id: impl Into<String>, capacity: usize) -> Self {
+        self.set_capacity(resource_id, capacity);
+        self
+    }
+    
+    /// Set the handler receiving contention signals
+    pub fn on_contention<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(ContentionSignal) + Send + Sync + 'static,
+    {
+        self.contention_handler = Some(ContentionHandler { handler: Arc::new(handler) });
+        self
+    }
+    
+    /// Limit the number of concurrent holders of a resource
+    pub fn set_capacity(&mut self, resource_id: impl Into<String>, capacity: usize) {
+        self.resources.entry(resource_id.into()).or_default().stats.capacity = Some(capacity);
+    }
+    
+    /// Get the acquisition statistics for a resource
+    pub fn stats(&self, resource_id: &str) -> Option<ResourceStats> {
+        self.resources.get(resource_id).map(|state| state.stats)
+    }
+    
+    /// Record an acquire attempt, returning whether it was granted immediately
+    ///
+    /// Attempts beyond capacity are queued and emit a contention signal.
+    pub fn record_acquire(&mut self, resource_id: &str) -> bool {
+        let now = Instant::now();
+        let state = self.resources.entry(resource_id.to_string()).or_default();
+        
+        let at_capacity = state
+            .stats
+            .capacity
+            .is_some_and(|capacity| state.stats.in_flight >= capacity);
+        if !at_capacity {
+            state.grant(now);
+            return true;
+        }
+        
+        state.waiters.push_back(now);
+        state.stats.waiting = state.waiters.len();
+        state.stats.peak_waiting = state.stats.peak_waiting.max(state.stats.waiting);
+        state.stats.contentions += 1;
+        
+        let signal = ContentionSignal {
+            resource_id: resource_id.to_string(),
+            capacity: state.stats.capacity.unwrap_or_default(),
+            in_flight: state.stats.in_flight,
+            waiting: state.stats.waiting,
+        };
+        if let Some(handler) = &self.contention_handler {
+            (handler.handler)(signal);
+        }
+        false
+    }
+    
+    /// Record a release, granting the resource to the oldest waiter if any
+    pub fn record_release(&mut self, resource_id: &str) {
+        let Some(state) = self.resources.get_mut(resource_id) else {
+            return;
+        };
+        
+        state.stats.in_flight = state.stats.in_flight.saturating_sub(1);
+        if let Some(requested_at) = state.waiters.pop_front() {
+            state.grant(requested_at);
+            state.stats.waiting = state.waiters.len();
+        }
+    }
+}
+
+impl Substrate for BasicResourceMonitor {
+    fn subject(&self) -> &Subject {
+        &self.subject
+    }
+}
+
+#[async_trait]
+impl Pipe<Box<dyn ResourceEvent>> for BasicResourceMonitor {
+    async fn emit(&mut self, emission: Box<dyn ResourceEvent>) -> SubstratesResult<()> {
+        match emission.event_type() {
+            ResourceEventType::Acquire | ResourceEventType::Lock => {
+                self.record_acquire(emission.resource_id());
+            }
+            ResourceEventType::Release | ResourceEventType::Unlock => {
+                self.record_release(emission.resource_id());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl ResourceMonitor for BasicResourceMonitor {}
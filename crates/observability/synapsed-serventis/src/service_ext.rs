@@ -3,7 +3,7 @@
 //! This module provides additional service functionality including dispatch and execute
 //! methods that wrap function execution with proper signal emission.
 
-use crate::{async_trait, Service};
+use crate::{async_trait, ErrorKind, Service};
 use std::future::Future;
 use std::pin::Pin;
 use synapsed_substrates::types::SubstratesResult;
@@ -11,6 +11,16 @@ use synapsed_substrates::types::SubstratesResult;
 /// Extended service trait with dispatch and execute methods
 #[async_trait]
 pub trait ServiceExt: Service {
+    /// Emit a fail signal followed by a categorized error signal when the
+    /// failure mode of `error` can be determined
+    async fn report_failure(&mut self, error: &(dyn std::error::Error + Send + Sync + 'static)) -> SubstratesResult<()> {
+        self.fail().await?;
+        if let Some(kind) = ErrorKind::classify(error) {
+            self.error(kind).await?;
+        }
+        Ok(())
+    }
+    
     /// Dispatch a function with proper signal emission (call -> success/fail)
     /// This is the async Rust equivalent of Java's dispatch(Fn) method
     async fn dispatch<F, R>(&mut self, f: F) -> Result<R, Box<dyn std::error::Error + Send + Sync>>
@@ -29,8 +39,8 @@ pub trait ServiceExt: Service {
                 Ok(result)
             }
             Err(e) => {
-                // Emit fail signal
-                self.fail().await?;
+                // Emit fail and categorized error signals
+                self.report_failure(e.as_ref()).await?;
                 Err(e)
             }
         }
@@ -53,8 +63,8 @@ pub trait ServiceExt: Service {
                 Ok(result)
             }
             Err(e) => {
-                // Emit fail signal
-                self.fail().await?;
+                // Emit fail and categorized error signals
+                self.report_failure(e.as_ref()).await?;
                 Err(e)
             }
         }
@@ -78,8 +88,8 @@ pub trait ServiceExt: Service {
                 Ok(result)
             }
             Err(e) => {
-                // Emit fail signal
-                self.fail().await?;
+                // Emit fail and categorized error signals
+                self.report_failure(e.as_ref()).await?;
                 Err(e)
             }
         };
@@ -107,8 +117,8 @@ pub trait ServiceExt: Service {
                 Ok(result)
             }
             Err(e) => {
-                // Emit fail signal
-                self.fail().await?;
+                // Emit fail and categorized error signals
+                self.report_failure(e.as_ref()).await?;
                 Err(e)
             }
         };
@@ -140,7 +150,7 @@ pub trait ServiceExt: Service {
                 }
                 Err(e) => {
                     if attempt >= max_retries {
-                        self.fail().await?;
+                        self.report_failure(e.as_ref()).await?;
                         break Err(e);
                     }
                     attempt += 1;
@@ -170,11 +180,12 @@ pub trait ServiceExt: Service {
                 Ok(result)
             }
             Ok(Err(e)) => {
-                self.fail().await?;
+                self.report_failure(e.as_ref()).await?;
                 Err(e)
             }
             Err(_) => {
                 self.expire().await?;
+                self.error(ErrorKind::Timeout).await?;
                 Err("Operation timed out".into())
             }
         };
@@ -209,7 +220,7 @@ pub trait ServiceExt: Service {
                 Ok(result)
             }
             Err(e) => {
-                self.fail().await?;
+                self.report_failure(e.as_ref()).await?;
                 // Activate recourse strategy
                 self.recourse().await?;
                 Err(e)
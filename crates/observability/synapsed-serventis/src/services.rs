@@ -5,6 +5,7 @@
 
 use crate::{async_trait, Arc, Composer, Pipe, Subject, Substrate};
 use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
 use std::fmt;
 use synapsed_substrates::types::SubstratesResult;
 
@@ -175,6 +176,11 @@ pub trait Service: Pipe<Signal> + Substrate + Send + Sync {
     async fn suspended(&mut self) -> SubstratesResult<()> {
         self.emit(Signal::Suspended).await
     }
+    
+    /// A signal released indicating a categorized error
+    async fn error(&mut self, kind: ErrorKind) -> SubstratesResult<()> {
+        self.emit(Signal::Error(kind)).await
+    }
 }
 
 /// Signal enum representing various types of signals services can emit
@@ -245,6 +251,8 @@ pub enum Signal {
     Disconnect,
     /// A signal received indicating disconnection of work
     Disconnected,
+    /// A signal released indicating a categorized error
+    Error(ErrorKind),
 }
 
 impl Signal {
@@ -267,6 +275,15 @@ impl Signal {
             Signal::Suspend | Signal::Suspended => Sign::Suspend,
             Signal::Resume | Signal::Resumed => Sign::Resume,
             Signal::Disconnect | Signal::Disconnected => Sign::Disconnect,
+            Signal::Error(_) => Sign::Error,
+        }
+    }
+    
+    /// Get the error category carried by this signal, if any
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        match self {
+            Signal::Error(kind) => Some(*kind),
+            _ => None,
         }
     }
     
@@ -276,7 +293,8 @@ impl Signal {
             Signal::Start | Signal::Stop | Signal::Call | Signal::Success | Signal::Fail
             | Signal::Recourse | Signal::Redirect | Signal::Expire | Signal::Retry
             | Signal::Reject | Signal::Discard | Signal::Delay | Signal::Schedule
-            | Signal::Suspend | Signal::Resume | Signal::Disconnect => Orientation::Release,
+            | Signal::Suspend | Signal::Resume | Signal::Disconnect
+            | Signal::Error(_) => Orientation::Release,
             
             Signal::Started | Signal::Stopped | Signal::Called | Signal::Succeeded
             | Signal::Failed | Signal::Recoursed | Signal::Redirected | Signal::Expired
@@ -324,6 +342,48 @@ pub enum Sign {
     Resume,
     /// Indicates inability to issue work
     Disconnect,
+    /// Indicates a categorized error
+    Error,
+}
+
+/// ErrorKind categorizes the failure mode behind an error signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorKind {
+    /// Work did not complete within its time budget
+    Timeout,
+    /// Work was refused by the remote side
+    Refused,
+    /// Work produced or received malformed data
+    Corrupt,
+    /// Work was shed because the service is saturated
+    Overload,
+}
+
+impl ErrorKind {
+    /// Categorize an error by inspecting its source chain
+    ///
+    /// Recognizes I/O errors and elapsed Tokio timeouts. Returns `None` when the
+    /// failure mode cannot be determined.
+    pub fn classify(error: &(dyn StdError + 'static)) -> Option<ErrorKind> {
+        let mut current = Some(error);
+        while let Some(err) = current {
+            if err.is::<tokio::time::error::Elapsed>() {
+                return Some(ErrorKind::Timeout);
+            }
+            if let Some(io) = err.downcast_ref::<std::io::Error>() {
+                match io.kind() {
+                    std::io::ErrorKind::TimedOut => return Some(ErrorKind::Timeout),
+                    std::io::ErrorKind::ConnectionRefused => return Some(ErrorKind::Refused),
+                    std::io::ErrorKind::InvalidData
+                    | std::io::ErrorKind::UnexpectedEof => return Some(ErrorKind::Corrupt),
+                    std::io::ErrorKind::OutOfMemory => return Some(ErrorKind::Overload),
+                    _ => {}
+                }
+            }
+            current = err.source();
+        }
+        None
+    }
 }
 
 /// Orientation classifies the method of signal recording
@@ -348,6 +408,12 @@ impl fmt::Display for Sign {
     }
 }
 
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 impl fmt::Display for Orientation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -266,4 +266,54 @@ async fn test_queue_monitor_async_behavior() {
     
     let events = tracker.get_events();
     assert_eq!(events.len(), 5);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_queue_monitor_threshold_breach() {
+    let breaches = Arc::new(Mutex::new(Vec::new()));
+    let breaches_clone = breaches.clone();
+    let subject = Subject::new(Name::from_part("work-queue"), SubjectType::Channel);
+    let mut monitor = BasicQueueMonitor::new(subject)
+        .with_thresholds(5, std::time::Duration::from_millis(20))
+        .on_breach(move |breach| breaches_clone.lock().unwrap().push(breach));
+    
+    // Three enqueues for every dequeue so the queue builds up
+    for _ in 0..4 {
+        for _ in 0..3 {
+            let event = BasicQueueEvent::new("jobs".to_string(), QueueEventType::Enqueue, None);
+            monitor.emit(Box::new(event)).await.unwrap();
+        }
+        let event = BasicQueueEvent::new("jobs".to_string(), QueueEventType::Dequeue, None);
+        monitor.emit(Box::new(event)).await.unwrap();
+    }
+    
+    let metrics = monitor.metrics("jobs").unwrap();
+    assert_eq!(metrics.depth, 8);
+    assert_eq!(metrics.peak_depth, 9);
+    assert_eq!(metrics.dequeues, 4);
+    
+    // Depth first exceeded 5 on the eighth enqueue and is only signalled once
+    assert_eq!(
+        breaches.lock().unwrap().clone(),
+        vec![QueueBreach::Depth {
+            queue_id: "jobs".to_string(),
+            depth: 6,
+            max_depth: 5,
+        }]
+    );
+    
+    // Items that wait too long trigger a latency breach
+    tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+    let event = BasicQueueEvent::new("jobs".to_string(), QueueEventType::Dequeue, None);
+    monitor.emit(Box::new(event)).await.unwrap();
+    
+    let breaches = breaches.lock().unwrap();
+    assert_eq!(breaches.len(), 2);
+    match &breaches[1] {
+        QueueBreach::Latency { queue_id, latency, max_latency } => {
+            assert_eq!(queue_id, "jobs");
+            assert!(*latency > *max_latency);
+        }
+        other => panic!("expected latency breach, got {:?}", other),
+    }
+}
@@ -0,0 +1,81 @@
+use synapsed_serventis::*;
+use synapsed_substrates::SubjectType;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn create_monitor(signals: Arc<Mutex<Vec<ContentionSignal>>>) -> BasicResourceMonitor {
+    let subject = Subject::new(Name::from_part("test-resources"), SubjectType::Channel);
+    BasicResourceMonitor::new(subject)
+        .with_capacity("db-pool", 2)
+        .on_contention(move |signal| signals.lock().unwrap().push(signal))
+}
+
+#[tokio::test]
+async fn test_basic_resource_event() {
+    let event = BasicResourceEvent::new("db-pool".to_string(), ResourceEventType::Acquire);
+    
+    assert_eq!(event.resource_id(), "db-pool");
+    assert_eq!(event.event_type(), ResourceEventType::Acquire);
+}
+
+#[tokio::test]
+async fn test_contention_signal_and_wait_stats() {
+    let signals = Arc::new(Mutex::new(Vec::new()));
+    let mut monitor = create_monitor(signals.clone());
+    
+    // Four concurrent acquires against a capacity of two
+    assert!(monitor.record_acquire("db-pool"));
+    assert!(monitor.record_acquire("db-pool"));
+    assert!(!monitor.record_acquire("db-pool"));
+    assert!(!monitor.record_acquire("db-pool"));
+    
+    {
+        let signals = signals.lock().unwrap();
+        assert_eq!(signals.len(), 2);
+        assert_eq!(
+            signals[1],
+            ContentionSignal {
+                resource_id: "db-pool".to_string(),
+                capacity: 2,
+                in_flight: 2,
+                waiting: 2,
+            }
+        );
+    }
+    
+    // Releasing hands the resource to the waiters in order
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    monitor.record_release("db-pool");
+    monitor.record_release("db-pool");
+    
+    let stats = monitor.stats("db-pool").unwrap();
+    assert_eq!(stats.in_flight, 2);
+    assert_eq!(stats.waiting, 0);
+    assert_eq!(stats.peak_waiting, 2);
+    assert_eq!(stats.acquisitions, 4);
+    assert_eq!(stats.contentions, 2);
+    assert!(stats.max_wait >= Duration::from_millis(20));
+    assert!(stats.mean_wait().unwrap() >= Duration::from_millis(10));
+}
+
+#[tokio::test]
+async fn test_resource_events_drive_monitor() {
+    let signals = Arc::new(Mutex::new(Vec::new()));
+    let mut monitor = create_monitor(signals.clone());
+    
+    for _ in 0..3 {
+        let event = BasicResourceEvent::new("db-pool".to_string(), ResourceEventType::Acquire);
+        monitor.emit(Box::new(event)).await.unwrap();
+    }
+    let event = BasicResourceEvent::new("db-pool".to_string(), ResourceEventType::Release);
+    monitor.emit(Box::new(event)).await.unwrap();
+    
+    // Unbounded resources are never contended
+    let event = BasicResourceEvent::new("cache".to_string(), ResourceEventType::Lock);
+    monitor.emit(Box::new(event)).await.unwrap();
+    
+    assert_eq!(signals.lock().unwrap().len(), 1);
+    assert_eq!(monitor.stats("db-pool").unwrap().in_flight, 2);
+    assert_eq!(monitor.stats("cache").unwrap().capacity, None);
+    assert_eq!(monitor.stats("cache").unwrap().in_flight, 1);
+}
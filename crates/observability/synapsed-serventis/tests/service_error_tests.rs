@@ -0,0 +1,93 @@
+//! Tests for categorized error signals
+
+use synapsed_serventis::*;
+use synapsed_substrates::types::{Name, SubjectType};
+use std::sync::{Arc, Mutex};
+
+fn recording_service() -> (BasicService, Arc<Mutex<Vec<Signal>>>) {
+    let signals = Arc::new(Mutex::new(Vec::new()));
+    let recorded = signals.clone();
+    let subject = Subject::new(Name::from_part("test"), SubjectType::Source);
+    let service = BasicService::with_handler(subject, move |signal| {
+        recorded.lock().unwrap().push(signal);
+    });
+    (service, signals)
+}
+
+#[tokio::test]
+async fn test_service_error_kinds_distinguishable() {
+    let (mut service, signals) = recording_service();
+
+    service.error(ErrorKind::Timeout).await.unwrap();
+
+    // Refusals surfaced through an I/O error are categorized by the helpers
+    let result = service
+        .dispatch(|| -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+            Err(Box::new(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)))
+        })
+        .await;
+    assert!(result.is_err());
+
+    let signals = signals.lock().unwrap().clone();
+    assert_eq!(
+        signals,
+        vec![
+            Signal::Error(ErrorKind::Timeout),
+            Signal::Call,
+            Signal::Fail,
+            Signal::Error(ErrorKind::Refused),
+        ]
+    );
+    assert_ne!(signals[0], signals[3]);
+    assert_eq!(signals[0].sign(), Sign::Error);
+    assert_eq!(signals[0].orientation(), Orientation::Release);
+    assert_eq!(signals[0].error_kind(), Some(ErrorKind::Timeout));
+    assert_eq!(signals[3].error_kind(), Some(ErrorKind::Refused));
+    assert_eq!(signals[2].error_kind(), None);
+}
+
+#[tokio::test]
+async fn test_uncategorized_failures_emit_only_fail() {
+    let (mut service, signals) = recording_service();
+
+    let result = service
+        .dispatch(|| -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+            Err("unexpected".into())
+        })
+        .await;
+    assert!(result.is_err());
+
+    assert_eq!(*signals.lock().unwrap(), vec![Signal::Call, Signal::Fail]);
+}
+
+/// Error wrapping a lower-level cause
+#[derive(Debug)]
+struct Wrapped(std::io::Error);
+
+impl std::fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "request failed")
+    }
+}
+
+impl std::error::Error for Wrapped {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn test_error_kind_classification() {
+    let timed_out = std::io::Error::from(std::io::ErrorKind::TimedOut);
+    assert_eq!(ErrorKind::classify(&timed_out), Some(ErrorKind::Timeout));
+
+    let corrupt = std::io::Error::from(std::io::ErrorKind::InvalidData);
+    assert_eq!(ErrorKind::classify(&corrupt), Some(ErrorKind::Corrupt));
+
+    // The source chain is searched for a recognizable cause
+    let wrapped = Wrapped(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+    assert_eq!(ErrorKind::classify(&wrapped), Some(ErrorKind::Refused));
+
+    let unknown = std::io::Error::from(std::io::ErrorKind::NotFound);
+    assert_eq!(ErrorKind::classify(&unknown), None);
+}
@@ -7,6 +7,7 @@
 
 use crate::circuit::{Channel, Inlet};
 use crate::pipe::{Pipe, Path, Sequencer};
+use crate::recent::{RecentEmissions, Recorder};
 use crate::subject::{Substrate, Subject};
 use crate::types::{Name, SubjectType, SubstratesResult, SubstratesError};
 use std::sync::Arc;
@@ -22,6 +23,8 @@ pub struct BasicChannel<E> {
     sender: mpsc::UnboundedSender<E>,
     /// Receiver is stored to prevent channel from closing
     _receiver: Arc<RwLock<Option<mpsc::UnboundedReceiver<E>>>>,
+    /// Records emissions through this channel's pipes, if set
+    recorder: Option<Recorder<E>>,
 }
 
 impl<E> BasicChannel<E> {
@@ -33,6 +36,7 @@ impl<E> BasicChannel<E> {
             subject: Subject::new(name, SubjectType::Channel),
             sender,
             _receiver: Arc::new(RwLock::new(Some(receiver))),
+            recorder: None,
         }
     }
     
@@ -44,9 +48,24 @@ impl<E> BasicChannel<E> {
             subject: Subject::with_parent(name, SubjectType::Channel, parent),
             sender,
             _receiver: Arc::new(RwLock::new(Some(receiver))),
+            recorder: None,
         }
     }
     
+    /// Keep every emission through this channel's pipes in `recent`
+    pub fn with_recent(self, recent: &Arc<RecentEmissions>) -> Self
+    where
+        E: std::fmt::Display + 'static,
+    {
+        let recorder = recent.recorder(self.subject.clone());
+        self.with_recorder(recorder)
+    }
+    
+    pub(crate) fn with_recorder(mut self, recorder: Recorder<E>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+    
     #[cfg(test)]
     pub(crate) fn sender(&self) -> &mpsc::UnboundedSender<E> {
         &self.sender
@@ -64,7 +83,7 @@ where
     E: Send + Sync + 'static,
 {
     fn pipe(&self) -> SubstratesResult<Arc<dyn Pipe<E>>> {
-        Ok(Arc::new(ChannelPipe::new(self.sender.clone())))
+        Ok(Arc::new(ChannelPipe::new(self.sender.clone()).recorded_by(self.recorder.clone())))
     }
 }
 
@@ -94,11 +113,17 @@ where
 /// Pipe implementation that emits through a channel
 pub(crate) struct ChannelPipe<E> {
     sender: mpsc::UnboundedSender<E>,
+    recorder: Option<Recorder<E>>,
 }
 
 impl<E> ChannelPipe<E> {
     pub(crate) fn new(sender: mpsc::UnboundedSender<E>) -> Self {
-        Self { sender }
+        Self { sender, recorder: None }
+    }
+    
+    fn recorded_by(mut self, recorder: Option<Recorder<E>>) -> Self {
+        self.recorder = recorder;
+        self
     }
 }
 
@@ -115,6 +140,9 @@ where
 {
     /// Emit a value through this pipe into the channel's pipeline
     async fn emit(&mut self, emission: E) -> SubstratesResult<()> {
+        if let Some(record) = &self.recorder {
+            record(&emission);
+        }
         self.sender
             .send(emission)
             .map_err(|_| SubstratesError::Closed("Channel closed".to_string()))
@@ -2,13 +2,16 @@
 
 use crate::percept::Composer;
 use crate::pipe::{Pipe, Path, Sequencer};
+use crate::recent::{RecentEmissions, RecorderFactory, DEFAULT_RECENT_CAPACITY};
 use crate::subject::{Component, Resource, Substrate};
 use crate::types::{Name, State, SubjectType, SubstratesError, SubstratesResult};
 use crate::{async_trait, Subject};
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// Computational network of conduits, containers, clocks, channels, and pipes
@@ -24,6 +27,62 @@ pub trait Circuit: Substrate + Resource + Send + Sync {
     // Generic methods moved to CircuitExt trait for object-safety
     /// Returns a Queue that can be used to coordinate execution
     fn queue(&self) -> Arc<dyn Queue>;
+    
+    /// Returns the ring buffer of this circuit's most recent emissions, if it keeps one
+    fn recent_emissions(&self) -> Option<Arc<RecentEmissions>> {
+        None
+    }
+    
+    /// Writes the most recent emissions to a file, returning how many were written
+    async fn dump_recent(&self, path: &std::path::Path) -> SubstratesResult<usize> {
+        match self.recent_emissions() {
+            Some(recent) => recent.dump(path).await,
+            None => Err(SubstratesError::InvalidOperation(
+                "Circuit does not keep recent emissions".to_string()
+            )),
+        }
+    }
+    
+    /// Stops accepting new work and delivers queued emissions, waiting at most `timeout`
+    async fn shutdown_graceful(&self, _timeout: Duration) -> SubstratesResult<ShutdownReport> {
+        Err(SubstratesError::InvalidOperation(
+            "Circuit does not support graceful shutdown".to_string()
+        ))
+    }
+    
+    /// Returns whether the circuit has been shut down
+    fn is_stopped(&self) -> bool {
+        false
+    }
+}
+
+/// Buffer of emissions that a circuit drains when shutting down gracefully
+#[async_trait]
+pub trait Drain: Send + Sync {
+    /// Stops accepting new emissions
+    fn stop_accepting(&self);
+    
+    /// Returns how many accepted emissions have not been delivered yet
+    fn pending(&self) -> usize;
+    
+    /// Discards accepted emissions that have not been delivered yet
+    fn discard_pending(&self);
+    
+    /// Waits until every accepted emission has been delivered
+    async fn await_empty(&self) {
+        while self.pending() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+/// Outcome of a graceful circuit shutdown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Queued work delivered after shutdown began
+    pub delivered: usize,
+    /// Queued work discarded because the timeout elapsed first
+    pub dropped: usize,
 }
 
 /// Component that emits clock ticks
@@ -193,22 +252,52 @@ pub trait Tap<T>: Send + Sync {
 }
 
 /// Basic implementation of Circuit
-#[derive(Debug)]
 pub struct BasicCircuit {
     subject: Subject,
     #[allow(dead_code)]
     channels: RwLock<HashMap<Name, Arc<dyn std::any::Any + Send + Sync>>>,
     queue: Arc<BasicQueue>,
+    recent: Arc<RecentEmissions>,
+    /// Buffers drained after the queue on graceful shutdown
+    drains: RwLock<Vec<Arc<dyn Drain>>>,
+    /// Claimed by the first graceful shutdown
+    stopping: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl std::fmt::Debug for BasicCircuit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicCircuit")
+            .field("subject", &self.subject)
+            .field("queue", &self.queue)
+            .field("drains_count", &self.drains.read().len())
+            .field("stopped", &self.stopped)
+            .finish()
+    }
 }
 
 impl BasicCircuit {
     pub fn new(name: Name) -> Self {
+        Self::with_recent_capacity(name, DEFAULT_RECENT_CAPACITY)
+    }
+    
+    /// Create a circuit that keeps the last `capacity` emissions
+    pub fn with_recent_capacity(name: Name, capacity: usize) -> Self {
         Self {
             subject: Subject::new(name, SubjectType::Circuit),
             channels: RwLock::new(HashMap::new()),
             queue: Arc::new(BasicQueue::new()),
+            recent: Arc::new(RecentEmissions::new(capacity)),
+            drains: RwLock::new(Vec::new()),
+            stopping: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
         }
     }
+    
+    /// Drain emissions buffered in `drain`, such as a conduit's source, on graceful shutdown
+    pub fn drain_on_shutdown(&self, drain: Arc<dyn Drain>) {
+        self.drains.write().push(drain);
+    }
 }
 
 impl Substrate for BasicCircuit {
@@ -255,6 +344,46 @@ impl Circuit for BasicCircuit {
     fn queue(&self) -> Arc<dyn Queue> {
         self.queue.clone()
     }
+    
+    fn recent_emissions(&self) -> Option<Arc<RecentEmissions>> {
+        Some(self.recent.clone())
+    }
+    
+    /// Drains the queue first, then the buffers registered with
+    /// [`drain_on_shutdown`](BasicCircuit::drain_on_shutdown), which keep
+    /// accepting emissions from queued scripts until the queue is empty
+    async fn shutdown_graceful(&self, timeout: Duration) -> SubstratesResult<ShutdownReport> {
+        if self.stopping.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+            return Err(SubstratesError::Closed("Circuit already stopped".to_string()));
+        }
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut report = self.queue.shutdown(timeout).await;
+        
+        let drains = self.drains.read().clone();
+        for drain in &drains {
+            drain.stop_accepting();
+        }
+        let buffered: usize = drains.iter().map(|drain| drain.pending()).sum();
+        let _ = tokio::time::timeout_at(deadline, async {
+            for drain in &drains {
+                drain.await_empty().await;
+            }
+        }).await;
+        
+        let left: usize = drains.iter().map(|drain| drain.pending()).sum();
+        for drain in &drains {
+            drain.discard_pending();
+        }
+        report.delivered += buffered.saturating_sub(left);
+        report.dropped += left;
+        
+        self.stopped.store(true, Ordering::SeqCst);
+        Ok(report)
+    }
+    
+    fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
 }
 
 /// Basic Current implementation for script execution context
@@ -290,6 +419,12 @@ impl Default for BasicCurrent {
 pub struct BasicQueue {
     sender: mpsc::UnboundedSender<Arc<dyn Script>>,
     pending_count: Arc<std::sync::atomic::AtomicUsize>,
+    /// Scripts that have finished executing
+    completed_count: Arc<AtomicUsize>,
+    /// Cleared once shutdown begins
+    accepting: AtomicBool,
+    /// Set when a shutdown times out, so remaining scripts are skipped
+    discard: Arc<AtomicBool>,
 }
 
 impl BasicQueue {
@@ -297,20 +432,56 @@ impl BasicQueue {
         let (sender, mut receiver) = mpsc::unbounded_channel::<Arc<dyn Script>>();
         let pending_count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let pending_count_clone = pending_count.clone();
+        let completed_count = Arc::new(AtomicUsize::new(0));
+        let completed_count_clone = completed_count.clone();
+        let discard = Arc::new(AtomicBool::new(false));
+        let discard_clone = discard.clone();
         
         // Spawn a task to process scripts
         tokio::spawn(async move {
             while let Some(script) = receiver.recv().await {
-                // Create a basic Current context for script execution
-                let current = BasicCurrent::new();
-                if let Err(e) = script.exec(&current).await {
-                    tracing::error!("Script execution failed: {}", e);
+                if !discard_clone.load(Ordering::SeqCst) {
+                    // Create a basic Current context for script execution
+                    let current = BasicCurrent::new();
+                    if let Err(e) = script.exec(&current).await {
+                        tracing::error!("Script execution failed: {}", e);
+                    }
+                    completed_count_clone.fetch_add(1, Ordering::SeqCst);
                 }
                 pending_count_clone.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
             }
         });
         
-        Self { sender, pending_count }
+        Self {
+            sender,
+            pending_count,
+            completed_count,
+            accepting: AtomicBool::new(true),
+            discard,
+        }
+    }
+    
+    /// Stop accepting scripts and wait up to `timeout` for queued ones to run
+    ///
+    /// Scripts still queued when the timeout elapses are discarded. A script
+    /// already executing at that point runs to completion, but is counted as
+    /// dropped since it wasn't delivered in time.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.accepting.store(false, Ordering::SeqCst);
+        let completed_before = self.completed_count.load(Ordering::SeqCst);
+        
+        let drained = tokio::time::timeout(timeout, self.await_empty()).await.is_ok();
+        let dropped = if drained {
+            0
+        } else {
+            self.discard.store(true, Ordering::SeqCst);
+            self.pending_count.load(Ordering::SeqCst)
+        };
+        
+        ShutdownReport {
+            delivered: self.completed_count.load(Ordering::SeqCst) - completed_before,
+            dropped,
+        }
     }
 }
 
@@ -330,6 +501,9 @@ impl Queue for BasicQueue {
     }
     
     async fn post(&self, script: Arc<dyn Script>) -> SubstratesResult<()> {
+        if !self.accepting.load(Ordering::SeqCst) {
+            return Err(SubstratesError::Closed("Queue shut down".to_string()));
+        }
         self.pending_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         self.sender
             .send(script)
@@ -484,6 +658,7 @@ pub struct BasicConduit<P, E> {
     circuit: Subject,
     sequencer: Option<Arc<dyn Sequencer<dyn Path<E>>>>,
     source: crate::source::BasicSource<E>,
+    recorders: Option<RecorderFactory<E>>,
 }
 
 impl<P, E> std::fmt::Debug for BasicConduit<P, E> {
@@ -504,12 +679,22 @@ impl<P, E> BasicConduit<P, E> {
             circuit,
             sequencer: None,
             source,
+            recorders: None,
         }
     }
     
     pub fn set_sequencer(&mut self, sequencer: Arc<dyn Sequencer<dyn Path<E>>>) {
         self.sequencer = Some(sequencer);
     }
+    
+    /// Keep every emission through this conduit's channels in `recent`
+    pub fn with_recent(mut self, recent: &Arc<RecentEmissions>) -> Self
+    where
+        E: std::fmt::Display + 'static,
+    {
+        self.recorders = Some(recent.recorder_factory());
+        self
+    }
 }
 
 impl<P, E> Substrate for BasicConduit<P, E> {
@@ -541,7 +726,11 @@ where
     E: Send + Sync + 'static,
 {
     fn get(&self, name: &Name) -> SubstratesResult<P> {
-        let channel = crate::channel::BasicChannel::<E>::new(name.clone());
+        let mut channel = crate::channel::BasicChannel::<E>::new(name.clone());
+        if let Some(recorders) = &self.recorders {
+            let recorder = recorders(channel.subject().clone());
+            channel = channel.with_recorder(recorder);
+        }
         Ok(self.composer.compose(Arc::new(channel)))
     }
 }
@@ -568,6 +757,7 @@ pub struct BasicContainer<P, E> {
     sequencer: Option<Arc<dyn Sequencer<dyn Path<E>>>>,
     source: crate::source::BasicSource<E>,
     channels: parking_lot::RwLock<HashMap<Name, Arc<dyn Channel<E>>>>,
+    recorders: Option<RecorderFactory<E>>,
 }
 
 impl<P, E> std::fmt::Debug for BasicContainer<P, E> {
@@ -590,6 +780,7 @@ impl<P, E> BasicContainer<P, E> {
             sequencer: None,
             source,
             channels: parking_lot::RwLock::new(HashMap::new()),
+            recorders: None,
         }
     }
     
@@ -629,7 +820,14 @@ where
     fn get(&self, name: &Name) -> SubstratesResult<P> {
         let mut channels = self.channels.write();
         let channel = channels.entry(name.clone())
-            .or_insert_with(|| Arc::new(crate::channel::BasicChannel::<E>::new(name.clone())))
+            .or_insert_with(|| {
+                let mut channel = crate::channel::BasicChannel::<E>::new(name.clone());
+                if let Some(recorders) = &self.recorders {
+                    let recorder = recorders(channel.subject().clone());
+                    channel = channel.with_recorder(recorder);
+                }
+                Arc::new(channel)
+            })
             .clone();
         
         Ok(self.composer.compose(channel))
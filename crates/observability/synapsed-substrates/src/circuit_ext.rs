@@ -1,11 +1,14 @@
 //! Extension trait for Circuit with generic methods
 //! This pattern allows the core Circuit trait to remain object-safe while providing the full Java API
 
-use crate::circuit::{Circuit, Closure, Conduit, Container, Current};
+use crate::circuit::{Circuit, Closure, Conduit, Container, Current, Script};
 use crate::percept::Composer;
 use crate::pipe::{Path, Sequencer};
+use crate::routing::Route;
+use crate::subject::Subscriber;
 use crate::types::{Name, SubstratesResult};
 use crate::{async_trait, Subject};
+use std::str::FromStr;
 use std::sync::Arc;
 
 /// Extension trait providing generic methods for Circuit
@@ -31,6 +34,17 @@ pub trait CircuitExt: Circuit {
         P: Send + Sync + 'static,
         E: Send + Sync + 'static;
     
+    /// Returns a named conduit whose channel emissions are kept in this
+    /// circuit's recent emissions, so they show up in `dump_recent`
+    async fn recording_conduit_named<P, E>(
+        &self,
+        name: Name,
+        composer: Arc<dyn Composer<P, E>>,
+    ) -> SubstratesResult<Arc<dyn Conduit<P, E>>>
+    where
+        P: Send + Sync + 'static,
+        E: std::fmt::Display + Send + Sync + 'static;
+    
     /// Returns a conduit with sequencer
     async fn conduit_with_sequencer<P, E>(
         &self,
@@ -71,6 +85,29 @@ pub trait CircuitExt: Circuit {
     where
         P: Send + Sync + 'static,
         E: Send + Sync + 'static;
+    
+    /// Loads emissions written by `dump_recent` and replays them into a subscriber
+    async fn load_and_replay<E>(
+        &self,
+        path: &std::path::Path,
+        subscriber: &mut dyn Subscriber<Emission = E>,
+    ) -> SubstratesResult<usize>
+    where
+        E: FromStr + Send + Sync + 'static,
+        E::Err: std::fmt::Display;
+    
+    /// Queues an emission for delivery to a route on this circuit's queue
+    ///
+    /// Queued emissions are delivered in order, and are drained by
+    /// `shutdown_graceful`.
+    async fn post_emission<E>(
+        &self,
+        route: Arc<dyn Route<E>>,
+        subject: Subject,
+        emission: E,
+    ) -> SubstratesResult<()>
+    where
+        E: Send + Sync + 'static;
 }
 
 // Default implementations for all Circuit types
@@ -104,6 +141,25 @@ impl<T: Circuit + Sync + ?Sized> CircuitExt for T {
         Ok(Arc::new(conduit))
     }
     
+    async fn recording_conduit_named<P, E>(
+        &self,
+        name: Name,
+        composer: Arc<dyn Composer<P, E>>,
+    ) -> SubstratesResult<Arc<dyn Conduit<P, E>>>
+    where
+        P: Send + Sync + 'static,
+        E: std::fmt::Display + Send + Sync + 'static,
+    {
+        use crate::circuit::BasicConduit;
+        
+        let subject = Subject::new(name, crate::types::SubjectType::Channel);
+        let mut conduit = BasicConduit::new(subject, composer, self.subject().clone());
+        if let Some(recent) = self.recent_emissions() {
+            conduit = conduit.with_recent(&recent);
+        }
+        Ok(Arc::new(conduit))
+    }
+    
     async fn conduit_with_sequencer<P, E>(
         &self,
         name: Name,
@@ -167,6 +223,57 @@ impl<T: Circuit + Sync + ?Sized> CircuitExt for T {
         container.set_sequencer(sequencer);
         Ok(Arc::new(container))
     }
+    
+    async fn load_and_replay<E>(
+        &self,
+        path: &std::path::Path,
+        subscriber: &mut dyn Subscriber<Emission = E>,
+    ) -> SubstratesResult<usize>
+    where
+        E: FromStr + Send + Sync + 'static,
+        E::Err: std::fmt::Display,
+    {
+        let emissions = crate::recent::load_dump(path).await?;
+        crate::recent::replay(&emissions, subscriber).await
+    }
+    
+    async fn post_emission<E>(
+        &self,
+        route: Arc<dyn Route<E>>,
+        subject: Subject,
+        emission: E,
+    ) -> SubstratesResult<()>
+    where
+        E: Send + Sync + 'static,
+    {
+        let script = EmissionScript {
+            route,
+            subject,
+            emission: parking_lot::Mutex::new(Some(emission)),
+        };
+        self.queue().post(Arc::new(script)).await
+    }
+}
+
+/// Script delivering a single queued emission to its route
+struct EmissionScript<E> {
+    route: Arc<dyn Route<E>>,
+    subject: Subject,
+    emission: parking_lot::Mutex<Option<E>>,
+}
+
+#[async_trait]
+impl<E> Script for EmissionScript<E>
+where
+    E: Send + Sync + 'static,
+{
+    async fn exec(&self, _current: &dyn Current) -> SubstratesResult<()> {
+        let emission = self.emission.lock().take();
+        match emission {
+            Some(emission) => self.route.dispatch(&self.subject, emission).await,
+            None => Ok(()),
+        }
+    }
 }
 
 /// Extension trait providing generic methods for Current
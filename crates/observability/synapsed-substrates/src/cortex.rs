@@ -4,6 +4,7 @@
 //! This is a refactored version that separates generic methods into an extension trait
 
 use crate::circuit::{BasicCircuit, Circuit, BasicScope};
+use crate::routing::RouteTable;
 use crate::types::{Name, Slot, State, SubstratesResult};
 use crate::async_trait;
 use std::collections::HashMap;
@@ -84,17 +85,27 @@ pub trait Cortex: Send + Sync {
     
     /// Creates a state with a single State slot
     fn state_with_state(&self, name: Name, value: State) -> State;
+    
+    /// Returns the default routes from subject types to conduits, if this cortex keeps them
+    ///
+    /// Use `CortexExt::register_route` and `CortexExt::emit` to register
+    /// and dispatch through these routes.
+    fn routes(&self) -> Option<&RouteTable> {
+        None
+    }
 }
 
 /// Default implementation of Cortex
 pub struct DefaultCortex {
     circuits: parking_lot::RwLock<HashMap<Name, Arc<dyn Circuit>>>,
+    routes: RouteTable,
 }
 
 impl std::fmt::Debug for DefaultCortex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DefaultCortex")
             .field("circuits_count", &self.circuits.read().len())
+            .field("routes_count", &self.routes.len())
             .finish()
     }
 }
@@ -104,6 +115,7 @@ impl DefaultCortex {
     pub fn new() -> Self {
         Self {
             circuits: parking_lot::RwLock::new(HashMap::new()),
+            routes: RouteTable::new(),
         }
     }
 }
@@ -218,6 +230,10 @@ impl Cortex for DefaultCortex {
     fn state_with_state(&self, name: Name, value: State) -> State {
         State::with_slot(name, value)
     }
+    
+    fn routes(&self) -> Option<&RouteTable> {
+        Some(&self.routes)
+    }
 }
 
 /// Factory function to create a new Cortex
@@ -4,10 +4,12 @@
 use crate::circuit::{Pool, Sink};
 use crate::cortex::Cortex;
 use crate::pipe::Capture;
+use crate::routing::Route;
 use crate::source::Source;
 use crate::subject::Context;
-use crate::types::{Name, Slot, SubstratesResult};
+use crate::types::{Name, Slot, SubjectType, SubstratesError, SubstratesResult};
 use crate::Subject;
+use std::future::Future;
 use std::sync::Arc;
 
 /// Extension trait providing generic methods for Cortex
@@ -71,6 +73,40 @@ pub trait CortexExt: crate::Cortex {
         todo!("Implement sink from source")
     }
     
+    /// Routes emissions of type `E` from subjects of `subject_type` to `conduit`
+    ///
+    /// Fails with `InvalidOperation` if this cortex keeps no routes.
+    fn register_route<E>(&self, subject_type: SubjectType, conduit: Arc<dyn Route<E>>) -> SubstratesResult<()>
+    where
+        E: 'static,
+    {
+        let routes = self.routes().ok_or_else(no_routes)?;
+        routes.register(subject_type, conduit);
+        Ok(())
+    }
+    
+    /// Emits a value to the conduit routed for the subject's type
+    ///
+    /// Fails with `NotFound` if no route is registered for the subject type
+    /// and emission type, and with `InvalidOperation` if this cortex keeps no
+    /// routes.
+    fn emit<'a, E>(
+        &'a self,
+        subject: &'a Subject,
+        emission: E,
+    ) -> impl Future<Output = SubstratesResult<()>> + Send + 'a
+    where
+        E: Send + 'static,
+    {
+        let routes = self.routes();
+        async move {
+            match routes {
+                Some(routes) => routes.dispatch(subject, emission).await,
+                None => Err(no_routes()),
+            }
+        }
+    }
+    
     /// Creates a slot with a generic value
     fn slot<T>(&self, name: Name, value: T) -> Slot<T>
     where
@@ -107,6 +143,10 @@ pub trait CortexExt: crate::Cortex {
     }
 }
 
+fn no_routes() -> SubstratesError {
+    SubstratesError::InvalidOperation("Cortex does not keep routes".to_string())
+}
+
 // Automatically implement CortexExt for all types that implement Cortex
 impl<T: Cortex + ?Sized> CortexExt for T {}
 
@@ -13,7 +13,7 @@
 //! - **Pipe**: Abstraction for passing typed values along a pipeline
 //! - **Subscriber**: Dynamically subscribes to a source and registers pipes with subjects
 //! - **Subject**: Hierarchical reference system for observing and addressing entities
-//! - **Cortex**: Bootstrap entry point into the Substrates runtime
+//! - **Cortex**: Bootstrap entry point into the Substrates runtime, routing emissions by subject type
 
 pub mod channel;
 pub mod circuit;
@@ -24,6 +24,8 @@ pub mod percept;
 pub mod pipe;
 pub mod path_ext;
 pub mod queue;
+pub mod recent;
+pub mod routing;
 pub mod scope_ext;
 pub mod sink;
 pub mod source;
@@ -36,8 +38,8 @@ pub mod types;
 pub use channel::{BasicChannel, BasicConduit};
 pub use circuit::{
     BasicCircuit, BasicCurrent, BasicQueue, BasicScope, Channel, Circuit, Clock, 
-    ClockCycle, Closure, Conduit, Container, Current, Inlet, 
-    Pool, Queue, Scope, Script, Sink, Tap,
+    ClockCycle, Closure, Conduit, Container, Current, Drain, Inlet, 
+    Pool, Queue, Scope, Script, ShutdownReport, Sink, Tap,
 };
 pub use circuit_ext::{CircuitExt, ClosureExt, CurrentExt};
 pub use cortex::{create_cortex, Cortex, DefaultCortex};
@@ -47,9 +49,11 @@ pub use percept::{
     Composer, IdentityComposer, MappingComposer, PipeComposer, 
     TypedComposer, TypedPercept,
 };
+pub use recent::{RecentEmission, RecentEmissions};
+pub use routing::{Route, RouteTable};
 pub use scope_ext::ScopeExt;
 pub use pipe::{
-    Assembly, Capture, EmptyPipe, FunctionPipe, Path, Pipe, Sequencer, Sift,
+    Assembly, Capture, EmptyPipe, FunctionPipe, MapPipe, Path, Pipe, Sequencer, Sift,
 };
 pub use queue::{
     AdvancedCurrent, CompositeScript, ManagedQueue, Priority, QueueStats,
@@ -1,6 +1,6 @@
 //! Pipe and Path abstractions - direct port of Java Substrates Pipe and Path interfaces
 
-use crate::types::SubstratesResult;
+use crate::types::{SubstratesError, SubstratesResult};
 use crate::{async_trait, Subject};
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -89,6 +89,62 @@ where
     }
 }
 
+/// Pipe that transforms each emission before passing it downstream
+///
+/// Map pipes compose: the downstream pipe may itself be a `MapPipe`, so a
+/// chain of transformations ends in a pipe of the final type.
+pub struct MapPipe<A, B, F>
+where
+    F: Fn(A) -> B + Send + Sync,
+{
+    func: F,
+    downstream: Arc<dyn Pipe<B>>,
+    _phantom: std::marker::PhantomData<fn(A)>,
+}
+
+impl<A, B, F> MapPipe<A, B, F>
+where
+    F: Fn(A) -> B + Send + Sync,
+{
+    /// Create a pipe applying `func` to each emission before emitting it to `downstream`
+    ///
+    /// The map pipe must be the only holder of `downstream`.
+    pub fn new(func: F, downstream: Arc<dyn Pipe<B>>) -> Self {
+        Self {
+            func,
+            downstream,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<A, B, F> Debug for MapPipe<A, B, F>
+where
+    F: Fn(A) -> B + Send + Sync,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapPipe")
+            .field("downstream", &self.downstream)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<A, B, F> Pipe<A> for MapPipe<A, B, F>
+where
+    A: Send + Sync,
+    B: Send + Sync,
+    F: Fn(A) -> B + Send + Sync,
+{
+    async fn emit(&mut self, emission: A) -> SubstratesResult<()> {
+        let value = (self.func)(emission);
+        let downstream = Arc::get_mut(&mut self.downstream).ok_or_else(|| {
+            SubstratesError::InvalidOperation("Map pipe downstream is shared".to_string())
+        })?;
+        downstream.emit(value).await
+    }
+}
+
 // TODO: Implement AsyncFunctionPipe properly
 // The current implementation has issues with Sync constraints on the Future type
 // /// Async function-based pipe implementation
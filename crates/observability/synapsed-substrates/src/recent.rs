@@ -0,0 +1,302 @@
+//! Always-on ring buffer of a circuit's most recent emissions
+//!
+//! Every circuit keeps its last N emissions so they can be dumped to disk for
+//! post-mortem analysis without turning on full recording ahead of time. Dumps
+//! use a compact line format: one emission per line with its timestamp,
+//! subject type, subject name and rendered value separated by tabs.
+
+use crate::pipe::Pipe;
+use crate::source::BasicRegistrar;
+use crate::subject::{Subject, Subscriber};
+use crate::types::{Name, SubjectType, SubstratesError, SubstratesResult};
+use chrono::{DateTime, SecondsFormat, Utc};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Number of emissions a circuit keeps by default
+pub const DEFAULT_RECENT_CAPACITY: usize = 1024;
+
+/// First line of every dump file
+const DUMP_HEADER: &str = "# substrates recent-emissions v1";
+
+/// Pipes registered by a replay subscriber, per subject
+type SubjectPipes<E> = HashMap<(Name, SubjectType), Vec<Arc<dyn Pipe<E>>>>;
+
+/// Records each emission of one channel into a ring buffer
+pub(crate) type Recorder<E> = Arc<dyn Fn(&E) + Send + Sync>;
+
+/// Creates the recorder for a channel from the channel's subject
+pub(crate) type RecorderFactory<E> = Arc<dyn Fn(Subject) -> Recorder<E> + Send + Sync>;
+
+/// A single emission captured by the ring buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentEmission {
+    /// When the emission was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Name of the emitting subject
+    pub subject: Name,
+    /// Type of the emitting subject
+    pub subject_type: SubjectType,
+    /// Emitted value rendered with `Display`
+    pub emission: String,
+}
+
+impl RecentEmission {
+    /// Encode as one dump line
+    fn encode(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.subject_type,
+            escape(&self.subject.to_path()),
+            escape(&self.emission),
+        )
+    }
+
+    /// Decode one dump line
+    fn decode(line: &str) -> SubstratesResult<Self> {
+        let fields: Vec<&str> = line.splitn(4, '\t').collect();
+        let [timestamp, subject_type, subject, emission] = fields[..] else {
+            return Err(SubstratesError::InvalidOperation(format!("Malformed dump line: {line}")));
+        };
+
+        let timestamp = DateTime::parse_from_rfc3339(timestamp)
+            .map_err(|e| SubstratesError::InvalidOperation(format!("Invalid timestamp {timestamp}: {e}")))?
+            .with_timezone(&Utc);
+
+        Ok(Self {
+            timestamp,
+            subject: Name::parse(&unescape(subject)),
+            subject_type: parse_subject_type(subject_type)?,
+            emission: unescape(emission),
+        })
+    }
+}
+
+/// Bounded buffer holding a circuit's most recent emissions, oldest first
+#[derive(Debug)]
+pub struct RecentEmissions {
+    capacity: usize,
+    entries: Mutex<VecDeque<RecentEmission>>,
+}
+
+impl RecentEmissions {
+    /// Create a buffer keeping at most `capacity` emissions
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Record an emission, evicting the oldest one when full
+    pub fn record<E: fmt::Display + ?Sized>(&self, subject: &Subject, emission: &E) {
+        let entry = RecentEmission {
+            timestamp: Utc::now(),
+            subject: subject.name().clone(),
+            subject_type: *subject.subject_type(),
+            emission: emission.to_string(),
+        };
+
+        let mut entries = self.entries.lock();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Recorder attributing emissions to `subject`
+    pub(crate) fn recorder<E: fmt::Display + 'static>(self: &Arc<Self>, subject: Subject) -> Recorder<E> {
+        let recent = Arc::clone(self);
+        Arc::new(move |emission: &E| recent.record(&subject, emission))
+    }
+
+    /// Recorder factory for the channels of a conduit or container
+    pub(crate) fn recorder_factory<E: fmt::Display + 'static>(self: &Arc<Self>) -> RecorderFactory<E> {
+        let recent = Arc::clone(self);
+        Arc::new(move |subject| recent.recorder(subject))
+    }
+
+    /// Copy of the buffered emissions, oldest first
+    pub fn snapshot(&self) -> Vec<RecentEmission> {
+        self.entries.lock().iter().cloned().collect()
+    }
+
+    /// Maximum number of emissions kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of emissions currently buffered
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Whether no emissions have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// Write the buffered emissions to `path`, returning how many were written
+    pub async fn dump(&self, path: &Path) -> SubstratesResult<usize> {
+        let entries = self.snapshot();
+        let mut contents = String::from(DUMP_HEADER);
+        contents.push('\n');
+        for entry in &entries {
+            contents.push_str(&entry.encode());
+            contents.push('\n');
+        }
+
+        tokio::fs::write(path, contents).await.map_err(|e| {
+            SubstratesError::Internal(format!("Failed to write dump {}: {}", path.display(), e))
+        })?;
+        Ok(entries.len())
+    }
+}
+
+impl Default for RecentEmissions {
+    fn default() -> Self {
+        Self::new(DEFAULT_RECENT_CAPACITY)
+    }
+}
+
+/// Read the emissions stored in a dump file, oldest first
+pub async fn load_dump(path: &Path) -> SubstratesResult<Vec<RecentEmission>> {
+    let contents = tokio::fs::read_to_string(path).await.map_err(|e| {
+        SubstratesError::Internal(format!("Failed to read dump {}: {}", path.display(), e))
+    })?;
+
+    let mut lines = contents.lines();
+    if lines.next() != Some(DUMP_HEADER) {
+        return Err(SubstratesError::InvalidOperation(format!(
+            "{} is not a recent-emissions dump",
+            path.display()
+        )));
+    }
+
+    lines
+        .filter(|line| !line.is_empty())
+        .map(RecentEmission::decode)
+        .collect()
+}
+
+/// Replay dumped emissions into a subscriber
+///
+/// The subscriber is offered each distinct subject once, in the order subjects
+/// first appear, and every emission is then sent to the pipes it registered for
+/// that subject. Returns the number of emissions replayed.
+pub async fn replay<E>(
+    emissions: &[RecentEmission],
+    subscriber: &mut dyn Subscriber<Emission = E>,
+) -> SubstratesResult<usize>
+where
+    E: FromStr + Send + Sync + 'static,
+    E::Err: fmt::Display,
+{
+    let mut pipes: SubjectPipes<E> = HashMap::new();
+
+    for entry in emissions {
+        let key = (entry.subject.clone(), entry.subject_type);
+        let subject_pipes = pipes.entry(key).or_insert_with(|| {
+            let subject = Subject::new(entry.subject.clone(), entry.subject_type);
+            let mut registrar = BasicRegistrar::<E>::new();
+            subscriber.accept(&subject, &mut registrar);
+            registrar.pipes().to_vec()
+        });
+
+        for pipe in subject_pipes.iter_mut() {
+            let emission = entry.emission.parse::<E>().map_err(|e| {
+                SubstratesError::InvalidOperation(format!("Cannot parse emission {:?}: {}", entry.emission, e))
+            })?;
+            if let Some(pipe_mut) = Arc::get_mut(pipe) {
+                pipe_mut.emit(emission).await?;
+            }
+        }
+    }
+
+    Ok(emissions.len())
+}
+
+fn parse_subject_type(s: &str) -> SubstratesResult<SubjectType> {
+    let subject_type = match s {
+        "Channel" => SubjectType::Channel,
+        "Circuit" => SubjectType::Circuit,
+        "Clock" => SubjectType::Clock,
+        "Conduit" => SubjectType::Conduit,
+        "Container" => SubjectType::Container,
+        "Current" => SubjectType::Current,
+        "Queue" => SubjectType::Queue,
+        "Source" => SubjectType::Source,
+        "Scope" => SubjectType::Scope,
+        "Script" => SubjectType::Script,
+        "Sink" => SubjectType::Sink,
+        "Subscriber" => SubjectType::Subscriber,
+        "Subscription" => SubjectType::Subscription,
+        other => {
+            return Err(SubstratesError::InvalidOperation(format!("Unknown subject type: {other}")));
+        }
+    };
+    Ok(subject_type)
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_round_trip_escapes_separators() {
+        let entry = RecentEmission {
+            timestamp: Utc::now(),
+            subject: Name::parse("svc.api"),
+            subject_type: SubjectType::Channel,
+            emission: "a\tb\nc\\d".to_string(),
+        };
+
+        let decoded = RecentEmission::decode(&entry.encode()).unwrap();
+        assert_eq!(decoded.subject, entry.subject);
+        assert_eq!(decoded.subject_type, entry.subject_type);
+        assert_eq!(decoded.emission, entry.emission);
+        assert_eq!(
+            decoded.timestamp.timestamp_micros(),
+            entry.timestamp.timestamp_micros()
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Routing of emissions to default conduits by subject type
+//!
+//! A [`RouteTable`] maps a [`SubjectType`] to the conduit that processes
+//! emissions of subjects of that type, so emitters don't have to be wired to
+//! their pipeline by hand. Routes are keyed by emission type as well, so the
+//! same subject type can be routed to different conduits for different
+//! emissions.
+
+use crate::subject::Subject;
+use crate::types::{SubjectType, SubstratesError, SubstratesResult};
+use crate::async_trait;
+use parking_lot::RwLock;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Destination that emissions can be routed to
+#[async_trait]
+pub trait Route<E>: Send + Sync {
+    /// Deliver an emission from a subject to this destination's subscribers
+    async fn dispatch(&self, subject: &Subject, emission: E) -> SubstratesResult<()>;
+}
+
+/// Registry of default routes by subject type and emission type
+#[derive(Default)]
+pub struct RouteTable {
+    /// Values are `Arc<dyn Route<E>>` for the emission type in the key
+    routes: RwLock<HashMap<(SubjectType, TypeId), Arc<dyn Any + Send + Sync>>>,
+}
+
+impl std::fmt::Debug for RouteTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteTable")
+            .field("routes_count", &self.len())
+            .finish()
+    }
+}
+
+impl RouteTable {
+    /// Create an empty route table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route emissions of type `E` from subjects of `subject_type` to `route`
+    ///
+    /// Replaces any route previously registered for the same subject type and
+    /// emission type.
+    pub fn register<E: 'static>(&self, subject_type: SubjectType, route: Arc<dyn Route<E>>) {
+        self.routes
+            .write()
+            .insert((subject_type, TypeId::of::<E>()), Arc::new(route));
+    }
+
+    /// Remove the route for emissions of type `E` from subjects of `subject_type`
+    pub fn unregister<E: 'static>(&self, subject_type: SubjectType) -> bool {
+        self.routes
+            .write()
+            .remove(&(subject_type, TypeId::of::<E>()))
+            .is_some()
+    }
+
+    /// The route for emissions of type `E` from subjects of `subject_type`
+    pub fn get<E: 'static>(&self, subject_type: SubjectType) -> Option<Arc<dyn Route<E>>> {
+        self.routes
+            .read()
+            .get(&(subject_type, TypeId::of::<E>()))
+            .and_then(|route| route.downcast_ref::<Arc<dyn Route<E>>>())
+            .cloned()
+    }
+
+    /// Number of registered routes
+    pub fn len(&self) -> usize {
+        self.routes.read().len()
+    }
+
+    /// Whether no routes are registered
+    pub fn is_empty(&self) -> bool {
+        self.routes.read().is_empty()
+    }
+
+    /// Dispatch an emission to the route registered for the subject's type
+    pub async fn dispatch<E: 'static>(&self, subject: &Subject, emission: E) -> SubstratesResult<()> {
+        let subject_type = *subject.subject_type();
+        let route = self.get::<E>(subject_type).ok_or_else(|| {
+            SubstratesError::NotFound(format!(
+                "No route for {} emissions from {} subjects",
+                std::any::type_name::<E>(),
+                subject_type
+            ))
+        })?;
+        route.dispatch(subject, emission).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cortex::{Cortex, DefaultCortex};
+    use crate::cortex_ext::CortexExt;
+    use crate::pipe::FunctionPipe;
+    use crate::source::FunctionSubscriber;
+    use crate::subscription::ConduitSource;
+    use crate::types::Name;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_emission_routed_by_subject_type() {
+        let cortex = DefaultCortex::new();
+        let conduit = Arc::new(ConduitSource::<(), String>::new(Name::from_part("queues")));
+
+        let received = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let subscriber = Arc::new(FunctionSubscriber::new(move |_subject, registrar| {
+            let sink = sink.clone();
+            registrar.register(Arc::new(FunctionPipe::new(move |value: String| {
+                sink.lock().push(value);
+                Ok(())
+            })));
+            Ok(())
+        }));
+        let _subscription = conduit.subscribe(subscriber).await.unwrap();
+
+        cortex.register_route::<String>(SubjectType::Queue, conduit.clone()).unwrap();
+        assert_eq!(cortex.routes().unwrap().len(), 1);
+
+        let queue = Subject::new(Name::from_part("work-queue"), SubjectType::Queue);
+        cortex.emit(&queue, "enqueued".to_string()).await.unwrap();
+        cortex.emit(&queue, "drained".to_string()).await.unwrap();
+
+        // Subjects of other types, or other emission types, have no route
+        let clock = Subject::new(Name::from_part("clock"), SubjectType::Clock);
+        assert!(cortex.emit(&clock, "tick".to_string()).await.is_err());
+        assert!(cortex.emit(&queue, 42u32).await.is_err());
+
+        // Emitting through a route returns once subscribers have the emission
+        assert_eq!(*received.lock(), vec!["enqueued".to_string(), "drained".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_register_route_replaces_previous() {
+        let table = RouteTable::new();
+
+        struct Counting(Arc<AtomicUsize>);
+
+        #[async_trait]
+        impl Route<u32> for Counting {
+            async fn dispatch(&self, _subject: &Subject, _emission: u32) -> SubstratesResult<()> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+
+        let first = Arc::new(AtomicUsize::new(0));
+        let second = Arc::new(AtomicUsize::new(0));
+        table.register::<u32>(SubjectType::Sink, Arc::new(Counting(first.clone())));
+        table.register::<u32>(SubjectType::Sink, Arc::new(Counting(second.clone())));
+
+        let sink = Subject::new(Name::from_part("sink"), SubjectType::Sink);
+        table.dispatch(&sink, 1u32).await.unwrap();
+
+        assert_eq!(table.len(), 1);
+        assert_eq!(first.load(Ordering::SeqCst), 0);
+        assert_eq!(second.load(Ordering::SeqCst), 1);
+        assert!(table.unregister::<u32>(SubjectType::Sink));
+        assert!(table.dispatch(&sink, 1u32).await.is_err());
+    }
+}
@@ -6,13 +6,17 @@
 //! - When a Subject emits, the emission flows through registered Pipes
 
 use crate::channel::{BasicChannel, BasicConduit};
-use crate::circuit::Channel;
+use crate::circuit::{Channel, Drain};
 use crate::pipe::Pipe;
+use crate::routing::Route;
 use crate::subject::{Registrar, Resource, Subscriber, Subscription, Substrate, Subject};
 use crate::types::{Name, SubjectType, SubstratesResult, SubstratesError};
+use crate::async_trait;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tokio::sync::mpsc;
 
 /// A complete Source implementation that manages Subject->Pipe routing
@@ -25,11 +29,17 @@ pub struct ManagedSource<E> {
     /// Channel for internal events
     event_sender: mpsc::UnboundedSender<SourceEvent<E>>,
     event_receiver: Arc<RwLock<Option<mpsc::UnboundedReceiver<SourceEvent<E>>>>>,
+    /// Emissions queued for the event processor but not yet delivered
+    pending: Arc<AtomicUsize>,
+    /// Cleared once the source stops accepting emissions
+    accepting: AtomicBool,
+    /// Set when queued emissions should be skipped
+    discard: Arc<AtomicBool>,
 }
 
 enum SourceEvent<E> {
     SubjectEmitted { subject: Subject, emission: E },
-    SubscriberAdded { subscriber: Arc<dyn Subscriber<Emission = E>> },
+    SubscriberAdded,
     SubscriptionClosed { subscription_id: String },
 }
 
@@ -46,6 +56,9 @@ where
             subscriptions: Arc::new(RwLock::new(Vec::new())),
             event_sender,
             event_receiver: Arc::new(RwLock::new(Some(event_receiver))),
+            pending: Arc::new(AtomicUsize::new(0)),
+            accepting: AtomicBool::new(true),
+            discard: Arc::new(AtomicBool::new(false)),
         };
         
         // Start event processing task
@@ -53,28 +66,77 @@ where
         source
     }
     
-    /// Register a new subject that can emit through this source
+    /// Register a subject that can emit through this source
+    ///
+    /// Existing subscribers are offered a new subject so they can register
+    /// pipes for it. Registering a subject again returns its existing channel.
     pub fn register_subject(&self, subject: Subject) -> Arc<BasicChannel<E>> {
+        // Held while offering, so concurrent registrations offer the subject once
         let mut channels = self.channels.write();
-        let channel = Arc::new(BasicChannel::new(Name::from_part(&subject.id().to_string())));
-        channels.insert(subject.id().to_string(), channel.clone());
+        let channel = match channels.entry(subject.id().to_string()) {
+            Entry::Occupied(entry) => return entry.get().clone(),
+            Entry::Vacant(entry) => entry
+                .insert(Arc::new(BasicChannel::new(Name::from_part(&subject.id().to_string()))))
+                .clone(),
+        };
+        
+        let subscriptions = self.subscriptions.read().clone();
+        for subscription in subscriptions.iter().filter(|s| s.is_active()) {
+            subscription.register_with_subject(&subject, channel.clone());
+        }
         channel
     }
     
+    /// Whether a subject has been registered with this source
+    pub fn has_subject(&self, subject: &Subject) -> bool {
+        self.channels.read().contains_key(&subject.id().to_string())
+    }
+    
     /// Emit a value from a specific subject
     pub async fn emit(&self, subject: &Subject, emission: E) -> SubstratesResult<()> {
+        if !self.accepting.load(Ordering::SeqCst) {
+            return Err(SubstratesError::Closed("Source stopped accepting emissions".to_string()));
+        }
+        
         // Send event to processor
+        self.pending.fetch_add(1, Ordering::SeqCst);
         self.event_sender
             .send(SourceEvent::SubjectEmitted {
                 subject: subject.clone(),
                 emission,
             })
-            .map_err(|_| SubstratesError::Closed("Source event channel closed".to_string()))?;
+            .map_err(|_| {
+                self.pending.fetch_sub(1, Ordering::SeqCst);
+                SubstratesError::Closed("Source event channel closed".to_string())
+            })?;
         Ok(())
     }
     
+    /// Deliver an emission to active subscriptions before returning
+    ///
+    /// Unlike [`emit`](Self::emit), this bypasses the source's event queue, so
+    /// callers that need ordering must not mix the two for the same subject.
+    pub async fn deliver(&self, subject: &Subject, emission: E) {
+        Self::notify_subscriptions(&self.subscriptions, subject, emission).await;
+    }
+    
+    async fn notify_subscriptions(
+        subscriptions: &RwLock<Vec<Arc<ManagedSubscription<E>>>>,
+        subject: &Subject,
+        emission: E,
+    ) {
+        let subs = subscriptions.read().clone();
+        for subscription in subs {
+            if subscription.is_active() {
+                subscription.notify_emission(subject, emission.clone()).await;
+            }
+        }
+    }
+    
     fn start_event_processor(&self) {
         let subscriptions = self.subscriptions.clone();
+        let pending = self.pending.clone();
+        let discard = self.discard.clone();
         let mut receiver = self.event_receiver.write().take()
             .expect("Event receiver already taken");
         
@@ -83,14 +145,12 @@ where
                 match event {
                     SourceEvent::SubjectEmitted { subject, emission } => {
                         // Notify all active subscriptions
-                        let subs = subscriptions.read().clone();
-                        for subscription in subs {
-                            if subscription.is_active() {
-                                subscription.notify_emission(&subject, emission.clone()).await;
-                            }
+                        if !discard.load(Ordering::SeqCst) {
+                            Self::notify_subscriptions(&subscriptions, &subject, emission).await;
                         }
+                        pending.fetch_sub(1, Ordering::SeqCst);
                     }
-                    SourceEvent::SubscriberAdded { subscriber: _ } => {
+                    SourceEvent::SubscriberAdded => {
                         // Handle new subscriber
                     }
                     SourceEvent::SubscriptionClosed { subscription_id: _ } => {
@@ -108,7 +168,7 @@ where
     ) -> SubstratesResult<Arc<ManagedSubscription<E>>> {
         let subscription = Arc::new(ManagedSubscription::new(
             self.subject.clone(),
-            subscriber.clone(),
+            subscriber,
             self.channels.clone(),
         ));
         
@@ -128,22 +188,56 @@ where
         self.subscriptions.write().push(subscription.clone());
         
         // Send event
-        let _ = self.event_sender.send(SourceEvent::SubscriberAdded { subscriber });
+        let _ = self.event_sender.send(SourceEvent::SubscriberAdded);
         
         Ok(subscription)
     }
 }
 
+#[async_trait]
+impl<E> Route<E> for ManagedSource<E>
+where
+    E: Send + Sync + Clone + 'static,
+{
+    /// Registers the subject on first use, then delivers to its subscribers
+    async fn dispatch(&self, subject: &Subject, emission: E) -> SubstratesResult<()> {
+        if !self.has_subject(subject) {
+            self.register_subject(subject.clone());
+        }
+        self.deliver(subject, emission).await;
+        Ok(())
+    }
+}
+
 impl<E> Substrate for ManagedSource<E> {
     fn subject(&self) -> &Subject {
         &self.subject
     }
 }
 
+#[async_trait]
+impl<E> Drain for ManagedSource<E>
+where
+    E: Send + Sync,
+{
+    fn stop_accepting(&self) {
+        self.accepting.store(false, Ordering::SeqCst);
+    }
+    
+    fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+    
+    fn discard_pending(&self) {
+        self.discard.store(true, Ordering::SeqCst);
+    }
+}
+
 /// A managed subscription that handles Subject->Pipe routing
 pub struct ManagedSubscription<E> {
     subject: Subject,
-    subscriber: Arc<dyn Subscriber<Emission = E>>,
+    /// Subscribers can only accept subjects while this is the sole reference
+    subscriber: Mutex<Arc<dyn Subscriber<Emission = E>>>,
     /// Pipes registered for each subject
     pipes: Arc<RwLock<HashMap<String, Vec<Arc<dyn Pipe<E>>>>>>,
     active: Arc<RwLock<bool>>,
@@ -161,7 +255,7 @@ where
     ) -> Self {
         Self {
             subject,
-            subscriber,
+            subscriber: Mutex::new(subscriber),
             pipes: Arc::new(RwLock::new(HashMap::new())),
             active: Arc::new(RwLock::new(true)),
             channels,
@@ -177,25 +271,31 @@ where
         let mut registrar = PipeRegistrar::new(subject.clone(), self.pipes.clone());
         
         // Let the subscriber register pipes
-        let mut subscriber = self.subscriber.clone();
-        Arc::get_mut(&mut subscriber).map(|s| s.accept(subject, &mut registrar));
+        let mut subscriber = self.subscriber.lock();
+        if let Some(subscriber) = Arc::get_mut(&mut subscriber) {
+            subscriber.accept(subject, &mut registrar);
+        }
     }
     
     pub async fn notify_emission(&self, subject: &Subject, emission: E) {
-        // Clone the pipes to release the lock before await
-        let pipes_to_notify = {
-            let pipes = self.pipes.read();
-            pipes.get(&subject.id().to_string()).cloned()
+        // Take the pipes out to release the lock before await; emissions are
+        // delivered sequentially by the source, and pipes need unique access
+        let key = subject.id().to_string();
+        let Some(mut subject_pipes) = self.pipes.write().remove(&key) else {
+            return;
         };
         
-        if let Some(subject_pipes) = pipes_to_notify {
-            for pipe in subject_pipes {
-                let mut pipe = pipe.clone();
-                if let Some(pipe_mut) = Arc::get_mut(&mut pipe) {
-                    let _ = pipe_mut.emit(emission.clone()).await;
-                }
+        for pipe in subject_pipes.iter_mut() {
+            if let Some(pipe_mut) = Arc::get_mut(pipe) {
+                let _ = pipe_mut.emit(emission.clone()).await;
             }
         }
+        
+        // Keep pipes registered while the emission was being delivered
+        let mut pipes = self.pipes.write();
+        let registered = pipes.entry(key).or_default();
+        subject_pipes.append(registered);
+        *registered = subject_pipes;
     }
 }
 
@@ -291,12 +391,42 @@ where
     }
 }
 
+#[async_trait]
+impl<P, E> Route<E> for ConduitSource<P, E>
+where
+    P: Send + Sync,
+    E: Send + Sync + Clone + 'static,
+{
+    async fn dispatch(&self, subject: &Subject, emission: E) -> SubstratesResult<()> {
+        self.source.dispatch(subject, emission).await
+    }
+}
+
 impl<P, E> Substrate for ConduitSource<P, E> {
     fn subject(&self) -> &Subject {
         self.source.subject()
     }
 }
 
+#[async_trait]
+impl<P, E> Drain for ConduitSource<P, E>
+where
+    P: Send + Sync,
+    E: Send + Sync,
+{
+    fn stop_accepting(&self) {
+        self.source.stop_accepting();
+    }
+    
+    fn pending(&self) -> usize {
+        self.source.pending()
+    }
+    
+    fn discard_pending(&self) {
+        self.source.discard_pending();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -374,4 +504,34 @@ mod tests {
         
         assert_eq!(counter.load(Ordering::SeqCst), 2);
     }
-}
\ No newline at end of file
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_first_dispatches_offer_subject_once() {
+        let source = Arc::new(ManagedSource::<u32>::new(Name::from_part("race-source")));
+        
+        let offers = Arc::new(AtomicUsize::new(0));
+        let offers_clone = offers.clone();
+        let subscriber = Arc::new(crate::source::FunctionSubscriber::new(
+            move |_subject, registrar| {
+                offers_clone.fetch_add(1, Ordering::SeqCst);
+                registrar.register(Arc::new(FunctionPipe::new(|_value: u32| Ok(()))));
+                Ok(())
+            }
+        ));
+        let _subscription = source.subscribe(subscriber).await.unwrap();
+        
+        let subject = Subject::new(Name::from_part("racy-subject"), SubjectType::Channel);
+        let dispatches: Vec<_> = (0..8)
+            .map(|i| {
+                let (source, subject) = (source.clone(), subject.clone());
+                tokio::spawn(async move { source.dispatch(&subject, i).await })
+            })
+            .collect();
+        for dispatch in dispatches {
+            dispatch.await.unwrap().unwrap();
+        }
+        
+        assert_eq!(offers.load(Ordering::SeqCst), 1);
+        assert!(source.has_subject(&subject));
+    }
+}
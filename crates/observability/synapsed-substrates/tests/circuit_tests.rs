@@ -134,4 +134,153 @@ async fn test_pipe_composer() {
     let _pipe = Arc::new(TestPipe);
     let _composer = PipeComposer::<String>::new();
     // PipeComposer exists and can be constructed
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_dump_recent_keeps_last_n_and_replays() {
+    let circuit = BasicCircuit::with_recent_capacity(Name::from_part("diag-circuit"), 5);
+    let subjects = [
+        Subject::new(Name::parse("svc.api"), SubjectType::Channel),
+        Subject::new(Name::parse("svc.db"), SubjectType::Channel),
+    ];
+    let conduit = circuit
+        .recording_conduit_named(Name::from_part("diag"), Arc::new(IdentityComposer::<String>::new()))
+        .await
+        .unwrap();
+    
+    for i in 0..12 {
+        let channel = conduit.get(subjects[i % 2].name()).unwrap();
+        let mut pipe = Channel::pipe(&*channel).unwrap();
+        Arc::get_mut(&mut pipe).unwrap().emit(format!("event-{i}")).await.unwrap();
+    }
+    
+    let path = std::env::temp_dir().join(format!("substrates-recent-{}.dump", Uuid::new_v4()));
+    let written = circuit.dump_recent(&path).await.unwrap();
+    assert_eq!(written, 5);
+    
+    let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = received.clone();
+    let mut subscriber = FunctionSubscriber::new(move |subject: &Subject, registrar: &mut dyn Registrar<Emission = String>| {
+        let sink = sink.clone();
+        let path = subject.name().to_string();
+        registrar.register(Arc::new(FunctionPipe::new(move |value: String| {
+            sink.lock().unwrap().push((path.clone(), value));
+            Ok(())
+        })));
+        Ok(())
+    });
+    
+    let replayed = circuit.load_and_replay(&path, &mut subscriber).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replayed, 5);
+    
+    let expected: Vec<(String, String)> = (7..12)
+        .map(|i| (subjects[i % 2].name().to_string(), format!("event-{i}")))
+        .collect();
+    assert_eq!(*received.lock().unwrap(), expected);
+}
+
+/// Pipe that takes a while to accept each emission
+#[derive(Debug)]
+struct SlowPipe {
+    received: Arc<std::sync::Mutex<Vec<u32>>>,
+}
+
+#[async_trait]
+impl Pipe<u32> for SlowPipe {
+    async fn emit(&mut self, emission: u32) -> SubstratesResult<()> {
+        sleep(Duration::from_millis(5)).await;
+        self.received.lock().unwrap().push(emission);
+        Ok(())
+    }
+}
+
+async fn slow_route(received: Arc<std::sync::Mutex<Vec<u32>>>) -> (Arc<ConduitSource<(), u32>>, Arc<dyn Subscription>) {
+    let conduit = Arc::new(ConduitSource::<(), u32>::new(Name::from_part("metrics")));
+    let subscriber = Arc::new(FunctionSubscriber::new(move |_subject, registrar| {
+        registrar.register(Arc::new(SlowPipe { received: received.clone() }));
+        Ok(())
+    }));
+    let subscription = conduit.subscribe(subscriber).await.unwrap();
+    (conduit, subscription)
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_delivers_queued_emissions() {
+    let circuit = BasicCircuit::new(Name::from_part("shutdown-circuit"));
+    let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (conduit, _subscription) = slow_route(received.clone()).await;
+    let subject = Subject::new(Name::from_part("requests"), SubjectType::Channel);
+    
+    for i in 0..20 {
+        circuit.post_emission(conduit.clone(), subject.clone(), i).await.unwrap();
+    }
+    
+    let report = circuit.shutdown_graceful(Duration::from_secs(5)).await.unwrap();
+    assert!(circuit.is_stopped());
+    assert_eq!(report, ShutdownReport { delivered: 20, dropped: 0 });
+    assert_eq!(*received.lock().unwrap(), (0..20).collect::<Vec<_>>());
+    
+    // No new emissions once shut down
+    assert!(circuit.post_emission(conduit.clone(), subject, 20).await.is_err());
+    assert!(circuit.shutdown_graceful(Duration::from_secs(1)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_reports_dropped_on_timeout() {
+    let circuit = BasicCircuit::new(Name::from_part("timeout-circuit"));
+    let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (conduit, _subscription) = slow_route(received.clone()).await;
+    let subject = Subject::new(Name::from_part("requests"), SubjectType::Channel);
+    
+    for i in 0..100 {
+        circuit.post_emission(conduit.clone(), subject.clone(), i).await.unwrap();
+    }
+    
+    let report = circuit.shutdown_graceful(Duration::from_millis(50)).await.unwrap();
+    assert!(circuit.is_stopped());
+    assert!(report.dropped > 0);
+    assert!(report.delivered + report.dropped >= 100);
+    
+    // Discarded emissions never reach the subscriber
+    sleep(Duration::from_millis(50)).await;
+    assert!(received.lock().unwrap().len() <= report.delivered + 1);
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_drains_emissions_buffered_in_conduits() {
+    let circuit = BasicCircuit::new(Name::from_part("conduit-circuit"));
+    let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (conduit, _subscription) = slow_route(received.clone()).await;
+    circuit.drain_on_shutdown(conduit.clone());
+    let channel = conduit.create_channel(Name::from_part("requests"));
+    
+    for i in 0..20 {
+        conduit.emit_through_channel(&channel, i).await.unwrap();
+    }
+    
+    let report = circuit.shutdown_graceful(Duration::from_secs(5)).await.unwrap();
+    assert_eq!(report, ShutdownReport { delivered: 20, dropped: 0 });
+    assert_eq!(*received.lock().unwrap(), (0..20).collect::<Vec<_>>());
+    
+    // The conduit stops accepting emissions along with the circuit
+    assert!(conduit.emit_through_channel(&channel, 20).await.is_err());
+}
+
+#[tokio::test]
+async fn test_concurrent_graceful_shutdown_is_rejected() {
+    let circuit = BasicCircuit::new(Name::from_part("concurrent-circuit"));
+    let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (conduit, _subscription) = slow_route(received.clone()).await;
+    let subject = Subject::new(Name::from_part("requests"), SubjectType::Channel);
+    
+    for i in 0..20 {
+        circuit.post_emission(conduit.clone(), subject.clone(), i).await.unwrap();
+    }
+    
+    let (first, second) = tokio::join!(
+        circuit.shutdown_graceful(Duration::from_secs(5)),
+        circuit.shutdown_graceful(Duration::from_secs(5)),
+    );
+    assert!(first.is_ok() != second.is_ok());
+    assert_eq!(received.lock().unwrap().len(), 20);
+}
@@ -46,6 +46,39 @@ async fn test_function_pipe_error() {
     assert!(pipe.emit(-5).await.is_err());
 }
 
+#[tokio::test]
+async fn test_map_pipe_chains_channel_to_sink() {
+    let source = ConduitSource::<(), i32>::new(Name::from_part("readings"));
+    let channel = source.create_channel(Name::from_part("sensor"));
+    let sink = Arc::new(BasicSink::<String>::new(Name::from_part("labels")));
+
+    let sink_ref = sink.clone();
+    let subscriber = Arc::new(FunctionSubscriber::new(move |_subject, registrar| {
+        // Double each reading, then format it for the sink
+        let format = MapPipe::new(|value: i32| format!("reading-{}", value), sink_ref.create_pipe());
+        registrar.register(Arc::new(MapPipe::new(|value: i32| value * 2, Arc::new(format))));
+        Ok(())
+    }));
+    let _subscription = source.subscribe(subscriber).await.unwrap();
+
+    for value in [1, 2, 3] {
+        source.emit_through_channel(&channel, value).await.unwrap();
+    }
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let collected: Vec<String> = sink.peek().into_iter().map(Capture::into_emission).collect();
+    assert_eq!(collected, vec!["reading-2", "reading-4", "reading-6"]);
+}
+
+#[tokio::test]
+async fn test_map_pipe_rejects_shared_downstream() {
+    let downstream: Arc<dyn Pipe<i32>> = Arc::new(EmptyPipe::new());
+    let _other = downstream.clone();
+
+    let mut pipe = MapPipe::new(|value: i32| value + 1, downstream);
+    assert!(pipe.emit(1).await.is_err());
+}
+
 #[tokio::test]
 async fn test_assembly_trait() {
     // Test that Assembly trait works properly
@@ -0,0 +1,276 @@
+//! Audit logging of access decisions
+//!
+//! Every authentication and authorization decision made through the
+//! [`IdentityManager`](crate::IdentityManager) is recorded as an [`AuditEvent`]
+//! and handed to an [`AuditSink`]. Denials are recorded as well as grants, so
+//! the log answers who accessed what, and whether it was allowed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Action recorded for authentication events
+pub const AUTHENTICATE_ACTION: &str = "authenticate";
+
+/// Kind of decision an audit event records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    /// Credentials were checked
+    Authentication,
+    /// Access to a resource was checked
+    Authorization,
+}
+
+/// Outcome of an audited decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The request was allowed
+    Allow,
+    /// The request was denied, or could not be decided
+    Deny,
+}
+
+/// A single authentication or authorization decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Event ID
+    pub id: Uuid,
+    /// When the decision was made
+    pub timestamp: DateTime<Utc>,
+    /// Kind of decision
+    pub kind: AuditEventKind,
+    /// Who made the request, if known
+    pub subject: Option<String>,
+    /// Resource accessed, for authorization events
+    pub resource: Option<String>,
+    /// Action requested
+    pub action: String,
+    /// Whether the request was allowed
+    pub outcome: AuditOutcome,
+    /// Why the decision was made
+    pub reason: String,
+}
+
+impl AuditEvent {
+    /// Record an authentication decision
+    pub fn authentication(subject: Option<String>, outcome: AuditOutcome, reason: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            kind: AuditEventKind::Authentication,
+            subject,
+            resource: None,
+            action: AUTHENTICATE_ACTION.to_string(),
+            outcome,
+            reason: reason.into(),
+        }
+    }
+
+    /// Record an authorization decision
+    pub fn authorization(
+        subject: &str,
+        resource: &str,
+        action: &str,
+        outcome: AuditOutcome,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            kind: AuditEventKind::Authorization,
+            subject: Some(subject.to_string()),
+            resource: Some(resource.to_string()),
+            action: action.to_string(),
+            outcome,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Destination for audit events
+///
+/// Sinks are infallible from the caller's side: a sink that can fail, such as
+/// one writing to a remote collector, handles its own retries and errors so
+/// that auditing never changes an access decision.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Record an event
+    async fn record(&self, event: AuditEvent);
+}
+
+/// Sink that emits events as structured `tracing` records
+///
+/// Events are logged under the `synapsed_identity::audit` target; allowed
+/// decisions at info level and denials at warn level.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingAuditSink;
+
+#[async_trait]
+impl AuditSink for TracingAuditSink {
+    async fn record(&self, event: AuditEvent) {
+        let subject = event.subject.as_deref().unwrap_or("-");
+        let resource = event.resource.as_deref().unwrap_or("-");
+        match event.outcome {
+            AuditOutcome::Allow => tracing::info!(
+                target: "synapsed_identity::audit",
+                event_id = %event.id,
+                kind = ?event.kind,
+                subject,
+                resource,
+                action = %event.action,
+                outcome = "allow",
+                reason = %event.reason,
+                "access decision"
+            ),
+            AuditOutcome::Deny => tracing::warn!(
+                target: "synapsed_identity::audit",
+                event_id = %event.id,
+                kind = ?event.kind,
+                subject,
+                resource,
+                action = %event.action,
+                outcome = "deny",
+                reason = %event.reason,
+                "access decision"
+            ),
+        }
+    }
+}
+
+/// Sink that keeps events in memory
+#[derive(Debug, Default)]
+pub struct InMemoryAuditSink {
+    events: Mutex<Vec<AuditEvent>>,
+}
+
+impl InMemoryAuditSink {
+    /// Create an empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events recorded so far, oldest first
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl AuditSink for InMemoryAuditSink {
+    async fn record(&self, event: AuditEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Authenticator;
+    use crate::authorization::Authorizer;
+    use crate::session::InMemorySessionManager;
+    use crate::storage::IdentityStore;
+    use crate::{Error, Identity, IdentityManager, IdentityTrait, Result};
+    use std::sync::Arc;
+
+    #[derive(Clone)]
+    struct NullStore;
+
+    impl IdentityStore for NullStore {
+        fn store_identity(&self, _identity: &dyn IdentityTrait) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_identity(&self, _identity_id: &str) -> Result<Option<Box<dyn IdentityTrait>>> {
+            Ok(None)
+        }
+
+        fn update_identity(&self, _identity: &dyn IdentityTrait) -> Result<()> {
+            Ok(())
+        }
+
+        fn delete_identity(&self, _identity_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn list_identity_ids(&self) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Accepts "secret" as the password for any username
+    struct MockAuthenticator;
+
+    #[async_trait]
+    impl Authenticator for MockAuthenticator {
+        type Credentials = (String, String);
+
+        async fn authenticate(&self, credentials: Self::Credentials) -> Result<Identity> {
+            if credentials.1 != "secret" {
+                return Err(Error::AuthenticationFailed("Invalid username or password".into()));
+            }
+            Ok(Identity {
+                id: Uuid::new_v4(),
+                username: credentials.0,
+                display_name: None,
+                roles: vec!["reader".to_string()],
+                attributes: std::collections::HashMap::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+        }
+
+        fn claimed_subject(&self, credentials: &Self::Credentials) -> Option<String> {
+            Some(credentials.0.clone())
+        }
+    }
+
+    /// Lets readers read, and nothing else
+    struct ReadOnlyAuthorizer;
+
+    #[async_trait]
+    impl Authorizer for ReadOnlyAuthorizer {
+        async fn authorize(&self, identity: &Identity, _resource: &str, action: &str) -> Result<bool> {
+            Ok(action == "read" && identity.roles.iter().any(|role| role == "reader"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_denial_is_audited_with_reason() {
+        let sink = Arc::new(InMemoryAuditSink::new());
+        let manager = IdentityManager::<_, _, _, InMemorySessionManager>::builder()
+            .with_storage(NullStore)
+            .with_authenticator(MockAuthenticator)
+            .with_authorizer(ReadOnlyAuthorizer)
+            .with_audit_sink(sink.clone())
+            .build()
+            .await
+            .unwrap();
+
+        assert!(manager.authenticate(("alice".to_string(), "wrong".to_string())).await.is_err());
+        let identity = manager.authenticate(("alice".to_string(), "secret".to_string())).await.unwrap();
+        assert!(manager.authorize(&identity, "reports/q3", "read").await.unwrap());
+        assert!(!manager.authorize(&identity, "reports/q3", "delete").await.unwrap());
+
+        let events = sink.events();
+        assert_eq!(events.len(), 4);
+
+        assert_eq!(events[0].kind, AuditEventKind::Authentication);
+        assert_eq!(events[0].subject.as_deref(), Some("alice"));
+        assert_eq!(events[0].outcome, AuditOutcome::Deny);
+        assert!(events[0].reason.contains("Invalid username or password"));
+        assert_eq!(events[1].outcome, AuditOutcome::Allow);
+        assert_eq!(events[2].outcome, AuditOutcome::Allow);
+
+        let denial = &events[3];
+        assert_eq!(denial.kind, AuditEventKind::Authorization);
+        assert_eq!(denial.subject.as_deref(), Some("alice"));
+        assert_eq!(denial.resource.as_deref(), Some("reports/q3"));
+        assert_eq!(denial.action, "delete");
+        assert_eq!(denial.outcome, AuditOutcome::Deny);
+        assert_eq!(denial.reason, "alice is not permitted to delete reports/q3");
+    }
+}
@@ -11,6 +11,11 @@ pub trait Authenticator: Send + Sync {
     
     /// Authenticate with the provided credentials
     async fn authenticate(&self, credentials: Self::Credentials) -> Result<Identity>;
+    
+    /// Who the credentials claim to be, recorded when authentication fails
+    fn claimed_subject(&self, _credentials: &Self::Credentials) -> Option<String> {
+        None
+    }
 }
 
 /// Password-based authentication
@@ -19,6 +24,9 @@ pub mod password;
 /// Token-based authentication
 pub mod token;
 
+/// SCRAM-SHA-256 challenge-response authentication
+pub mod scram;
+
 /// OAuth provider integration
 // TODO: Implement OAuth module
 // #[cfg(feature = "oauth")]
@@ -26,4 +34,5 @@ pub mod token;
 
 // Re-export common types
 pub use password::{PasswordAuthenticator, PasswordCredentials};
-pub use token::{TokenAuthenticator, TokenCredentials};
\ No newline at end of file
+pub use token::{TokenAuthenticator, TokenCredentials};
+pub use scram::{ChannelBinding, ScramClient, ScramCredentials, ScramError, ScramServer, ServerChannelBinding};
\ No newline at end of file
@@ -109,7 +109,7 @@ impl PasswordHasher {
 }
 
 /// Constant-time equality comparison
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
         return false;
     }
@@ -181,6 +181,10 @@ impl<S: UserStore> Authenticator for PasswordAuthenticator<S> {
             Err(Error::AuthenticationFailed("Invalid username or password".into()))
         }
     }
+    
+    fn claimed_subject(&self, credentials: &Self::Credentials) -> Option<String> {
+        Some(credentials.username.clone())
+    }
 }
 
 /// Password strength validator
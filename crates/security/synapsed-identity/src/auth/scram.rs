@@ -0,0 +1,573 @@
+//! SCRAM-SHA-256 challenge-response authentication
+//!
+//! Implements the RFC 5802 exchange with the SHA-256 hash from RFC 7677:
+//!
+//! ```text
+//! client-first   n,,n=user,r=<client nonce>
+//! server-first   r=<client nonce><server nonce>,s=<salt>,i=<iterations>
+//! client-final   c=<gs2 header + channel binding>,r=<nonce>,p=<client proof>
+//! server-final   v=<server signature> | e=<error>
+//! ```
+//!
+//! The password never crosses the wire, and the server only stores
+//! [`ScramCredentials`] derived from it. Each side proves knowledge of the
+//! salted password to the other, so the client also authenticates the server.
+//! Channel binding ties the exchange to the underlying TLS connection.
+//!
+//! Passwords are used as their UTF-8 bytes without SASLprep normalization, so
+//! non-ASCII passwords must be entered the same way on both sides.
+
+use crate::{auth::password::constant_time_eq, Error, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hmac::{Hmac, Mac};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Minimum iteration count accepted by clients, per RFC 7677
+pub const MIN_ITERATIONS: u32 = 4096;
+
+const NONCE_LENGTH: usize = 24;
+const SALT_LENGTH: usize = 16;
+
+/// What the server stores for a user, derived from their password
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramCredentials {
+    /// Salt for the password hash
+    pub salt: Vec<u8>,
+    /// Hash iteration count
+    pub iterations: u32,
+    /// H(ClientKey), checked against the client's proof
+    pub stored_key: [u8; 32],
+    /// Key for signing the server-final message
+    pub server_key: [u8; 32],
+}
+
+impl ScramCredentials {
+    /// Derive credentials with a random salt
+    pub fn generate(password: &str, iterations: u32) -> Self {
+        let mut salt = vec![0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(password, salt, iterations)
+    }
+
+    /// Derive credentials with the given salt
+    pub fn derive(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        let salted = salted_password(password, &salt, iterations);
+        Self {
+            stored_key: Sha256::digest(hmac(salted.as_ref(), b"Client Key")).into(),
+            server_key: hmac(salted.as_ref(), b"Server Key"),
+            salt,
+            iterations,
+        }
+    }
+}
+
+/// Channel binding requested by the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelBinding {
+    /// The client does not support channel binding (`n`)
+    Unsupported,
+    /// The client supports channel binding but thinks the server does not (`y`)
+    NotAdvertised,
+    /// Bind to the connection, e.g. `tls-server-end-point` with the certificate hash (`p=`)
+    Bound {
+        /// Channel binding type
+        kind: String,
+        /// Channel binding data
+        data: Vec<u8>,
+    },
+}
+
+impl ChannelBinding {
+    fn gs2_flag(&self) -> String {
+        match self {
+            ChannelBinding::Unsupported => "n".to_string(),
+            ChannelBinding::NotAdvertised => "y".to_string(),
+            ChannelBinding::Bound { kind, .. } => format!("p={}", kind),
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        match self {
+            ChannelBinding::Bound { data, .. } => data,
+            _ => &[],
+        }
+    }
+}
+
+/// Why the server rejected an exchange, sent to the client as `e=<value>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ScramError {
+    /// A message could not be parsed
+    #[error("invalid-encoding")]
+    InvalidEncoding,
+    /// The client sent a mandatory extension
+    #[error("extensions-not-supported")]
+    ExtensionsNotSupported,
+    /// The client's proof does not match the stored credentials
+    #[error("invalid-proof")]
+    InvalidProof,
+    /// The client is bound to a different channel
+    #[error("channel-bindings-dont-match")]
+    ChannelBindingsDontMatch,
+    /// The client did not bind although the server offered it
+    #[error("server-does-support-channel-binding")]
+    ServerDoesSupportChannelBinding,
+    /// The client asked for a channel binding type the server does not offer
+    #[error("unsupported-channel-binding-type")]
+    UnsupportedChannelBindingType,
+    /// No credentials are stored for the username
+    #[error("unknown-user")]
+    UnknownUser,
+    /// A message arrived out of order
+    #[error("other-error")]
+    OtherError,
+}
+
+impl ScramError {
+    /// The server-final message reporting this error
+    pub fn server_final(&self) -> String {
+        format!("e={}", self)
+    }
+}
+
+impl From<ScramError> for Error {
+    fn from(error: ScramError) -> Self {
+        Error::AuthenticationFailed(format!("SCRAM: {}", error))
+    }
+}
+
+#[derive(Debug)]
+enum ClientState {
+    Initial,
+    ClientFirstSent {
+        gs2_header: String,
+        client_first_bare: String,
+    },
+    ClientFinalSent {
+        server_signature: [u8; 32],
+    },
+    Done,
+}
+
+/// Client side of a SCRAM-SHA-256 exchange
+#[derive(Debug)]
+pub struct ScramClient {
+    username: String,
+    password: Zeroizing<String>,
+    binding: ChannelBinding,
+    nonce: String,
+    state: ClientState,
+}
+
+impl ScramClient {
+    /// A client authenticating `username` with `password`
+    pub fn new(username: &str, password: &str, binding: ChannelBinding) -> Self {
+        Self::with_nonce(username, password, binding, random_nonce())
+    }
+
+    fn with_nonce(username: &str, password: &str, binding: ChannelBinding, nonce: String) -> Self {
+        Self {
+            username: username.to_string(),
+            password: Zeroizing::new(password.to_string()),
+            binding,
+            nonce,
+            state: ClientState::Initial,
+        }
+    }
+
+    /// Start the exchange
+    pub fn client_first(&mut self) -> Result<String> {
+        if !matches!(self.state, ClientState::Initial) {
+            return Err(out_of_order());
+        }
+        let gs2_header = format!("{},,", self.binding.gs2_flag());
+        let client_first_bare = format!("n={},r={}", escape_username(&self.username), self.nonce);
+        let message = format!("{}{}", gs2_header, client_first_bare);
+        self.state = ClientState::ClientFirstSent { gs2_header, client_first_bare };
+        Ok(message)
+    }
+
+    /// Answer the server's challenge with proof of the password
+    pub fn client_final(&mut self, server_first: &str) -> Result<String> {
+        let ClientState::ClientFirstSent { gs2_header, client_first_bare } = &self.state else {
+            return Err(out_of_order());
+        };
+        let attributes = parse_attributes(server_first).map_err(Error::from)?;
+        let nonce = attribute(&attributes, "r")?;
+        let salt = STANDARD
+            .decode(attribute(&attributes, "s")?)
+            .map_err(|_| Error::AuthenticationFailed("SCRAM: invalid salt encoding".into()))?;
+        let iterations: u32 = attribute(&attributes, "i")?
+            .parse()
+            .map_err(|_| Error::AuthenticationFailed("SCRAM: invalid iteration count".into()))?;
+
+        if !nonce.starts_with(&self.nonce) || nonce.len() == self.nonce.len() {
+            return Err(Error::AuthenticationFailed("SCRAM: server nonce does not extend ours".into()));
+        }
+        if iterations < MIN_ITERATIONS {
+            return Err(Error::AuthenticationFailed(format!(
+                "SCRAM: iteration count {} is below {}",
+                iterations, MIN_ITERATIONS
+            )));
+        }
+
+        let channel = [gs2_header.as_bytes(), self.binding.data()].concat();
+        let without_proof = format!("c={},r={}", STANDARD.encode(channel), nonce);
+        let auth_message = format!("{},{},{}", client_first_bare, server_first, without_proof);
+
+        let salted = salted_password(&self.password, &salt, iterations);
+        let client_key = hmac(salted.as_ref(), b"Client Key");
+        let stored_key: [u8; 32] = Sha256::digest(client_key).into();
+        let client_signature = hmac(&stored_key, auth_message.as_bytes());
+        let proof = xor(&client_key, &client_signature);
+        let server_signature = hmac(&hmac(salted.as_ref(), b"Server Key"), auth_message.as_bytes());
+
+        self.state = ClientState::ClientFinalSent { server_signature };
+        Ok(format!("{},p={}", without_proof, STANDARD.encode(proof)))
+    }
+
+    /// Check the server's signature, authenticating the server
+    pub fn verify_server_final(&mut self, server_final: &str) -> Result<()> {
+        let ClientState::ClientFinalSent { server_signature } = &self.state else {
+            return Err(out_of_order());
+        };
+        let attributes = parse_attributes(server_final).map_err(Error::from)?;
+        if let Ok(error) = attribute(&attributes, "e") {
+            return Err(Error::AuthenticationFailed(format!("SCRAM: server rejected authentication: {}", error)));
+        }
+        let signature = STANDARD
+            .decode(attribute(&attributes, "v")?)
+            .map_err(|_| Error::AuthenticationFailed("SCRAM: invalid server signature encoding".into()))?;
+        if !constant_time_eq(&signature, server_signature) {
+            return Err(Error::AuthenticationFailed("SCRAM: server signature mismatch".into()));
+        }
+        self.state = ClientState::Done;
+        Ok(())
+    }
+}
+
+/// Channel binding the server can check clients against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerChannelBinding {
+    /// Channel binding type, e.g. `tls-server-end-point`
+    pub kind: String,
+    /// Channel binding data for the current connection
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+enum ServerState {
+    Initial,
+    ServerFirstSent {
+        username: String,
+        gs2_header: String,
+        client_first_bare: String,
+        server_first: String,
+        nonce: String,
+        credentials: Box<ScramCredentials>,
+    },
+    Done {
+        username: String,
+    },
+}
+
+/// Server side of a SCRAM-SHA-256 exchange
+///
+/// On failure, send the client [`ScramError::server_final`].
+#[derive(Debug)]
+pub struct ScramServer {
+    binding: Option<ServerChannelBinding>,
+    nonce: String,
+    state: ServerState,
+}
+
+impl ScramServer {
+    /// A server offering channel binding when `binding` is given
+    pub fn new(binding: Option<ServerChannelBinding>) -> Self {
+        Self::with_nonce(binding, random_nonce())
+    }
+
+    fn with_nonce(binding: Option<ServerChannelBinding>, nonce: String) -> Self {
+        Self {
+            binding,
+            nonce,
+            state: ServerState::Initial,
+        }
+    }
+
+    /// Challenge the client, looking up the stored credentials for its username
+    pub fn server_first<F>(&mut self, client_first: &str, lookup: F) -> std::result::Result<String, ScramError>
+    where
+        F: FnOnce(&str) -> Option<ScramCredentials>,
+    {
+        if !matches!(self.state, ServerState::Initial) {
+            return Err(ScramError::OtherError);
+        }
+        let mut parts = client_first.splitn(3, ',');
+        let (Some(flag), Some(authzid), Some(client_first_bare)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(ScramError::InvalidEncoding);
+        };
+        if !authzid.is_empty() && !authzid.starts_with("a=") {
+            return Err(ScramError::InvalidEncoding);
+        }
+        match (flag, &self.binding) {
+            ("n", _) => {}
+            // The client would have bound had it seen our offer, so it may have been downgraded
+            ("y", Some(_)) => return Err(ScramError::ServerDoesSupportChannelBinding),
+            ("y", None) => {}
+            (flag, Some(binding)) if flag.strip_prefix("p=") == Some(binding.kind.as_str()) => {}
+            (flag, _) if flag.starts_with("p=") => return Err(ScramError::UnsupportedChannelBindingType),
+            _ => return Err(ScramError::InvalidEncoding),
+        }
+
+        let attributes = parse_attributes(client_first_bare)?;
+        if attributes.first().map(|(key, _)| *key) == Some("m") {
+            return Err(ScramError::ExtensionsNotSupported);
+        }
+        let username = unescape_username(attribute(&attributes, "n").map_err(|_| ScramError::InvalidEncoding)?)?;
+        let client_nonce = attribute(&attributes, "r").map_err(|_| ScramError::InvalidEncoding)?;
+        let credentials = lookup(&username).ok_or(ScramError::UnknownUser)?;
+
+        let nonce = format!("{}{}", client_nonce, self.nonce);
+        let server_first = format!(
+            "r={},s={},i={}",
+            nonce,
+            STANDARD.encode(&credentials.salt),
+            credentials.iterations
+        );
+        self.state = ServerState::ServerFirstSent {
+            username,
+            gs2_header: format!("{},{},", flag, authzid),
+            client_first_bare: client_first_bare.to_string(),
+            server_first: server_first.clone(),
+            nonce,
+            credentials: Box::new(credentials),
+        };
+        Ok(server_first)
+    }
+
+    /// Verify the client's proof, returning the server signature message
+    pub fn server_final(&mut self, client_final: &str) -> std::result::Result<String, ScramError> {
+        let ServerState::ServerFirstSent {
+            username,
+            gs2_header,
+            client_first_bare,
+            server_first,
+            nonce,
+            credentials,
+        } = &self.state
+        else {
+            return Err(ScramError::OtherError);
+        };
+
+        let (without_proof, proof) = client_final.rsplit_once(",p=").ok_or(ScramError::InvalidEncoding)?;
+        let attributes = parse_attributes(without_proof)?;
+        let channel = STANDARD
+            .decode(attribute(&attributes, "c").map_err(|_| ScramError::InvalidEncoding)?)
+            .map_err(|_| ScramError::InvalidEncoding)?;
+        let binding_data = self.binding.as_ref().filter(|_| gs2_header.starts_with("p=")).map(|b| b.data.as_slice());
+        let expected_channel = [gs2_header.as_bytes(), binding_data.unwrap_or_default()].concat();
+        if !constant_time_eq(&channel, &expected_channel) {
+            return Err(ScramError::ChannelBindingsDontMatch);
+        }
+        if attribute(&attributes, "r").map_err(|_| ScramError::InvalidEncoding)? != nonce {
+            return Err(ScramError::InvalidProof);
+        }
+        let proof = STANDARD.decode(proof).map_err(|_| ScramError::InvalidEncoding)?;
+        if proof.len() != 32 {
+            return Err(ScramError::InvalidProof);
+        }
+
+        let auth_message = format!("{},{},{}", client_first_bare, server_first, without_proof);
+        let client_signature = hmac(&credentials.stored_key, auth_message.as_bytes());
+        let client_key = xor(&proof, &client_signature);
+        let stored_key: [u8; 32] = Sha256::digest(client_key).into();
+        if !constant_time_eq(&stored_key, &credentials.stored_key) {
+            return Err(ScramError::InvalidProof);
+        }
+
+        let server_signature = hmac(&credentials.server_key, auth_message.as_bytes());
+        self.state = ServerState::Done { username: username.clone() };
+        Ok(format!("v={}", STANDARD.encode(server_signature)))
+    }
+
+    /// The authenticated username, once the client's proof has been verified
+    pub fn authenticated_user(&self) -> Option<&str> {
+        match &self.state {
+            ServerState::Done { username } => Some(username),
+            _ => None,
+        }
+    }
+}
+
+fn out_of_order() -> Error {
+    Error::AuthenticationFailed("SCRAM: message out of order".into())
+}
+
+fn random_nonce() -> String {
+    let mut bytes = [0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut bytes);
+    STANDARD.encode(bytes)
+}
+
+/// Hi() from RFC 5802, which is PBKDF2 with a single output block
+fn salted_password(password: &str, salt: &[u8], iterations: u32) -> Zeroizing<[u8; 32]> {
+    let mut block = hmac(password.as_bytes(), &[salt, &1u32.to_be_bytes()].concat());
+    let mut result = Zeroizing::new(block);
+    for _ in 1..iterations {
+        block = hmac(password.as_bytes(), &block);
+        for (out, byte) in result.iter_mut().zip(block) {
+            *out ^= byte;
+        }
+    }
+    result
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+fn xor(a: &[u8], b: &[u8; 32]) -> [u8; 32] {
+    let mut out = *b;
+    for (out, byte) in out.iter_mut().zip(a) {
+        *out ^= byte;
+    }
+    out
+}
+
+fn parse_attributes(message: &str) -> std::result::Result<Vec<(&str, &str)>, ScramError> {
+    message
+        .split(',')
+        .map(|part| {
+            part.split_once('=')
+                .filter(|(key, _)| key.len() == 1)
+                .ok_or(ScramError::InvalidEncoding)
+        })
+        .collect()
+}
+
+fn attribute<'a>(attributes: &[(&str, &'a str)], key: &str) -> Result<&'a str> {
+    attributes
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| *value)
+        .ok_or_else(|| Error::AuthenticationFailed(format!("SCRAM: missing '{}' attribute", key)))
+}
+
+fn escape_username(username: &str) -> String {
+    username.replace('=', "=3D").replace(',', "=2C")
+}
+
+fn unescape_username(escaped: &str) -> std::result::Result<String, ScramError> {
+    let mut username = String::with_capacity(escaped.len());
+    let mut rest = escaped;
+    while let Some(index) = rest.find('=') {
+        username.push_str(&rest[..index]);
+        match rest.get(index..index + 3) {
+            Some("=3D") => username.push('='),
+            Some("=2C") => username.push(','),
+            _ => return Err(ScramError::InvalidEncoding),
+        }
+        rest = &rest[index + 3..];
+    }
+    username.push_str(rest);
+    Ok(username)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handshake(
+        client: &mut ScramClient,
+        server: &mut ScramServer,
+        credentials: &ScramCredentials,
+    ) -> std::result::Result<(), ScramError> {
+        let client_first = client.client_first().unwrap();
+        let server_first = server.server_first(&client_first, |_| Some(credentials.clone()))?;
+        let client_final = client.client_final(&server_first).unwrap();
+        match server.server_final(&client_final) {
+            Ok(server_final) => {
+                client.verify_server_final(&server_final).unwrap();
+                Ok(())
+            }
+            Err(error) => {
+                assert!(client.verify_server_final(&error.server_final()).is_err());
+                Err(error)
+            }
+        }
+    }
+
+    #[test]
+    fn test_rfc7677_exchange() {
+        let credentials = ScramCredentials::derive("pencil", STANDARD.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap(), 4096);
+        let mut client = ScramClient::with_nonce("user", "pencil", ChannelBinding::Unsupported, "rOprNGfwEbeRWgbNEkqO".into());
+        let mut server = ScramServer::with_nonce(None, "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0".into());
+
+        let client_first = client.client_first().unwrap();
+        assert_eq!(client_first, "n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+        let server_first = server.server_first(&client_first, |_| Some(credentials.clone())).unwrap();
+        assert_eq!(
+            server_first,
+            "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096"
+        );
+        let client_final = client.client_final(&server_first).unwrap();
+        assert_eq!(
+            client_final,
+            "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+        );
+        let server_final = server.server_final(&client_final).unwrap();
+        assert_eq!(server_final, "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=");
+        client.verify_server_final(&server_final).unwrap();
+        assert_eq!(server.authenticated_user(), Some("user"));
+    }
+
+    #[test]
+    fn test_handshake_with_right_and_wrong_password() {
+        let credentials = ScramCredentials::generate("correct horse", MIN_ITERATIONS);
+
+        let mut client = ScramClient::new("alice,admin", "correct horse", ChannelBinding::Unsupported);
+        let mut server = ScramServer::new(None);
+        assert_eq!(handshake(&mut client, &mut server, &credentials), Ok(()));
+        assert_eq!(server.authenticated_user(), Some("alice,admin"));
+
+        let mut client = ScramClient::new("alice,admin", "battery staple", ChannelBinding::Unsupported);
+        let mut server = ScramServer::new(None);
+        assert_eq!(handshake(&mut client, &mut server, &credentials), Err(ScramError::InvalidProof));
+        assert_eq!(server.authenticated_user(), None);
+    }
+
+    #[test]
+    fn test_channel_binding() {
+        let credentials = ScramCredentials::generate("pencil", MIN_ITERATIONS);
+        let server_binding = ServerChannelBinding {
+            kind: "tls-server-end-point".into(),
+            data: b"certificate hash".to_vec(),
+        };
+        let bound = |data: &[u8]| ChannelBinding::Bound {
+            kind: "tls-server-end-point".into(),
+            data: data.to_vec(),
+        };
+
+        let mut client = ScramClient::new("user", "pencil", bound(b"certificate hash"));
+        let mut server = ScramServer::new(Some(server_binding.clone()));
+        assert_eq!(handshake(&mut client, &mut server, &credentials), Ok(()));
+
+        // A different TLS channel, e.g. a man in the middle
+        let mut client = ScramClient::new("user", "pencil", bound(b"other certificate"));
+        let mut server = ScramServer::new(Some(server_binding.clone()));
+        assert_eq!(handshake(&mut client, &mut server, &credentials), Err(ScramError::ChannelBindingsDontMatch));
+
+        // A client that would have bound if it had seen the offer
+        let mut client = ScramClient::new("user", "pencil", ChannelBinding::NotAdvertised);
+        let mut server = ScramServer::new(Some(server_binding));
+        assert_eq!(handshake(&mut client, &mut server, &credentials), Err(ScramError::ServerDoesSupportChannelBinding));
+    }
+}
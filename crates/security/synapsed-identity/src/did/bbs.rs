@@ -0,0 +1,439 @@
+//! BBS signatures for selective disclosure
+//!
+//! This module provides:
+//! - BBS key generation and credential signing over BLS12-381
+//! - Zero-knowledge proofs of a signature that reveal a chosen subset of attributes
+//! - Presentation verification against the issuer's public key
+//!
+//! Follows the construction in draft-irtf-cfrg-bbs-signatures. Each attribute
+//! is signed as its own message, hashed together with the attribute name.
+//! Messages are ordered by attribute name, so disclosed indices refer to the
+//! credential's attributes in sorted name order. A presentation reveals the
+//! names of hidden attributes but nothing about their values, and presentations
+//! of the same credential cannot be linked to each other.
+
+use std::collections::HashMap;
+use ark_bls12_381::{g1, Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{
+    hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+    pairing::Pairing,
+    AffineRepr, CurveGroup, Group,
+};
+use ark_ff::{field_hashers::{DefaultFieldHasher, HashToField}, Field, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use chrono::Utc;
+use rand_core::{OsRng, RngCore};
+use sha2::Sha256;
+use crate::{Result, Error};
+use super::Did;
+use super::zkp::{AnonymousCredential, AttributeValue, CredentialSignature, ProofType, VerificationKey, ZkProof};
+
+/// Domain separation tag for message generators
+const GENERATOR_DST: &[u8] = b"SYNAPSED_BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_GENERATORS_";
+
+/// Domain separation tag for hashing to scalars
+const SCALAR_DST: &[u8] = b"SYNAPSED_BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_H2S_";
+
+/// Length of the random nonce bound into each presentation
+const NONCE_LENGTH: usize = 32;
+
+/// BBS issuer key pair
+pub struct BbsKeyPair {
+    /// Secret scalar
+    secret_key: Fr,
+    /// Public key in G2
+    public_key: BbsPublicKey,
+}
+
+impl BbsKeyPair {
+    /// Generate a new key pair
+    pub fn generate() -> Self {
+        let secret_key = Fr::rand(&mut OsRng);
+        let public_key = BbsPublicKey((G2Projective::generator() * secret_key).into_affine());
+        Self { secret_key, public_key }
+    }
+
+    /// The public key verifiers check presentations against
+    pub fn public_key(&self) -> &BbsPublicKey {
+        &self.public_key
+    }
+}
+
+/// BBS issuer public key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BbsPublicKey(G2Affine);
+
+impl BbsPublicKey {
+    /// Compressed encoding of the key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.0.serialize_compressed(&mut bytes).expect("serializing to a Vec cannot fail");
+        bytes
+    }
+
+    /// Decode a compressed key
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let point = G2Affine::deserialize_compressed(bytes)
+            .map_err(|e| Error::ZkProofError(format!("Invalid BBS public key: {}", e)))?;
+        if point.is_zero() {
+            return Err(Error::ZkProofError("Invalid BBS public key: identity".into()));
+        }
+        Ok(Self(point))
+    }
+
+    /// The key as a verification key for [`ZkpVerifier`](super::ZkpVerifier)
+    pub fn to_verification_key(&self) -> VerificationKey {
+        VerificationKey {
+            key_type: ProofType::BBS_PLUS,
+            key_data: self.to_bytes(),
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+/// Issue a credential with a BBS signature over each attribute
+pub fn issue_bbs_credential(
+    issuer: &Did,
+    key_pair: &BbsKeyPair,
+    subject: Option<Did>,
+    attributes: HashMap<String, AttributeValue>,
+) -> Result<AnonymousCredential> {
+    if attributes.is_empty() {
+        return Err(Error::ZkProofError("A BBS credential needs at least one attribute".into()));
+    }
+
+    let messages = messages(&sorted_attributes(&attributes));
+    let generators = Generators::new(messages.len())?;
+    let domain = domain(&key_pair.public_key, messages.len());
+    let b = generators.commit(domain, messages.iter().copied().enumerate());
+
+    // e is derived from the key and messages, so signing is deterministic
+    let mut e_input = Vec::new();
+    write_scalar(&mut e_input, &key_pair.secret_key);
+    write_scalar(&mut e_input, &domain);
+    for message in &messages {
+        write_scalar(&mut e_input, message);
+    }
+    let e = hash_to_scalar(&e_input);
+    let inverse = (key_pair.secret_key + e).inverse()
+        .ok_or_else(|| Error::ZkProofError("Degenerate BBS signature".into()))?;
+    let a = (b * inverse).into_affine();
+
+    let mut signature = Vec::new();
+    write_point(&mut signature, &a);
+    write_scalar(&mut signature, &e);
+
+    Ok(AnonymousCredential {
+        id: format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+        issuer: issuer.clone(),
+        subject,
+        attributes,
+        signature: CredentialSignature::BbsPlus {
+            signature,
+            public_key: key_pair.public_key.to_bytes(),
+        },
+        issued_at: Utc::now(),
+        expires_at: None,
+        revocation_registry_id: None,
+    })
+}
+
+/// Prove possession of a BBS credential, revealing only the attributes at `disclosed_indices`
+///
+/// Indices refer to the credential's attributes sorted by name.
+pub fn create_selective_presentation(
+    credential: &AnonymousCredential,
+    disclosed_indices: &[usize],
+) -> Result<ZkProof> {
+    let CredentialSignature::BbsPlus { signature, public_key } = &credential.signature else {
+        return Err(Error::ZkProofError("Credential does not carry a BBS signature".into()));
+    };
+    let public_key = BbsPublicKey::from_bytes(public_key)?;
+    let mut reader = signature.as_slice();
+    let a = read_point(&mut reader)?;
+    let e = read_scalar(&mut reader)?;
+
+    let attributes = sorted_attributes(&credential.attributes);
+    if let Some(index) = disclosed_indices.iter().find(|index| **index >= attributes.len()) {
+        return Err(Error::ZkProofError(format!(
+            "Disclosed index {} out of range for {} attributes", index, attributes.len()
+        )));
+    }
+    let messages = messages(&attributes);
+    let disclosed: Vec<bool> = (0..messages.len()).map(|i| disclosed_indices.contains(&i)).collect();
+    let generators = Generators::new(messages.len())?;
+    let domain = domain(&public_key, messages.len());
+    let b = generators.commit(domain, messages.iter().copied().enumerate());
+
+    let mut nonce = vec![0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut nonce);
+
+    let rng = &mut OsRng;
+    let (r1, r2) = (Fr::rand(rng), Fr::rand(rng));
+    let (e_blind, r1_blind, r3_blind) = (Fr::rand(rng), Fr::rand(rng), Fr::rand(rng));
+    let hidden: Vec<usize> = (0..messages.len()).filter(|i| !disclosed[*i]).collect();
+    let message_blinds: Vec<Fr> = hidden.iter().map(|_| Fr::rand(rng)).collect();
+
+    let d = b * r2;
+    let a_bar = a * (r1 * r2);
+    let b_bar = d * r1 - a_bar * e;
+    let t1 = a_bar * e_blind + d * r1_blind;
+    let t2 = d * r3_blind + hidden.iter().zip(&message_blinds)
+        .map(|(i, blind)| generators.messages[*i] * blind)
+        .sum::<G1Projective>();
+
+    let points = [a_bar, b_bar, d].map(|point| point.into_affine());
+    let challenge = challenge(&points, &[t1.into_affine(), t2.into_affine()], &disclosed, &messages, domain, &nonce);
+    let r3 = r2.inverse().ok_or_else(|| Error::ZkProofError("Degenerate presentation randomness".into()))?;
+
+    let mut proof_data = Vec::new();
+    for point in &points {
+        write_point(&mut proof_data, point);
+    }
+    for scalar in [e_blind + e * challenge, r1_blind - r1 * challenge, r3_blind - r3 * challenge, challenge] {
+        write_scalar(&mut proof_data, &scalar);
+    }
+    for (i, blind) in hidden.iter().zip(&message_blinds) {
+        write_scalar(&mut proof_data, &(*blind + messages[*i] * challenge));
+    }
+
+    let mut revealed_attributes = HashMap::new();
+    let mut unrevealed_attributes = Vec::new();
+    for (i, (name, value)) in attributes.into_iter().enumerate() {
+        if disclosed[i] {
+            revealed_attributes.insert(name.to_string(), value.to_string());
+        } else {
+            unrevealed_attributes.push(name.to_string());
+        }
+    }
+
+    Ok(ZkProof {
+        proof_type: ProofType::BBS_PLUS,
+        proof_data,
+        revealed_attributes,
+        unrevealed_attributes,
+        predicates: Vec::new(),
+        nonce,
+    })
+}
+
+/// Verify a selective disclosure presentation against the issuer's public key
+///
+/// Returns `Ok(false)` when the proof does not hold, including when a
+/// revealed attribute was altered.
+pub fn verify_bbs_presentation(proof: &ZkProof, public_key: &BbsPublicKey) -> Result<bool> {
+    if proof.proof_type != ProofType::BBS_PLUS {
+        return Err(Error::ZkProofError(format!("Not a BBS proof: {:?}", proof.proof_type)));
+    }
+
+    // Rebuild the message order from every attribute name
+    let mut names: Vec<&String> = proof.revealed_attributes.keys().chain(&proof.unrevealed_attributes).collect();
+    names.sort();
+    if names.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Ok(false);
+    }
+    let disclosed: Vec<bool> = names.iter().map(|name| proof.revealed_attributes.contains_key(*name)).collect();
+    let messages: Vec<Fr> = names.iter()
+        .map(|name| proof.revealed_attributes.get(*name).map(|value| message(name, value)).unwrap_or_default())
+        .collect();
+
+    let mut reader = proof.proof_data.as_slice();
+    let parsed = (|| -> Result<_> {
+        let points = [read_point(&mut reader)?, read_point(&mut reader)?, read_point(&mut reader)?];
+        let scalars = [read_scalar(&mut reader)?, read_scalar(&mut reader)?, read_scalar(&mut reader)?, read_scalar(&mut reader)?];
+        let hidden = (0..proof.unrevealed_attributes.len())
+            .map(|_| read_scalar(&mut reader))
+            .collect::<Result<Vec<Fr>>>()?;
+        Ok((points, scalars, hidden))
+    })();
+    let Ok(([a_bar, b_bar, d], [e_hat, r1_hat, r3_hat, c], hidden_responses)) = parsed else {
+        return Ok(false);
+    };
+    if !reader.is_empty() || a_bar.is_zero() || d.is_zero() {
+        return Ok(false);
+    }
+
+    let generators = Generators::new(messages.len())?;
+    let domain = domain(public_key, messages.len());
+    let t1 = b_bar * c + a_bar * e_hat + d * r1_hat;
+    let b_disclosed = generators.commit(
+        domain,
+        messages.iter().copied().enumerate().filter(|(i, _)| disclosed[*i]),
+    );
+    let hidden = (0..messages.len()).filter(|i| !disclosed[*i]);
+    let t2 = b_disclosed * c + d * r3_hat + hidden.zip(&hidden_responses)
+        .map(|(i, response)| generators.messages[i] * response)
+        .sum::<G1Projective>();
+
+    if challenge(&[a_bar, b_bar, d], &[t1.into_affine(), t2.into_affine()], &disclosed, &messages, domain, &proof.nonce) != c {
+        return Ok(false);
+    }
+
+    // e(Abar, W) == e(Bbar, P2) holds only for a signature under the issuer's key
+    let pairing = Bls12_381::multi_pairing([a_bar, b_bar], [public_key.0, (-G2Projective::generator()).into_affine()]);
+    Ok(pairing.is_zero())
+}
+
+/// Generators for the signature's domain and each message
+struct Generators {
+    /// Generator for the domain scalar
+    domain: G1Affine,
+    /// One generator per message
+    messages: Vec<G1Affine>,
+}
+
+impl Generators {
+    fn new(count: usize) -> Result<Self> {
+        let hasher = MapToCurveBasedHasher::<G1Projective, DefaultFieldHasher<Sha256>, WBMap<g1::Config>>::new(GENERATOR_DST)
+            .map_err(|e| Error::ZkProofError(format!("Hash to curve failed: {:?}", e)))?;
+        let mut points = (0..=count as u64)
+            .map(|i| hasher.hash(&i.to_be_bytes()))
+            .collect::<std::result::Result<Vec<G1Affine>, _>>()
+            .map_err(|e| Error::ZkProofError(format!("Hash to curve failed: {:?}", e)))?;
+        let domain = points.remove(0);
+        Ok(Self { domain, messages: points })
+    }
+
+    /// P1 + Q1 * domain + the sum of H_i * m_i over the given messages
+    fn commit(&self, domain: Fr, messages: impl Iterator<Item = (usize, Fr)>) -> G1Projective {
+        G1Projective::generator() + self.domain * domain
+            + messages.map(|(i, message)| self.messages[i] * message).sum::<G1Projective>()
+    }
+}
+
+/// Attributes in message order
+fn sorted_attributes(attributes: &HashMap<String, AttributeValue>) -> Vec<(&str, String)> {
+    let mut sorted: Vec<(&str, String)> = attributes.iter()
+        .map(|(name, value)| (name.as_str(), value.to_string()))
+        .collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+    sorted
+}
+
+fn messages(attributes: &[(&str, String)]) -> Vec<Fr> {
+    attributes.iter().map(|(name, value)| message(name, value)).collect()
+}
+
+/// Message scalar for an attribute, bound to its name
+fn message(name: &str, value: &str) -> Fr {
+    let mut input = Vec::with_capacity(name.len() + value.len() + 8);
+    input.extend_from_slice(&(name.len() as u64).to_be_bytes());
+    input.extend_from_slice(name.as_bytes());
+    input.extend_from_slice(value.as_bytes());
+    hash_to_scalar(&input)
+}
+
+/// Scalar binding signatures to the issuer key and message count
+fn domain(public_key: &BbsPublicKey, count: usize) -> Fr {
+    let mut input = public_key.to_bytes();
+    input.extend_from_slice(&(count as u64).to_be_bytes());
+    hash_to_scalar(&input)
+}
+
+/// Fiat-Shamir challenge for a presentation
+fn challenge(
+    points: &[G1Affine; 3],
+    commitments: &[G1Affine; 2],
+    disclosed: &[bool],
+    messages: &[Fr],
+    domain: Fr,
+    nonce: &[u8],
+) -> Fr {
+    let mut input = Vec::new();
+    for point in points.iter().chain(commitments) {
+        write_point(&mut input, point);
+    }
+    for (i, message) in messages.iter().enumerate().filter(|(i, _)| disclosed[*i]) {
+        input.extend_from_slice(&(i as u64).to_be_bytes());
+        write_scalar(&mut input, message);
+    }
+    write_scalar(&mut input, &domain);
+    input.extend_from_slice(nonce);
+    hash_to_scalar(&input)
+}
+
+fn hash_to_scalar(input: &[u8]) -> Fr {
+    let hasher = <DefaultFieldHasher<Sha256> as HashToField<Fr>>::new(SCALAR_DST);
+    hasher.hash_to_field(input, 1)[0]
+}
+
+fn write_point(out: &mut Vec<u8>, point: &G1Affine) {
+    point.serialize_compressed(out).expect("serializing to a Vec cannot fail");
+}
+
+fn write_scalar(out: &mut Vec<u8>, scalar: &Fr) {
+    scalar.serialize_compressed(out).expect("serializing to a Vec cannot fail");
+}
+
+fn read_point(reader: &mut &[u8]) -> Result<G1Affine> {
+    G1Affine::deserialize_compressed(reader)
+        .map_err(|e| Error::ZkProofError(format!("Invalid curve point: {}", e)))
+}
+
+fn read_scalar(reader: &mut &[u8]) -> Result<Fr> {
+    Fr::deserialize_compressed(reader)
+        .map_err(|e| Error::ZkProofError(format!("Invalid scalar: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::ZkpVerifier;
+
+    fn issue(key_pair: &BbsKeyPair) -> AnonymousCredential {
+        let mut attributes = HashMap::new();
+        attributes.insert("birth_year".to_string(), AttributeValue::Number(1990));
+        attributes.insert("name".to_string(), AttributeValue::String("Alice Example".to_string()));
+        attributes.insert("over_18".to_string(), AttributeValue::Boolean(true));
+        issue_bbs_credential(&Did::new("key", "issuer"), key_pair, None, attributes).unwrap()
+    }
+
+    #[test]
+    fn test_disclose_one_of_three_attributes() {
+        let key_pair = BbsKeyPair::generate();
+        let credential = issue(&key_pair);
+
+        let mut verifier = ZkpVerifier::new();
+        verifier.register_verification_key(&credential.issuer, key_pair.public_key().to_verification_key());
+
+        // Attributes sort as birth_year, name, over_18
+        let presentation = create_selective_presentation(&credential, &[2]).unwrap();
+        assert!(verifier.verify_presentation(&credential.issuer, &presentation).unwrap());
+
+        assert_eq!(presentation.revealed_attributes.len(), 1);
+        assert_eq!(presentation.revealed_attributes.get("over_18").map(String::as_str), Some("true"));
+        let mut hidden = presentation.unrevealed_attributes.clone();
+        hidden.sort();
+        assert_eq!(hidden, vec!["birth_year".to_string(), "name".to_string()]);
+
+        // Hidden values appear nowhere in the presentation
+        let serialized = serde_json::to_string(&presentation).unwrap();
+        assert!(!serialized.contains("Alice"));
+        assert!(!serialized.contains("1990"));
+
+        // Two presentations of the same credential share no proof bytes
+        let again = create_selective_presentation(&credential, &[2]).unwrap();
+        assert_ne!(presentation.proof_data[..48], again.proof_data[..48]);
+    }
+
+    #[test]
+    fn test_altered_presentation_fails() {
+        let key_pair = BbsKeyPair::generate();
+        let credential = issue(&key_pair);
+        let presentation = create_selective_presentation(&credential, &[0, 2]).unwrap();
+        assert!(verify_bbs_presentation(&presentation, key_pair.public_key()).unwrap());
+
+        let mut altered = presentation.clone();
+        altered.revealed_attributes.insert("birth_year".to_string(), "2010".to_string());
+        assert!(!verify_bbs_presentation(&altered, key_pair.public_key()).unwrap());
+
+        let mut replayed = presentation.clone();
+        replayed.nonce[0] ^= 1;
+        assert!(!verify_bbs_presentation(&replayed, key_pair.public_key()).unwrap());
+
+        let other_issuer = BbsKeyPair::generate();
+        assert!(!verify_bbs_presentation(&presentation, other_issuer.public_key()).unwrap());
+
+        assert!(create_selective_presentation(&credential, &[3]).is_err());
+    }
+}
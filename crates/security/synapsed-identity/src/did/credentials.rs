@@ -0,0 +1,379 @@
+//! W3C Verifiable Credentials
+//!
+//! This module provides:
+//! - Verifiable Credential data model v1.1
+//! - `Ed25519Signature2020` proofs made with the issuer's DID key
+//! - Expiry and revocation status checks
+//!
+//! Documents are canonicalized as JSON with sorted object keys rather than
+//! with RDF dataset canonicalization, so proofs verify between Synapsed
+//! implementations but not with JSON-LD tooling.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+use crate::{Result, Error};
+use super::{Did, DidDocument, DidKey, PublicKeyMaterial, VerificationRelationship};
+use super::methods::KeyType;
+
+/// Base context for all credentials
+pub const CREDENTIALS_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+
+/// Context defining the Ed25519Signature2020 proof suite
+pub const ED25519_2020_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
+
+/// Proof type produced and accepted by this module
+pub const ED25519_SIGNATURE_2020: &str = "Ed25519Signature2020";
+
+/// Credential status type checked against a [`RevocationList`]
+pub const REVOCATION_LIST_STATUS: &str = "SynapsedRevocationList";
+
+/// W3C Verifiable Credential
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    /// JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    /// Credential ID
+    pub id: String,
+    /// Credential types, starting with `VerifiableCredential`
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    /// DID of the issuer
+    pub issuer: String,
+    /// When the credential was issued
+    pub issuance_date: DateTime<Utc>,
+    /// When the credential stops being valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<DateTime<Utc>>,
+    /// Claims about the subject
+    pub credential_subject: CredentialSubject,
+    /// Where to check whether the credential was revoked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
+    /// Issuer's proof over the rest of the credential
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof: Option<CredentialProof>,
+}
+
+impl VerifiableCredential {
+    /// Whether the credential is within its validity period at the given time
+    pub fn is_valid_at(&self, at_time: DateTime<Utc>) -> bool {
+        self.issuance_date <= at_time && self.expiration_date.is_none_or(|expires| at_time < expires)
+    }
+
+    /// Verify the proof against the issuer's DID document
+    ///
+    /// The proof must be made with a verification method the document lists
+    /// under `assertionMethod`. Returns `Ok(false)` for a bad signature.
+    pub fn verify_proof(&self, issuer_document: &DidDocument) -> Result<bool> {
+        let proof = self.proof.as_ref()
+            .ok_or_else(|| Error::Validation("Credential has no proof".into()))?;
+        if proof.proof_type != ED25519_SIGNATURE_2020 {
+            return Err(Error::NotSupported(format!("Unsupported proof type: {}", proof.proof_type)));
+        }
+        if issuer_document.id.to_string() != self.issuer {
+            return Err(Error::Validation("DID document does not belong to the issuer".into()));
+        }
+
+        let asserts = issuer_document.assertion_method.iter().any(|relationship| match relationship {
+            VerificationRelationship::Reference(id) => *id == proof.verification_method,
+            VerificationRelationship::Embedded(method) => method.id == proof.verification_method,
+        });
+        if !asserts {
+            return Ok(false);
+        }
+        let method = issuer_document.all_verification_methods().into_iter()
+            .find(|method| method.id == proof.verification_method)
+            .ok_or_else(|| Error::DidDocumentError(format!(
+                "Verification method not found: {}", proof.verification_method
+            )))?;
+        let public_key = ed25519_public_key(&method.public_key)?;
+
+        let (_base, signature) = multibase::decode(&proof.proof_value)
+            .map_err(|e| Error::Validation(format!("Invalid proof value encoding: {}", e)))?;
+        let Ok(signature) = Signature::from_slice(&signature) else {
+            return Ok(false);
+        };
+
+        Ok(public_key.verify(&self.signing_input(proof)?, &signature).is_ok())
+    }
+
+    /// Hash of the proof options followed by the hash of the unsigned credential
+    fn signing_input(&self, proof: &CredentialProof) -> Result<Vec<u8>> {
+        let mut options = serde_json::to_value(proof)?;
+        if let Value::Object(ref mut map) = options {
+            map.remove("proofValue");
+            map.insert("@context".to_string(), serde_json::to_value(&self.context)?);
+        }
+        let mut document = serde_json::to_value(self)?;
+        if let Value::Object(ref mut map) = document {
+            map.remove("proof");
+        }
+
+        let mut input = Sha256::digest(canonical_json(&options)).to_vec();
+        input.extend_from_slice(&Sha256::digest(canonical_json(&document)));
+        Ok(input)
+    }
+}
+
+/// Subject of a credential and the claims made about it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialSubject {
+    /// DID of the subject
+    pub id: String,
+    /// Claims about the subject
+    #[serde(flatten)]
+    pub claims: HashMap<String, Value>,
+}
+
+/// Revocation status entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialStatus {
+    /// Status entry ID
+    pub id: String,
+    /// Status method
+    #[serde(rename = "type")]
+    pub status_type: String,
+}
+
+/// Linked data proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialProof {
+    /// Proof suite
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    /// When the proof was created
+    pub created: DateTime<Utc>,
+    /// Verification method in the issuer's DID document
+    pub verification_method: String,
+    /// Why the proof was made
+    pub proof_purpose: String,
+    /// Multibase-encoded signature
+    pub proof_value: String,
+}
+
+/// Issues credentials signed with a did:key Ed25519 key
+pub struct CredentialIssuer {
+    /// Issuer DID
+    did: Did,
+    /// Signing key for the DID
+    signing_key: SigningKey,
+    /// How long issued credentials stay valid
+    validity: Option<Duration>,
+}
+
+impl CredentialIssuer {
+    /// Create an issuer with a new did:key
+    pub fn generate() -> Result<Self> {
+        use rand_core::{RngCore, OsRng};
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        Self::from_secret_key(&secret)
+    }
+
+    /// Create an issuer from an Ed25519 secret key
+    pub fn from_secret_key(secret_key: &[u8]) -> Result<Self> {
+        let secret: [u8; 32] = secret_key.try_into()
+            .map_err(|_| Error::KeyManagementError("Ed25519 secret key must be 32 bytes".into()))?;
+        let signing_key = SigningKey::from_bytes(&secret);
+        let did = DidKey::new().from_public_key(KeyType::Ed25519, signing_key.verifying_key().as_bytes())?;
+
+        Ok(Self {
+            did,
+            signing_key,
+            validity: None,
+        })
+    }
+
+    /// Expire issued credentials after `validity`
+    pub fn with_validity(mut self, validity: Duration) -> Self {
+        self.validity = Some(validity);
+        self
+    }
+
+    /// The issuer DID
+    pub fn did(&self) -> &Did {
+        &self.did
+    }
+
+    /// Verification method that signs credentials, as listed in the did:key document
+    pub fn verification_method(&self) -> String {
+        format!("{}#{}", self.did.to_string(), self.did.method_specific_id)
+    }
+
+    /// Issue a signed credential making `claims` about `subject`
+    pub fn issue(&self, subject: &Did, claims: HashMap<String, Value>) -> Result<VerifiableCredential> {
+        let issued_at = Utc::now();
+        let id = format!("urn:uuid:{}", uuid::Uuid::new_v4());
+        let mut credential = VerifiableCredential {
+            context: vec![CREDENTIALS_CONTEXT.to_string(), ED25519_2020_CONTEXT.to_string()],
+            credential_status: Some(CredentialStatus {
+                id: id.clone(),
+                status_type: REVOCATION_LIST_STATUS.to_string(),
+            }),
+            id,
+            credential_type: vec!["VerifiableCredential".to_string()],
+            issuer: self.did.to_string(),
+            issuance_date: issued_at,
+            expiration_date: self.validity.map(|validity| issued_at + validity),
+            credential_subject: CredentialSubject {
+                id: subject.to_string(),
+                claims,
+            },
+            proof: None,
+        };
+
+        let mut proof = CredentialProof {
+            proof_type: ED25519_SIGNATURE_2020.to_string(),
+            created: issued_at,
+            verification_method: self.verification_method(),
+            proof_purpose: "assertionMethod".to_string(),
+            proof_value: String::new(),
+        };
+        let signature = self.signing_key.sign(&credential.signing_input(&proof)?);
+        proof.proof_value = multibase::encode(multibase::Base::Base58Btc, signature.to_bytes());
+        credential.proof = Some(proof);
+
+        Ok(credential)
+    }
+}
+
+/// Registry of revoked credential IDs
+///
+/// Clones share the same registry, so a revocation is seen by every
+/// verifier holding a clone.
+#[derive(Debug, Clone, Default)]
+pub struct RevocationList {
+    /// IDs of revoked credentials
+    revoked: Arc<RwLock<HashSet<String>>>,
+}
+
+impl RevocationList {
+    /// Create an empty revocation list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Revoke a credential by ID
+    pub fn revoke(&self, credential_id: &str) {
+        self.revoked.write().unwrap().insert(credential_id.to_string());
+    }
+
+    /// Whether the credential's status entry has been revoked
+    pub fn is_revoked(&self, credential: &VerifiableCredential) -> bool {
+        credential.credential_status.as_ref()
+            .is_some_and(|status| {
+                status.status_type == REVOCATION_LIST_STATUS && self.revoked.read().unwrap().contains(&status.id)
+            })
+    }
+}
+
+/// Decode an Ed25519 public key from verification method material
+fn ed25519_public_key(material: &PublicKeyMaterial) -> Result<VerifyingKey> {
+    let bytes = match material {
+        PublicKeyMaterial::PublicKeyMultibase { public_key_multibase } => {
+            let (_base, decoded) = multibase::decode(public_key_multibase)
+                .map_err(|e| Error::DidDocumentError(format!("Invalid multibase encoding: {}", e)))?;
+            if decoded.len() == 32 {
+                decoded
+            } else {
+                // did:key documents carry the multicodec-prefixed key
+                match DidKey::new().decode_multicodec_key(&decoded)? {
+                    (KeyType::Ed25519, key) => key,
+                    (key_type, _) => return Err(Error::NotSupported(format!(
+                        "Verification method key type {:?} cannot verify Ed25519 signatures", key_type
+                    ))),
+                }
+            }
+        }
+        PublicKeyMaterial::PublicKeyBase58 { public_key_base58 } => bs58::decode(public_key_base58)
+            .into_vec()
+            .map_err(|e| Error::DidDocumentError(format!("Invalid base58 encoding: {}", e)))?,
+        _ => return Err(Error::NotSupported("Unsupported public key material for Ed25519".into())),
+    };
+
+    let bytes: [u8; 32] = bytes.as_slice().try_into()
+        .map_err(|_| Error::DidDocumentError("Ed25519 public key must be 32 bytes".into()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| Error::CryptoError(format!("Invalid Ed25519 public key: {}", e)))
+}
+
+/// Serialize JSON with object keys sorted, so equal documents hash equally
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries.into_iter()
+                .map(|(key, value)| format!("{}:{}", Value::String(key.clone()), canonical_json(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::DidMethod;
+
+    fn degree_claims() -> HashMap<String, Value> {
+        let mut claims = HashMap::new();
+        claims.insert("degree".to_string(), serde_json::json!({"type": "BachelorDegree", "name": "Computer Science"}));
+        claims
+    }
+
+    #[test]
+    fn test_issued_credential_verifies_against_issuer_document() {
+        let issuer = CredentialIssuer::generate().unwrap();
+        let subject = Did::new("key", "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK");
+        let credential = issuer.issue(&subject, degree_claims()).unwrap();
+        let document = DidKey::new().create_document(issuer.did()).unwrap();
+
+        assert_eq!(credential.issuer, issuer.did().to_string());
+        assert!(credential.proof.as_ref().unwrap().proof_value.starts_with('z'));
+        assert!(credential.verify_proof(&document).unwrap());
+
+        // Survives a round trip through JSON
+        let json = serde_json::to_string(&credential).unwrap();
+        let parsed: VerifiableCredential = serde_json::from_str(&json).unwrap();
+        assert!(parsed.verify_proof(&document).unwrap());
+
+        // Another issuer's key does not verify it
+        let other = CredentialIssuer::generate().unwrap();
+        let mut forged = credential.clone();
+        forged.issuer = other.did().to_string();
+        forged.proof.as_mut().unwrap().verification_method = other.verification_method();
+        let other_document = DidKey::new().create_document(other.did()).unwrap();
+        assert!(!forged.verify_proof(&other_document).unwrap());
+    }
+
+    #[test]
+    fn test_validity_period_and_revocation() {
+        let issuer = CredentialIssuer::generate().unwrap().with_validity(Duration::days(30));
+        let subject = Did::new("key", "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK");
+        let credential = issuer.issue(&subject, degree_claims()).unwrap();
+
+        assert!(credential.is_valid_at(Utc::now()));
+        assert!(!credential.is_valid_at(Utc::now() + Duration::days(31)));
+        assert!(!credential.is_valid_at(credential.issuance_date - Duration::seconds(1)));
+
+        let revocations = RevocationList::new();
+        let shared = revocations.clone();
+        assert!(!revocations.is_revoked(&credential));
+        revocations.revoke(&credential.id);
+        assert!(shared.is_revoked(&credential));
+    }
+}
@@ -114,7 +114,7 @@ impl DidKey {
     }
 
     /// Decode multicodec key to get key type and public key
-    fn decode_multicodec_key(&self, data: &[u8]) -> Result<(KeyType, Vec<u8>)> {
+    pub(crate) fn decode_multicodec_key(&self, data: &[u8]) -> Result<(KeyType, Vec<u8>)> {
         if data.is_empty() {
             return Err(Error::DidMethodError("Empty key data".into()));
         }
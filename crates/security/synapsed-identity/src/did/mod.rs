@@ -6,6 +6,8 @@
 //! - Key rotation and lifecycle management
 //! - Local-first storage with encryption
 //! - Zero-knowledge proof integration
+//! - BBS selective disclosure of credential attributes
+//! - Verifiable Credentials with Ed25519Signature2020 proofs
 
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
@@ -17,15 +19,18 @@ pub mod methods;
 pub mod resolver;
 pub mod key_management;
 pub mod zkp;
+pub mod bbs;
 pub mod storage;
 pub mod zkp_subscription;
 pub mod recovery_system;
+pub mod credentials;
 
 pub use document::{DidDocument, VerificationMethod, Service, DidMetadata, PublicKeyMaterial, VerificationRelationship};
 pub use methods::{DidKey, DidWeb, DidMethod};
 pub use resolver::{DidResolver, ResolutionResult};
 pub use key_management::{KeyRotationManager, KeyHierarchy, RecoveryMechanism, EncryptedKeyMaterial};
 pub use zkp::{ZkpVerifier, AnonymousCredential, ProofRequest};
+pub use bbs::{BbsKeyPair, BbsPublicKey, issue_bbs_credential, create_selective_presentation, verify_bbs_presentation};
 pub use storage::{LocalFirstStorage, SyncManager, ContactVault};
 pub use zkp_subscription::{
     AnonymousSubscription, SubscriptionTier, SubscriptionProof, VerificationResult,
@@ -33,6 +38,7 @@ pub use zkp_subscription::{
     generate_subscription_proof, verify_subscription_proof
 };
 pub use recovery_system::{RecoveryMethod, RecoveryData, SecretShare, generate_recovery_info};
+pub use credentials::{VerifiableCredential, CredentialSubject, CredentialStatus, CredentialProof, CredentialIssuer, RevocationList};
 
 /// DID URI structure according to W3C DID Core v1.0
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
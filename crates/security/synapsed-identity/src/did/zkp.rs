@@ -152,6 +152,15 @@ impl ZkpVerifier {
         Ok(true)
     }
 
+    /// Verify a BBS selective disclosure presentation from a registered issuer
+    pub fn verify_presentation(&self, issuer: &Did, presentation: &ZkProof) -> Result<bool> {
+        let key = self.verification_keys.get(&issuer.to_string())
+            .filter(|key| key.key_type == ProofType::BBS_PLUS)
+            .ok_or_else(|| Error::ZkProofError(format!("No BBS verification key registered for {}", issuer)))?;
+        let public_key = super::bbs::BbsPublicKey::from_bytes(&key.key_data)?;
+        super::bbs::verify_bbs_presentation(presentation, &public_key)
+    }
+
     /// Check if proof satisfies the proof request
     fn proof_satisfies_request(&self, proof: &ZkProof, request: &ProofRequest) -> Result<bool> {
         // Verify proof covers required attributes
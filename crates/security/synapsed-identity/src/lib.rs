@@ -52,6 +52,9 @@ pub mod auth;
 /// Authorization module for access control
 pub mod authorization;
 
+/// Audit logging of authentication and authorization decisions
+pub mod audit;
+
 /// Cryptographic utilities
 pub mod crypto;
 
@@ -174,6 +177,7 @@ pub struct IdentityManager<S, A, Z, M> {
     authenticator: A,
     authorizer: Z,
     session_manager: M,
+    audit_sink: std::sync::Arc<dyn audit::AuditSink>,
 }
 
 // Implement core traits for Identity
@@ -329,18 +333,50 @@ where
     }
 
     /// Authenticate a user with credentials
+    ///
+    /// The decision is recorded with the audit sink whether or not it succeeds.
     pub async fn authenticate(&self, credentials: A::Credentials) -> Result<Identity> {
-        self.authenticator.authenticate(credentials).await
+        use audit::{AuditEvent, AuditOutcome};
+
+        let claimed_subject = self.authenticator.claimed_subject(&credentials);
+        let result = self.authenticator.authenticate(credentials).await;
+        let event = match &result {
+            Ok(identity) => AuditEvent::authentication(
+                Some(identity.username.clone()),
+                AuditOutcome::Allow,
+                "credentials accepted",
+            ),
+            Err(e) => AuditEvent::authentication(claimed_subject, AuditOutcome::Deny, e.to_string()),
+        };
+        self.audit_sink.record(event).await;
+        result
     }
 
     /// Check if an identity is authorized for a resource/action
+    ///
+    /// The decision is recorded with the audit sink. An authorizer error is
+    /// recorded as a denial with the error as its reason.
     pub async fn authorize(
         &self,
         identity: &Identity,
         resource: &str,
         action: &str,
     ) -> Result<bool> {
-        self.authorizer.authorize(identity, resource, action).await
+        use audit::{AuditEvent, AuditOutcome};
+
+        let result = self.authorizer.authorize(identity, resource, action).await;
+        let (outcome, reason) = match &result {
+            Ok(true) => (AuditOutcome::Allow, "permitted by authorizer".to_string()),
+            Ok(false) => (
+                AuditOutcome::Deny,
+                format!("{} is not permitted to {} {}", identity.username, action, resource),
+            ),
+            Err(e) => (AuditOutcome::Deny, e.to_string()),
+        };
+        self.audit_sink
+            .record(AuditEvent::authorization(&identity.username, resource, action, outcome, reason))
+            .await;
+        result
     }
 
     /// Create a new session for an identity
@@ -375,6 +411,7 @@ pub struct IdentityManagerBuilder<S, A, Z, M> {
     authenticator: Option<A>,
     authorizer: Option<Z>,
     session_manager: Option<M>,
+    audit_sink: Option<std::sync::Arc<dyn audit::AuditSink>>,
 }
 
 impl<S, A, Z, M> IdentityManagerBuilder<S, A, Z, M> {
@@ -384,6 +421,7 @@ impl<S, A, Z, M> IdentityManagerBuilder<S, A, Z, M> {
             authenticator: None,
             authorizer: None,
             session_manager: None,
+            audit_sink: None,
         }
     }
 
@@ -405,6 +443,12 @@ impl<S, A, Z, M> IdentityManagerBuilder<S, A, Z, M> {
         self
     }
 
+    /// Set where access decisions are recorded, [`audit::TracingAuditSink`] by default
+    pub fn with_audit_sink(mut self, sink: std::sync::Arc<dyn audit::AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
     /// Build the IdentityManager
     pub async fn build(self) -> Result<IdentityManager<S, A, Z, session::InMemorySessionManager>>
     where
@@ -425,11 +469,15 @@ impl<S, A, Z, M> IdentityManagerBuilder<S, A, Z, M> {
             session::SessionConfig::default()
         );
 
+        let audit_sink = self.audit_sink
+            .unwrap_or_else(|| std::sync::Arc::new(audit::TracingAuditSink));
+
         Ok(IdentityManager {
             storage,
             authenticator,
             authorizer,
             session_manager,
+            audit_sink,
         })
     }
 }
@@ -441,6 +489,8 @@ pub struct DidIdentityManagerBuilder {
     key_manager: Option<did::KeyRotationManager>,
     zkp_verifier: Option<did::ZkpVerifier>,
     storage: Option<did::LocalFirstStorage>,
+    issuer: Option<did::CredentialIssuer>,
+    revocations: Option<did::RevocationList>,
 }
 
 #[cfg(feature = "did-core")]
@@ -451,6 +501,8 @@ impl DidIdentityManagerBuilder {
             key_manager: None,
             zkp_verifier: None,
             storage: None,
+            issuer: None,
+            revocations: None,
         }
     }
 
@@ -478,6 +530,18 @@ impl DidIdentityManagerBuilder {
         self
     }
 
+    /// Set the issuer for verifiable credentials
+    pub fn with_issuer(mut self, issuer: did::CredentialIssuer) -> Self {
+        self.issuer = Some(issuer);
+        self
+    }
+
+    /// Set the shared revocation registry checked when verifying credentials
+    pub fn with_revocation_list(mut self, revocations: did::RevocationList) -> Self {
+        self.revocations = Some(revocations);
+        self
+    }
+
     /// Build the DID identity manager
     pub async fn build(self) -> Result<DidIdentityManager> {
         let resolver = self.resolver.unwrap_or_default();
@@ -494,6 +558,8 @@ impl DidIdentityManagerBuilder {
             key_manager,
             zkp_verifier,
             storage,
+            issuer: self.issuer,
+            revocations: self.revocations.unwrap_or_default(),
         })
     }
 }
@@ -505,6 +571,8 @@ pub struct DidIdentityManager {
     key_manager: did::KeyRotationManager,
     zkp_verifier: did::ZkpVerifier,
     storage: did::LocalFirstStorage,
+    issuer: Option<did::CredentialIssuer>,
+    revocations: did::RevocationList,
 }
 
 #[cfg(feature = "did-core")]
@@ -537,10 +605,54 @@ impl DidIdentityManager {
     }
 
     /// Verify anonymous credential
-    pub fn verify_credential(&mut self, presentation: &did::zkp::CredentialPresentation, request: &did::zkp::ProofRequest) -> Result<bool> {
+    pub fn verify_credential_presentation(&mut self, presentation: &did::zkp::CredentialPresentation, request: &did::zkp::ProofRequest) -> Result<bool> {
         self.zkp_verifier.verify_credential_presentation(presentation, request)
     }
 
+    /// Verify anonymous credential
+    #[deprecated(note = "use `verify_credential_presentation`")]
+    pub fn verify_credential(&mut self, presentation: &did::zkp::CredentialPresentation, request: &did::zkp::ProofRequest) -> Result<bool> {
+        self.verify_credential_presentation(presentation, request)
+    }
+
+    /// Issue a verifiable credential about a subject, signed with the issuer's DID key
+    pub fn issue_credential(
+        &self,
+        subject_did: &did::Did,
+        claims: std::collections::HashMap<String, serde_json::Value>,
+    ) -> Result<did::VerifiableCredential> {
+        let issuer = self.issuer.as_ref().ok_or_else(|| {
+            Error::Configuration("Credential issuer is required".to_string())
+        })?;
+        issuer.issue(subject_did, claims)
+    }
+
+    /// Verify a verifiable credential
+    ///
+    /// Resolves the issuer DID to check the proof. Expired, not yet valid
+    /// and revoked credentials fail verification.
+    pub async fn verify_verifiable_credential(&mut self, credential: &did::VerifiableCredential) -> Result<bool> {
+        if !credential.is_valid_at(chrono::Utc::now()) || self.revocations.is_revoked(credential) {
+            return Ok(false);
+        }
+
+        let issuer = did::Did::parse(&credential.issuer)?;
+        let document = self.resolve_did(&issuer).await?.ok_or_else(|| {
+            Error::DidResolutionError(format!("Issuer DID not found: {}", credential.issuer))
+        })?;
+        credential.verify_proof(&document)
+    }
+
+    /// Revoke a credential so that it no longer verifies
+    pub fn revoke_credential(&self, credential_id: &str) {
+        self.revocations.revoke(credential_id);
+    }
+
+    /// The revocation registry, to share with other verifiers
+    pub fn revocation_list(&self) -> did::RevocationList {
+        self.revocations.clone()
+    }
+
     /// Store DID document
     pub async fn store_document(&mut self, document: &did::DidDocument) -> Result<()> {
         self.storage.store_did_document(document).await
@@ -2,11 +2,12 @@
 //! 
 //! Provides:
 //! - Session creation and validation
-//! - Session storage and retrieval
+//! - Session storage and retrieval through any [`SessionStore`]
 //! - Session expiration and renewal
 //! - Concurrent session management
 
 use crate::{Error, Result, IdentityTrait as Identity};
+use crate::storage::{InMemorySessionStore, SessionStore, StoredSession};
 use zeroize::Zeroize;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 
@@ -43,7 +44,7 @@ pub struct Session {
     pub id: String,
     /// User ID
     pub user_id: String,
-    /// Session token
+    /// Session token, empty when the session was loaded from its store
     pub token: String,
     /// Session metadata
     pub metadata: SessionMetadata,
@@ -74,16 +75,21 @@ pub struct SessionMetadata {
 
 // Default implementation is automatically derived
 
-/// In-memory session manager implementation
-pub struct InMemorySessionManager {
-    /// Sessions storage
-    sessions: std::sync::RwLock<BTreeMap<String, Session>>,
-    /// User to sessions mapping
-    user_sessions: std::sync::RwLock<BTreeMap<String, Vec<String>>>,
+/// Session manager that keeps sessions in a [`SessionStore`]
+///
+/// Instances sharing a store, such as a Redis-backed `RedisSessionStore`,
+/// share sessions. Stores only keep a hash of each session token, so tokens
+/// are only returned when a session is created or refreshed.
+pub struct StoreSessionManager<S: SessionStore> {
+    /// Session storage
+    store: S,
     /// Session configuration
     config: SessionConfig,
 }
 
+/// Session manager keeping sessions in process memory
+pub type InMemorySessionManager = StoreSessionManager<InMemorySessionStore>;
+
 /// Session configuration
 #[derive(Debug, Clone)]
 pub struct SessionConfig {
@@ -114,11 +120,19 @@ impl Default for SessionConfig {
 impl InMemorySessionManager {
     /// Create new in-memory session manager
     pub fn new(config: SessionConfig) -> Self {
-        Self {
-            sessions: std::sync::RwLock::new(BTreeMap::new()),
-            user_sessions: std::sync::RwLock::new(BTreeMap::new()),
-            config,
-        }
+        Self::with_store(InMemorySessionStore::new(), config)
+    }
+}
+
+impl<S: SessionStore> StoreSessionManager<S> {
+    /// Create a session manager over any session store
+    pub fn with_store(store: S, config: SessionConfig) -> Self {
+        Self { store, config }
+    }
+
+    /// The underlying session store
+    pub fn store(&self) -> &S {
+        &self.store
     }
     
     /// Generate session token
@@ -128,55 +142,75 @@ impl InMemorySessionManager {
         OsRng.fill_bytes(&mut bytes);
         STANDARD.encode(&bytes)
     }
-    
-    /// Clean up expired sessions
-    fn cleanup_expired(&self) -> Result<()> {
-        let now = chrono::Utc::now();
-        let mut sessions = self.sessions.write().unwrap();
-        let mut user_sessions = self.user_sessions.write().unwrap();
-        
-        let expired: Vec<String> = sessions
+
+    /// Hash a session token for storage
+    fn hash_token(token: &str) -> String {
+        use sha2::{Sha256, Digest};
+        Sha256::digest(token.as_bytes())
             .iter()
-            .filter(|(_, session)| session.expires_at < now)
-            .map(|(id, _)| id.clone())
-            .collect();
-        
-        for session_id in expired {
-            if let Some(session) = sessions.remove(&session_id) {
-                // Remove from user sessions
-                if let Some(user_session_ids) = user_sessions.get_mut(&session.user_id) {
-                    user_session_ids.retain(|id| id != &session_id);
-                }
-            }
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Convert a session for storage
+    fn to_stored(session: &Session) -> StoredSession {
+        StoredSession {
+            id: session.id.clone(),
+            user_id: session.user_id.clone(),
+            token_hash: Self::hash_token(&session.token),
+            ip_address: session.metadata.ip_address.clone(),
+            user_agent: session.metadata.user_agent.clone(),
+            device_id: session.metadata.device_id.clone(),
+            location: session.metadata.location.clone(),
+            attributes: session.metadata.attributes.clone(),
+            created_at: session.created_at,
+            last_accessed: session.last_accessed,
+            expires_at: session.expires_at,
+            active: session.active,
+        }
+    }
+
+    /// Convert a stored session, without its token
+    fn from_stored(stored: StoredSession) -> Session {
+        Session {
+            id: stored.id,
+            user_id: stored.user_id,
+            token: String::new(),
+            metadata: SessionMetadata {
+                ip_address: stored.ip_address,
+                user_agent: stored.user_agent,
+                device_id: stored.device_id,
+                location: stored.location,
+                attributes: stored.attributes,
+            },
+            created_at: stored.created_at,
+            last_accessed: stored.last_accessed,
+            expires_at: stored.expires_at,
+            active: stored.active,
         }
-        
-        Ok(())
     }
 }
 
-impl SessionManager for InMemorySessionManager {
+impl<S: SessionStore> SessionManager for StoreSessionManager<S> {
     fn create_session(&self, identity: &dyn Identity, metadata: SessionMetadata) -> Result<Session> {
         // Clean up expired sessions first
-        self.cleanup_expired()?;
+        self.store.cleanup_expired()?;
         
         let user_id = identity.id().to_string();
         
         // Check max sessions per user
         if let Some(max_sessions) = self.config.max_sessions_per_user {
-            let user_sessions = self.user_sessions.read().unwrap();
-            if let Some(session_ids) = user_sessions.get(&user_id) {
-                if session_ids.len() >= max_sessions {
-                    return Err(Error::SessionError(
-                        format!("Maximum sessions ({}) reached for user", max_sessions)
-                    ));
-                }
+            if self.store.get_user_sessions(&user_id)?.len() >= max_sessions {
+                return Err(Error::SessionError(
+                    format!("Maximum sessions ({}) reached for user", max_sessions)
+                ));
             }
         }
         
         // Create new session
         let session = Session {
             id: format!("sess_{}", uuid::Uuid::new_v4()),
-            user_id: user_id.clone(),
+            user_id,
             token: Self::generate_token(),
             metadata,
             created_at: chrono::Utc::now(),
@@ -185,56 +219,36 @@ impl SessionManager for InMemorySessionManager {
             active: true,
         };
         
-        // Store session
-        {
-            let mut sessions = self.sessions.write().unwrap();
-            sessions.insert(session.id.clone(), session.clone());
-        }
-        
-        // Update user sessions
-        {
-            let mut user_sessions = self.user_sessions.write().unwrap();
-            user_sessions
-                .entry(user_id)
-                .or_insert_with(Vec::new)
-                .push(session.id.clone());
-        }
+        self.store.store_session(&Self::to_stored(&session))?;
         
         Ok(session)
     }
     
     fn get_session(&self, session_id: &str) -> Result<Option<Session>> {
-        let sessions = self.sessions.read().unwrap();
-        Ok(sessions.get(session_id).cloned())
+        Ok(self.store.get_session(session_id)?.map(Self::from_stored))
     }
     
     fn validate_session(&self, session_id: &str) -> Result<bool> {
         let now = chrono::Utc::now();
-        let mut sessions = self.sessions.write().unwrap();
         
-        if let Some(session) = sessions.get_mut(session_id) {
-            // Check if expired
-            if session.expires_at < now {
-                session.active = false;
-                return Ok(false);
-            }
-            
-            // Check idle timeout
-            if let Some(idle_timeout) = self.config.idle_timeout {
-                let idle_duration = now - session.last_accessed;
-                if idle_duration.num_seconds() as u64 > idle_timeout {
-                    session.active = false;
-                    return Ok(false);
-                }
-            }
-            
-            // Update last accessed
-            session.last_accessed = now;
-            
-            Ok(session.active)
-        } else {
-            Ok(false)
+        let Some(mut session) = self.store.get_session(session_id)? else {
+            return Ok(false);
+        };
+        
+        // Check if expired or idle
+        let idle = self.config.idle_timeout
+            .is_some_and(|idle_timeout| (now - session.last_accessed).num_seconds() as u64 > idle_timeout);
+        if session.expires_at < now || idle {
+            session.active = false;
+            self.store.update_session(&session)?;
+            return Ok(false);
         }
+        
+        // Update last accessed
+        session.last_accessed = now;
+        self.store.update_session(&session)?;
+        
+        Ok(session.active)
     }
     
     fn refresh_session(&self, session_id: &str) -> Result<Session> {
@@ -242,75 +256,41 @@ impl SessionManager for InMemorySessionManager {
             return Err(Error::SessionError("Session refresh not allowed".into()));
         }
         
-        let mut sessions = self.sessions.write().unwrap();
-        
-        if let Some(session) = sessions.get_mut(session_id) {
-            if !session.active {
-                return Err(Error::SessionError("Cannot refresh inactive session".into()));
-            }
-            
-            // Extend expiration
-            session.expires_at = chrono::Utc::now() + 
-                chrono::Duration::seconds(self.config.refresh_lifetime as i64);
-            session.last_accessed = chrono::Utc::now();
-            
-            // Generate new token
-            session.token = Self::generate_token();
-            
-            Ok(session.clone())
-        } else {
-            Err(Error::NotFound("Session not found".into()))
+        let stored = self.store.get_session(session_id)?
+            .ok_or_else(|| Error::NotFound("Session not found".into()))?;
+        if !stored.active {
+            return Err(Error::SessionError("Cannot refresh inactive session".into()));
         }
+        
+        let mut session = Self::from_stored(stored);
+        
+        // Extend expiration
+        session.expires_at = chrono::Utc::now() + 
+            chrono::Duration::seconds(self.config.refresh_lifetime as i64);
+        session.last_accessed = chrono::Utc::now();
+        
+        // Generate new token
+        session.token = Self::generate_token();
+        
+        self.store.update_session(&Self::to_stored(&session))?;
+        
+        Ok(session)
     }
     
     fn invalidate_session(&self, session_id: &str) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let mut user_sessions = self.user_sessions.write().unwrap();
-        
-        if let Some(mut session) = sessions.remove(session_id) {
-            // Clear sensitive data
-            session.token.zeroize();
-            
-            // Remove from user sessions
-            if let Some(user_session_ids) = user_sessions.get_mut(&session.user_id) {
-                user_session_ids.retain(|id| id != session_id);
-            }
-            
-            Ok(())
-        } else {
-            Err(Error::NotFound("Session not found".into()))
-        }
+        self.store.delete_session(session_id)
     }
     
     fn get_user_sessions(&self, user_id: &str) -> Result<Vec<Session>> {
-        let sessions = self.sessions.read().unwrap();
-        let user_sessions = self.user_sessions.read().unwrap();
-        
-        if let Some(session_ids) = user_sessions.get(user_id) {
-            let user_sessions: Vec<Session> = session_ids
-                .iter()
-                .filter_map(|id| sessions.get(id).cloned())
-                .collect();
-            
-            Ok(user_sessions)
-        } else {
-            Ok(Vec::new())
-        }
+        Ok(self.store
+            .get_user_sessions(user_id)?
+            .into_iter()
+            .map(Self::from_stored)
+            .collect())
     }
     
     fn invalidate_user_sessions(&self, user_id: &str) -> Result<()> {
-        let mut sessions = self.sessions.write().unwrap();
-        let mut user_sessions = self.user_sessions.write().unwrap();
-        
-        if let Some(session_ids) = user_sessions.remove(user_id) {
-            for session_id in session_ids {
-                if let Some(mut session) = sessions.remove(&session_id) {
-                    session.token.zeroize();
-                }
-            }
-        }
-        
-        Ok(())
+        self.store.delete_user_sessions(user_id)
     }
 }
 
@@ -428,4 +408,38 @@ mod tests {
         let result = manager.create_session(&identity, SessionMetadata::default());
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+    
+    #[cfg(feature = "redis")]
+    #[test]
+    fn test_session_shared_through_redis() {
+        use crate::storage::RedisSessionStore;
+        
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        let prefix = format!("synapsed:test:{}:", uuid::Uuid::new_v4());
+        let store = || RedisSessionStore::new(&url).unwrap().with_key_prefix(&prefix);
+        
+        let first = StoreSessionManager::with_store(store(), SessionConfig::default());
+        let second = StoreSessionManager::with_store(store(), SessionConfig::default());
+        let identity = MockIdentity {
+            id: "user123".to_string(),
+        };
+        
+        let metadata = SessionMetadata {
+            device_id: Some("device-1".to_string()),
+            ..Default::default()
+        };
+        let session = first.create_session(&identity, metadata).unwrap();
+        
+        // The second instance sees the session created by the first
+        let loaded = second.get_session(&session.id).unwrap().unwrap();
+        assert_eq!(loaded.user_id, "user123");
+        assert_eq!(loaded.metadata.device_id.as_deref(), Some("device-1"));
+        assert!(second.validate_session(&session.id).unwrap());
+        assert_eq!(second.get_user_sessions("user123").unwrap().len(), 1);
+        
+        // Invalidating on one instance removes it for both
+        second.invalidate_session(&session.id).unwrap();
+        assert!(first.get_session(&session.id).unwrap().is_none());
+        assert!(first.get_user_sessions("user123").unwrap().is_empty());
+    }
+}
@@ -307,13 +307,15 @@ impl CredentialStore for InMemoryCredentialStore {
 }
 
 /// In-memory session store
-struct InMemorySessionStore {
+#[derive(Default)]
+pub struct InMemorySessionStore {
     sessions: std::sync::RwLock<BTreeMap<String, StoredSession>>,
     user_sessions: std::sync::RwLock<BTreeMap<String, Vec<String>>>,
 }
 
 impl InMemorySessionStore {
-    fn new() -> Self {
+    /// Create an empty session store
+    pub fn new() -> Self {
         Self {
             sessions: std::sync::RwLock::new(BTreeMap::new()),
             user_sessions: std::sync::RwLock::new(BTreeMap::new()),
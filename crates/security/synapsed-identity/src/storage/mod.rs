@@ -14,10 +14,17 @@
 pub mod memory;
 pub mod traits;
 
+/// Redis session store
+#[cfg(feature = "redis")]
+pub mod redis;
+
 pub use traits::{
     IdentityStore, UserStore, CredentialStore, SessionStore,
     User, StoredCredential, StoredSession
 };
+pub use memory::InMemorySessionStore;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisSessionStore;
 
 /// Storage backend for all identity-related data
 pub trait IdentityStorageBackend: Send + Sync {
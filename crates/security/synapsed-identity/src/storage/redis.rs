@@ -0,0 +1,183 @@
+//! Redis session store
+//!
+//! Sessions are shared by every server instance using the same Redis, and
+//! survive restarts of those instances. Each session is a JSON value whose key
+//! TTL matches the session's remaining lifetime, so Redis expires sessions on
+//! its own. A set per user indexes that user's session IDs; IDs whose session
+//! has expired are pruned when the set is read.
+
+use super::traits::{SessionStore, StoredSession};
+use crate::{Error, Result};
+use ::redis::{Commands, Connection, RedisResult};
+use std::sync::Mutex;
+
+/// Default prefix for Redis keys
+pub const DEFAULT_KEY_PREFIX: &str = "synapsed:identity:";
+
+/// Session store backed by Redis
+pub struct RedisSessionStore {
+    /// Redis client
+    client: ::redis::Client,
+    /// Connection, opened on first use and reopened after connection errors
+    connection: Mutex<Option<Connection>>,
+    /// Prefix for all keys written by this store
+    key_prefix: String,
+}
+
+impl RedisSessionStore {
+    /// Create a store for the Redis server at `url`, e.g. `redis://127.0.0.1/`
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self::with_client(::redis::Client::open(url)?))
+    }
+
+    /// Create a store using an existing client
+    pub fn with_client(client: ::redis::Client) -> Self {
+        Self {
+            client,
+            connection: Mutex::new(None),
+            key_prefix: DEFAULT_KEY_PREFIX.to_string(),
+        }
+    }
+
+    /// Use a different key prefix, e.g. to separate deployments sharing a Redis
+    pub fn with_key_prefix(mut self, prefix: &str) -> Self {
+        self.key_prefix = prefix.to_string();
+        self
+    }
+
+    fn session_key(&self, session_id: &str) -> String {
+        format!("{}session:{}", self.key_prefix, session_id)
+    }
+
+    fn user_key(&self, user_id: &str) -> String {
+        format!("{}user_sessions:{}", self.key_prefix, user_id)
+    }
+
+    /// Run commands on the shared connection
+    fn with_connection<T>(&self, f: impl FnOnce(&mut Connection) -> RedisResult<T>) -> Result<T> {
+        let mut slot = self.connection.lock().unwrap();
+        let connection = match slot.as_mut() {
+            Some(connection) => connection,
+            None => slot.insert(self.client.get_connection()?),
+        };
+
+        let result = f(connection);
+        if let Err(e) = &result {
+            if e.is_io_error() || e.is_connection_dropped() {
+                *slot = None;
+            }
+        }
+        Ok(result?)
+    }
+
+    /// Seconds until the session expires, or `None` if it already has
+    fn ttl(session: &StoredSession) -> Option<u64> {
+        let remaining = (session.expires_at - chrono::Utc::now()).num_seconds();
+        (remaining > 0).then_some(remaining as u64)
+    }
+}
+
+impl SessionStore for RedisSessionStore {
+    fn store_session(&self, session: &StoredSession) -> Result<()> {
+        let Some(ttl) = Self::ttl(session) else {
+            return Ok(());
+        };
+        let value = serde_json::to_string(session)?;
+        let session_key = self.session_key(&session.id);
+        let user_key = self.user_key(&session.user_id);
+
+        self.with_connection(|conn| {
+            ::redis::pipe()
+                .atomic()
+                .set_ex(&session_key, value, ttl).ignore()
+                .sadd(&user_key, &session.id).ignore()
+                .query(conn)
+        })
+    }
+
+    fn get_session(&self, session_id: &str) -> Result<Option<StoredSession>> {
+        let key = self.session_key(session_id);
+        let value: Option<String> = self.with_connection(|conn| conn.get(&key))?;
+        value.map(|value| serde_json::from_str(&value).map_err(Error::from)).transpose()
+    }
+
+    fn get_user_sessions(&self, user_id: &str) -> Result<Vec<StoredSession>> {
+        let user_key = self.user_key(user_id);
+        let session_ids: Vec<String> = self.with_connection(|conn| conn.smembers(&user_key))?;
+        if session_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = session_ids.iter().map(|id| self.session_key(id)).collect();
+        let values: Vec<Option<String>> = self.with_connection(|conn| {
+            ::redis::cmd("MGET").arg(&keys).query(conn)
+        })?;
+
+        let mut sessions = Vec::new();
+        let mut expired = Vec::new();
+        for (session_id, value) in session_ids.iter().zip(values) {
+            match value {
+                Some(value) => sessions.push(serde_json::from_str(&value)?),
+                None => expired.push(session_id),
+            }
+        }
+        if !expired.is_empty() {
+            self.with_connection(|conn| conn.srem::<_, _, ()>(&user_key, expired))?;
+        }
+
+        Ok(sessions)
+    }
+
+    fn update_session(&self, session: &StoredSession) -> Result<()> {
+        let key = self.session_key(&session.id);
+        let Some(ttl) = Self::ttl(session) else {
+            let user_key = self.user_key(&session.user_id);
+            return self.with_connection(|conn| {
+                ::redis::pipe()
+                    .atomic()
+                    .del(&key).ignore()
+                    .srem(&user_key, &session.id).ignore()
+                    .query(conn)
+            });
+        };
+        let value = serde_json::to_string(session)?;
+
+        // XX only overwrites an existing key, so expired sessions stay gone
+        let updated: Option<String> = self.with_connection(|conn| {
+            ::redis::cmd("SET").arg(&key).arg(value).arg("XX").arg("EX").arg(ttl).query(conn)
+        })?;
+        match updated {
+            Some(_) => Ok(()),
+            None => Err(Error::NotFound(format!("Session {} not found", session.id))),
+        }
+    }
+
+    fn delete_session(&self, session_id: &str) -> Result<()> {
+        let session = self.get_session(session_id)?
+            .ok_or_else(|| Error::NotFound(format!("Session {} not found", session_id)))?;
+        let session_key = self.session_key(session_id);
+        let user_key = self.user_key(&session.user_id);
+
+        self.with_connection(|conn| {
+            ::redis::pipe()
+                .atomic()
+                .del(&session_key).ignore()
+                .srem(&user_key, session_id).ignore()
+                .query(conn)
+        })
+    }
+
+    fn delete_user_sessions(&self, user_id: &str) -> Result<()> {
+        let user_key = self.user_key(user_id);
+        let session_ids: Vec<String> = self.with_connection(|conn| conn.smembers(&user_key))?;
+        let mut keys: Vec<String> = session_ids.iter().map(|id| self.session_key(id)).collect();
+        keys.push(user_key);
+
+        self.with_connection(|conn| conn.del(keys))
+    }
+
+    fn cleanup_expired(&self) -> Result<usize> {
+        // Redis expires session keys itself; stale index entries are pruned on read
+        Ok(0)
+    }
+}
@@ -1,6 +1,8 @@
 //! Storage traits for identity persistence
 
 use crate::{Result, IdentityTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // String, Vec, and Box are available in std prelude
 
@@ -61,7 +63,7 @@ pub struct StoredCredential {
 }
 
 /// Stored session data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredSession {
     /// Session ID
     pub id: String,
@@ -73,6 +75,12 @@ pub struct StoredSession {
     pub ip_address: Option<String>,
     /// User agent
     pub user_agent: Option<String>,
+    /// Device ID
+    pub device_id: Option<String>,
+    /// Location
+    pub location: Option<String>,
+    /// Custom attributes
+    pub attributes: BTreeMap<String, serde_json::Value>,
     /// Created at
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Last accessed
@@ -57,6 +57,76 @@ mod did_tests {
         assert_eq!(loaded_doc.unwrap().id, did);
     }
 
+    #[tokio::test]
+    async fn test_verifiable_credential_issuance() {
+        let temp_dir = TempDir::new().unwrap();
+        let key_manager = did::KeyRotationManager::new(
+            did::key_management::RotationPolicy::default(),
+            did::key_management::RecoveryMechanism::default(),
+        );
+        let storage = did::LocalFirstStorage::new(
+            temp_dir.path(),
+            "test_password",
+            did::storage::StorageConfig::default(),
+        ).unwrap();
+
+        let issuer = did::CredentialIssuer::generate().unwrap()
+            .with_validity(chrono::Duration::days(365));
+        let issuer_did = issuer.did().clone();
+        let mut manager = IdentityManager::with_did_support()
+            .with_key_manager(key_manager)
+            .with_storage(storage)
+            .with_issuer(issuer)
+            .build()
+            .await
+            .unwrap();
+
+        // Issue a credential about another identity
+        let subject = manager.create_did("key").await.unwrap();
+        let mut claims = std::collections::HashMap::new();
+        claims.insert("role".to_string(), serde_json::json!("auditor"));
+        claims.insert("clearance".to_string(), serde_json::json!(3));
+        let credential = manager.issue_credential(&subject, claims).unwrap();
+
+        assert_eq!(credential.issuer, issuer_did.to_string());
+        assert_eq!(credential.credential_subject.id, subject.to_string());
+        assert_eq!(credential.proof.as_ref().unwrap().proof_type, "Ed25519Signature2020");
+        assert!(credential.expiration_date.unwrap() > Utc::now());
+        assert!(manager.verify_verifiable_credential(&credential).await.unwrap());
+
+        // Tampering with a claim breaks the proof
+        let mut tampered = credential.clone();
+        tampered.credential_subject.claims.insert("clearance".to_string(), serde_json::json!(5));
+        assert!(!manager.verify_verifiable_credential(&tampered).await.unwrap());
+
+        // Expired and revoked credentials no longer verify
+        let mut expired = credential.clone();
+        expired.expiration_date = Some(Utc::now() - chrono::Duration::days(1));
+        assert!(!manager.verify_verifiable_credential(&expired).await.unwrap());
+
+        // Revocations reach every verifier sharing the registry
+        let verifier_dir = TempDir::new().unwrap();
+        let mut verifier = IdentityManager::with_did_support()
+            .with_key_manager(did::KeyRotationManager::new(
+                did::key_management::RotationPolicy::default(),
+                did::key_management::RecoveryMechanism::default(),
+            ))
+            .with_storage(did::LocalFirstStorage::new(
+                verifier_dir.path(),
+                "test_password",
+                did::storage::StorageConfig::default(),
+            ).unwrap())
+            .with_revocation_list(manager.revocation_list())
+            .build()
+            .await
+            .unwrap();
+        assert!(verifier.verify_verifiable_credential(&credential).await.unwrap());
+
+        manager.revoke_credential(&credential.id);
+        assert!(!manager.verify_verifiable_credential(&credential).await.unwrap());
+        assert!(!verifier.verify_verifiable_credential(&credential).await.unwrap());
+    }
+
     #[test]
     fn test_did_parsing() {
         let did_str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
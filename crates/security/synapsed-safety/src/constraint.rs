@@ -216,6 +216,102 @@ impl DefaultConstraintEngine {
         Ok(true)
     }
 
+    /// Evaluate a constraint against a state history, oldest first
+    ///
+    /// Snapshot constraints only look at the latest state, temporal
+    /// constraints at the states within their window.
+    fn evaluate_over_history(&self, constraint: &Constraint, history: &[SafetyState]) -> Result<bool> {
+        let Some(latest) = history.last() else {
+            return Ok(true);
+        };
+        let temporal = match &constraint.constraint_type {
+            ConstraintType::TemporalConstraint(temporal) if constraint.enabled => temporal,
+            _ => return self.evaluate_constraint(constraint, latest),
+        };
+        let window_start = latest.timestamp - chrono::Duration::milliseconds(temporal.window_ms as i64);
+
+        match temporal.operator {
+            TemporalOperator::Sustained => {
+                // Find where the run of failures ending at the latest state began
+                let mut failing_since = None;
+                for state in history.iter().rev() {
+                    if self.evaluate_constraint(constraint, state)? {
+                        break;
+                    }
+                    failing_since = Some(state.timestamp);
+                }
+                Ok(!matches!(failing_since, Some(since) if since <= window_start))
+            }
+            TemporalOperator::Always => {
+                for state in history.iter().rev().take_while(|state| state.timestamp >= window_start) {
+                    if !self.evaluate_constraint(constraint, state)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    /// Evaluate constraints against a state history, collecting violations and warnings
+    fn check_constraints<'a>(
+        &self,
+        constraints: impl Iterator<Item = &'a Constraint>,
+        history: &[SafetyState],
+    ) -> (Vec<ConstraintViolation>, Vec<ConstraintWarning>) {
+        let mut violations = Vec::new();
+        let mut warnings = Vec::new();
+
+        for constraint in constraints {
+            match self.evaluate_over_history(constraint, history) {
+                Ok(true) => {
+                    // Constraint satisfied
+                    debug!("Constraint {} satisfied", constraint.id);
+                }
+                Ok(false) => {
+                    // Constraint violated
+                    warn!("Constraint {} violated", constraint.id);
+                    violations.push(ConstraintViolation {
+                        constraint_id: constraint.id.clone(),
+                        severity: constraint.severity,
+                        message: format!("Constraint violated: {}", constraint.description),
+                        actual_value: StateValue::String("violation detected".to_string()),
+                        expected_value: Some(StateValue::String("constraint satisfied".to_string())),
+                        timestamp: chrono::Utc::now(),
+                        context: HashMap::new(),
+                    });
+                }
+                Err(e) => {
+                    // Evaluation error - treat as warning
+                    warn!("Failed to evaluate constraint {}: {}", constraint.id, e);
+                    warnings.push(ConstraintWarning {
+                        constraint_id: constraint.id.clone(),
+                        message: format!("Evaluation error: {}", e),
+                        suggested_action: Some("Check constraint rule syntax".to_string()),
+                        timestamp: chrono::Utc::now(),
+                    });
+                }
+            }
+        }
+
+        (violations, warnings)
+    }
+
+    /// Longest window of any temporal constraint
+    ///
+    /// State history must be retained for at least this long for temporal
+    /// constraints to be evaluated over their full window.
+    pub fn longest_temporal_window(&self) -> Option<Duration> {
+        self.constraints
+            .read()
+            .values()
+            .filter_map(|constraint| match &constraint.constraint_type {
+                ConstraintType::TemporalConstraint(temporal) => Some(temporal.window()),
+                _ => None,
+            })
+            .max()
+    }
+
     /// Update engine statistics
     fn update_stats(&self, evaluation_time: Duration, violations_found: usize) {
         let mut stats = self.stats.write();
@@ -305,41 +401,7 @@ impl ConstraintEngine for DefaultConstraintEngine {
         }
 
         let constraints = self.constraints.read();
-        let mut violations = Vec::new();
-        let mut warnings = Vec::new();
-
-        // Evaluate all active constraints
-        for constraint in constraints.values() {
-            match self.evaluate_constraint(constraint, state) {
-                Ok(true) => {
-                    // Constraint satisfied
-                    debug!("Constraint {} satisfied", constraint.id);
-                }
-                Ok(false) => {
-                    // Constraint violated
-                    warn!("Constraint {} violated", constraint.id);
-                    violations.push(ConstraintViolation {
-                        constraint_id: constraint.id.clone(),
-                        severity: constraint.severity,
-                        message: format!("Constraint violated: {}", constraint.description),
-                        actual_value: StateValue::String("violation detected".to_string()),
-                        expected_value: Some(StateValue::String("constraint satisfied".to_string())),
-                        timestamp: chrono::Utc::now(),
-                        context: HashMap::new(),
-                    });
-                }
-                Err(e) => {
-                    // Evaluation error - treat as warning
-                    warn!("Failed to evaluate constraint {}: {}", constraint.id, e);
-                    warnings.push(ConstraintWarning {
-                        constraint_id: constraint.id.clone(),
-                        message: format!("Evaluation error: {}", e),
-                        suggested_action: Some("Check constraint rule syntax".to_string()),
-                        timestamp: chrono::Utc::now(),
-                    });
-                }
-            }
-        }
+        let (violations, warnings) = self.check_constraints(constraints.values(), std::slice::from_ref(state));
 
         let evaluation_time = start_time.elapsed();
         let result = ValidationResult {
@@ -382,6 +444,34 @@ impl ConstraintEngine for DefaultConstraintEngine {
         Ok(result)
     }
 
+    async fn validate_history(&self, history: &[SafetyState]) -> Result<ValidationResult> {
+        let Some(latest) = history.last() else {
+            return Err(SafetyError::StateInconsistent {
+                description: "Cannot validate an empty state history".to_string(),
+            });
+        };
+        let start_time = Instant::now();
+        debug!("Validating state {} with {} states of history", latest.id, history.len());
+
+        let constraints = self.constraints.read();
+        let (violations, warnings) = self.check_constraints(constraints.values(), history);
+
+        let evaluation_time = start_time.elapsed();
+        self.update_stats(evaluation_time, violations.len());
+
+        Ok(ValidationResult {
+            passed: violations.is_empty(),
+            violations,
+            warnings,
+            metadata: ValidationMetadata {
+                duration_ms: evaluation_time.as_millis() as u64,
+                constraints_evaluated: constraints.len() as u32,
+                engine: "DefaultConstraintEngine".to_string(),
+                metrics: HashMap::new(),
+            },
+        })
+    }
+
     async fn validate_constraints(
         &self,
         state: &SafetyState,
@@ -398,7 +488,7 @@ impl ConstraintEngine for DefaultConstraintEngine {
         for constraint_id in constraint_ids {
             if let Some(constraint) = constraints.get(constraint_id) {
                 evaluated_count += 1;
-                match self.evaluate_constraint(constraint, state) {
+                match self.evaluate_over_history(constraint, std::slice::from_ref(state)) {
                     Ok(true) => {
                         debug!("Constraint {} satisfied", constraint.id);
                     }
@@ -833,4 +923,40 @@ mod tests {
         assert_eq!(stats.violations_found, 0);
         assert!(stats.avg_evaluation_time_ms > 0.0);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_sustained_temporal_constraint() {
+        let mut engine = DefaultConstraintEngine::new();
+        let mut constraint = DefaultConstraintEngine::memory_constraint(0.9);
+        constraint.constraint_type =
+            ConstraintType::TemporalConstraint(TemporalConstraint::sustained(Duration::from_secs(30)));
+        engine.add_constraint(constraint).await.unwrap();
+
+        let monitor = crate::monitor::DefaultSafetyMonitor::new();
+        monitor.set_history_retention(engine.longest_temporal_window().unwrap());
+
+        let start = chrono::Utc::now();
+        let sample = |seconds: i64, memory_fraction: f64| {
+            let mut state = create_test_state();
+            state.timestamp = start + chrono::Duration::seconds(seconds);
+            state.resource_usage.memory_usage = (state.resource_usage.memory_limit as f64 * memory_fraction) as u64;
+            state
+        };
+
+        // A 10 second spike over 90% is tolerated
+        for seconds in (0..=60).step_by(5) {
+            let usage = if (20..30).contains(&seconds) { 0.95 } else { 0.5 };
+            monitor.record_state(sample(seconds, usage));
+            let result = engine.validate_history(&monitor.state_history()).await.unwrap();
+            assert!(result.passed, "spike tripped the constraint at {}s", seconds);
+        }
+
+        // Usage staying over 90% trips it once it has lasted 30 seconds
+        for seconds in (65..=95).step_by(5) {
+            monitor.record_state(sample(seconds, 0.95));
+            let result = engine.validate_history(&monitor.state_history()).await.unwrap();
+            assert_eq!(result.passed, seconds < 95, "unexpected result at {}s", seconds);
+        }
+        assert!(monitor.state_history().len() <= 8);
+    }
+}
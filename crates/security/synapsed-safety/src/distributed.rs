@@ -0,0 +1,620 @@
+//! Distributed checkpoint coordination
+//!
+//! This module takes consistent global snapshots across nodes using the
+//! Chandy-Lamport protocol. The initiating node records its local state and
+//! sends a marker on every outgoing channel. A node receiving its first marker
+//! of a snapshot records its own state and forwards markers in turn. Messages
+//! arriving on a channel after the node recorded its state, but before that
+//! channel's marker, were in flight when the snapshot was taken and are
+//! recorded as the channel's state.
+//!
+//! Every node reports its local snapshot to the initiator, which assembles the
+//! [`GlobalSnapshot`] once all nodes have reported. Nodes are connected by
+//! in-process FIFO channels, see [`SnapshotNode::connect`].
+
+use crate::error::{Result, SafetyError};
+use crate::types::SafetyState;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Identifier of a node taking part in global snapshots
+pub type NodeId = String;
+
+/// Identifier of a global snapshot
+pub type SnapshotId = Uuid;
+
+/// Default time to wait for all nodes to report their local snapshots
+pub const DEFAULT_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Application message received from a peer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeMessage {
+    /// Sending node
+    pub from: NodeId,
+    /// Message content
+    pub payload: Vec<u8>,
+}
+
+/// State of one node within a global snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalSnapshot {
+    /// Node the snapshot was taken on
+    pub node: NodeId,
+    /// Local state when the node recorded its snapshot
+    pub state: SafetyState,
+    /// Messages in flight towards this node per sending peer, in send order
+    pub in_flight: HashMap<NodeId, Vec<Vec<u8>>>,
+}
+
+/// Consistent snapshot of all nodes and the channels between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSnapshot {
+    /// Snapshot identifier
+    pub id: SnapshotId,
+    /// Node that initiated the snapshot
+    pub initiator: NodeId,
+    /// When the snapshot was assembled
+    pub timestamp: DateTime<Utc>,
+    /// Local snapshot of every node
+    pub nodes: HashMap<NodeId, LocalSnapshot>,
+}
+
+impl GlobalSnapshot {
+    /// Messages in flight when the snapshot was taken, as sender, receiver and payload
+    pub fn in_flight_messages(&self) -> impl Iterator<Item = (&NodeId, &NodeId, &[u8])> {
+        self.nodes.values().flat_map(|local| {
+            local.in_flight.iter().flat_map(move |(from, payloads)| {
+                payloads
+                    .iter()
+                    .map(move |payload| (from, &local.node, payload.as_slice()))
+            })
+        })
+    }
+
+    /// Write the snapshot to `path`, replacing any previous snapshot there
+    ///
+    /// The snapshot goes to a temporary file that is synced and renamed into
+    /// place, and the directory is synced after the rename, so a crash leaves
+    /// either the previous or the new snapshot on disk.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = serde_json::to_vec(self).map_err(|e| SafetyError::Serialization {
+            message: format!("Failed to serialize global snapshot {}: {}", self.id, e),
+        })?;
+
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+
+        let mut file = File::create(&temp_path).map_err(io_error)?;
+        file.write_all(&bytes).map_err(io_error)?;
+        file.sync_all().map_err(io_error)?;
+        drop(file);
+
+        fs::rename(&temp_path, path).map_err(io_error)?;
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        File::open(dir).and_then(|dir| dir.sync_all()).map_err(io_error)?;
+
+        debug!("Global snapshot {} saved to {}", self.id, path.display());
+        Ok(())
+    }
+
+    /// Read a snapshot written by [`save`](Self::save)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = fs::read(path.as_ref()).map_err(io_error)?;
+        serde_json::from_slice(&bytes).map_err(|e| SafetyError::Serialization {
+            message: format!("Failed to deserialize global snapshot: {}", e),
+        })
+    }
+}
+
+fn io_error(e: std::io::Error) -> SafetyError {
+    SafetyError::Io {
+        message: e.to_string(),
+    }
+}
+
+/// Message on a channel between two nodes
+#[derive(Debug)]
+enum Envelope {
+    Application {
+        from: NodeId,
+        payload: Vec<u8>,
+    },
+    Marker {
+        from: NodeId,
+        snapshot: SnapshotId,
+        initiator: NodeId,
+    },
+    Report {
+        snapshot: SnapshotId,
+        local: Box<LocalSnapshot>,
+    },
+}
+
+/// Snapshot in progress on a node
+#[derive(Debug)]
+struct Recording {
+    initiator: NodeId,
+    state: SafetyState,
+    in_flight: HashMap<NodeId, Vec<Vec<u8>>>,
+    /// Incoming channels whose marker has not arrived yet
+    open: HashSet<NodeId>,
+}
+
+/// Local snapshots gathered by the initiator
+#[derive(Debug)]
+struct Collection {
+    reports: HashMap<NodeId, LocalSnapshot>,
+    done: oneshot::Sender<GlobalSnapshot>,
+}
+
+#[derive(Debug)]
+struct NodeInner {
+    state: SafetyState,
+    recordings: HashMap<SnapshotId, Recording>,
+    collections: HashMap<SnapshotId, Collection>,
+}
+
+/// Node taking part in global snapshots
+///
+/// Sends, receives and local state changes are applied under one lock, so a
+/// recorded state always matches the messages sent and received before it.
+/// Markers and reports are handled by [`receive`](Self::receive), so every
+/// node must keep receiving while a snapshot is in progress.
+#[derive(Debug)]
+pub struct SnapshotNode {
+    id: NodeId,
+    peers: HashMap<NodeId, mpsc::UnboundedSender<Envelope>>,
+    incoming: tokio::sync::Mutex<mpsc::UnboundedReceiver<Envelope>>,
+    inner: Mutex<NodeInner>,
+    snapshot_timeout: Duration,
+}
+
+impl SnapshotNode {
+    /// Create fully connected nodes with the given initial states
+    ///
+    /// Node identifiers must be unique, later duplicates are skipped.
+    pub fn connect(nodes: Vec<(NodeId, SafetyState)>) -> Vec<SnapshotNode> {
+        let mut receivers = HashMap::new();
+        let mut senders = HashMap::new();
+        for (id, _) in &nodes {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            senders.insert(id.clone(), sender);
+            receivers.insert(id.clone(), receiver);
+        }
+
+        nodes
+            .into_iter()
+            .filter_map(|(id, state)| {
+                let receiver = receivers.remove(&id)?;
+                let peers = senders
+                    .iter()
+                    .filter(|(peer, _)| **peer != id)
+                    .map(|(peer, sender)| (peer.clone(), sender.clone()))
+                    .collect();
+                Some(Self {
+                    id,
+                    peers,
+                    incoming: tokio::sync::Mutex::new(receiver),
+                    inner: Mutex::new(NodeInner {
+                        state,
+                        recordings: HashMap::new(),
+                        collections: HashMap::new(),
+                    }),
+                    snapshot_timeout: DEFAULT_SNAPSHOT_TIMEOUT,
+                })
+            })
+            .collect()
+    }
+
+    /// Set how long [`initiate_snapshot`](Self::initiate_snapshot) waits for reports
+    pub fn with_snapshot_timeout(mut self, timeout: Duration) -> Self {
+        self.snapshot_timeout = timeout;
+        self
+    }
+
+    /// Identifier of this node
+    pub fn id(&self) -> &NodeId {
+        &self.id
+    }
+
+    /// Nodes this node is connected to
+    pub fn peers(&self) -> impl Iterator<Item = &NodeId> {
+        self.peers.keys()
+    }
+
+    /// Current local state
+    pub fn state(&self) -> SafetyState {
+        self.inner.lock().state.clone()
+    }
+
+    /// Change the local state
+    pub fn update_state(&self, update: impl FnOnce(&mut SafetyState)) {
+        update(&mut self.inner.lock().state);
+    }
+
+    /// Send a message to a peer, applying `update` to the local state with the send
+    pub fn send(
+        &self,
+        to: &str,
+        payload: Vec<u8>,
+        update: impl FnOnce(&mut SafetyState),
+    ) -> Result<()> {
+        let peer = self.peers.get(to).ok_or_else(|| SafetyError::Configuration {
+            message: format!("Node {} is not connected to {}", self.id, to),
+        })?;
+
+        let mut inner = self.inner.lock();
+        peer.send(Envelope::Application {
+            from: self.id.clone(),
+            payload,
+        })
+        .map_err(|_| SafetyError::External {
+            message: format!("Node {} is no longer reachable", to),
+        })?;
+        update(&mut inner.state);
+        Ok(())
+    }
+
+    /// Receive the next message from a peer, applying `apply` to the local state
+    ///
+    /// Returns `None` once no peer can send any more messages.
+    pub async fn receive(
+        &self,
+        apply: impl FnOnce(&mut SafetyState, &NodeMessage),
+    ) -> Result<Option<NodeMessage>> {
+        let mut apply = Some(apply);
+        let mut incoming = self.incoming.lock().await;
+
+        while let Some(envelope) = incoming.recv().await {
+            let mut inner = self.inner.lock();
+            match envelope {
+                Envelope::Application { from, payload } => {
+                    for recording in inner.recordings.values_mut() {
+                        if recording.open.contains(&from) {
+                            recording.in_flight.entry(from.clone()).or_default().push(payload.clone());
+                        }
+                    }
+
+                    let message = NodeMessage { from, payload };
+                    if let Some(apply) = apply.take() {
+                        apply(&mut inner.state, &message);
+                    }
+                    return Ok(Some(message));
+                }
+                Envelope::Marker { from, snapshot, initiator } => {
+                    match inner.recordings.get_mut(&snapshot) {
+                        Some(recording) => {
+                            recording.open.remove(&from);
+                        }
+                        None => self.start_recording(&mut inner, snapshot, initiator, Some(&from)),
+                    }
+                    self.complete_recording(&mut inner, snapshot);
+                }
+                Envelope::Report { snapshot, local } => self.collect(&mut inner, snapshot, *local),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Take a consistent snapshot of all nodes
+    ///
+    /// Waits until every node has reported its local snapshot, or fails with
+    /// [`SafetyError::Timeout`] after the snapshot timeout.
+    pub async fn initiate_snapshot(&self) -> Result<GlobalSnapshot> {
+        let snapshot = Uuid::new_v4();
+        let (done, reported) = oneshot::channel();
+
+        info!("Node {} initiating global snapshot {}", self.id, snapshot);
+        {
+            let mut inner = self.inner.lock();
+            inner.collections.insert(snapshot, Collection {
+                reports: HashMap::new(),
+                done,
+            });
+            self.start_recording(&mut inner, snapshot, self.id.clone(), None);
+            self.complete_recording(&mut inner, snapshot);
+        }
+
+        match tokio::time::timeout(self.snapshot_timeout, reported).await {
+            Ok(Ok(global)) => Ok(global),
+            Ok(Err(_)) => Err(SafetyError::StateInconsistent {
+                description: format!("Global snapshot {} was abandoned", snapshot),
+            }),
+            Err(_) => {
+                self.inner.lock().collections.remove(&snapshot);
+                Err(SafetyError::Timeout {
+                    duration_ms: self.snapshot_timeout.as_millis() as u64,
+                })
+            }
+        }
+    }
+
+    /// Record the local state and send markers on every outgoing channel
+    fn start_recording(
+        &self,
+        inner: &mut NodeInner,
+        snapshot: SnapshotId,
+        initiator: NodeId,
+        marker_from: Option<&NodeId>,
+    ) {
+        debug!("Node {} recording state for snapshot {}", self.id, snapshot);
+
+        let mut open: HashSet<NodeId> = self.peers.keys().cloned().collect();
+        if let Some(from) = marker_from {
+            open.remove(from);
+        }
+        let state = inner.state.clone();
+        inner.recordings.insert(snapshot, Recording {
+            initiator: initiator.clone(),
+            state,
+            in_flight: HashMap::new(),
+            open,
+        });
+
+        for (peer, channel) in &self.peers {
+            let marker = Envelope::Marker {
+                from: self.id.clone(),
+                snapshot,
+                initiator: initiator.clone(),
+            };
+            if channel.send(marker).is_err() {
+                warn!("Node {} could not send snapshot marker to {}", self.id, peer);
+            }
+        }
+    }
+
+    /// Report the local snapshot once markers arrived on all incoming channels
+    fn complete_recording(&self, inner: &mut NodeInner, snapshot: SnapshotId) {
+        if !inner.recordings.get(&snapshot).is_some_and(|recording| recording.open.is_empty()) {
+            return;
+        }
+        let Some(recording) = inner.recordings.remove(&snapshot) else {
+            return;
+        };
+
+        let local = LocalSnapshot {
+            node: self.id.clone(),
+            state: recording.state,
+            in_flight: recording.in_flight,
+        };
+        if recording.initiator == self.id {
+            self.collect(inner, snapshot, local);
+        } else if let Some(channel) = self.peers.get(&recording.initiator) {
+            if channel.send(Envelope::Report { snapshot, local: Box::new(local) }).is_err() {
+                warn!("Node {} could not report snapshot {}", self.id, snapshot);
+            }
+        }
+    }
+
+    /// Gather a local snapshot on the initiator
+    fn collect(&self, inner: &mut NodeInner, snapshot: SnapshotId, local: LocalSnapshot) {
+        let Some(collection) = inner.collections.get_mut(&snapshot) else {
+            debug!("Node {} ignoring report for unknown snapshot {}", self.id, snapshot);
+            return;
+        };
+        collection.reports.insert(local.node.clone(), local);
+        if collection.reports.len() <= self.peers.len() {
+            return;
+        }
+
+        if let Some(collection) = inner.collections.remove(&snapshot) {
+            info!("Global snapshot {} complete", snapshot);
+            let _ = collection.done.send(GlobalSnapshot {
+                id: snapshot,
+                initiator: self.id.clone(),
+                timestamp: Utc::now(),
+                nodes: collection.reports,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use std::sync::Arc;
+
+    fn account(balance: i64) -> SafetyState {
+        SafetyState {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            values: HashMap::from([
+                ("balance".to_string(), StateValue::Integer(balance)),
+                ("sent".to_string(), StateValue::Array(vec![])),
+                ("received".to_string(), StateValue::Array(vec![])),
+            ]),
+            active_constraints: vec![],
+            resource_usage: ResourceUsage {
+                cpu_usage: 0.0,
+                memory_usage: 0,
+                memory_limit: 1024,
+                network_usage: 0,
+                disk_io: 0,
+                file_descriptors: 0,
+                thread_count: 0,
+                custom_resources: HashMap::new(),
+            },
+            health_indicators: HealthIndicators {
+                overall_health: 1.0,
+                component_health: HashMap::new(),
+                error_rates: HashMap::new(),
+                response_times: HashMap::new(),
+                availability: HashMap::new(),
+                performance_indicators: HashMap::new(),
+            },
+            metadata: StateMetadata {
+                source: "test".to_string(),
+                version: "1.0".to_string(),
+                checksum: String::new(),
+                size_bytes: 0,
+                compression_ratio: None,
+                tags: vec![],
+                properties: HashMap::new(),
+            },
+        }
+    }
+
+    fn balance(state: &SafetyState) -> i64 {
+        state.values["balance"].as_i64().unwrap()
+    }
+
+    fn message_ids(state: &SafetyState, key: &str) -> Vec<String> {
+        match &state.values[key] {
+            StateValue::Array(ids) => ids
+                .iter()
+                .map(|id| match id {
+                    StateValue::String(id) => id.clone(),
+                    other => panic!("unexpected message id {:?}", other),
+                })
+                .collect(),
+            other => panic!("unexpected {} value {:?}", key, other),
+        }
+    }
+
+    fn adjust(state: &mut SafetyState, amount: i64, key: &str, id: String) {
+        let balance = balance(state) + amount;
+        state.values.insert("balance".to_string(), StateValue::Integer(balance));
+        if let Some(StateValue::Array(ids)) = state.values.get_mut(key) {
+            ids.push(StateValue::String(id));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_global_snapshot_is_consistent() {
+        let names = ["a", "b", "c"];
+        let nodes: Vec<Arc<SnapshotNode>> = SnapshotNode::connect(
+            names.iter().map(|name| (name.to_string(), account(100))).collect(),
+        )
+        .into_iter()
+        .map(Arc::new)
+        .collect();
+
+        let mut receivers = Vec::new();
+        let mut senders = Vec::new();
+        for node in &nodes {
+            let receiving = node.clone();
+            receivers.push(tokio::spawn(async move {
+                while receiving
+                    .receive(|state, message| {
+                        let id = String::from_utf8(message.payload.clone()).unwrap();
+                        adjust(state, 1, "received", id);
+                    })
+                    .await
+                    .unwrap()
+                    .is_some()
+                {}
+            }));
+
+            let sending = node.clone();
+            senders.push(tokio::spawn(async move {
+                let peers: Vec<NodeId> = sending.peers().cloned().collect();
+                for i in 0..200 {
+                    let id = format!("{}-{}", sending.id(), i);
+                    let to = &peers[i % peers.len()];
+                    sending
+                        .send(to, id.clone().into_bytes(), |state| adjust(state, -1, "sent", id))
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            }));
+        }
+
+        tokio::task::yield_now().await;
+        let snapshot = nodes[0].initiate_snapshot().await.unwrap();
+        for sender in senders {
+            sender.await.unwrap();
+        }
+        for receiver in receivers {
+            receiver.abort();
+        }
+
+        assert_eq!(snapshot.initiator, "a");
+        assert_eq!(snapshot.nodes.len(), names.len());
+
+        let sent: HashSet<String> = snapshot
+            .nodes
+            .values()
+            .flat_map(|local| message_ids(&local.state, "sent"))
+            .collect();
+        let received: HashSet<String> = snapshot
+            .nodes
+            .values()
+            .flat_map(|local| message_ids(&local.state, "received"))
+            .collect();
+        let in_flight: Vec<String> = snapshot
+            .in_flight_messages()
+            .map(|(_, _, payload)| String::from_utf8(payload.to_vec()).unwrap())
+            .collect();
+
+        // No message is received before it was sent
+        assert!(received.is_subset(&sent));
+        // Messages in flight were sent but not yet received
+        for id in &in_flight {
+            assert!(sent.contains(id));
+            assert!(!received.contains(id));
+        }
+        // Every sent message is either received or in flight
+        assert_eq!(sent.len(), received.len() + in_flight.len());
+
+        let total: i64 = snapshot.nodes.values().map(|local| balance(&local.state)).sum();
+        assert_eq!(total + in_flight.len() as i64, 300);
+    }
+
+    #[tokio::test]
+    async fn test_single_node_snapshot() {
+        let node = SnapshotNode::connect(vec![("solo".to_string(), account(5))]).remove(0);
+
+        let snapshot = node.initiate_snapshot().await.unwrap();
+        assert_eq!(snapshot.nodes.len(), 1);
+        assert_eq!(balance(&snapshot.nodes["solo"].state), 5);
+        assert_eq!(snapshot.in_flight_messages().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_saved_snapshot_loads_back() {
+        let node = SnapshotNode::connect(vec![("solo".to_string(), account(5))]).remove(0);
+        let snapshot = node.initiate_snapshot().await.unwrap();
+
+        let dir = std::env::temp_dir().join(format!("synapsed-snapshot-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("global.json");
+        snapshot.save(&path).unwrap();
+        // Saving again replaces the previous snapshot
+        snapshot.save(&path).unwrap();
+
+        let loaded = GlobalSnapshot::load(&path).unwrap();
+        assert_eq!(loaded.id, snapshot.id);
+        assert_eq!(balance(&loaded.nodes["solo"].state), 5);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_times_out_without_receivers() {
+        let node = SnapshotNode::connect(vec![
+            ("a".to_string(), account(0)),
+            ("b".to_string(), account(0)),
+        ])
+        .remove(0)
+        .with_snapshot_timeout(Duration::from_millis(50));
+
+        let result = node.initiate_snapshot().await;
+        assert!(matches!(result, Err(SafetyError::Timeout { duration_ms: 50 })));
+    }
+}
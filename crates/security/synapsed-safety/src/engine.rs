@@ -4,9 +4,10 @@
 //! integrating monitoring, constraint checking, and rollback capabilities.
 
 use crate::constraint::DefaultConstraintEngine;
+use crate::distributed::{GlobalSnapshot, SnapshotNode};
 use crate::error::{Result, SafetyError};
 use crate::monitor::DefaultSafetyMonitor;
-use crate::rollback::DefaultRollbackManager;
+use crate::rollback::{DefaultRollbackManager, RollbackConfig};
 use crate::traits::{ConstraintEngine, RollbackManager, SafetyMonitor, StateChangeCallback};
 use crate::types::*;
 use async_trait::async_trait;
@@ -40,6 +41,8 @@ pub struct SafetyEngine {
     engine_state: Arc<RwLock<EngineState>>,
     /// Last successful checkpoint
     last_checkpoint: Arc<RwLock<Option<CheckpointId>>>,
+    /// Node used for distributed snapshots
+    snapshot_node: Option<Arc<SnapshotNode>>,
 }
 
 /// Internal engine state
@@ -133,7 +136,9 @@ impl SafetyEngine {
         
         let constraint_engine = Arc::new(RwLock::new(DefaultConstraintEngine::new()));
         let safety_monitor = Arc::new(RwLock::new(DefaultSafetyMonitor::new()));
-        let rollback_manager = Arc::new(RwLock::new(DefaultRollbackManager::new()));
+        let rollback_manager = Arc::new(RwLock::new(DefaultRollbackManager::with_config(
+            RollbackConfig::from(&config),
+        )));
         
         let engine = Self {
             constraint_engine,
@@ -160,6 +165,7 @@ impl SafetyEngine {
             violation_tx: Arc::new(RwLock::new(None)),
             engine_state: Arc::new(RwLock::new(EngineState::Initializing)),
             last_checkpoint: Arc::new(RwLock::new(None)),
+            snapshot_node: None,
         };
         
         info!("SafetyEngine initialized successfully");
@@ -192,6 +198,7 @@ impl SafetyEngine {
                     violation_tx: Arc::clone(&self.violation_tx),
                     engine_state: Arc::clone(&self.engine_state),
                     last_checkpoint: Arc::clone(&self.last_checkpoint),
+                    snapshot_node: self.snapshot_node.clone(),
                 })),
             };
             
@@ -253,9 +260,23 @@ impl SafetyEngine {
         let mut constraint_engine = self.constraint_engine.write();
         constraint_engine.add_constraint(constraint).await?;
         
+        // Keep enough history for the longest temporal constraint
+        if let Some(window) = constraint_engine.longest_temporal_window() {
+            self.safety_monitor.read().set_history_retention(window);
+        }
+        
         Ok(())
     }
 
+    /// Check whether a set of constraints can all hold at once
+    ///
+    /// Run this before adding constraints to catch contradictory safety rules
+    /// at configuration time.
+    #[cfg(feature = "formal-verification")]
+    pub fn check_satisfiability(&self, constraints: &[Constraint]) -> Result<crate::formal::SatisfiabilityResult> {
+        crate::formal::Z3Verifier::new().check_satisfiability(constraints)
+    }
+
     /// Execute an operation with safety monitoring
     pub async fn execute_safe<F, T>(&self, operation: F) -> Result<T>
     where
@@ -362,6 +383,27 @@ impl SafetyEngine {
         Ok(checkpoint_id)
     }
 
+    /// Attach the node this engine takes part in distributed snapshots with
+    pub fn attach_snapshot_node(&mut self, node: Arc<SnapshotNode>) {
+        info!("Attaching snapshot node: {}", node.id());
+        self.snapshot_node = Some(node);
+    }
+
+    /// Take a consistent snapshot of all nodes connected to the attached node
+    pub async fn initiate_global_snapshot(&self) -> Result<GlobalSnapshot> {
+        let node = self.snapshot_node.as_ref().ok_or_else(|| SafetyError::Configuration {
+            message: "No snapshot node attached".to_string(),
+        })?;
+
+        let snapshot = node.initiate_snapshot().await?;
+        info!(
+            "Global snapshot {} captured across {} nodes",
+            snapshot.id,
+            snapshot.nodes.len()
+        );
+        Ok(snapshot)
+    }
+
     /// Commit a checkpoint (mark as permanent)
     pub async fn commit_checkpoint(&self, checkpoint_id: &CheckpointId) -> Result<()> {
         debug!("Committing checkpoint: {}", checkpoint_id);
@@ -396,8 +438,13 @@ impl SafetyEngine {
         let start_time = Instant::now();
         
         let current_state = self.safety_monitor.read().get_current_state().await?;
+        let mut history = self.safety_monitor.read().state_history();
+        if history.last().map(|state| state.id) != Some(current_state.id) {
+            history.push(current_state);
+        }
+        
         let constraint_engine = self.constraint_engine.read();
-        let result = constraint_engine.validate_state(&current_state).await?;
+        let result = constraint_engine.validate_history(&history).await?;
         
         let validation_time = start_time.elapsed();
         
@@ -576,6 +623,7 @@ impl SafetyEngine {
             violation_tx: Arc::clone(&self.violation_tx),
             engine_state: Arc::clone(&self.engine_state),
             last_checkpoint: Arc::clone(&self.last_checkpoint),
+            snapshot_node: self.snapshot_node.clone(),
         })));
         
         let handle = tokio::spawn(async move {
@@ -818,6 +866,40 @@ mod tests {
         engine.stop().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_global_snapshot_across_engines() {
+        let mut engine = create_test_engine().await;
+        let result = engine.initiate_global_snapshot().await;
+        assert!(matches!(result, Err(SafetyError::Configuration { .. })));
+        
+        engine.start().await.unwrap();
+        sleep(Duration::from_millis(100)).await;
+        
+        let state = engine.safety_monitor.read().get_current_state().await.unwrap();
+        let mut nodes = SnapshotNode::connect(vec![
+            ("local".to_string(), state.clone()),
+            ("remote".to_string(), state),
+        ]);
+        let remote = Arc::new(nodes.pop().unwrap());
+        let local = Arc::new(nodes.pop().unwrap());
+        
+        let receivers: Vec<_> = [local.clone(), remote]
+            .into_iter()
+            .map(|node| tokio::spawn(async move { while node.receive(|_, _| {}).await.unwrap().is_some() {} }))
+            .collect();
+        
+        engine.attach_snapshot_node(local);
+        let snapshot = engine.initiate_global_snapshot().await.unwrap();
+        assert_eq!(snapshot.initiator, "local");
+        assert!(snapshot.nodes.contains_key("local"));
+        assert!(snapshot.nodes.contains_key("remote"));
+        
+        for receiver in receivers {
+            receiver.abort();
+        }
+        engine.stop().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_state_validation() {
         let mut engine = create_test_engine().await;
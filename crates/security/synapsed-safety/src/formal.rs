@@ -4,39 +4,453 @@
 //! like Z3, Coq, and TLA+ for proving safety properties.
 
 #[cfg(feature = "z3")]
-use z3::{Context, Solver, ast};
+use z3::{ast, ast::Ast, Config, Context, SatResult, Solver};
 
 use crate::error::{Result, SafetyError};
 use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Outcome of checking whether a set of constraints can hold together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SatisfiabilityResult {
+    /// All constraints can hold at once
+    Satisfiable {
+        /// Example values of the constraint variables satisfying every constraint
+        assignment: HashMap<String, f64>,
+    },
+    /// The constraints contradict each other
+    Unsatisfiable {
+        /// Constraints that cannot hold together, removing any one of them
+        /// resolves this conflict
+        core: Vec<ConstraintId>,
+    },
+    /// The solver could not decide
+    Unknown {
+        /// Reason reported by the solver
+        reason: String,
+    },
+}
+
+impl SatisfiabilityResult {
+    /// Whether the constraints are known to be satisfiable
+    pub fn is_satisfiable(&self) -> bool {
+        matches!(self, SatisfiabilityResult::Satisfiable { .. })
+    }
+}
+
+/// Arithmetic term of a constraint expression
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    /// Decimal literal, kept as text to encode it exactly
+    Number(String),
+    Variable(String),
+    Neg(Box<Term>),
+    Add(Box<Term>, Box<Term>),
+    Sub(Box<Term>, Box<Term>),
+    Mul(Box<Term>, Box<Term>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// Boolean formula of a constraint expression
+#[derive(Debug, Clone, PartialEq)]
+enum Formula {
+    Literal(bool),
+    Compare(Term, Comparison, Term),
+    Not(Box<Formula>),
+    And(Box<Formula>, Box<Formula>),
+    Or(Box<Formula>, Box<Formula>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 15] = [
+    "<=", ">=", "==", "!=", "&&", "||", "<", ">", "!", "(", ")", "+", "-", "*", "=",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() || c == '.' {
+            let end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+            tokens.push(Token::Number(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            // A single `=` is accepted as equality
+            tokens.push(Token::Symbol(if *symbol == "=" { "==" } else { symbol }));
+            rest = &rest[symbol.len()..];
+        } else {
+            return Err(parse_error(expression, &format!("unexpected character '{}'", c)));
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+fn parse_error(expression: &str, reason: &str) -> SafetyError {
+    SafetyError::Configuration {
+        message: format!("Cannot encode constraint expression '{}': {}", expression, reason),
+    }
+}
+
+/// Recursive descent parser for constraint expressions
+///
+/// Supports comparisons between arithmetic terms over numeric variables,
+/// combined with `&&`, `||` and `!`.
+struct Parser<'a> {
+    expression: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(expression: &'a str) -> Result<Formula> {
+        let mut parser = Self {
+            expression,
+            tokens: tokenize(expression)?,
+            position: 0,
+        };
+        let formula = parser.or()?;
+        match parser.peek() {
+            None => Ok(formula),
+            Some(token) => Err(parser.error(&format!("unexpected {:?}", token))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<()> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", symbol)))
+        }
+    }
+
+    fn error(&self, reason: &str) -> SafetyError {
+        parse_error(self.expression, reason)
+    }
+
+    fn or(&mut self) -> Result<Formula> {
+        let mut formula = self.and()?;
+        while self.eat("||") {
+            formula = Formula::Or(Box::new(formula), Box::new(self.and()?));
+        }
+        Ok(formula)
+    }
+
+    fn and(&mut self) -> Result<Formula> {
+        let mut formula = self.unary()?;
+        while self.eat("&&") {
+            formula = Formula::And(Box::new(formula), Box::new(self.unary()?));
+        }
+        Ok(formula)
+    }
+
+    fn unary(&mut self) -> Result<Formula> {
+        if self.eat("!") {
+            return Ok(Formula::Not(Box::new(self.unary()?)));
+        }
+        match self.peek() {
+            Some(Token::Ident(name)) if name == "true" || name == "false" => {
+                let value = name == "true";
+                self.position += 1;
+                return Ok(Formula::Literal(value));
+            }
+            _ => {}
+        }
+
+        // `(` opens either a nested formula or a parenthesized term
+        let start = self.position;
+        match self.comparison() {
+            Ok(formula) => Ok(formula),
+            Err(e) => {
+                self.position = start;
+                if !self.eat("(") {
+                    return Err(e);
+                }
+                let formula = self.or()?;
+                self.expect(")")?;
+                Ok(formula)
+            }
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Formula> {
+        let left = self.sum()?;
+        let comparison = match self.peek() {
+            Some(Token::Symbol("<")) => Comparison::Lt,
+            Some(Token::Symbol("<=")) => Comparison::Le,
+            Some(Token::Symbol(">")) => Comparison::Gt,
+            Some(Token::Symbol(">=")) => Comparison::Ge,
+            Some(Token::Symbol("==")) => Comparison::Eq,
+            Some(Token::Symbol("!=")) => Comparison::Ne,
+            _ => return Err(self.error("expected a comparison")),
+        };
+        self.position += 1;
+        Ok(Formula::Compare(left, comparison, self.sum()?))
+    }
+
+    fn sum(&mut self) -> Result<Term> {
+        let mut term = self.product()?;
+        loop {
+            if self.eat("+") {
+                term = Term::Add(Box::new(term), Box::new(self.product()?));
+            } else if self.eat("-") {
+                term = Term::Sub(Box::new(term), Box::new(self.product()?));
+            } else {
+                return Ok(term);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Term> {
+        let mut term = self.atom()?;
+        while self.eat("*") {
+            term = Term::Mul(Box::new(term), Box::new(self.atom()?));
+        }
+        Ok(term)
+    }
+
+    fn atom(&mut self) -> Result<Term> {
+        if self.eat("-") {
+            return Ok(Term::Neg(Box::new(self.atom()?)));
+        }
+        if self.eat("(") {
+            let term = self.sum()?;
+            self.expect(")")?;
+            return Ok(term);
+        }
+        match self.peek().cloned() {
+            Some(Token::Number(number)) => {
+                self.position += 1;
+                Ok(Term::Number(number))
+            }
+            Some(Token::Ident(name)) => {
+                self.position += 1;
+                Ok(Term::Variable(name))
+            }
+            _ => Err(self.error("expected a number or variable")),
+        }
+    }
+}
+
+/// Split a decimal literal into an exact numerator and denominator
+fn decimal_fraction(number: &str) -> Option<(String, String)> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if (whole.is_empty() && fraction.is_empty()) || fraction.contains('.') {
+        return None;
+    }
+    let numerator = format!("{}{}", whole, fraction).trim_start_matches('0').to_string();
+    let numerator = if numerator.is_empty() { "0".to_string() } else { numerator };
+    Some((numerator, format!("1{}", "0".repeat(fraction.len()))))
+}
 
 /// Z3-based formal verifier
 #[cfg(feature = "z3")]
 pub struct Z3Verifier {
-    context: Context,
-    solver: Solver,
+    config: Config,
 }
 
 #[cfg(feature = "z3")]
 impl Z3Verifier {
     /// Create a new Z3 verifier
     pub fn new() -> Self {
-        let context = Context::new(&Default::default());
-        let solver = Solver::new(&context);
-        
         Self {
-            context,
-            solver,
+            config: Config::new(),
         }
     }
     
-    /// Verify a constraint using Z3
+    /// Verify that a constraint can be satisfied on its own
     pub fn verify_constraint(&mut self, constraint: &Constraint) -> Result<bool> {
-        // This would translate the constraint to Z3 AST and check satisfiability
-        // For now, placeholder implementation
-        Ok(true)
+        Ok(self.check_satisfiability(std::slice::from_ref(constraint))?.is_satisfiable())
+    }
+
+    /// Check whether all constraints can be satisfied at once
+    ///
+    /// Each constraint's rule expression is encoded over real-valued
+    /// variables shared between constraints. An unsatisfiable result carries
+    /// a minimal set of conflicting constraints.
+    pub fn check_satisfiability(&self, constraints: &[Constraint]) -> Result<SatisfiabilityResult> {
+        let formulas = constraints
+            .iter()
+            .map(|constraint| Parser::parse(&constraint.rule.expression))
+            .collect::<Result<Vec<_>>>()?;
+
+        let context = Context::new(&self.config);
+        let solver = Solver::new(&context);
+        let mut variables = HashMap::new();
+
+        // Each constraint is guarded by an assumption literal so the solver
+        // can report which constraints conflict
+        let mut assumptions = Vec::with_capacity(constraints.len());
+        for (index, formula) in formulas.iter().enumerate() {
+            let encoded = encode_formula(&context, formula, &mut variables)?;
+            let assumption = ast::Bool::new_const(&context, format!("constraint_{}", index));
+            solver.assert(&assumption.implies(&encoded));
+            assumptions.push(assumption);
+        }
+
+        match solver.check_assumptions(&assumptions) {
+            SatResult::Sat => {
+                let model = solver
+                    .get_model()
+                    .ok_or_else(|| SafetyError::VerificationFailed { property: "model unavailable".to_string() })?;
+                let assignment = variables
+                    .iter()
+                    .filter_map(|(name, variable)| {
+                        let (numerator, denominator) = model.eval(variable, true)?.as_real()?;
+                        Some((name.clone(), numerator as f64 / denominator as f64))
+                    })
+                    .collect();
+                Ok(SatisfiabilityResult::Satisfiable { assignment })
+            }
+            SatResult::Unsat => {
+                let mut core: Vec<usize> = solver
+                    .get_unsat_core()
+                    .iter()
+                    .filter_map(|literal| assumptions.iter().position(|assumption| assumption == literal))
+                    .collect();
+
+                // Drop constraints the conflict does not depend on
+                let mut i = 0;
+                while i < core.len() {
+                    let without: Vec<ast::Bool> = core
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .map(|(_, index)| assumptions[*index].clone())
+                        .collect();
+                    if solver.check_assumptions(&without) == SatResult::Unsat {
+                        core.remove(i);
+                    } else {
+                        i += 1;
+                    }
+                }
+
+                core.sort_unstable();
+                Ok(SatisfiabilityResult::Unsatisfiable {
+                    core: core.into_iter().map(|index| constraints[index].id.clone()).collect(),
+                })
+            }
+            SatResult::Unknown => Ok(SatisfiabilityResult::Unknown {
+                reason: solver.get_reason_unknown().unwrap_or_else(|| "unknown".to_string()),
+            }),
+        }
     }
 }
 
+#[cfg(feature = "z3")]
+impl Default for Z3Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "z3")]
+fn encode_formula<'ctx>(
+    context: &'ctx Context,
+    formula: &Formula,
+    variables: &mut HashMap<String, ast::Real<'ctx>>,
+) -> Result<ast::Bool<'ctx>> {
+    Ok(match formula {
+        Formula::Literal(value) => ast::Bool::from_bool(context, *value),
+        Formula::Compare(left, comparison, right) => {
+            let left = encode_term(context, left, variables)?;
+            let right = encode_term(context, right, variables)?;
+            match comparison {
+                Comparison::Lt => left.lt(&right),
+                Comparison::Le => left.le(&right),
+                Comparison::Gt => left.gt(&right),
+                Comparison::Ge => left.ge(&right),
+                Comparison::Eq => left._eq(&right),
+                Comparison::Ne => left._eq(&right).not(),
+            }
+        }
+        Formula::Not(inner) => encode_formula(context, inner, variables)?.not(),
+        Formula::And(left, right) => {
+            let left = encode_formula(context, left, variables)?;
+            let right = encode_formula(context, right, variables)?;
+            ast::Bool::and(context, &[&left, &right])
+        }
+        Formula::Or(left, right) => {
+            let left = encode_formula(context, left, variables)?;
+            let right = encode_formula(context, right, variables)?;
+            ast::Bool::or(context, &[&left, &right])
+        }
+    })
+}
+
+#[cfg(feature = "z3")]
+fn encode_term<'ctx>(
+    context: &'ctx Context,
+    term: &Term,
+    variables: &mut HashMap<String, ast::Real<'ctx>>,
+) -> Result<ast::Real<'ctx>> {
+    Ok(match term {
+        Term::Number(number) => {
+            let (numerator, denominator) = decimal_fraction(number)
+                .ok_or_else(|| parse_error(number, "invalid number"))?;
+            ast::Real::from_real_str(context, &numerator, &denominator)
+                .ok_or_else(|| parse_error(number, "number out of range"))?
+        }
+        Term::Variable(name) => variables
+            .entry(name.clone())
+            .or_insert_with(|| ast::Real::new_const(context, name.as_str()))
+            .clone(),
+        Term::Neg(inner) => encode_term(context, inner, variables)?.unary_minus(),
+        Term::Add(left, right) => {
+            let left = encode_term(context, left, variables)?;
+            let right = encode_term(context, right, variables)?;
+            ast::Real::add(context, &[&left, &right])
+        }
+        Term::Sub(left, right) => {
+            let left = encode_term(context, left, variables)?;
+            let right = encode_term(context, right, variables)?;
+            ast::Real::sub(context, &[&left, &right])
+        }
+        Term::Mul(left, right) => {
+            let left = encode_term(context, left, variables)?;
+            let right = encode_term(context, right, variables)?;
+            ast::Real::mul(context, &[&left, &right])
+        }
+    })
+}
+
 /// TLA+ specification generator
 pub struct TLAPlusGenerator {
     specifications: Vec<String>,
@@ -102,6 +516,64 @@ impl CoqProofGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constraint::DefaultConstraintEngine;
+    use crate::SafetyEngine;
+
+    fn constraint(id: &str, expression: &str) -> Constraint {
+        let mut constraint = DefaultConstraintEngine::memory_constraint(0.8);
+        constraint.id = id.to_string();
+        constraint.rule.expression = expression.to_string();
+        constraint
+    }
+
+    #[test]
+    fn test_parse_constraint_expressions() {
+        assert_eq!(
+            Parser::parse("memory_usage < 0.8").unwrap(),
+            Formula::Compare(
+                Term::Variable("memory_usage".to_string()),
+                Comparison::Lt,
+                Term::Number("0.8".to_string()),
+            )
+        );
+        assert!(Parser::parse("(x + 1) * 2 >= y && !(z == 3) || false").is_ok());
+        assert!(Parser::parse("x >").is_err());
+        assert!(Parser::parse("x > 1 $ y").is_err());
+        assert_eq!(decimal_fraction("0.8"), Some(("8".to_string(), "10".to_string())));
+        assert_eq!(decimal_fraction("12"), Some(("12".to_string(), "1".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_contradictory_constraints_are_unsatisfiable() {
+        let engine = SafetyEngine::new().await.unwrap();
+        let constraints = vec![
+            constraint("x_above_10", "x > 10"),
+            constraint("y_below_3", "y < 3"),
+            constraint("x_below_5", "x < 5"),
+        ];
+
+        let result = engine.check_satisfiability(&constraints).unwrap();
+        assert_eq!(
+            result,
+            SatisfiabilityResult::Unsatisfiable {
+                core: vec!["x_above_10".to_string(), "x_below_5".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_satisfiable_constraints_have_assignment() {
+        let verifier = Z3Verifier::new();
+        let constraints = vec![constraint("lower", "x > 10"), constraint("upper", "x <= 20")];
+
+        match verifier.check_satisfiability(&constraints).unwrap() {
+            SatisfiabilityResult::Satisfiable { assignment } => {
+                let x = assignment["x"];
+                assert!(x > 10.0 && x <= 20.0);
+            }
+            other => panic!("Expected satisfiable, got {:?}", other),
+        }
+    }
     
     #[test]
     fn test_tla_plus_generator() {
@@ -175,6 +175,7 @@ pub mod constraint;
 pub mod engine;
 pub mod rollback;
 pub mod monitor;
+pub mod distributed;
 
 // Verification systems
 #[cfg(feature = "verification")]
@@ -189,14 +190,18 @@ pub mod healing;
 
 // Re-exports for convenience
 pub use error::{SafetyError, Result};
-pub use types::{Constraint, SafetyState, Severity, CheckpointId, SafetyConfig};
+pub use types::{Constraint, SafetyState, Severity, CheckpointId, SafetyConfig, CheckpointCompression};
 pub use traits::{SafetyMonitor, ConstraintEngine, RollbackManager};
 pub use engine::SafetyEngine;
 
+#[cfg(feature = "formal-verification")]
+pub use formal::SatisfiabilityResult;
+
 // Re-export main implementations
 pub use constraint::DefaultConstraintEngine;
 pub use monitor::DefaultSafetyMonitor;
 pub use rollback::DefaultRollbackManager;
+pub use distributed::{GlobalSnapshot, SnapshotNode};
 
 // Common constraint builders
 pub mod prelude {
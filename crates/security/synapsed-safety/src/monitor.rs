@@ -8,7 +8,7 @@ use crate::traits::{SafetyMonitor, StateChangeCallback, MonitorConfig, Monitorin
 use crate::types::*;
 use async_trait::async_trait;
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
@@ -37,6 +37,10 @@ pub struct DefaultSafetyMonitor {
     metadata: MonitorMetadata,
     /// Last monitoring check time
     last_check: Arc<RwLock<Option<Instant>>>,
+    /// Captured states, oldest first
+    history: Arc<RwLock<VecDeque<SafetyState>>>,
+    /// How long captured states are kept in the history
+    history_retention: Arc<RwLock<Duration>>,
 }
 
 /// State change event
@@ -122,6 +126,41 @@ impl DefaultSafetyMonitor {
                 ],
             },
             last_check: Arc::new(RwLock::new(None)),
+            history: Arc::new(RwLock::new(VecDeque::new())),
+            history_retention: Arc::new(RwLock::new(Duration::ZERO)),
+        }
+    }
+
+    /// Keep captured states for at least `retention`
+    ///
+    /// Set this to the longest temporal constraint window so those
+    /// constraints can be evaluated over their full window.
+    pub fn set_history_retention(&self, retention: Duration) {
+        *self.history_retention.write() = retention;
+    }
+
+    /// Captured states within the retention window, oldest first
+    ///
+    /// The newest state captured before the window is included too, so the
+    /// history always reaches back to the start of the window.
+    pub fn state_history(&self) -> Vec<SafetyState> {
+        self.history.read().iter().cloned().collect()
+    }
+
+    /// Add a state to the history, dropping states older than the retention window
+    pub fn record_state(&self, state: SafetyState) {
+        let retention = *self.history_retention.read();
+        // Retention too long to represent keeps everything
+        let cutoff = chrono::Duration::from_std(retention)
+            .ok()
+            .and_then(|retention| state.timestamp.checked_sub_signed(retention));
+
+        let mut history = self.history.write();
+        history.push_back(state);
+        if let Some(cutoff) = cutoff {
+            while history.len() > 1 && history[1].timestamp <= cutoff {
+                history.pop_front();
+            }
         }
     }
 
@@ -337,6 +376,7 @@ impl DefaultSafetyMonitor {
                         let mut current = self.current_state.write();
                         current.replace(new_state.clone())
                     };
+                    self.record_state(new_state.clone());
                     
                     // Determine change type
                     let change_type = if old_state.is_none() {
@@ -634,6 +674,8 @@ impl Clone for DefaultSafetyMonitor {
             active: Arc::clone(&self.active),
             metadata: self.metadata.clone(),
             last_check: Arc::clone(&self.last_check),
+            history: Arc::clone(&self.history),
+            history_retention: Arc::clone(&self.history_retention),
         }
     }
 }
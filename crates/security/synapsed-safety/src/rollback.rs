@@ -11,6 +11,7 @@ use parking_lot::RwLock;
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use synapsed_core::clock::{SharedClock, SystemClock};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -18,7 +19,7 @@ use uuid::Uuid;
 #[derive(Debug)]
 pub struct DefaultRollbackManager {
     /// Stored checkpoints
-    checkpoints: Arc<RwLock<HashMap<CheckpointId, Checkpoint>>>,
+    checkpoints: Arc<RwLock<HashMap<CheckpointId, StoredCheckpoint>>>,
     /// Checkpoint history ordered by creation time
     checkpoint_history: Arc<RwLock<VecDeque<CheckpointId>>>,
     /// Tagged checkpoints for quick access
@@ -31,6 +32,8 @@ pub struct DefaultRollbackManager {
     current_state: Arc<RwLock<Option<SafetyState>>>,
     /// Retention policy
     retention_policy: Arc<RwLock<RetentionPolicy>>,
+    /// Clock for checkpoint timestamps and retention
+    clock: SharedClock,
 }
 
 /// Configuration for rollback manager
@@ -38,10 +41,8 @@ pub struct DefaultRollbackManager {
 pub struct RollbackConfig {
     /// Maximum number of checkpoints to keep
     pub max_checkpoints: u32,
-    /// Enable compression for checkpoints
-    pub compression_enabled: bool,
-    /// Compression algorithm
-    pub compression_algorithm: String,
+    /// Compression for newly created checkpoints
+    pub compression: CheckpointCompression,
     /// Maximum memory usage for checkpoints
     pub max_memory_bytes: u64,
     /// Enable integrity checking
@@ -54,8 +55,7 @@ impl Default for RollbackConfig {
     fn default() -> Self {
         Self {
             max_checkpoints: 100,
-            compression_enabled: true,
-            compression_algorithm: "zstd".to_string(),
+            compression: CheckpointCompression::Zstd,
             max_memory_bytes: 100 * 1024 * 1024, // 100MB
             integrity_checking: true,
             validate_on_create: true,
@@ -63,6 +63,61 @@ impl Default for RollbackConfig {
     }
 }
 
+impl From<&SafetyConfig> for RollbackConfig {
+    fn from(config: &SafetyConfig) -> Self {
+        Self {
+            max_checkpoints: config.max_checkpoints,
+            compression: if config.compression_enabled {
+                config.checkpoint_compression
+            } else {
+                CheckpointCompression::None
+            },
+            ..Self::default()
+        }
+    }
+}
+
+/// Checkpoint as held by the manager, with its state encoded in `snapshot`
+#[derive(Debug, Clone)]
+struct StoredCheckpoint {
+    id: CheckpointId,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    description: String,
+    tags: Vec<String>,
+    /// Compression of `snapshot`
+    compression: CheckpointCompression,
+    /// SHA-256 of `snapshot`, hex encoded
+    integrity_hash: String,
+    /// Serialized state
+    snapshot: Vec<u8>,
+}
+
+impl StoredCheckpoint {
+    fn size_bytes(&self) -> u64 {
+        self.snapshot.len() as u64
+    }
+}
+
+/// Compress serialized checkpoint data
+fn compress(data: &[u8], compression: CheckpointCompression) -> Result<Vec<u8>> {
+    match compression {
+        CheckpointCompression::None => Ok(data.to_vec()),
+        CheckpointCompression::Zstd => zstd::encode_all(data, 0).map_err(|e| SafetyError::Serialization {
+            message: format!("Failed to compress checkpoint with zstd: {}", e),
+        }),
+        CheckpointCompression::Lz4 => Ok(lz4_flex::compress_prepend_size(data)),
+    }
+}
+
+/// Decompress checkpoint data, `None` if it is not valid for the algorithm
+fn decompress(data: &[u8], compression: CheckpointCompression) -> Option<Vec<u8>> {
+    match compression {
+        CheckpointCompression::None => Some(data.to_vec()),
+        CheckpointCompression::Zstd => zstd::decode_all(data).ok(),
+        CheckpointCompression::Lz4 => lz4_flex::decompress_size_prepended(data).ok(),
+    }
+}
+
 impl DefaultRollbackManager {
     /// Create a new rollback manager with default configuration
     pub fn new() -> Self {
@@ -91,65 +146,93 @@ impl DefaultRollbackManager {
                 compress_after_hours: 1,
                 delete_compressed_after_days: 7,
             })),
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Use the given clock for checkpoint timestamps and retention
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Set current state for rollback operations
     pub async fn set_current_state(&self, state: SafetyState) -> Result<()> {
         *self.current_state.write() = Some(state);
         Ok(())
     }
 
+    /// Set the compression used for checkpoints created from now on
+    ///
+    /// Existing checkpoints keep the compression they were stored with.
+    pub fn set_compression(&mut self, compression: CheckpointCompression) {
+        self.config.compression = compression;
+    }
+
     /// Calculate checksum for integrity checking
-    fn calculate_checksum(&self, checkpoint: &Checkpoint) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+    fn calculate_checksum(&self, checkpoint: &StoredCheckpoint) -> String {
+        use sha2::{Digest, Sha256};
 
-        let mut hasher = DefaultHasher::new();
-        checkpoint.id.hash(&mut hasher);
-        checkpoint.timestamp.hash(&mut hasher);
-        checkpoint.state.id.hash(&mut hasher);
-        checkpoint.description.hash(&mut hasher);
-        
-        format!("{:x}", hasher.finish())
+        format!("{:x}", Sha256::digest(&checkpoint.snapshot))
     }
 
-    /// Compress checkpoint data if enabled
-    async fn compress_checkpoint(&self, checkpoint: &mut Checkpoint) -> Result<()> {
-        if !self.config.compression_enabled {
-            return Ok(());
-        }
-
-        debug!("Compressing checkpoint: {}", checkpoint.id);
-        
-        // Serialize the state
-        let serialized = serde_json::to_string(&checkpoint.state)
+    /// Serialize and compress a state for storage
+    fn encode_state(&self, state: &SafetyState, compression: CheckpointCompression) -> Result<Vec<u8>> {
+        let serialized = serde_json::to_vec(state)
             .map_err(|e| SafetyError::Serialization {
                 message: format!("Failed to serialize checkpoint state: {}", e),
             })?;
 
-        let original_size = serialized.len() as u64;
-        
-        // In a real implementation, you would use actual compression algorithms
-        // For now, we'll simulate compression by updating metadata
-        let compressed_size = (original_size as f64 * 0.6) as u64; // Simulate 40% compression
-        
-        checkpoint.compression = Some(self.config.compression_algorithm.clone());
-        checkpoint.size_bytes = compressed_size;
-        
-        let compression_ratio = original_size as f64 / compressed_size as f64;
-        info!(
-            "Checkpoint {} compressed: {} -> {} bytes (ratio: {:.2}x)",
-            checkpoint.id, original_size, compressed_size, compression_ratio
+        let snapshot = compress(&serialized, compression)?;
+        debug!(
+            "Checkpoint state encoded with {}: {} -> {} bytes",
+            compression,
+            serialized.len(),
+            snapshot.len()
         );
-        
-        Ok(())
+
+        Ok(snapshot)
+    }
+
+    /// Verify a stored checkpoint and decode its state
+    fn restore_state(&self, checkpoint: &StoredCheckpoint) -> Result<SafetyState> {
+        let corrupted = || SafetyError::CheckpointCorrupted {
+            checkpoint_id: checkpoint.id,
+        };
+
+        if !self.validate_checkpoint_integrity(checkpoint) {
+            return Err(corrupted());
+        }
+
+        let serialized = decompress(&checkpoint.snapshot, checkpoint.compression).ok_or_else(|| {
+            warn!("Checkpoint {} could not be decompressed", checkpoint.id);
+            corrupted()
+        })?;
+
+        serde_json::from_slice(&serialized).map_err(|e| {
+            warn!("Checkpoint {} state could not be decoded: {}", checkpoint.id, e);
+            corrupted()
+        })
+    }
+
+    /// Decode a stored checkpoint
+    fn to_checkpoint(&self, checkpoint: &StoredCheckpoint) -> Result<Checkpoint> {
+        Ok(Checkpoint {
+            id: checkpoint.id,
+            timestamp: checkpoint.timestamp,
+            state: self.restore_state(checkpoint)?,
+            description: checkpoint.description.clone(),
+            tags: checkpoint.tags.clone(),
+            size_bytes: checkpoint.size_bytes(),
+            compression: checkpoint.compression,
+            integrity_hash: checkpoint.integrity_hash.clone(),
+        })
     }
 
     /// Validate checkpoint integrity
-    async fn validate_checkpoint_integrity(&self, checkpoint: &Checkpoint) -> Result<bool> {
+    fn validate_checkpoint_integrity(&self, checkpoint: &StoredCheckpoint) -> bool {
         if !self.config.integrity_checking {
-            return Ok(true);
+            return true;
         }
 
         let calculated_checksum = self.calculate_checksum(checkpoint);
@@ -162,7 +245,7 @@ impl DefaultRollbackManager {
             );
         }
         
-        Ok(valid)
+        valid
     }
 
     /// Enforce retention policy
@@ -172,13 +255,13 @@ impl DefaultRollbackManager {
         let mut history = self.checkpoint_history.write();
         let mut tagged = self.tagged_checkpoints.write();
         
-        let now = chrono::Utc::now();
+        let now = self.clock.now();
         let mut total_size = 0u64;
         let mut expired_checkpoints = Vec::new();
         
         // Calculate total size and find expired checkpoints
         for checkpoint in checkpoints.values() {
-            total_size += checkpoint.size_bytes;
+            total_size += checkpoint.size_bytes();
             
             let age_hours = (now - checkpoint.timestamp).num_hours();
             if age_hours > policy.max_age_hours as i64 {
@@ -209,9 +292,9 @@ impl DefaultRollbackManager {
                 if let Some(checkpoint) = checkpoints.remove(&oldest_id) {
                     info!(
                         "Removing checkpoint for size limit: {} ({} bytes)",
-                        oldest_id, checkpoint.size_bytes
+                        oldest_id, checkpoint.size_bytes()
                     );
-                    total_size -= checkpoint.size_bytes;
+                    total_size -= checkpoint.size_bytes();
                     tagged.retain(|_, id| *id != oldest_id);
                 }
             }
@@ -265,35 +348,30 @@ impl RollbackManager for DefaultRollbackManager {
         };
         
         let checkpoint_id = Uuid::new_v4();
-        let timestamp = chrono::Utc::now();
+        let timestamp = self.clock.now();
         
         info!(
             "Creating checkpoint: {} (description: {:?}, tags: {:?})",
             checkpoint_id, description, tags
         );
         
-        let mut checkpoint = Checkpoint {
+        let compression = self.config.compression;
+        let mut checkpoint = StoredCheckpoint {
             id: checkpoint_id,
             timestamp,
-            state,
             description: description.unwrap_or_else(|| format!("Checkpoint created at {}", timestamp)),
             tags: tags.clone(),
-            size_bytes: 0, // Will be updated after compression
-            compression: None,
+            compression,
             integrity_hash: String::new(), // Will be calculated below
+            snapshot: self.encode_state(&state, compression)?,
         };
         
-        // Compress if enabled
-        self.compress_checkpoint(&mut checkpoint).await?;
-        
         // Calculate integrity hash
         checkpoint.integrity_hash = self.calculate_checksum(&checkpoint);
         
-        // Validate if enabled
+        // Validate if enabled, making sure the state decodes again
         if self.config.validate_on_create {
-            if !self.validate_checkpoint_integrity(&checkpoint).await? {
-                return Err(SafetyError::CheckpointCorrupted { checkpoint_id });
-            }
+            self.restore_state(&checkpoint)?;
         }
         
         // Store checkpoint
@@ -318,7 +396,7 @@ impl RollbackManager for DefaultRollbackManager {
             
             let checkpoints = self.checkpoints.read();
             if !checkpoints.is_empty() {
-                let total_size: u64 = checkpoints.values().map(|c| c.size_bytes).sum();
+                let total_size: u64 = checkpoints.values().map(|c| c.size_bytes()).sum();
                 stats.avg_checkpoint_size_bytes = total_size / checkpoints.len() as u64;
             }
         }
@@ -345,15 +423,11 @@ impl RollbackManager for DefaultRollbackManager {
             reason: "Checkpoint not found".to_string(),
         })?;
         
-        // Validate checkpoint integrity
-        if !self.validate_checkpoint_integrity(&checkpoint).await? {
-            return Err(SafetyError::CheckpointCorrupted {
-                checkpoint_id: *checkpoint_id,
-            });
-        }
+        // Validate checkpoint integrity before restoring anything
+        let state = self.restore_state(&checkpoint)?;
         
         // Apply rollback
-        self.apply_rollback(&checkpoint.state).await?;
+        self.apply_rollback(&state).await?;
         
         // Update statistics
         let rollback_time = start_time.elapsed();
@@ -429,7 +503,7 @@ impl RollbackManager for DefaultRollbackManager {
         
         info!(
             "Checkpoint deleted: {} ({} bytes freed)",
-            checkpoint_id, checkpoint.size_bytes
+            checkpoint_id, checkpoint.size_bytes()
         );
         
         Ok(())
@@ -448,8 +522,8 @@ impl RollbackManager for DefaultRollbackManager {
                     timestamp: checkpoint.timestamp,
                     description: checkpoint.description.clone(),
                     tags: checkpoint.tags.clone(),
-                    size_bytes: checkpoint.size_bytes,
-                    compressed: checkpoint.compression.is_some(),
+                    size_bytes: checkpoint.size_bytes(),
+                    compressed: checkpoint.compression.is_compressed(),
                 });
             }
         }
@@ -458,40 +532,52 @@ impl RollbackManager for DefaultRollbackManager {
     }
 
     async fn get_checkpoint(&self, checkpoint_id: &CheckpointId) -> Result<Option<Checkpoint>> {
-        let checkpoints = self.checkpoints.read();
-        Ok(checkpoints.get(checkpoint_id).cloned())
+        let checkpoint = self.checkpoints.read().get(checkpoint_id).cloned();
+        checkpoint.map(|checkpoint| self.to_checkpoint(&checkpoint)).transpose()
     }
 
     async fn compress_checkpoints(&mut self, older_than: Duration) -> Result<crate::traits::CompressionStats> {
         let start_time = Instant::now();
-        let cutoff_time = chrono::Utc::now() - chrono::Duration::from_std(older_than).unwrap();
+        let cutoff_time = self.clock.now() - chrono::Duration::from_std(older_than).unwrap();
         
         info!("Compressing checkpoints older than: {}", cutoff_time);
         
+        let compression = if self.config.compression.is_compressed() {
+            self.config.compression
+        } else {
+            CheckpointCompression::default()
+        };
+        
         let mut checkpoints = self.checkpoints.write();
         let mut compressed_count: u32 = 0;
-        let mut bytes_saved: u64 = 0;
+        let mut original_bytes: u64 = 0;
+        let mut compressed_bytes: u64 = 0;
         
         for checkpoint in checkpoints.values_mut() {
-            if checkpoint.timestamp < cutoff_time && checkpoint.compression.is_none() {
-                let original_size = checkpoint.size_bytes;
+            if checkpoint.timestamp < cutoff_time && !checkpoint.compression.is_compressed() {
+                // Never carry a corrupted checkpoint over under a fresh hash
+                if !self.validate_checkpoint_integrity(checkpoint) {
+                    continue;
+                }
                 
-                // Simulate compression
-                let compressed_size = (original_size as f64 * 0.6) as u64;
-                checkpoint.size_bytes = compressed_size;
-                checkpoint.compression = Some(self.config.compression_algorithm.clone());
+                let original_size = checkpoint.size_bytes();
+                checkpoint.snapshot = compress(&checkpoint.snapshot, compression)?;
+                checkpoint.compression = compression;
+                checkpoint.integrity_hash = self.calculate_checksum(checkpoint);
                 
-                bytes_saved += original_size - compressed_size;
+                original_bytes += original_size;
+                compressed_bytes += checkpoint.size_bytes();
                 compressed_count += 1;
                 
                 debug!("Compressed checkpoint: {} ({} -> {} bytes)", 
-                       checkpoint.id, original_size, compressed_size);
+                       checkpoint.id, original_size, checkpoint.size_bytes());
             }
         }
         
         let duration = start_time.elapsed();
-        let compression_ratio = if bytes_saved > 0 {
-            1.0 - (bytes_saved as f64 / (bytes_saved + (compressed_count as u64) * 1024) as f64)
+        let bytes_saved = original_bytes.saturating_sub(compressed_bytes);
+        let compression_ratio = if original_bytes > 0 {
+            compressed_bytes as f64 / original_bytes as f64
         } else {
             1.0
         };
@@ -520,7 +606,7 @@ impl RollbackManager for DefaultRollbackManager {
             })?.clone()
         };
         
-        self.validate_checkpoint_integrity(&checkpoint).await
+        Ok(self.validate_checkpoint_integrity(&checkpoint))
     }
 
     async fn get_stats(&self) -> Result<crate::traits::RollbackStats> {
@@ -543,14 +629,16 @@ impl RollbackManager for DefaultRollbackManager {
     async fn export_checkpoint(&self, checkpoint_id: &CheckpointId, destination: &str) -> Result<()> {
         info!("Exporting checkpoint {} to: {}", checkpoint_id, destination);
         
-        let checkpoints = self.checkpoints.read();
-        let checkpoint = checkpoints.get(checkpoint_id).ok_or_else(|| SafetyError::RollbackFailed {
-            checkpoint_id: *checkpoint_id,
-            reason: "Checkpoint not found".to_string(),
-        })?;
+        let checkpoint = {
+            let checkpoints = self.checkpoints.read();
+            checkpoints.get(checkpoint_id).ok_or_else(|| SafetyError::RollbackFailed {
+                checkpoint_id: *checkpoint_id,
+                reason: "Checkpoint not found".to_string(),
+            })?.clone()
+        };
         
         // Serialize checkpoint
-        let serialized = serde_json::to_string_pretty(checkpoint)
+        let serialized = serde_json::to_string_pretty(&self.to_checkpoint(&checkpoint)?)
             .map_err(|e| SafetyError::Serialization {
                 message: format!("Failed to serialize checkpoint: {}", e),
             })?;
@@ -577,25 +665,17 @@ impl RollbackManager for DefaultRollbackManager {
         };
         
         if let Some(state) = state_clone {
-            let imported_checkpoint = Checkpoint {
+            let compression = self.config.compression;
+            let mut imported_checkpoint = StoredCheckpoint {
                 id: checkpoint_id,
                 timestamp: chrono::Utc::now(),
-                state: state.clone(),
                 description: format!("Imported from: {}", source),
                 tags: vec!["imported".to_string()],
-                size_bytes: 1024, // Placeholder
-                compression: None,
-                integrity_hash: self.calculate_checksum(&Checkpoint {
-                    id: checkpoint_id,
-                    timestamp: chrono::Utc::now(),
-                    state: state.clone(),
-                    description: format!("Imported from: {}", source),
-                    tags: vec!["imported".to_string()],
-                    size_bytes: 1024,
-                    compression: None,
-                    integrity_hash: String::new(),
-                }),
+                compression,
+                integrity_hash: String::new(),
+                snapshot: self.encode_state(&state, compression)?,
             };
+            imported_checkpoint.integrity_hash = self.calculate_checksum(&imported_checkpoint);
             
             // Store the imported checkpoint
             {
@@ -627,6 +707,7 @@ impl RollbackManager for DefaultRollbackManager {
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use synapsed_core::clock::MockClock;
 
     fn create_test_state() -> SafetyState {
         SafetyState {
@@ -768,6 +849,69 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_corrupted_checkpoint_is_not_restored() {
+        let mut manager = DefaultRollbackManager::new();
+        manager.set_current_state(create_test_state()).await.unwrap();
+        
+        let checkpoint_id = manager
+            .create_checkpoint(Some("Before corruption".to_string()))
+            .await
+            .unwrap();
+        
+        let mut modified_state = create_test_state();
+        modified_state.values.insert("balance".to_string(), StateValue::Integer(200));
+        manager.set_current_state(modified_state).await.unwrap();
+        
+        // Flip a byte of the stored state
+        {
+            let mut checkpoints = manager.checkpoints.write();
+            let checkpoint = checkpoints.get_mut(&checkpoint_id).unwrap();
+            let middle = checkpoint.snapshot.len() / 2;
+            checkpoint.snapshot[middle] ^= 0xff;
+        }
+        
+        let result = manager.rollback_to_checkpoint(&checkpoint_id).await;
+        assert!(matches!(
+            result,
+            Err(SafetyError::CheckpointCorrupted { checkpoint_id: id }) if id == checkpoint_id
+        ));
+        assert!(!manager.validate_checkpoint(&checkpoint_id).await.unwrap());
+        
+        // The current state is left untouched
+        let current_state = manager.current_state.read();
+        assert!(matches!(
+            current_state.as_ref().unwrap().values.get("balance"),
+            Some(StateValue::Integer(200))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_compression_selection() {
+        let mut manager = DefaultRollbackManager::new();
+        manager.set_current_state(create_test_state()).await.unwrap();
+        
+        let mut ids = Vec::new();
+        for compression in [
+            CheckpointCompression::None,
+            CheckpointCompression::Zstd,
+            CheckpointCompression::Lz4,
+        ] {
+            manager.set_compression(compression);
+            ids.push((compression, manager.create_checkpoint(None).await.unwrap()));
+        }
+        
+        for (compression, checkpoint_id) in ids {
+            let checkpoint = manager.get_checkpoint(&checkpoint_id).await.unwrap().unwrap();
+            assert_eq!(checkpoint.compression, compression);
+            assert!(matches!(
+                checkpoint.state.values.get("balance"),
+                Some(StateValue::Integer(100))
+            ));
+            manager.rollback_to_checkpoint(&checkpoint_id).await.unwrap();
+        }
+    }
+
     #[tokio::test]
     async fn test_checkpoint_listing() {
         let mut manager = DefaultRollbackManager::new();
@@ -855,6 +999,34 @@ mod tests {
         assert!(checkpoint.is_some());
     }
 
+    #[tokio::test]
+    async fn test_retention_expires_by_age() {
+        let clock = MockClock::default();
+        let mut manager = DefaultRollbackManager::new().with_clock(Arc::new(clock.clone()));
+        manager.set_current_state(create_test_state()).await.unwrap();
+        
+        let policy = crate::traits::RetentionPolicy {
+            max_checkpoints: 10,
+            max_age_hours: 1,
+            max_total_size_bytes: 1024 * 1024,
+            compress_after_hours: 0,
+            delete_compressed_after_days: 1,
+        };
+        manager.set_retention_policy(policy).await.unwrap();
+        
+        let old = manager.create_checkpoint(Some("old".to_string())).await.unwrap();
+        
+        clock.advance(chrono::Duration::minutes(90));
+        manager.create_checkpoint(Some("recent".to_string())).await.unwrap();
+        assert!(manager.get_checkpoint(&old).await.unwrap().is_some());
+        
+        clock.advance(chrono::Duration::minutes(40));
+        let latest = manager.create_checkpoint(Some("latest".to_string())).await.unwrap();
+        assert!(manager.get_checkpoint(&old).await.unwrap().is_none());
+        assert!(manager.get_checkpoint(&latest).await.unwrap().is_some());
+        assert_eq!(manager.list_checkpoints().await.unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_rollback_statistics() {
         let mut manager = DefaultRollbackManager::new();
@@ -3,7 +3,7 @@
 //! This module defines the key abstractions that enable pluggable and
 //! extensible safety mechanisms throughout the system.
 
-use crate::error::Result;
+use crate::error::{Result, SafetyError};
 use crate::types::*;
 use async_trait::async_trait;
 use std::time::Duration;
@@ -69,6 +69,17 @@ pub trait ConstraintEngine: Send + Sync {
     /// Validate state against all active constraints
     async fn validate_state(&self, state: &SafetyState) -> Result<ValidationResult>;
 
+    /// Validate the latest state of a history, oldest first, against all
+    /// active constraints, evaluating temporal constraints over the history
+    async fn validate_history(&self, history: &[SafetyState]) -> Result<ValidationResult> {
+        match history.last() {
+            Some(state) => self.validate_state(state).await,
+            None => Err(SafetyError::StateInconsistent {
+                description: "Cannot validate an empty state history".to_string(),
+            }),
+        }
+    }
+
     /// Validate state against specific constraints
     async fn validate_constraints(
         &self,
@@ -227,12 +227,55 @@ pub enum ConstraintType {
     Postcondition,
     /// Temporal constraint (time-based)
     Temporal,
+    /// Rule evaluated over a window of state history
+    TemporalConstraint(TemporalConstraint),
     /// Resource constraint
     Resource,
     /// Custom constraint type
     Custom(String),
 }
 
+/// Operator applied to a rule over a window of state history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TemporalOperator {
+    /// Violated once the rule has failed continuously for the whole window
+    Sustained,
+    /// Violated while any state in the window fails the rule
+    Always,
+}
+
+/// Temporal rule, evaluated over the state history instead of a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemporalConstraint {
+    /// How rule outcomes in the window are combined
+    pub operator: TemporalOperator,
+    /// Length of the window
+    pub window_ms: u64,
+}
+
+impl TemporalConstraint {
+    /// Violated once the rule has failed for at least `window`
+    pub fn sustained(window: std::time::Duration) -> Self {
+        Self {
+            operator: TemporalOperator::Sustained,
+            window_ms: window.as_millis() as u64,
+        }
+    }
+
+    /// Violated while the rule failed at any point within `window`
+    pub fn always(window: std::time::Duration) -> Self {
+        Self {
+            operator: TemporalOperator::Always,
+            window_ms: window.as_millis() as u64,
+        }
+    }
+
+    /// Length of the window
+    pub fn window(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.window_ms)
+    }
+}
+
 /// Constraint rule definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConstraintRule {
@@ -385,12 +428,41 @@ pub struct Checkpoint {
     pub tags: Vec<String>,
     /// Size of checkpoint data
     pub size_bytes: u64,
-    /// Compression used
-    pub compression: Option<String>,
-    /// Integrity hash
+    /// Compression used for the stored state
+    pub compression: CheckpointCompression,
+    /// SHA-256 of the stored state, hex encoded
     pub integrity_hash: String,
 }
 
+/// Compression algorithm for stored checkpoint state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CheckpointCompression {
+    /// Store the serialized state as is
+    None,
+    /// Zstandard, smaller checkpoints at a higher CPU cost
+    #[default]
+    Zstd,
+    /// LZ4, faster checkpoints with less compression
+    Lz4,
+}
+
+impl CheckpointCompression {
+    /// Whether the state is compressed at all
+    pub fn is_compressed(&self) -> bool {
+        *self != CheckpointCompression::None
+    }
+}
+
+impl fmt::Display for CheckpointCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointCompression::None => write!(f, "none"),
+            CheckpointCompression::Zstd => write!(f, "zstd"),
+            CheckpointCompression::Lz4 => write!(f, "lz4"),
+        }
+    }
+}
+
 /// Configuration for safety system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyConfig {
@@ -405,7 +477,12 @@ pub struct SafetyConfig {
     /// Enable compression for checkpoints
     pub compression_enabled: bool,
     /// Compression algorithm to use
+    #[deprecated(note = "ignored; use `checkpoint_compression` instead")]
+    #[serde(default)]
     pub compression_algorithm: String,
+    /// Compression algorithm for checkpoints when compression is enabled
+    #[serde(default)]
+    pub checkpoint_compression: CheckpointCompression,
     /// Enable formal verification
     pub formal_verification_enabled: bool,
     /// Enable self-healing
@@ -415,6 +492,7 @@ pub struct SafetyConfig {
 }
 
 impl Default for SafetyConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             max_checkpoints: 100,
@@ -423,6 +501,7 @@ impl Default for SafetyConfig {
             memory_limit_bytes: 100 * 1024 * 1024, // 100MB
             compression_enabled: true,
             compression_algorithm: "zstd".to_string(),
+            checkpoint_compression: CheckpointCompression::Zstd,
             formal_verification_enabled: false,
             self_healing_enabled: true,
             custom_properties: HashMap::new(),
@@ -467,6 +546,7 @@ pub struct MemoryStats {
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use super::*;
 
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::collections::HashMap;
+use chrono::{DateTime, Utc};
 use crate::{SemanticCoords, TrustScore, SemanticDistance};
 
 /// Affinity between agents (like chemical bonds)
@@ -77,14 +78,43 @@ impl PromiseChemistry {
         reverse_bond.weaken(amount * 1.2);
     }
     
+    /// Record the outcome of a collaboration between two agents
+    ///
+    /// Decay accumulated since the agents last interacted is settled first,
+    /// so a success after a long idle period builds on the decayed bond.
+    pub fn reinforce(&mut self, a: Uuid, b: Uuid, outcome: CollaborationOutcome) {
+        let now = Utc::now();
+        for key in [(a, b), (b, a)] {
+            let baseline = self.calculate_base_affinity(key.0, key.1);
+            if let Some(bond) = self.affinity_map.get_mut(&key) {
+                bond.strength = bond.strength_at(now, baseline, self.config.decay_half_life_secs);
+                bond.last_interaction = now;
+            }
+        }
+        
+        match outcome {
+            CollaborationOutcome::Success => self.strengthen_bond(a, b, self.config.reinforcement_rate),
+            CollaborationOutcome::Failure => self.weaken_bond(a, b, self.config.reinforcement_rate),
+        }
+    }
+    
     /// Get affinity between agents
+    ///
+    /// Bonds decay toward the agents' base affinity the longer they go
+    /// without interacting.
     pub fn get_affinity(&self, from: Uuid, to: Uuid) -> f64 {
         self.affinity_map
             .get(&(from, to))
-            .map(|b| b.strength)
+            .map(|b| self.current_strength(b, Utc::now()))
             .unwrap_or(self.calculate_base_affinity(from, to))
     }
     
+    /// Strength of a bond after time-based decay
+    fn current_strength(&self, bond: &AffinityBond, now: DateTime<Utc>) -> f64 {
+        let baseline = self.calculate_base_affinity(bond.from, bond.to);
+        bond.strength_at(now, baseline, self.config.decay_half_life_secs)
+    }
+    
     /// Calculate base affinity from semantic distance
     fn calculate_base_affinity(&self, from: Uuid, to: Uuid) -> f64 {
         if let (Some(pos1), Some(pos2)) = (self.positions.get(&from), self.positions.get(&to)) {
@@ -104,19 +134,22 @@ impl PromiseChemistry {
             .collect();
         
         for (from, to) in keys_to_update {
+            let base_affinity = self.calculate_base_affinity(from, to);
             if let Some(bond) = self.affinity_map.get_mut(&(from, to)) {
-                bond.update_semantic_factor(self.calculate_base_affinity(from, to));
+                bond.update_semantic_factor(base_affinity);
             }
         }
     }
     
     /// Find agents with high affinity to given agent
     pub fn find_compatible_agents(&self, agent_id: Uuid, threshold: f64) -> Vec<(Uuid, f64)> {
+        let now = Utc::now();
         let mut compatible: Vec<(Uuid, f64)> = Vec::new();
         
         for ((from, to), bond) in &self.affinity_map {
-            if *from == agent_id && bond.strength >= threshold {
-                compatible.push((*to, bond.strength));
+            let strength = self.current_strength(bond, now);
+            if *from == agent_id && strength >= threshold {
+                compatible.push((*to, strength));
             }
         }
         
@@ -171,8 +204,9 @@ impl PromiseChemistry {
             return 0.0;
         }
         
+        let now = Utc::now();
         let total_strength: f64 = self.affinity_map.values()
-            .map(|b| b.strength)
+            .map(|b| self.current_strength(b, now))
             .sum();
         
         let max_possible = self.affinity_map.len() as f64;
@@ -183,6 +217,7 @@ impl PromiseChemistry {
     pub fn find_affinity_clusters(&self, min_cluster_size: usize) -> Vec<Vec<Uuid>> {
         use std::collections::{HashSet, VecDeque};
         
+        let now = Utc::now();
         let mut clusters = Vec::new();
         let mut visited = HashSet::new();
         
@@ -203,7 +238,7 @@ impl PromiseChemistry {
                 for ((from, to), bond) in &self.affinity_map {
                     if *from == current 
                         && !visited.contains(to) 
-                        && bond.strength >= self.config.cluster_threshold {
+                        && self.current_strength(bond, now) >= self.config.cluster_threshold {
                         visited.insert(*to);
                         queue.push_back(*to);
                     }
@@ -248,6 +283,9 @@ pub struct AffinityBond {
     
     /// Bond type
     pub bond_type: BondType,
+    
+    /// Last time the agents collaborated
+    pub last_interaction: DateTime<Utc>,
 }
 
 impl AffinityBond {
@@ -261,6 +299,7 @@ impl AffinityBond {
             failure_count: 0,
             semantic_factor: 0.5,
             bond_type: BondType::Neutral,
+            last_interaction: Utc::now(),
         }
     }
     
@@ -268,6 +307,7 @@ impl AffinityBond {
     pub fn strengthen(&mut self, amount: f64) {
         self.strength = (self.strength + amount).min(1.0);
         self.success_count += 1;
+        self.last_interaction = Utc::now();
         self.update_type();
     }
     
@@ -275,9 +315,21 @@ impl AffinityBond {
     pub fn weaken(&mut self, amount: f64) {
         self.strength = (self.strength - amount).max(0.0);
         self.failure_count += 1;
+        self.last_interaction = Utc::now();
         self.update_type();
     }
     
+    /// Strength at `now`, having decayed toward `baseline` since the last interaction
+    ///
+    /// The gap to the baseline halves every `half_life_secs` without interaction.
+    pub fn strength_at(&self, now: DateTime<Utc>, baseline: f64, half_life_secs: f64) -> f64 {
+        let idle_secs = (now - self.last_interaction).num_milliseconds().max(0) as f64 / 1000.0;
+        if half_life_secs <= 0.0 {
+            return baseline;
+        }
+        baseline + (self.strength - baseline) * 0.5_f64.powf(idle_secs / half_life_secs)
+    }
+    
     /// Apply decay
     pub fn decay(&mut self, rate: f64) {
         self.strength *= (1.0 - rate);
@@ -312,6 +364,15 @@ impl AffinityBond {
     }
 }
 
+/// Outcome of a collaboration between agents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollaborationOutcome {
+    /// The collaboration succeeded
+    Success,
+    /// The collaboration failed
+    Failure,
+}
+
 /// Types of affinity bonds
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BondType {
@@ -360,6 +421,12 @@ pub struct ChemistryConfig {
     
     /// Threshold for cluster formation
     pub cluster_threshold: f64,
+    
+    /// Idle time after which a bond is halfway back to its base affinity
+    pub decay_half_life_secs: f64,
+    
+    /// Strength change per reinforced collaboration
+    pub reinforcement_rate: f64,
 }
 
 impl Default for ChemistryConfig {
@@ -370,6 +437,8 @@ impl Default for ChemistryConfig {
             decay_rate: 0.01,
             min_bond_strength: 0.1,
             cluster_threshold: 0.6,
+            decay_half_life_secs: 7.0 * 24.0 * 3600.0, // One week
+            reinforcement_rate: 0.05,
         }
     }
 }
@@ -437,4 +506,52 @@ mod tests {
         assert_eq!(bond.strength, 0.3);
         assert_eq!(bond.failure_count, 1);
     }
+    
+    #[test]
+    fn test_reinforcement_raises_affinity() {
+        let mut chemistry = PromiseChemistry::new();
+        let (agent1, agent2) = (Uuid::new_v4(), Uuid::new_v4());
+        
+        let mut previous = chemistry.get_affinity(agent1, agent2);
+        for _ in 0..5 {
+            chemistry.reinforce(agent1, agent2, CollaborationOutcome::Success);
+            let affinity = chemistry.get_affinity(agent1, agent2);
+            assert!(affinity > previous);
+            previous = affinity;
+        }
+        assert!(chemistry.get_affinity(agent2, agent1) > 0.5);
+        
+        chemistry.reinforce(agent1, agent2, CollaborationOutcome::Failure);
+        assert!(chemistry.get_affinity(agent1, agent2) < previous);
+    }
+    
+    #[test]
+    fn test_idle_bond_decays_toward_baseline() {
+        let mut chemistry = PromiseChemistry::new();
+        let (agent1, agent2) = (Uuid::new_v4(), Uuid::new_v4());
+        for _ in 0..8 {
+            chemistry.reinforce(agent1, agent2, CollaborationOutcome::Success);
+        }
+        let baseline = chemistry.config.default_affinity;
+        let fresh = chemistry.get_affinity(agent1, agent2);
+        assert!(fresh > 0.8);
+        
+        // One half-life idle halves the gap to the baseline
+        let half_life = chrono::Duration::seconds(chemistry.config.decay_half_life_secs as i64);
+        let bond = chemistry.affinity_map.get_mut(&(agent1, agent2)).unwrap();
+        bond.last_interaction -= half_life;
+        let decayed = chemistry.get_affinity(agent1, agent2);
+        assert!((decayed - (baseline + (fresh - baseline) / 2.0)).abs() < 1e-3);
+        
+        // A long idle period brings it back to the baseline
+        let bond = chemistry.affinity_map.get_mut(&(agent1, agent2)).unwrap();
+        bond.last_interaction -= half_life * 20;
+        assert!((chemistry.get_affinity(agent1, agent2) - baseline).abs() < 1e-3);
+        assert!(chemistry.find_compatible_agents(agent1, 0.5).is_empty());
+        
+        // Collaborating again starts from the decayed strength
+        chemistry.reinforce(agent1, agent2, CollaborationOutcome::Success);
+        let renewed = chemistry.get_affinity(agent1, agent2);
+        assert!(renewed > baseline && renewed < fresh);
+    }
 }
\ No newline at end of file
@@ -148,7 +148,7 @@ impl SemanticPosition {
 }
 
 /// Semantic distance calculator with different metrics
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum SemanticDistance {
     /// Euclidean distance in semantic space
     Euclidean,
@@ -24,9 +24,9 @@ pub use coordinates::{SemanticCoords, SemanticPosition, SemanticDistance};
 pub use traits::{SemanticAgent, StoryTeller, VoluntaryAgent, NarrativeParticipant, Intent};
 pub use relations::{SemanticRelation, RelationType, SemanticLink};
 pub use story::{Story, StoryPath, StoryFragment, Narrative, StoryOutcome, StoryEvent, StoryContext, TrustDelta};
-pub use navigation::{SemanticNavigator, AgentNode, PathMetrics};
-pub use trust::{TrustScore, TrustNetwork, TrustRelationship, TrustCategory, TrustDecision};
-pub use chemistry::{PromiseChemistry, AffinityBond, CollaborationSuggestion};
+pub use navigation::{SemanticNavigator, AgentNode, AgentMetadata, PathMetrics};
+pub use trust::{TrustScore, TrustNetwork, TrustRelationship, TrustCategory, TrustDecision, GraphJson, GraphNode, GraphEdge};
+pub use chemistry::{PromiseChemistry, AffinityBond, CollaborationOutcome, CollaborationSuggestion};
 pub use substrates_bridge::{SubstratesStoryBridge, EventTypeMapper, SemanticPositionTracker};
 pub use serventis_bridge::{ServentisStoryHealth, StoryHealth, RecoveryAction, StoryHealthMetrics};
 pub use verification_gate::{VerificationGate, GateConfig, GateTicket, VerifiedStory, VerificationStrategy};
@@ -10,6 +10,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use petgraph::{
     graph::{DiGraph, NodeIndex},
     algo::{dijkstra, all_simple_paths},
+    visit::EdgeRef,
     Direction,
 };
 
@@ -56,6 +57,59 @@ impl SemanticNavigator {
         self.node_map.insert(agent_id, idx);
     }
     
+    /// Infer an agent's position from its declared metadata
+    ///
+    /// Every capability, dependency and keyword term is embedded as a point in
+    /// semantic space and the agent is placed at their weighted center, so
+    /// agents sharing terms land near each other. Terms from a dimension's
+    /// vocabulary (e.g. "verify" for promise) pull the agent along that
+    /// dimension, and dependencies on agents already in the space pull it
+    /// toward them.
+    pub fn infer_coordinates(&self, metadata: &AgentMetadata) -> SemanticCoords {
+        let mut points = Vec::new();
+        for capability in &metadata.capabilities {
+            points.extend(tokenize(capability).map(|term| (term_embedding(&term), CAPABILITY_WEIGHT)));
+        }
+        for keyword in &metadata.keywords {
+            points.extend(tokenize(keyword).map(|term| (term_embedding(&term), KEYWORD_WEIGHT)));
+        }
+        for dependency in &metadata.dependencies {
+            let known = self.graph
+                .node_weights()
+                .find(|node| node.name.eq_ignore_ascii_case(dependency));
+            match known {
+                Some(node) => points.push((node.position, CAPABILITY_WEIGHT)),
+                None => points.extend(tokenize(dependency).map(|term| (term_embedding(&term), DEPENDENCY_WEIGHT))),
+            }
+        }
+        
+        let total_weight: f64 = points.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0.0 {
+            return SemanticCoords::default();
+        }
+        
+        // The mean of many terms collapses toward the center of the space, so
+        // scale the offset back up to keep unrelated agents apart
+        let spread = total_weight.sqrt().max(1.0);
+        let axis = |value: fn(&SemanticCoords) -> f64| {
+            let mean = points.iter().map(|(p, w)| value(p) * w).sum::<f64>() / total_weight;
+            0.5 + (mean - 0.5) * spread
+        };
+        SemanticCoords::new(
+            axis(|c| c.intent),
+            axis(|c| c.promise),
+            axis(|c| c.context),
+            axis(|c| c.expression),
+        )
+    }
+    
+    /// Add an agent at the position inferred from its metadata
+    pub fn add_inferred_agent(&mut self, agent_id: Uuid, metadata: &AgentMetadata) -> SemanticCoords {
+        let position = self.infer_coordinates(metadata);
+        self.add_agent(agent_id, position, metadata.name.clone());
+        position
+    }
+    
     /// Connect two agents with a semantic relation
     pub fn connect_agents(
         &mut self,
@@ -298,6 +352,69 @@ pub struct AgentNode {
     pub visited_count: u64,
 }
 
+/// Declared metadata used to place an agent in semantic space
+#[derive(Debug, Clone, Default)]
+pub struct AgentMetadata {
+    /// Agent name
+    pub name: String,
+    
+    /// Capabilities the agent provides
+    pub capabilities: Vec<String>,
+    
+    /// Names of agents or modules the agent depends on
+    pub dependencies: Vec<String>,
+    
+    /// Free-form descriptive keywords
+    pub keywords: Vec<String>,
+}
+
+/// Weight of capability terms when inferring coordinates
+const CAPABILITY_WEIGHT: f64 = 1.0;
+
+/// Weight of keyword terms when inferring coordinates
+const KEYWORD_WEIGHT: f64 = 0.5;
+
+/// Weight of terms from unknown dependencies when inferring coordinates
+const DEPENDENCY_WEIGHT: f64 = 0.5;
+
+/// Terms that place an agent high on each dimension
+const INTENT_TERMS: &[&str] = &["intent", "goal", "plan", "planning", "task", "orchestrate", "coordinate", "decide"];
+const PROMISE_TERMS: &[&str] = &["promise", "verify", "verification", "trust", "guarantee", "proof", "consensus", "audit"];
+const CONTEXT_TERMS: &[&str] = &["context", "state", "memory", "storage", "cache", "environment", "session", "config"];
+const EXPRESSION_TERMS: &[&str] = &["expression", "render", "output", "ui", "api", "display", "report", "emit"];
+
+/// Lowercase alphanumeric words of a term
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+}
+
+/// Deterministic point in semantic space for a term
+///
+/// Uses FNV-1a so positions are stable across runs and builds.
+fn term_embedding(term: &str) -> SemanticCoords {
+    let hash = term.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    let component = |i: u32| ((hash >> (i * 16)) & 0xffff) as f64 / 65535.0;
+    let mut coords = SemanticCoords::new(component(0), component(1), component(2), component(3));
+    
+    if INTENT_TERMS.contains(&term) {
+        coords.intent = 1.0;
+    }
+    if PROMISE_TERMS.contains(&term) {
+        coords.promise = 1.0;
+    }
+    if CONTEXT_TERMS.contains(&term) {
+        coords.context = 1.0;
+    }
+    if EXPRESSION_TERMS.contains(&term) {
+        coords.expression = 1.0;
+    }
+    coords
+}
+
 /// Pathfinding algorithm options
 #[derive(Debug, Clone, Copy)]
 pub enum PathfindingAlgorithm {
@@ -385,4 +502,63 @@ mod tests {
         assert_eq!(nearest.len(), 2);
         assert_eq!(nearest[0].0, agent1); // Closest to origin
     }
+    
+    #[test]
+    fn test_infer_coordinates_places_similar_agents_nearby() {
+        let nav = SemanticNavigator::new();
+        let metadata = |name: &str, capabilities: &[&str], dependencies: &[&str]| AgentMetadata {
+            name: name.to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            keywords: vec![],
+        };
+        
+        let linter = nav.infer_coordinates(&metadata("linter", &["code-review", "static-analysis", "lint"], &["parser"]));
+        let reviewer = nav.infer_coordinates(&metadata("reviewer", &["code-review", "static-analysis", "style"], &["parser"]));
+        let billing = nav.infer_coordinates(&metadata("billing", &["invoice", "payment"], &["ledger"]));
+        let renderer = nav.infer_coordinates(&metadata("renderer", &["render", "display"], &["gpu"]));
+        
+        let distance = SemanticDistance::Euclidean;
+        let overlapping = distance.calculate(&linter, &reviewer);
+        assert!(overlapping < distance.calculate(&billing, &renderer));
+        assert!(overlapping < distance.calculate(&linter, &billing));
+        assert!(overlapping < distance.calculate(&reviewer, &renderer));
+        
+        // Inference is deterministic
+        assert_eq!(linter, nav.infer_coordinates(&metadata("linter", &["code-review", "static-analysis", "lint"], &["parser"])));
+        assert_eq!(nav.infer_coordinates(&AgentMetadata::default()), SemanticCoords::default());
+    }
+    
+    #[test]
+    fn test_infer_coordinates_uses_vocabulary_and_known_dependencies() {
+        let mut nav = SemanticNavigator::new();
+        let verifier = nav.infer_coordinates(&AgentMetadata {
+            name: "verifier".to_string(),
+            capabilities: vec!["verify".to_string(), "audit".to_string()],
+            ..Default::default()
+        });
+        assert!(verifier.promise > 0.9);
+        
+        // Depending on a placed agent pulls a new agent toward it
+        let storage = Uuid::new_v4();
+        let storage_position = SemanticCoords::new(0.1, 0.1, 0.9, 0.1);
+        nav.add_agent(storage, storage_position, "storage".to_string());
+        let plain = AgentMetadata {
+            name: "indexer".to_string(),
+            capabilities: vec!["index".to_string()],
+            ..Default::default()
+        };
+        let dependent = AgentMetadata {
+            dependencies: vec!["Storage".to_string()],
+            ..plain.clone()
+        };
+        assert!(
+            nav.infer_coordinates(&dependent).distance_to(&storage_position)
+                < nav.infer_coordinates(&plain).distance_to(&storage_position)
+        );
+        
+        let id = Uuid::new_v4();
+        let position = nav.add_inferred_agent(id, &dependent);
+        assert_eq!(nav.find_nearest(position, 1)[0].0, id);
+    }
 }
\ No newline at end of file
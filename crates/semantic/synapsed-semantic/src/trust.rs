@@ -2,7 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
 use chrono::{DateTime, Utc};
 
 /// Trust score between agents (0-1)
@@ -151,6 +152,84 @@ impl TrustCategory {
             Self::Untrusted => 0.0,
         }
     }
+    
+    /// GraphViz color used when exporting edges of this category
+    fn dot_color(&self) -> &'static str {
+        match self {
+            Self::High => "darkgreen",
+            Self::Good => "green",
+            Self::Neutral => "gray",
+            Self::Low => "orange",
+            Self::Untrusted => "red",
+        }
+    }
+}
+
+/// Trust network exported as a graph for visualization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphJson {
+    /// Agents in the network
+    pub nodes: Vec<GraphNode>,
+    /// Directed trust relationships
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Agent node in an exported trust graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Agent ID
+    pub id: Uuid,
+    /// Global reputation
+    pub reputation: f64,
+}
+
+/// Directed edge from trustor to trustee in an exported trust graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// The trustor
+    pub from: Uuid,
+    /// The trustee
+    pub to: Uuid,
+    /// Current trust score
+    pub weight: f64,
+    /// Trust category
+    pub category: TrustCategory,
+    /// Number of interactions
+    pub interaction_count: u64,
+    /// Confidence in the trust score
+    pub confidence: f64,
+}
+
+impl GraphJson {
+    /// Render the graph in GraphViz DOT format
+    ///
+    /// Edges are colored by category and drawn thicker the higher the trust.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph trust {\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\\n{:.2}\"];",
+                node.id,
+                &node.id.to_string()[..8],
+                node.reputation
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [weight={:.3}, label=\"{:.2}\", color={}, penwidth={:.2}];",
+                edge.from,
+                edge.to,
+                edge.weight,
+                edge.weight,
+                edge.category.dot_color(),
+                1.0 + edge.weight * 4.0
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
 }
 
 /// Trust network managing all trust relationships
@@ -291,6 +370,44 @@ impl TrustNetwork {
         
         None
     }
+    
+    /// Export the network as a graph of agents and trust relationships
+    ///
+    /// Nodes and edges are sorted by agent ID so exports are stable.
+    pub fn to_graph_json(&self) -> GraphJson {
+        let agents: BTreeSet<Uuid> = self.relationships
+            .keys()
+            .flat_map(|&(from, to)| [from, to])
+            .chain(self.reputation.keys().copied())
+            .collect();
+        let nodes = agents
+            .into_iter()
+            .map(|id| GraphNode {
+                id,
+                reputation: self.get_reputation(id),
+            })
+            .collect();
+        
+        let mut edges: Vec<GraphEdge> = self.relationships
+            .values()
+            .map(|rel| GraphEdge {
+                from: rel.from,
+                to: rel.to,
+                weight: rel.score.value(),
+                category: rel.category,
+                interaction_count: rel.interaction_count,
+                confidence: rel.confidence(),
+            })
+            .collect();
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+        
+        GraphJson { nodes, edges }
+    }
+    
+    /// Export the network in GraphViz DOT format
+    pub fn to_dot(&self) -> String {
+        self.to_graph_json().to_dot()
+    }
 }
 
 impl Default for TrustNetwork {
@@ -418,4 +535,83 @@ mod tests {
         let rep = network.get_reputation(agent2);
         assert!(rep > 0.5);
     }
+    
+    #[test]
+    fn test_graph_export() {
+        let mut network = TrustNetwork::new();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        for _ in 0..8 {
+            network.record_interaction(a, b, true);
+        }
+        network.record_interaction(b, c, false);
+        network.record_interaction(c, a, true);
+        network.apply_decay();
+        
+        let graph = network.to_graph_json();
+        let mut agents = vec![a, b, c];
+        agents.sort();
+        assert_eq!(graph.nodes.iter().map(|n| n.id).collect::<Vec<_>>(), agents);
+        for node in &graph.nodes {
+            assert_eq!(node.reputation, network.get_reputation(node.id));
+        }
+        
+        assert_eq!(graph.edges.len(), 3);
+        for edge in &graph.edges {
+            let rel = &network.relationships[&(edge.from, edge.to)];
+            assert_eq!(edge.weight, network.get_trust(edge.from, edge.to).value());
+            assert_eq!(edge.category, rel.category);
+            assert_eq!(edge.interaction_count, rel.interaction_count);
+        }
+        let edge = |from, to| graph.edges.iter().find(|e| e.from == from && e.to == to).unwrap();
+        assert_eq!(edge(a, b).category, TrustCategory::High);
+        assert_eq!(edge(b, c).category, TrustCategory::Low);
+        
+        let json = serde_json::to_value(&graph).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["edges"][0]["category"], serde_json::json!(graph.edges[0].category));
+    }
+    
+    #[test]
+    fn test_dot_export_parses() {
+        let mut network = TrustNetwork::new();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        network.record_interaction(a, b, true);
+        network.record_interaction(a, c, false);
+        network.record_interaction(c, b, true);
+        
+        let dot = network.to_dot();
+        let mut lines = dot.lines();
+        assert_eq!(lines.next(), Some("digraph trust {"));
+        assert_eq!(dot.lines().last(), Some("}"));
+        
+        // Every statement is a quoted node or edge with a balanced attribute list
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        for line in dot.lines().skip(1).filter(|l| *l != "}") {
+            let statement = line.trim().strip_suffix("];").expect("Statement ends with an attribute list");
+            let (target, attributes) = statement.split_once(" [").expect("Statement has attributes");
+            assert!(!attributes.contains('[') && attributes.matches('"').count() % 2 == 0);
+            
+            let ids: Vec<Uuid> = target
+                .split(" -> ")
+                .map(|id| id.strip_prefix('"').and_then(|id| id.strip_suffix('"')).unwrap().parse().unwrap())
+                .collect();
+            match ids.as_slice() {
+                [id] => nodes.push(*id),
+                [from, to] => {
+                    let weight = attributes.split(", ").find_map(|a| a.strip_prefix("weight=")).unwrap();
+                    let weight: f64 = weight.parse().unwrap();
+                    assert!((weight - network.get_trust(*from, *to).value()).abs() < 1e-3);
+                    edges.push((*from, *to));
+                }
+                _ => panic!("Unexpected statement: {}", line),
+            }
+        }
+        
+        assert_eq!(nodes.len(), 3);
+        edges.sort();
+        let mut expected = vec![(a, b), (a, c), (c, b)];
+        expected.sort();
+        assert_eq!(edges, expected);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+//! Merge audit reports
+//!
+//! Merges resolve conflicts silently. An audited merge additionally records
+//! which operations conflicted and how each conflict was resolved, which
+//! helps explain surprising convergence outcomes. Only concurrent writes
+//! conflict: a write made after observing the other replica's write is a
+//! plain overwrite and is not reported.
+
+use crate::types::{ActorId, VectorClock};
+use serde::{Deserialize, Serialize};
+
+/// How a merge conflict was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resolution {
+    /// The operation with the greater timestamp won, ties broken by actor
+    LastWriterWins,
+    /// An add survived a concurrent remove that had not observed it
+    AddWins,
+}
+
+/// Two conflicting operations and which one the merge kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeConflict<Op> {
+    /// Operation reflected in the merged state
+    pub winner: Op,
+    /// Operation overridden by the winner
+    pub loser: Op,
+    /// Rule that decided the conflict
+    pub resolution: Resolution,
+}
+
+/// Conflicts resolved while merging another replica
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeReport<Op> {
+    /// Conflicts in the order they were found
+    pub conflicts: Vec<MergeConflict<Op>>,
+}
+
+impl<Op> MergeReport<Op> {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self { conflicts: Vec::new() }
+    }
+    
+    /// Record a resolved conflict
+    pub fn record(&mut self, winner: Op, loser: Op, resolution: Resolution) {
+        self.conflicts.push(MergeConflict { winner, loser, resolution });
+    }
+    
+    /// Get the number of conflicts
+    pub fn len(&self) -> usize {
+        self.conflicts.len()
+    }
+    
+    /// Check if the merge resolved no conflicts
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl<Op> Default for MergeReport<Op> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Latest write time applied from each actor
+///
+/// An actor's writes are applied in time order, so a replica has observed a
+/// write once its entry for the write's actor reaches the write's time.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ObservedWrites(VectorClock);
+
+impl ObservedWrites {
+    /// Record that a write has been applied
+    pub(crate) fn record(&mut self, actor: ActorId, time: u64) {
+        if self.0.get(&actor) < time {
+            self.0.set(actor, time);
+        }
+    }
+    
+    /// Check if a write has been applied
+    pub(crate) fn contains(&self, actor: &ActorId, time: u64) -> bool {
+        self.0.get(actor) >= time
+    }
+    
+    /// Include the writes observed by another replica
+    pub(crate) fn merge(&mut self, other: &ObservedWrites) {
+        self.0.merge(&other.0);
+    }
+}
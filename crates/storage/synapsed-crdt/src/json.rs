@@ -0,0 +1,1133 @@
+//! JSON document CRDT for schemaless structured data
+//!
+//! A `JsonCrdt` holds an arbitrary JSON document. Every object field and array
+//! element is a last-writer-wins register ordered by hybrid logical clock, with
+//! the replica ID as a deterministic tiebreak, so concurrent edits to different
+//! paths merge and concurrent edits to the same path converge on one value.
+//! Arrays use RGA ordering so concurrently inserted elements keep a stable
+//! order on every replica.
+
+use crate::{
+    audit::{MergeReport, ObservedWrites, Resolution},
+    error::{CrdtError, Result},
+    traits::{Crdt, Mergeable, Synchronizable},
+    types::{ActorId, Delta, HybridLogicalClock, VectorClock},
+    clock::ClockManager,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fmt::{self, Display},
+    str::FromStr,
+};
+use parking_lot::RwLock;
+use uuid::Uuid;
+
+/// Segment of a path into a JSON document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathSegment {
+    /// Object field
+    Key(String),
+    /// Array element by visible index
+    Index(usize),
+}
+
+/// Path into a JSON document, written as `servers[0].host`
+///
+/// The empty path refers to the document root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonPath(Vec<PathSegment>);
+
+impl JsonPath {
+    /// Create the path to the document root
+    pub fn root() -> Self {
+        Self(Vec::new())
+    }
+    
+    /// Get the path segments
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+    
+    /// Check if this is the root path
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = CrdtError;
+    
+    fn from_str(path: &str) -> Result<Self> {
+        let invalid = |reason: &str| CrdtError::InvalidOperation(format!("Invalid path '{}': {}", path, reason));
+        let mut segments = Vec::new();
+        
+        if path.is_empty() {
+            return Ok(Self::root());
+        }
+        
+        for part in path.split('.') {
+            let (key, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+            
+            if key.is_empty() && (rest.is_empty() || !segments.is_empty()) {
+                return Err(invalid("empty key"));
+            }
+            if !key.is_empty() {
+                segments.push(PathSegment::Key(key.to_string()));
+            }
+            
+            while !rest.is_empty() {
+                let close = rest.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+                let index = rest[1..close].parse().map_err(|_| invalid("bad array index"))?;
+                segments.push(PathSegment::Index(index));
+                
+                rest = &rest[close + 1..];
+                if !rest.is_empty() && !rest.starts_with('[') {
+                    return Err(invalid("unexpected characters after ']'"));
+                }
+            }
+        }
+        
+        Ok(Self(segments))
+    }
+}
+
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                PathSegment::Key(key) => write!(f, ".{}", key)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Unique identifier for an array element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementId {
+    /// Timestamp of the operation that created the element
+    pub timestamp: HybridLogicalClock,
+    /// Position within that operation, for arrays assigned as a whole
+    pub seq: u32,
+}
+
+impl ElementId {
+    /// Compare element IDs for RGA ordering
+    pub fn compare(&self, other: &ElementId) -> Ordering {
+        self.timestamp
+            .compare(&other.timestamp)
+            .then_with(|| self.seq.cmp(&other.seq))
+    }
+}
+
+/// Step of a path resolved against a document, addressing array elements by ID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathStep {
+    /// Object field
+    Key(String),
+    /// Array element
+    Element(ElementId),
+}
+
+/// JSON CRDT operation types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JsonOperation {
+    /// Assign a value, creating missing parent objects
+    Set {
+        path: Vec<PathStep>,
+        value: Value,
+        timestamp: HybridLogicalClock,
+    },
+    /// Insert a new element into the array at `path`
+    Insert {
+        path: Vec<PathStep>,
+        id: ElementId,
+        origin: Option<ElementId>, // None means insert at beginning
+        value: Value,
+    },
+    /// Delete the value at a path
+    Delete {
+        path: Vec<PathStep>,
+        timestamp: HybridLogicalClock,
+    },
+}
+
+/// Value held in a document register
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum JsonNode {
+    /// Null, boolean, number or string
+    Primitive(Value),
+    /// Object with a register per field
+    Object(JsonObject),
+    /// Array with RGA-ordered elements
+    Array(JsonArray),
+}
+
+impl JsonNode {
+    /// Build a node from a JSON value assigned at `timestamp`
+    fn from_value(value: &Value, timestamp: HybridLogicalClock) -> Self {
+        match value {
+            Value::Object(map) => JsonNode::Object(JsonObject {
+                created: Some(timestamp),
+                fields: map
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Register::new(JsonNode::from_value(value, timestamp), timestamp)))
+                    .collect(),
+            }),
+            Value::Array(items) => {
+                let mut elements = Vec::with_capacity(items.len());
+                let mut origin = None;
+                for (seq, item) in items.iter().enumerate() {
+                    let id = ElementId { timestamp, seq: seq as u32 };
+                    elements.push(Element {
+                        id,
+                        origin,
+                        register: Register::new(JsonNode::from_value(item, timestamp), timestamp),
+                    });
+                    origin = Some(id);
+                }
+                JsonNode::Array(JsonArray { created: Some(timestamp), elements })
+            }
+            primitive => JsonNode::Primitive(primitive.clone()),
+        }
+    }
+    
+    /// Convert to a plain JSON value
+    fn to_value(&self) -> Value {
+        match self {
+            JsonNode::Primitive(value) => value.clone(),
+            JsonNode::Object(object) => Value::Object(
+                object
+                    .fields
+                    .iter()
+                    .filter_map(|(key, register)| register.value.as_ref().map(|node| (key.clone(), node.to_value())))
+                    .collect(),
+            ),
+            JsonNode::Array(array) => Value::Array(array.visible().map(|element| element.1.to_value()).collect()),
+        }
+    }
+}
+
+/// Last-writer-wins register holding a value or a deletion tombstone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Register {
+    /// Current value, `None` if deleted
+    value: Option<JsonNode>,
+    /// Timestamp of the last assignment
+    timestamp: HybridLogicalClock,
+}
+
+impl Register {
+    /// Create a register holding a value
+    fn new(value: JsonNode, timestamp: HybridLogicalClock) -> Self {
+        Self { value: Some(value), timestamp }
+    }
+    
+    /// Create an empty register that any assignment overwrites
+    fn vacant() -> Self {
+        Self {
+            value: None,
+            timestamp: HybridLogicalClock {
+                logical_time: 0,
+                physical_time: 0,
+                replica_id: ActorId::from_uuid(Uuid::nil()),
+            },
+        }
+    }
+    
+    /// Assign a value if `timestamp` is newer than the current one
+    fn assign(&mut self, value: Option<JsonNode>, timestamp: HybridLogicalClock) {
+        if timestamp.compare(&self.timestamp).is_gt() {
+            self.value = value;
+            self.timestamp = timestamp;
+        }
+    }
+    
+    /// Merge with a register from another replica
+    ///
+    /// Containers created by the same assignment, or implicitly as parents,
+    /// merge their contents; anything else is last-writer-wins.
+    fn merge(&mut self, other: &Register) {
+        match (&mut self.value, &other.value) {
+            (Some(JsonNode::Object(local)), Some(JsonNode::Object(remote))) if local.created == remote.created => {
+                local.merge(remote);
+            }
+            (Some(JsonNode::Array(local)), Some(JsonNode::Array(remote))) if local.created == remote.created => {
+                local.merge(remote);
+            }
+            _ => {
+                self.assign(other.value.clone(), other.timestamp);
+                return;
+            }
+        }
+        
+        if other.timestamp.compare(&self.timestamp).is_gt() {
+            self.timestamp = other.timestamp;
+        }
+    }
+}
+
+/// Object with a register per field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct JsonObject {
+    /// Assignment that created this object, `None` for the root and for
+    /// objects created implicitly as parents of a nested assignment
+    created: Option<HybridLogicalClock>,
+    /// Fields, including deleted ones
+    fields: BTreeMap<String, Register>,
+}
+
+impl JsonObject {
+    /// Merge fields from another replica
+    fn merge(&mut self, other: &JsonObject) {
+        for (key, register) in &other.fields {
+            match self.fields.get_mut(key) {
+                Some(local) => local.merge(register),
+                None => {
+                    self.fields.insert(key.clone(), register.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Array element with its RGA metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Element {
+    /// Unique element ID
+    id: ElementId,
+    /// Element this one was inserted after
+    origin: Option<ElementId>,
+    /// Element value
+    register: Register,
+}
+
+/// Array with RGA-ordered elements
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct JsonArray {
+    /// Assignment that created this array
+    created: Option<HybridLogicalClock>,
+    /// Elements in document order, including deleted ones
+    elements: Vec<Element>,
+}
+
+impl JsonArray {
+    /// Iterate visible elements in order
+    fn visible(&self) -> impl Iterator<Item = (ElementId, &JsonNode)> {
+        self.elements
+            .iter()
+            .filter_map(|element| element.register.value.as_ref().map(|node| (element.id, node)))
+    }
+    
+    /// Get ID of the visible element at an index
+    fn visible_id(&self, index: usize) -> Option<ElementId> {
+        self.visible().nth(index).map(|(id, _)| id)
+    }
+    
+    /// Get mutable element by ID
+    fn element_mut(&mut self, id: &ElementId) -> Option<&mut Element> {
+        self.elements.iter_mut().find(|element| element.id == *id)
+    }
+    
+    /// Insert an element after its origin, keeping RGA order
+    ///
+    /// Concurrent inserts after the same origin are ordered by descending ID.
+    /// Elements whose origin is unknown are dropped.
+    fn integrate(&mut self, element: Element) {
+        if self.elements.iter().any(|existing| existing.id == element.id) {
+            return;
+        }
+        
+        let mut position = match element.origin {
+            Some(origin) => match self.elements.iter().position(|existing| existing.id == origin) {
+                Some(index) => index + 1,
+                None => return,
+            },
+            None => 0,
+        };
+        
+        while position < self.elements.len() && self.elements[position].id.compare(&element.id).is_gt() {
+            position += 1;
+        }
+        
+        self.elements.insert(position, element);
+    }
+    
+    /// Merge elements from another replica
+    fn merge(&mut self, other: &JsonArray) {
+        // Origins always precede their elements, so document order is causal
+        for element in &other.elements {
+            match self.element_mut(&element.id) {
+                Some(local) => local.register.merge(&element.register),
+                None => self.integrate(element.clone()),
+            }
+        }
+    }
+}
+
+/// Get the register for a path step, creating an empty field if `create` is set
+fn child_register<'a>(node: &'a mut JsonNode, step: &PathStep, create: bool) -> Option<&'a mut Register> {
+    match (node, step) {
+        (JsonNode::Object(object), PathStep::Key(key)) => {
+            if create {
+                Some(object.fields.entry(key.clone()).or_insert_with(Register::vacant))
+            } else {
+                object.fields.get_mut(key)
+            }
+        }
+        (JsonNode::Array(array), PathStep::Element(id)) => array.element_mut(id).map(|element| &mut element.register),
+        _ => None,
+    }
+}
+
+/// Walk `parents` from `node`
+///
+/// When `vivify` carries the timestamp of an assignment, missing parent
+/// objects are created, subject to last-writer-wins against existing values.
+fn walk_mut<'a>(
+    mut node: &'a mut JsonNode,
+    parents: &[PathStep],
+    last: &PathStep,
+    vivify: Option<HybridLogicalClock>,
+) -> Option<&'a mut JsonNode> {
+    for (i, step) in parents.iter().enumerate() {
+        let register = child_register(node, step, vivify.is_some())?;
+        
+        if let Some(timestamp) = vivify {
+            let next = parents.get(i + 1).unwrap_or(last);
+            if matches!(next, PathStep::Key(_)) && !matches!(register.value, Some(JsonNode::Object(_))) {
+                register.assign(Some(JsonNode::Object(JsonObject::default())), timestamp);
+            }
+        }
+        
+        node = register.value.as_mut()?;
+    }
+    
+    Some(node)
+}
+
+/// Resolved target of a local edit
+enum Target {
+    /// Existing or new register
+    Register(Vec<PathStep>),
+    /// Position one past the end of an array
+    Append {
+        array: Vec<PathStep>,
+        origin: Option<ElementId>,
+    },
+}
+
+/// JSON document state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonState {
+    /// Root object
+    root: JsonNode,
+}
+
+impl JsonState {
+    /// Create new empty document state
+    pub fn new() -> Self {
+        Self {
+            root: JsonNode::Object(JsonObject::default()),
+        }
+    }
+    
+    /// Get the document as a plain JSON value
+    pub fn to_value(&self) -> Value {
+        self.root.to_value()
+    }
+    
+    /// Get the node at a path
+    fn node(&self, path: &JsonPath) -> Option<&JsonNode> {
+        let mut node = &self.root;
+        for segment in path.segments() {
+            node = match (node, segment) {
+                (JsonNode::Object(object), PathSegment::Key(key)) => object.fields.get(key)?.value.as_ref()?,
+                (JsonNode::Array(array), PathSegment::Index(index)) => array.visible().nth(*index)?.1,
+                _ => return None,
+            };
+        }
+        Some(node)
+    }
+    
+    /// Resolve a user path into element IDs
+    ///
+    /// Missing object keys are allowed and will be created by the assignment.
+    fn resolve(&self, path: &JsonPath) -> Result<Target> {
+        let invalid = |reason: &str| CrdtError::InvalidOperation(format!("Cannot resolve '{}': {}", path, reason));
+        let mut steps = Vec::new();
+        let mut node = Some(&self.root);
+        
+        if path.is_root() {
+            return Err(invalid("the document root cannot be replaced"));
+        }
+        
+        for (i, segment) in path.segments().iter().enumerate() {
+            match (node, segment) {
+                (Some(JsonNode::Object(object)), PathSegment::Key(key)) => {
+                    node = object.fields.get(key).and_then(|register| register.value.as_ref());
+                    steps.push(PathStep::Key(key.clone()));
+                }
+                (None, PathSegment::Key(key)) => steps.push(PathStep::Key(key.clone())),
+                (Some(JsonNode::Array(array)), PathSegment::Index(index)) => {
+                    match array.visible().nth(*index) {
+                        Some((id, child)) => {
+                            node = Some(child);
+                            steps.push(PathStep::Element(id));
+                        }
+                        None if *index == array.visible().count() && i + 1 == path.segments().len() => {
+                            let origin = array.visible().last().map(|(id, _)| id);
+                            return Ok(Target::Append { array: steps, origin });
+                        }
+                        None => return Err(invalid("array index out of bounds")),
+                    }
+                }
+                (_, PathSegment::Key(_)) => return Err(invalid("not an object")),
+                (_, PathSegment::Index(_)) => return Err(invalid("not an array")),
+            }
+        }
+        
+        Ok(Target::Register(steps))
+    }
+    
+    /// Apply an operation to the document
+    ///
+    /// Operations that target missing elements or lose to a newer assignment
+    /// are ignored.
+    fn apply(&mut self, operation: &JsonOperation) {
+        match operation {
+            JsonOperation::Set { path, value, timestamp } => {
+                let Some((last, parents)) = path.split_last() else { return };
+                if let Some(register) = walk_mut(&mut self.root, parents, last, Some(*timestamp))
+                    .and_then(|node| child_register(node, last, true))
+                {
+                    register.assign(Some(JsonNode::from_value(value, *timestamp)), *timestamp);
+                }
+            }
+            JsonOperation::Insert { path, id, origin, value } => {
+                let Some((last, parents)) = path.split_last() else { return };
+                if let Some(register) = walk_mut(&mut self.root, parents, last, None)
+                    .and_then(|node| child_register(node, last, false))
+                {
+                    if let Some(JsonNode::Array(array)) = &mut register.value {
+                        array.integrate(Element {
+                            id: *id,
+                            origin: *origin,
+                            register: Register::new(JsonNode::from_value(value, id.timestamp), id.timestamp),
+                        });
+                    }
+                }
+            }
+            JsonOperation::Delete { path, timestamp } => {
+                let Some((last, parents)) = path.split_last() else { return };
+                // Record a tombstone even for missing fields so older assignments lose
+                if let Some(register) = walk_mut(&mut self.root, parents, last, None)
+                    .and_then(|node| child_register(node, last, true))
+                {
+                    register.assign(None, *timestamp);
+                }
+            }
+        }
+    }
+    
+    /// Merge with state from another replica
+    fn merge(&mut self, other: &JsonState) {
+        if let (JsonNode::Object(local), JsonNode::Object(remote)) = (&mut self.root, &other.root) {
+            local.merge(remote);
+        }
+    }
+    
+    /// Record the conflicts that merging `other` resolves
+    ///
+    /// `observed` holds the writes seen by this replica and by `other`.
+    fn record_conflicts(
+        &self,
+        other: &JsonState,
+        observed: (&ObservedWrites, &ObservedWrites),
+        report: &mut MergeReport<JsonOperation>,
+    ) {
+        if let (JsonNode::Object(local), JsonNode::Object(remote)) = (&self.root, &other.root) {
+            conflicts_object(&mut Vec::new(), local, remote, observed, report);
+        }
+    }
+}
+
+impl Default for JsonState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collect operations that bring `local` up to date with `other`
+fn diff_register(path: &mut Vec<PathStep>, local: Option<&Register>, other: &Register, ops: &mut Vec<JsonOperation>) {
+    match (local.and_then(|register| register.value.as_ref()), &other.value) {
+        (Some(JsonNode::Object(a)), Some(JsonNode::Object(b))) if a.created == b.created => {
+            diff_object(path, Some(a), b, ops);
+        }
+        (Some(JsonNode::Array(a)), Some(JsonNode::Array(b))) if a.created == b.created => {
+            diff_array(path, a, b, ops);
+        }
+        _ if local.is_none_or(|register| other.timestamp.compare(&register.timestamp).is_gt()) => {
+            match &other.value {
+                None => ops.push(JsonOperation::Delete {
+                    path: path.clone(),
+                    timestamp: other.timestamp,
+                }),
+                // Implicit parents are recreated by their fields' assignments
+                Some(JsonNode::Object(object)) if object.created.is_none() => diff_object(path, None, object, ops),
+                Some(node) => ops.push(JsonOperation::Set {
+                    path: path.clone(),
+                    value: node.to_value(),
+                    timestamp: other.timestamp,
+                }),
+            }
+        }
+        _ => {}
+    }
+}
+
+fn diff_object(path: &mut Vec<PathStep>, local: Option<&JsonObject>, other: &JsonObject, ops: &mut Vec<JsonOperation>) {
+    for (key, register) in &other.fields {
+        path.push(PathStep::Key(key.clone()));
+        diff_register(path, local.and_then(|object| object.fields.get(key)), register, ops);
+        path.pop();
+    }
+}
+
+fn diff_array(path: &mut Vec<PathStep>, local: &JsonArray, other: &JsonArray, ops: &mut Vec<JsonOperation>) {
+    for element in &other.elements {
+        let existing = local.elements.iter().find(|candidate| candidate.id == element.id);
+        
+        if existing.is_none() {
+            ops.push(JsonOperation::Insert {
+                path: path.clone(),
+                id: element.id,
+                origin: element.origin,
+                value: element.register.value.as_ref().map_or(Value::Null, JsonNode::to_value),
+            });
+            if element.register.timestamp == element.id.timestamp && element.register.value.is_some() {
+                continue;
+            }
+        }
+        
+        path.push(PathStep::Element(element.id));
+        diff_register(path, existing.map(|existing| &existing.register), &element.register, ops);
+        path.pop();
+    }
+}
+
+/// Operation that produced a register's current value
+fn register_operation(path: &[PathStep], register: &Register) -> JsonOperation {
+    match &register.value {
+        Some(node) => JsonOperation::Set {
+            path: path.to_vec(),
+            value: node.to_value(),
+            timestamp: register.timestamp,
+        },
+        None => JsonOperation::Delete {
+            path: path.to_vec(),
+            timestamp: register.timestamp,
+        },
+    }
+}
+
+/// Collect conflicts between registers that merging `other` into `local` resolves
+///
+/// Mirrors `Register::merge`: containers from the same assignment are merged
+/// field by field, anything else is a last-writer-wins conflict unless one
+/// write had already observed the other.
+fn conflicts_register(
+    path: &mut Vec<PathStep>,
+    local: &Register,
+    other: &Register,
+    observed: (&ObservedWrites, &ObservedWrites),
+    report: &mut MergeReport<JsonOperation>,
+) {
+    let (local_observed, other_observed) = observed;
+    match (&local.value, &other.value) {
+        (Some(JsonNode::Object(a)), Some(JsonNode::Object(b))) if a.created == b.created => {
+            conflicts_object(path, a, b, observed, report);
+        }
+        (Some(JsonNode::Array(a)), Some(JsonNode::Array(b))) if a.created == b.created => {
+            conflicts_array(path, a, b, observed, report);
+        }
+        // Vacant registers hold no write to conflict with
+        _ if *local == Register::vacant() || *other == Register::vacant() => {}
+        // A write made after observing the other one simply overwrites it
+        _ if other_observed.contains(&local.timestamp.replica_id, local.timestamp.logical_time)
+            || local_observed.contains(&other.timestamp.replica_id, other.timestamp.logical_time) => {}
+        _ => match other.timestamp.compare(&local.timestamp) {
+            Ordering::Greater => report.record(
+                register_operation(path, other),
+                register_operation(path, local),
+                Resolution::LastWriterWins,
+            ),
+            Ordering::Less => report.record(
+                register_operation(path, local),
+                register_operation(path, other),
+                Resolution::LastWriterWins,
+            ),
+            Ordering::Equal => {}
+        },
+    }
+}
+
+fn conflicts_object(
+    path: &mut Vec<PathStep>,
+    local: &JsonObject,
+    other: &JsonObject,
+    observed: (&ObservedWrites, &ObservedWrites),
+    report: &mut MergeReport<JsonOperation>,
+) {
+    for (key, register) in &other.fields {
+        if let Some(existing) = local.fields.get(key) {
+            path.push(PathStep::Key(key.clone()));
+            conflicts_register(path, existing, register, observed, report);
+            path.pop();
+        }
+    }
+}
+
+fn conflicts_array(
+    path: &mut Vec<PathStep>,
+    local: &JsonArray,
+    other: &JsonArray,
+    observed: (&ObservedWrites, &ObservedWrites),
+    report: &mut MergeReport<JsonOperation>,
+) {
+    for element in &other.elements {
+        if let Some(existing) = local.elements.iter().find(|candidate| candidate.id == element.id) {
+            path.push(PathStep::Element(element.id));
+            conflicts_register(path, &existing.register, &element.register, observed, report);
+            path.pop();
+        }
+    }
+}
+
+/// JSON document CRDT
+#[derive(Debug)]
+pub struct JsonCrdt {
+    /// Actor ID for this replica
+    actor_id: ActorId,
+    /// Current document state
+    state: RwLock<JsonState>,
+    /// Clock manager
+    clock_manager: ClockManager,
+    /// Writes applied from each replica
+    observed: RwLock<ObservedWrites>,
+}
+
+impl JsonCrdt {
+    /// Create new empty JSON document
+    pub fn new(actor_id: ActorId) -> Self {
+        Self {
+            actor_id,
+            state: RwLock::new(JsonState::new()),
+            clock_manager: ClockManager::new(actor_id),
+            observed: RwLock::new(ObservedWrites::default()),
+        }
+    }
+    
+    /// Set the value at a path
+    ///
+    /// Missing parent objects are created. Setting one past the end of an
+    /// array appends to it.
+    pub async fn set(&mut self, path: &str, value: Value) -> Result<JsonOperation> {
+        let path: JsonPath = path.parse()?;
+        let target = self.state.read().resolve(&path)?;
+        let timestamp = self.clock_manager.advance_hlc();
+        
+        let operation = match target {
+            Target::Register(path) => JsonOperation::Set { path, value, timestamp },
+            Target::Append { array, origin } => JsonOperation::Insert {
+                path: array,
+                id: ElementId { timestamp, seq: 0 },
+                origin,
+                value,
+            },
+        };
+        
+        self.apply_operation(operation.clone()).await?;
+        Ok(operation)
+    }
+    
+    /// Insert a value into the array at `path` before the element at `index`
+    pub async fn insert(&mut self, path: &str, index: usize, value: Value) -> Result<JsonOperation> {
+        let path: JsonPath = path.parse()?;
+        let (array, origin) = {
+            let state = self.state.read();
+            let Target::Register(steps) = state.resolve(&path)? else {
+                return Err(CrdtError::InvalidOperation(format!("'{}' is not an array", path)));
+            };
+            let Some(JsonNode::Array(array)) = state.node(&path) else {
+                return Err(CrdtError::InvalidOperation(format!("'{}' is not an array", path)));
+            };
+            
+            let origin = match index {
+                0 => None,
+                _ => Some(array.visible_id(index - 1).ok_or_else(|| {
+                    CrdtError::InvalidOperation(format!("Index {} out of bounds for '{}'", index, path))
+                })?),
+            };
+            (steps, origin)
+        };
+        
+        let timestamp = self.clock_manager.advance_hlc();
+        let operation = JsonOperation::Insert {
+            path: array,
+            id: ElementId { timestamp, seq: 0 },
+            origin,
+            value,
+        };
+        
+        self.apply_operation(operation.clone()).await?;
+        Ok(operation)
+    }
+    
+    /// Delete the value at a path
+    pub async fn delete(&mut self, path: &str) -> Result<JsonOperation> {
+        let path: JsonPath = path.parse()?;
+        let steps = {
+            let state = self.state.read();
+            match state.resolve(&path)? {
+                Target::Register(steps) if state.node(&path).is_some() => steps,
+                _ => return Err(CrdtError::NodeNotFound { id: path.to_string() }),
+            }
+        };
+        
+        let operation = JsonOperation::Delete {
+            path: steps,
+            timestamp: self.clock_manager.advance_hlc(),
+        };
+        
+        self.apply_operation(operation.clone()).await?;
+        Ok(operation)
+    }
+    
+    /// Get the value at a path, or `None` if it does not exist
+    pub fn get(&self, path: &str) -> Option<Value> {
+        let path: JsonPath = path.parse().ok()?;
+        self.state.read().node(&path).map(JsonNode::to_value)
+    }
+    
+    /// Get the whole document as a plain JSON value
+    pub fn value(&self) -> Value {
+        self.state.read().to_value()
+    }
+    
+    /// Get a cloned state
+    pub fn clone_state(&self) -> JsonState {
+        self.state.read().clone()
+    }
+    
+    /// Get current vector clock
+    pub fn get_vector_clock(&self) -> VectorClock {
+        self.clock_manager.vector_clock()
+    }
+}
+
+impl Clone for JsonCrdt {
+    fn clone(&self) -> Self {
+        Self {
+            actor_id: self.actor_id,
+            state: RwLock::new(self.state.read().clone()),
+            clock_manager: self.clock_manager.clone(),
+            observed: RwLock::new(self.observed.read().clone()),
+        }
+    }
+}
+
+#[async_trait]
+impl Crdt for JsonCrdt {
+    type Operation = JsonOperation;
+    type State = JsonState;
+    
+    async fn apply_operation(&mut self, operation: Self::Operation) -> Result<()> {
+        self.validate_operation(&operation)?;
+        
+        let timestamp = match &operation {
+            JsonOperation::Set { timestamp, .. } | JsonOperation::Delete { timestamp, .. } => *timestamp,
+            JsonOperation::Insert { id, .. } => id.timestamp,
+        };
+        self.clock_manager.advance_hlc_remote(&timestamp);
+        self.observed.write().record(timestamp.replica_id, timestamp.logical_time);
+        
+        self.state.write().apply(&operation);
+        Ok(())
+    }
+    
+    async fn apply_remote_operation(&mut self, operation: Self::Operation) -> Result<()> {
+        self.apply_operation(operation).await
+    }
+    
+    fn state(&self) -> &Self::State {
+        unimplemented!("Use clone_state() instead")
+    }
+    
+    fn actor_id(&self) -> &ActorId {
+        &self.actor_id
+    }
+    
+    fn vector_clock(&self) -> &VectorClock {
+        unimplemented!("Use get_vector_clock() instead")
+    }
+    
+    fn validate_operation(&self, operation: &Self::Operation) -> Result<()> {
+        let path = match operation {
+            JsonOperation::Set { path, .. }
+            | JsonOperation::Insert { path, .. }
+            | JsonOperation::Delete { path, .. } => path,
+        };
+        
+        if path.is_empty() {
+            return Err(CrdtError::InvalidOperation(
+                "Operations cannot target the document root".to_string()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Mergeable for JsonCrdt {
+    async fn merge(&mut self, other: &Self) -> Result<()> {
+        let other_state = other.clone_state();
+        self.state.write().merge(&other_state);
+        self.clock_manager.advance_hlc_remote(&other.clock_manager.hlc());
+        self.observed.write().merge(&other.observed.read());
+        Ok(())
+    }
+    
+    async fn merge_audited(&mut self, other: &Self) -> Result<MergeReport<Self::Operation>> {
+        let mut report = MergeReport::new();
+        self.state.read().record_conflicts(
+            &other.state.read(),
+            (&self.observed.read(), &other.observed.read()),
+            &mut report,
+        );
+        
+        self.merge(other).await?;
+        Ok(report)
+    }
+    
+    fn can_merge(&self, _other: &Self) -> bool {
+        true // JSON documents can always merge
+    }
+    
+    fn diff(&self, other: &Self) -> Vec<Self::Operation> {
+        let mut operations = Vec::new();
+        let self_state = self.state.read();
+        let other_state = other.state.read();
+        
+        if let (JsonNode::Object(local), JsonNode::Object(remote)) = (&self_state.root, &other_state.root) {
+            diff_object(&mut Vec::new(), Some(local), remote, &mut operations);
+        }
+        
+        operations
+    }
+}
+
+#[async_trait]
+impl Synchronizable for JsonCrdt {
+    fn delta_since(&self, _clock: &VectorClock) -> Result<Delta<Self::State>> {
+        Ok(Delta::FullState(self.clone_state()))
+    }
+    
+    async fn apply_delta(&mut self, delta: Delta<Self::State>) -> Result<()> {
+        match delta {
+            Delta::FullState(state) => {
+                self.state.write().merge(&state);
+                Ok(())
+            }
+            Delta::Operation(bytes) => {
+                let operation: JsonOperation = serde_json::from_slice(&bytes)?;
+                self.apply_remote_operation(operation).await
+            }
+            Delta::Batch(operations) => {
+                for op_bytes in operations {
+                    let operation: JsonOperation = serde_json::from_slice(&op_bytes)?;
+                    self.apply_remote_operation(operation).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+    
+    fn operations_since(&self, _clock: &VectorClock) -> Vec<Self::Operation> {
+        // Operations are not logged; use delta_since for state transfer
+        Vec::new()
+    }
+    
+    fn size_bytes(&self) -> usize {
+        // Rough estimate
+        serde_json::to_vec(&*self.state.read()).map(|bytes| bytes.len()).unwrap_or(0)
+    }
+}
+
+impl Display for JsonCrdt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JSON[{}]: {}", self.actor_id, self.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    #[test]
+    fn test_path_parsing() {
+        let path: JsonPath = "servers[0][2].host".parse().unwrap();
+        assert_eq!(
+            path.segments(),
+            &[
+                PathSegment::Key("servers".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Index(2),
+                PathSegment::Key("host".to_string()),
+            ]
+        );
+        assert_eq!(path.to_string(), "servers[0][2].host");
+        
+        assert!("".parse::<JsonPath>().unwrap().is_root());
+        assert!("a..b".parse::<JsonPath>().is_err());
+        assert!("a[x]".parse::<JsonPath>().is_err());
+        assert!("a[0".parse::<JsonPath>().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_json_set_get_delete() {
+        let mut doc = JsonCrdt::new(ActorId::new());
+        
+        doc.set("config", json!({ "name": "synapsed", "ports": [80, 443] })).await.unwrap();
+        doc.set("config.limits.connections", json!(100)).await.unwrap();
+        doc.set("config.ports[2]", json!(8080)).await.unwrap();
+        doc.insert("config.ports", 0, json!(22)).await.unwrap();
+        
+        assert_eq!(doc.get("config.name"), Some(json!("synapsed")));
+        assert_eq!(doc.get("config.ports"), Some(json!([22, 80, 443, 8080])));
+        assert_eq!(doc.get("config.limits"), Some(json!({ "connections": 100 })));
+        
+        doc.delete("config.ports[1]").await.unwrap();
+        doc.delete("config.name").await.unwrap();
+        assert_eq!(doc.get("config.name"), None);
+        assert_eq!(doc.get("config.ports"), Some(json!([22, 443, 8080])));
+        
+        assert!(doc.delete("config.missing").await.is_err());
+        assert!(doc.set("config.ports.first", json!(1)).await.is_err());
+        assert!(doc.set("config.ports[9]", json!(1)).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_json_concurrent_sibling_keys_survive_merge() {
+        let mut doc1 = JsonCrdt::new(ActorId::new());
+        doc1.set("config", json!({ "log_level": "info" })).await.unwrap();
+        let mut doc2 = JsonCrdt::new(ActorId::new());
+        doc2.merge(&doc1).await.unwrap();
+        
+        doc1.set("config.timeout_ms", json!(500)).await.unwrap();
+        doc2.set("config.retries", json!(3)).await.unwrap();
+        // Implicitly created parents merge too
+        doc1.set("features.sync", json!(true)).await.unwrap();
+        doc2.set("features.compression", json!("zstd")).await.unwrap();
+        
+        doc1.merge(&doc2).await.unwrap();
+        doc2.merge(&doc1).await.unwrap();
+        
+        let expected = json!({
+            "config": { "log_level": "info", "timeout_ms": 500, "retries": 3 },
+            "features": { "sync": true, "compression": "zstd" },
+        });
+        assert_eq!(doc1.value(), expected);
+        assert_eq!(doc2.value(), expected);
+    }
+    
+    #[tokio::test]
+    async fn test_json_concurrent_same_path_converges() {
+        let mut doc1 = JsonCrdt::new(ActorId::new());
+        let mut doc2 = JsonCrdt::new(ActorId::new());
+        
+        doc1.set("mode", json!("fast")).await.unwrap();
+        doc2.set("mode", json!({ "kind": "safe" })).await.unwrap();
+        doc2.delete("mode.kind").await.unwrap();
+        
+        let mut merged1 = doc1.clone();
+        merged1.merge(&doc2).await.unwrap();
+        let mut merged2 = doc2.clone();
+        merged2.merge(&doc1).await.unwrap();
+        assert_eq!(merged1.value(), merged2.value());
+        
+        // Replaying the diff as operations reaches the same document
+        let mut replayed = doc1.clone();
+        for operation in doc1.diff(&doc2) {
+            replayed.apply_remote_operation(operation).await.unwrap();
+        }
+        assert_eq!(replayed.value(), merged1.value());
+    }
+    
+    #[tokio::test]
+    async fn test_json_merge_audited_reports_field_conflicts() {
+        let mut doc1 = JsonCrdt::new(ActorId::new());
+        doc1.set("config", json!({ "mode": "fast", "retries": 3 })).await.unwrap();
+        let mut doc2 = JsonCrdt::new(ActorId::new());
+        doc2.merge(&doc1).await.unwrap();
+        
+        doc1.set("config.mode", json!("safe")).await.unwrap();
+        doc2.delete("config.mode").await.unwrap();
+        doc2.set("config.timeout_ms", json!(500)).await.unwrap();
+        // Overwriting a value already merged from doc1 is not a conflict
+        doc2.set("config.retries", json!(5)).await.unwrap();
+        
+        let mut plain = doc1.clone();
+        plain.merge(&doc2).await.unwrap();
+        let report = doc1.merge_audited(&doc2).await.unwrap();
+        assert_eq!(doc1.value(), plain.value());
+        
+        // Only the concurrently edited field conflicts
+        assert_eq!(report.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.resolution, Resolution::LastWriterWins);
+        let (JsonOperation::Set { path, .. } | JsonOperation::Delete { path, .. }) = &conflict.winner else {
+            panic!("unexpected winner {:?}", conflict.winner);
+        };
+        assert_eq!(path.last(), Some(&PathStep::Key("mode".to_string())));
+        
+        // The winner matches the merged document
+        match (&conflict.winner, &conflict.loser) {
+            (JsonOperation::Set { value, .. }, JsonOperation::Delete { .. }) => {
+                assert_eq!(doc1.get("config.mode"), Some(value.clone()));
+            }
+            (JsonOperation::Delete { .. }, JsonOperation::Set { .. }) => {
+                assert_eq!(doc1.get("config.mode"), None);
+            }
+            other => panic!("unexpected conflict {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_json_concurrent_array_inserts_keep_stable_order() {
+        let mut doc1 = JsonCrdt::new(ActorId::new());
+        doc1.set("items", json!(["a", "d"])).await.unwrap();
+        let mut doc2 = JsonCrdt::new(ActorId::new());
+        doc2.merge(&doc1).await.unwrap();
+        
+        doc1.insert("items", 1, json!("b")).await.unwrap();
+        doc2.insert("items", 1, json!("c")).await.unwrap();
+        doc2.set("items[2]", json!("D")).await.unwrap();
+        
+        doc1.merge(&doc2).await.unwrap();
+        doc2.merge(&doc1).await.unwrap();
+        
+        assert_eq!(doc1.get("items"), doc2.get("items"));
+        let items = doc1.get("items").unwrap();
+        assert_eq!(items.as_array().unwrap().len(), 4);
+        assert_eq!(items[0], json!("a"));
+        assert_eq!(items[3], json!("D"));
+    }
+}
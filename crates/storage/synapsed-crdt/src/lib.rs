@@ -11,6 +11,7 @@
 //! - **OR-Set**: Observed-Remove Set for distributed sets
 //! - **PN-Counter**: Increment/Decrement counter
 //! - **RGA**: Replicated Growable Array for collaborative text editing
+//! - **JSON**: Nested JSON documents for schemaless application state
 //! - **Merkle Tree**: Efficient synchronization with cryptographic verification
 //!
 //! ## Features
@@ -54,6 +55,7 @@
 pub mod error;
 pub mod types;
 pub mod traits;
+pub mod audit;
 
 // CRDT implementations
 #[cfg(feature = "lww")]
@@ -68,6 +70,9 @@ pub mod pn_counter;
 #[cfg(feature = "rga")]
 pub mod rga;
 
+#[cfg(feature = "json")]
+pub mod json;
+
 // Utilities
 pub mod clock;
 pub mod sync;
@@ -77,8 +82,9 @@ pub mod merkle;
 
 // Re-exports for convenience
 pub use error::{CrdtError, Result};
-pub use types::{ActorId, Timestamp, VectorClock, Delta};
+pub use types::{ActorId, Timestamp, VectorClock, RetiredActors, Delta};
 pub use traits::{Crdt, Mergeable, Synchronizable};
+pub use audit::{MergeConflict, MergeReport, Resolution};
 
 #[cfg(feature = "lww")]
 pub use lww_register::LwwRegister;
@@ -90,4 +96,7 @@ pub use or_set::OrSet;
 pub use pn_counter::PnCounter;
 
 #[cfg(feature = "rga")]
-pub use rga::Rga;
\ No newline at end of file
+pub use rga::Rga;
+
+#[cfg(feature = "json")]
+pub use json::JsonCrdt;
\ No newline at end of file
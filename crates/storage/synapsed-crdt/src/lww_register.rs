@@ -4,6 +4,7 @@
 //! The most recent write wins in case of conflicts.
 
 use crate::{
+    audit::{MergeReport, ObservedWrites, Resolution},
     error::Result,
     traits::{Crdt, Mergeable, Synchronizable},
     types::{ActorId, Delta, Timestamp, VectorClock},
@@ -72,6 +73,8 @@ pub struct LwwRegister<T> {
     state: RwLock<LwwState<T>>,
     /// Clock manager
     clock_manager: ClockManager,
+    /// Writes applied from each replica
+    observed: RwLock<ObservedWrites>,
 }
 
 impl<T> LwwRegister<T>
@@ -84,6 +87,7 @@ where
             actor_id: actor_id.clone(),
             state: RwLock::new(LwwState::new()),
             clock_manager: ClockManager::new(actor_id),
+            observed: RwLock::new(ObservedWrites::default()),
         }
     }
     
@@ -130,6 +134,7 @@ where
             actor_id: self.actor_id.clone(),
             state: RwLock::new(self.state.read().clone()),
             clock_manager: self.clock_manager.clone(),
+            observed: RwLock::new(self.observed.read().clone()),
         }
     }
 }
@@ -143,6 +148,7 @@ where
     type State = LwwState<T>;
     
     async fn apply_operation(&mut self, operation: Self::Operation) -> Result<()> {
+        self.observed.write().record(operation.actor, operation.timestamp.as_millis());
         let mut state = self.state.write();
         
         // Apply if this operation is newer
@@ -192,6 +198,19 @@ where
     pub fn get_vector_clock(&self) -> VectorClock {
         self.clock_manager.vector_clock()
     }
+    
+    /// Get the write that produced the current value
+    fn current_write(&self) -> Option<LwwOperation<T>> {
+        let state = self.state.read();
+        match (&state.value, &state.actor) {
+            (Some(value), Some(actor)) => Some(LwwOperation {
+                value: value.clone(),
+                timestamp: state.timestamp,
+                actor: *actor,
+            }),
+            _ => None,
+        }
+    }
 }
 
 #[async_trait]
@@ -216,10 +235,33 @@ where
         if let Some(op) = operation {
             self.apply_remote_operation(op).await?;
         }
+        self.observed.write().merge(&other.observed.read());
         
         Ok(())
     }
     
+    async fn merge_audited(&mut self, other: &Self) -> Result<MergeReport<Self::Operation>> {
+        let mut report = MergeReport::new();
+        
+        if let (Some(local), Some(remote)) = (self.current_write(), other.current_write()) {
+            // A write made after observing the other one simply overwrites it
+            let concurrent = !other.observed.read().contains(&local.actor, local.timestamp.as_millis())
+                && !self.observed.read().contains(&remote.actor, remote.timestamp.as_millis());
+            
+            // Same ordering as apply_operation
+            let remote_wins = remote.timestamp > local.timestamp ||
+                (remote.timestamp == local.timestamp && remote.actor > local.actor);
+            if concurrent && remote_wins {
+                report.record(remote, local, Resolution::LastWriterWins);
+            } else if concurrent {
+                report.record(local, remote, Resolution::LastWriterWins);
+            }
+        }
+        
+        self.merge(other).await?;
+        Ok(report)
+    }
+    
     fn can_merge(&self, _other: &Self) -> bool {
         true // LWW can always merge
     }
@@ -352,7 +394,7 @@ mod tests {
         let actor2 = ActorId::new();
         
         let mut lww1 = LwwRegister::new(actor1.clone());
-        let mut lww2 = LwwRegister::new(actor2.clone());
+        let _lww2: LwwRegister<String> = LwwRegister::new(actor2.clone());
         
         // Set same timestamp but different actors
         let timestamp = Timestamp::now();
@@ -374,4 +416,53 @@ mod tests {
         let result = lww1.get().unwrap();
         assert!(result == "value1" || result == "value2");
     }
+    
+    #[tokio::test]
+    async fn test_lww_merge_audited_reports_winner_and_loser() {
+        let actor1 = ActorId::new();
+        let actor2 = ActorId::new();
+        
+        let mut lww1 = LwwRegister::new(actor1.clone());
+        let mut lww2 = LwwRegister::new(actor2.clone());
+        
+        // Concurrent writes, the second one later
+        let earlier = LwwOperation {
+            value: "value1".to_string(),
+            timestamp: Timestamp::from_millis(1_000),
+            actor: actor1,
+        };
+        let later = LwwOperation {
+            value: "value2".to_string(),
+            timestamp: Timestamp::from_millis(2_000),
+            actor: actor2,
+        };
+        lww1.apply_operation(earlier.clone()).await.unwrap();
+        lww2.apply_operation(later.clone()).await.unwrap();
+        
+        let mut plain = lww1.clone();
+        plain.merge(&lww2).await.unwrap();
+        let unmerged = lww1.clone();
+        
+        let report = lww1.merge_audited(&lww2).await.unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.conflicts[0].winner, later);
+        assert_eq!(report.conflicts[0].loser, earlier);
+        assert_eq!(report.conflicts[0].resolution, Resolution::LastWriterWins);
+        
+        // Auditing does not change the merge result
+        assert_eq!(lww1.clone_state(), plain.clone_state());
+        
+        // The other side keeps its own write and reports the same outcome
+        let report = lww2.merge_audited(&unmerged).await.unwrap();
+        assert_eq!(report.conflicts[0].winner, later);
+        assert_eq!(report.conflicts[0].loser, earlier);
+        
+        // Converged replicas have nothing left to resolve
+        assert!(lww1.merge_audited(&lww2).await.unwrap().is_empty());
+        
+        // Overwriting a merged value is not a conflict
+        lww2.set("value3".to_string()).await.unwrap();
+        assert!(lww1.merge_audited(&lww2).await.unwrap().is_empty());
+        assert_eq!(lww1.get(), Some("value3".to_string()));
+    }
 }
\ No newline at end of file
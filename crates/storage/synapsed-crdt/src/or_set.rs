@@ -4,6 +4,7 @@
 //! Removes are only effective if they have observed the corresponding add.
 
 use crate::{
+    audit::{MergeReport, Resolution},
     error::{CrdtError, Result},
     traits::{Crdt, Mergeable, Synchronizable},
     types::{ActorId, Delta, Timestamp, VectorClock},
@@ -71,7 +72,7 @@ pub enum OrSetOperation<T> {
 
 /// OR-Set state
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(bound = "T: Clone + Eq + Hash + Serialize + for<'de> Deserialize<'de>")]
+#[serde(bound = "T: Clone + Eq + Hash + Serialize + serde::de::DeserializeOwned")]
 pub struct OrSetState<T> {
     /// Elements with their tags (added elements)
     added: HashMap<T, HashSet<ElementTag>>,
@@ -131,6 +132,53 @@ where
     pub fn get_tags(&self, element: &T) -> HashSet<ElementTag> {
         self.added.get(element).cloned().unwrap_or_default()
     }
+    
+    /// Record adds that survive a concurrent remove when merging `other`
+    ///
+    /// A remove only covers the tags its replica had observed, so an add the
+    /// remover had not seen keeps the element in the set.
+    fn record_add_wins(&self, other: &Self, report: &mut MergeReport<OrSetOperation<T>>) {
+        let empty = HashSet::new();
+        let elements: HashSet<&T> = self.added.keys().chain(other.added.keys()).collect();
+        
+        for element in elements {
+            let local_added = self.added.get(element).unwrap_or(&empty);
+            let local_removed = self.removed.get(element).unwrap_or(&empty);
+            let remote_added = other.added.get(element).unwrap_or(&empty);
+            let remote_removed = other.removed.get(element).unwrap_or(&empty);
+            
+            // Removes made on one side without seeing the other side's adds
+            for (removed, seen_removed, added, seen_added) in [
+                (remote_removed, local_removed, local_added, remote_added),
+                (local_removed, remote_removed, remote_added, local_added),
+            ] {
+                let remove: HashSet<_> = removed.difference(seen_removed).cloned().collect();
+                if remove.is_empty() {
+                    continue;
+                }
+                
+                let mut surviving: Vec<_> = added
+                    .iter()
+                    .filter(|tag| !seen_added.contains(tag) && !seen_removed.contains(tag))
+                    .collect();
+                surviving.sort();
+                
+                for tag in surviving {
+                    report.record(
+                        OrSetOperation::Add {
+                            element: element.clone(),
+                            tag: tag.clone(),
+                        },
+                        OrSetOperation::Remove {
+                            element: element.clone(),
+                            observed_tags: remove.clone(),
+                        },
+                        Resolution::AddWins,
+                    );
+                }
+            }
+        }
+    }
 }
 
 impl<T> Default for OrSetState<T>
@@ -380,6 +428,14 @@ where
         Ok(())
     }
     
+    async fn merge_audited(&mut self, other: &Self) -> Result<MergeReport<Self::Operation>> {
+        let mut report = MergeReport::new();
+        self.state.read().record_add_wins(&other.state.read(), &mut report);
+        
+        self.merge(other).await?;
+        Ok(report)
+    }
+    
     fn can_merge(&self, _other: &Self) -> bool {
         true // OR-Set can always merge
     }
@@ -502,7 +558,7 @@ where
 
 impl<T> Display for OrSet<T>
 where
-    T: Display + Clone + Eq + Hash,
+    T: Display + Clone + Eq + Hash + Send + Sync + Serialize + for<'de> Deserialize<'de>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let elements: Vec<String> = self.elements().iter().map(|e| e.to_string()).collect();
@@ -585,4 +641,39 @@ mod tests {
         assert!(set1.contains(&"X".to_string()));
         assert!(set2.contains(&"X".to_string()));
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_or_set_merge_audited_reports_add_wins() {
+        let mut set1 = OrSet::new(ActorId::new());
+        let mut set2 = OrSet::new(ActorId::new());
+        
+        let first = set1.add("X".to_string()).await.unwrap();
+        set2.merge(&set1).await.unwrap();
+        
+        // set1 removes the element while set2 concurrently re-adds it
+        let remove = set1.remove(&"X".to_string()).await.unwrap();
+        let add = set2.add("X".to_string()).await.unwrap();
+        assert!(matches!(&remove, OrSetOperation::Remove { observed_tags, .. } if observed_tags.len() == 1));
+        
+        let mut plain = set1.clone();
+        plain.merge(&set2).await.unwrap();
+        let unmerged = set1.clone();
+        
+        let report = set1.merge_audited(&set2).await.unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.conflicts[0].winner, add);
+        assert_eq!(report.conflicts[0].loser, remove);
+        assert_eq!(report.conflicts[0].resolution, Resolution::AddWins);
+        assert_eq!(set1.elements(), plain.elements());
+        assert!(set1.contains(&"X".to_string()));
+        
+        // The add that the remove observed is not reported
+        assert_ne!(report.conflicts[0].winner, first);
+        
+        // The other side sees the same conflict with its own add winning
+        let report = set2.merge_audited(&unmerged).await.unwrap();
+        assert_eq!(report.conflicts[0].winner, add);
+        assert_eq!(report.conflicts[0].loser, remove);
+        assert!(set1.merge_audited(&set2).await.unwrap().is_empty());
+    }
+}
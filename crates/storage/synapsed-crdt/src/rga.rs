@@ -4,6 +4,7 @@
 //! It maintains a linear sequence of characters with unique identifiers for each position.
 
 use crate::{
+    audit::{MergeReport, ObservedWrites, Resolution},
     error::{CrdtError, Result},
     traits::{Crdt, Mergeable, Synchronizable, GarbageCollectable},
     types::{ActorId, Delta, GlobalId, HybridLogicalClock, VectorClock},
@@ -54,6 +55,35 @@ impl RgaNode {
         self.visible = false;
         self.timestamp = timestamp;
     }
+    
+    /// Make a deleted node visible again
+    pub fn restore(&mut self, timestamp: HybridLogicalClock) {
+        self.visible = true;
+        self.timestamp = timestamp;
+    }
+    
+    /// Check if the node's visibility is still the one it was inserted with
+    fn is_unchanged(&self) -> bool {
+        self.timestamp.replica_id == self.id.replica_id && self.timestamp.logical_time == self.id.sequence
+    }
+    
+    /// Operation that last set this node's visibility
+    fn last_operation(&self) -> RgaOperation {
+        let (target_id, timestamp, author) = (self.id.clone(), self.timestamp, self.timestamp.replica_id);
+        if self.is_unchanged() {
+            RgaOperation::Insert {
+                id: target_id,
+                content: self.content,
+                position: None,
+                timestamp,
+                author,
+            }
+        } else if self.visible {
+            RgaOperation::Restore { target_id, timestamp, author }
+        } else {
+            RgaOperation::Delete { target_id, timestamp, author }
+        }
+    }
 }
 
 /// RGA operation types
@@ -73,6 +103,24 @@ pub enum RgaOperation {
         timestamp: HybridLogicalClock,
         author: ActorId,
     },
+    /// Restore a previously deleted character
+    Restore {
+        target_id: GlobalId,
+        timestamp: HybridLogicalClock,
+        author: ActorId,
+    },
+}
+
+/// Local undo/redo history
+///
+/// Holds the local edits that can be undone and the ones that were undone
+/// and can be redone. Only edits made by this replica are recorded.
+#[derive(Debug, Clone, Default)]
+struct UndoHistory {
+    /// Local edits, most recent last
+    undo_stack: Vec<RgaOperation>,
+    /// Undone edits, most recently undone last
+    redo_stack: Vec<RgaOperation>,
 }
 
 /// RGA document state
@@ -168,6 +216,10 @@ pub struct Rga {
     counter: RwLock<u64>,
     /// Buffered operations for causal delivery
     operation_buffer: RwLock<VecDeque<RgaOperation>>,
+    /// Local undo/redo history
+    history: RwLock<UndoHistory>,
+    /// Edits applied from each replica
+    observed: RwLock<ObservedWrites>,
 }
 
 impl Rga {
@@ -181,6 +233,8 @@ impl Rga {
             clock_manager,
             counter: RwLock::new(0),
             operation_buffer: RwLock::new(VecDeque::new()),
+            history: RwLock::new(UndoHistory::default()),
+            observed: RwLock::new(ObservedWrites::default()),
         }
     }
     
@@ -212,6 +266,7 @@ impl Rga {
         };
         
         self.apply_operation(operation.clone()).await?;
+        self.record_local(operation.clone());
         Ok(operation)
     }
     
@@ -232,9 +287,79 @@ impl Rga {
         };
         
         self.apply_operation(operation.clone()).await?;
+        self.record_local(operation.clone());
         Ok(operation)
     }
     
+    /// Undo the most recent local edit
+    ///
+    /// Rather than rewinding state, this applies and returns a new operation
+    /// that reverses the edit (deleting an inserted character or restoring a
+    /// deleted one), so it merges cleanly with concurrent edits from other
+    /// replicas. Returns `None` if there is nothing to undo.
+    pub async fn undo(&mut self) -> Result<Option<RgaOperation>> {
+        let Some(edit) = self.history.write().undo_stack.pop() else {
+            return Ok(None);
+        };
+        
+        let target_id = Self::edit_target(&edit);
+        let timestamp = self.clock_manager.advance_hlc();
+        let inverse = match edit {
+            RgaOperation::Insert { .. } => RgaOperation::Delete { target_id, timestamp, author: self.actor_id },
+            _ => RgaOperation::Restore { target_id, timestamp, author: self.actor_id },
+        };
+        
+        self.apply_operation(inverse.clone()).await?;
+        self.history.write().redo_stack.push(edit);
+        Ok(Some(inverse))
+    }
+    
+    /// Redo the most recently undone local edit
+    ///
+    /// Like [`Rga::undo`], this produces a new operation rather than
+    /// replaying the original. Returns `None` if there is nothing to redo.
+    pub async fn redo(&mut self) -> Result<Option<RgaOperation>> {
+        let Some(edit) = self.history.write().redo_stack.pop() else {
+            return Ok(None);
+        };
+        
+        let target_id = Self::edit_target(&edit);
+        let timestamp = self.clock_manager.advance_hlc();
+        let operation = match edit {
+            RgaOperation::Insert { .. } => RgaOperation::Restore { target_id, timestamp, author: self.actor_id },
+            _ => RgaOperation::Delete { target_id, timestamp, author: self.actor_id },
+        };
+        
+        self.apply_operation(operation.clone()).await?;
+        self.history.write().undo_stack.push(edit);
+        Ok(Some(operation))
+    }
+    
+    /// Check whether there is a local edit to undo
+    pub fn can_undo(&self) -> bool {
+        !self.history.read().undo_stack.is_empty()
+    }
+    
+    /// Check whether there is an undone edit to redo
+    pub fn can_redo(&self) -> bool {
+        !self.history.read().redo_stack.is_empty()
+    }
+    
+    /// Record a local edit, invalidating the redo history
+    fn record_local(&self, operation: RgaOperation) {
+        let mut history = self.history.write();
+        history.undo_stack.push(operation);
+        history.redo_stack.clear();
+    }
+    
+    /// ID of the node an operation acts on
+    fn edit_target(operation: &RgaOperation) -> GlobalId {
+        match operation {
+            RgaOperation::Insert { id, .. } => id.clone(),
+            RgaOperation::Delete { target_id, .. } | RgaOperation::Restore { target_id, .. } => target_id.clone(),
+        }
+    }
+    
     /// Get current text
     pub fn text(&self) -> String {
         self.state.read().text()
@@ -323,7 +448,8 @@ impl Rga {
         
         if let Some(&index) = state.node_index.get(&target_id) {
             if let Some(node) = state.nodes.get_mut(index) {
-                if node.visible {
+                // Visibility is last-writer-wins, so a stale delete cannot hide a later restore
+                if node.visible && timestamp.compare(&node.timestamp).is_gt() {
                     node.delete(timestamp);
                     state.text_length -= 1;
                 }
@@ -333,6 +459,27 @@ impl Rga {
         
         Ok(())
     }
+    
+    /// Apply restore operation
+    async fn apply_restore(
+        &mut self,
+        target_id: GlobalId,
+        timestamp: HybridLogicalClock,
+        _author: ActorId,
+    ) -> Result<()> {
+        let mut state = self.state.write();
+        
+        if let Some(&index) = state.node_index.get(&target_id) {
+            if let Some(node) = state.nodes.get_mut(index) {
+                if !node.visible && timestamp.compare(&node.timestamp).is_gt() {
+                    node.restore(timestamp);
+                    state.text_length += 1;
+                }
+            }
+        }
+        
+        Ok(())
+    }
 }
 
 impl Clone for Rga {
@@ -343,6 +490,8 @@ impl Clone for Rga {
             clock_manager: self.clock_manager.clone(),
             counter: RwLock::new(*self.counter.read()),
             operation_buffer: RwLock::new(self.operation_buffer.read().clone()),
+            history: RwLock::new(self.history.read().clone()),
+            observed: RwLock::new(self.observed.read().clone()),
         }
     }
 }
@@ -353,6 +502,13 @@ impl Crdt for Rga {
     type State = RgaState;
     
     async fn apply_operation(&mut self, operation: Self::Operation) -> Result<()> {
+        let timestamp = match &operation {
+            RgaOperation::Insert { timestamp, .. }
+            | RgaOperation::Delete { timestamp, .. }
+            | RgaOperation::Restore { timestamp, .. } => *timestamp,
+        };
+        self.observed.write().record(timestamp.replica_id, timestamp.logical_time);
+        
         match operation {
             RgaOperation::Insert { id, content, position, timestamp, author } => {
                 self.clock_manager.advance_hlc_remote(&timestamp);
@@ -362,6 +518,10 @@ impl Crdt for Rga {
                 self.clock_manager.advance_hlc_remote(&timestamp);
                 self.apply_delete(target_id, timestamp, author).await
             }
+            RgaOperation::Restore { target_id, timestamp, author } => {
+                self.clock_manager.advance_hlc_remote(&timestamp);
+                self.apply_restore(target_id, timestamp, author).await
+            }
         }
     }
     
@@ -394,7 +554,7 @@ impl Crdt for Rga {
                 }
                 Ok(())
             }
-            RgaOperation::Delete { .. } => Ok(()),
+            RgaOperation::Delete { .. } | RgaOperation::Restore { .. } => Ok(()),
         }
     }
 }
@@ -421,25 +581,31 @@ impl Mergeable for Rga {
             
             // Collect operations from other replica
             for node in &other_state.nodes {
-                // Apply if not already present
-                if !self_state.node_index.contains_key(&node.id) {
-                    let operation = if node.visible {
-                        RgaOperation::Insert {
-                            id: node.id.clone(),
-                            content: node.content,
-                            position: None, // We'll figure this out during insertion
-                            timestamp: node.timestamp,
-                            author: node.author,
-                        }
-                    } else {
-                        RgaOperation::Delete {
-                            target_id: node.id.clone(),
-                            timestamp: node.timestamp,
-                            author: node.author,
-                        }
-                    };
-                    ops.push(operation);
+                // Adopt newer deletes and restores of nodes we already have
+                if let Some(local) = self_state.find_node(&node.id) {
+                    if local.visible != node.visible && node.timestamp.compare(&local.timestamp).is_gt() {
+                        ops.push(node.last_operation());
+                    }
+                    continue;
                 }
+                
+                // Apply if not already present
+                let operation = if node.visible {
+                    RgaOperation::Insert {
+                        id: node.id.clone(),
+                        content: node.content,
+                        position: None, // We'll figure this out during insertion
+                        timestamp: node.timestamp,
+                        author: node.author,
+                    }
+                } else {
+                    RgaOperation::Delete {
+                        target_id: node.id.clone(),
+                        timestamp: node.timestamp,
+                        author: node.timestamp.replica_id,
+                    }
+                };
+                ops.push(operation);
             }
             
             ops
@@ -449,10 +615,51 @@ impl Mergeable for Rga {
         for operation in operations {
             self.apply_remote_operation(operation).await?;
         }
+        self.observed.write().merge(&other.observed.read());
         
         Ok(())
     }
     
+    async fn merge_audited(&mut self, other: &Self) -> Result<MergeReport<Self::Operation>> {
+        let mut report = MergeReport::new();
+        {
+            let other_state = other.state.read();
+            let self_state = self.state.read();
+            let other_observed = other.observed.read();
+            let self_observed = self.observed.read();
+            
+            // Concurrent deletes and restores of the same node; the later one wins
+            for node in &other_state.nodes {
+                let Some(local) = self_state.find_node(&node.id) else { continue };
+                if local.visible == node.visible {
+                    continue;
+                }
+                
+                // An edit made after observing the other one simply replaces it
+                let (local_time, node_time) = (&local.timestamp, &node.timestamp);
+                if other_observed.contains(&local_time.replica_id, local_time.logical_time)
+                    || self_observed.contains(&node_time.replica_id, node_time.logical_time)
+                {
+                    continue;
+                }
+                
+                let (winner, loser) = if node.timestamp.compare(&local.timestamp).is_gt() {
+                    (node, local)
+                } else {
+                    (local, node)
+                };
+                report.record(
+                    winner.last_operation(),
+                    loser.last_operation(),
+                    Resolution::LastWriterWins,
+                );
+            }
+        }
+        
+        self.merge(other).await?;
+        Ok(report)
+    }
+    
     fn can_merge(&self, _other: &Self) -> bool {
         true // RGA can always merge
     }
@@ -462,9 +669,13 @@ impl Mergeable for Rga {
         let self_state = self.state.read();
         let other_state = other.state.read();
         
-        // Find nodes in other that we don't have
+        // Find nodes in other that we don't have, or whose visibility changed later
         for node in &other_state.nodes {
-            if !self_state.node_index.contains_key(&node.id) {
+            if let Some(local) = self_state.find_node(&node.id) {
+                if local.visible != node.visible && node.timestamp.compare(&local.timestamp).is_gt() {
+                    operations.push(node.last_operation());
+                }
+            } else {
                 let operation = if node.visible {
                     RgaOperation::Insert {
                         id: node.id.clone(),
@@ -477,7 +688,7 @@ impl Mergeable for Rga {
                     RgaOperation::Delete {
                         target_id: node.id.clone(),
                         timestamp: node.timestamp,
-                        author: node.author,
+                        author: node.timestamp.replica_id,
                     }
                 };
                 operations.push(operation);
@@ -608,4 +819,82 @@ mod tests {
         // Both should converge to the same state
         assert_eq!(rga1.text(), rga2.text());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_rga_undo_local_insert_preserves_concurrent_remote_insert() {
+        let mut rga1 = Rga::new(ActorId::new());
+        let mut rga2 = Rga::new(ActorId::new());
+        
+        rga1.insert_at_offset(0, 'A').await.unwrap();
+        rga2.insert_at_offset(0, 'B').await.unwrap();
+        rga1.merge(&rga2).await.unwrap();
+        
+        let inverse = rga1.undo().await.unwrap();
+        assert!(matches!(inverse, Some(RgaOperation::Delete { .. })));
+        
+        rga2.merge(&rga1).await.unwrap();
+        rga1.merge(&rga2).await.unwrap();
+        
+        assert_eq!(rga1.text(), "B");
+        assert_eq!(rga2.text(), "B");
+    }
+    
+    #[tokio::test]
+    async fn test_rga_merge_audited_reports_concurrent_visibility() {
+        let mut rga1 = Rga::new(ActorId::new());
+        let mut rga2 = Rga::new(ActorId::new());
+        
+        rga1.insert_at_offset(0, 'x').await.unwrap();
+        rga1.insert_at_offset(1, 'y').await.unwrap();
+        rga2.merge(&rga1).await.unwrap();
+        
+        // rga1 deletes and restores 'x' while rga2 deletes both characters
+        rga1.delete_at_offset(0).await.unwrap();
+        let restore = rga1.undo().await.unwrap().unwrap();
+        let delete_x = rga2.delete_at_offset(0).await.unwrap();
+        rga2.delete_at_offset(0).await.unwrap();
+        
+        let mut plain = rga1.clone();
+        plain.merge(&rga2).await.unwrap();
+        let report = rga1.merge_audited(&rga2).await.unwrap();
+        assert_eq!(rga1.text(), plain.text());
+        
+        // Deleting 'y' after observing its insert is not a conflict
+        assert_eq!(report.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.resolution, Resolution::LastWriterWins);
+        if rga1.text() == "x" {
+            assert_eq!((&conflict.winner, &conflict.loser), (&restore, &delete_x));
+        } else {
+            assert_eq!(rga1.text(), "");
+            assert_eq!((&conflict.winner, &conflict.loser), (&delete_x, &restore));
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_rga_undo_redo() {
+        let mut rga = Rga::new(ActorId::new());
+        
+        rga.insert_at_offset(0, 'H').await.unwrap();
+        rga.insert_at_offset(1, 'i').await.unwrap();
+        rga.delete_at_offset(0).await.unwrap();
+        assert_eq!(rga.text(), "i");
+        
+        rga.undo().await.unwrap();
+        assert_eq!(rga.text(), "Hi");
+        rga.undo().await.unwrap();
+        assert_eq!(rga.text(), "H");
+        assert!(rga.can_redo());
+        
+        rga.redo().await.unwrap();
+        rga.redo().await.unwrap();
+        assert_eq!(rga.text(), "i");
+        assert!(rga.redo().await.unwrap().is_none());
+        
+        // A new edit discards the redo history
+        rga.undo().await.unwrap();
+        rga.insert_at_offset(2, 'o').await.unwrap();
+        assert!(!rga.can_redo());
+        assert_eq!(rga.text(), "Hio");
+    }
+}
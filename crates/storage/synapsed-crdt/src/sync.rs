@@ -556,6 +556,422 @@ pub mod bandwidth {
     }
 }
 
+/// Invertible Bloom lookup table set reconciliation
+///
+/// Each peer encodes its set into an [`Iblt`] sized for the expected
+/// difference rather than for the set. Subtracting one table from the other
+/// cancels the shared elements, and decoding the result lists the elements
+/// only one side holds, in both directions. Elements can be removed as well
+/// as inserted, so a peer can keep its table current as its set changes. If
+/// the difference is larger than the table can decode, decoding fails and the
+/// peers retry with a larger table.
+pub mod iblt {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::hash::{Hash as StdHash, Hasher};
+    
+    /// Number of cells each element is stored in
+    const NUM_HASHES: usize = 3;
+    
+    /// Deterministic FNV-1a hasher so that peers agree on element IDs
+    struct StableHasher(u64);
+    
+    impl Hasher for StableHasher {
+        fn finish(&self) -> u64 {
+            mix(self.0)
+        }
+        
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 ^= *byte as u64;
+                self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+    
+    /// SplitMix64 finalizer
+    fn mix(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    
+    /// Stable 64-bit ID of an element, identical on every peer
+    pub fn element_id<T: StdHash + ?Sized>(item: &T) -> u64 {
+        let mut hasher = StableHasher(0xcbf2_9ce4_8422_2325);
+        item.hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    /// Checksum used to tell cells holding a single element from mixed ones
+    fn id_checksum(id: u64) -> u64 {
+        mix(id ^ 0x9e37_79b9_7f4a_7c15)
+    }
+    
+    /// One cell of the table
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+    struct Cell {
+        /// Inserted minus removed elements
+        count: i64,
+        /// XOR of element IDs
+        id_sum: u64,
+        /// XOR of element ID checksums
+        checksum_sum: u64,
+    }
+    
+    impl Cell {
+        fn apply(&mut self, id: u64, sign: i64) {
+            self.count += sign;
+            self.id_sum ^= id;
+            self.checksum_sum ^= id_checksum(id);
+        }
+        
+        /// Whether the cell holds exactly one element, inserted or removed
+        fn is_pure(&self) -> bool {
+            (self.count == 1 || self.count == -1) && self.checksum_sum == id_checksum(self.id_sum)
+        }
+        
+        fn is_empty(&self) -> bool {
+            self.count == 0 && self.id_sum == 0 && self.checksum_sum == 0
+        }
+    }
+    
+    /// Element IDs recovered by decoding a table difference
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct IdDifference {
+        /// IDs only in the table that was subtracted from
+        pub only_in_self: Vec<u64>,
+        /// IDs only in the table that was subtracted
+        pub only_in_other: Vec<u64>,
+    }
+    
+    /// Invertible Bloom lookup table over set elements
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Iblt {
+        /// Cells, split into one equal partition per hash function
+        cells: Vec<Cell>,
+        /// Seed for cell positions
+        seed: u64,
+    }
+    
+    impl Iblt {
+        /// Create an empty table able to decode about `expected_difference` elements
+        pub fn new(expected_difference: usize, seed: u64) -> Self {
+            let per_hash = expected_difference.max(1).div_ceil(2) + 2;
+            Self {
+                cells: vec![Cell::default(); per_hash * NUM_HASHES],
+                seed,
+            }
+        }
+        
+        /// Build a table containing every element of a set
+        pub fn from_set<'a, T, I>(items: I, expected_difference: usize, seed: u64) -> Self
+        where
+            T: StdHash + 'a,
+            I: IntoIterator<Item = &'a T>,
+        {
+            let mut table = Self::new(expected_difference, seed);
+            for item in items {
+                table.insert(item);
+            }
+            table
+        }
+        
+        /// Create an empty table with the same shape as this one
+        pub fn empty_like(&self) -> Self {
+            Self {
+                cells: vec![Cell::default(); self.cells.len()],
+                seed: self.seed,
+            }
+        }
+        
+        /// Cell indexes of an element ID, one in each partition
+        fn positions(&self, id: u64) -> impl Iterator<Item = usize> {
+            let per_hash = self.cells.len() / NUM_HASHES;
+            let seed = self.seed;
+            (0..NUM_HASHES).map(move |i| {
+                let h = mix(id ^ mix(seed.wrapping_add(i as u64)));
+                i * per_hash + (h % per_hash as u64) as usize
+            })
+        }
+        
+        fn apply(&mut self, id: u64, sign: i64) {
+            let positions: Vec<usize> = self.positions(id).collect();
+            for position in positions {
+                self.cells[position].apply(id, sign);
+            }
+        }
+        
+        /// Add an element
+        pub fn insert<T: StdHash + ?Sized>(&mut self, item: &T) {
+            self.apply(element_id(item), 1);
+        }
+        
+        /// Remove a previously inserted element
+        pub fn remove<T: StdHash + ?Sized>(&mut self, item: &T) {
+            self.apply(element_id(item), -1);
+        }
+        
+        /// Subtract another table, leaving only the elements that differ
+        ///
+        /// Both tables must have been created with the same size and seed.
+        pub fn subtract(&self, other: &Iblt) -> Result<Iblt> {
+            if self.cells.len() != other.cells.len() || self.seed != other.seed {
+                return Err(CrdtError::InvalidOperation(
+                    "cannot subtract tables of different shape".to_string(),
+                ));
+            }
+            
+            let cells = self
+                .cells
+                .iter()
+                .zip(&other.cells)
+                .map(|(a, b)| Cell {
+                    count: a.count - b.count,
+                    id_sum: a.id_sum ^ b.id_sum,
+                    checksum_sum: a.checksum_sum ^ b.checksum_sum,
+                })
+                .collect();
+            Ok(Iblt { cells, seed: self.seed })
+        }
+        
+        /// List the element IDs in the table
+        ///
+        /// Applied to a difference from [`Iblt::subtract`], returns the IDs on
+        /// each side. Returns `None` if the table holds more elements than it
+        /// can decode.
+        pub fn decode(&self) -> Option<IdDifference> {
+            let mut table = self.clone();
+            let mut difference = IdDifference::default();
+            let mut pure: VecDeque<usize> = (0..table.cells.len())
+                .filter(|&i| table.cells[i].is_pure())
+                .collect();
+            
+            while let Some(index) = pure.pop_front() {
+                let cell = table.cells[index];
+                if !cell.is_pure() {
+                    continue;
+                }
+                
+                if cell.count == 1 {
+                    difference.only_in_self.push(cell.id_sum);
+                } else {
+                    difference.only_in_other.push(cell.id_sum);
+                }
+                
+                let positions: Vec<usize> = table.positions(cell.id_sum).collect();
+                for position in positions {
+                    table.cells[position].apply(cell.id_sum, -cell.count);
+                    if table.cells[position].is_pure() {
+                        pure.push_back(position);
+                    }
+                }
+            }
+            
+            table.cells.iter().all(Cell::is_empty).then_some(difference)
+        }
+        
+        /// Seed used for cell positions
+        pub fn seed(&self) -> u64 {
+            self.seed
+        }
+        
+        /// Size of the table on the wire, in bytes
+        pub fn size_bytes(&self) -> usize {
+            self.cells.len() * 24 + 8
+        }
+    }
+    
+    /// Order-independent summary of a set, used to verify reconciliation
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct SetDigest {
+        /// Number of elements
+        pub len: u64,
+        /// Sum of element IDs
+        pub checksum: u64,
+    }
+    
+    impl SetDigest {
+        /// Compute the digest of a set
+        pub fn of<'a, T, I>(items: I) -> Self
+        where
+            T: StdHash + 'a,
+            I: IntoIterator<Item = &'a T>,
+        {
+            items.into_iter().fold(Self { len: 0, checksum: 0 }, |digest, item| Self {
+                len: digest.len + 1,
+                checksum: digest.checksum.wrapping_add(element_id(item)),
+            })
+        }
+        
+        /// Size of the digest on the wire, in bytes
+        pub fn size_bytes(&self) -> usize {
+            16
+        }
+    }
+    
+    /// Difference between a local set and a remote peer's table
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SetDifference<T> {
+        /// Local elements the remote peer is missing
+        pub local_only: Vec<T>,
+        /// IDs of remote elements the local peer is missing
+        ///
+        /// The remote peer resolves them with [`elements_with_ids`].
+        pub remote_only: Vec<u64>,
+    }
+    
+    /// Compare a local set against a remote peer's table
+    ///
+    /// Returns `None` if the difference is too large for the table to decode;
+    /// the peers should retry with a larger table.
+    pub fn reconcile_iblt<'a, T, I>(local_set: I, remote: &Iblt) -> Option<SetDifference<T>>
+    where
+        T: StdHash + Clone + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let mut local = remote.empty_like();
+        let mut by_id = HashMap::new();
+        for item in local_set {
+            let id = element_id(item);
+            local.apply(id, 1);
+            by_id.insert(id, item);
+        }
+        
+        let difference = local.subtract(remote).ok()?.decode()?;
+        let local_only = difference
+            .only_in_self
+            .iter()
+            .map(|id| by_id.get(id).map(|item| (*item).clone()))
+            .collect::<Option<Vec<T>>>()?;
+        
+        Some(SetDifference {
+            local_only,
+            remote_only: difference.only_in_other,
+        })
+    }
+    
+    /// Select the elements of a set with the given IDs
+    pub fn elements_with_ids<'a, T, I>(set: I, ids: &[u64]) -> Vec<T>
+    where
+        T: StdHash + Clone + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let ids: std::collections::HashSet<u64> = ids.iter().copied().collect();
+        set.into_iter()
+            .filter(|item| ids.contains(&element_id(*item)))
+            .cloned()
+            .collect()
+    }
+}
+
+pub use iblt::{elements_with_ids, reconcile_iblt, Iblt, SetDifference, SetDigest};
+
+/// Causal delivery of incoming deltas
+pub mod causal {
+    use super::*;
+    
+    /// Delta held back until its causal dependencies are delivered
+    #[derive(Debug, Clone)]
+    struct PendingDelta<T> {
+        /// Actor that produced the delta
+        origin: ActorId,
+        /// Vector clock of the origin after producing the delta
+        clock: VectorClock,
+        /// The delta itself
+        delta: T,
+    }
+    
+    /// Buffer that releases deltas in causal order
+    ///
+    /// A delta from `origin` stamped with clock `V` is delivered once every
+    /// earlier delta from `origin` has been delivered (`V[origin]` is one
+    /// past the delivered count) and everything else it depends on has been
+    /// delivered (`V[b]` is at most the delivered count for every other actor
+    /// `b`). Deltas that were already delivered are dropped.
+    #[derive(Debug, Clone)]
+    pub struct CausalBuffer<T> {
+        /// Clock of everything delivered so far
+        delivered: VectorClock,
+        /// Deltas waiting on their dependencies
+        pending: Vec<PendingDelta<T>>,
+    }
+    
+    impl<T> CausalBuffer<T> {
+        /// Create an empty buffer
+        pub fn new() -> Self {
+            Self::with_clock(VectorClock::new())
+        }
+        
+        /// Create a buffer for a replica that has already applied everything up to `clock`
+        pub fn with_clock(clock: VectorClock) -> Self {
+            Self {
+                delivered: clock,
+                pending: Vec::new(),
+            }
+        }
+        
+        /// Clock of all deltas delivered so far
+        pub fn delivered_clock(&self) -> &VectorClock {
+            &self.delivered
+        }
+        
+        /// Number of deltas waiting on dependencies
+        pub fn pending_len(&self) -> usize {
+            self.pending.len()
+        }
+        
+        /// Check whether there are deltas waiting on dependencies
+        pub fn has_pending(&self) -> bool {
+            !self.pending.is_empty()
+        }
+        
+        /// Accept an incoming delta and return every delta that is now deliverable, in causal order
+        pub fn receive(&mut self, origin: ActorId, clock: VectorClock, delta: T) -> Vec<T> {
+            if self.is_delivered(&origin, &clock) {
+                return Vec::new();
+            }
+            
+            self.pending.push(PendingDelta { origin, clock, delta });
+            
+            let mut released = Vec::new();
+            while let Some(index) = self.pending.iter().position(|p| self.is_deliverable(&p.origin, &p.clock)) {
+                let next = self.pending.swap_remove(index);
+                self.delivered.set(next.origin, next.clock.get(&next.origin));
+                released.push(next.delta);
+            }
+            
+            // Drop duplicates of deltas delivered in the meantime
+            let delivered = &self.delivered;
+            self.pending.retain(|p| p.clock.get(&p.origin) > delivered.get(&p.origin));
+            
+            released
+        }
+        
+        /// Check whether a delta has already been delivered
+        fn is_delivered(&self, origin: &ActorId, clock: &VectorClock) -> bool {
+            clock.get(origin) <= self.delivered.get(origin)
+        }
+        
+        /// Check whether all of a delta's causal dependencies have been delivered
+        fn is_deliverable(&self, origin: &ActorId, clock: &VectorClock) -> bool {
+            clock.get(origin) == self.delivered.get(origin) + 1
+                && clock
+                    .actors()
+                    .filter(|actor| *actor != origin)
+                    .all(|actor| clock.get(actor) <= self.delivered.get(actor))
+        }
+    }
+    
+    impl<T> Default for CausalBuffer<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+pub use causal::CausalBuffer;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,4 +1013,128 @@ mod tests {
         limiter.record_sent(400);
         assert!(!limiter.can_send(200));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_iblt_insert_and_remove_cancel() {
+        let items: Vec<u64> = (0..1_000).collect();
+        let mut table = Iblt::from_set(&items, 10, 7);
+        for item in &items[500..] {
+            table.remove(item);
+        }
+        
+        assert_eq!(table, Iblt::from_set(&items[..500], 10, 7));
+        
+        // A table within its capacity lists the elements it holds
+        let mut expected: Vec<u64> = items[..5].iter().map(iblt::element_id).collect();
+        let mut decoded = Iblt::from_set(&items[..5], 10, 7).decode().unwrap().only_in_self;
+        expected.sort_unstable();
+        decoded.sort_unstable();
+        assert_eq!(decoded, expected);
+    }
+    
+    #[test]
+    fn test_reconcile_iblt_lists_differences_both_ways() {
+        use std::collections::HashSet;
+        
+        let shared: HashSet<String> = (0..100_000).map(|i| format!("document/{:08}", i)).collect();
+        let local_extra: HashSet<String> = (0..50).map(|i| format!("local/{}", i)).collect();
+        let remote_extra: HashSet<String> = (0..30).map(|i| format!("remote/{}", i)).collect();
+        let mut local: HashSet<String> = shared.union(&local_extra).cloned().collect();
+        let mut remote: HashSet<String> = shared.union(&remote_extra).cloned().collect();
+        
+        let full_set_bytes: usize = local.iter().map(|item| item.len()).sum();
+        let mut exchanged_bytes = 0;
+        
+        // A table sized for too small a difference fails to decode
+        let small = Iblt::from_set(&remote, 10, 1);
+        assert!(reconcile_iblt(&local, &small).is_none());
+        exchanged_bytes += small.size_bytes();
+        
+        // Remote summarizes its set; local decodes the difference
+        let table = Iblt::from_set(&remote, 160, 2);
+        exchanged_bytes += table.size_bytes();
+        let difference = reconcile_iblt(&local, &table).expect("difference should decode");
+        
+        let local_only: HashSet<String> = difference.local_only.iter().cloned().collect();
+        assert_eq!(local_only, local_extra);
+        
+        // Remote resolves the IDs it holds and local is missing
+        exchanged_bytes += difference.remote_only.len() * 8;
+        let requested = elements_with_ids(&remote, &difference.remote_only);
+        assert_eq!(requested.iter().cloned().collect::<HashSet<_>>(), remote_extra);
+        
+        exchanged_bytes += difference.local_only.iter().map(|item| item.len()).sum::<usize>();
+        exchanged_bytes += requested.iter().map(|item| item.len()).sum::<usize>();
+        remote.extend(difference.local_only);
+        local.extend(requested);
+        
+        assert_eq!(local, remote);
+        assert_eq!(SetDigest::of(&local), SetDigest::of(&remote));
+        assert!(
+            exchanged_bytes * 5 < full_set_bytes,
+            "exchanged {} bytes for a {} byte set",
+            exchanged_bytes,
+            full_set_bytes
+        );
+    }
+    
+    #[test]
+    fn test_causal_buffer_releases_reversed_deltas_in_causal_order() {
+        let alice = ActorId::new();
+        let bob = ActorId::new();
+        
+        // Alice and Bob take turns, each edit building on the previous one
+        let mut clock = VectorClock::new();
+        let mut history = Vec::new();
+        for step in 0..6 {
+            let actor = if step % 2 == 0 { alice } else { bob };
+            clock.advance(&actor);
+            history.push((actor, clock.clone(), format!("edit-{}", step)));
+        }
+        
+        let in_order: Vec<String> = history.iter().map(|(_, _, edit)| edit.clone()).collect();
+        
+        let mut buffer = CausalBuffer::new();
+        let mut applied = Vec::new();
+        for (actor, clock, edit) in history.iter().rev().cloned() {
+            let released = buffer.receive(actor, clock, edit);
+            if buffer.has_pending() {
+                assert!(released.is_empty(), "released before dependencies were met");
+            }
+            applied.extend(released);
+        }
+        
+        assert_eq!(applied, in_order);
+        assert!(!buffer.has_pending());
+        assert_eq!(buffer.delivered_clock(), &clock);
+    }
+    
+    #[test]
+    fn test_causal_buffer_waits_for_missing_dependency() {
+        let alice = ActorId::new();
+        let bob = ActorId::new();
+        
+        let mut first = VectorClock::new();
+        first.advance(&alice);
+        let mut reply = first.clone();
+        reply.advance(&bob);
+        let mut second = VectorClock::new();
+        second.advance(&bob);
+        second.advance(&bob);
+        
+        let mut buffer = CausalBuffer::new();
+        
+        // Bob's reply depends on Alice's first edit
+        assert!(buffer.receive(bob, reply.clone(), "reply").is_empty());
+        assert_eq!(buffer.pending_len(), 1);
+        
+        assert_eq!(buffer.receive(alice, first.clone(), "first"), vec!["first", "reply"]);
+        
+        // Redelivery is ignored
+        assert!(buffer.receive(bob, reply, "reply").is_empty());
+        assert!(buffer.receive(alice, first, "first").is_empty());
+        
+        // Bob's second edit needs only his first
+        assert_eq!(buffer.receive(bob, second, "second"), vec!["second"]);
+    }
+}
@@ -1,6 +1,6 @@
 //! Core traits for CRDT implementations
 
-use crate::{ActorId, Delta, Result, VectorClock};
+use crate::{audit::MergeReport, ActorId, Delta, Result, VectorClock};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +38,15 @@ pub trait Mergeable: Crdt {
     /// Merge this CRDT with another replica
     async fn merge(&mut self, other: &Self) -> Result<()>;
     
+    /// Merge this CRDT with another replica, reporting the conflicts resolved
+    ///
+    /// The merged state is the same as with `merge`. The default reports no
+    /// conflicts, which suits types whose merges never discard an update.
+    async fn merge_audited(&mut self, other: &Self) -> Result<MergeReport<Self::Operation>> {
+        self.merge(other).await?;
+        Ok(MergeReport::new())
+    }
+    
     /// Check if this CRDT can be merged with another
     fn can_merge(&self, other: &Self) -> bool;
     
@@ -1,10 +1,12 @@
 //! Core types for CRDT implementations
 
+use crate::error::{CrdtError, Result};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fmt::{self, Display},
     hash::{Hash as StdHash, Hasher},
+    sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 use uuid::Uuid;
@@ -112,9 +114,17 @@ impl StdHash for Timestamp {
 }
 
 /// Vector clock for causal ordering
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Entries of pruned actors are kept as dots: each pruned actor is fixed at
+/// its final counter in a table shared by every clock pruned with the same
+/// [`RetiredActors`], so pruned and unpruned clocks still compare and merge
+/// correctly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VectorClock {
     clocks: HashMap<ActorId, u64>,
+    /// Final counters of pruned actors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dots: Option<Arc<HashMap<ActorId, u64>>>,
 }
 
 impl VectorClock {
@@ -122,18 +132,22 @@ impl VectorClock {
     pub fn new() -> Self {
         Self {
             clocks: HashMap::new(),
+            dots: None,
         }
     }
     
     /// Advance clock for an actor
     pub fn advance(&mut self, actor: &ActorId) {
-        let counter = self.clocks.entry(actor.clone()).or_insert(0);
-        *counter += 1;
+        let counter = self.get(actor);
+        self.clocks.insert(*actor, counter + 1);
     }
     
     /// Get clock value for an actor
     pub fn get(&self, actor: &ActorId) -> u64 {
-        self.clocks.get(actor).copied().unwrap_or(0)
+        self.clocks.get(actor)
+            .or_else(|| self.dots.as_ref().and_then(|dots| dots.get(actor)))
+            .copied()
+            .unwrap_or(0)
     }
     
     /// Set clock value for an actor
@@ -142,10 +156,30 @@ impl VectorClock {
     }
     
     /// Merge with another vector clock (take maximum of each clock)
+    ///
+    /// Entries already covered by a dot on either side stay pruned.
     pub fn merge(&mut self, other: &VectorClock) {
+        match (&mut self.dots, &other.dots) {
+            (_, None) => {}
+            (None, Some(theirs)) => self.dots = Some(theirs.clone()),
+            (Some(ours), Some(theirs)) if Arc::ptr_eq(ours, theirs) => {}
+            (Some(ours), Some(theirs)) => {
+                let ours = Arc::make_mut(ours);
+                for (actor, &counter) in theirs.iter() {
+                    let current = ours.entry(*actor).or_insert(0);
+                    *current = (*current).max(counter);
+                }
+            }
+        }
+        
         for (actor, &timestamp) in &other.clocks {
-            let current = self.clocks.entry(actor.clone()).or_insert(0);
-            *current = (*current).max(timestamp);
+            if timestamp > self.get(actor) {
+                self.clocks.insert(*actor, timestamp);
+            }
+        }
+        
+        if let Some(dots) = &self.dots {
+            self.clocks.retain(|actor, counter| dots.get(actor).is_none_or(|dot| *counter > *dot));
         }
     }
     
@@ -154,10 +188,10 @@ impl VectorClock {
         let mut less_than = false;
         let mut greater_than = false;
         
-        // Get all actors from both clocks
+        // Get all actors from both clocks, including pruned ones
         let mut all_actors = std::collections::HashSet::new();
-        all_actors.extend(self.clocks.keys());
-        all_actors.extend(other.clocks.keys());
+        all_actors.extend(self.actors());
+        all_actors.extend(other.actors());
         
         for actor in all_actors {
             let self_time = self.get(actor);
@@ -188,18 +222,117 @@ impl VectorClock {
         matches!(self.compare(other), VectorClockComparison::Concurrent)
     }
     
-    /// Get all actors in this vector clock
+    /// Get all actors in this vector clock, including pruned ones
     pub fn actors(&self) -> impl Iterator<Item = &ActorId> {
-        self.clocks.keys()
+        self.clocks.keys().chain(self.dots.iter().flat_map(|dots| dots.keys()))
+    }
+    
+    /// Get the number of actor entries, not counting pruned actors
+    pub fn len(&self) -> usize {
+        self.clocks.len()
+    }
+    
+    /// Check if the clock has no entries
+    pub fn is_empty(&self) -> bool {
+        self.clocks.is_empty() && self.dots.as_ref().is_none_or(|dots| dots.is_empty())
+    }
+    
+    /// Move entries of causally stable retired actors into dots
+    ///
+    /// An entry is only pruned once it has reached the actor's final counter
+    /// and the stability threshold shows every live replica has reached it
+    /// too. Entries that are not yet stable are kept. Returns the number of
+    /// pruned entries, or an error without pruning anything if an entry is
+    /// beyond its actor's final counter.
+    pub fn prune(&mut self, retired_actors: &RetiredActors) -> Result<usize> {
+        let mut prunable = Vec::new();
+        
+        for (actor, &counter) in &self.clocks {
+            let Some(&final_counter) = retired_actors.finals.get(actor) else {
+                continue;
+            };
+            
+            if counter > final_counter {
+                return Err(CrdtError::VersionVectorError(format!(
+                    "Actor {} has counter {} beyond its final counter {}",
+                    actor, counter, final_counter
+                )));
+            }
+            if counter == final_counter && retired_actors.stable.get(actor) >= final_counter {
+                prunable.push((*actor, counter));
+            }
+        }
+        if prunable.is_empty() {
+            return Ok(0);
+        }
+        
+        let mut dots = self.dots.as_deref().cloned().unwrap_or_default();
+        for (actor, counter) in &prunable {
+            self.clocks.remove(actor);
+            dots.insert(*actor, *counter);
+        }
+        
+        // Share the retired actors' table when this clock pruned all of them
+        self.dots = Some(if dots == *retired_actors.finals {
+            retired_actors.finals.clone()
+        } else {
+            Arc::new(dots)
+        });
+        Ok(prunable.len())
     }
 }
 
+impl PartialEq for VectorClock {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == VectorClockComparison::Equal
+    }
+}
+
+impl Eq for VectorClock {}
+
 impl Default for VectorClock {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Retired actors and the stability threshold for pruning them
+///
+/// The stability threshold is a clock every live replica is known to have
+/// reached, typically the pointwise minimum of their latest clocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetiredActors {
+    /// Final counter of each retired actor
+    finals: Arc<HashMap<ActorId, u64>>,
+    /// Clock that every live replica has reached
+    stable: VectorClock,
+}
+
+impl RetiredActors {
+    /// Create an empty set of retired actors with a stability threshold
+    pub fn new(stable: VectorClock) -> Self {
+        Self {
+            finals: Arc::default(),
+            stable,
+        }
+    }
+    
+    /// Mark an actor as retired after issuing `final_counter` events
+    pub fn retire(&mut self, actor: ActorId, final_counter: u64) {
+        Arc::make_mut(&mut self.finals).insert(actor, final_counter);
+    }
+    
+    /// Replace the stability threshold
+    pub fn set_stable(&mut self, stable: VectorClock) {
+        self.stable = stable;
+    }
+    
+    /// Check if an actor has been retired
+    pub fn is_retired(&self, actor: &ActorId) -> bool {
+        self.finals.contains_key(actor)
+    }
+}
+
 /// Vector clock comparison result
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VectorClockComparison {
@@ -448,4 +581,103 @@ impl StdHash for MessageId {
         self.author.hash(state);
         self.nonce.hash(state);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn clock(entries: &[(ActorId, u64)]) -> VectorClock {
+        let mut clock = VectorClock::new();
+        for (actor, counter) in entries {
+            clock.set(*actor, *counter);
+        }
+        clock
+    }
+    
+    #[test]
+    fn test_prune_retired_actor_preserves_ordering() {
+        let (a, b, retired) = (ActorId::new(), ActorId::new(), ActorId::new());
+        
+        let mut first = clock(&[(a, 1), (b, 1), (retired, 2)]);
+        let mut second = clock(&[(a, 2), (b, 1), (retired, 2)]);
+        let mut third = clock(&[(a, 1), (b, 2), (retired, 2)]);
+        
+        let mut retired_actors = RetiredActors::new(clock(&[(a, 1), (b, 1), (retired, 2)]));
+        retired_actors.retire(retired, 2);
+        
+        for clock in [&mut first, &mut second, &mut third] {
+            assert_eq!(clock.prune(&retired_actors).unwrap(), 1);
+            assert_eq!(clock.len(), 2);
+            assert_eq!(clock.get(&retired), 2);
+        }
+        
+        // Clocks pruned together share one table of dots
+        assert!(Arc::ptr_eq(first.dots.as_ref().unwrap(), second.dots.as_ref().unwrap()));
+        
+        assert!(first.happens_before(&second));
+        assert!(first.happens_before(&third));
+        assert!(second.is_concurrent(&third));
+        assert_eq!(second.compare(&first), VectorClockComparison::After);
+    }
+    
+    #[test]
+    fn test_pruned_and_unpruned_clocks_compare_and_merge() {
+        let (a, retired) = (ActorId::new(), ActorId::new());
+        let mut retired_actors = RetiredActors::new(clock(&[(a, 1), (retired, 2)]));
+        retired_actors.retire(retired, 2);
+        
+        let mut pruned = clock(&[(a, 2), (retired, 2)]);
+        pruned.prune(&retired_actors).unwrap();
+        
+        // Clocks that were never pruned, like those of in-flight operations
+        let same = clock(&[(a, 2), (retired, 2)]);
+        let stale = clock(&[(a, 1), (retired, 1)]);
+        let missed_retired = clock(&[(a, 3)]);
+        assert_eq!(pruned, same);
+        assert!(stale.happens_before(&pruned));
+        assert!(pruned.is_concurrent(&missed_retired));
+        
+        // Merging an unpruned clock does not bring the entry back
+        let mut merged = pruned.clone();
+        merged.merge(&stale);
+        merged.merge(&same);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged, same);
+        
+        // Merging a pruned clock into an unpruned one prunes it
+        let mut merged = stale.clone();
+        merged.merge(&pruned);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged.get(&retired), 2);
+        assert_eq!(merged, same);
+        
+        // Dots survive serialization
+        let decoded: VectorClock = serde_json::from_str(&serde_json::to_string(&pruned).unwrap()).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded, same);
+    }
+    
+    #[test]
+    fn test_prune_keeps_causally_relevant_entries() {
+        let (a, retired) = (ActorId::new(), ActorId::new());
+        let mut retired_actors = RetiredActors::new(clock(&[(a, 1), (retired, 2)]));
+        retired_actors.retire(retired, 2);
+        
+        // This clock has not seen the retired actor's last event
+        let mut behind = clock(&[(a, 1), (retired, 1)]);
+        assert_eq!(behind.prune(&retired_actors).unwrap(), 0);
+        assert_eq!(behind.get(&retired), 1);
+        
+        // Not every live replica has seen the last event yet
+        let mut current = clock(&[(a, 1), (retired, 2)]);
+        retired_actors.set_stable(clock(&[(a, 1), (retired, 1)]));
+        assert_eq!(current.prune(&retired_actors).unwrap(), 0);
+        assert_eq!(current.len(), 2);
+        
+        // Events past the final counter mean the retirement is wrong
+        let mut ahead = clock(&[(a, 1), (retired, 3)]);
+        assert!(ahead.prune(&retired_actors).is_err());
+        assert_eq!(ahead.len(), 2);
+    }
+}
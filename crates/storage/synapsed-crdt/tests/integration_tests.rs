@@ -1,6 +1,7 @@
 //! Integration tests for CRDT implementations
 
 use synapsed_crdt::*;
+use synapsed_crdt::traits::GarbageCollectable;
 use tokio_test;
 
 #[tokio::test]
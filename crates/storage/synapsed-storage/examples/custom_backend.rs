@@ -42,6 +42,16 @@ impl Storage for LoggingStorage {
         println!("DELETE: {:?}", String::from_utf8_lossy(key));
         self.inner.delete(key).await
     }
+    
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        println!("CAS: {:?}", String::from_utf8_lossy(key));
+        self.inner.compare_and_swap(key, expected, new).await
+    }
+    
+    async fn list(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        println!("LIST: {:?}", String::from_utf8_lossy(prefix));
+        self.inner.list(prefix).await
+    }
 }
 
 #[tokio::main]
@@ -91,6 +91,18 @@ impl Storage for FileStorage {
         self.save().await
     }
     
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        {
+            let mut cache = self.cache.write().await;
+            if cache.get(key).map(Vec::as_slice) != expected {
+                return Ok(false);
+            }
+            cache.insert(key.to_vec(), new.to_vec());
+        }
+        self.save().await?;
+        Ok(true)
+    }
+    
     async fn delete(&self, key: &[u8]) -> Result<()> {
         {
             let mut cache = self.cache.write().await;
@@ -1,7 +1,9 @@
 //! In-memory storage backend for testing and development
 
 use crate::error::{Result, StorageError};
-use crate::traits::{Storage, StorageStats};
+use crate::traits::{Storage, StorageStats, TransactionalStorage, WatchEvent, WatchableStorage};
+use crate::transaction::BufferedTransaction;
+use crate::watch::{ChangeFeed, ChangeWatcher};
 use crate::config::MemoryConfig;
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -13,6 +15,7 @@ use std::sync::{Arc, RwLock};
 pub struct MemoryStorage {
     data: Arc<RwLock<HashMap<Vec<u8>, Vec<u8>>>>,
     stats: Arc<RwLock<StorageStats>>,
+    changes: ChangeFeed,
     config: MemoryConfig,
 }
 
@@ -28,6 +31,7 @@ impl MemoryStorage {
         Self {
             data: Arc::new(RwLock::new(HashMap::with_capacity(capacity))),
             stats: Arc::new(RwLock::new(StorageStats::default())),
+            changes: ChangeFeed::default(),
             config,
         }
     }
@@ -40,6 +44,92 @@ impl MemoryStorage {
         };
         Self::new(config)
     }
+
+    /// Insert under already-held locks, enforcing the memory limit
+    fn insert(
+        &self,
+        data: &mut HashMap<Vec<u8>, Vec<u8>>,
+        stats: &mut StorageStats,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<()> {
+        // Check memory limits if configured
+        if self.config.max_memory_bytes > 0 {
+            let current_size = stats.size_bytes as usize;
+            let projected_size = match data.get(key) {
+                Some(old) => current_size - old.len() + value.len(),
+                None => current_size + key.len() + value.len(),
+            };
+            if projected_size > self.config.max_memory_bytes {
+                return Err(StorageError::StorageFull);
+            }
+        }
+        
+        let is_new = !data.contains_key(key);
+        data.insert(key.to_vec(), value.to_vec());
+        
+        if is_new {
+            stats.key_count += 1;
+        }
+        stats.put_count += 1;
+        stats.size_bytes = data.values().map(|v| v.len() as u64).sum();
+
+        self.changes.publish(|| {
+            let key = Bytes::copy_from_slice(key);
+            let value = Bytes::copy_from_slice(value);
+            if is_new {
+                WatchEvent::Insert { key, value }
+            } else {
+                WatchEvent::Update { key, value }
+            }
+        });
+        
+        Ok(())
+    }
+
+    /// Remove under already-held locks
+    fn remove(&self, data: &mut HashMap<Vec<u8>, Vec<u8>>, stats: &mut StorageStats, key: &[u8]) {
+        if data.remove(key).is_some() {
+            stats.key_count = stats.key_count.saturating_sub(1);
+            stats.delete_count += 1;
+            stats.size_bytes = data.values().map(|v| v.len() as u64).sum();
+            self.changes.publish(|| WatchEvent::Delete { key: Bytes::copy_from_slice(key) });
+        }
+    }
+
+    /// Check that a batch of writes stays within the memory limit throughout
+    fn check_capacity(
+        &self,
+        data: &HashMap<Vec<u8>, Vec<u8>>,
+        stats: &StorageStats,
+        writes: &[(&[u8], Option<&[u8]>)],
+    ) -> Result<()> {
+        if self.config.max_memory_bytes == 0 {
+            return Ok(());
+        }
+
+        // Value sizes as the batch would leave them, mirroring `insert`
+        let mut size = stats.size_bytes as usize;
+        let mut written: HashMap<&[u8], Option<usize>> = HashMap::new();
+        for (key, value) in writes {
+            let old = match written.get(key) {
+                Some(len) => *len,
+                None => data.get(*key).map(Vec::len),
+            };
+            if let Some(value) = value {
+                let projected = match old {
+                    Some(old) => size - old + value.len(),
+                    None => size + key.len() + value.len(),
+                };
+                if projected > self.config.max_memory_bytes {
+                    return Err(StorageError::StorageFull);
+                }
+            }
+            size = size - old.unwrap_or(0) + value.map_or(0, <[u8]>::len);
+            written.insert(key, value.map(<[u8]>::len));
+        }
+        Ok(())
+    }
 }
 
 impl Default for MemoryStorage {
@@ -63,46 +153,47 @@ impl Storage for MemoryStorage {
     async fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
         let mut data = self.data.write().unwrap();
         let mut stats = self.stats.write().unwrap();
-        
-        // Check memory limits if configured
-        if self.config.max_memory_bytes > 0 {
-            let current_size = stats.size_bytes as usize;
-            let new_entry_size = key.len() + value.len();
-            let is_update = data.contains_key(key);
-            let old_entry_size = if is_update {
-                data.get(key).map(|v| key.len() + v.len()).unwrap_or(0)
-            } else {
-                0
-            };
-            
-            let projected_size = current_size - old_entry_size + new_entry_size;
-            if projected_size > self.config.max_memory_bytes {
-                return Err(StorageError::StorageFull);
-            }
+        self.insert(&mut data, &mut stats, key, value)
+    }
+
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        let mut data = self.data.write().unwrap();
+        let mut stats = self.stats.write().unwrap();
+
+        if data.get(key).map(Vec::as_slice) != expected {
+            return Ok(false);
         }
-        
-        let is_new = !data.contains_key(key);
-        data.insert(key.to_vec(), value.to_vec());
-        
-        if is_new {
-            stats.key_count += 1;
+        self.insert(&mut data, &mut stats, key, new)?;
+        Ok(true)
+    }
+
+    async fn compare_and_apply(
+        &self,
+        expected: &[(&[u8], Option<&[u8]>)],
+        writes: &[(&[u8], Option<&[u8]>)],
+    ) -> Result<bool> {
+        let mut data = self.data.write().unwrap();
+        let mut stats = self.stats.write().unwrap();
+
+        if expected.iter().any(|(key, value)| data.get(*key).map(Vec::as_slice) != *value) {
+            return Ok(false);
         }
-        stats.put_count += 1;
-        stats.size_bytes = data.values().map(|v| v.len() as u64).sum();
-        
-        Ok(())
+
+        // Refuse the whole batch up front rather than stop halfway through
+        self.check_capacity(&data, &stats, writes)?;
+        for (key, value) in writes {
+            match value {
+                Some(value) => self.insert(&mut data, &mut stats, key, value)?,
+                None => self.remove(&mut data, &mut stats, key),
+            }
+        }
+        Ok(true)
     }
 
     async fn delete(&self, key: &[u8]) -> Result<()> {
         let mut data = self.data.write().unwrap();
         let mut stats = self.stats.write().unwrap();
-        
-        if data.remove(key).is_some() {
-            stats.key_count = stats.key_count.saturating_sub(1);
-            stats.delete_count += 1;
-            stats.size_bytes = data.values().map(|v| v.len() as u64).sum();
-        }
-        
+        self.remove(&mut data, &mut stats, key);
         Ok(())
     }
 
@@ -127,6 +218,28 @@ impl Storage for MemoryStorage {
     }
 }
 
+#[async_trait]
+impl TransactionalStorage for MemoryStorage {
+    type Transaction = BufferedTransaction<MemoryStorage>;
+
+    async fn begin_transaction(&self) -> Result<Self::Transaction> {
+        Ok(BufferedTransaction::new(self.clone()))
+    }
+}
+
+#[async_trait]
+impl WatchableStorage for MemoryStorage {
+    type Watcher = ChangeWatcher;
+
+    async fn watch(&self, key: &[u8]) -> Result<ChangeWatcher> {
+        Ok(self.changes.watch(key))
+    }
+
+    async fn watch_prefix(&self, prefix: &[u8]) -> Result<ChangeWatcher> {
+        Ok(self.changes.watch_prefix(prefix))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +363,4 @@ mod tests {
         storage2.put(b"key2", b"value2").await.unwrap();
         assert_eq!(storage1.get(b"key2").await.unwrap(), Some(Bytes::from("value2")));
     }
-}
\ No newline at end of file
+}
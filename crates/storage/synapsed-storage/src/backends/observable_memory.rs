@@ -85,6 +85,26 @@ impl Storage for ObservableMemoryStorage {
         Ok(())
     }
 
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        let mut data = self.data.write();
+        if data.get(key).map(Vec::as_slice) != expected {
+            return Ok(false);
+        }
+        data.insert(key.to_vec(), new.to_vec());
+        
+        self.notify_observers(StorageEvent {
+            event_type: EventType::Put,
+            key: Some(key.to_vec()),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            metadata: None,
+        });
+        
+        Ok(true)
+    }
+
     async fn delete(&self, key: &[u8]) -> Result<()> {
         let mut data = self.data.write();
         let _existed = data.remove(key).is_some();
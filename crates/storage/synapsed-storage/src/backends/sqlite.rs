@@ -32,6 +32,10 @@ impl Storage for SqliteStorage {
         self.inner.put(key, value).await
     }
     
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        self.inner.compare_and_swap(key, expected, new).await
+    }
+    
     async fn delete(&self, key: &[u8]) -> Result<()> {
         self.inner.delete(key).await
     }
@@ -71,6 +71,21 @@ impl<S: Storage + ?Sized> Storage for CacheLayer<S> {
         Ok(())
     }
 
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        let swapped = self.inner.compare_and_swap(key, expected, new).await.map_err(|_| StorageError::Backend(
+            crate::error::BackendError::Other("Backend compare-and-swap failed".to_string())
+        ))?;
+
+        // A failed swap means the cached value may be stale
+        if swapped {
+            self.cache.put(key, Bytes::copy_from_slice(new)).await?;
+        } else {
+            self.cache.remove(key).await?;
+        }
+
+        Ok(swapped)
+    }
+
     async fn delete(&self, key: &[u8]) -> Result<()> {
         // Delete from storage
         self.inner.delete(key).await.map_err(|_| StorageError::Backend(
@@ -45,6 +45,15 @@ impl<S: Storage + ?Sized> CompressionLayer<S> {
             config,
         })
     }
+
+    /// Encode a value the way it is stored in the inner backend
+    fn encode(&self, value: &[u8]) -> Result<Bytes> {
+        if self.config.enabled && value.len() >= self.config.min_size {
+            self.compressor.compress(value)
+        } else {
+            Ok(Bytes::copy_from_slice(value))
+        }
+    }
 }
 
 #[async_trait]
@@ -70,17 +79,24 @@ impl<S: Storage + ?Sized> Storage for CompressionLayer<S> {
     }
 
     async fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
-        let data = if self.config.enabled && value.len() >= self.config.min_size {
-            self.compressor.compress(value)?
-        } else {
-            Bytes::copy_from_slice(value)
-        };
+        let data = self.encode(value)?;
 
         self.inner.put(key, &data).await.map_err(|_| StorageError::Backend(
             crate::error::BackendError::Other("Backend put failed".to_string())
         ))
     }
 
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        // Compression is deterministic, so the encoded expected value matches
+        // what `put` stored for it
+        let expected = expected.map(|value| self.encode(value)).transpose()?;
+        let data = self.encode(new)?;
+
+        self.inner.compare_and_swap(key, expected.as_deref(), &data).await.map_err(|_| StorageError::Backend(
+            crate::error::BackendError::Other("Backend compare-and-swap failed".to_string())
+        ))
+    }
+
     async fn delete(&self, key: &[u8]) -> Result<()> {
         self.inner.delete(key).await.map_err(|_| StorageError::Backend(
             crate::error::BackendError::Other("Backend delete failed".to_string())
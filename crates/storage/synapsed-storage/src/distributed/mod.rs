@@ -40,4 +40,9 @@ impl Storage for DistributedStorage {
         // TODO: Implement distributed delete
         Err(StorageError::Other("Distributed storage not yet implemented".to_string()))
     }
+
+    async fn compare_and_swap(&self, _key: &[u8], _expected: Option<&[u8]>, _new: &[u8]) -> Result<bool> {
+        // TODO: Implement distributed compare-and-swap
+        Err(StorageError::Other("Distributed storage not yet implemented".to_string()))
+    }
 }
\ No newline at end of file
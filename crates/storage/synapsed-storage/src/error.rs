@@ -52,6 +52,14 @@ pub enum StorageError {
     #[error("Transaction conflict")]
     TransactionConflict,
 
+    /// No savepoint with the given name in the transaction
+    #[error("Savepoint not found: {0}")]
+    SavepointNotFound(String),
+
+    /// A watcher fell behind and missed change events
+    #[error("Watcher lagged behind by {0} events")]
+    WatchLagged(u64),
+
     /// Storage is read-only
     #[error("Storage is read-only")]
     ReadOnly,
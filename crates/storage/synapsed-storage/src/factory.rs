@@ -8,6 +8,7 @@ use crate::{
     config::{MemoryConfig, StorageConfig},
     error::{Result, StorageError},
     observable::{ObservableStorageBuilder, MonitoringConfig},
+    tiered::{TieredStorage, TieringPolicy},
     traits::Storage,
     StorageBuilder,
 };
@@ -71,6 +72,22 @@ impl StorageFactory {
         }
     }
 
+    /// Create tiered storage over a hot and a cold backend
+    ///
+    /// Background migration of idle keys to the cold backend is started and
+    /// runs until the storage is dropped.
+    pub async fn create_tiered(
+        hot: StorageBackend,
+        cold: StorageBackend,
+        policy: TieringPolicy,
+    ) -> Result<Arc<TieredStorage>> {
+        let hot = Self::create_base(hot).await?;
+        let cold = Self::create_base(cold).await?;
+        let storage = Arc::new(TieredStorage::new(hot, cold, policy));
+        storage.start_migration();
+        Ok(storage)
+    }
+
     /// Create a base storage backend without observability
     async fn create_base(
         backend: StorageBackend,
@@ -211,6 +228,20 @@ mod tests {
         assert!(storage.exists(b"test").await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_factory_create_tiered() {
+        let storage = StorageFactory::create_tiered(
+            StorageBackend::Memory { capacity: None },
+            StorageBackend::Memory { capacity: None },
+            TieringPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        storage.put(b"test", b"data").await.unwrap();
+        assert_eq!(storage.get(b"test").await.unwrap(), Some(bytes::Bytes::from("data")));
+    }
+
     #[tokio::test]
     async fn test_advanced_builder() {
         let storage = AdvancedStorageBuilder::new(StorageBackend::Memory { capacity: Some(50) })
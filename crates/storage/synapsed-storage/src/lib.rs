@@ -14,6 +14,8 @@ pub mod compression;
 pub mod config;
 pub mod error;
 pub mod traits;
+pub mod transaction;
+pub mod watch;
 
 #[cfg(feature = "distributed")]
 pub mod distributed;
@@ -25,6 +27,7 @@ pub mod utils;
 
 pub mod observable;
 pub mod factory;
+pub mod tiered;
 
 // Re-export commonly used types
 pub use config::{CacheConfig, CompressionConfig, StorageConfig};
@@ -33,6 +36,8 @@ pub use traits::{
     BatchedStorage, IterableStorage, Storage, StorageIterator, StorageTransaction,
     TransactionalStorage,
 };
+pub use tiered::{TieredStorage, TieringPolicy};
+pub use transaction::BufferedTransaction;
 
 // Re-export core types for better integration
 pub use synapsed_core::{SynapsedError, SynapsedResult};
@@ -46,6 +51,8 @@ impl From<StorageError> for SynapsedError {
             StorageError::InvalidKey(key) => SynapsedError::InvalidInput(format!("Invalid key: {}", key)),
             StorageError::InvalidValue(value) => SynapsedError::InvalidInput(format!("Invalid value: {}", value)),
             StorageError::TransactionConflict => SynapsedError::Internal("Transaction conflict".to_string()),
+            StorageError::SavepointNotFound(name) => SynapsedError::NotFound(format!("Savepoint not found: {}", name)),
+            StorageError::WatchLagged(missed) => SynapsedError::Internal(format!("Watcher lagged behind by {} events", missed)),
             StorageError::ReadOnly => SynapsedError::PermissionDenied("Storage is read-only".to_string()),
             StorageError::Serialization(msg) => SynapsedError::Serialization(msg),
             StorageError::Deserialization(msg) => SynapsedError::Serialization(msg),
@@ -74,6 +74,19 @@ impl<S: Storage + ?Sized> Storage for MetricsLayer<S> {
         result
     }
 
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        let start = std::time::Instant::now();
+        let result = self.inner.compare_and_swap(key, expected, new).await.map_err(|_| StorageError::Backend(
+            crate::error::BackendError::Other("Backend compare-and-swap failed".to_string())
+        ));
+        
+        let duration = start.elapsed();
+        self.put_count.fetch_add(1, Ordering::Relaxed);
+        self.put_latency_us.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        
+        result
+    }
+
     async fn delete(&self, key: &[u8]) -> Result<()> {
         self.delete_count.fetch_add(1, Ordering::Relaxed);
         self.inner.delete(key).await.map_err(|_| StorageError::Backend(
@@ -150,6 +150,19 @@ impl<S: Storage + ?Sized> Storage for ObservableStorage<S> {
         self.inner.put(key, value).await
     }
 
+    async fn compare_and_swap(
+        &self,
+        key: &[u8],
+        expected: Option<&[u8]>,
+        new: &[u8],
+    ) -> Result<bool, Self::Error> {
+        let swapped = self.inner.compare_and_swap(key, expected, new).await?;
+        if swapped {
+            self.emit_event(EventType::Put, Some(key));
+        }
+        Ok(swapped)
+    }
+
     async fn delete(&self, key: &[u8]) -> Result<(), Self::Error> {
         self.emit_event(EventType::Delete, Some(key));
         self.inner.delete(key).await
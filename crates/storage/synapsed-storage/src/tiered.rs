@@ -0,0 +1,295 @@
+//! Tiered storage keeping the working set on a fast backend
+//!
+//! [`TieredStorage`] writes to a hot backend and tracks when each hot key was
+//! last accessed. Keys left idle for longer than the policy window are demoted
+//! to the cold backend by [`TieredStorage::demote_idle`], which
+//! [`TieredStorage::start_migration`] runs periodically. Reading a cold key
+//! promotes it back to the hot tier. Every key lives in exactly one tier.
+//!
+//! Moving a key between tiers and writing it are serialized by a lock per key,
+//! so a demotion in progress never holds up access to other keys.
+
+use crate::error::Result;
+use crate::traits::Storage;
+use crate::StorageError;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use synapsed_core::clock::{SharedClock, SystemClock};
+use tokio::sync::OwnedMutexGuard;
+
+/// When keys move between tiers
+#[derive(Debug, Clone)]
+pub struct TieringPolicy {
+    /// Idle time after which a hot key is demoted
+    pub demote_after: Duration,
+    /// Maximum number of hot keys, the least recently used are demoted first
+    pub max_hot_keys: Option<usize>,
+    /// How often the background migration runs
+    pub migration_interval: Duration,
+}
+
+impl Default for TieringPolicy {
+    fn default() -> Self {
+        Self {
+            demote_after: Duration::from_secs(3600),
+            max_hot_keys: None,
+            migration_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Counters for movement between tiers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TieringStats {
+    /// Keys moved from cold to hot
+    pub promotions: u64,
+    /// Keys moved from hot to cold
+    pub demotions: u64,
+}
+
+type Backend = Arc<dyn Storage<Error = StorageError>>;
+
+/// Locks of the keys currently being written or moved between tiers
+#[derive(Default)]
+struct KeyLocks(Mutex<HashMap<Vec<u8>, Arc<tokio::sync::Mutex<()>>>>);
+
+impl KeyLocks {
+    /// Wait for exclusive access to `key`
+    async fn lock(&self, key: &[u8]) -> KeyGuard<'_> {
+        let lock = self.0.lock().entry(key.to_vec()).or_default().clone();
+        KeyGuard {
+            locks: self,
+            key: key.to_vec(),
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+}
+
+/// Exclusive access to a key, dropping its lock once nobody else wants it
+struct KeyGuard<'a> {
+    locks: &'a KeyLocks,
+    key: Vec<u8>,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for KeyGuard<'_> {
+    fn drop(&mut self) {
+        self.guard.take();
+        let mut locks = self.locks.0.lock();
+        if locks.get(&self.key).is_some_and(|lock| Arc::strong_count(lock) == 1) {
+            locks.remove(&self.key);
+        }
+    }
+}
+
+/// Storage splitting keys between a hot and a cold backend
+pub struct TieredStorage {
+    hot: Backend,
+    cold: Backend,
+    policy: TieringPolicy,
+    /// Clock for access times
+    clock: SharedClock,
+    /// Last access of each hot key
+    access: Mutex<HashMap<Vec<u8>, DateTime<Utc>>>,
+    /// Serialize writes with migrations of the same key so it is never lost
+    /// between tiers
+    key_locks: KeyLocks,
+    promotions: AtomicU64,
+    demotions: AtomicU64,
+}
+
+impl TieredStorage {
+    /// Create tiered storage over a hot and a cold backend
+    pub fn new(hot: Backend, cold: Backend, policy: TieringPolicy) -> Self {
+        Self {
+            hot,
+            cold,
+            policy,
+            clock: SystemClock::shared(),
+            access: Mutex::new(HashMap::new()),
+            key_locks: KeyLocks::default(),
+            promotions: AtomicU64::new(0),
+            demotions: AtomicU64::new(0),
+        }
+    }
+
+    /// Use `clock` to decide when keys are idle
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The tiering policy
+    pub fn policy(&self) -> &TieringPolicy {
+        &self.policy
+    }
+
+    /// Promotion and demotion counters
+    pub fn stats(&self) -> TieringStats {
+        TieringStats {
+            promotions: self.promotions.load(Ordering::Relaxed),
+            demotions: self.demotions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Demote idle hot keys to the cold tier, returning how many were moved
+    ///
+    /// Candidates are picked without locking; each is then moved under its own
+    /// lock, and skipped if it was accessed or deleted in the meantime.
+    pub async fn demote_idle(&self) -> Result<usize> {
+        // Keys written to the hot backend directly count as accessed now
+        let hot_keys = self.hot.list(&[]).await?;
+        let now = self.clock.now();
+        let mut candidates: Vec<(Vec<u8>, DateTime<Utc>)> = {
+            let mut access = self.access.lock();
+            let present: HashSet<&Vec<u8>> = hot_keys.iter().collect();
+            access.retain(|key, _| present.contains(key));
+            for key in &hot_keys {
+                access.entry(key.clone()).or_insert(now);
+            }
+            access.iter().map(|(key, at)| (key.clone(), *at)).collect()
+        };
+        candidates.sort_by_key(|(_, at)| *at);
+
+        let idle = candidates
+            .iter()
+            .take_while(|(_, at)| (now - *at).to_std().is_ok_and(|idle| idle >= self.policy.demote_after))
+            .count();
+        let over_capacity = self
+            .policy
+            .max_hot_keys
+            .map_or(0, |max| candidates.len().saturating_sub(max));
+
+        let mut demoted = 0;
+        for (key, accessed) in candidates.iter().take(idle.max(over_capacity)) {
+            let _guard = self.key_locks.lock(key).await;
+            if self.access.lock().get(key) != Some(accessed) {
+                continue;
+            }
+            if let Some(value) = self.hot.get(key).await? {
+                self.cold.put(key, &value).await?;
+            }
+            self.hot.delete(key).await?;
+            self.access.lock().remove(key);
+            demoted += 1;
+        }
+
+        self.demotions.fetch_add(demoted as u64, Ordering::Relaxed);
+        Ok(demoted)
+    }
+
+    /// Run [`demote_idle`](Self::demote_idle) every migration interval
+    ///
+    /// The task stops once the storage is dropped.
+    pub fn start_migration(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let storage: Weak<Self> = Arc::downgrade(self);
+        let interval = self.policy.migration_interval;
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(storage) = storage.upgrade() else {
+                    break;
+                };
+                if let Err(e) = storage.demote_idle().await {
+                    tracing::warn!("Tiered storage migration failed: {}", e);
+                }
+            }
+        })
+    }
+
+    fn touch(&self, key: &[u8]) {
+        self.access.lock().insert(key.to_vec(), self.clock.now());
+    }
+
+    /// Move a cold key to the hot tier, returning its value
+    ///
+    /// Must be called while holding the key's lock.
+    async fn promote(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        let Some(value) = self.cold.get(key).await? else {
+            return Ok(None);
+        };
+        self.hot.put(key, &value).await?;
+        self.cold.delete(key).await?;
+        self.touch(key);
+        self.promotions.fetch_add(1, Ordering::Relaxed);
+        Ok(Some(value))
+    }
+}
+
+#[async_trait]
+impl Storage for TieredStorage {
+    type Error = StorageError;
+
+    async fn get(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        if let Some(value) = self.hot.get(key).await? {
+            self.touch(key);
+            return Ok(Some(value));
+        }
+
+        let _guard = self.key_locks.lock(key).await;
+        // A concurrent migration may have promoted the key already
+        if let Some(value) = self.hot.get(key).await? {
+            self.touch(key);
+            return Ok(Some(value));
+        }
+        self.promote(key).await
+    }
+
+    async fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let _guard = self.key_locks.lock(key).await;
+        self.hot.put(key, value).await?;
+        self.cold.delete(key).await?;
+        self.touch(key);
+        Ok(())
+    }
+
+    async fn compare_and_swap(&self, key: &[u8], expected: Option<&[u8]>, new: &[u8]) -> Result<bool> {
+        let _guard = self.key_locks.lock(key).await;
+        if !self.hot.exists(key).await? {
+            self.promote(key).await?;
+        }
+        let swapped = self.hot.compare_and_swap(key, expected, new).await?;
+        if swapped {
+            self.touch(key);
+        }
+        Ok(swapped)
+    }
+
+    async fn delete(&self, key: &[u8]) -> Result<()> {
+        let _guard = self.key_locks.lock(key).await;
+        self.hot.delete(key).await?;
+        self.cold.delete(key).await?;
+        self.access.lock().remove(key);
+        Ok(())
+    }
+
+    async fn exists(&self, key: &[u8]) -> Result<bool> {
+        Ok(self.hot.exists(key).await? || self.cold.exists(key).await?)
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.hot.flush().await?;
+        self.cold.flush().await
+    }
+
+    async fn list(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let mut keys = self.hot.list(prefix).await?;
+        let hot: HashSet<Vec<u8>> = keys.iter().cloned().collect();
+        keys.extend(
+            self.cold
+                .list(prefix)
+                .await?
+                .into_iter()
+                .filter(|key| !hot.contains(key)),
+        );
+        Ok(keys)
+    }
+}
@@ -2,6 +2,7 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::stream::{self, BoxStream};
 use std::error::Error;
 
 /// Core storage trait that all backends must implement
@@ -19,6 +20,65 @@ pub trait Storage: Send + Sync {
     /// Delete a key
     async fn delete(&self, key: &[u8]) -> Result<(), Self::Error>;
 
+    /// Atomically store `new` if the current value of `key` equals `expected`
+    ///
+    /// `expected` of `None` means the key must not exist. Returns whether the
+    /// value was swapped; a mismatch is not an error.
+    async fn compare_and_swap(
+        &self,
+        key: &[u8],
+        expected: Option<&[u8]>,
+        new: &[u8],
+    ) -> Result<bool, Self::Error>;
+
+    /// Atomically apply `writes` if every key in `expected` still holds the given value
+    ///
+    /// A write of `None` deletes the key. Returns whether the writes were
+    /// applied; a mismatch is not an error. Either every write is applied or
+    /// none is: if one fails, those already made are undone before the error
+    /// is returned. The default works key by key and is not isolated from
+    /// concurrent writers, so backends should override it to check and write
+    /// under their own lock.
+    async fn compare_and_apply(
+        &self,
+        expected: &[(&[u8], Option<&[u8]>)],
+        writes: &[(&[u8], Option<&[u8]>)],
+    ) -> Result<bool, Self::Error> {
+        for (key, value) in expected {
+            if self.get(key).await?.as_deref() != *value {
+                return Ok(false);
+            }
+        }
+
+        let mut applied = Vec::with_capacity(writes.len());
+        for (key, value) in writes {
+            let result = async {
+                let previous = self.get(key).await?;
+                match value {
+                    Some(value) => self.put(key, value).await?,
+                    None => self.delete(key).await?,
+                }
+                Ok::<_, Self::Error>(previous)
+            }
+            .await;
+
+            match result {
+                Ok(previous) => applied.push((*key, previous)),
+                Err(e) => {
+                    // Best effort; the write that failed is the error to report
+                    for (key, previous) in applied.into_iter().rev() {
+                        let _ = match previous {
+                            Some(previous) => self.put(key, &previous).await,
+                            None => self.delete(key).await,
+                        };
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(true)
+    }
+
     /// Check if a key exists
     async fn exists(&self, key: &[u8]) -> Result<bool, Self::Error> {
         Ok(self.get(key).await?.is_some())
@@ -101,6 +161,23 @@ pub trait StorageTransaction: Send {
     /// Delete a key within the transaction
     async fn delete(&mut self, key: &[u8]) -> Result<(), Self::Error>;
 
+    /// Mark a savepoint that later changes can be rolled back to
+    ///
+    /// Savepoints nest; reusing a name shadows the earlier savepoint until the
+    /// newer one is released.
+    async fn savepoint(&mut self, name: &str) -> Result<(), Self::Error>;
+
+    /// Discard the changes made since a savepoint
+    ///
+    /// The savepoint itself stays in place, savepoints created after it are
+    /// removed.
+    async fn rollback_to(&mut self, name: &str) -> Result<(), Self::Error>;
+
+    /// Remove a savepoint, keeping the changes made since it
+    ///
+    /// Savepoints created after it are removed as well.
+    async fn release(&mut self, name: &str) -> Result<(), Self::Error>;
+
     /// Commit the transaction
     async fn commit(self) -> Result<(), Self::Error>;
 
@@ -148,6 +225,9 @@ pub struct SnapshotMetadata {
 }
 
 /// Watch/Subscribe support for change notifications
+///
+/// Watchers only see changes made after they were created. Changes to the same
+/// key are delivered in the order they were applied.
 #[async_trait]
 pub trait WatchableStorage: Storage {
     /// Watcher type for this storage
@@ -171,17 +251,34 @@ pub trait StorageWatcher: Send {
 
     /// Stop watching
     async fn cancel(self) -> Result<(), Self::Error>;
+
+    /// Turn the watcher into a stream of events, ending after the first error
+    fn into_stream(self) -> BoxStream<'static, Result<WatchEvent, Self::Error>>
+    where
+        Self: Sized + 'static,
+    {
+        Box::pin(stream::try_unfold(self, |mut watcher| async move {
+            Ok(watcher.next_event().await?.map(|event| (event, watcher)))
+        }))
+    }
 }
 
 /// Event types for storage watchers
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WatchEvent {
-    /// A key was created or updated
-    Put { 
-        /// The key that was created or updated
-        key: Bytes, 
+    /// A key was created
+    Insert {
+        /// The key that was created
+        key: Bytes,
+        /// The value stored at the key
+        value: Bytes,
+    },
+    /// An existing key was overwritten
+    Update {
+        /// The key that was updated
+        key: Bytes,
         /// The new value stored at the key
-        value: Bytes 
+        value: Bytes,
     },
     /// A key was deleted
     Delete { 
@@ -190,6 +287,25 @@ pub enum WatchEvent {
     },
 }
 
+impl WatchEvent {
+    /// The key the event is about
+    pub fn key(&self) -> &Bytes {
+        match self {
+            WatchEvent::Insert { key, .. }
+            | WatchEvent::Update { key, .. }
+            | WatchEvent::Delete { key } => key,
+        }
+    }
+
+    /// The value written, `None` for deletes
+    pub fn value(&self) -> Option<&Bytes> {
+        match self {
+            WatchEvent::Insert { value, .. } | WatchEvent::Update { value, .. } => Some(value),
+            WatchEvent::Delete { .. } => None,
+        }
+    }
+}
+
 /// Storage statistics and metrics
 #[async_trait]
 pub trait StorageMetrics: Storage {
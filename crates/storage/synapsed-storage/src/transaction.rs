@@ -0,0 +1,167 @@
+//! Transactions emulated with a write buffer and an operation log
+//!
+//! [`BufferedTransaction`] works on top of any [`Storage`]. Writes are kept in
+//! memory until commit, so no backend locks are held while the transaction is
+//! open and concurrent transactions cannot deadlock each other. Values read
+//! from the backend are remembered, and commit applies the writes with
+//! [`Storage::compare_and_apply`] only if none of them has changed since.
+//! While a savepoint is active, every write records the buffer state it
+//! replaced, which lets savepoints be rolled back by undoing the log back to
+//! the savepoint's position.
+
+use crate::error::{Result, StorageError};
+use crate::traits::{Storage, StorageTransaction};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Buffer entry for a key: `Some` for a pending put, `None` for a pending delete
+type Pending = Option<Vec<u8>>;
+
+/// Log entry restoring a key's buffer state
+#[derive(Debug)]
+struct Undo {
+    key: Vec<u8>,
+    /// Buffer state before the write, `None` if the key was not buffered
+    previous: Option<Pending>,
+}
+
+/// Transaction buffering writes over a storage backend until commit
+#[derive(Debug)]
+pub struct BufferedTransaction<S> {
+    storage: S,
+    writes: BTreeMap<Vec<u8>, Pending>,
+    /// Backend values as first read, checked again at commit
+    reads: Mutex<BTreeMap<Vec<u8>, Option<Bytes>>>,
+    log: Vec<Undo>,
+    savepoints: Vec<(String, usize)>,
+}
+
+impl<S> BufferedTransaction<S>
+where
+    S: Storage<Error = StorageError>,
+{
+    /// Start a transaction over `storage`
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            writes: BTreeMap::new(),
+            reads: Mutex::new(BTreeMap::new()),
+            log: Vec::new(),
+            savepoints: Vec::new(),
+        }
+    }
+
+    /// Names of the active savepoints, oldest first
+    pub fn savepoints(&self) -> impl Iterator<Item = &str> {
+        self.savepoints.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Number of keys with pending writes
+    pub fn pending_writes(&self) -> usize {
+        self.writes.len()
+    }
+
+    fn record(&mut self, key: &[u8], value: Pending) {
+        let previous = self.writes.insert(key.to_vec(), value);
+
+        // Without a savepoint nothing can be rolled back short of the whole transaction
+        if !self.savepoints.is_empty() {
+            self.log.push(Undo {
+                key: key.to_vec(),
+                previous,
+            });
+        }
+    }
+
+    /// Index of the most recent savepoint named `name`
+    fn find_savepoint(&self, name: &str) -> Result<usize> {
+        self.savepoints
+            .iter()
+            .rposition(|(savepoint, _)| savepoint == name)
+            .ok_or_else(|| StorageError::SavepointNotFound(name.to_string()))
+    }
+}
+
+#[async_trait]
+impl<S> StorageTransaction for BufferedTransaction<S>
+where
+    S: Storage<Error = StorageError>,
+{
+    type Error = StorageError;
+
+    async fn get(&self, key: &[u8]) -> Result<Option<Bytes>> {
+        if let Some(pending) = self.writes.get(key) {
+            return Ok(pending.as_deref().map(Bytes::copy_from_slice));
+        }
+
+        let value = self.storage.get(key).await?;
+        self.reads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.to_vec())
+            .or_insert_with(|| value.clone());
+        Ok(value)
+    }
+
+    async fn put(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.record(key, Some(value.to_vec()));
+        Ok(())
+    }
+
+    async fn delete(&mut self, key: &[u8]) -> Result<()> {
+        self.record(key, None);
+        Ok(())
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoints.push((name.to_string(), self.log.len()));
+        Ok(())
+    }
+
+    async fn rollback_to(&mut self, name: &str) -> Result<()> {
+        let index = self.find_savepoint(name)?;
+        let position = self.savepoints[index].1;
+
+        for undo in self.log.drain(position..).rev() {
+            match undo.previous {
+                Some(previous) => self.writes.insert(undo.key, previous),
+                None => self.writes.remove(&undo.key),
+            };
+        }
+        self.savepoints.truncate(index + 1);
+        Ok(())
+    }
+
+    async fn release(&mut self, name: &str) -> Result<()> {
+        let index = self.find_savepoint(name)?;
+        self.savepoints.truncate(index);
+        if self.savepoints.is_empty() {
+            self.log.clear();
+        }
+        Ok(())
+    }
+
+    async fn commit(self) -> Result<()> {
+        let reads = self.reads.into_inner().unwrap_or_else(PoisonError::into_inner);
+        let expected: Vec<_> = reads
+            .iter()
+            .map(|(key, value)| (key.as_slice(), value.as_deref()))
+            .collect();
+        let writes: Vec<_> = self
+            .writes
+            .iter()
+            .map(|(key, pending)| (key.as_slice(), pending.as_deref()))
+            .collect();
+
+        if !self.storage.compare_and_apply(&expected, &writes).await? {
+            return Err(StorageError::TransactionConflict);
+        }
+        self.storage.flush().await
+    }
+
+    async fn rollback(self) -> Result<()> {
+        Ok(())
+    }
+}
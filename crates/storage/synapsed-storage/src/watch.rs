@@ -0,0 +1,121 @@
+//! Change feeds backing [`WatchableStorage`]
+//!
+//! A backend publishes a [`WatchEvent`] to its [`ChangeFeed`] for every
+//! mutation while still holding the lock that serializes writes, so watchers
+//! see the changes to any key in the order they were applied. A watcher that
+//! falls too far behind gets a [`StorageError::WatchLagged`] error and is then
+//! closed, since the events it missed can't be replayed.
+//!
+//! [`WatchableStorage`]: crate::traits::WatchableStorage
+
+use crate::error::{Result, StorageError};
+use crate::traits::{StorageWatcher, WatchEvent};
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Default number of events buffered for slow watchers
+pub const DEFAULT_WATCH_CAPACITY: usize = 1024;
+
+/// Publisher side of a backend's change feed
+#[derive(Debug, Clone)]
+pub struct ChangeFeed {
+    sender: broadcast::Sender<WatchEvent>,
+}
+
+impl ChangeFeed {
+    /// Create a feed buffering up to `capacity` events per watcher
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Publish a change
+    ///
+    /// Must be called while holding the backend's write lock, so that events
+    /// are published in the order the changes were applied.
+    pub fn publish(&self, event: impl FnOnce() -> WatchEvent) {
+        // Skip building the event when nobody is watching
+        if self.sender.receiver_count() > 0 {
+            let _ = self.sender.send(event());
+        }
+    }
+
+    /// Watch changes to exactly `key`
+    pub fn watch(&self, key: &[u8]) -> ChangeWatcher {
+        ChangeWatcher::new(self.sender.subscribe(), Filter::Key(Bytes::copy_from_slice(key)))
+    }
+
+    /// Watch changes to keys starting with `prefix`
+    pub fn watch_prefix(&self, prefix: &[u8]) -> ChangeWatcher {
+        ChangeWatcher::new(self.sender.subscribe(), Filter::Prefix(Bytes::copy_from_slice(prefix)))
+    }
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        Self::new(DEFAULT_WATCH_CAPACITY)
+    }
+}
+
+/// Keys a watcher is interested in
+#[derive(Debug)]
+enum Filter {
+    Key(Bytes),
+    Prefix(Bytes),
+}
+
+impl Filter {
+    fn matches(&self, key: &[u8]) -> bool {
+        match self {
+            Filter::Key(watched) => key == watched.as_ref(),
+            Filter::Prefix(prefix) => key.starts_with(prefix),
+        }
+    }
+}
+
+/// Watcher receiving events from a [`ChangeFeed`]
+#[derive(Debug)]
+pub struct ChangeWatcher {
+    /// `None` once the watcher lagged or the feed was dropped
+    receiver: Option<broadcast::Receiver<WatchEvent>>,
+    filter: Filter,
+}
+
+impl ChangeWatcher {
+    fn new(receiver: broadcast::Receiver<WatchEvent>, filter: Filter) -> Self {
+        Self {
+            receiver: Some(receiver),
+            filter,
+        }
+    }
+}
+
+#[async_trait]
+impl StorageWatcher for ChangeWatcher {
+    type Error = StorageError;
+
+    async fn next_event(&mut self) -> Result<Option<WatchEvent>> {
+        let Some(receiver) = self.receiver.as_mut() else {
+            return Ok(None);
+        };
+        loop {
+            match receiver.recv().await {
+                Ok(event) if self.filter.matches(event.key()) => return Ok(Some(event)),
+                Ok(_) => {}
+                Err(RecvError::Lagged(missed)) => {
+                    self.receiver = None;
+                    return Err(StorageError::WatchLagged(missed));
+                }
+                Err(RecvError::Closed) => {
+                    self.receiver = None;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    async fn cancel(self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
 //! Integration tests for memory backend
 
 use synapsed_storage::backends::memory::MemoryStorage;
-use synapsed_storage::traits::Storage;
+use synapsed_storage::traits::{
+    Storage, StorageTransaction, StorageWatcher, TransactionalStorage, WatchEvent, WatchableStorage,
+};
 use synapsed_storage::config::MemoryConfig;
+use synapsed_storage::{BufferedTransaction, StorageError};
+use async_trait::async_trait;
 use bytes::Bytes;
 
 #[tokio::test]
@@ -150,4 +154,202 @@ async fn test_memory_backend_special_characters_in_keys() {
         let retrieved = storage.get(key).await.unwrap();
         assert_eq!(retrieved, Some(Bytes::copy_from_slice(value)));
     }
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_memory_backend_savepoint_rollback() {
+    let storage = MemoryStorage::default();
+    storage.put(b"existing", b"original").await.unwrap();
+
+    let mut tx = storage.begin_transaction().await.unwrap();
+    tx.put(b"before", b"kept").await.unwrap();
+    tx.savepoint("speculative").await.unwrap();
+    tx.put(b"after", b"discarded").await.unwrap();
+    tx.put(b"before", b"overwritten").await.unwrap();
+    tx.delete(b"existing").await.unwrap();
+
+    tx.savepoint("inner").await.unwrap();
+    tx.put(b"inner", b"discarded").await.unwrap();
+
+    tx.rollback_to("speculative").await.unwrap();
+    assert_eq!(tx.get(b"before").await.unwrap(), Some(Bytes::from("kept")));
+    assert_eq!(tx.get(b"existing").await.unwrap(), Some(Bytes::from("original")));
+    assert!(tx.get(b"after").await.unwrap().is_none());
+
+    // Later savepoints are gone, the rolled-back one can be reused
+    assert!(tx.rollback_to("inner").await.is_err());
+    tx.put(b"retry", b"kept").await.unwrap();
+    tx.release("speculative").await.unwrap();
+    assert!(tx.rollback_to("speculative").await.is_err());
+
+    // Nothing reaches the backend before commit
+    assert!(storage.get(b"before").await.unwrap().is_none());
+    tx.commit().await.unwrap();
+
+    assert_eq!(storage.get(b"before").await.unwrap(), Some(Bytes::from("kept")));
+    assert_eq!(storage.get(b"retry").await.unwrap(), Some(Bytes::from("kept")));
+    assert_eq!(storage.get(b"existing").await.unwrap(), Some(Bytes::from("original")));
+    assert!(storage.get(b"after").await.unwrap().is_none());
+    assert!(storage.get(b"inner").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_memory_backend_transaction_conflict() {
+    let storage = MemoryStorage::default();
+    storage.put(b"balance", b"100").await.unwrap();
+
+    let mut tx = storage.begin_transaction().await.unwrap();
+    assert_eq!(tx.get(b"balance").await.unwrap(), Some(Bytes::from("100")));
+    tx.put(b"balance", b"90").await.unwrap();
+
+    // Another writer changes the value the transaction based its write on
+    storage.put(b"balance", b"50").await.unwrap();
+
+    assert!(matches!(tx.commit().await, Err(StorageError::TransactionConflict)));
+    assert_eq!(storage.get(b"balance").await.unwrap(), Some(Bytes::from("50")));
+}
+
+#[tokio::test]
+async fn test_memory_backend_commit_storage_full() {
+    let storage = MemoryStorage::new(MemoryConfig {
+        initial_capacity: 16,
+        max_memory_bytes: 32,
+    });
+    storage.put(b"existing", b"original").await.unwrap();
+
+    let mut tx = storage.begin_transaction().await.unwrap();
+    tx.delete(b"existing").await.unwrap();
+    tx.put(b"first", b"fits").await.unwrap();
+    tx.put(b"second", &[0; 64]).await.unwrap();
+
+    assert!(matches!(tx.commit().await, Err(StorageError::StorageFull)));
+    assert_eq!(storage.get(b"existing").await.unwrap(), Some(Bytes::from("original")));
+    assert!(storage.get(b"first").await.unwrap().is_none());
+    assert!(storage.get(b"second").await.unwrap().is_none());
+}
+
+/// Backend without its own `compare_and_apply` that fills up on one key
+struct FullOn {
+    inner: MemoryStorage,
+    key: &'static [u8],
+}
+
+#[async_trait]
+impl Storage for FullOn {
+    type Error = StorageError;
+
+    async fn get(&self, key: &[u8]) -> Result<Option<Bytes>, StorageError> {
+        self.inner.get(key).await
+    }
+
+    async fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        if key == self.key {
+            return Err(StorageError::StorageFull);
+        }
+        self.inner.put(key, value).await
+    }
+
+    async fn delete(&self, key: &[u8]) -> Result<(), StorageError> {
+        self.inner.delete(key).await
+    }
+
+    async fn compare_and_swap(
+        &self,
+        key: &[u8],
+        expected: Option<&[u8]>,
+        new: &[u8],
+    ) -> Result<bool, StorageError> {
+        self.inner.compare_and_swap(key, expected, new).await
+    }
+
+    async fn list(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>, StorageError> {
+        self.inner.list(prefix).await
+    }
+}
+
+#[tokio::test]
+async fn test_transaction_undoes_writes_when_commit_fails_midway() {
+    let inner = MemoryStorage::default();
+    inner.put(b"a-existing", b"original").await.unwrap();
+    inner.put(b"b-updated", b"original").await.unwrap();
+    let storage = FullOn { inner: inner.clone(), key: b"c-full" };
+
+    // Keys commit in order, so the first two are written before the failure
+    let mut tx = BufferedTransaction::new(storage);
+    tx.delete(b"a-existing").await.unwrap();
+    tx.put(b"b-updated", b"changed").await.unwrap();
+    tx.put(b"c-full", b"rejected").await.unwrap();
+
+    assert!(matches!(tx.commit().await, Err(StorageError::StorageFull)));
+    assert_eq!(inner.get(b"a-existing").await.unwrap(), Some(Bytes::from("original")));
+    assert_eq!(inner.get(b"b-updated").await.unwrap(), Some(Bytes::from("original")));
+    assert!(inner.get(b"c-full").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_memory_backend_concurrent_compare_and_swap() {
+    use std::sync::Arc;
+    use tokio::task;
+    
+    let storage = Arc::new(MemoryStorage::new(MemoryConfig {
+        initial_capacity: 1024,
+        max_memory_bytes: 0,
+    }));
+    storage.put(b"counter", b"0").await.unwrap();
+    
+    // Both tasks read version "0" and race to advance it
+    let handles: Vec<_> = [b"a", b"b"]
+        .into_iter()
+        .map(|writer| {
+            let storage = Arc::clone(&storage);
+            task::spawn(async move {
+                storage.compare_and_swap(b"counter", Some(b"0"), writer).await.unwrap()
+            })
+        })
+        .collect();
+    
+    let mut swapped = 0;
+    for handle in handles {
+        if handle.await.unwrap() {
+            swapped += 1;
+        }
+    }
+    assert_eq!(swapped, 1);
+    
+    let value = storage.get(b"counter").await.unwrap().unwrap();
+    assert!(value == Bytes::from_static(b"a") || value == Bytes::from_static(b"b"));
+    
+    // Absent expectation only succeeds for a missing key
+    assert!(!storage.compare_and_swap(b"counter", None, b"c").await.unwrap());
+    assert!(storage.compare_and_swap(b"fresh", None, b"c").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_memory_backend_watch_prefix() {
+    use futures::StreamExt;
+    
+    let storage = MemoryStorage::default();
+    storage.put(b"user:1", b"before").await.unwrap();
+    let mut changes = storage.watch_prefix(b"user:").await.unwrap().into_stream();
+    
+    storage.put(b"user:1", b"alice").await.unwrap();
+    storage.put(b"order:1", b"ignored").await.unwrap();
+    storage.put(b"user:2", b"bob").await.unwrap();
+    storage.delete(b"user:1").await.unwrap();
+    storage.delete(b"user:missing").await.unwrap();
+    storage.put(b"user:2", b"robert").await.unwrap();
+    
+    let expected = vec![
+        WatchEvent::Update { key: Bytes::from("user:1"), value: Bytes::from("alice") },
+        WatchEvent::Insert { key: Bytes::from("user:2"), value: Bytes::from("bob") },
+        WatchEvent::Delete { key: Bytes::from("user:1") },
+        WatchEvent::Update { key: Bytes::from("user:2"), value: Bytes::from("robert") },
+    ];
+    for event in expected {
+        assert_eq!(changes.next().await.unwrap().unwrap(), event);
+    }
+    
+    // Dropping the storage closes the feed and ends the stream
+    drop(storage);
+    assert!(changes.next().await.is_none());
+}
@@ -2,7 +2,10 @@
 
 use crate::{
     registry::{Component, ComponentRegistry, Capability},
-    recipe::{Recipe, Connection, ConnectionPoint, Transform, ConnectionProperties},
+    recipe::{
+        Recipe, ComponentSpec, Connection, ConnectionPoint, Transform, ConnectionProperties,
+        RecipeStep, StepType, Validation, ValidationCheck,
+    },
     composer::Composer,
     validator::Validator,
     Result, BuilderError,
@@ -247,6 +250,81 @@ impl SynapsedBuilder {
         builder
     }
     
+    /// Describe the composition as a recipe
+    ///
+    /// Components are initialized after the components they depend on, then
+    /// connected and started.
+    pub fn recipe(&self, category: impl Into<String>, tags: Vec<String>) -> Recipe {
+        let components: Vec<ComponentSpec> = self.components
+            .iter()
+            .map(|name| ComponentSpec {
+                name: name.clone(),
+                version: self.registry.get(name).map(|c| format!("^{}", c.version)),
+                features: vec![],
+                optional: false,
+                alias: None,
+            })
+            .collect();
+        
+        let mut steps: Vec<RecipeStep> = self.components
+            .iter()
+            .map(|name| RecipeStep {
+                name: format!("init-{}", name),
+                step_type: StepType::Initialize,
+                params: json!({ "component": name }),
+                depends_on: self.registry
+                    .get(name)
+                    .map(|c| c.dependencies
+                        .iter()
+                        .filter(|dep| self.components.contains(dep))
+                        .map(|dep| format!("init-{}", dep))
+                        .collect())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let initialized: Vec<String> = steps.iter().map(|s| s.name.clone()).collect();
+        steps.push(RecipeStep {
+            name: "connect".to_string(),
+            step_type: StepType::Connect,
+            params: json!({ "connections": self.connections.len() }),
+            depends_on: initialized,
+        });
+        steps.push(RecipeStep {
+            name: "start".to_string(),
+            step_type: StepType::Start,
+            params: json!({}),
+            depends_on: vec!["connect".to_string()],
+        });
+        
+        let mut validations: Vec<Validation> = self.components
+            .iter()
+            .map(|name| Validation {
+                name: format!("{}-exists", name),
+                check: ValidationCheck::ComponentExists(name.clone()),
+                critical: true,
+            })
+            .collect();
+        validations.push(Validation {
+            name: "resources".to_string(),
+            check: ValidationCheck::ResourcesAvailable,
+            critical: false,
+        });
+        
+        Recipe {
+            name: self.config.name.clone(),
+            version: self.config.version.clone(),
+            description: self.config.description.clone(),
+            category: category.into(),
+            components,
+            connections: self.connections.clone(),
+            configurations: self.configurations.clone(),
+            environment: self.environment.clone(),
+            steps,
+            validations,
+            tags,
+        }
+    }
+    
     /// Disable validations (for testing)
     pub fn skip_validations(mut self) -> Self {
         self.validations_enabled = false;
@@ -9,15 +9,17 @@ pub mod builder;
 pub mod composer;
 pub mod validator;
 pub mod templates;
+pub mod migration;
 
 #[cfg(test)]
 mod tests;
 
 pub use registry::{ComponentRegistry, Component, Capability};
-pub use recipe::{Recipe, RecipeStep, Connection};
+pub use recipe::{Recipe, RecipeDiff, RecipeStep, Connection};
 pub use builder::{SynapsedBuilder, BuilderConfig};
 pub use composer::{Composer, CompositionResult};
 pub use validator::{Validator, ValidationResult};
+pub use migration::{migrate, MigrationFix, MigrationIssue, MigrationResult};
 
 /// Prelude for convenient imports
 pub mod prelude {
@@ -0,0 +1,216 @@
+//! Migrating recipes as the component registry evolves
+
+use crate::{
+    registry::{Capability, Component, ComponentRegistry, Interface},
+    recipe::{ComponentSpec, Connection, ConnectionPoint, ConnectionProperties, Recipe, RecipeDiff},
+};
+use thiserror::Error;
+
+/// A change made to bring a recipe in line with the registry
+#[derive(Debug, Clone)]
+pub enum MigrationFix {
+    /// A component the recipe now needs
+    AddComponent {
+        component: String,
+        reason: String,
+    },
+
+    /// Wiring from a component to the provider of a capability it now requires
+    AddConnection {
+        connection: Connection,
+        reason: String,
+    },
+}
+
+/// A change that can't be migrated automatically
+#[derive(Debug, Clone, Error)]
+pub enum MigrationIssue {
+    #[error("Component '{0}' is no longer in the registry")]
+    UnknownComponent(String),
+
+    #[error("Component '{component}' now depends on '{dependency}', which is not in the registry")]
+    MissingDependency { component: String, dependency: String },
+
+    #[error("Component '{component}' now requires {capability:?}, which no registered component provides")]
+    NoProvider { component: String, capability: Capability },
+
+    #[error("Component '{component}' no longer has port '{port}'")]
+    MissingPort { component: String, port: String },
+}
+
+/// Outcome of migrating a recipe
+#[derive(Debug, Clone)]
+pub struct MigrationResult {
+    /// The recipe with every fix applied
+    pub recipe: Recipe,
+
+    /// Fixes applied to `recipe`, to review or apply elsewhere
+    pub fixes: Vec<MigrationFix>,
+
+    /// Changes that need manual attention
+    pub issues: Vec<MigrationIssue>,
+}
+
+impl MigrationResult {
+    /// Whether the recipe was out of date
+    pub fn needs_migration(&self) -> bool {
+        !self.fixes.is_empty() || !self.issues.is_empty()
+    }
+
+    /// Whether every change could be migrated
+    pub fn is_complete(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// What the fixes change in the original recipe
+    pub fn diff(&self, original: &Recipe) -> RecipeDiff {
+        original.diff(&self.recipe)
+    }
+}
+
+/// Check a recipe against the current registry, fixing what can be fixed
+///
+/// Missing dependencies are added, and a component whose required
+/// capabilities are no longer provided within the recipe gets the provider
+/// added and wired to it. The original recipe is left untouched.
+pub fn migrate(recipe: &Recipe, registry: &ComponentRegistry) -> MigrationResult {
+    let mut result = MigrationResult {
+        recipe: recipe.clone(),
+        fixes: Vec::new(),
+        issues: Vec::new(),
+    };
+
+    // Components added by fixes are checked too
+    let mut index = 0;
+    while index < result.recipe.components.len() {
+        let name = result.recipe.components[index].name.clone();
+        index += 1;
+
+        let Some(component) = registry.get(&name) else {
+            result.issues.push(MigrationIssue::UnknownComponent(name));
+            continue;
+        };
+
+        for dependency in &component.dependencies {
+            if includes(&result.recipe, dependency) {
+                continue;
+            }
+            if let Some(added) = registry.get(dependency) {
+                add_component(&mut result, added, format!("'{}' depends on it", name));
+            } else {
+                result.issues.push(MigrationIssue::MissingDependency {
+                    component: name.clone(),
+                    dependency: dependency.clone(),
+                });
+            }
+        }
+
+        let mut requires: Vec<&Capability> = component.requires.iter().collect();
+        requires.sort_by_key(|capability| format!("{:?}", capability));
+        for capability in requires {
+            if provides(&result.recipe, registry, capability) {
+                continue;
+            }
+            let mut providers = registry.find_by_capability(capability);
+            providers.sort_by(|a, b| a.name.cmp(&b.name));
+            let Some(provider) = providers.first() else {
+                result.issues.push(MigrationIssue::NoProvider {
+                    component: name.clone(),
+                    capability: capability.clone(),
+                });
+                continue;
+            };
+
+            let reason = format!("'{}' requires {:?}", name, capability);
+            add_component(&mut result, provider, reason.clone());
+            let connection = Connection {
+                from: ConnectionPoint {
+                    component: name.clone(),
+                    port: first_port(component, |iface| &iface.events),
+                },
+                to: ConnectionPoint {
+                    component: provider.name.clone(),
+                    port: first_port(provider, |iface| &iface.methods),
+                },
+                transform: None,
+                properties: ConnectionProperties::default(),
+            };
+            result.recipe.connections.push(connection.clone());
+            result.fixes.push(MigrationFix::AddConnection { connection, reason });
+        }
+    }
+
+    // Ports can only be checked on components that declare interfaces
+    for connection in &recipe.connections {
+        for (point, outgoing) in [(&connection.from, true), (&connection.to, false)] {
+            if point.component == "*" || point.port == "*" {
+                continue;
+            }
+            let Some(component) = resolve(recipe, &point.component).and_then(|name| registry.get(name)) else {
+                continue;
+            };
+            if component.interfaces.is_empty() {
+                continue;
+            }
+            let has_port = component.interfaces.iter().any(|iface| {
+                iface.methods.contains(&point.port) || (outgoing && iface.events.contains(&point.port))
+            });
+            if !has_port {
+                result.issues.push(MigrationIssue::MissingPort {
+                    component: component.name.clone(),
+                    port: point.port.clone(),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+fn includes(recipe: &Recipe, name: &str) -> bool {
+    recipe.components.iter().any(|spec| spec.name == name)
+}
+
+/// Component name for a name or alias used in connections
+fn resolve<'a>(recipe: &'a Recipe, reference: &'a str) -> Option<&'a str> {
+    recipe.components
+        .iter()
+        .find(|spec| spec.name == reference || spec.alias.as_deref() == Some(reference))
+        .map(|spec| spec.name.as_str())
+}
+
+fn provides(recipe: &Recipe, registry: &ComponentRegistry, capability: &Capability) -> bool {
+    recipe.components
+        .iter()
+        .filter_map(|spec| registry.get(&spec.name))
+        .any(|component| component.provides.contains(capability))
+}
+
+fn add_component(result: &mut MigrationResult, component: &Component, reason: String) {
+    if includes(&result.recipe, &component.name) {
+        return;
+    }
+    result.recipe.components.push(ComponentSpec {
+        name: component.name.clone(),
+        version: Some(format!("^{}", component.version)),
+        features: vec![],
+        optional: false,
+        alias: None,
+    });
+    result.fixes.push(MigrationFix::AddComponent {
+        component: component.name.clone(),
+        reason,
+    });
+}
+
+fn first_port(
+    component: &Component,
+    ports: impl Fn(&Interface) -> &Vec<String>,
+) -> String {
+    component.interfaces
+        .iter()
+        .flat_map(|iface| ports(iface).iter())
+        .next()
+        .cloned()
+        .unwrap_or_else(|| "*".to_string())
+}
@@ -205,6 +205,99 @@ pub enum ValidationCheck {
     Custom(String),
 }
 
+impl Recipe {
+    /// Compare this recipe with a newer version of it
+    ///
+    /// Changes are reported from `self` to `other`.
+    pub fn diff(&self, other: &Recipe) -> RecipeDiff {
+        let mut diff = RecipeDiff::default();
+        
+        for spec in &other.components {
+            match self.components.iter().find(|c| c.name == spec.name) {
+                None => diff.added_components.push(spec.name.clone()),
+                Some(old) if old.version != spec.version || old.features != spec.features => {
+                    diff.changed_components.push(spec.name.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed_components = self.components
+            .iter()
+            .filter(|c| !other.components.iter().any(|spec| spec.name == c.name))
+            .map(|c| c.name.clone())
+            .collect();
+        
+        diff.added_connections = other.connections
+            .iter()
+            .filter(|c| !self.connections.iter().any(|old| old.same_endpoints(c)))
+            .cloned()
+            .collect();
+        diff.removed_connections = self.connections
+            .iter()
+            .filter(|c| !other.connections.iter().any(|new| new.same_endpoints(c)))
+            .cloned()
+            .collect();
+        
+        let mut configured: Vec<&String> = self.configurations
+            .keys()
+            .chain(other.configurations.keys())
+            .collect();
+        configured.sort();
+        configured.dedup();
+        diff.changed_configurations = configured
+            .into_iter()
+            .filter(|name| self.configurations.get(*name) != other.configurations.get(*name))
+            .cloned()
+            .collect();
+        
+        diff
+    }
+}
+
+impl Connection {
+    /// Whether both connections join the same ports
+    pub fn same_endpoints(&self, other: &Connection) -> bool {
+        self.from.component == other.from.component
+            && self.from.port == other.from.port
+            && self.to.component == other.to.component
+            && self.to.port == other.to.port
+    }
+}
+
+/// Differences between two versions of a recipe
+#[derive(Debug, Clone, Default)]
+pub struct RecipeDiff {
+    /// Components only in the newer recipe
+    pub added_components: Vec<String>,
+    
+    /// Components only in the older recipe
+    pub removed_components: Vec<String>,
+    
+    /// Components whose version constraint or features changed
+    pub changed_components: Vec<String>,
+    
+    /// Connections only in the newer recipe
+    pub added_connections: Vec<Connection>,
+    
+    /// Connections only in the older recipe
+    pub removed_connections: Vec<Connection>,
+    
+    /// Components whose configuration was added, removed or changed
+    pub changed_configurations: Vec<String>,
+}
+
+impl RecipeDiff {
+    /// Whether the recipes compose the same application
+    pub fn is_empty(&self) -> bool {
+        self.added_components.is_empty()
+            && self.removed_components.is_empty()
+            && self.changed_components.is_empty()
+            && self.added_connections.is_empty()
+            && self.removed_connections.is_empty()
+            && self.changed_configurations.is_empty()
+    }
+}
+
 /// Recipe loader and manager
 pub struct RecipeManager {
     recipes: HashMap<String, Recipe>,
@@ -182,6 +182,55 @@ impl ComponentRegistry {
             .collect()
     }
     
+    /// Components that provide a capability, most relevant first
+    ///
+    /// Components dedicated to the capability rank above those that provide
+    /// many others, then those with fewer requirements of their own.
+    pub fn providers_of(&self, capability: &Capability) -> Vec<Component> {
+        let mut providers: Vec<&Component> = self.find_by_capability(capability);
+        providers.sort_by(|a, b| {
+            a.provides.len().cmp(&b.provides.len())
+                .then(a.requires.len().cmp(&b.requires.len()))
+                .then(a.dependencies.len().cmp(&b.dependencies.len()))
+                .then(a.name.cmp(&b.name))
+        });
+        providers.into_iter().cloned().collect()
+    }
+    
+    /// Components that require a capability, most relevant first
+    ///
+    /// Components with fewer other requirements rank higher.
+    pub fn consumers_of(&self, capability: &Capability) -> Vec<Component> {
+        let mut consumers: Vec<&Component> = self.components
+            .values()
+            .filter(|c| c.requires.contains(capability))
+            .collect();
+        consumers.sort_by(|a, b| {
+            a.requires.len().cmp(&b.requires.len())
+                .then(a.name.cmp(&b.name))
+        });
+        consumers.into_iter().cloned().collect()
+    }
+    
+    /// Fuzzy search by name and description, best matches first
+    ///
+    /// Exact and substring name matches rank above description matches, and
+    /// names containing the query's letters in order still match.
+    pub fn search(&self, query: &str) -> Vec<Component> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        
+        let mut scored: Vec<(u32, &Component)> = self.components
+            .values()
+            .map(|c| (search_score(c, &query), c))
+            .filter(|(score, _)| *score > 0)
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.name.cmp(&b.1.name)));
+        scored.into_iter().map(|(_, c)| c.clone()).collect()
+    }
+    
     /// Get a component by name
     pub fn get(&self, name: &str) -> Option<&Component> {
         self.components.get(name)
@@ -383,10 +432,248 @@ impl ComponentRegistry {
             },
         }).unwrap();
         
+        // Cryptography
+        self.register(Component {
+            name: "synapsed-crypto".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Post-quantum cryptography".to_string(),
+            category: ComponentCategory::Core,
+            provides: hashset![Capability::Cryptography, Capability::Encryption],
+            requires: HashSet::new(),
+            dependencies: vec![],
+            interfaces: vec![
+                Interface {
+                    name: "Kem".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["encapsulate".to_string(), "decapsulate".to_string(), "sign".to_string()],
+                    events: vec![],
+                }
+            ],
+            config_schema: None,
+            observable: true,
+            resources: ResourceRequirements::default(),
+        }).unwrap();
+        
+        // Identity
+        self.register(Component {
+            name: "synapsed-identity".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Identity and access management".to_string(),
+            category: ComponentCategory::Security,
+            provides: hashset![Capability::Authentication, Capability::Authorization],
+            requires: hashset![Capability::Cryptography],
+            dependencies: vec!["synapsed-core".to_string(), "synapsed-crypto".to_string()],
+            interfaces: vec![
+                Interface {
+                    name: "Identity".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["authenticate".to_string(), "authorize".to_string()],
+                    events: vec!["identity.authenticated".to_string()],
+                }
+            ],
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "auth_providers": {"type": "array", "items": {"type": "string"}},
+                    "session_timeout": {"type": "integer"}
+                }
+            })),
+            observable: true,
+            resources: ResourceRequirements::default(),
+        }).unwrap();
+        
+        // Payments
+        self.register(Component {
+            name: "synapsed-payments".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Payment processing with risk assessment".to_string(),
+            category: ComponentCategory::Application,
+            provides: hashset![Capability::PaymentProcessing],
+            requires: hashset![Capability::Authentication, Capability::Storage],
+            dependencies: vec![
+                "synapsed-core".to_string(),
+                "synapsed-identity".to_string(),
+                "synapsed-crypto".to_string(),
+            ],
+            interfaces: vec![
+                Interface {
+                    name: "PaymentProcessor".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["process".to_string(), "refund".to_string()],
+                    events: vec![
+                        "payment.processed".to_string(),
+                        "payment.flagged".to_string(),
+                    ],
+                }
+            ],
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "supported_currencies": {"type": "array", "items": {"type": "string"}},
+                    "risk_threshold": {"type": "integer", "minimum": 0, "maximum": 100}
+                }
+            })),
+            observable: true,
+            resources: ResourceRequirements {
+                requires_network: true,
+                ..Default::default()
+            },
+        }).unwrap();
+        
+        // Anonymous routing
+        self.register(Component {
+            name: "synapsed-routing".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Anonymous onion routing".to_string(),
+            category: ComponentCategory::Network,
+            provides: hashset![Capability::Routing],
+            requires: hashset![Capability::Cryptography],
+            dependencies: vec!["synapsed-crypto".to_string()],
+            interfaces: vec![
+                Interface {
+                    name: "OnionRouter".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["send_anonymous".to_string(), "reply".to_string()],
+                    events: vec!["message.received".to_string(), "cell.outbound".to_string()],
+                }
+            ],
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "hop_count": {"type": "integer"},
+                    "circuit_lifetime": {"type": "integer"},
+                    "use_cover_traffic": {"type": "boolean"}
+                }
+            })),
+            observable: true,
+            resources: ResourceRequirements {
+                requires_network: true,
+                ..Default::default()
+            },
+        }).unwrap();
+        
+        // Agent swarm
+        self.register(Component {
+            name: "synapsed-swarm".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Verified multi-agent coordination".to_string(),
+            category: ComponentCategory::Intent,
+            provides: hashset![Capability::Custom("agent-swarm".to_string())],
+            requires: hashset![Capability::IntentVerification],
+            dependencies: vec![
+                "synapsed-core".to_string(),
+                "synapsed-intent".to_string(),
+                "synapsed-verify".to_string(),
+            ],
+            interfaces: vec![
+                Interface {
+                    name: "SwarmCoordinator".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["add_agent".to_string(), "assign_task".to_string()],
+                    events: vec!["task.assigned".to_string(), "claim.submitted".to_string()],
+                }
+            ],
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "max_agents": {"type": "integer"},
+                    "min_trust": {"type": "number"}
+                }
+            })),
+            observable: true,
+            resources: ResourceRequirements::default(),
+        }).unwrap();
+        
+        // Service signals
+        self.register(Component {
+            name: "synapsed-serventis".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Service-level signals and probes".to_string(),
+            category: ComponentCategory::Observability,
+            provides: hashset![Capability::Custom("service-signals".to_string())],
+            requires: hashset![Capability::Observability],
+            dependencies: vec!["synapsed-core".to_string(), "synapsed-substrates".to_string()],
+            interfaces: vec![
+                Interface {
+                    name: "Services".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["signal".to_string()],
+                    events: vec!["service.signal".to_string(), "probe.observed".to_string()],
+                }
+            ],
+            config_schema: None,
+            observable: false,
+            resources: ResourceRequirements::default(),
+        }).unwrap();
+        
+        // Monitoring
+        self.register(Component {
+            name: "synapsed-monitor".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Dashboards and alerting".to_string(),
+            category: ComponentCategory::Observability,
+            provides: hashset![Capability::Monitoring],
+            requires: hashset![Capability::Observability],
+            dependencies: vec!["synapsed-core".to_string(), "synapsed-substrates".to_string()],
+            interfaces: vec![
+                Interface {
+                    name: "Monitor".to_string(),
+                    version: "1.0".to_string(),
+                    methods: vec!["ingest".to_string()],
+                    events: vec!["alert.raised".to_string()],
+                }
+            ],
+            config_schema: Some(json!({
+                "type": "object",
+                "properties": {
+                    "dashboard_port": {"type": "integer"},
+                    "metrics_port": {"type": "integer"}
+                }
+            })),
+            observable: false,
+            resources: ResourceRequirements {
+                requires_network: true,
+                ..Default::default()
+            },
+        }).unwrap();
+        
         // Add more default components as needed...
     }
 }
 
+/// Relevance of a component to a lowercase search query
+fn search_score(component: &Component, query: &str) -> u32 {
+    let name = component.name.to_lowercase();
+    let short_name = name.strip_prefix("synapsed-").unwrap_or(&name);
+    let description = component.description.to_lowercase();
+    
+    let mut score = if name == query || short_name == query {
+        100
+    } else if short_name.starts_with(query) {
+        60
+    } else if name.contains(query) {
+        40
+    } else if is_subsequence(query, short_name) {
+        10
+    } else {
+        0
+    };
+    
+    // Each query word found in the description
+    score += query
+        .split_whitespace()
+        .filter(|word| description.contains(word))
+        .count() as u32 * 15;
+    
+    score
+}
+
+/// Whether all characters of `needle` appear in `haystack` in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().filter(|c| !c.is_whitespace()).all(|c| chars.any(|h| h == c))
+}
+
 // Helper macro for creating HashSets
 macro_rules! hashset {
     ($($val:expr),*) => {
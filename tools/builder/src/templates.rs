@@ -2,6 +2,7 @@
 
 use crate::{
     builder::{SynapsedBuilder, StorageBackend, ObservabilityLevel, NetworkType},
+    recipe::Recipe,
     Result,
 };
 
@@ -168,6 +169,105 @@ impl Templates {
             .add_storage(StorageBackend::Memory)
     }
     
+    /// Swarm of agents whose claims are verified before they're trusted
+    pub fn verifiable_agent_swarm() -> SynapsedBuilder {
+        SynapsedBuilder::new("verifiable-agent-swarm")
+            .description("Agent swarm with verified task claims and trust tracking")
+            .add_component("synapsed-core")
+            .add_component("synapsed-intent")
+            .add_component("synapsed-verify")
+            .add_component("synapsed-swarm")
+            .add_storage(StorageBackend::Sqlite)
+            .add_observability(ObservabilityLevel::Basic)
+            .connect("synapsed-swarm", "task.assigned", "synapsed-intent", "build")
+            .connect("synapsed-swarm", "claim.submitted", "synapsed-verify", "verify")
+            .connect("synapsed-intent", "intent.executed", "synapsed-storage", "put")
+            .configure("synapsed-swarm", serde_json::json!({
+                "max_agents": 16,
+                "min_trust": 0.5,
+                "require_verification": true
+            }))
+            .configure("synapsed-intent", serde_json::json!({
+                "max_depth": 5,
+                "timeout_ms": 30000
+            }))
+    }
+    
+    /// Anonymous peer-to-peer messaging over onion circuits
+    pub fn anonymous_messaging() -> SynapsedBuilder {
+        SynapsedBuilder::new("anonymous-p2p-messaging")
+            .description("Peer-to-peer messaging over onion-routed circuits")
+            .add_component("synapsed-core")
+            .add_component("synapsed-crypto")
+            .add_network(NetworkType::P2P)
+            .add_storage(StorageBackend::Memory)
+            .add_observability(ObservabilityLevel::Basic)
+            .connect("synapsed-routing", "cell.outbound", "synapsed-net", "send")
+            .connect("synapsed-routing", "message.received", "synapsed-storage", "put")
+            .configure("synapsed-routing", serde_json::json!({
+                "hop_count": 3,
+                "circuit_lifetime": 600,
+                "use_cover_traffic": true
+            }))
+            .configure("synapsed-net", serde_json::json!({
+                "listen_addr": "/ip4/0.0.0.0/tcp/0",
+                "bootstrap_nodes": []
+            }))
+    }
+    
+    /// Payment service that scores every payment before processing it
+    pub fn payment_risk_service() -> SynapsedBuilder {
+        SynapsedBuilder::new("payment-risk-service")
+            .description("Payment service with risk scoring and audit storage")
+            .add_component("synapsed-core")
+            .add_payments()
+            .add_storage(StorageBackend::RocksDb)
+            .add_observability(ObservabilityLevel::Full)
+            .connect("synapsed-payments", "payment.flagged", "synapsed-storage", "put")
+            .connect("synapsed-payments", "payment.processed", "synapsed-identity", "authorize")
+            .configure("synapsed-payments", serde_json::json!({
+                "supported_currencies": ["USD", "EUR"],
+                "risk_threshold": 70,
+                "risk_engine": "basic"
+            }))
+            .configure("synapsed-identity", serde_json::json!({
+                "auth_providers": ["webauthn"],
+                "session_timeout": 900
+            }))
+    }
+    
+    /// Event capture, service signals, dashboards and alerting
+    pub fn observability_stack() -> SynapsedBuilder {
+        SynapsedBuilder::new("observability-stack")
+            .description("Event capture, service signals, dashboards and alerting")
+            .add_component("synapsed-core")
+            .add_observability(ObservabilityLevel::Full)
+            .add_component("synapsed-serventis")
+            .add_storage(StorageBackend::Memory)
+            .connect("synapsed-serventis", "service.signal", "synapsed-monitor", "ingest")
+            .connect("synapsed-monitor", "alert.raised", "synapsed-storage", "put")
+            .configure("synapsed-substrates", serde_json::json!({
+                "buffer_size": 1000,
+                "sampling_rate": 1.0
+            }))
+            .configure("synapsed-monitor", serde_json::json!({
+                "dashboard_port": 8080,
+                "metrics_port": 9090
+            }))
+    }
+    
+    /// Names of the application archetype templates
+    ///
+    /// Each one validates against the default registry and composes.
+    pub fn archetypes() -> Vec<&'static str> {
+        vec![
+            "verifiable-agent-swarm",
+            "anonymous-p2p-messaging",
+            "payment-risk-service",
+            "observability-stack",
+        ]
+    }
+    
     /// List all available templates
     pub fn list() -> Vec<TemplateInfo> {
         vec![
@@ -274,6 +374,52 @@ impl Templates {
                 ],
                 tags: vec!["minimal".to_string(), "starter".to_string()],
             },
+            TemplateInfo {
+                name: "verifiable-agent-swarm".to_string(),
+                description: "Agent swarm with verified task claims and trust tracking".to_string(),
+                components: vec![
+                    "synapsed-swarm".to_string(),
+                    "synapsed-intent".to_string(),
+                    "synapsed-verify".to_string(),
+                    "synapsed-storage".to_string(),
+                    "synapsed-substrates".to_string(),
+                ],
+                tags: vec!["ai".to_string(), "verification".to_string(), "distributed".to_string()],
+            },
+            TemplateInfo {
+                name: "anonymous-p2p-messaging".to_string(),
+                description: "Peer-to-peer messaging over onion-routed circuits".to_string(),
+                components: vec![
+                    "synapsed-routing".to_string(),
+                    "synapsed-net".to_string(),
+                    "synapsed-crypto".to_string(),
+                    "synapsed-storage".to_string(),
+                ],
+                tags: vec!["p2p".to_string(), "privacy".to_string(), "messaging".to_string()],
+            },
+            TemplateInfo {
+                name: "payment-risk-service".to_string(),
+                description: "Payment service with risk scoring and audit storage".to_string(),
+                components: vec![
+                    "synapsed-payments".to_string(),
+                    "synapsed-identity".to_string(),
+                    "synapsed-crypto".to_string(),
+                    "synapsed-storage".to_string(),
+                    "synapsed-monitor".to_string(),
+                ],
+                tags: vec!["payments".to_string(), "security".to_string(), "risk".to_string()],
+            },
+            TemplateInfo {
+                name: "observability-stack".to_string(),
+                description: "Event capture, service signals, dashboards and alerting".to_string(),
+                components: vec![
+                    "synapsed-substrates".to_string(),
+                    "synapsed-serventis".to_string(),
+                    "synapsed-monitor".to_string(),
+                    "synapsed-storage".to_string(),
+                ],
+                tags: vec!["observability".to_string(), "monitoring".to_string()],
+            },
         ]
     }
     
@@ -290,9 +436,27 @@ impl Templates {
             "neural-pipeline" => Some(Self::neural_pipeline()),
             "dev-sandbox" => Some(Self::dev_sandbox()),
             "minimal" => Some(Self::minimal()),
+            "verifiable-agent-swarm" => Some(Self::verifiable_agent_swarm()),
+            "anonymous-p2p-messaging" => Some(Self::anonymous_messaging()),
+            "payment-risk-service" => Some(Self::payment_risk_service()),
+            "observability-stack" => Some(Self::observability_stack()),
             _ => None,
         }
     }
+    
+    /// Get template by name as a recipe
+    ///
+    /// The recipe's category is the template's first tag.
+    pub fn recipe(name: &str) -> Option<Recipe> {
+        let builder = Self::get(name)?;
+        let tags = Self::list()
+            .into_iter()
+            .find(|info| info.name == name)
+            .map(|info| info.tags)
+            .unwrap_or_default();
+        let category = tags.first().cloned().unwrap_or_else(|| "general".to_string());
+        Some(builder.recipe(category, tags))
+    }
 }
 
 /// Information about a template
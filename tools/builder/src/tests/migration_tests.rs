@@ -0,0 +1,108 @@
+//! Unit tests for recipe diffing and migration
+//!
+//! Intent: Test that recipes are brought up to date with the registry
+//! Verification: Changed requirements are fixed or reported
+
+use crate::migration::{migrate, MigrationFix, MigrationIssue};
+use crate::registry::{Capability, ComponentRegistry, Interface};
+use crate::templates::Templates;
+use crate::validator::Validator;
+use serde_json::json;
+
+/// Default registry with `synapsed-swarm` requiring an extra capability
+fn registry_with_swarm_requiring(capability: Capability) -> ComponentRegistry {
+    let mut registry = ComponentRegistry::with_defaults();
+    let mut swarm = registry.get("synapsed-swarm").unwrap().clone();
+    swarm.requires.insert(capability);
+    registry.register(swarm).unwrap();
+    registry
+}
+
+#[test]
+fn test_recipe_diff() {
+    let original = Templates::recipe("observability-stack").unwrap();
+    assert!(original.diff(&original).is_empty());
+
+    let mut updated = original.clone();
+    updated.components.retain(|c| c.name != "synapsed-serventis");
+    updated.connections.retain(|c| c.from.component != "synapsed-serventis");
+    updated.configurations.insert("synapsed-monitor".to_string(), json!({"dashboard_port": 3000}));
+
+    let diff = original.diff(&updated);
+    assert_eq!(diff.removed_components, vec!["synapsed-serventis".to_string()]);
+    assert!(diff.added_components.is_empty());
+    assert_eq!(diff.removed_connections.len(), 1);
+    assert_eq!(diff.changed_configurations, vec!["synapsed-monitor".to_string()]);
+}
+
+#[test]
+fn test_migrate_wires_newly_required_capability() {
+    let recipe = Templates::recipe("verifiable-agent-swarm").unwrap();
+    assert!(!migrate(&recipe, &ComponentRegistry::with_defaults()).needs_migration());
+
+    // The swarm now routes agent traffic anonymously
+    let registry = registry_with_swarm_requiring(Capability::Routing);
+    let result = migrate(&recipe, &registry);
+    assert!(result.needs_migration());
+    assert!(result.is_complete());
+
+    // Routing is added along with its own dependency, and wired to the swarm
+    let added: Vec<&str> = result.fixes.iter().filter_map(|fix| match fix {
+        MigrationFix::AddComponent { component, .. } => Some(component.as_str()),
+        _ => None,
+    }).collect();
+    assert_eq!(added, vec!["synapsed-routing", "synapsed-crypto"]);
+    let wiring = result.fixes.iter().find_map(|fix| match fix {
+        MigrationFix::AddConnection { connection, reason } => Some((connection, reason)),
+        _ => None,
+    }).unwrap();
+    assert_eq!(wiring.0.from.component, "synapsed-swarm");
+    assert_eq!(wiring.0.to.component, "synapsed-routing");
+    assert_eq!(wiring.0.to.port, "send_anonymous");
+    assert!(wiring.1.contains("Routing"));
+
+    let diff = result.diff(&recipe);
+    assert_eq!(diff.added_components.len(), 2);
+    assert_eq!(diff.added_connections.len(), 1);
+
+    // The migrated recipe is valid against the new registry
+    let components: Vec<String> = result.recipe.components.iter().map(|c| c.name.clone()).collect();
+    let report = Validator::new(&registry)
+        .validate_composition(&components, &result.recipe.connections)
+        .unwrap();
+    assert!(!report.has_errors(), "{:?}", report.errors);
+    assert!(!report.warnings.iter().any(|w| w.contains("Routing")));
+}
+
+#[test]
+fn test_migrate_reports_unmigratable_changes() {
+    let recipe = Templates::recipe("verifiable-agent-swarm").unwrap();
+
+    // Nothing provides the new requirement, and intent dropped a port the recipe uses
+    let mut registry = registry_with_swarm_requiring(Capability::ZeroKnowledge);
+    let mut intent = registry.get("synapsed-intent").unwrap().clone();
+    intent.interfaces = vec![Interface {
+        name: "IntentBuilder".to_string(),
+        version: "2.0".to_string(),
+        methods: vec!["declare".to_string()],
+        events: vec!["intent.executed".to_string()],
+    }];
+    registry.register(intent).unwrap();
+
+    let result = migrate(&recipe, &registry);
+    assert!(!result.is_complete());
+    assert!(result.fixes.is_empty());
+    assert!(result.issues.iter().any(|issue| matches!(
+        issue,
+        MigrationIssue::NoProvider { component, capability: Capability::ZeroKnowledge }
+            if component == "synapsed-swarm"
+    )));
+    assert!(result.issues.iter().any(|issue| matches!(
+        issue,
+        MigrationIssue::MissingPort { component, port }
+            if component == "synapsed-intent" && port == "build"
+    )));
+
+    let messages: Vec<String> = result.issues.iter().map(|issue| issue.to_string()).collect();
+    assert!(messages.iter().any(|m| m.contains("ZeroKnowledge") && m.contains("no registered component")));
+}
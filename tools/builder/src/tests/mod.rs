@@ -23,4 +23,7 @@ mod composer_tests;
 mod validator_tests;
 
 #[cfg(test)]
-mod template_tests;
\ No newline at end of file
+mod template_tests;
+
+#[cfg(test)]
+mod migration_tests;
@@ -313,4 +313,67 @@ macro_rules! hashset {
     };
 }
 
-use hashset;
\ No newline at end of file
+use hashset;
+#[test]
+fn test_providers_of_overlapping_capabilities() {
+    let mut registry = ComponentRegistry::new();
+    
+    let mut kv = create_test_component("kv-store");
+    kv.provides = hashset![Capability::Storage];
+    let mut replicated = create_test_component("replicated-store");
+    replicated.provides = hashset![Capability::Storage, Capability::CRDT, Capability::Networking];
+    replicated.requires = hashset![Capability::Cryptography];
+    let mut encrypted = create_test_component("encrypted-store");
+    encrypted.provides = hashset![Capability::Storage, Capability::Encryption];
+    let mut transport = create_test_component("transport");
+    transport.provides = hashset![Capability::Networking];
+    transport.requires = hashset![Capability::Storage];
+    let mut gateway = create_test_component("gateway");
+    gateway.requires = hashset![Capability::Storage, Capability::Networking];
+    
+    for component in [kv, replicated, encrypted, transport, gateway] {
+        registry.register(component).unwrap();
+    }
+    
+    // Exactly the storage providers, most dedicated first
+    let names: Vec<String> = registry.providers_of(&Capability::Storage)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, vec!["kv-store", "encrypted-store", "replicated-store"]);
+    
+    let names: Vec<String> = registry.providers_of(&Capability::Networking)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, vec!["transport", "replicated-store"]);
+    
+    assert!(registry.providers_of(&Capability::Consensus).is_empty());
+    
+    let names: Vec<String> = registry.consumers_of(&Capability::Storage)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, vec!["transport", "gateway"]);
+}
+
+#[test]
+fn test_search_ranks_by_relevance() {
+    let registry = ComponentRegistry::with_defaults();
+    
+    // Exact short name first, then other components mentioning it
+    let results = registry.search("storage");
+    assert_eq!(results[0].name, "synapsed-storage");
+    
+    // Description words
+    let results = registry.search("onion");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "synapsed-routing");
+    
+    // Letters in order still match
+    let results = registry.search("sbstrts");
+    assert_eq!(results[0].name, "synapsed-substrates");
+    
+    assert!(registry.search("blockchain-oracle").is_empty());
+    assert!(registry.search("  ").is_empty());
+}
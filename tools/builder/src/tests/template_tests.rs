@@ -4,7 +4,9 @@
 //! Verification: All templates are valid and can be instantiated
 
 use crate::templates::{Templates, TemplateInfo};
-use crate::builder::{StorageBackend, ObservabilityLevel, NetworkType};
+use crate::builder::{SynapsedBuilder, StorageBackend, ObservabilityLevel, NetworkType};
+use crate::registry::ComponentRegistry;
+use crate::validator::Validator;
 
 #[test]
 fn test_template_list() {
@@ -251,4 +253,47 @@ fn test_template_completeness() {
             assert!(!template.components.is_empty());
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_archetype_recipes_validate_and_compose() {
+    let registry = ComponentRegistry::with_defaults();
+    let validator = Validator::new(&registry);
+    
+    for name in Templates::archetypes() {
+        let recipe = Templates::recipe(name).expect(name);
+        assert_eq!(recipe.name, name);
+        assert!(!recipe.tags.is_empty());
+        
+        let components: Vec<String> = recipe.components.iter().map(|c| c.name.clone()).collect();
+        let report = validator.validate_composition(&components, &recipe.connections).unwrap();
+        assert!(!report.has_errors(), "{}: {:?}", name, report.errors);
+        let report = validator.validate_recipe(&recipe).unwrap();
+        assert!(!report.has_errors(), "{}: {:?}", name, report.errors);
+        
+        let app = SynapsedBuilder::from_recipe(recipe)
+            .build()
+            .unwrap_or_else(|e| panic!("{} failed to compose: {}", name, e));
+        assert!(app.config_files.contains_key("app.json"));
+    }
+}
+
+#[test]
+fn test_archetype_recipe_wiring() {
+    let recipe = Templates::recipe("anonymous-p2p-messaging").unwrap();
+    assert_eq!(recipe.category, "p2p");
+    let names: Vec<&str> = recipe.components.iter().map(|c| c.name.as_str()).collect();
+    assert!(names.contains(&"synapsed-routing"));
+    assert!(names.contains(&"synapsed-net"));
+    assert!(recipe.connections.iter().any(|c| {
+        c.from.component == "synapsed-routing" && c.to.component == "synapsed-net"
+    }));
+    
+    // Components start after what they depend on, then get connected
+    let init_routing = recipe.steps.iter().find(|s| s.name == "init-synapsed-routing").unwrap();
+    assert_eq!(init_routing.depends_on, vec!["init-synapsed-crypto".to_string()]);
+    assert_eq!(recipe.steps.last().unwrap().name, "start");
+    
+    let app = SynapsedBuilder::from_recipe(recipe).build().unwrap();
+    assert!(app.manifest.dependencies["synapsed-routing"].contains("network/synapsed-routing"));
+}